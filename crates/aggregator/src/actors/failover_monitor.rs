@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use crate::domain::failover::{FailoverDecision, FailoverPolicy, FailoverTracker};
+use actix::prelude::*;
+use e3_events::{
+    prelude::*, AggregatorPresumedUnresponsive, BusHandle, Committee, EType, EventType,
+    InterfoldEvent, InterfoldEventData,
+};
+use e3_utils::MAILBOX_LIMIT;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+
+/// Upper bound on how often the watches are evaluated.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Wall-clock driver of the aggregator failover policy.
+///
+/// Watches every E3 with a finalized committee and, when the active aggregator fails to get
+/// the public key or the plaintext on-chain within the failover timeout, publishes
+/// `AggregatorPresumedUnresponsive` on the local bus so the `CiphernodeSelector` promotes the
+/// next standby. Every node runs its own monitor over the same on-chain signals, so honest
+/// nodes converge on the same replacement. Decisions are only taken once effects are enabled,
+/// never while replaying history.
+pub struct FailoverMonitor {
+    bus: BusHandle,
+    policy: FailoverPolicy,
+    tracker: FailoverTracker,
+    origin: Instant,
+    effects_enabled: bool,
+}
+
+impl FailoverMonitor {
+    pub fn new(bus: &BusHandle, timeout: Duration) -> Self {
+        Self {
+            bus: bus.clone(),
+            policy: FailoverPolicy::new(timeout),
+            tracker: FailoverTracker::new(),
+            origin: Instant::now(),
+            effects_enabled: false,
+        }
+    }
+
+    pub fn attach(bus: &BusHandle, timeout: Duration) -> Addr<Self> {
+        let addr = FailoverMonitor::new(bus, timeout).start();
+        bus.subscribe_all(
+            &[
+                EventType::CommitteeFinalized,
+                EventType::CommitteeMemberExpelled,
+                EventType::CommitteePublished,
+                EventType::CiphertextOutputPublished,
+                EventType::PlaintextOutputPublished,
+                EventType::E3RequestComplete,
+                EventType::E3Failed,
+                EventType::EffectsEnabled,
+                EventType::Shutdown,
+            ],
+            addr.clone().recipient(),
+        );
+        info!(timeout = ?timeout, "FailoverMonitor watching active aggregators");
+        addr
+    }
+
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+
+    fn check(&mut self) {
+        if !self.effects_enabled {
+            return;
+        }
+        let now = self.now();
+        for (e3_id, decision) in self.tracker.check(&self.policy, now) {
+            match decision {
+                FailoverDecision::Promote {
+                    demote, new_addr, ..
+                } => {
+                    warn!(
+                        e3_id = %e3_id,
+                        party_id = demote,
+                        new_aggregator = %new_addr,
+                        "Active aggregator missed its deadline, promoting the next standby"
+                    );
+                    if let Err(err) =
+                        self.bus
+                            .publish_without_context(AggregatorPresumedUnresponsive {
+                                e3_id,
+                                party_id: demote,
+                            })
+                    {
+                        self.bus.err(EType::Sortition, err);
+                    }
+                }
+                FailoverDecision::Exhausted { demote } => {
+                    error!(
+                        e3_id = %e3_id,
+                        party_id = demote,
+                        "Every standby aggregator missed its deadline; the E3 can only be failed on-chain"
+                    );
+                }
+                FailoverDecision::Hold => (),
+            }
+        }
+    }
+}
+
+impl Actor for FailoverMonitor {
+    type Context = Context<Self>;
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.set_mailbox_capacity(MAILBOX_LIMIT);
+        let interval = CHECK_INTERVAL.min(self.policy.timeout());
+        ctx.run_interval(interval, |act, _| act.check());
+    }
+}
+
+impl Handler<InterfoldEvent> for FailoverMonitor {
+    type Result = ();
+    fn handle(&mut self, msg: InterfoldEvent, ctx: &mut Self::Context) -> Self::Result {
+        let now = self.now();
+        match msg.into_data() {
+            InterfoldEventData::CommitteeFinalized(mut data) => {
+                // Party ids follow the same score ordering the CiphernodeSelector applies.
+                data.sort_by_score();
+                self.tracker
+                    .committee_finalized(&data.e3_id, Committee::new(data.committee), now);
+            }
+            InterfoldEventData::CommitteeMemberExpelled(data) => {
+                if let Some(party_id) = data.party_id {
+                    self.tracker.member_expelled(&data.e3_id, party_id, now);
+                }
+            }
+            InterfoldEventData::CommitteePublished(data) => {
+                self.tracker.public_key_published(&data.e3_id)
+            }
+            InterfoldEventData::CiphertextOutputPublished(data) => {
+                self.tracker.ciphertext_published(&data.e3_id, now)
+            }
+            InterfoldEventData::PlaintextOutputPublished(data) => {
+                self.tracker.plaintext_published(&data.e3_id)
+            }
+            InterfoldEventData::E3RequestComplete(data) => self.tracker.remove(&data.e3_id),
+            InterfoldEventData::E3Failed(data) => self.tracker.remove(&data.e3_id),
+            InterfoldEventData::EffectsEnabled(_) => self.effects_enabled = true,
+            InterfoldEventData::Shutdown(_) => ctx.stop(),
+            _ => (),
+        }
+    }
+}
@@ -9,12 +9,14 @@
 
 mod committee_finalizer;
 mod decryptionshare_created_buffer;
+mod failover_monitor;
 mod keyshare_created_filter_buffer;
 mod publickey_aggregator;
 mod threshold_plaintext_aggregator;
 
 pub use committee_finalizer::*;
 pub use decryptionshare_created_buffer::*;
+pub use failover_monitor::*;
 pub use keyshare_created_filter_buffer::*;
 pub use publickey_aggregator::*;
 pub use threshold_plaintext_aggregator::*;
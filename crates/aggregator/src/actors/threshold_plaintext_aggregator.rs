@@ -9,7 +9,8 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use crate::domain::threshold_plaintext_aggregation::{
-    build_decryption_aggregation_jobs, format_decrypted_plaintext, ThresholdPlaintextAggregation,
+    build_decryption_aggregation_jobs, build_share_commitment_violation,
+    format_decrypted_plaintext, C6Attempt, ThresholdPlaintextAggregation,
 };
 use actix::prelude::*;
 use actix::SpawnHandle;
@@ -169,9 +170,8 @@ impl ThresholdPlaintextAggregator {
         signed_decryption_proofs: Vec<SignedProofPayload>,
         ec: &EventContext<Sequenced>,
     ) -> Result<()> {
-        let required_shares = self.aggregated_committee_n();
         ensure!(
-            required_shares > 0,
+            self.aggregated_committee_n() > 0,
             "honest committee addresses must not be empty before collecting decryption shares"
         );
         self.state.try_mutate(ec, |state| {
@@ -180,7 +180,6 @@ impl ThresholdPlaintextAggregator {
                 party_id,
                 share.clone(),
                 signed_decryption_proofs.clone(),
-                required_shares,
             )
         })
     }
@@ -196,6 +195,39 @@ impl ThresholdPlaintextAggregator {
         })
     }
 
+    /// True while shares are still accepted: either collecting towards the first subset or
+    /// verifying a subset (later shares are buffered as alternates).
+    fn is_accepting_shares(&self) -> bool {
+        matches!(
+            self.state.get(),
+            Some(ThresholdPlaintextAggregatorState::Collecting(_))
+                | Some(ThresholdPlaintextAggregatorState::VerifyingC6(_))
+        )
+    }
+
+    /// Dispatch C6 verification for the current subset when `mutate` moved the state from
+    /// `Collecting` into `VerifyingC6` (i.e. a new subset attempt was started).
+    fn dispatch_if_attempt_started(
+        &mut self,
+        was_collecting: bool,
+        ec: EventContext<Sequenced>,
+    ) -> Result<()> {
+        if !was_collecting {
+            return Ok(());
+        }
+        if let Some(ThresholdPlaintextAggregatorState::VerifyingC6(ref state)) = self.state.get() {
+            self.dispatch_c6_verification(state.pending_c6_proofs(), ec)?;
+        }
+        Ok(())
+    }
+
+    fn is_collecting(&self) -> bool {
+        matches!(
+            self.state.get(),
+            Some(ThresholdPlaintextAggregatorState::Collecting(_))
+        )
+    }
+
     /// Dispatch C6 proof verification through ShareVerificationActor.
     pub fn dispatch_c6_verification(
         &mut self,
@@ -219,7 +251,12 @@ impl ThresholdPlaintextAggregator {
         Ok(())
     }
 
-    /// Handle ShareVerificationComplete for C6: filter dishonest parties, transition to Computing.
+    /// Handle ShareVerificationComplete for C6: resolve the current subset attempt.
+    ///
+    /// Members that failed ZK verification (already accused by `ShareVerificationActor`) or whose
+    /// share bytes do not match their C6 `d_commitment` (accused here) are excluded. If the whole
+    /// subset passed the shares are combined; otherwise an alternate subset is dispatched, or the
+    /// aggregator waits for more shares, or the round fails when no subset can be formed.
     pub fn handle_c6_verification_complete(
         &mut self,
         msg: TypedEvent<ShareVerificationComplete>,
@@ -240,40 +277,34 @@ impl ThresholdPlaintextAggregator {
             .ok_or(anyhow!("Could not get state"))?
             .try_into()?;
 
-        let mut dishonest_parties = msg.dishonest_parties.clone();
-        if !dishonest_parties.is_empty() {
-            warn!(
-                "C6 verification: {} dishonest parties filtered: {:?}",
-                dishonest_parties.len(),
-                dishonest_parties
-            );
-        }
-
-        // Filter shares to only honest parties
-        let mut honest_shares: Vec<(u64, Vec<ArcBytes>)> = state
-            .shares
-            .iter()
-            .filter(|(id, _)| !dishonest_parties.contains(id))
-            .map(|(id, s)| (*id, s.clone()))
+        let attempted = state.unverified_subset();
+        let mut failed: BTreeSet<u64> = msg
+            .dishonest_parties
+            .intersection(&attempted)
+            .copied()
             .collect();
-
-        if honest_shares.len() <= state.threshold_m as usize {
+        if !failed.is_empty() {
             warn!(
-                "Not enough honest shares after C6 verification: {} honest shares, {} required",
-                honest_shares.len(),
-                state.threshold_m + 1
+                "C6 verification: {} subset members failed: {:?}",
+                failed.len(),
+                failed
             );
-            return self.fail_decryption_round(ec);
         }
 
-        // Verify each honest party's raw decryption share matches the
+        // Verify each newly verified party's raw decryption share matches the
         // d_commitment attested by their verified C6 proof. Catches the attack
         // where a node sends a valid C6 proof for share d_A but broadcasts
         // different bytes d_B.
+        let passed_zk: Vec<(u64, Vec<ArcBytes>)> = state
+            .shares
+            .iter()
+            .filter(|(id, _)| attempted.contains(id) && !failed.contains(id))
+            .map(|(id, s)| (*id, s.clone()))
+            .collect();
         let share_mismatch_parties =
             ThresholdPlaintextAggregation::verify_shares_match_c6_commitments(
                 self.params_preset,
-                &honest_shares,
+                &passed_zk,
                 &state.c6_proofs,
             );
         if !share_mismatch_parties.is_empty() {
@@ -282,18 +313,34 @@ impl ThresholdPlaintextAggregator {
                 share_mismatch_parties.len(),
                 share_mismatch_parties,
             );
+            for party_id in &share_mismatch_parties {
+                if let Some(violation) =
+                    build_share_commitment_violation(&self.e3_id, *party_id, &state.c6_proofs)
+                {
+                    self.bus.publish(violation, ec.clone())?;
+                }
+            }
+            failed.extend(&share_mismatch_parties);
+        }
 
-            dishonest_parties.extend(&share_mismatch_parties);
-            honest_shares.retain(|(id, _)| !share_mismatch_parties.contains(id));
-            if honest_shares.len() <= state.threshold_m as usize {
+        let honest_shares = match ThresholdPlaintextAggregation::resolve_c6_attempt(
+            state.clone(),
+            &failed,
+            self.aggregated_committee_n(),
+        ) {
+            C6Attempt::Accepted(honest_shares) => honest_shares,
+            C6Attempt::Retry(next) => {
+                self.state.try_mutate(&ec, |_| Ok(next))?;
+                return self.dispatch_if_attempt_started(true, ec);
+            }
+            C6Attempt::Exhausted => {
                 warn!(
-                    "Not enough honest shares after d_commitment check: {} honest, {} required",
-                    honest_shares.len(),
+                    "Not enough honest shares left for threshold decryption: {} required",
                     state.threshold_m + 1
                 );
                 return self.fail_decryption_round(ec);
             }
-        }
+        };
 
         info!(
             "C6 verification passed: {} honest parties, transitioning to Computing",
@@ -306,7 +353,7 @@ impl ThresholdPlaintextAggregator {
         let honest_c6: Vec<(u64, Vec<Proof>)> = state
             .c6_proofs
             .iter()
-            .filter(|(id, _)| !dishonest_parties.contains(id))
+            .filter(|(id, _)| state.subset.contains(id))
             .map(|(id, signed)| {
                 (
                     *id,
@@ -806,12 +853,10 @@ impl Handler<TypedEvent<DecryptionshareCreated>> for ThresholdPlaintextAggregato
             EType::PublickeyAggregation,
             &self.bus.with_ec(msg.get_ctx()),
             || {
-                let Some(ThresholdPlaintextAggregatorState::Collecting(Collecting { .. })) =
-                    self.state.get()
-                else {
+                if !self.is_accepting_shares() {
                     debug!(state=?self.state, "Aggregator has been closed for collecting so ignoring this event.");
                     return Ok(());
-                };
+                }
                 let node = msg.node.clone();
                 let e3_id = msg.e3_id.clone();
                 let request = E3CommitteeContainsRequest::new(e3_id, node, msg, ctx.address());
@@ -867,17 +912,12 @@ impl Handler<E3CommitteeContainsResponse<TypedEvent<DecryptionshareCreated>>>
                 // Capture the latest context so a subsequent collection timeout can emit
                 // `E3Failed` with a sensible causal parent.
                 self.timeout_ec = Some(ec.clone());
+                let was_collecting = self.is_collecting();
                 self.add_share(party_id, decryption_share, signed_decryption_proofs, &ec)?;
 
-                // If we transitioned to VerifyingC6, dispatch C6 verification
+                // If this share completed a subset, dispatch C6 verification for it
                 // using the proofs persisted in state
-                if let Some(ThresholdPlaintextAggregatorState::VerifyingC6(ref state)) =
-                    self.state.get()
-                {
-                    self.dispatch_c6_verification(state.c6_proofs.clone(), ec)?;
-                }
-
-                Ok(())
+                self.dispatch_if_attempt_started(was_collecting, ec)
             },
         )
     }
@@ -931,15 +971,9 @@ impl Handler<TypedEvent<CommitteeMemberExpelled>> for ThresholdPlaintextAggregat
                     return Ok(());
                 };
 
+                let was_collecting = self.is_collecting();
                 self.handle_member_expelled(party_id, &ec)?;
-
-                if let Some(ThresholdPlaintextAggregatorState::VerifyingC6(ref state)) =
-                    self.state.get()
-                {
-                    self.dispatch_c6_verification(state.c6_proofs.clone(), ec)?;
-                }
-
-                Ok(())
+                self.dispatch_if_attempt_started(was_collecting, ec)
             },
         )
     }
@@ -1040,8 +1074,12 @@ mod tests {
                 (1, vec![ArcBytes::from_bytes(&[8])]),
             ]),
             c6_proofs: BTreeMap::new(),
+            seed: Seed([0u8; 32]),
             ciphertext_output: vec![ArcBytes::from_bytes(&[9])],
             params: test_params(),
+            subset: BTreeSet::from([0, 1]),
+            excluded: BTreeSet::new(),
+            verified: BTreeSet::new(),
         })
     }
 
@@ -1063,6 +1101,8 @@ mod tests {
             seed: Seed([0u8; 32]),
             ciphertext_output: vec![ArcBytes::from_bytes(&[9])],
             params: test_params(),
+            excluded: BTreeSet::new(),
+            verified: BTreeSet::new(),
         })
     }
 
@@ -1081,6 +1121,7 @@ mod tests {
             finalized_committees: test_persistable(HashMap::<E3id, Committee>::new()),
             ciphernode_selector: selector,
             address: "node-1".to_string(),
+            operator_groups: HashMap::new(),
         })
         .start()
     }
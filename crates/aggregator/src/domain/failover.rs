@@ -23,14 +23,17 @@
 //! the timeout and made harmless by the on-chain publish being single-shot.
 //!
 //! All decisions here are deterministic given their inputs (no clock access),
-//! so the policy is exercised entirely by unit tests; the driving actor owns the
-//! wall clock and feeds `elapsed` in.
+//! so the policy is exercised entirely by unit tests. [`FailoverTracker`] keeps
+//! the per-E3 watches; its driver owns the clock and feeds `now` in. Nodes run
+//! it from the wall clock in [`crate::FailoverMonitor`]; the deterministic
+//! network simulator in `e3-test-helpers` drives it from a virtual clock to test
+//! failover end-to-end.
 
+use e3_events::{Committee, E3id};
 use std::time::Duration;
 
 /// The progress an aggregator round can be waiting on. Used to scope which
 /// absence of progress should arm the failover timer.
-#[allow(dead_code)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AggregatorPhase {
     /// Waiting for the public key to be published on-chain (DKG output).
@@ -43,7 +46,6 @@ pub enum AggregatorPhase {
 
 /// Policy parameters for failover. A single wall-clock budget governs how long
 /// the active aggregator may be silent before the next standby is promoted.
-#[allow(dead_code)]
 #[derive(Debug, Clone, Copy)]
 pub struct FailoverPolicy {
     /// How long the expected on-chain progress may be absent before the active
@@ -51,7 +53,6 @@ pub struct FailoverPolicy {
     timeout: Duration,
 }
 
-#[allow(dead_code)]
 impl FailoverPolicy {
     pub fn new(timeout: Duration) -> Self {
         Self { timeout }
@@ -63,7 +64,6 @@ impl FailoverPolicy {
 }
 
 /// The outcome of a liveness evaluation for one E3.
-#[allow(dead_code)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FailoverDecision {
     /// The round is settled or making progress within budget; do nothing.
@@ -94,7 +94,6 @@ pub enum FailoverDecision {
 ///   `standbys[0].0` when present.
 ///
 /// Deterministic and clock-free.
-#[allow(dead_code)]
 pub fn decide_failover(
     policy: &FailoverPolicy,
     phase: AggregatorPhase,
@@ -119,6 +118,135 @@ pub fn decide_failover(
     }
 }
 
+/// Liveness tracking of the active aggregator for one E3.
+#[derive(Debug, Clone)]
+struct AggregatorWatch {
+    e3_id: E3id,
+    committee: Committee,
+    skipped: Vec<u64>,
+    phase: AggregatorPhase,
+    armed_at: Duration,
+}
+
+/// Tracks the aggregator of every E3 with a finalized committee and decides when to fail
+/// over. Times are offsets from any fixed origin chosen by the driver.
+#[derive(Debug, Default)]
+pub struct FailoverTracker {
+    watches: Vec<AggregatorWatch>,
+}
+
+impl FailoverTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start watching an E3 whose committee (in party order) was finalized at `now`.
+    pub fn committee_finalized(&mut self, e3_id: &E3id, committee: Committee, now: Duration) {
+        if self.watch_mut(e3_id).is_some() {
+            return;
+        }
+        self.watches.push(AggregatorWatch {
+            e3_id: e3_id.clone(),
+            committee,
+            skipped: Vec::new(),
+            phase: AggregatorPhase::AwaitingPublicKey,
+            armed_at: now,
+        });
+    }
+
+    /// The aggregated public key was accepted on-chain.
+    pub fn public_key_published(&mut self, e3_id: &E3id) {
+        if let Some(watch) = self.watch_mut(e3_id) {
+            watch.phase = AggregatorPhase::Settled;
+        }
+    }
+
+    /// The ciphertext output is on-chain; the active aggregator must now publish the plaintext.
+    pub fn ciphertext_published(&mut self, e3_id: &E3id, now: Duration) {
+        if let Some(watch) = self.watch_mut(e3_id) {
+            watch.phase = AggregatorPhase::AwaitingPlaintext;
+            watch.armed_at = now;
+        }
+    }
+
+    /// The plaintext output was accepted on-chain.
+    pub fn plaintext_published(&mut self, e3_id: &E3id) {
+        if let Some(watch) = self.watch_mut(e3_id) {
+            watch.phase = AggregatorPhase::Settled;
+        }
+    }
+
+    /// A member was expelled on-chain. If it was the active aggregator, its successor gets a
+    /// fresh budget from `now`.
+    pub fn member_expelled(&mut self, e3_id: &E3id, party_id: u64, now: Duration) {
+        let Some(watch) = self.watch_mut(e3_id) else {
+            return;
+        };
+        if watch.skipped.contains(&party_id) {
+            return;
+        }
+        if watch.committee.active_aggregator_party_id(&watch.skipped) == Some(party_id) {
+            watch.armed_at = now;
+        }
+        watch.skipped.push(party_id);
+    }
+
+    /// Stop watching a completed or failed E3.
+    pub fn remove(&mut self, e3_id: &E3id) {
+        self.watches.retain(|watch| &watch.e3_id != e3_id);
+    }
+
+    /// Address of the member currently expected to aggregate for the E3.
+    pub fn active_aggregator(&self, e3_id: &E3id) -> Option<String> {
+        let watch = self.watches.iter().find(|w| &w.e3_id == e3_id)?;
+        let party_id = watch.committee.active_aggregator_party_id(&watch.skipped)?;
+        watch.committee.members().get(party_id as usize).cloned()
+    }
+
+    /// Evaluate every watch at `now`. Promotions demote the active aggregator and re-arm the
+    /// watch for its successor; exhausted watches settle. Returns every decision other than
+    /// [`FailoverDecision::Hold`].
+    pub fn check(
+        &mut self,
+        policy: &FailoverPolicy,
+        now: Duration,
+    ) -> Vec<(E3id, FailoverDecision)> {
+        let mut decisions = Vec::new();
+        for watch in self.watches.iter_mut() {
+            let Some(active) = watch.committee.active_aggregator_party_id(&watch.skipped) else {
+                continue;
+            };
+            let standbys = watch
+                .committee
+                .aggregator_standbys(&watch.skipped, watch.committee.members().len());
+            let decision = decide_failover(
+                policy,
+                watch.phase,
+                now.saturating_sub(watch.armed_at),
+                active,
+                &standbys,
+            );
+
+            match &decision {
+                FailoverDecision::Hold => continue,
+                FailoverDecision::Promote { demote, .. } => {
+                    watch.skipped.push(*demote);
+                    watch.armed_at = now;
+                }
+                FailoverDecision::Exhausted { .. } => {
+                    watch.phase = AggregatorPhase::Settled;
+                }
+            }
+            decisions.push((watch.e3_id.clone(), decision));
+        }
+        decisions
+    }
+
+    fn watch_mut(&mut self, e3_id: &E3id) -> Option<&mut AggregatorWatch> {
+        self.watches.iter_mut().find(|w| &w.e3_id == e3_id)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,4 +335,83 @@ mod tests {
         );
         assert_eq!(d, FailoverDecision::Exhausted { demote: 2 });
     }
+
+    fn e3_id() -> E3id {
+        E3id::new("1", 1)
+    }
+
+    fn tracker() -> FailoverTracker {
+        let mut tracker = FailoverTracker::new();
+        tracker.committee_finalized(
+            &e3_id(),
+            Committee::new(vec!["0xa".into(), "0xb".into(), "0xc".into()]),
+            Duration::ZERO,
+        );
+        tracker
+    }
+
+    #[test]
+    fn tracker_promotes_and_rearms_for_the_successor() {
+        let mut tracker = tracker();
+        assert!(tracker.check(&policy(), Duration::from_secs(59)).is_empty());
+
+        let decisions = tracker.check(&policy(), Duration::from_secs(60));
+        assert_eq!(
+            decisions,
+            vec![(
+                e3_id(),
+                FailoverDecision::Promote {
+                    demote: 0,
+                    promote_to: 1,
+                    new_addr: "0xb".into()
+                }
+            )]
+        );
+        assert_eq!(tracker.active_aggregator(&e3_id()).as_deref(), Some("0xb"));
+        // The successor gets a full budget of its own.
+        assert!(tracker
+            .check(&policy(), Duration::from_secs(119))
+            .is_empty());
+    }
+
+    #[test]
+    fn tracker_holds_once_settled_and_rearms_for_decryption() {
+        let mut tracker = tracker();
+        tracker.public_key_published(&e3_id());
+        assert!(tracker
+            .check(&policy(), Duration::from_secs(600))
+            .is_empty());
+
+        tracker.ciphertext_published(&e3_id(), Duration::from_secs(600));
+        assert!(tracker
+            .check(&policy(), Duration::from_secs(659))
+            .is_empty());
+        assert_eq!(tracker.check(&policy(), Duration::from_secs(660)).len(), 1);
+
+        tracker.plaintext_published(&e3_id());
+        assert!(tracker
+            .check(&policy(), Duration::from_secs(6000))
+            .is_empty());
+    }
+
+    #[test]
+    fn tracker_skips_expelled_aggregator_and_settles_when_exhausted() {
+        let mut tracker = tracker();
+        tracker.member_expelled(&e3_id(), 0, Duration::from_secs(30));
+        assert_eq!(tracker.active_aggregator(&e3_id()).as_deref(), Some("0xb"));
+        assert!(tracker.check(&policy(), Duration::from_secs(60)).is_empty());
+
+        tracker.check(&policy(), Duration::from_secs(90));
+        let decisions = tracker.check(&policy(), Duration::from_secs(150));
+        assert_eq!(
+            decisions,
+            vec![(e3_id(), FailoverDecision::Exhausted { demote: 2 })]
+        );
+        assert!(tracker
+            .check(&policy(), Duration::from_secs(1000))
+            .is_empty());
+
+        tracker.remove(&e3_id());
+        assert!(tracker.active_aggregator(&e3_id()).is_none());
+    }
 }
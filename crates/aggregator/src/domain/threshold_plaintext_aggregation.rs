@@ -11,11 +11,20 @@
 //! here touches actix, `Persistable`, or the event bus: the actor feeds inputs in, gets a
 //! next-state or a decision back, and performs the persistence/publish/dispatch side effects
 //! itself.
+//!
+//! Decryption is speculative: as soon as `threshold_m + 1` shares are buffered the aggregator
+//! picks a share subset and verifies only that subset's C6 proofs. If any member fails (bad C6
+//! proof or a share that does not match its C6 `d_commitment`) the member is excluded, the members
+//! that passed are remembered, and a new subset is assembled from the remaining buffered shares.
+//! A single malicious member therefore costs one extra verification round instead of stalling
+//! the round until every honest share has arrived.
 
 use std::collections::{BTreeMap, BTreeSet};
 
+use alloy::primitives::{keccak256, Bytes};
+use alloy::sol_types::SolValue;
 use anyhow::{bail, Result};
-use e3_events::CircuitName;
+use e3_events::{CircuitName, CommitmentConsistencyViolation, E3id, ProofType};
 use e3_events::{
     DecryptionAggregationJobRequest, PartyProofsToVerify, Proof, Seed, SignedProofPayload,
 };
@@ -37,16 +46,57 @@ pub struct Collecting {
     pub(crate) seed: Seed,
     pub(crate) ciphertext_output: Vec<ArcBytes>,
     pub(crate) params: ArcBytes,
+    /// Parties whose share failed verification in an earlier subset attempt (or who were
+    /// expelled). Their shares are never considered again.
+    pub(crate) excluded: BTreeSet<u64>,
+    /// Parties whose share already passed C6 and `d_commitment` verification in an earlier
+    /// subset attempt. They are reused without being re-verified.
+    pub(crate) verified: BTreeSet<u64>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct VerifyingC6 {
     pub(crate) threshold_m: u64,
     pub(crate) threshold_n: u64,
+    /// Every buffered share, including shares that arrived after the current attempt started.
     pub(crate) shares: BTreeMap<u64, Vec<ArcBytes>>,
     pub(crate) c6_proofs: BTreeMap<u64, Vec<SignedProofPayload>>,
+    pub(crate) seed: Seed,
     pub(crate) ciphertext_output: Vec<ArcBytes>,
     pub(crate) params: ArcBytes,
+    /// The `threshold_m + 1` parties whose shares are being combined in the current attempt.
+    pub(crate) subset: BTreeSet<u64>,
+    pub(crate) excluded: BTreeSet<u64>,
+    pub(crate) verified: BTreeSet<u64>,
+}
+
+impl VerifyingC6 {
+    /// Members of the current subset that still need C6 verification.
+    pub(crate) fn unverified_subset(&self) -> BTreeSet<u64> {
+        self.subset.difference(&self.verified).copied().collect()
+    }
+
+    /// C6 proofs for [`Self::unverified_subset`], ready for [`ThresholdPlaintextAggregation::plan_c6_dispatch`].
+    pub(crate) fn pending_c6_proofs(&self) -> BTreeMap<u64, Vec<SignedProofPayload>> {
+        let pending = self.unverified_subset();
+        self.c6_proofs
+            .iter()
+            .filter(|(id, _)| pending.contains(id))
+            .map(|(id, proofs)| (*id, proofs.clone()))
+            .collect()
+    }
+}
+
+/// Result of resolving one speculative subset attempt.
+#[derive(Debug)]
+pub(crate) enum C6Attempt {
+    /// Every subset member verified; combine these shares (ascending party id).
+    Accepted(Vec<(u64, Vec<ArcBytes>)>),
+    /// Some members failed. The next state either carries a fresh subset (`VerifyingC6`) that
+    /// must be dispatched, or waits for more shares (`Collecting`).
+    Retry(ThresholdPlaintextAggregatorState),
+    /// Too few non-excluded honest-committee members remain to ever reach `threshold_m + 1`.
+    Exhausted,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -157,121 +207,202 @@ impl ThresholdPlaintextAggregatorState {
             seed,
             ciphertext_output,
             params,
+            excluded: BTreeSet::new(),
+            verified: BTreeSet::new(),
         })
     }
 }
 
+/// Number of shares needed to combine a threshold decryption (`threshold_m + 1`).
+pub(crate) fn decryption_quorum(threshold_m: u64) -> usize {
+    threshold_m as usize + 1
+}
+
 /// Plain, synchronous domain service for threshold-plaintext aggregation decisions.
 pub(crate) struct ThresholdPlaintextAggregation;
 
 impl ThresholdPlaintextAggregation {
-    /// Add a decryption share to a `Collecting` state, returning the next state. Once all
-    /// `required_shares` honest-committee shares have arrived this transitions to `VerifyingC6`.
-    /// `required_shares` is the canonical honest-committee size `H` (computed by the actor).
+    /// Add a decryption share, returning the next state.
+    ///
+    /// While `Collecting`, the share is buffered and, once `threshold_m + 1` usable shares are
+    /// available, a subset is chosen and the state moves to `VerifyingC6`. While `VerifyingC6`
+    /// the share is only buffered so it can replace a subset member that fails verification.
+    /// Shares from excluded or already-verified parties are ignored.
     pub(crate) fn add_share(
         state: ThresholdPlaintextAggregatorState,
         party_id: u64,
         share: Vec<ArcBytes>,
         signed_decryption_proofs: Vec<SignedProofPayload>,
-        required_shares: u64,
     ) -> Result<ThresholdPlaintextAggregatorState> {
         info!("Adding share for party_id={}", party_id);
-        let current: Collecting = state.try_into()?;
-        let ciphertext_output = current.ciphertext_output;
-        let threshold_m = current.threshold_m;
-        let threshold_n = current.threshold_n;
-        let params = current.params.clone();
-        let mut shares = current.shares;
-        let mut c6_proofs = current.c6_proofs;
-
-        info!("pushing to share collection {} {:?}", party_id, share);
-        shares.insert(party_id, share);
-        c6_proofs.insert(party_id, signed_decryption_proofs);
-
-        if (shares.len() as u64) < required_shares {
-            return Ok(ThresholdPlaintextAggregatorState::Collecting(Collecting {
-                params,
-                threshold_n,
-                threshold_m,
-                ciphertext_output,
-                shares,
-                c6_proofs,
-                seed: current.seed,
-            }));
+        match state {
+            ThresholdPlaintextAggregatorState::Collecting(mut current) => {
+                if current.excluded.contains(&party_id) || current.verified.contains(&party_id) {
+                    warn!("Ignoring decryption share from party {party_id}: already resolved");
+                    return Ok(ThresholdPlaintextAggregatorState::Collecting(current));
+                }
+                current.shares.insert(party_id, share);
+                current.c6_proofs.insert(party_id, signed_decryption_proofs);
+                Ok(Self::try_start_attempt(current))
+            }
+            ThresholdPlaintextAggregatorState::VerifyingC6(mut current) => {
+                if current.excluded.contains(&party_id) || current.subset.contains(&party_id) {
+                    warn!("Ignoring decryption share from party {party_id}: already resolved");
+                    return Ok(ThresholdPlaintextAggregatorState::VerifyingC6(current));
+                }
+                info!("Buffering decryption share for party {party_id} as a subset alternate");
+                current.shares.insert(party_id, share);
+                current.c6_proofs.insert(party_id, signed_decryption_proofs);
+                Ok(ThresholdPlaintextAggregatorState::VerifyingC6(current))
+            }
+            _ => {
+                bail!("PlaintextState was expected to be Collecting or VerifyingC6 but it was not.")
+            }
+        }
+    }
+
+    /// Pick the next subset from a `Collecting` state if enough usable shares are buffered.
+    ///
+    /// Already-verified parties are always kept; the remaining slots are filled with unverified,
+    /// non-excluded parties in ascending party-id order so the choice is deterministic.
+    fn try_start_attempt(current: Collecting) -> ThresholdPlaintextAggregatorState {
+        let quorum = decryption_quorum(current.threshold_m);
+        let unverified = current
+            .shares
+            .keys()
+            .filter(|id| !current.excluded.contains(id) && !current.verified.contains(id))
+            .copied();
+        let subset: BTreeSet<u64> = current
+            .verified
+            .iter()
+            .copied()
+            .chain(unverified)
+            .take(quorum)
+            .collect();
+
+        if subset.len() < quorum {
+            return ThresholdPlaintextAggregatorState::Collecting(current);
         }
 
         info!(
-            "Changing state to VerifyingC6 because received all {required_shares} honest-committee shares..."
+            "Changing state to VerifyingC6: speculatively combining subset {:?} ({} verified, {} excluded)",
+            subset,
+            current.verified.len(),
+            current.excluded.len()
         );
 
-        Ok(ThresholdPlaintextAggregatorState::VerifyingC6(
-            VerifyingC6 {
-                shares,
-                c6_proofs,
-                ciphertext_output,
-                threshold_m,
-                threshold_n,
-                params,
-            },
-        ))
+        ThresholdPlaintextAggregatorState::VerifyingC6(VerifyingC6 {
+            threshold_m: current.threshold_m,
+            threshold_n: current.threshold_n,
+            shares: current.shares,
+            c6_proofs: current.c6_proofs,
+            seed: current.seed,
+            ciphertext_output: current.ciphertext_output,
+            params: current.params,
+            subset,
+            excluded: current.excluded,
+            verified: current.verified,
+        })
     }
 
-    /// Apply a committee-member expulsion to a `Collecting` state, removing the party's share
-    /// and C6 proofs, and transitioning to `VerifyingC6` when enough shares remain.
+    /// Apply a committee-member expulsion, removing the party's share and C6 proofs and
+    /// excluding it from future subsets.
+    ///
+    /// In `Collecting` this may complete a subset and transition to `VerifyingC6`. In
+    /// `VerifyingC6` the in-flight attempt is left alone; [`Self::resolve_c6_attempt`] treats the
+    /// expelled member as failed when the verification result arrives.
     pub(crate) fn handle_member_expelled(
         state: ThresholdPlaintextAggregatorState,
         party_id: u64,
         required_shares: u64,
     ) -> Result<ThresholdPlaintextAggregatorState> {
-        let ThresholdPlaintextAggregatorState::Collecting(current) = state else {
-            return Ok(state);
-        };
-
-        let mut shares = current.shares;
-        let mut c6_proofs = current.c6_proofs;
-        let threshold_n = current.threshold_n;
+        match state {
+            ThresholdPlaintextAggregatorState::Collecting(mut current) => {
+                current.shares.remove(&party_id);
+                current.c6_proofs.remove(&party_id);
+                current.verified.remove(&party_id);
+                current.excluded.insert(party_id);
+
+                if required_shares < current.threshold_m {
+                    warn!(
+                        "ThresholdPlaintextAggregator: honest committee size H ({required_shares}) < threshold_m ({}) after expulsion",
+                        current.threshold_m
+                    );
+                    return Ok(ThresholdPlaintextAggregatorState::Collecting(current));
+                }
+
+                Ok(Self::try_start_attempt(current))
+            }
+            ThresholdPlaintextAggregatorState::VerifyingC6(mut current) => {
+                current.shares.remove(&party_id);
+                current.c6_proofs.remove(&party_id);
+                current.verified.remove(&party_id);
+                current.excluded.insert(party_id);
+                Ok(ThresholdPlaintextAggregatorState::VerifyingC6(current))
+            }
+            state => Ok(state),
+        }
+    }
 
-        shares.remove(&party_id);
-        c6_proofs.remove(&party_id);
+    /// Resolve the current subset attempt given the parties that failed verification.
+    ///
+    /// `failed` holds the attempted parties whose C6 proof failed or whose share did not match
+    /// its C6 `d_commitment`. `required_shares` is the honest-committee size `H`, used to decide
+    /// whether an alternate subset can still be formed.
+    pub(crate) fn resolve_c6_attempt(
+        state: VerifyingC6,
+        failed: &BTreeSet<u64>,
+        required_shares: u64,
+    ) -> C6Attempt {
+        let quorum = decryption_quorum(state.threshold_m);
+        let attempted = state.unverified_subset();
+
+        let mut excluded = state.excluded;
+        excluded.extend(failed.iter().filter(|id| attempted.contains(id)));
+
+        let mut verified = state.verified;
+        verified.extend(attempted.iter().filter(|id| !excluded.contains(id)));
+
+        if state.subset.iter().all(|id| verified.contains(id)) {
+            let honest_shares = state
+                .shares
+                .into_iter()
+                .filter(|(id, _)| state.subset.contains(id))
+                .collect();
+            return C6Attempt::Accepted(honest_shares);
+        }
 
-        if required_shares < current.threshold_m {
+        if (required_shares as usize).saturating_sub(excluded.len()) < quorum {
             warn!(
-                "ThresholdPlaintextAggregator: honest committee size H ({required_shares}) < threshold_m ({}) after expulsion",
-                current.threshold_m
+                "Cannot form another decryption subset: H={required_shares}, excluded={}, need {quorum}",
+                excluded.len()
             );
-            return Ok(ThresholdPlaintextAggregatorState::Collecting(Collecting {
-                threshold_m: current.threshold_m,
-                threshold_n,
-                shares,
-                c6_proofs,
-                seed: current.seed,
-                ciphertext_output: current.ciphertext_output,
-                params: current.params,
-            }));
+            return C6Attempt::Exhausted;
         }
 
-        if (shares.len() as u64) < required_shares {
-            return Ok(ThresholdPlaintextAggregatorState::Collecting(Collecting {
-                threshold_m: current.threshold_m,
-                threshold_n,
-                shares,
-                c6_proofs,
-                seed: current.seed,
-                ciphertext_output: current.ciphertext_output,
-                params: current.params,
-            }));
+        let mut shares = state.shares;
+        let mut c6_proofs = state.c6_proofs;
+        for id in &excluded {
+            shares.remove(id);
+            c6_proofs.remove(id);
         }
 
-        Ok(ThresholdPlaintextAggregatorState::VerifyingC6(
-            VerifyingC6 {
-                threshold_m: current.threshold_m,
-                threshold_n,
-                shares,
-                c6_proofs,
-                ciphertext_output: current.ciphertext_output,
-                params: current.params,
-            },
-        ))
+        warn!(
+            "Decryption subset {:?} rejected (excluded so far: {:?}); retrying with alternates",
+            state.subset, excluded
+        );
+
+        C6Attempt::Retry(Self::try_start_attempt(Collecting {
+            threshold_m: state.threshold_m,
+            threshold_n: state.threshold_n,
+            shares,
+            c6_proofs,
+            seed: state.seed,
+            ciphertext_output: state.ciphertext_output,
+            params: state.params,
+            excluded,
+            verified,
+        }))
     }
 
     /// Build the per-party C6 proof bundles dispatched to ShareVerification.
@@ -392,6 +523,40 @@ impl ThresholdPlaintextAggregation {
     }
 }
 
+/// Build the accusation raised against a party whose broadcast decryption share does not match
+/// the `d_commitment` of its own (valid) C6 proof.
+///
+/// The evidence is the accused party's first C6 proof, encoded the same way as other
+/// commitment-consistency violations so the slashing path can bind it to voter signatures.
+/// Returns `None` when the party has no C6 proof or its signer cannot be recovered.
+pub(crate) fn build_share_commitment_violation(
+    e3_id: &E3id,
+    party_id: u64,
+    c6_proofs: &BTreeMap<u64, Vec<SignedProofPayload>>,
+) -> Option<CommitmentConsistencyViolation> {
+    let signed = c6_proofs.get(&party_id)?.first()?;
+    let accused_address = match signed.recover_address() {
+        Ok(addr) => addr,
+        Err(err) => {
+            warn!("Could not recover C6 signer for party {party_id}: {err}");
+            return None;
+        }
+    };
+    let evidence = (
+        Bytes::copy_from_slice(&signed.payload.proof.data),
+        Bytes::copy_from_slice(&signed.payload.proof.public_signals),
+    )
+        .abi_encode();
+    Some(CommitmentConsistencyViolation {
+        e3_id: e3_id.clone(),
+        accused_party_id: party_id,
+        accused_address,
+        proof_type: ProofType::C6ThresholdShareDecryption,
+        data_hash: keccak256(&evidence).into(),
+        evidence: Bytes::from(evidence),
+    })
+}
+
 /// Pad/truncate each decrypted plaintext limb to the fixed `MAX_MSG_NON_ZERO_COEFFS * 8`
 /// byte width expected by consumers of `PlaintextAggregated`.
 pub(crate) fn format_decrypted_plaintext(plaintext: &[ArcBytes]) -> Vec<ArcBytes> {
@@ -457,11 +622,27 @@ mod tests {
         )
     }
 
+    fn with_shares(
+        mut state: ThresholdPlaintextAggregatorState,
+        parties: impl IntoIterator<Item = u64>,
+    ) -> ThresholdPlaintextAggregatorState {
+        for pid in parties {
+            state =
+                ThresholdPlaintextAggregation::add_share(state, pid, vec![ab(pid as u8)], vec![])
+                    .unwrap();
+        }
+        state
+    }
+
+    fn verifying(state: ThresholdPlaintextAggregatorState) -> VerifyingC6 {
+        state.try_into().expect("expected VerifyingC6")
+    }
+
     #[test]
     fn add_share_below_required_stays_collecting() {
         let state = collecting(1, 3);
         let next =
-            ThresholdPlaintextAggregation::add_share(state, 0, vec![ab(10)], vec![], 3).unwrap();
+            ThresholdPlaintextAggregation::add_share(state, 0, vec![ab(10)], vec![]).unwrap();
         match next {
             ThresholdPlaintextAggregatorState::Collecting(c) => {
                 assert_eq!(c.shares.len(), 1);
@@ -473,17 +654,7 @@ mod tests {
 
     #[test]
     fn add_share_reaching_required_transitions_to_verifying_c6() {
-        let mut state = collecting(1, 3);
-        for pid in 0..3u64 {
-            state = ThresholdPlaintextAggregation::add_share(
-                state,
-                pid,
-                vec![ab(pid as u8)],
-                vec![],
-                3,
-            )
-            .unwrap();
-        }
+        let state = with_shares(collecting(1, 3), 0..3);
         match state {
             ThresholdPlaintextAggregatorState::VerifyingC6(v) => {
                 assert_eq!(v.shares.len(), 3);
@@ -492,39 +663,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn add_share_starts_attempt_at_quorum_not_full_committee() {
+        // threshold_m = 1 -> quorum 2, even though the committee has 5 members.
+        let v = verifying(with_shares(collecting(1, 5), [3, 1]));
+        assert_eq!(v.subset, BTreeSet::from([1, 3]));
+        assert_eq!(v.unverified_subset(), BTreeSet::from([1, 3]));
+    }
+
+    #[test]
+    fn add_share_while_verifying_buffers_alternate() {
+        let v = verifying(with_shares(collecting(1, 5), [0, 1, 4]));
+        assert_eq!(v.subset, BTreeSet::from([0, 1]));
+        assert!(v.shares.contains_key(&4));
+        assert!(!v.pending_c6_proofs().contains_key(&4));
+    }
+
     #[test]
     fn add_share_wrong_state_errors() {
-        let state = ThresholdPlaintextAggregatorState::VerifyingC6(VerifyingC6 {
-            threshold_m: 1,
-            threshold_n: 3,
-            shares: BTreeMap::new(),
-            c6_proofs: BTreeMap::new(),
-            ciphertext_output: vec![ab(1)],
-            params: ab(2),
+        let state = ThresholdPlaintextAggregatorState::Complete(Complete {
+            decrypted: vec![ab(1)],
+            shares: vec![],
         });
-        let res = ThresholdPlaintextAggregation::add_share(state, 0, vec![ab(0)], vec![], 3);
+        let res = ThresholdPlaintextAggregation::add_share(state, 0, vec![ab(0)], vec![]);
         assert!(res.is_err());
     }
 
     #[test]
     fn handle_member_expelled_removes_share_and_stays_collecting() {
-        let mut state = collecting(1, 3);
-        for pid in 0..2u64 {
-            state = ThresholdPlaintextAggregation::add_share(
-                state,
-                pid,
-                vec![ab(pid as u8)],
-                vec![],
-                3,
-            )
-            .unwrap();
-        }
-        // required_shares stays 3; remove party 0 -> 1 share left -> Collecting
+        let state = with_shares(collecting(2, 3), 0..2);
+        // quorum is 3; remove party 0 -> 1 share left -> Collecting
         let next = ThresholdPlaintextAggregation::handle_member_expelled(state, 0, 3).unwrap();
         match next {
             ThresholdPlaintextAggregatorState::Collecting(c) => {
                 assert_eq!(c.shares.len(), 1);
                 assert!(!c.shares.contains_key(&0));
+                assert!(c.excluded.contains(&0));
             }
             _ => panic!("expected Collecting"),
         }
@@ -532,46 +706,25 @@ mod tests {
 
     #[test]
     fn handle_member_expelled_transitions_when_enough_remain() {
-        let mut state = collecting(1, 3);
-        for pid in 0..3u64 {
-            state = ThresholdPlaintextAggregation::add_share(
-                state,
-                pid,
-                vec![ab(pid as u8)],
-                vec![],
-                3,
-            )
-            .unwrap();
-        }
-        // After 3 shares it's already VerifyingC6; rebuild a Collecting with 3 shares to
-        // exercise the expulsion->VerifyingC6 path with required_shares lowered to 2.
-        let state = ThresholdPlaintextAggregatorState::Collecting(Collecting {
-            threshold_m: 1,
-            threshold_n: 3,
-            shares: BTreeMap::from([(0, vec![ab(0)]), (1, vec![ab(1)]), (2, vec![ab(2)])]),
-            c6_proofs: BTreeMap::new(),
-            seed: Seed([0u8; 32]),
-            ciphertext_output: vec![ab(1)],
-            params: ab(2),
-        });
-        let _ = state;
         let state = ThresholdPlaintextAggregatorState::Collecting(Collecting {
-            threshold_m: 1,
-            threshold_n: 3,
+            threshold_m: 2,
+            threshold_n: 4,
             shares: BTreeMap::from([(0, vec![ab(0)]), (1, vec![ab(1)]), (2, vec![ab(2)])]),
             c6_proofs: BTreeMap::new(),
             seed: Seed([0u8; 32]),
             ciphertext_output: vec![ab(1)],
             params: ab(2),
+            excluded: BTreeSet::new(),
+            verified: BTreeSet::new(),
         });
-        // remove party 0 -> 2 shares remain, required_shares=2 -> VerifyingC6
-        let next = ThresholdPlaintextAggregation::handle_member_expelled(state, 0, 2).unwrap();
-        match next {
-            ThresholdPlaintextAggregatorState::VerifyingC6(v) => {
-                assert_eq!(v.shares.len(), 2);
-            }
-            _ => panic!("expected VerifyingC6"),
-        }
+        let state = ThresholdPlaintextAggregation::handle_member_expelled(state, 0, 3).unwrap();
+        assert!(matches!(
+            state,
+            ThresholdPlaintextAggregatorState::Collecting(_)
+        ));
+        // A late share from party 3 completes a subset that skips the expelled party.
+        let v = verifying(with_shares(state, [3]));
+        assert_eq!(v.subset, BTreeSet::from([1, 2, 3]));
     }
 
     #[test]
@@ -587,6 +740,93 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn resolve_accepts_when_subset_passes() {
+        let v = verifying(with_shares(collecting(1, 3), [0, 1, 2]));
+        match ThresholdPlaintextAggregation::resolve_c6_attempt(v, &BTreeSet::new(), 3) {
+            C6Attempt::Accepted(shares) => {
+                let ids: Vec<u64> = shares.iter().map(|(id, _)| *id).collect();
+                assert_eq!(ids, vec![0, 1]);
+            }
+            other => panic!("expected Accepted, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn resolve_retries_with_buffered_alternate() {
+        let v = verifying(with_shares(collecting(1, 3), [0, 1, 2]));
+        let next =
+            match ThresholdPlaintextAggregation::resolve_c6_attempt(v, &BTreeSet::from([0]), 3) {
+                C6Attempt::Retry(next) => verifying(next),
+                other => panic!("expected Retry, got {other:?}"),
+            };
+        assert_eq!(next.subset, BTreeSet::from([1, 2]));
+        assert_eq!(next.excluded, BTreeSet::from([0]));
+        assert_eq!(next.verified, BTreeSet::from([1]));
+        // Only the newcomer needs verification; party 1 already passed.
+        assert_eq!(next.unverified_subset(), BTreeSet::from([2]));
+        assert!(!next.shares.contains_key(&0));
+
+        match ThresholdPlaintextAggregation::resolve_c6_attempt(next, &BTreeSet::new(), 3) {
+            C6Attempt::Accepted(shares) => {
+                let ids: Vec<u64> = shares.iter().map(|(id, _)| *id).collect();
+                assert_eq!(ids, vec![1, 2]);
+            }
+            other => panic!("expected Accepted, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn resolve_waits_for_more_shares_when_no_alternate_buffered() {
+        let v = verifying(with_shares(collecting(1, 4), [0, 1]));
+        let next =
+            match ThresholdPlaintextAggregation::resolve_c6_attempt(v, &BTreeSet::from([1]), 4) {
+                C6Attempt::Retry(next) => next,
+                other => panic!("expected Retry, got {other:?}"),
+            };
+        let ThresholdPlaintextAggregatorState::Collecting(ref c) = next else {
+            panic!("expected Collecting");
+        };
+        assert_eq!(c.verified, BTreeSet::from([0]));
+        // A share from the excluded party is ignored; a new party completes the subset.
+        let next = with_shares(next, [1]);
+        assert!(matches!(
+            next,
+            ThresholdPlaintextAggregatorState::Collecting(_)
+        ));
+        let v = verifying(with_shares(next, [3]));
+        assert_eq!(v.subset, BTreeSet::from([0, 3]));
+        assert_eq!(v.unverified_subset(), BTreeSet::from([3]));
+    }
+
+    #[test]
+    fn resolve_treats_member_expelled_mid_attempt_as_failed() {
+        let state = with_shares(collecting(1, 3), [0, 1, 2]);
+        let state = ThresholdPlaintextAggregation::handle_member_expelled(state, 1, 3).unwrap();
+        let v = verifying(state);
+        match ThresholdPlaintextAggregation::resolve_c6_attempt(v, &BTreeSet::new(), 3) {
+            C6Attempt::Retry(next) => assert_eq!(verifying(next).subset, BTreeSet::from([0, 2])),
+            other => panic!("expected Retry, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn resolve_exhausted_when_too_few_members_remain() {
+        let v = verifying(with_shares(collecting(1, 3), [0, 1]));
+        assert!(matches!(
+            ThresholdPlaintextAggregation::resolve_c6_attempt(v, &BTreeSet::from([0, 1]), 3),
+            C6Attempt::Exhausted
+        ));
+    }
+
+    #[test]
+    fn share_commitment_violation_requires_c6_proof() {
+        let e3_id = E3id::new("1", 1);
+        let c6: BTreeMap<u64, Vec<SignedProofPayload>> = BTreeMap::from([(0, vec![])]);
+        assert!(build_share_commitment_violation(&e3_id, 0, &c6).is_none());
+        assert!(build_share_commitment_violation(&e3_id, 1, &c6).is_none());
+    }
+
     #[test]
     fn plan_c6_dispatch_emits_party_proofs_in_party_order() {
         let mut c6: BTreeMap<u64, Vec<SignedProofPayload>> = BTreeMap::new();
@@ -19,4 +19,5 @@ mod repo;
 
 pub use actors::*;
 pub use domain::committee_hash;
+pub use domain::failover;
 pub use repo::*;
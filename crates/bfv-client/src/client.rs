@@ -5,10 +5,10 @@
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
 use anyhow::{anyhow, Result};
-use e3_fhe_params::{build_bfv_params_arc, DEFAULT_BFV_PRESET};
+use e3_fhe_params::{build_bfv_params_arc, BfvParamSet, BfvPreset, DEFAULT_BFV_PRESET};
 use e3_zk_helpers::circuits::threshold::user_data_encryption::circuit::UserDataEncryptionCircuitData;
 use e3_zk_helpers::circuits::threshold::user_data_encryption::Inputs as UserDataEncryptionInputs;
-use e3_zk_helpers::circuits::Computation;
+use e3_zk_helpers::circuits::{Computation, ProgramCircuit};
 use fhe::bfv::{Ciphertext, Encoding, Plaintext, PublicKey, SecretKey};
 use fhe::Error as FheError;
 use fhe_traits::{DeserializeParametrized, FheEncoder, FheEncrypter, Serialize};
@@ -93,7 +93,86 @@ pub fn bfv_verifiable_encrypt<T>(
 where
     Plaintext: for<'a> FheEncoder<&'a T, Error = FheError>,
 {
-    let params = build_bfv_params_arc(degree, plaintext_modulus, &moduli, None);
+    let inputs = user_data_encryption_inputs(
+        data,
+        public_key,
+        degree,
+        plaintext_modulus,
+        &moduli,
+        DEFAULT_BFV_PRESET,
+    )?;
+
+    let encrypted_data = inputs.ciphertext.clone();
+    let circuit_inputs = inputs.to_json()?.to_string();
+
+    Ok(VerifiableEncryptionResult {
+        encrypted_data,
+        circuit_inputs,
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct ProgramEncryptionResult {
+    pub encryption: VerifiableEncryptionResult,
+    /// Inputs of the program circuit, serialized like `circuit_inputs`.
+    pub program_inputs: String,
+}
+
+/// Verifiably encrypt some data like [`bfv_verifiable_encrypt`] and derive the inputs of a
+/// program circuit that constrains the plaintext (e.g. "the ballot is one-hot")
+///
+/// # Arguments
+/// * `data` - The value to encrypt (Generic type T)
+/// * `public_key` - Serialized BFV public key bytes
+/// * `preset` - BFV preset of the E3, which fixes the BFV parameters and the circuit configs
+/// * `circuit` - The program circuit to derive inputs for
+///
+/// # Errors
+/// Returns error string if encryption fails or the program circuit rejects the plaintext
+pub fn bfv_verifiable_encrypt_for_program<T, C: ProgramCircuit>(
+    data: T,
+    public_key: Vec<u8>,
+    preset: BfvPreset,
+    circuit: &C,
+) -> Result<ProgramEncryptionResult>
+where
+    Plaintext: for<'a> FheEncoder<&'a T, Error = FheError>,
+{
+    let param_set: BfvParamSet = preset.into();
+    let inputs = user_data_encryption_inputs(
+        data,
+        public_key,
+        param_set.degree,
+        param_set.plaintext_modulus,
+        param_set.moduli,
+        preset,
+    )?;
+    let program_inputs = circuit
+        .inputs(preset, &inputs)
+        .map_err(|e| anyhow!("Error computing program circuit inputs: {}", e))?
+        .to_string();
+
+    Ok(ProgramEncryptionResult {
+        encryption: VerifiableEncryptionResult {
+            encrypted_data: inputs.ciphertext.clone(),
+            circuit_inputs: inputs.to_json()?.to_string(),
+        },
+        program_inputs,
+    })
+}
+
+fn user_data_encryption_inputs<T>(
+    data: T,
+    public_key: Vec<u8>,
+    degree: usize,
+    plaintext_modulus: u64,
+    moduli: &[u64],
+    preset: BfvPreset,
+) -> Result<UserDataEncryptionInputs>
+where
+    Plaintext: for<'a> FheEncoder<&'a T, Error = FheError>,
+{
+    let params = build_bfv_params_arc(degree, plaintext_modulus, moduli, None);
 
     let pk = PublicKey::from_bytes(&public_key, &params)
         .map_err(|e| anyhow!("Error deserializing public key: {}", e))?;
@@ -101,21 +180,13 @@ where
     let plaintext = Plaintext::try_encode(&data, Encoding::poly(), &params)
         .map_err(|e: FheError| anyhow!("Error encoding plaintext: {}", e))?;
 
-    let inputs = UserDataEncryptionInputs::compute(
-        DEFAULT_BFV_PRESET,
+    Ok(UserDataEncryptionInputs::compute(
+        preset,
         &UserDataEncryptionCircuitData {
             public_key: pk,
             plaintext,
         },
-    )?;
-
-    let encrypted_data = inputs.ciphertext.clone();
-    let circuit_inputs = inputs.to_json()?.to_string();
-
-    Ok(VerifiableEncryptionResult {
-        encrypted_data,
-        circuit_inputs,
-    })
+    )?)
 }
 
 /// Generates a new public/secret key pair and returns the public key.
@@ -307,4 +378,36 @@ mod tests {
         assert_eq!(decoded[0], num[0]);
         assert_eq!(decoded[1], num[1]);
     }
+
+    #[test]
+    fn test_bfv_verifiable_encrypt_for_program() {
+        use e3_zk_helpers::circuits::program::OneHotBallotCircuit;
+        use fhe::bfv::{PublicKey, SecretKey};
+        use fhe_traits::Serialize;
+
+        let param_set: BfvParamSet = DEFAULT_BFV_PRESET.into();
+        let params = build_bfv_params_from_set_arc(param_set);
+        let mut rng = rand::rng();
+        let sk = SecretKey::random(&params, &mut rng);
+        let pk = PublicKey::new(&sk, &mut rng);
+        let circuit = OneHotBallotCircuit { options: 3 };
+
+        let result = bfv_verifiable_encrypt_for_program(
+            vec![0u64, 1, 0],
+            pk.to_bytes(),
+            DEFAULT_BFV_PRESET,
+            &circuit,
+        )
+        .unwrap();
+        assert!(result.program_inputs.contains("\"k1\""));
+        assert!(!result.encryption.encrypted_data.is_empty());
+
+        let rejected = bfv_verifiable_encrypt_for_program(
+            vec![1u64, 1, 0],
+            pk.to_bytes(),
+            DEFAULT_BFV_PRESET,
+            &circuit,
+        );
+        assert!(rejected.is_err());
+    }
 }
@@ -10,10 +10,11 @@ use fhe::bfv::{Encoding, Plaintext};
 use fhe_traits::FheDecoder;
 use thiserror::Error as ThisError;
 
-pub use client::VerifiableEncryptionResult;
 pub use client::{
-    bfv_encrypt, bfv_verifiable_encrypt, compute_ct_commitment, compute_pk_commitment,
+    bfv_encrypt, bfv_verifiable_encrypt, bfv_verifiable_encrypt_for_program, compute_ct_commitment,
+    compute_pk_commitment,
 };
+pub use client::{ProgramEncryptionResult, VerifiableEncryptionResult};
 
 #[derive(ThisError, Debug)]
 pub enum Error {
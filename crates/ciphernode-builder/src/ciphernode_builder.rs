@@ -10,43 +10,50 @@ use crate::{
 };
 use actix::{Actor, Addr};
 use alloy::primitives::Address;
-use anyhow::Result;
+use alloy::providers::Provider;
+use anyhow::{Context, Result};
 use derivative::Derivative;
 use e3_aggregator::ext::{PublicKeyAggregatorExtension, ThresholdPlaintextAggregatorExtension};
-use e3_aggregator::CommitteeFinalizer;
+use e3_aggregator::{CommitteeFinalizer, FailoverMonitor};
 use e3_config::chain_config::ChainConfig;
+use e3_config::{BbEngineKind, NetDiscoveryConfig, NetLimitsConfig, NetTransportConfig};
 use e3_crypto::Cipher;
 use e3_data::{InMemStore, RepositoriesFactory};
 use e3_events::{
-    AggregateConfig, AggregateId, BusHandle, EventBus, EventBusConfig, EvmEventConfig,
+    AggregateConfig, AggregateId, BusHandle, CircuitName, EventBus, EventBusConfig, EvmEventConfig,
     InterfoldEvent,
 };
 use e3_evm::{
-    fetch_accusation_vote_validity, fetch_dkg_fold_attestation_verifier, BondingRegistrySolReader,
-    CiphernodeRegistrySol, CiphernodeRegistrySolReader, InterfoldSolReader, InterfoldSolWriter,
-    ProviderConfig, SlashingManagerSolReader, SlashingManagerSolWriter,
+    fetch_accusation_vote_validity, fetch_circuit_verifiers, fetch_dkg_fold_attestation_verifier,
+    BondingRegistrySolReader, CiphernodeRegistrySol, CiphernodeRegistrySolReader, CircuitVerifiers,
+    InterfoldSolReader, InterfoldSolWriter, ProviderConfig, SlashingManagerSolReader,
+    SlashingManagerSolWriter,
 };
 use e3_fhe::ext::FheExtension;
 use e3_keyshare::ext::ThresholdKeyshareExtension;
 use e3_multithread::{Multithread, MultithreadReport, TaskPool};
 use e3_net::{
-    create_channel_bridge, setup_libp2p_keypair, setup_net, setup_net_interface,
-    NetRepositoryFactory,
+    create_channel_bridge, events::NetCommand, record_bandwidth, record_peer_identities,
+    setup_libp2p_keypair, setup_net, setup_net_interface, NetInterface, NetRepositoryFactory,
 };
 use e3_request::E3LifecycleCoordinator;
 use e3_request::E3Router;
 use e3_slashing::{AccusationManagerExtension, CommitmentConsistencyCheckerExtension};
 use e3_sortition::{
     CiphernodeSelector, CiphernodeSelectorFactory, EmitPersistedAggregatorState,
-    FinalizedCommitteesRepositoryFactory, NodeStateRepositoryFactory, Sortition, SortitionBackend,
-    SortitionRepositoryFactory,
+    FinalizedCommitteesRepositoryFactory, GetBondedOperators, NodeStateRepositoryFactory,
+    ReputationWeights, Sortition, SortitionBackend, SortitionRepositoryFactory,
 };
 use e3_sync::sync;
 use e3_utils::SharedRng;
-use e3_zk_prover::{setup_zk_actors, ZkBackend};
+use e3_zk_prover::{setup_zk_actors, BbEngine, ZkBackend};
 use libp2p::PeerId;
 use std::time::Duration;
-use std::{collections::HashMap, path::PathBuf, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+};
 use tracing::{error, info, warn};
 
 #[derive(Clone, Debug)]
@@ -64,6 +71,7 @@ enum EventSystemType {
 #[derivative(Debug)]
 pub struct CiphernodeBuilder {
     address: Option<String>,
+    aggregator_failover: Option<Duration>,
     chains: Vec<ChainConfig>,
     #[derivative(Debug = "ignore")]
     cipher: Arc<Cipher>,
@@ -75,6 +83,7 @@ pub struct CiphernodeBuilder {
     multithread_cache: Option<Addr<Multithread>>,
     multithread_concurrent_jobs: Option<usize>,
     multithread_report: Option<Addr<MultithreadReport>>,
+    operator_groups: HashMap<Address, String>,
     pubkey_agg: bool,
     rng: SharedRng,
     sortition_backend: SortitionBackend,
@@ -84,7 +93,12 @@ pub struct CiphernodeBuilder {
     signer: Option<alloy::signers::local::PrivateKeySigner>,
     threshold_plaintext_agg: bool,
     zk_backend: Option<ZkBackend>,
+    bb_engine: BbEngineKind,
     net_config: Option<NetConfig>,
+    net_transport: NetTransportConfig,
+    net_discovery: NetDiscoveryConfig,
+    net_limits: NetLimitsConfig,
+    peer_allowlist: bool,
     global_shared_store: bool,
     global_shared_eventstore: bool,
     collect_history: bool,
@@ -129,6 +143,7 @@ impl CiphernodeBuilder {
     pub fn new(rng: SharedRng, cipher: Arc<Cipher>) -> Self {
         Self {
             address: None,
+            aggregator_failover: None,
             chains: vec![],
             cipher,
             contract_components: ContractComponents::default(),
@@ -139,6 +154,7 @@ impl CiphernodeBuilder {
             multithread_cache: None,
             multithread_concurrent_jobs: None,
             multithread_report: None,
+            operator_groups: HashMap::new(),
             pubkey_agg: false,
             rng,
             sortition_backend: SortitionBackend::score(),
@@ -148,7 +164,12 @@ impl CiphernodeBuilder {
             signer: None,
             threshold_plaintext_agg: false,
             net_config: None,
+            net_transport: NetTransportConfig::default(),
+            net_discovery: NetDiscoveryConfig::default(),
+            net_limits: NetLimitsConfig::default(),
+            peer_allowlist: false,
             zk_backend: None,
+            bb_engine: BbEngineKind::default(),
             global_shared_store: false,
             global_shared_eventstore: false,
             collect_history: false,
@@ -374,12 +395,26 @@ impl CiphernodeBuilder {
         self
     }
 
+    /// Watch the active aggregator of every E3 and promote the next standby when it fails to
+    /// get the public key or the plaintext on-chain within `timeout`.
+    pub fn with_aggregator_failover(mut self, timeout: Duration) -> Self {
+        self.aggregator_failover = Some(timeout);
+        self
+    }
+
     /// Enable ZK proof generation with the given backend.
     pub fn with_zkproof(mut self, backend: ZkBackend) -> Self {
         self.zk_backend = Some(backend);
         self
     }
 
+    /// Barretenberg engine the ZK backend proves with. `Ffi` fails the build unless the node was
+    /// compiled with the `bb-ffi` feature.
+    pub fn with_bb_engine(mut self, kind: BbEngineKind) -> Self {
+        self.bb_engine = kind;
+        self
+    }
+
     /// Pre-populate the signer cache with the given signer.
     /// The signer is used for EVM transactions and EIP-712 signatures.
     pub fn with_signer(mut self, signer: alloy::signers::local::PrivateKeySigner) -> Self {
@@ -393,6 +428,26 @@ impl CiphernodeBuilder {
         self
     }
 
+    /// Use score-based sortition admitting at most `max_per_group` committee members per declared
+    /// operator group
+    pub fn with_sortition_diverse(mut self, max_per_group: usize) -> Self {
+        self.sortition_backend = SortitionBackend::diverse(max_per_group);
+        self
+    }
+
+    /// Use score-based sortition weighted by the slashing and expulsion history of the nodes
+    pub fn with_sortition_reputation(mut self, weights: ReputationWeights) -> Self {
+        self.sortition_backend = SortitionBackend::reputation(weights);
+        self
+    }
+
+    /// Declare the group (eg. region) of operators for diversity constrained sortition. Every node
+    /// must declare the same groups to agree on committees.
+    pub fn with_operator_groups(mut self, groups: HashMap<Address, String>) -> Self {
+        self.operator_groups = groups;
+        self
+    }
+
     /// Setup an Interfold contract reader for every evm chain provided
     pub fn with_contract_interfold_reader(mut self) -> Self {
         self.contract_components.interfold_reader = true;
@@ -441,6 +496,30 @@ impl CiphernodeBuilder {
         self
     }
 
+    /// Configure the TCP fallback listener, relays and NAT traversal used by `with_net`.
+    pub fn with_net_transport(mut self, transport: NetTransportConfig) -> Self {
+        self.net_transport = transport;
+        self
+    }
+
+    /// Configure DHT bootstrap, random walks and operator address records used by `with_net`.
+    pub fn with_net_discovery(mut self, discovery: NetDiscoveryConfig) -> Self {
+        self.net_discovery = discovery;
+        self
+    }
+
+    /// Configure the message size caps and per-peer rate limits used by `with_net`.
+    pub fn with_net_limits(mut self, limits: NetLimitsConfig) -> Self {
+        self.net_limits = limits;
+        self
+    }
+
+    /// Only keep connections to peers that prove they are bonded operators.
+    pub fn with_peer_allowlist(mut self, enabled: bool) -> Self {
+        self.peer_allowlist = enabled;
+        self
+    }
+
     fn create_local_bus() -> Addr<EventBus<InterfoldEvent>> {
         EventBus::<InterfoldEvent>::new(EventBusConfig { deduplicate: true }).start()
     }
@@ -463,6 +542,15 @@ impl CiphernodeBuilder {
     pub async fn build(mut self) -> anyhow::Result<CiphernodeHandle> {
         let local_bus = self.resolve_bus();
 
+        if let Some(backend) = self.zk_backend.take() {
+            let engine = BbEngine::for_kind(self.bb_engine, backend.bb_binary.clone())?;
+            info!(
+                "ZK proofs run with the {} Barretenberg engine",
+                engine.name()
+            );
+            self.zk_backend = Some(backend.with_bb_engine(engine));
+        }
+
         // Optional event collectors for debugging / testing.
         let history = if self.collect_history {
             info!("Setting up history collector");
@@ -500,6 +588,9 @@ impl CiphernodeBuilder {
         // Setup sortition
         let (sortition, ciphernode_selector) =
             self.setup_sortition(&bus, &repositories, &addr).await?;
+        if let Some(timeout) = self.aggregator_failover {
+            FailoverMonitor::attach(&bus, timeout);
+        }
 
         // Setup the durable E3 lifecycle coordinator (additive observer that
         // tracks each E3's stage for restart-resume awareness and shutdown).
@@ -512,6 +603,9 @@ impl CiphernodeBuilder {
         let (dkg_fold_verifier_by_chain, accusation_vote_validity_by_chain) =
             self.fetch_chain_configuration(&mut provider_cache).await?;
 
+        // Refuse to start with circuits whose proofs the on-chain verifiers would reject
+        self.check_circuit_verifiers(&mut provider_cache).await?;
+
         // Setup protocol extensions (keyshare, aggregation, ZK, accusation, commitment)
         let e3_builder = self
             .setup_extensions(
@@ -530,7 +624,23 @@ impl CiphernodeBuilder {
 
         // Setup networking
         let topic = "interfold-gossip";
-        let (peer_id, interface, net_kind) = self.setup_networking(&store, topic).await?;
+        let signer = provider_cache.ensure_signer().await?;
+        let (peer_id, interface, net_kind) = self.setup_networking(&store, topic, &signer).await?;
+        if matches!(net_kind, NetInterfaceKind::Libp2p) {
+            record_peer_identities(repositories.peer_identities(), &interface);
+            record_bandwidth(repositories.bandwidth(), &interface);
+            if self.peer_allowlist {
+                info!("Restricting peers to bonded operators");
+            }
+            if self.peer_allowlist || self.net_discovery.resolve_operators {
+                Self::track_bonded_operators(
+                    sortition.clone(),
+                    interface.tx(),
+                    self.peer_allowlist,
+                    self.net_discovery.resolve_operators,
+                );
+            }
+        }
         setup_net(topic, bus.clone(), eventstore.ts(), interface)?;
 
         // Run the sync routine
@@ -606,6 +716,7 @@ impl CiphernodeBuilder {
             repositories.node_state(),
             repositories.finalized_committees(),
             self.sortition_backend.clone(),
+            self.operator_groups.clone(),
             ciphernode_selector.clone(),
             addr,
         )
@@ -680,6 +791,91 @@ impl CiphernodeBuilder {
         ))
     }
 
+    /// Compare the installed circuits' EVM VK hashes with the Honk verifiers of every enabled
+    /// chain. The verifiers are read from the Interfold's BFV `pkVerifier` and
+    /// `decryptionVerifier` unless `dkg_aggregator_verifier` or `decryption_aggregator_verifier`
+    /// is configured.
+    async fn check_circuit_verifiers(
+        &self,
+        provider_cache: &mut ProviderCache<WriteEnabled>,
+    ) -> Result<()> {
+        let Some(backend) = self.zk_backend.as_ref() else {
+            return Ok(());
+        };
+        let mut installed = false;
+        for chain in self.chains.iter().filter(|c| c.enabled.unwrap_or(true)) {
+            let provider = provider_cache.ensure_read_provider(chain).await?;
+            let interfold = chain.contracts.interfold.address()?;
+            let onchain = fetch_circuit_verifiers(provider.provider(), interfold)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!(
+                        chain = %chain.name,
+                        interfold = %interfold,
+                        "could not read the circuit verifiers from the Interfold: {e:#}"
+                    );
+                    CircuitVerifiers::default()
+                });
+            let verifiers = [
+                (
+                    CircuitName::DkgAggregator,
+                    &chain.contracts.dkg_aggregator_verifier,
+                    onchain.dkg_aggregator,
+                ),
+                (
+                    CircuitName::DecryptionAggregator,
+                    &chain.contracts.decryption_aggregator_verifier,
+                    onchain.decryption_aggregator,
+                ),
+            ];
+
+            for (circuit, configured, onchain) in verifiers {
+                let address = match configured {
+                    Some(contract) => contract.address()?,
+                    None => {
+                        let Some(address) = onchain else {
+                            info!(
+                                chain = %chain.name,
+                                "no {} verifier found on-chain; skipping its VK check",
+                                circuit.as_str()
+                            );
+                            continue;
+                        };
+                        address
+                    }
+                };
+                if !installed {
+                    backend.ensure_installed().await?;
+                    installed = true;
+                }
+                let code = provider.provider().get_code_at(address).await?;
+                if code.is_empty() {
+                    anyhow::bail!(
+                        "no contract deployed at {address} ({} verifier) on chain {}",
+                        circuit.as_str(),
+                        chain.name
+                    );
+                }
+                let matching = backend.check_onchain_vk(circuit, &code).with_context(|| {
+                    format!(
+                        "{} verifier {address} on chain {}; install matching circuits with \
+                         `interfold noir setup` or `interfold noir import`",
+                        circuit.as_str(),
+                        chain.name
+                    )
+                })?;
+                info!(
+                    chain = %chain.name,
+                    verifier = %address,
+                    "{} verification key matches installed circuits: {}",
+                    circuit.as_str(),
+                    matching.join(", ")
+                );
+            }
+        }
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)]
     async fn setup_extensions(
         &mut self,
@@ -701,14 +897,18 @@ impl CiphernodeBuilder {
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("ZK backend is required for threshold keyshare"))?;
             backend.ensure_installed().await?;
-            let _signer = provider_cache.ensure_signer().await?;
+            let signer = provider_cache.ensure_signer().await?;
 
             info!("Setting up ThresholdKeyshareExtension");
-            e3_builder =
-                e3_builder.with(ThresholdKeyshareExtension::create(bus, &self.cipher, addr));
+            e3_builder = e3_builder.with(ThresholdKeyshareExtension::create(
+                bus,
+                &self.cipher,
+                addr,
+                signer.clone(),
+            ));
 
             info!("Setting up ZK actors");
-            setup_zk_actors(bus, backend, _signer, dkg_fold_verifier_by_chain.clone());
+            setup_zk_actors(bus, backend, signer, dkg_fold_verifier_by_chain.clone());
         }
 
         // ── Public key aggregation ──
@@ -774,6 +974,7 @@ impl CiphernodeBuilder {
         &self,
         store: &e3_data::DataStore,
         topic: &str,
+        signer: &alloy::signers::local::PrivateKeySigner,
     ) -> Result<(PeerId, e3_net::NetInterfaceHandle, NetInterfaceKind)> {
         if let Some(ref net_config) = self.net_config {
             let repositories = store.repositories();
@@ -784,6 +985,10 @@ impl CiphernodeBuilder {
                 keypair,
                 net_config.peers.clone(),
                 net_config.quic_port,
+                self.net_transport.clone(),
+                self.net_discovery.clone(),
+                self.net_limits.clone(),
+                Some(signer.clone()),
             )?;
             Ok((peer_id, interface, NetInterfaceKind::Libp2p))
         } else {
@@ -797,6 +1002,52 @@ impl CiphernodeBuilder {
         }
     }
 
+    /// Periodically push the bonded operators known to sortition to the net interface, to resolve
+    /// their addresses and/or restrict peers to them. Nothing is sent while sortition knows no
+    /// bonded operators (e.g. before the first sync) so that a fresh node does not drop every
+    /// peer.
+    fn track_bonded_operators(
+        sortition: Addr<Sortition>,
+        net_tx: tokio::sync::mpsc::Sender<NetCommand>,
+        allowlist: bool,
+        resolve: bool,
+    ) {
+        actix::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(30));
+            loop {
+                interval.tick().await;
+                let bonded = match sortition.send(GetBondedOperators).await {
+                    Ok(bonded) => bonded,
+                    Err(e) => {
+                        warn!("Could not fetch bonded operators: {e}");
+                        continue;
+                    }
+                };
+                let operators: HashSet<Address> = bonded
+                    .iter()
+                    .filter_map(|operator| operator.parse().ok())
+                    .collect();
+                if operators.is_empty() {
+                    continue;
+                }
+                let mut commands = vec![];
+                if resolve {
+                    commands.push(NetCommand::ResolveOperators {
+                        operators: operators.clone(),
+                    });
+                }
+                if allowlist {
+                    commands.push(NetCommand::SetOperatorAllowlist { operators });
+                }
+                for command in commands {
+                    if net_tx.send(command).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
     fn ensure_multithread(&mut self, bus: &BusHandle) -> Addr<Multithread> {
         if let Some(cached) = self.multithread_cache.clone() {
             return cached;
@@ -6,6 +6,7 @@
 
 use crate::ciphernode::{self, ChainArgs, CiphernodeCommands};
 use crate::config::{self, ConfigCommands};
+use crate::decryption::{self, DecryptionCommands};
 use crate::events::{self, EventsCommands};
 use crate::helpers::telemetry::{setup_simple_tracing, setup_tracing};
 use crate::net::{self, NetCommands};
@@ -16,13 +17,14 @@ use crate::password::PasswordCommands;
 use crate::program::{self, ProgramCommands};
 use crate::wallet::WalletCommands;
 use crate::{init, noir, password, purge_all, rev, wallet};
-use crate::{print_env, start};
+use crate::{print_env, prover_worker, start};
 use anyhow::{bail, Result};
 use clap::{command, ArgAction, Parser, Subcommand};
 use e3_config::validation::ValidUrl;
 use e3_config::{load_config, AppConfig};
 use e3_console::{log, Console};
 use e3_entrypoint::helpers::datastore::close_all_connections;
+use e3_fhe_params::PresetRegistry;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -86,6 +88,20 @@ impl Cli {
     #[instrument(skip_all)]
     pub async fn execute(self, out: Console, config_result: Result<AppConfig>) -> Result<()> {
         let log_level = self.log_level();
+
+        // Cluster files generate their own configuration
+        if let Commands::Nodes { command } = &self.command {
+            if command.cluster().is_some() {
+                setup_simple_tracing(log_level);
+                return nodes::execute_cluster(
+                    command.clone(),
+                    self.verbose,
+                    self.otel.clone().map(Into::into),
+                )
+                .await;
+            }
+        }
+
         // Attempt to load the config, but only treat "not found" as
         // the trigger for the init flow.  All other errors bubble up.
         let config = match config_result {
@@ -146,6 +162,11 @@ impl Cli {
         setup_tracing(&config, log_level)?;
         info!("Config loaded from: {:?}", config.config_file());
 
+        if let Some(preset_file) = config.preset_file() {
+            PresetRegistry::load(preset_file)?.install()?;
+            info!("BFV presets loaded from: {:?}", preset_file);
+        }
+
         if config.autopassword() {
             e3_entrypoint::password::set::autopassword(&config).await?;
         }
@@ -185,6 +206,12 @@ impl Cli {
             Commands::Noir { command } => noir::execute(out, command, &config).await?,
             Commands::Net { command } => net::execute(&out, command, &config).await?,
             Commands::Events { command } => events::execute(out, command, &config).await?,
+            Commands::Decryption { command } => decryption::execute(out, command, &config).await?,
+            Commands::ProverWorker {
+                listen,
+                token,
+                max_jobs,
+            } => prover_worker::execute(out, &config, listen, token, max_jobs).await?,
             Commands::Node { command } => node::execute(out, command, &config).await?,
             Commands::Rev => rev::execute(out).await?,
             Commands::Config { command } => config::execute(out, command, &config).await?,
@@ -316,6 +343,27 @@ pub enum Commands {
         command: EventsCommands,
     },
 
+    /// Verify published E3 decryptions
+    Decryption {
+        #[command(subcommand)]
+        command: DecryptionCommands,
+    },
+
+    /// Serve ZK proving and verification to other nodes over HTTP
+    ProverWorker {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:9200")]
+        listen: String,
+
+        /// Bearer token clients must present. Defaults to $INTERFOLD_PROVER_WORKER_TOKEN.
+        #[arg(long)]
+        token: Option<String>,
+
+        /// Maximum number of concurrent bb jobs (defaults to the number of CPUs)
+        #[arg(long)]
+        max_jobs: Option<usize>,
+    },
+
     /// Get config values
     Config {
         #[command(subcommand)]
@@ -341,6 +389,8 @@ pub struct RemoteCli {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum RemoteCommand {
     NetGetPeerId,
+    NetPeers,
+    NetBandwidth,
     CiphernodeStatus {
         chain: ChainArgs,
     },
@@ -370,6 +420,12 @@ impl TryFrom<Commands> for RemoteCommand {
             Commands::Net {
                 command: NetCommands::GetPeerId,
             } => Ok(RemoteCommand::NetGetPeerId),
+            Commands::Net {
+                command: NetCommands::Peers,
+            } => Ok(RemoteCommand::NetPeers),
+            Commands::Net {
+                command: NetCommands::Bandwidth,
+            } => Ok(RemoteCommand::NetBandwidth),
             Commands::Noir {
                 command: NoirCommands::Status,
             } => Ok(RemoteCommand::NoirStatus),
@@ -437,6 +493,12 @@ impl TryFrom<RemoteCommand> for Commands {
             RemoteCommand::NetGetPeerId => Commands::Net {
                 command: NetCommands::GetPeerId,
             },
+            RemoteCommand::NetPeers => Commands::Net {
+                command: NetCommands::Peers,
+            },
+            RemoteCommand::NetBandwidth => Commands::Net {
+                command: NetCommands::Bandwidth,
+            },
             RemoteCommand::EventsQuery { agg, since, limit } => Commands::Events {
                 command: EventsCommands::Query { agg, since, limit },
             },
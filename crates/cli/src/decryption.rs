@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use alloy::primitives::U256;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Args, Subcommand};
+use e3_config::AppConfig;
+use e3_console::{log, Console};
+use e3_events::{CircuitName, E3id, InterfoldEvent};
+use e3_evm::helpers::{decode_zk_proof, ProviderConfig};
+use e3_evm::{fetch_published_decryption, PublishedDecryption};
+use e3_fhe_params::PresetRegistry;
+use e3_zk_helpers::CiphernodesCommitteeSize;
+use e3_zk_prover::{
+    audit_decryption, DecryptionEvidence, ProverDecryptionVerifier, ZkBackend, ZkProver,
+};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum DecryptionCommands {
+    /// Independently verify the commitment chain and proofs behind an E3's plaintext output
+    Verify(VerifyArgs),
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct VerifyArgs {
+    /// E3 id
+    #[arg(long)]
+    e3_id: String,
+
+    /// Exported node events (JSON lines as printed by `interfold events query`) carrying the
+    /// C6/C7 proofs
+    #[arg(long)]
+    events: Option<PathBuf>,
+
+    /// Chain name as defined in the interfold config (defaults to the first entry)
+    #[arg(long)]
+    chain: Option<String>,
+
+    /// Do not query the chain; verify the exported events only
+    #[arg(long, requires = "events")]
+    offline: bool,
+
+    /// Chain id the E3 lives on. Required with `--offline`, otherwise read from the RPC.
+    #[arg(long)]
+    chain_id: Option<u64>,
+
+    /// BFV preset name (e.g. SECURE_THRESHOLD_8192), built-in or from the configured preset
+    /// file. Defaults to the on-chain param set.
+    #[arg(long)]
+    preset: Option<String>,
+
+    /// Committee size (minimum, micro, small). Defaults to the on-chain committee size.
+    #[arg(long)]
+    committee: Option<String>,
+
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+pub async fn execute(out: Console, command: DecryptionCommands, config: &AppConfig) -> Result<()> {
+    match command {
+        DecryptionCommands::Verify(args) => execute_verify(out, config, args).await?,
+    }
+    Ok(())
+}
+
+async fn execute_verify(out: Console, config: &AppConfig, args: VerifyArgs) -> Result<()> {
+    let (chain_id, published) = if args.offline {
+        let chain_id = args
+            .chain_id
+            .ok_or_else(|| anyhow!("--chain-id is required with --offline"))?;
+        (chain_id, None)
+    } else {
+        let (chain_id, published) =
+            fetch_onchain(config, args.chain.as_deref(), &args.e3_id).await?;
+        (args.chain_id.unwrap_or(chain_id), Some(published))
+    };
+    let e3_id = E3id::new(args.e3_id.clone(), chain_id);
+
+    let mut evidence = match &args.events {
+        Some(path) => DecryptionEvidence::from_events(e3_id.clone(), read_events(path)?),
+        None => DecryptionEvidence::new(e3_id.clone()),
+    };
+    if let Some(published) = &published {
+        apply_onchain(&mut evidence, published)?;
+    }
+
+    let registry = PresetRegistry::global();
+    let preset = match (&args.preset, &published) {
+        (Some(name), _) => registry
+            .get(name)
+            .ok_or_else(|| anyhow!("unknown preset {name}"))?,
+        (None, Some(published)) => registry
+            .resolve_on_chain(published.param_set)
+            .ok_or_else(|| anyhow!("unknown on-chain param set {}", published.param_set))?,
+        (None, None) => bail!("--preset is required with --offline"),
+    };
+    let (threshold_params, _) = preset.build_pair()?;
+    let committee = match (&args.committee, &published) {
+        (Some(name), _) => CiphernodesCommitteeSize::from_str(name)?,
+        (None, Some(published)) => published.committee()?,
+        (None, None) => bail!("--committee is required with --offline"),
+    };
+
+    let backend = ZkBackend::new(config.bb_binary(), config.circuits_dir(), config.work_dir());
+    let prover = ZkProver::new(&backend);
+    let verifier = ProverDecryptionVerifier::new(
+        &prover,
+        &e3_id,
+        preset.artifacts_dir_for_committee(committee.as_str()),
+    );
+    let report = audit_decryption(&evidence, &threshold_params, &verifier);
+
+    if args.json {
+        log!(out, "{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        log!(out, "{}", report);
+    }
+
+    if !report.passed() {
+        bail!("Decryption verification did not pass for E3 {}", e3_id);
+    }
+    Ok(())
+}
+
+async fn fetch_onchain(
+    config: &AppConfig,
+    chain: Option<&str>,
+    e3_id: &str,
+) -> Result<(u64, PublishedDecryption)> {
+    let chain = match chain {
+        Some(name) => config
+            .chains()
+            .iter()
+            .find(|c| c.name == name)
+            .ok_or_else(|| anyhow!("Chain '{}' not found in configuration", name))?,
+        None => config
+            .chains()
+            .first()
+            .ok_or_else(|| anyhow!("No chains configured."))?,
+    };
+    let provider = ProviderConfig::new(chain.rpc_url()?, chain.rpc_auth.clone())
+        .create_readonly_provider()
+        .await?;
+    let interfold = chain.contracts.interfold.address()?;
+    let registry = chain.contracts.ciphernode_registry.address()?;
+    let from_block = chain.contracts.interfold.deploy_block().unwrap_or(0);
+    let e3_id = U256::from_str(e3_id).context("Invalid E3 id")?;
+
+    let published =
+        fetch_published_decryption(provider.provider(), interfold, registry, e3_id, from_block)
+            .await?;
+    Ok((provider.chain_id(), published))
+}
+
+/// On-chain values are authoritative and replace anything taken from exported events.
+fn apply_onchain(evidence: &mut DecryptionEvidence, published: &PublishedDecryption) -> Result<()> {
+    evidence.pk_commitment = Some(published.pk_commitment);
+    evidence.ciphertext_output_hash = Some(published.ciphertext_output_hash);
+    if !published.plaintext_output.is_empty() {
+        evidence.plaintext_output = Some(published.plaintext_output.clone());
+    }
+    if let Some(pk) = &published.public_key {
+        evidence.public_key = Some(pk.clone());
+    }
+    if let Some(ct) = &published.ciphertext_output {
+        evidence.ciphertext_output = Some(ct.clone());
+    }
+    if let Some(proof) = &published.decryption_proof {
+        evidence.decryption_aggregator_proof =
+            Some(decode_zk_proof(CircuitName::DecryptionAggregator, proof)?);
+    }
+    Ok(())
+}
+
+fn read_events(path: &Path) -> Result<Vec<e3_events::InterfoldEventData>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read events file {}", path.display()))?;
+    // `events query` ends with a cursor line; anything that is not an event is skipped.
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<InterfoldEvent>(line).ok())
+        .map(|event| event.split().0)
+        .collect())
+}
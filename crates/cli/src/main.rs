@@ -16,11 +16,14 @@ mod ciphernode;
 mod cli;
 mod config;
 mod config_setup;
+mod decryption;
 mod events;
 pub mod helpers;
 mod init;
 mod net;
+mod net_bandwidth;
 mod net_get_peer_id;
+mod net_peers;
 mod node;
 mod nodes;
 mod nodes_daemon;
@@ -38,6 +41,7 @@ mod password_delete;
 mod password_set;
 mod print_env;
 mod program;
+mod prover_worker;
 mod purge_all;
 mod rev;
 mod start;
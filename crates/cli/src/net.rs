@@ -9,17 +9,23 @@ use clap::Subcommand;
 use e3_config::AppConfig;
 use e3_console::Console;
 
-use crate::net_get_peer_id;
+use crate::{net_bandwidth, net_get_peer_id, net_peers};
 
 #[derive(Subcommand, Clone, Debug)]
 pub enum NetCommands {
     /// Get the ciphernode's libp2p PeerId
     GetPeerId,
+    /// List peers whose PeerId is verifiably bound to an operator address
+    Peers,
+    /// Show bytes sent and received and dropped messages per protocol and peer
+    Bandwidth,
 }
 
 pub async fn execute(out: &Console, command: NetCommands, config: &AppConfig) -> Result<()> {
     match command {
         NetCommands::GetPeerId => net_get_peer_id::execute(out, config).await?,
+        NetCommands::Peers => net_peers::execute(out, config).await?,
+        NetCommands::Bandwidth => net_bandwidth::execute(out, config).await?,
     };
 
     Ok(())
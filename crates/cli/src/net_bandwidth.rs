@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use anyhow::Result;
+use chrono::DateTime;
+use e3_config::AppConfig;
+use e3_console::{log, Console};
+
+pub async fn execute(out: &Console, config: &AppConfig) -> Result<()> {
+    let Some(report) = e3_entrypoint::net::bandwidth::execute(config).await? else {
+        log!(out, "No bandwidth report has been recorded yet.");
+        return Ok(());
+    };
+
+    let generated_at = DateTime::from_timestamp(report.generated_at, 0)
+        .map(|ts| ts.to_rfc3339())
+        .unwrap_or_else(|| report.generated_at.to_string());
+    log!(out, "Report from {generated_at}");
+    log!(out, "");
+    log!(
+        out,
+        "{:<18} {:>16} {:>16} {:>10}",
+        "PROTOCOL",
+        "IN (BYTES)",
+        "OUT (BYTES)",
+        "DROPPED"
+    );
+    for (protocol, counters) in &report.protocols {
+        log!(
+            out,
+            "{:<18} {:>16} {:>16} {:>10}",
+            protocol.to_string(),
+            counters.inbound_bytes,
+            counters.outbound_bytes,
+            counters.violations
+        );
+    }
+
+    if report.peers.is_empty() {
+        return Ok(());
+    }
+    log!(out, "");
+    log!(
+        out,
+        "{:<54} {:<18} {:>16} {:>16} {:>10} {}",
+        "PEER ID",
+        "PROTOCOL",
+        "IN (BYTES)",
+        "OUT (BYTES)",
+        "DROPPED",
+        "CONNECTED"
+    );
+    for (peer_id, peer) in &report.peers {
+        for (protocol, counters) in &peer.protocols {
+            log!(
+                out,
+                "{:<54} {:<18} {:>16} {:>16} {:>10} {}",
+                peer_id,
+                protocol.to_string(),
+                counters.inbound_bytes,
+                counters.outbound_bytes,
+                counters.violations,
+                peer.connected
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use anyhow::Result;
+use chrono::DateTime;
+use e3_config::AppConfig;
+use e3_console::{log, Console};
+
+pub async fn execute(out: &Console, config: &AppConfig) -> Result<()> {
+    let records = e3_entrypoint::net::peers::execute(config).await?;
+    if records.is_empty() {
+        log!(out, "No peer identities have been verified yet.");
+        return Ok(());
+    }
+
+    log!(
+        out,
+        "{:<54} {:<44} {}",
+        "PEER ID",
+        "OPERATOR",
+        "VERIFIED AT"
+    );
+    for record in records {
+        let verified_at = DateTime::from_timestamp(record.verified_at, 0)
+            .map(|ts| ts.to_rfc3339())
+            .unwrap_or_else(|| record.verified_at.to_string());
+        log!(
+            out,
+            "{:<54} {:<44} {}",
+            record.peer_id,
+            record.operator.to_string(),
+            verified_at
+        );
+    }
+    Ok(())
+}
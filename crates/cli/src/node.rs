@@ -8,6 +8,7 @@ use anyhow::{bail, Result};
 use clap::Subcommand;
 use e3_config::AppConfig;
 use e3_console::{log, Console};
+use e3_entrypoint::committee_audit::{audit_e3_committee, render};
 use e3_entrypoint::validate::validate_node;
 
 #[derive(Subcommand, Clone, Debug)]
@@ -20,6 +21,20 @@ pub enum NodeCommands {
     /// ends"). Safe to run while the node is stopped; intended as the
     /// pre-upgrade and post-crash health check. Exits non-zero on failure.
     Validate,
+
+    /// Recompute the committee selection of an E3 from the node's event store.
+    ///
+    /// Replays the sortition state at the time the E3 was requested, ranks every node by its
+    /// best ticket score and prints the ranking with ticket counts, active flags and the buffer
+    /// size. Exits non-zero when the published committee does not match the ranking.
+    AuditCommittee {
+        /// The E3 id to audit
+        e3_id: String,
+
+        /// The chain the E3 was requested on. Inferred from the event store when omitted
+        #[arg(long)]
+        chain_id: Option<u64>,
+    },
 }
 
 pub async fn execute(out: Console, command: NodeCommands, config: &AppConfig) -> Result<()> {
@@ -36,6 +51,15 @@ pub async fn execute(out: Console, command: NodeCommands, config: &AppConfig) ->
                 bail!("node validation failed");
             }
         }
+        NodeCommands::AuditCommittee { e3_id, chain_id } => {
+            let _fence =
+                e3_entrypoint::fence::ProcessFence::acquire(&config.db_file(), &config.name())?;
+            let audit = audit_e3_committee(config, &e3_id, chain_id).await?;
+            log!(out, "{}", render(&audit));
+            if !audit.mismatches().is_empty() {
+                bail!("committee audit found mismatches");
+            }
+        }
     }
     Ok(())
 }
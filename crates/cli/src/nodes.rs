@@ -7,6 +7,7 @@
 use anyhow::*;
 use clap::Subcommand;
 use e3_config::AppConfig;
+use std::path::PathBuf;
 
 use crate::{
     nodes_daemon, nodes_down, nodes_ps, nodes_purge, nodes_restart, nodes_start, nodes_status,
@@ -24,6 +25,16 @@ pub enum NodeCommands {
         /// Exclude nodes by name
         #[arg(short, long, value_delimiter = ',')]
         exclude: Vec<String>,
+
+        /// Generate the nodes from a cluster file and run them against a fresh local chain with
+        /// the contracts deployed and the nodes registered as operators
+        #[arg(long)]
+        cluster: Option<PathBuf>,
+
+        /// Replace an existing config and node data that were not generated from the cluster
+        /// file
+        #[arg(long, requires = "cluster")]
+        force: bool,
     },
 
     /// Shutdown all nodes
@@ -34,6 +45,15 @@ pub enum NodeCommands {
         /// Exclude nodes by name
         #[arg(short, long, value_delimiter = ',')]
         exclude: Vec<String>,
+
+        /// Cluster file to bring up
+        #[arg(long)]
+        cluster: Option<PathBuf>,
+
+        /// Replace an existing config and node data that were not generated from the cluster
+        /// file
+        #[arg(long, requires = "cluster")]
+        force: bool,
     },
 
     /// List all process statuses
@@ -72,6 +92,19 @@ pub enum NodeCommands {
     },
 }
 
+impl NodeCommands {
+    /// The cluster file of commands that bring up a cluster. These generate their own
+    /// configuration.
+    pub fn cluster(&self) -> Option<&PathBuf> {
+        match self {
+            NodeCommands::Up { cluster, .. } | NodeCommands::Daemon { cluster, .. } => {
+                cluster.as_ref()
+            }
+            _ => None,
+        }
+    }
+}
+
 pub async fn execute(
     command: NodeCommands,
     config: &AppConfig,
@@ -80,12 +113,12 @@ pub async fn execute(
     otel: Option<String>,
 ) -> Result<()> {
     match command {
-        NodeCommands::Up { detach, exclude } => {
-            nodes_up::execute(config, detach, exclude, verbose, config_string, otel).await?
-        }
+        NodeCommands::Up {
+            detach, exclude, ..
+        } => nodes_up::execute(config, detach, exclude, verbose, config_string, otel).await?,
         NodeCommands::Down => nodes_down::execute().await?,
         NodeCommands::Ps => nodes_ps::execute().await?,
-        NodeCommands::Daemon { exclude } => {
+        NodeCommands::Daemon { exclude, .. } => {
             nodes_daemon::execute(config, exclude, verbose, config_string, otel).await?
         }
         NodeCommands::Start { id } => nodes_start::execute(&id).await?,
@@ -97,3 +130,27 @@ pub async fn execute(
 
     Ok(())
 }
+
+/// Run a command that brings up a cluster from a cluster file
+pub async fn execute_cluster(
+    command: NodeCommands,
+    verbose: u8,
+    otel: Option<String>,
+) -> Result<()> {
+    match command {
+        NodeCommands::Up {
+            detach,
+            exclude,
+            cluster: Some(cluster),
+            force,
+        } => nodes_up::execute_cluster(&cluster, force, detach, exclude, verbose, otel).await?,
+        NodeCommands::Daemon {
+            exclude,
+            cluster: Some(cluster),
+            force,
+        } => nodes_daemon::execute_cluster(&cluster, force, exclude, verbose, otel).await?,
+        _ => bail!("Command does not bring up a cluster"),
+    };
+
+    Ok(())
+}
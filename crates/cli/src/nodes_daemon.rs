@@ -7,6 +7,7 @@
 use anyhow::*;
 use e3_config::AppConfig;
 use e3_entrypoint::nodes::daemon;
+use std::path::Path;
 
 pub async fn execute(
     config: &AppConfig,
@@ -17,3 +18,13 @@ pub async fn execute(
 ) -> Result<()> {
     daemon::execute(config, exclude, verbose, config_string, otel).await
 }
+
+pub async fn execute_cluster(
+    cluster: &Path,
+    force: bool,
+    exclude: Vec<String>,
+    verbose: u8,
+    otel: Option<String>,
+) -> Result<()> {
+    daemon::execute_cluster(cluster, force, exclude, verbose, otel).await
+}
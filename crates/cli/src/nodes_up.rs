@@ -7,6 +7,7 @@
 use anyhow::*;
 use e3_config::AppConfig;
 use e3_entrypoint::nodes::up;
+use std::path::Path;
 
 pub async fn execute(
     config: &AppConfig,
@@ -18,3 +19,14 @@ pub async fn execute(
 ) -> Result<()> {
     up::execute(config, detach, exclude, verbose, config_string, otel).await
 }
+
+pub async fn execute_cluster(
+    cluster: &Path,
+    force: bool,
+    detach: bool,
+    exclude: Vec<String>,
+    verbose: u8,
+    otel: Option<String>,
+) -> Result<()> {
+    up::execute_cluster(cluster, force, detach, exclude, verbose, otel).await
+}
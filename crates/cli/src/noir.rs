@@ -4,11 +4,13 @@
 // without even the implied warranty of MERCHANTABILITY
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
+use alloy::primitives::Address;
 use anyhow::*;
 use clap::Subcommand;
 use e3_config::AppConfig;
 use e3_console::{log, Console};
-use e3_zk_prover::{SetupStatus, ZkBackend};
+use e3_zk_prover::{export_benchmarks, summarize, ProfileStore, SetupStatus, ZkBackend};
+use std::path::{Path, PathBuf};
 
 #[derive(Subcommand, Clone, Debug)]
 pub enum NoirCommands {
@@ -17,6 +19,30 @@ pub enum NoirCommands {
         #[arg(long, short)]
         force: bool,
     },
+    /// List installed circuits per BFV preset and committee size
+    List,
+    /// Write bb and the installed circuits to an offline bundle signed by the node wallet
+    Export {
+        /// Bundle file to write (.tar.gz)
+        #[arg(long, short)]
+        output: PathBuf,
+    },
+    /// Verify an offline bundle and install it
+    Import {
+        /// Bundle file produced by `interfold noir export`
+        bundle: PathBuf,
+        /// Address whose signature on the bundle is accepted (repeatable)
+        #[arg(long = "trusted-signer", required = true)]
+        trusted_signers: Vec<Address>,
+    },
+    /// Show per-circuit proving times and sizes recorded for an E3
+    Profile {
+        /// E3 id (e.g. `31337:5`); lists E3s with profiles when omitted
+        e3_id: Option<String>,
+        /// Also write `circuits/benchmarks`-compatible JSON files to this directory
+        #[arg(long, requires = "e3_id")]
+        export: Option<PathBuf>,
+    },
 }
 
 pub async fn execute(out: Console, command: NoirCommands, config: &AppConfig) -> Result<()> {
@@ -29,6 +55,34 @@ pub async fn execute(out: Console, command: NoirCommands, config: &AppConfig) ->
         NoirCommands::Setup { force } => {
             execute_setup(out, &backend, force).await?;
         }
+        NoirCommands::List => {
+            execute_list(out, &backend).await?;
+        }
+        NoirCommands::Export { output } => {
+            let signer = e3_entrypoint::wallet::signer::execute(config).await?;
+            let manifest = backend
+                .export_bundle(&output, &signer)
+                .await
+                .map_err(|e| anyhow!("Export failed: {}", e))?;
+            log!(
+                out,
+                "Exported bb {} and circuits {} ({} files) to {}",
+                manifest.bb_version,
+                manifest.circuits_version,
+                manifest.files.len(),
+                output.display()
+            );
+            log!(out, "  signed by: {}", signer.address());
+        }
+        NoirCommands::Import {
+            bundle,
+            trusted_signers,
+        } => {
+            execute_import(out, &backend, &bundle, &trusted_signers).await?;
+        }
+        NoirCommands::Profile { e3_id, export } => {
+            execute_profile(out, &backend, e3_id.as_deref(), export.as_deref())?;
+        }
     }
 
     Ok(())
@@ -45,11 +99,176 @@ pub async fn execute_without_config(out: Console, command: NoirCommands) -> Resu
         NoirCommands::Setup { force } => {
             execute_setup(out, &backend, force).await?;
         }
+        NoirCommands::List => {
+            execute_list(out, &backend).await?;
+        }
+        NoirCommands::Export { .. } => {
+            bail!("`interfold noir export` signs with the node wallet and needs a node config");
+        }
+        NoirCommands::Import {
+            bundle,
+            trusted_signers,
+        } => {
+            execute_import(out, &backend, &bundle, &trusted_signers).await?;
+        }
+        NoirCommands::Profile { e3_id, export } => {
+            execute_profile(out, &backend, e3_id.as_deref(), export.as_deref())?;
+        }
     }
 
     Ok(())
 }
 
+async fn execute_list(out: Console, backend: &ZkBackend) -> Result<()> {
+    let version_info = backend.load_version_info().await;
+    log!(
+        out,
+        "Circuits version: {}",
+        version_info
+            .circuits_version
+            .as_deref()
+            .unwrap_or("not installed")
+    );
+    log!(
+        out,
+        "Circuits dir:     {}\n",
+        backend.circuits_dir.display()
+    );
+
+    let installed = backend.installed_circuits();
+    if installed.is_empty() {
+        log!(out, "No per-committee circuits installed.");
+        log!(
+            out,
+            "\nRun `interfold noir setup` or `interfold noir import` to install"
+        );
+        return Ok(());
+    }
+
+    for set in installed {
+        let counts = set
+            .circuits
+            .iter()
+            .map(|(variant, count)| format!("{variant}={count}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        log!(
+            out,
+            "{:<24} {:<8} {:<22} {}",
+            set.preset.name(),
+            set.committee.as_str(),
+            set.artifacts_dir,
+            counts
+        );
+        for (circuit, hash) in &set.evm_vk_hashes {
+            log!(out, "  {} evm vk_hash: {}", circuit.as_str(), hash);
+        }
+    }
+
+    Ok(())
+}
+
+fn execute_profile(
+    out: Console,
+    backend: &ZkBackend,
+    e3_id: Option<&str>,
+    export: Option<&Path>,
+) -> Result<()> {
+    let Some(dir) = &backend.profile_dir else {
+        bail!("Proof profiling is disabled for this backend");
+    };
+    let store = ProfileStore::new(dir.clone());
+
+    let Some(e3_id) = e3_id else {
+        let ids = store.e3_ids()?;
+        if ids.is_empty() {
+            log!(out, "No proof profiles recorded in {}", dir.display());
+        }
+        for id in ids {
+            log!(out, "{}", id);
+        }
+        return Ok(());
+    };
+
+    let records = store.load(e3_id)?;
+    if records.is_empty() {
+        bail!("No proof profiles recorded for E3 {}", e3_id);
+    }
+
+    log!(
+        out,
+        "{:<40} {:>6} {:>6} {:>9} {:>9} {:>10} {:>10} {:>10} {:>9}",
+        "circuit",
+        "proofs",
+        "cached",
+        "avg s",
+        "max s",
+        "witness B",
+        "proof B",
+        "peak RSS",
+        "verify s"
+    );
+    for summary in summarize(&records) {
+        log!(
+            out,
+            "{:<40} {:>6} {:>6} {:>9.3} {:>9.3} {:>10} {:>10} {:>10} {:>9.3}",
+            summary.circuit_path,
+            summary.proofs,
+            summary.cache_hits,
+            summary.prove_seconds_avg(),
+            summary.prove_seconds_max,
+            summary.witness_bytes_avg,
+            summary.proof_bytes_avg,
+            summary
+                .peak_rss_kb_max
+                .map(|kb| format!("{} MiB", kb / 1024))
+                .unwrap_or_else(|| "-".to_string()),
+            summary.verify_seconds_avg()
+        );
+        if summary.failures > 0 {
+            log!(out, "  {} failed", summary.failures);
+        }
+    }
+
+    if let Some(export) = export {
+        let written = export_benchmarks(e3_id, &records, export)?;
+        log!(
+            out,
+            "\nWrote {} benchmark files to {}",
+            written.len(),
+            export.display()
+        );
+        log!(
+            out,
+            "  compare with: circuits/benchmarks/scripts/generate_report.sh --input-dir {} --output report.md",
+            export.display()
+        );
+    }
+
+    Ok(())
+}
+
+async fn execute_import(
+    out: Console,
+    backend: &ZkBackend,
+    bundle: &Path,
+    trusted_signers: &[Address],
+) -> Result<()> {
+    log!(out, "Verifying bundle {}...", bundle.display());
+    let manifest = backend
+        .import_bundle(bundle, trusted_signers)
+        .await
+        .map_err(|e| anyhow!("Import failed: {}", e))?;
+
+    log!(out, "\nBundle installed.");
+    log!(out, "  bb version:         {}", manifest.bb_version);
+    log!(out, "  circuits version:   {}", manifest.circuits_version);
+    log!(out, "  files:              {}", manifest.files.len());
+    log!(out, "  created:            {}", manifest.created);
+
+    Ok(())
+}
+
 async fn execute_status(out: Console, backend: &ZkBackend) -> Result<()> {
     let status = backend.check_status().await;
     let version_info = backend.load_version_info().await;
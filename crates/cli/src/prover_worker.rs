@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use anyhow::{Context, Result};
+use e3_config::AppConfig;
+use e3_console::{log, Console};
+use e3_zk_prover::{ProverWorkerServer, ZkBackend};
+use std::net::SocketAddr;
+
+/// Env var read when `--token` is not passed, so the secret stays out of the process list.
+const TOKEN_ENV: &str = "INTERFOLD_PROVER_WORKER_TOKEN";
+
+pub async fn execute(
+    out: Console,
+    config: &AppConfig,
+    listen: String,
+    token: Option<String>,
+    max_jobs: Option<usize>,
+) -> Result<()> {
+    let addr: SocketAddr = listen
+        .parse()
+        .with_context(|| format!("Invalid listen address '{listen}'"))?;
+    let token = token.or_else(|| std::env::var(TOKEN_ENV).ok());
+
+    let backend = ZkBackend::new(config.bb_binary(), config.circuits_dir(), config.work_dir());
+    let mut builder = ProverWorkerServer::builder(backend)
+        .with_host(addr.ip().to_string())
+        .with_port(addr.port());
+    if let Some(token) = token {
+        builder = builder.with_token(token);
+    }
+    if let Some(jobs) = max_jobs {
+        builder = builder.with_max_jobs(jobs);
+    }
+    let server = builder.build();
+
+    log!(
+        out,
+        "Prover worker listening on http://{}",
+        server.bind_address()
+    );
+    server.run().await
+}
@@ -14,7 +14,7 @@ use anyhow::Result;
 use e3_ciphernode_builder::CiphernodeHandle;
 use e3_config::AppConfig;
 use e3_console::Console;
-use e3_daemon_server::start_daemon_server;
+use e3_daemon_server::{mark_ready, start_daemon_server};
 use e3_events::{prelude::*, Shutdown};
 use e3_utils::{colorize, Color};
 use tokio::signal::unix::{signal, SignalKind};
@@ -62,6 +62,7 @@ pub async fn execute(mut config: AppConfig, peers: Vec<String>) -> Result<()> {
         node.address,
         node.peer_id
     );
+    mark_ready();
 
     shutdown.await;
     graceful_shutdown(Some(node)).await;
@@ -5,6 +5,7 @@
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
 use crate::compute_input::ComputeInput;
+use crate::input_validation::InputValidationReport;
 
 pub trait ComputeProvider {
     type Output: Send + Sync;
@@ -17,4 +18,12 @@ pub struct ComputeResult {
     pub ciphertext_hash: Vec<u8>,
     pub params_hash: Vec<u8>,
     pub merkle_root: Vec<u8>,
+    /// Which inputs passed validation, when the manager validated them.
+    ///
+    /// Skipped by serde because the guest commits this struct as the journal, and verifiers
+    /// rebuild the journal on chain from the other three fields. The report is not lost: the
+    /// host providers copy it back from [`ComputeInput`] after decoding the journal, and
+    /// `merkle_root` already commits to exactly the verified leaves.
+    #[serde(skip)]
+    pub input_validation: Option<InputValidationReport>,
 }
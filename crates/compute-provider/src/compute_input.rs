@@ -5,6 +5,7 @@
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
 use crate::ciphertext_output::ComputeResult;
+use crate::input_validation::InputValidationReport;
 use crate::merkle_tree_builder::MerkleTreeBuilder;
 use sha3::{Digest, Keccak256};
 
@@ -21,6 +22,9 @@ pub struct ComputeInput {
     pub fhe_inputs: FHEInputs,
     pub ciphertext_hash: Vec<u8>,
     pub leaf_hashes: Vec<String>,
+    /// Carried to [`ComputeResult::input_validation`] on the host; the guest does not see it.
+    #[serde(skip)]
+    pub input_validation: Option<InputValidationReport>,
 }
 
 impl ComputeInput {
@@ -44,6 +48,7 @@ impl ComputeInput {
             ciphertext_hash: processed_hash,
             params_hash,
             merkle_root: hex::decode(merkle_root).unwrap(),
+            input_validation: self.input_validation.clone(),
         }
     }
 }
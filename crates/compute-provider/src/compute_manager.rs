@@ -6,6 +6,7 @@
 
 use crate::ciphertext_output::ComputeProvider;
 use crate::compute_input::{ComputeInput, FHEInputs};
+use crate::input_validation::{InputProof, InputValidationReport, InputValidator};
 use crate::merkle_tree_builder::MerkleTreeBuilder;
 use crate::FHEProcessor;
 use rayon::prelude::*;
@@ -21,6 +22,7 @@ where
     processor: FHEProcessor,
     use_parallel: bool,
     batch_size: Option<usize>,
+    validation: Option<(InputValidator, Vec<InputProof>)>,
 }
 
 impl<P> ComputeManager<P>
@@ -40,14 +42,38 @@ where
                 fhe_inputs,
                 ciphertext_hash: Vec::new(),
                 leaf_hashes: Vec::new(),
+                input_validation: None,
             },
             processor: fhe_processor,
             use_parallel,
             batch_size,
+            validation: None,
         }
     }
 
+    /// Verify each ciphertext's `user_data_encryption` proof (`proofs[i]` for ciphertext `i`)
+    /// before it enters the Merkle tree; rejected ciphertexts are dropped.
+    pub fn with_input_validation(
+        mut self,
+        validator: InputValidator,
+        proofs: Vec<InputProof>,
+    ) -> Self {
+        self.validation = Some((validator, proofs));
+        self
+    }
+
+    /// Set once [`Self::start`] has validated the inputs.
+    pub fn input_validation(&self) -> Option<&InputValidationReport> {
+        self.input.input_validation.as_ref()
+    }
+
     pub fn start(&mut self) -> (P::Output, Vec<u8>) {
+        if let Some((validator, proofs)) = self.validation.take() {
+            let (accepted, report) = validator.validate(&self.input.fhe_inputs, &proofs);
+            self.input.fhe_inputs = accepted;
+            self.input.input_validation = Some(report);
+        }
+
         if self.use_parallel {
             self.start_parallel()
         } else {
@@ -105,6 +131,7 @@ where
                     fhe_inputs,
                     ciphertext_hash,
                     leaf_hashes: tree_builder.leaf_hashes.clone(),
+                    input_validation: None,
                 };
 
                 (self.provider.prove(&input), ciphertext, merkle_root)
@@ -132,6 +159,7 @@ where
             fhe_inputs,
             ciphertext_hash,
             leaf_hashes: leaf_hashes.clone(),
+            input_validation: self.input.input_validation.clone(),
         };
 
         (self.provider.prove(&final_input), ciphertext)
@@ -0,0 +1,316 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use crate::compute_input::FHEInputs;
+use e3_bfv_client::client::compute_ct_commitment;
+use e3_fhe_params::decode_bfv_params;
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// Public inputs of `threshold/user_data_encryption`, in ABI order: the two inner key hashes,
+/// then the returned `(pk_commitment, ct_commitment, k1_commitment)`.
+const PUBLIC_FIELDS: usize = 5;
+const CT0_KEY_HASH_FIELD: usize = 0;
+const CT1_KEY_HASH_FIELD: usize = 1;
+const PK_COMMITMENT_FIELD: usize = 2;
+const CT_COMMITMENT_FIELD: usize = 3;
+
+/// The `user_data_encryption` proof a client submits alongside one ciphertext.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct InputProof {
+    pub proof: Vec<u8>,
+    /// Concatenated 32-byte big-endian public fields.
+    pub public_inputs: Vec<u8>,
+}
+
+impl InputProof {
+    fn field(&self, index: usize) -> &[u8] {
+        &self.public_inputs[index * 32..(index + 1) * 32]
+    }
+}
+
+/// Checks a proof against the `user_data_encryption` verification key, e.g. by running `bb`.
+/// Binding the proof to the ciphertext and the E3 key is done by [`InputValidator`].
+pub trait InputProofVerifier: Send + Sync {
+    /// VK hashes of the installed `user_data_encryption_ct0` and `user_data_encryption_ct1`
+    /// circuits. The outer circuit verifies the inner proofs against whatever key hash the
+    /// prover supplies, so these pin the inner proofs to the real circuits.
+    fn inner_key_hashes(&self) -> [[u8; 32]; 2];
+
+    fn verify(&self, proof: &InputProof) -> Result<bool, String>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error, serde::Serialize, serde::Deserialize)]
+pub enum InputRejection {
+    #[error("no proof submitted")]
+    MissingProof,
+    #[error("expected {PUBLIC_FIELDS} public fields, got {0} bytes")]
+    MalformedPublicInputs(usize),
+    #[error("ct0 proof was not made with the user_data_encryption_ct0 circuit")]
+    Ct0KeyHashMismatch,
+    #[error("ct1 proof was not made with the user_data_encryption_ct1 circuit")]
+    Ct1KeyHashMismatch,
+    #[error("proof is for a different public key")]
+    PkCommitmentMismatch,
+    #[error("proof is for a different ciphertext")]
+    CiphertextCommitmentMismatch,
+    #[error("ciphertext does not decode: {0}")]
+    InvalidCiphertext(String),
+    #[error("proof does not verify")]
+    InvalidProof,
+    #[error("verifier failed: {0}")]
+    VerifierError(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VerifiedInput {
+    /// Index the ciphertext was submitted with.
+    pub index: u64,
+    /// Merkle leaf of the ciphertext, as proven.
+    pub ct_commitment: [u8; 32],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RejectedInput {
+    pub index: u64,
+    pub reason: InputRejection,
+}
+
+/// Outcome of validating one batch of inputs. Only [`Self::verified`] inputs reach the Merkle
+/// tree and the FHE computation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct InputValidationReport {
+    pub verified: Vec<VerifiedInput>,
+    pub rejected: Vec<RejectedInput>,
+}
+
+/// Accepts a ciphertext only if its proof verifies, was made for the E3's public key and
+/// commits to this exact ciphertext.
+#[derive(Clone)]
+pub struct InputValidator {
+    verifier: Arc<dyn InputProofVerifier>,
+    pk_commitment: [u8; 32],
+}
+
+impl InputValidator {
+    /// `pk_commitment` is the E3 public key's commitment as computed by
+    /// `e3_bfv_client::client::compute_pk_commitment`.
+    pub fn new(verifier: Arc<dyn InputProofVerifier>, pk_commitment: [u8; 32]) -> Self {
+        Self {
+            verifier,
+            pk_commitment,
+        }
+    }
+
+    /// Validates `inputs.ciphertexts[i]` against `proofs[i]` and returns the accepted inputs
+    /// in their original order.
+    pub fn validate(
+        &self,
+        inputs: &FHEInputs,
+        proofs: &[InputProof],
+    ) -> (FHEInputs, InputValidationReport) {
+        let params = decode_bfv_params(&inputs.params).expect("Failed to decode BFV params");
+        let degree = params.degree();
+        let plaintext_modulus = params.plaintext();
+        let moduli = params.moduli().to_vec();
+
+        let outcomes: Vec<Result<[u8; 32], InputRejection>> = inputs
+            .ciphertexts
+            .par_iter()
+            .enumerate()
+            .map(|(i, (ciphertext, _))| {
+                let proof = proofs.get(i).ok_or(InputRejection::MissingProof)?;
+                let ct_commitment = compute_ct_commitment(
+                    ciphertext.clone(),
+                    degree,
+                    plaintext_modulus,
+                    moduli.clone(),
+                )
+                .map_err(|e| InputRejection::InvalidCiphertext(e.to_string()))?;
+                self.check(proof, &ct_commitment)?;
+                Ok(ct_commitment)
+            })
+            .collect();
+
+        let mut accepted = Vec::new();
+        let mut report = InputValidationReport::default();
+        for ((ciphertext, index), outcome) in inputs.ciphertexts.iter().zip(outcomes) {
+            match outcome {
+                Ok(ct_commitment) => {
+                    accepted.push((ciphertext.clone(), *index));
+                    report.verified.push(VerifiedInput {
+                        index: *index,
+                        ct_commitment,
+                    });
+                }
+                Err(reason) => report.rejected.push(RejectedInput {
+                    index: *index,
+                    reason,
+                }),
+            }
+        }
+
+        let accepted = FHEInputs {
+            ciphertexts: accepted,
+            params: inputs.params.clone(),
+        };
+        (accepted, report)
+    }
+
+    /// Cheap binding checks first so junk never reaches the verifier.
+    fn check(&self, proof: &InputProof, ct_commitment: &[u8; 32]) -> Result<(), InputRejection> {
+        if proof.public_inputs.len() != PUBLIC_FIELDS * 32 {
+            return Err(InputRejection::MalformedPublicInputs(
+                proof.public_inputs.len(),
+            ));
+        }
+        let [ct0_key_hash, ct1_key_hash] = self.verifier.inner_key_hashes();
+        if proof.field(CT0_KEY_HASH_FIELD) != ct0_key_hash {
+            return Err(InputRejection::Ct0KeyHashMismatch);
+        }
+        if proof.field(CT1_KEY_HASH_FIELD) != ct1_key_hash {
+            return Err(InputRejection::Ct1KeyHashMismatch);
+        }
+        if proof.field(PK_COMMITMENT_FIELD) != self.pk_commitment {
+            return Err(InputRejection::PkCommitmentMismatch);
+        }
+        if proof.field(CT_COMMITMENT_FIELD) != ct_commitment {
+            return Err(InputRejection::CiphertextCommitmentMismatch);
+        }
+        match self.verifier.verify(proof) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(InputRejection::InvalidProof),
+            Err(e) => Err(InputRejection::VerifierError(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use e3_bfv_client::client::{bfv_encrypt, compute_pk_commitment, generate_public_key};
+    use e3_fhe_params::{
+        build_bfv_params_from_set_arc, encode_bfv_params, BfvParamSet, DEFAULT_BFV_PRESET,
+    };
+
+    const CT0_KEY_HASH: [u8; 32] = [1u8; 32];
+    const CT1_KEY_HASH: [u8; 32] = [2u8; 32];
+
+    /// Accepts every proof whose first byte is non-zero.
+    struct FlagVerifier;
+
+    impl InputProofVerifier for FlagVerifier {
+        fn inner_key_hashes(&self) -> [[u8; 32]; 2] {
+            [CT0_KEY_HASH, CT1_KEY_HASH]
+        }
+
+        fn verify(&self, proof: &InputProof) -> Result<bool, String> {
+            Ok(proof.proof.first().is_some_and(|b| *b != 0))
+        }
+    }
+
+    fn proof_for(pk_commitment: &[u8; 32], ct_commitment: &[u8; 32], valid: bool) -> InputProof {
+        let mut public_inputs = vec![0u8; PUBLIC_FIELDS * 32];
+        public_inputs[0..32].copy_from_slice(&CT0_KEY_HASH);
+        public_inputs[32..64].copy_from_slice(&CT1_KEY_HASH);
+        public_inputs[64..96].copy_from_slice(pk_commitment);
+        public_inputs[96..128].copy_from_slice(ct_commitment);
+        InputProof {
+            proof: vec![valid as u8],
+            public_inputs,
+        }
+    }
+
+    #[test]
+    fn test_validate_reports_each_rejected_leaf() {
+        let param_set: BfvParamSet = DEFAULT_BFV_PRESET.into();
+        let params = build_bfv_params_from_set_arc(param_set);
+        let (degree, t, moduli) = (
+            param_set.degree,
+            param_set.plaintext_modulus,
+            param_set.moduli.to_vec(),
+        );
+        let public_key = generate_public_key(degree, t, moduli.clone()).unwrap();
+        let pk_commitment =
+            compute_pk_commitment(public_key.clone(), degree, t, moduli.clone()).unwrap();
+
+        let ciphertexts: Vec<(Vec<u8>, u64)> = (0..5)
+            .map(|i| {
+                let ct = bfv_encrypt(vec![i], public_key.clone(), degree, t, &moduli).unwrap();
+                (ct, i)
+            })
+            .collect();
+        let ct_commitments: Vec<[u8; 32]> = ciphertexts
+            .iter()
+            .map(|(ct, _)| compute_ct_commitment(ct.clone(), degree, t, moduli.clone()).unwrap())
+            .collect();
+
+        let proofs = vec![
+            proof_for(&pk_commitment, &ct_commitments[0], true),
+            proof_for(&pk_commitment, &ct_commitments[1], false),
+            proof_for(&[7u8; 32], &ct_commitments[2], true),
+            proof_for(&pk_commitment, &ct_commitments[0], true),
+        ];
+        let inputs = FHEInputs {
+            ciphertexts: ciphertexts.clone(),
+            params: encode_bfv_params(&params),
+        };
+
+        let validator = InputValidator::new(Arc::new(FlagVerifier), pk_commitment);
+        let (accepted, report) = validator.validate(&inputs, &proofs);
+
+        assert_eq!(accepted.ciphertexts, vec![ciphertexts[0].clone()]);
+        assert_eq!(
+            report.verified,
+            vec![VerifiedInput {
+                index: 0,
+                ct_commitment: ct_commitments[0],
+            }]
+        );
+        let reasons: Vec<_> = report
+            .rejected
+            .iter()
+            .map(|r| (r.index, r.reason.clone()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                (1, InputRejection::InvalidProof),
+                (2, InputRejection::PkCommitmentMismatch),
+                (3, InputRejection::CiphertextCommitmentMismatch),
+                (4, InputRejection::MissingProof),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_rejects_proofs_built_on_other_inner_circuits() {
+        let (pk_commitment, ct_commitment) = ([3u8; 32], [4u8; 32]);
+        let validator = InputValidator::new(Arc::new(FlagVerifier), pk_commitment);
+        assert_eq!(
+            validator.check(
+                &proof_for(&pk_commitment, &ct_commitment, true),
+                &ct_commitment
+            ),
+            Ok(())
+        );
+
+        // A proof of any circuit verifies under the outer VK if its key hash is supplied
+        let mut proof = proof_for(&pk_commitment, &ct_commitment, true);
+        proof.public_inputs[0..32].copy_from_slice(&[9u8; 32]);
+        assert_eq!(
+            validator.check(&proof, &ct_commitment),
+            Err(InputRejection::Ct0KeyHashMismatch)
+        );
+
+        let mut proof = proof_for(&pk_commitment, &ct_commitment, true);
+        proof.public_inputs[32..64].copy_from_slice(&[9u8; 32]);
+        assert_eq!(
+            validator.check(&proof, &ct_commitment),
+            Err(InputRejection::Ct1KeyHashMismatch)
+        );
+    }
+}
@@ -7,8 +7,10 @@
 mod ciphertext_output;
 mod compute_input;
 mod compute_manager;
+mod input_validation;
 mod merkle_tree_builder;
 
 pub use ciphertext_output::*;
 pub use compute_input::*;
 pub use compute_manager::*;
+pub use input_validation::*;
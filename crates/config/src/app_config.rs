@@ -21,6 +21,7 @@ use figment::{
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::time::Duration;
 use std::{collections::HashMap, env, path::PathBuf};
 
 /// The structure within the app configuration
@@ -30,7 +31,8 @@ use std::{collections::HashMap, env, path::PathBuf};
 pub struct NodeDefinition {
     /// Ethereum Address for the node
     pub address: Option<Address>,
-    /// A list of libp2p multiaddrs to dial to as peers when joining the network
+    /// A list of libp2p multiaddrs to dial when joining the network. They only seed the DHT;
+    /// further operators are discovered through `discovery`.
     pub peers: Vec<String>,
     /// The port to use for the quic listener
     pub quic_port: u16,
@@ -60,16 +62,255 @@ pub struct NodeDefinition {
     /// Max concurrent CPU-bound jobs (ZK proofs + TrBFV). When unset, defaults to all CPUs minus
     /// `multithread_reserve_threads`. Override with env `E3_NODE__MULTITHREAD_CONCURRENT_JOBS`.
     pub multithread_concurrent_jobs: Option<usize>,
+    /// Optional out-of-process prover workers (`interfold prover-worker`) to offload ZK proving to.
+    pub prover_workers: Option<ProverWorkersConfig>,
+    /// How local proofs run: by spawning `bb` or through Barretenberg linked into the node
+    /// (`ffi`, needs a build with the `bb-ffi` feature).
+    pub bb_engine: BbEngineKind,
+    /// Additional libp2p transports and NAT traversal on top of the QUIC listener.
+    pub transport: NetTransportConfig,
+    /// Only keep connections to peers that prove (with an operator-signed PeerId) that they are
+    /// bonded operators. Configured `peers` and relays are exempt.
+    pub peer_allowlist: bool,
+    /// DHT-based discovery of the other operators' addresses.
+    pub discovery: NetDiscoveryConfig,
+    /// Per-protocol message size caps and per-peer inbound rate limits.
+    pub net_limits: NetLimitsConfig,
+    /// How `interfold nodes` supervises this node's process.
+    pub supervisor: SupervisorConfig,
+    /// Seconds the active aggregator of an E3 may take to get the public key or the plaintext
+    /// on-chain before the node presumes it down and promotes the next standby. Unset disables
+    /// automatic aggregator failover.
+    pub aggregator_failover_secs: Option<u64>,
+    /// Committee selection policy and declared operator groups. Every node of a network must
+    /// use the same settings to agree on committees.
+    pub sortition: SortitionConfig,
 }
 
 fn default_multithread_reserve_threads() -> usize {
     1
 }
 
+/// Remote prover worker pool used instead of (or before) the local `bb` binary.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ProverWorkersConfig {
+    /// Base URLs of the workers, e.g. `http://10.0.0.5:9200`
+    pub urls: Vec<String>,
+    /// Shared bearer token expected by the workers
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Per-request timeout in seconds
+    #[serde(default = "default_prover_worker_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Prove locally when every worker fails or is unreachable
+    #[serde(default = "default_prover_worker_fallback_local")]
+    pub fallback_local: bool,
+}
+
+/// Transport and NAT traversal options for nodes that cannot rely on a public UDP port.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct NetTransportConfig {
+    /// Also listen on TCP (Noise + Yamux) on this port, for UDP-hostile networks
+    pub tcp_port: Option<u16>,
+    /// Circuit relay v2 multiaddrs (ending in `/p2p/<relay-peer-id>`) to reserve a slot on, so
+    /// peers can reach this node through `<relay>/p2p-circuit/p2p/<peer-id>`
+    pub relays: Vec<String>,
+    /// Serve as a circuit relay v2 for other nodes (needs a publicly reachable listener)
+    pub relay_server: bool,
+    /// Publicly reachable multiaddrs to announce, e.g. the public TCP address of a relay server
+    pub external_addrs: Vec<String>,
+    /// Probe our own reachability with AutoNAT
+    pub autonat: bool,
+    /// Upgrade relayed connections to direct ones with DCUtR hole punching
+    pub hole_punching: bool,
+}
+
+impl Default for NetTransportConfig {
+    fn default() -> Self {
+        Self {
+            tcp_port: None,
+            relays: vec![],
+            relay_server: false,
+            external_addrs: vec![],
+            autonat: true,
+            hole_punching: true,
+        }
+    }
+}
+
+/// Peer discovery through the Kademlia DHT and the on-chain operator registry.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct NetDiscoveryConfig {
+    /// Publish this node's reachable multiaddrs in a DHT record signed by the operator wallet
+    pub publish_addrs: bool,
+    /// Look up and dial the published addresses of every bonded operator in the registry
+    pub resolve_operators: bool,
+    /// Seconds between Kademlia bootstraps
+    pub bootstrap_interval_secs: u64,
+    /// Seconds between random walks (lookups of a random PeerId) that refresh the routing table
+    pub random_walk_interval_secs: u64,
+}
+
+impl Default for NetDiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            publish_addrs: false,
+            resolve_operators: true,
+            bootstrap_interval_secs: 300,
+            random_walk_interval_secs: 60,
+        }
+    }
+}
+
+/// Inbound limits for the gossipsub, request-response and Kademlia protocols.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct NetLimitsConfig {
+    pub gossipsub: ProtocolLimitConfig,
+    pub request_response: ProtocolLimitConfig,
+    pub kademlia: ProtocolLimitConfig,
+}
+
+/// Limits applied to what a single peer sends us over one protocol. Peers exceeding them have
+/// their messages dropped and a violation recorded against them.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct ProtocolLimitConfig {
+    /// Largest accepted message (or DHT record value) in bytes. Defaults to the protocol's
+    /// built-in maximum, which also bounds larger values.
+    pub max_message_bytes: Option<u64>,
+    /// Sustained inbound bytes per second accepted from one peer; unlimited when unset. Not
+    /// applied to gossipsub, whose relaying peers are scored by the gossipsub router instead.
+    pub peer_bytes_per_sec: Option<u64>,
+    /// Bytes a peer may send in a burst before the rate applies; defaults to one second's worth
+    pub peer_burst_bytes: Option<u64>,
+}
+
+/// Committee selection settings of the node.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct SortitionConfig {
+    pub policy: SortitionPolicyConfig,
+    /// Group (eg. region) of each operator, used by the `diverse` policy. Operators without a
+    /// group are not constrained.
+    pub operator_groups: HashMap<Address, String>,
+}
+
+/// Policy committees are selected under.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortitionPolicyConfig {
+    /// Rank the nodes by their best ticket score
+    #[default]
+    Score,
+    /// Rank by score but admit at most `max_per_group` members per operator group
+    Diverse { max_per_group: usize },
+    /// Rank by score scaled by the slashing and expulsion history of the nodes
+    Reputation {
+        /// Penalty per slash in basis points
+        #[serde(default = "default_slash_penalty_bps")]
+        slash_penalty_bps: u64,
+        /// Penalty per committee expulsion in basis points
+        #[serde(default = "default_expulsion_penalty_bps")]
+        expulsion_penalty_bps: u64,
+    },
+}
+
+fn default_slash_penalty_bps() -> u64 {
+    5000
+}
+
+fn default_expulsion_penalty_bps() -> u64 {
+    2500
+}
+
+/// Barretenberg engine used for local proving and verification.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BbEngineKind {
+    /// Spawn the `bb` binary for every proof
+    #[default]
+    Cli,
+    /// Call the linked Barretenberg library, keeping circuits and keys resident
+    Ffi,
+}
+
+/// When `interfold nodes` restarts a node process that exited.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    /// Leave the node stopped
+    Never,
+    /// Restart the node when it exits with an error or fails its liveness check
+    #[default]
+    OnFailure,
+    /// Restart the node whenever it exits
+    Always,
+}
+
+/// Restart, health check and log settings of a node supervised by `interfold nodes`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct SupervisorConfig {
+    pub restart: RestartPolicy,
+    /// Give up after this many consecutive restarts; unlimited when unset
+    pub max_restarts: Option<u32>,
+    /// Delay before the first restart. It doubles with every consecutive restart.
+    pub backoff_initial_secs: u64,
+    /// Upper bound of the restart delay
+    pub backoff_max_secs: u64,
+    /// Running this long before exiting resets the restart delay and the `max_restarts` count
+    pub backoff_reset_secs: u64,
+    /// Seconds between probes of the node's ctrl socket
+    pub health_check_interval_secs: u64,
+    /// Seconds a node may take to answer its ctrl socket before it counts as not alive
+    pub startup_timeout_secs: u64,
+    /// Consecutive failed liveness probes after which the node is killed
+    pub liveness_failures: u32,
+    /// Size at which the node's output log is rotated
+    pub log_max_bytes: u64,
+    /// Rotated output logs to keep besides the current one
+    pub log_max_files: u32,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            restart: RestartPolicy::default(),
+            max_restarts: None,
+            backoff_initial_secs: 1,
+            backoff_max_secs: 60,
+            backoff_reset_secs: 300,
+            health_check_interval_secs: 5,
+            startup_timeout_secs: 120,
+            liveness_failures: 3,
+            log_max_bytes: 10 * 1024 * 1024,
+            log_max_files: 5,
+        }
+    }
+}
+
+fn default_prover_worker_timeout_secs() -> u64 {
+    600
+}
+
+fn default_prover_worker_fallback_local() -> bool {
+    true
+}
+
 impl Default for NodeDefinition {
     fn default() -> Self {
         Self {
-            peers: vec![], // NOTE: We should look at generation via ipns fetch for the latest nodes
+            peers: vec![],
             address: None,
             quic_port: 9091,
             ctrl_port: 50505,
@@ -84,6 +325,15 @@ impl Default for NodeDefinition {
             dashboard_port: None,
             multithread_reserve_threads: default_multithread_reserve_threads(),
             multithread_concurrent_jobs: None,
+            prover_workers: None,
+            bb_engine: BbEngineKind::default(),
+            transport: NetTransportConfig::default(),
+            peer_allowlist: false,
+            discovery: NetDiscoveryConfig::default(),
+            net_limits: NetLimitsConfig::default(),
+            supervisor: SupervisorConfig::default(),
+            aggregator_failover_secs: None,
+            sortition: SortitionConfig::default(),
         }
     }
 }
@@ -115,6 +365,8 @@ pub struct AppConfig {
     program: ProgramConfig,
     /// A custom bb implementation has been provided do not download and checksum a binary
     using_custom_bb: bool,
+    /// Preset file registering BFV presets beyond the built-in ones
+    preset_file: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -191,6 +443,10 @@ impl AppConfig {
             config.custom_bb.as_ref(),
         );
         let found_config_file = config.found_config_file.clone();
+        let preset_file = config
+            .preset_file
+            .as_ref()
+            .map(|path| paths.relative_to_config(path));
         Ok(AppConfig {
             name: name.to_owned(),
             nodes: config.nodes,
@@ -204,6 +460,7 @@ impl AppConfig {
             autonetkey: node.autonetkey,
             program: config.program.unwrap_or_default(),
             using_custom_bb: config.custom_bb.is_some(),
+            preset_file,
         })
     }
 
@@ -227,6 +484,11 @@ impl AppConfig {
         self.paths.log_file()
     }
 
+    /// Preset file registering additional BFV presets, if configured
+    pub fn preset_file(&self) -> Option<&PathBuf> {
+        self.preset_file.as_ref()
+    }
+
     /// Get the bb binary path
     pub fn bb_binary(&self) -> BBPath {
         let bb = self.paths.bb_binary();
@@ -352,6 +614,53 @@ impl AppConfig {
     pub fn multithread_concurrent_jobs(&self) -> Option<usize> {
         self.node_def().multithread_concurrent_jobs
     }
+
+    /// Remote prover workers configured for this node, if any.
+    pub fn prover_workers(&self) -> Option<&ProverWorkersConfig> {
+        self.node_def().prover_workers.as_ref()
+    }
+
+    /// Barretenberg engine for local proofs.
+    pub fn bb_engine(&self) -> BbEngineKind {
+        self.node_def().bb_engine
+    }
+
+    /// Extra libp2p transports and NAT traversal options.
+    pub fn transport(&self) -> &NetTransportConfig {
+        &self.node_def().transport
+    }
+
+    /// Whether peers must be bonded operators to stay connected.
+    pub fn peer_allowlist(&self) -> bool {
+        self.node_def().peer_allowlist
+    }
+
+    /// DHT peer discovery options.
+    pub fn discovery(&self) -> &NetDiscoveryConfig {
+        &self.node_def().discovery
+    }
+
+    /// Deadline after which the active aggregator is presumed down, if failover is enabled.
+    pub fn aggregator_failover_timeout(&self) -> Option<Duration> {
+        self.node_def()
+            .aggregator_failover_secs
+            .map(Duration::from_secs)
+    }
+
+    /// Committee selection policy and operator groups.
+    pub fn sortition(&self) -> &SortitionConfig {
+        &self.node_def().sortition
+    }
+
+    /// Message size caps and per-peer rate limits of the network protocols.
+    pub fn net_limits(&self) -> &NetLimitsConfig {
+        &self.node_def().net_limits
+    }
+
+    /// File the output of node `node_name` is written to when it runs under `interfold nodes`.
+    pub fn node_output_file(&self, node_name: &str) -> PathBuf {
+        self.paths.node_output_file(node_name)
+    }
 }
 
 #[derive(Debug, Default, Deserialize, Serialize)]
@@ -379,6 +688,9 @@ pub struct UnscopedAppConfig {
     /// is up to the node operator to ensure bb matches the version that exactly matches the
     /// application.
     custom_bb: Option<PathBuf>,
+    /// Preset file (see `e3_fhe_params::registry`) whose presets are recognized in addition to
+    /// the built-in ones. Relative paths are resolved against the config file.
+    preset_file: Option<PathBuf>,
 }
 
 impl UnscopedAppConfig {
@@ -509,12 +821,42 @@ program:
   risc0:
     risc0_dev_mode: 0
 
+preset_file: "/etc/interfold/presets.toml"
+
 nodes:
   ag:
     quic_port: 1235
     peers:
       - "one"
       - "two"
+    prover_workers:
+      urls:
+        - "http://10.0.0.5:9200"
+      token: "secret"
+    bb_engine: ffi
+    transport:
+      tcp_port: 9092
+      relays:
+        - "/ip4/10.0.0.1/tcp/9092/p2p/12D3KooWRelay"
+      hole_punching: false
+    peer_allowlist: true
+    discovery:
+      publish_addrs: true
+      random_walk_interval_secs: 120
+    net_limits:
+      gossipsub:
+        peer_bytes_per_sec: 1048576
+      kademlia:
+        max_message_bytes: 4194304
+    supervisor:
+      restart: always
+      max_restarts: 10
+    sortition:
+      policy:
+        diverse:
+          max_per_group: 2
+      operator_groups:
+        "0x2546BcD3c84621e976D8185a91A922aE77ECEc30": eu
 
 "#;
         {
@@ -537,6 +879,10 @@ nodes:
                 PathBuf::from("/myconfig/override/_default/key")
             );
             assert_eq!(config.quic_port(), 1234);
+            assert_eq!(
+                config.preset_file(),
+                Some(&PathBuf::from("/etc/interfold/presets.toml"))
+            );
             assert_eq!(
                 config.program().risc0(),
                 Some(&Risc0Config {
@@ -545,6 +891,17 @@ nodes:
                 })
             );
             assert!(config.peers().is_empty());
+            assert!(config.prover_workers().is_none());
+            assert_eq!(config.bb_engine(), BbEngineKind::Cli);
+            assert_eq!(config.transport(), &NetTransportConfig::default());
+            assert!(!config.peer_allowlist());
+            assert_eq!(config.discovery(), &NetDiscoveryConfig::default());
+            assert_eq!(config.net_limits(), &NetLimitsConfig::default());
+            assert_eq!(config.sortition(), &SortitionConfig::default());
+            assert_eq!(
+                config.node_output_file("ag"),
+                PathBuf::from("/mydata/interfold/ag/output.log")
+            );
         };
         {
             // investigate ag serialization
@@ -564,6 +921,62 @@ nodes:
                 "0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9"
             );
             assert_eq!(config.peers(), vec!["one", "two"]);
+            assert_eq!(
+                config.prover_workers(),
+                Some(&ProverWorkersConfig {
+                    urls: vec!["http://10.0.0.5:9200".to_string()],
+                    token: Some("secret".to_string()),
+                    timeout_secs: 600,
+                    fallback_local: true,
+                })
+            );
+            assert_eq!(config.bb_engine(), BbEngineKind::Ffi);
+            assert_eq!(
+                config.transport(),
+                &NetTransportConfig {
+                    tcp_port: Some(9092),
+                    relays: vec!["/ip4/10.0.0.1/tcp/9092/p2p/12D3KooWRelay".to_string()],
+                    relay_server: false,
+                    external_addrs: vec![],
+                    autonat: true,
+                    hole_punching: false,
+                }
+            );
+            assert!(config.peer_allowlist());
+            assert_eq!(
+                config.discovery(),
+                &NetDiscoveryConfig {
+                    publish_addrs: true,
+                    random_walk_interval_secs: 120,
+                    ..NetDiscoveryConfig::default()
+                }
+            );
+            assert_eq!(
+                config.net_limits(),
+                &NetLimitsConfig {
+                    gossipsub: ProtocolLimitConfig {
+                        peer_bytes_per_sec: Some(1048576),
+                        ..ProtocolLimitConfig::default()
+                    },
+                    request_response: ProtocolLimitConfig::default(),
+                    kademlia: ProtocolLimitConfig {
+                        max_message_bytes: Some(4194304),
+                        ..ProtocolLimitConfig::default()
+                    },
+                }
+            );
+            assert_eq!(
+                config.sortition(),
+                &SortitionConfig {
+                    policy: SortitionPolicyConfig::Diverse { max_per_group: 2 },
+                    operator_groups: HashMap::from([(
+                        "0x2546BcD3c84621e976D8185a91A922aE77ECEc30"
+                            .parse()
+                            .unwrap(),
+                        "eu".to_string()
+                    )]),
+                }
+            );
             assert_eq!(
                 config.config_file(),
                 PathBuf::from("/default/config/interfold.config.yaml")
@@ -573,6 +986,14 @@ nodes:
                 config.key_file(),
                 PathBuf::from("/myconfig/interfold/ag/key")
             );
+            assert_eq!(
+                config.nodes()["ag"].supervisor,
+                SupervisorConfig {
+                    restart: RestartPolicy::Always,
+                    max_restarts: Some(10),
+                    ..SupervisorConfig::default()
+                }
+            );
         };
         Ok(())
     }
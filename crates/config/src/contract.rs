@@ -50,6 +50,13 @@ pub struct ContractAddresses {
     pub fee_token: Option<Contract>,
     pub slashing_manager: Option<Contract>,
     pub dkg_fold_attestation_verifier: Option<Contract>,
+    /// Honk verifier for `dkg_aggregator` proofs. Overrides the one behind the Interfold's BFV
+    /// `pkVerifier`. Only read at startup to check the local circuits' VK hash, never indexed,
+    /// so it is not part of [`Self::contracts`].
+    pub dkg_aggregator_verifier: Option<Contract>,
+    /// Honk verifier for `decryption_aggregator` proofs. Overrides the one behind the
+    /// Interfold's BFV `decryptionVerifier`; see `dkg_aggregator_verifier`.
+    pub decryption_aggregator_verifier: Option<Contract>,
 }
 
 impl ContractAddresses {
@@ -44,6 +44,7 @@ pub const DEFAULT_CONFIG_NAME: &str = "interfold.config.yaml";
 pub const DEFAULT_KEY_NAME: &str = "key";
 pub const DEFAULT_DB_NAME: &str = "db";
 pub const DEFAULT_LOG_NAME: &str = "log";
+pub const DEFAULT_NODE_OUTPUT_NAME: &str = "output.log";
 
 // Find the config file is specified anywhere upstream from cwd and if found then locate the
 // data and config folders under .interfold/data and .interfold/config relative to the location of
@@ -134,6 +135,15 @@ impl PathsEngine {
         clean(self.get_data_dir().join(&self.name).join(DEFAULT_LOG_NAME))
     }
 
+    /// Output log of a node supervised by `interfold nodes`
+    pub fn node_output_file(&self, node_name: &str) -> PathBuf {
+        clean(
+            self.get_data_dir()
+                .join(node_name)
+                .join(DEFAULT_NODE_OUTPUT_NAME),
+        )
+    }
+
     pub fn relative_to_config(&self, path: &PathBuf) -> PathBuf {
         if path.is_absolute() {
             return PathBuf::from(path);
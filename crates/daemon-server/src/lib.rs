@@ -9,7 +9,9 @@ use e3_config::AppConfig;
 use e3_console::{log, Console};
 use serde::Serialize;
 use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpStream;
 use tracing::error;
@@ -18,10 +20,45 @@ const TCP_ADDRESS: &str = "127.0.0.1"; // using localhost specifically so that i
                                        // externally. We might change this if we need to control
                                        // externally and add authentication or TLS
 
+/// Set once the node has been built. Until then `HEAD` probes of the ctrl socket are answered
+/// with `503 Service Unavailable`.
+static NODE_READY: AtomicBool = AtomicBool::new(false);
+
+/// Mark the node served by this process as ready
+pub fn mark_ready() {
+    NODE_READY.store(true, Ordering::SeqCst);
+}
+
 pub struct ServerInfo {
     pub port: u16,
 }
 
+/// Health of a node as seen through its ctrl socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaemonProbe {
+    /// Nothing answers on the ctrl socket
+    Unreachable,
+    /// The ctrl socket answers but the node is still starting
+    Alive,
+    /// The node is up and serving commands
+    Ready,
+}
+
+/// Probe the ctrl socket on `port`
+pub async fn probe_daemon(port: u16, timeout: Duration) -> DaemonProbe {
+    let url = format!("http://{}:{}", TCP_ADDRESS, port);
+    match reqwest::Client::new()
+        .head(&url)
+        .timeout(timeout)
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status().is_success() => DaemonProbe::Ready,
+        Ok(_) => DaemonProbe::Alive,
+        Err(_) => DaemonProbe::Unreachable,
+    }
+}
+
 pub async fn connect_daemon(maybe_config: Option<&AppConfig>) -> Option<ServerInfo> {
     let config = maybe_config?;
     let port = config.ctrl_port();
@@ -97,6 +134,10 @@ where
     let (reader, mut writer) = stream.into_split();
     let mut buf_reader = BufReader::new(reader);
 
+    let mut request_line = String::new();
+    buf_reader.read_line(&mut request_line).await?;
+    let is_probe = request_line.starts_with("HEAD ");
+
     // Read headers until blank line
     let mut content_length: usize = 0;
     loop {
@@ -110,6 +151,20 @@ where
         }
     }
 
+    // Health probes are answered without running a command
+    if is_probe {
+        let status = if NODE_READY.load(Ordering::SeqCst) {
+            "200 OK"
+        } else {
+            "503 Service Unavailable"
+        };
+        let response =
+            format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+        writer.write_all(response.as_bytes()).await?;
+        writer.shutdown().await?;
+        return Ok(());
+    }
+
     // Read body
     let mut body = vec![0u8; content_length];
     tokio::io::AsyncReadExt::read_exact(&mut buf_reader, &mut body).await?;
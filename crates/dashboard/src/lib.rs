@@ -179,6 +179,7 @@ async fn route(
         "/api/noir" => serde_json::json!("NoirStatus"),
         "/api/wallet" => serde_json::json!("WalletGet"),
         "/api/peer-id" => serde_json::json!("NetGetPeerId"),
+        "/api/bandwidth" => serde_json::json!("NetBandwidth"),
         _ => {
             return (
                 "404 Not Found".to_string(),
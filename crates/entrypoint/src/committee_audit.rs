@@ -0,0 +1,237 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Offline committee selection audit.
+//!
+//! Backs the `interfold node audit-committee` CLI command. It reads the node's event log
+//! **read-only**, replays the sortition state up to the E3 request with
+//! [`e3_sortition::audit_committee`] and renders the recomputed ranking next to the committee
+//! that was published on-chain, so an operator can see why a node was or was not selected.
+//!
+//! The ranking uses the selection policy and operator groups the node persisted with its
+//! sortition state.
+
+use crate::helpers::datastore::{get_eventstore_reader, get_repositories};
+use crate::validate::{aggregate_ids, read_all_events};
+use alloy::primitives::Address;
+use anyhow::{bail, Result};
+use e3_config::AppConfig;
+use e3_events::{AggregateId, E3id, Event, InterfoldEventData};
+use e3_sortition::{
+    audit_committee, CommitteeAudit, CommitteeMismatch, NodeStateRepositoryFactory,
+    SelectionPolicy, SortitionRepositoryFactory,
+};
+use std::collections::{BTreeSet, HashMap};
+
+/// Recompute the committee selection of E3 `e3_id` from the node's event store.
+///
+/// When `chain_id` is omitted it is inferred from the `E3Requested` events in the store.
+pub async fn audit_e3_committee(
+    config: &AppConfig,
+    e3_id: &str,
+    chain_id: Option<u64>,
+) -> Result<CommitteeAudit> {
+    let eventstore = get_eventstore_reader(config)?;
+    let chain_id = match chain_id {
+        Some(chain_id) => chain_id,
+        None => {
+            let mut events = Vec::new();
+            for agg in aggregate_ids(config) {
+                events.extend(read_all_events(&eventstore, agg).await?);
+            }
+            resolve_chain_id(events.iter().map(|e| e.get_data()), e3_id)?
+        }
+    };
+
+    let (policy, groups) = selection_settings(config, chain_id).await?;
+    let events = read_all_events(&eventstore, AggregateId::from_chain_id(Some(chain_id))).await?;
+    audit_committee(
+        events.iter().map(|e| e.get_data()),
+        &E3id::new(e3_id, chain_id),
+        &policy,
+        &groups,
+    )
+}
+
+/// The selection policy and declared operator groups the node selects committees of `chain_id`
+/// with. A node that never persisted sortition state falls back to score sortition, the policy
+/// `interfold start` uses.
+async fn selection_settings(
+    config: &AppConfig,
+    chain_id: u64,
+) -> Result<(SelectionPolicy, HashMap<Address, String>)> {
+    let repositories = get_repositories(config)?;
+    let policy = repositories
+        .sortition()
+        .read()
+        .await?
+        .and_then(|backends| {
+            // New chains use the backend stored under `u64::MAX`
+            backends
+                .get(&chain_id)
+                .or_else(|| backends.get(&u64::MAX))
+                .map(|backend| backend.policy())
+        })
+        .unwrap_or(SelectionPolicy::Score);
+    let groups = repositories
+        .node_state()
+        .read()
+        .await?
+        .and_then(|mut state| state.remove(&chain_id))
+        .map(|state| {
+            state
+                .nodes
+                .into_iter()
+                .filter_map(|(address, node)| Some((address.parse().ok()?, node.group?)))
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok((policy, groups))
+}
+
+/// Find the single chain on which `e3_id` was requested.
+fn resolve_chain_id<'a>(
+    events: impl IntoIterator<Item = &'a InterfoldEventData>,
+    e3_id: &str,
+) -> Result<u64> {
+    let chains: BTreeSet<u64> = events
+        .into_iter()
+        .filter_map(|event| match event {
+            InterfoldEventData::E3Requested(d) if d.e3_id.e3_id() == e3_id => {
+                Some(d.e3_id.chain_id())
+            }
+            _ => None,
+        })
+        .collect();
+    match chains.len() {
+        0 => bail!("No E3Requested event found for E3 {e3_id}"),
+        1 => Ok(chains.into_iter().next().unwrap_or_default()),
+        _ => bail!("E3 {e3_id} was requested on several chains {chains:?}, pass --chain-id"),
+    }
+}
+
+/// Render the audit as human-readable text.
+pub fn render(audit: &CommitteeAudit) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "Committee selection audit for E3 {}\n",
+        audit.e3_id
+    ));
+    out.push_str("==============================\n");
+    out.push_str(&format!("seed:         {:?}\n", audit.seed));
+    out.push_str(&format!("policy:       {:?}\n", audit.policy));
+    out.push_str(&format!(
+        "threshold:    {}/{}\n",
+        audit.threshold_m, audit.threshold_n
+    ));
+    out.push_str(&format!(
+        "buffer:       {} (selection size {})\n",
+        audit.buffer,
+        audit.selection_size()
+    ));
+    out.push_str(&format!("ticket price: {}\n\n", audit.ticket_price));
+
+    let headers = [
+        "rank",
+        "address",
+        "score",
+        "ticket",
+        "tickets",
+        "active",
+        "selected",
+        "published",
+    ];
+    let rows: Vec<Vec<String>> = audit
+        .nodes
+        .iter()
+        .map(|n| {
+            let (ticket, score) = match &n.best_ticket {
+                Some((ticket_id, score)) => (ticket_id.to_string(), format!("{score:064x}")),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            let selected = n.rank.is_some_and(|rank| rank < audit.selection_size());
+            vec![
+                n.rank
+                    .map_or("-".to_string(), |rank| (rank + 1).to_string()),
+                n.address.clone(),
+                score.chars().take(16).collect(),
+                ticket,
+                n.available_tickets.to_string(),
+                yes_no(n.active),
+                yes_no(selected),
+                yes_no(n.published),
+            ]
+        })
+        .collect();
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    for row in std::iter::once(headers.map(String::from).to_vec()).chain(rows) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+
+    out.push_str("------------------------------\n");
+    let Some(published) = &audit.published else {
+        out.push_str("No CommitteePublished event found; the committee has not been finalized.\n");
+        return out;
+    };
+    out.push_str(&format!(
+        "published committee: {} node(s)\n",
+        published.len()
+    ));
+    let mismatches = audit.mismatches();
+    if mismatches.is_empty() {
+        out.push_str("AUDIT PASSED — the published committee matches the recomputed ranking.\n");
+    }
+    for mismatch in &mismatches {
+        match mismatch {
+            CommitteeMismatch::NotSelected { address } => out.push_str(&format!(
+                "[MISMATCH] {address} is on the published committee but was not selected\n"
+            )),
+            CommitteeMismatch::NotPublished { address, rank } => out.push_str(&format!(
+                "[MISMATCH] {address} ranked {} but is not on the published committee\n",
+                rank + 1
+            )),
+        }
+    }
+    out
+}
+
+fn yes_no(value: bool) -> String {
+    if value { "yes" } else { "no" }.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use e3_events::E3Requested;
+
+    fn requested(id: &str, chain_id: u64) -> InterfoldEventData {
+        InterfoldEventData::E3Requested(E3Requested {
+            e3_id: E3id::new(id, chain_id),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn resolves_the_chain_of_the_request() {
+        let events = vec![requested("1", 1), requested("2", 5)];
+        assert_eq!(resolve_chain_id(&events, "2").unwrap(), 5);
+        assert!(resolve_chain_id(&events, "3").is_err());
+
+        let events = vec![requested("1", 1), requested("1", 5)];
+        assert!(resolve_chain_id(&events, "1").is_err());
+    }
+}
@@ -4,6 +4,7 @@
 // without even the implied warranty of MERCHANTABILITY
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
+pub mod committee_audit;
 pub mod config;
 pub mod fence;
 pub mod helpers;
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use crate::helpers::datastore::get_repositories;
+use anyhow::Result;
+use e3_config::AppConfig;
+use e3_net::bandwidth::BandwidthReport;
+use e3_net::NetRepositoryFactory;
+
+/// The latest traffic snapshot written by the running node, if any.
+pub async fn execute(config: &AppConfig) -> Result<Option<BandwidthReport>> {
+    let repositories = get_repositories(config)?;
+    repositories.bandwidth().read().await
+}
@@ -4,4 +4,6 @@
 // without even the implied warranty of MERCHANTABILITY
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
+pub mod bandwidth;
 pub mod get_peer_id;
+pub mod peers;
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use crate::helpers::datastore::get_repositories;
+use anyhow::Result;
+use e3_config::AppConfig;
+use e3_net::peer_identity::PeerIdentityRecord;
+use e3_net::NetRepositoryFactory;
+
+/// Verified PeerId -> operator bindings, most recently verified first.
+pub async fn execute(config: &AppConfig) -> Result<Vec<PeerIdentityRecord>> {
+    let repositories = get_repositories(config)?;
+    let mut records: Vec<_> = repositories
+        .peer_identities()
+        .read()
+        .await?
+        .unwrap_or_default()
+        .into_values()
+        .collect();
+    records.sort_by(|a, b| b.verified_at.cmp(&a.verified_at));
+    Ok(records)
+}
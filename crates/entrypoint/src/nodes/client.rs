@@ -7,11 +7,13 @@
 use anyhow::Result;
 use reqwest::Client;
 use std::env;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{error, trace};
 
 use crate::helpers::termtable::print_table;
 
-use super::nodes::{spawn_process, Action, ProcessStatus, Query, SERVER_ADDRESS};
+use super::nodes::{spawn_process, Action, ProcessInfo, Query, SERVER_ADDRESS};
 
 pub async fn get_status() -> Result<Query> {
     let client = Client::new();
@@ -64,25 +66,65 @@ pub async fn restart(id: &str) -> Result<()> {
 pub async fn status(id: &str) -> Result<()> {
     let status = get_status().await?;
     if let Query::Status { status } = status {
-        let state = status.processes.get(id).unwrap_or(&ProcessStatus::Stopped);
-        println!("{:?}", state);
+        let info = status.processes.get(id).cloned().unwrap_or_default();
+        println!("{:?}", info.status);
     }
 
     Ok(())
 }
 
+/// Format a duration in seconds as e.g. `2h13m` or `45s`
+fn format_uptime(secs: u64) -> String {
+    match secs {
+        s if s >= 86400 => format!("{}d{}h", s / 86400, s % 86400 / 3600),
+        s if s >= 3600 => format!("{}h{}m", s / 3600, s % 3600 / 60),
+        s if s >= 60 => format!("{}m{}s", s / 60, s % 60),
+        s => format!("{}s", s),
+    }
+}
+
+fn ps_row(id: &str, info: &ProcessInfo, now: u64) -> Vec<String> {
+    let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    vec![
+        id.to_string(),
+        format!("{:?}", info.status),
+        or_dash(info.pid.map(|pid| pid.to_string())),
+        or_dash(
+            info.started_at
+                .map(|started_at| format_uptime(now.saturating_sub(started_at))),
+        ),
+        info.restarts.to_string(),
+        or_dash(info.last_exit_code.map(|code| code.to_string())),
+    ]
+}
+
 pub async fn ps() -> Result<()> {
-    let rows: Vec<Vec<String>> = if let Ok(Query::Status { status }) = get_status().await {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut rows: Vec<Vec<String>> = if let Ok(Query::Status { status }) = get_status().await {
         status
             .processes
             .iter()
-            .map(|(k, v)| vec![k.to_string(), format!("{:?}", v)])
+            .map(|(id, info)| ps_row(id, info, now))
             .collect()
     } else {
         vec![]
     };
-
-    print_table(&["PROCESS", "STATUS"], &rows);
+    rows.sort();
+
+    print_table(
+        &[
+            "PROCESS",
+            "STATUS",
+            "PID",
+            "UPTIME",
+            "RESTARTS",
+            "LAST EXIT",
+        ],
+        &rows,
+    );
 
     Ok(())
 }
@@ -98,6 +140,7 @@ pub async fn is_ready() -> Result<bool> {
 pub async fn start_daemon(
     verbose: u8,
     maybe_config_string: &Option<String>,
+    maybe_cluster: Option<(&Path, bool)>,
     exclude: &[String],
 ) -> Result<()> {
     if is_ready().await? {
@@ -115,6 +158,14 @@ pub async fn start_daemon(
         args.push(config_string.to_string());
     }
 
+    if let Some((cluster, force)) = maybe_cluster {
+        args.push("--cluster".to_string());
+        args.push(cluster.display().to_string());
+        if force {
+            args.push("--force".to_string());
+        }
+    }
+
     if verbose > 0 {
         args.push(format!("-{}", "v".repeat(verbose as usize))); // -vvv
     }
@@ -0,0 +1,504 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use alloy_primitives::Address;
+use anyhow::*;
+use e3_config::{load_config, AppConfig, RestartPolicy, SupervisorConfig};
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tracing::info;
+use zeroize::{Zeroize, Zeroizing};
+
+use super::nodes::NodeCommand;
+use crate::helpers::{datastore::close_all_connections, rand::generate_random_bytes};
+
+/// Process id of the local chain in the nodes set
+pub const CHAIN_PROCESS: &str = "anvil";
+/// The hardhat `localhost` network the contracts are deployed with is fixed to this port
+const CHAIN_PORT: u16 = 8545;
+const CHAIN_ID: u64 = 31337;
+const CHAIN_MNEMONIC: &str = "test test test test test test test test test test test junk";
+/// Name of the chain in the generated config and of the network in `deployed_contracts.json`
+const CHAIN_NAME: &str = "localhost";
+const CHAIN_STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+const CONFIG_HEADER: &str =
+    "# Generated by `interfold nodes up --cluster`. Changes are overwritten on the next run.";
+
+/// Contracts read from `deployed_contracts.json` and the config keys they are written to
+const DEPLOYED_CONTRACTS: [(&str, &str); 6] = [
+    ("Interfold", "interfold"),
+    ("CiphernodeRegistryOwnable", "ciphernode_registry"),
+    ("BondingRegistry", "bonding_registry"),
+    ("MockE3Program", "e3_program"),
+    ("MockUSDC", "fee_token"),
+    ("SlashingManager", "slashing_manager"),
+];
+
+/// A declarative development cluster. `interfold nodes up --cluster cluster.yaml` generates the
+/// nodes described here, starts a local chain with the contracts deployed and the nodes
+/// registered as bonded operators and then launches the nodes against it.
+///
+/// Relative paths are resolved against the folder the cluster file is in.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct ClusterSpec {
+    /// Number of nodes to generate
+    pub nodes: usize,
+    /// Nodes are named `<name_prefix>1` to `<name_prefix><nodes>`
+    pub name_prefix: String,
+    /// QUIC port of the first node. Every following node uses the next port.
+    pub quic_port: u16,
+    /// Ctrl port of the first node. Every following node uses the next port.
+    pub ctrl_port: u16,
+    /// Seconds between blocks on the local chain
+    pub block_time: u64,
+    /// The `interfold-contracts` package that is deployed to the local chain
+    pub contracts_dir: PathBuf,
+    /// The configuration file that is generated for the cluster
+    pub config: PathBuf,
+    /// License tokens every operator bonds
+    pub license_bond_amount: String,
+    /// Ticket balance every operator is funded with
+    pub ticket_amount: String,
+    /// Settings applied to every generated node eg. `supervisor` or `net_limits`
+    pub node: Mapping,
+    /// The `program` section of the generated config. Defaults to `dev: true`.
+    pub program: Option<Value>,
+}
+
+impl Default for ClusterSpec {
+    fn default() -> Self {
+        Self {
+            nodes: 3,
+            name_prefix: "cn".to_string(),
+            quic_port: 9201,
+            ctrl_port: 50501,
+            block_time: 1,
+            contracts_dir: PathBuf::from("packages/interfold-contracts"),
+            config: PathBuf::from("interfold.config.yaml"),
+            license_bond_amount: "1000".to_string(),
+            ticket_amount: "1000".to_string(),
+            node: Mapping::new(),
+            program: None,
+        }
+    }
+}
+
+impl ClusterSpec {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Could not read cluster file {}", path.display()))?;
+        let spec: ClusterSpec = serde_yaml::from_str(&contents)
+            .with_context(|| format!("Could not parse cluster file {}", path.display()))?;
+        let base = path.parent().unwrap_or(Path::new("."));
+        Ok(spec.resolve_paths(base))
+    }
+
+    fn resolve_paths(mut self, base: &Path) -> Self {
+        self.contracts_dir = base.join(&self.contracts_dir);
+        self.config = base.join(&self.config);
+        self
+    }
+
+    pub fn node_names(&self) -> Vec<String> {
+        (1..=self.nodes)
+            .map(|n| format!("{}{}", self.name_prefix, n))
+            .collect()
+    }
+
+    pub fn config_string(&self) -> String {
+        self.config.display().to_string()
+    }
+
+    /// The `.interfold` folder holding the keys and data of the generated nodes
+    fn root_dir(&self) -> PathBuf {
+        self.config
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(".interfold")
+    }
+
+    /// Whether the config file carries the header written by [`ClusterSpec::write_config`]
+    fn is_generated(&self) -> Result<bool> {
+        match fs::read_to_string(&self.config) {
+            std::result::Result::Ok(contents) => Ok(contents.starts_with(CONFIG_HEADER)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e).with_context(|| format!("Could not read {}", self.config.display())),
+        }
+    }
+
+    /// Refuse to replace a config and node keys or data that were not generated from a cluster
+    /// file unless `force` is set
+    pub fn ensure_resettable(&self, force: bool) -> Result<()> {
+        if force || self.is_generated()? {
+            return Ok(());
+        }
+        let existing: Vec<String> = [
+            self.config.clone(),
+            self.root_dir().join("config"),
+            self.root_dir().join("data"),
+        ]
+        .iter()
+        .filter(|path| path.exists())
+        .map(|path| path.display().to_string())
+        .collect();
+        if !existing.is_empty() {
+            bail!(
+                "{} were not generated from a cluster file and would be overwritten. Pass --force to replace them.",
+                existing.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    fn node_definition(&self, index: usize, address: Option<&Address>) -> Value {
+        let mut node = self.node.clone();
+        if let Some(address) = address {
+            node.insert("address".into(), address.to_string().into());
+        }
+        node.insert("quic_port".into(), (self.quic_port as usize + index).into());
+        node.insert("ctrl_port".into(), (self.ctrl_port as usize + index).into());
+        node.insert("autopassword".into(), true.into());
+        Value::Mapping(node)
+    }
+
+    /// Build the configuration document for the cluster. The chain is only added once the
+    /// contracts have been deployed.
+    pub fn config_document(
+        &self,
+        addresses: &HashMap<String, Address>,
+        contracts: Option<Mapping>,
+    ) -> Value {
+        let mut nodes = Mapping::new();
+        for (index, name) in self.node_names().into_iter().enumerate() {
+            let definition = self.node_definition(index, addresses.get(&name));
+            nodes.insert(name.into(), definition);
+        }
+
+        let mut document = Mapping::new();
+        if let Some(contracts) = contracts {
+            let mut chain = Mapping::new();
+            chain.insert("name".into(), CHAIN_NAME.into());
+            chain.insert(
+                "rpc_url".into(),
+                format!("ws://localhost:{CHAIN_PORT}").into(),
+            );
+            chain.insert("chain_id".into(), CHAIN_ID.into());
+            chain.insert("contracts".into(), Value::Mapping(contracts));
+            document.insert(
+                "chains".into(),
+                Value::Sequence(vec![Value::Mapping(chain)]),
+            );
+        }
+        let program = self.program.clone().unwrap_or_else(|| {
+            let mut program = Mapping::new();
+            program.insert("dev".into(), true.into());
+            Value::Mapping(program)
+        });
+        document.insert("program".into(), program);
+        document.insert("nodes".into(), Value::Mapping(nodes));
+        Value::Mapping(document)
+    }
+
+    pub fn write_config(
+        &self,
+        addresses: &HashMap<String, Address>,
+        contracts: Option<Mapping>,
+    ) -> Result<()> {
+        let yaml = serde_yaml::to_string(&self.config_document(addresses, contracts))?;
+        if let Some(parent) = self.config.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.config, format!("{CONFIG_HEADER}\n{yaml}"))
+            .with_context(|| format!("Could not write {}", self.config.display()))?;
+        Ok(())
+    }
+
+    /// Load the generated config scoped to the given node
+    pub fn load_config(&self, name: &str, otel: Option<String>) -> Result<AppConfig> {
+        load_config(name, Some(self.config_string()), otel)
+    }
+
+    /// The command running the local chain
+    pub fn chain_command(&self, config: &AppConfig) -> NodeCommand {
+        let args = [
+            "--host",
+            "127.0.0.1",
+            "--port",
+            &CHAIN_PORT.to_string(),
+            "--chain-id",
+            &CHAIN_ID.to_string(),
+            "--block-time",
+            &self.block_time.to_string(),
+            "--mnemonic",
+            CHAIN_MNEMONIC,
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        NodeCommand {
+            params: ("anvil".to_string(), args),
+            ctrl_port: None,
+            // A restarted chain has lost every deployment so there is no point restarting it
+            supervisor: SupervisorConfig {
+                restart: RestartPolicy::Never,
+                ..SupervisorConfig::default()
+            },
+            log_file: config.node_output_file(CHAIN_PROCESS),
+        }
+    }
+}
+
+/// Read the addresses of the deployed contracts from `deployed_contracts.json` as the `contracts`
+/// section of a chain
+pub fn contracts_from_deployments(deployments: &serde_json::Value) -> Result<Mapping> {
+    let network = deployments
+        .get(CHAIN_NAME)
+        .ok_or_else(|| anyhow!("No '{}' deployments found", CHAIN_NAME))?;
+
+    let mut contracts = Mapping::new();
+    for (name, key) in DEPLOYED_CONTRACTS {
+        let deployment = &network[name];
+        let (Some(address), Some(deploy_block)) = (
+            deployment["address"].as_str(),
+            deployment["blockNumber"].as_u64(),
+        ) else {
+            bail!(
+                "Deployment of {} is missing 'address' or 'blockNumber'",
+                name
+            );
+        };
+        let mut contract = Mapping::new();
+        contract.insert("address".into(), address.into());
+        contract.insert("deploy_block".into(), deploy_block.into());
+        contracts.insert(key.into(), Value::Mapping(contract));
+    }
+    Ok(contracts)
+}
+
+/// Generate the nodes of the cluster from scratch and give every node a password and a fresh
+/// wallet. The local chain starts empty on every run so previous node data is removed. Data that
+/// was not generated from a cluster file is only removed when `force` is set.
+pub async fn generate(spec: &ClusterSpec, force: bool) -> Result<HashMap<String, Address>> {
+    spec.ensure_resettable(force)?;
+    for dir in ["config", "data"] {
+        let dir = spec.root_dir().join(dir);
+        if dir.exists() {
+            fs::remove_dir_all(&dir)
+                .with_context(|| format!("Could not reset {}", dir.display()))?;
+        }
+    }
+
+    spec.write_config(&HashMap::new(), None)?;
+
+    let mut addresses = HashMap::new();
+    for name in spec.node_names() {
+        let config = spec.load_config(&name, None)?;
+        crate::password::set::autopassword(&config).await?;
+        let mut bytes = generate_random_bytes(32);
+        let key = Zeroizing::new(hex::encode(&bytes));
+        bytes.zeroize();
+        let (address, peer_id) = crate::wallet::set::execute(&config, key).await?;
+        info!(
+            "Generated {} with address {} and peer id {}",
+            name, address, peer_id
+        );
+        addresses.insert(name, address);
+    }
+    // The nodes open their own stores once launched
+    close_all_connections();
+
+    spec.write_config(&addresses, None)?;
+    Ok(addresses)
+}
+
+/// Wait until the local chain answers RPC requests
+pub async fn wait_for_chain() -> Result<()> {
+    let client = reqwest::Client::new();
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_chainId",
+        "params": [],
+    });
+    let started = Instant::now();
+    loop {
+        let response = client
+            .post(format!("http://127.0.0.1:{CHAIN_PORT}"))
+            .json(&request)
+            .send()
+            .await;
+        if response.is_ok_and(|response| response.status().is_success()) {
+            return Ok(());
+        }
+        if started.elapsed() > CHAIN_STARTUP_TIMEOUT {
+            bail!(
+                "Local chain did not start on port {}. Is anvil installed?",
+                CHAIN_PORT
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+async fn pnpm(contracts_dir: &Path, args: &[&str]) -> Result<()> {
+    info!("Running pnpm {}", args.join(" "));
+    let output = Command::new("pnpm")
+        .args(args)
+        .current_dir(contracts_dir)
+        .output()
+        .await
+        .with_context(|| format!("Could not run pnpm in {}", contracts_dir.display()))?;
+    if !output.status.success() {
+        bail!(
+            "pnpm {} failed:\n{}{}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(())
+}
+
+/// Deploy the contracts to the local chain, register and bond every operator and return the
+/// deployed contracts
+pub async fn deploy(spec: &ClusterSpec, addresses: &HashMap<String, Address>) -> Result<Mapping> {
+    pnpm(&spec.contracts_dir, &["deploy:mocks"]).await?;
+
+    for name in spec.node_names() {
+        let address = addresses[&name].to_string();
+        pnpm(
+            &spec.contracts_dir,
+            &[
+                "ciphernode:admin-add",
+                "--ciphernode-address",
+                &address,
+                "--license-bond-amount",
+                &spec.license_bond_amount,
+                "--ticket-amount",
+                &spec.ticket_amount,
+                "--network",
+                CHAIN_NAME,
+            ],
+        )
+        .await?;
+        info!("Registered {} ({}) as a bonded operator", name, address);
+    }
+
+    let deployments_file = spec.contracts_dir.join("deployed_contracts.json");
+    let deployments: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(&deployments_file)
+            .with_context(|| format!("Could not read {}", deployments_file.display()))?,
+    )?;
+    contracts_from_deployments(&deployments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn parses_cluster_files_with_defaults() -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("cluster.yaml");
+        fs::write(
+            &path,
+            r#"
+nodes: 2
+contracts_dir: ../contracts
+node:
+  supervisor:
+    restart: always
+"#,
+        )?;
+
+        let spec = ClusterSpec::load(&path)?;
+
+        assert_eq!(spec.node_names(), vec!["cn1", "cn2"]);
+        assert_eq!(spec.contracts_dir, dir.path().join("../contracts"));
+        assert_eq!(spec.config, dir.path().join("interfold.config.yaml"));
+        assert_eq!(spec.quic_port, 9201);
+        Ok(())
+    }
+
+    #[test]
+    fn generated_config_loads_for_every_node() -> Result<()> {
+        let dir = tempdir()?;
+        let spec = ClusterSpec {
+            nodes: 2,
+            node: serde_yaml::from_str("supervisor:\n  restart: always")?,
+            ..ClusterSpec::default()
+        }
+        .resolve_paths(dir.path());
+        let address: Address = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse()?;
+        let addresses = HashMap::from([("cn2".to_string(), address)]);
+        let contracts = contracts_from_deployments(&serde_json::json!({
+            "localhost": {
+                "Interfold": { "address": "0xA51c1fc2f0D1a1b8494Ed1FE312d7C3a78Ed91C0", "blockNumber": 18 },
+                "CiphernodeRegistryOwnable": { "address": "0xa513E6E4b8f2a923D98304ec87F64353C4D5C853", "blockNumber": 13 },
+                "BondingRegistry": { "address": "0x8A791620dd6260079BF849Dc5567aDC3F2FdC318", "blockNumber": 14 },
+                "MockE3Program": { "address": "0x4c5859f0F772848b2D91F1D83E2Fe57935348029", "blockNumber": 37 },
+                "MockUSDC": { "address": "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512", "blockNumber": 8 },
+                "SlashingManager": { "address": "0x5FC8d32690cc91D4c39d9d3abcBD16989F875707", "blockNumber": 12 },
+            }
+        }))?;
+
+        spec.write_config(&addresses, Some(contracts))?;
+
+        let config = spec.load_config("cn2", None)?;
+        assert_eq!(config.address(), Some(address));
+        assert_eq!(config.quic_port(), 9202);
+        assert_eq!(config.ctrl_port(), 50502);
+        assert_eq!(
+            config.nodes()["cn1"].supervisor.restart,
+            RestartPolicy::Always
+        );
+        let chain = &config.chains()[0];
+        assert_eq!(chain.rpc_url, "ws://localhost:8545");
+        assert_eq!(
+            chain.contracts.interfold.address_str(),
+            "0xA51c1fc2f0D1a1b8494Ed1FE312d7C3a78Ed91C0"
+        );
+        assert_eq!(chain.contracts.interfold.deploy_block(), Some(18));
+        Ok(())
+    }
+
+    #[test]
+    fn only_resets_generated_nodes_unless_forced() -> Result<()> {
+        let dir = tempdir()?;
+        let spec = ClusterSpec::default().resolve_paths(dir.path());
+        spec.ensure_resettable(false)?;
+
+        fs::write(&spec.config, "nodes: {}\n")?;
+        fs::create_dir_all(spec.root_dir().join("data"))?;
+        assert!(spec.ensure_resettable(false).is_err());
+        spec.ensure_resettable(true)?;
+
+        spec.write_config(&HashMap::new(), None)?;
+        spec.ensure_resettable(false)?;
+
+        fs::remove_file(&spec.config)?;
+        assert!(spec.ensure_resettable(false).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn missing_deployments_are_an_error() {
+        let deployments = serde_json::json!({
+            "localhost": { "Interfold": { "address": "0xA51c1fc2f0D1a1b8494Ed1FE312d7C3a78Ed91C0" } }
+        });
+        assert!(contracts_from_deployments(&deployments).is_err());
+        assert!(contracts_from_deployments(&serde_json::json!({})).is_err());
+    }
+}
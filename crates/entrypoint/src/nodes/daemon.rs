@@ -5,16 +5,18 @@
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
 use anyhow::*;
-use e3_config::{combine_unique, AppConfig, NodeDefinition};
+use e3_config::{combine_unique, AppConfig, NodeDefinition, SupervisorConfig};
+use std::path::Path;
 use std::sync::Arc;
 use std::{collections::HashMap, env};
 use tokio::sync::Mutex;
-use tracing::{error, info, instrument};
+use tracing::{error, info, instrument, warn};
 
+use super::cluster::{self, ClusterSpec, CHAIN_PROCESS};
 use super::process_manager::ProcessManager;
 use super::server::server;
 
-use super::nodes::{CommandMap, CommandParams};
+use super::nodes::{CommandMap, CommandParams, NodeCommand};
 
 /// Metadata used to workout launch charachteristics for swarm mode
 #[derive(Clone, Debug)]
@@ -22,7 +24,9 @@ pub struct LaunchCommand {
     pub name: String,
     pub ip: String, // maybe this should be an actual socket addr?
     pub quic_port: u16,
+    pub ctrl_port: u16,
     pub peers: Vec<String>,
+    pub supervisor: SupervisorConfig,
 }
 
 impl LaunchCommand {
@@ -31,7 +35,9 @@ impl LaunchCommand {
             name: name.to_owned(),
             ip: ip.to_owned(),
             quic_port: definition.quic_port,
+            ctrl_port: definition.ctrl_port,
             peers: vec![],
+            supervisor: definition.supervisor.clone(),
         }
     }
 
@@ -85,7 +91,7 @@ impl LaunchCommand {
 }
 
 fn extract_commands(
-    nodes: &HashMap<String, NodeDefinition>,
+    config: &AppConfig,
     ip: &str,
     exclude: Vec<String>,
     verbose: u8,
@@ -98,7 +104,8 @@ fn extract_commands(
     exclude_list.push("_default".to_string());
 
     // Filter all the nodes
-    let mut filtered: Vec<LaunchCommand> = nodes
+    let mut filtered: Vec<LaunchCommand> = config
+        .nodes()
         .iter()
         .filter(|(name, _)| !exclude_list.contains(name))
         .map(|(name, value)| LaunchCommand::from_definition(name, ip, value))
@@ -109,10 +116,33 @@ fn extract_commands(
         item.add_peers(&peers);
     }
 
+    // Nodes sharing a ctrl port cannot be told apart by their probes
+    let mut ctrl_ports: HashMap<u16, usize> = HashMap::new();
+    for item in filtered.iter() {
+        *ctrl_ports.entry(item.ctrl_port).or_default() += 1;
+    }
+
     let mut cmds = HashMap::new();
     for item in filtered.iter() {
         let params = item.to_params(verbose, &maybe_config_string, &maybe_otel)?;
-        cmds.insert(item.name.clone(), params);
+        let ctrl_port = if ctrl_ports[&item.ctrl_port] > 1 {
+            warn!(
+                "{} shares ctrl_port {} with another node; health checks are disabled for it",
+                item.name, item.ctrl_port
+            );
+            None
+        } else {
+            Some(item.ctrl_port)
+        };
+        cmds.insert(
+            item.name.clone(),
+            NodeCommand {
+                params,
+                ctrl_port,
+                supervisor: item.supervisor.clone(),
+                log_file: config.node_output_file(&item.name),
+            },
+        );
     }
 
     Ok(cmds)
@@ -127,7 +157,7 @@ pub async fn execute(
     maybe_otel: Option<String>,
 ) -> Result<()> {
     let command_map = extract_commands(
-        config.nodes(),
+        config,
         "127.0.0.1",
         exclude,
         verbose,
@@ -139,8 +169,66 @@ pub async fn execute(
 
     process_manager.lock().await.start_all().await?;
 
-    let manager = process_manager.clone();
+    serve(process_manager).await
+}
+
+/// Bring up a development cluster described by a cluster file: generate the nodes, start a local
+/// chain, deploy the contracts, register the nodes as operators and launch them.
+#[instrument(skip_all)]
+pub async fn execute_cluster(
+    cluster_file: &Path,
+    force: bool,
+    exclude: Vec<String>,
+    verbose: u8,
+    maybe_otel: Option<String>,
+) -> Result<()> {
+    let spec = ClusterSpec::load(cluster_file)?;
+    let addresses = cluster::generate(&spec, force).await?;
+    let config = spec.load_config("_default", maybe_otel.clone())?;
+
+    let process_manager = Arc::new(Mutex::new(ProcessManager::from(HashMap::from([(
+        CHAIN_PROCESS.to_string(),
+        spec.chain_command(&config),
+    )]))));
+    process_manager.lock().await.start(CHAIN_PROCESS).await?;
+
+    let provisioned = tokio::select! {
+        res = async {
+            cluster::wait_for_chain().await?;
+            cluster::deploy(&spec, &addresses).await
+        } => res,
+        _ = tokio::signal::ctrl_c() => Err(anyhow!("Interrupted")),
+    };
+    let contracts = match provisioned {
+        std::result::Result::Ok(contracts) => contracts,
+        Err(e) => {
+            process_manager.lock().await.stop_all().await?;
+            return Err(e.context("Could not provision the cluster"));
+        }
+    };
+    spec.write_config(&addresses, Some(contracts))?;
+    info!("Cluster config written to {}", spec.config.display());
+
+    let config = spec.load_config("_default", maybe_otel.clone())?;
+    let command_map = extract_commands(
+        &config,
+        "127.0.0.1",
+        exclude,
+        verbose,
+        Some(spec.config_string()),
+        maybe_otel,
+    )?;
+    let ids: Vec<String> = command_map.keys().cloned().collect();
+    process_manager.lock().await.add(command_map);
+    for id in ids {
+        process_manager.lock().await.start(&id).await?;
+    }
+
+    serve(process_manager).await
+}
 
+/// Serve the daemon until it is terminated
+async fn serve(manager: Arc<Mutex<ProcessManager>>) -> Result<()> {
     tokio::select! {
         res = server(manager.clone()) => {
             if let Err(e) = res { error!(%e, "Signal server errored"); }
@@ -5,6 +5,7 @@
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
 pub mod client;
+pub mod cluster;
 pub mod daemon;
 pub mod down;
 #[allow(clippy::module_inception)]
@@ -13,6 +14,7 @@ pub mod process_manager;
 pub mod ps;
 pub mod purge;
 pub mod restart;
+pub mod rotating_log;
 pub mod server;
 pub mod start;
 pub mod status;
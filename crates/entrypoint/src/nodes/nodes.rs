@@ -5,11 +5,12 @@
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
 use anyhow::*;
+use e3_config::SupervisorConfig;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, process::Stdio, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, process::Stdio, sync::Arc};
 use tokio::{
     process::{Child, Command},
-    sync::Mutex,
+    sync::{oneshot, Mutex},
     task::JoinHandle,
 };
 
@@ -17,12 +18,30 @@ pub const SERVER_ADDRESS: &str = "127.0.0.1:13415";
 
 /// All the parameters of a command
 pub type CommandParams = (String, Vec<String>);
+/// Everything needed to launch and supervise a node
+#[derive(Clone, Debug)]
+pub struct NodeCommand {
+    pub params: CommandParams,
+    /// Port of the node's ctrl socket used for readiness and liveness probes. `None` disables
+    /// the probes.
+    pub ctrl_port: Option<u16>,
+    pub supervisor: SupervisorConfig,
+    /// File the node's output is written to
+    pub log_file: PathBuf,
+}
 /// A map of all the start commands to manage
-pub type CommandMap = HashMap<String, CommandParams>;
-/// The management record of the individual process
-pub type ProcessRecord = (Child, Vec<JoinHandle<()>>);
+pub type CommandMap = HashMap<String, NodeCommand>;
+/// The management record of the task supervising an individual process
+#[derive(Debug)]
+pub struct ProcessRecord {
+    /// Tells the supervisor to kill the process and stop restarting it
+    pub stop: oneshot::Sender<()>,
+    pub task: JoinHandle<()>,
+}
 /// The map that holds processes
 pub type ProcessMap = Arc<Mutex<HashMap<String, ProcessRecord>>>;
+/// The supervision state of every process
+pub type StatusMap = Arc<Mutex<HashMap<String, ProcessInfo>>>;
 
 /// Spawn a child process and return the Child handle
 pub async fn spawn_process(program: &str, args: Vec<String>) -> Result<Child> {
@@ -55,14 +74,38 @@ pub enum Query {
     Status { status: SwarmStatus },
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ProcessStatus {
-    Started,
+    /// Running but not ready to serve commands yet
+    Starting,
+    /// Running and answering on its ctrl socket
+    Ready,
+    /// Running but failing its liveness probes
+    Unhealthy,
+    /// Exited and waiting to be restarted
+    Restarting,
+    /// Exited and left stopped by its restart policy
+    Exited,
+    /// Stopped on request or never started
+    #[default]
     Stopped,
 }
 
+/// Supervision state of a process as shown by `interfold nodes ps`
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProcessInfo {
+    pub status: ProcessStatus,
+    pub pid: Option<u32>,
+    /// Automatic restarts since the swarm started
+    pub restarts: u32,
+    /// Unix timestamp (seconds) the running process was started at
+    pub started_at: Option<u64>,
+    /// Exit code of the previous process; `None` if none exited yet or it was killed by a signal
+    pub last_exit_code: Option<i32>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct SwarmStatus {
-    pub processes: HashMap<String, ProcessStatus>,
+    pub processes: HashMap<String, ProcessInfo>,
 }
@@ -5,13 +5,19 @@
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
 use anyhow::*;
+use e3_config::{RestartPolicy, SupervisorConfig};
+use e3_daemon_server::{probe_daemon, DaemonProbe};
 use std::collections::HashMap;
 use std::io::Write;
+use std::process::ExitStatus;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::io::AsyncBufReadExt;
+use tokio::process::Child;
 use tokio::signal::unix::{signal, SignalKind};
-use tokio::sync::Mutex;
+use tokio::sync::{oneshot, Mutex};
+use tokio::time::sleep;
 use tokio::{
     io::AsyncWriteExt,
     process::{ChildStderr, ChildStdout},
@@ -20,11 +26,20 @@ use tokio::{
 use tracing::{error, info, warn};
 
 use super::nodes::{
-    spawn_process, CommandMap, ProcessMap, ProcessRecord, ProcessStatus, SwarmStatus,
+    spawn_process, CommandMap, NodeCommand, ProcessInfo, ProcessMap, ProcessRecord, ProcessStatus,
+    StatusMap, SwarmStatus,
 };
+use super::rotating_log::{RotatingLog, SharedLog};
 
-/// Forward stdout from child process to parent's stdout
-fn forward_stdout(id: &str, stdout: ChildStdout) -> JoinHandle<()> {
+/// Write a line of child output to the node's log file
+fn write_log(id: &str, log: &SharedLog, line: &[u8]) {
+    if let Err(e) = log.lock().unwrap().write(line) {
+        error!("Failed to write log of {}: {}", id, e);
+    }
+}
+
+/// Forward stdout from child process to parent's stdout and the node's log file
+fn forward_stdout(id: &str, stdout: ChildStdout, log: SharedLog) -> JoinHandle<()> {
     let id = id.to_owned();
     tokio::spawn(async move {
         let mut reader = tokio::io::BufReader::new(stdout);
@@ -36,6 +51,7 @@ fn forward_stdout(id: &str, stdout: ChildStdout) -> JoinHandle<()> {
             if n == 0 {
                 break;
             }
+            write_log(&id, &log, &buffer);
             if let Err(e) = tokio::io::stdout()
                 .write_all(format!("[{}] {}", id, String::from_utf8_lossy(&buffer)).as_bytes())
                 .await
@@ -46,8 +62,8 @@ fn forward_stdout(id: &str, stdout: ChildStdout) -> JoinHandle<()> {
     })
 }
 
-/// Forward stderr from child process to parent's stderr
-fn forward_stderr(id: &str, stderr: ChildStderr) -> JoinHandle<()> {
+/// Forward stderr from child process to parent's stderr and the node's log file
+fn forward_stderr(id: &str, stderr: ChildStderr, log: SharedLog) -> JoinHandle<()> {
     let id = id.to_owned();
     tokio::spawn(async move {
         let mut reader = tokio::io::BufReader::new(stderr);
@@ -59,6 +75,7 @@ fn forward_stderr(id: &str, stderr: ChildStderr) -> JoinHandle<()> {
             if n == 0 {
                 break;
             }
+            write_log(&id, &log, &buffer);
             if let Err(e) = tokio::io::stderr()
                 .write_all(format!("[{}] {}", id, String::from_utf8_lossy(&buffer)).as_bytes())
                 .await
@@ -70,100 +87,325 @@ fn forward_stderr(id: &str, stderr: ChildStderr) -> JoinHandle<()> {
 }
 
 /// Run a single command
-async fn run_command(id: &str, program: &str, args: Vec<String>) -> Result<ProcessRecord> {
+async fn run_command(
+    id: &str,
+    program: &str,
+    args: Vec<String>,
+    log: &SharedLog,
+) -> Result<(Child, Vec<JoinHandle<()>>)> {
     let mut handles = vec![];
     let mut child = spawn_process(program, args).await?;
 
     if let Some(stdout) = child.stdout.take() {
-        handles.push(forward_stdout(id, stdout));
+        handles.push(forward_stdout(id, stdout, log.clone()));
     }
 
     if let Some(stderr) = child.stderr.take() {
-        handles.push(forward_stderr(id, stderr));
+        handles.push(forward_stderr(id, stderr, log.clone()));
     }
 
     Ok((child, handles))
 }
 
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Update the supervision state of a process
+async fn update_info(statuses: &StatusMap, id: &str, update: impl FnOnce(&mut ProcessInfo)) {
+    update(statuses.lock().await.entry(id.to_owned()).or_default());
+}
+
+/// Decides whether and after which delay a supervised process is restarted
+#[derive(Debug)]
+pub struct RestartBackoff {
+    config: SupervisorConfig,
+    /// Restarts since the process last ran for `backoff_reset_secs`
+    consecutive: u32,
+}
+
+impl RestartBackoff {
+    pub fn new(config: &SupervisorConfig) -> Self {
+        Self {
+            config: config.clone(),
+            consecutive: 0,
+        }
+    }
+
+    /// Delay before restarting a process that exited after running for `uptime`, or `None` if
+    /// it should stay stopped.
+    pub fn next(&mut self, failed: bool, uptime: Duration) -> Option<Duration> {
+        let restart = match self.config.restart {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => failed,
+            RestartPolicy::Always => true,
+        };
+        if !restart {
+            return None;
+        }
+        if uptime >= Duration::from_secs(self.config.backoff_reset_secs) {
+            self.consecutive = 0;
+        }
+        if let Some(max) = self.config.max_restarts {
+            if self.consecutive >= max {
+                return None;
+            }
+        }
+        let delay = self
+            .config
+            .backoff_initial_secs
+            .saturating_mul(1u64 << self.consecutive.min(32))
+            .min(self.config.backoff_max_secs);
+        self.consecutive += 1;
+        Some(Duration::from_secs(delay))
+    }
+}
+
+/// Probe the node's ctrl socket and keep its status up to date. Returns once the node fails its
+/// liveness check: it did not answer within the startup timeout, or stopped answering for
+/// `liveness_failures` consecutive probes.
+async fn watch_health(
+    id: &str,
+    ctrl_port: Option<u16>,
+    config: &SupervisorConfig,
+    statuses: &StatusMap,
+) {
+    let Some(port) = ctrl_port else {
+        return std::future::pending().await;
+    };
+    let interval = Duration::from_secs(config.health_check_interval_secs.max(1));
+    let startup_timeout = Duration::from_secs(config.startup_timeout_secs);
+    let started = Instant::now();
+    let mut answered = false;
+    let mut failures = 0;
+
+    loop {
+        sleep(interval).await;
+        let status = match probe_daemon(port, interval).await {
+            DaemonProbe::Ready => ProcessStatus::Ready,
+            DaemonProbe::Alive => ProcessStatus::Starting,
+            DaemonProbe::Unreachable if !answered && started.elapsed() < startup_timeout => {
+                continue
+            }
+            DaemonProbe::Unreachable => {
+                failures += 1;
+                if failures >= config.liveness_failures.max(1) {
+                    warn!("{} failed its liveness check", id);
+                    return;
+                }
+                update_info(statuses, id, |info| info.status = ProcessStatus::Unhealthy).await;
+                continue;
+            }
+        };
+        answered = true;
+        failures = 0;
+        update_info(statuses, id, |info| info.status = status).await;
+    }
+}
+
+/// Kill a child process and wait for it to exit
+async fn kill_child(id: &str, child: &mut Child, handles: Vec<JoinHandle<()>>) {
+    info!("Terminating {}", id);
+    for handle in handles {
+        // drop all stdout/in handlers
+        handle.abort();
+    }
+
+    if let Err(e) = child.kill().await {
+        error!("Failed to kill process {}: {}", id, e);
+    }
+
+    info!("Terminating process: {}...", id);
+    let _ = child.wait().await;
+    info!("Process {} terminated.", id);
+}
+
+/// Why a supervised process is no longer running
+enum Outcome {
+    Exited(Option<ExitStatus>),
+    Unhealthy,
+    Stopped,
+}
+
+/// Run a node and restart it according to its restart policy until `stop` fires
+async fn supervise(
+    id: String,
+    command: NodeCommand,
+    log: SharedLog,
+    statuses: StatusMap,
+    mut stop: oneshot::Receiver<()>,
+) {
+    let mut backoff = RestartBackoff::new(&command.supervisor);
+    loop {
+        let (program, args) = command.params.clone();
+        let started = Instant::now();
+        let exit = match run_command(&id, &program, args, &log).await {
+            Err(e) => {
+                error!("Failed to start {}: {}", id, e);
+                None
+            }
+            std::result::Result::Ok((mut child, handles)) => {
+                let pid = child.id();
+                update_info(&statuses, &id, |info| {
+                    info.status = ProcessStatus::Starting;
+                    info.pid = pid;
+                    info.started_at = Some(unix_now());
+                })
+                .await;
+
+                let outcome = tokio::select! {
+                    _ = &mut stop => Outcome::Stopped,
+                    status = child.wait() => Outcome::Exited(status.ok()),
+                    _ = watch_health(&id, command.ctrl_port, &command.supervisor, &statuses) => Outcome::Unhealthy,
+                };
+                match outcome {
+                    Outcome::Stopped => {
+                        kill_child(&id, &mut child, handles).await;
+                        break;
+                    }
+                    Outcome::Unhealthy => {
+                        kill_child(&id, &mut child, handles).await;
+                        None
+                    }
+                    Outcome::Exited(status) => {
+                        // Let the forwarders flush the last lines of output
+                        for handle in handles {
+                            let _ = handle.await;
+                        }
+                        status
+                    }
+                }
+            }
+        };
+
+        let failed = !exit.is_some_and(|status| status.success());
+        let code = exit.and_then(|status| status.code());
+        let delay = backoff.next(failed, started.elapsed());
+        update_info(&statuses, &id, |info| {
+            info.pid = None;
+            info.started_at = None;
+            info.last_exit_code = code;
+            info.status = if delay.is_some() {
+                ProcessStatus::Restarting
+            } else {
+                ProcessStatus::Exited
+            };
+        })
+        .await;
+
+        let Some(delay) = delay else {
+            warn!(
+                "{} exited with code {:?} and will not be restarted",
+                id, code
+            );
+            return;
+        };
+        warn!(
+            "{} exited with code {:?}, restarting in {}s",
+            id,
+            code,
+            delay.as_secs()
+        );
+        tokio::select! {
+            _ = &mut stop => break,
+            _ = sleep(delay) => {}
+        }
+        update_info(&statuses, &id, |info| info.restarts += 1).await;
+    }
+
+    update_info(&statuses, &id, |info| {
+        info.status = ProcessStatus::Stopped;
+        info.pid = None;
+        info.started_at = None;
+    })
+    .await;
+}
+
 /// Run commands as child processes and set up output forwarding
-async fn run_commands(commands: &CommandMap, processes: &ProcessMap) -> Result<()> {
-    let commands = commands.clone();
-    for (id, (program, args)) in commands {
-        let record = run_command(&id, &program, args).await?;
-
-        // Store the process
-        let mut processes_guard = processes.lock().await;
-        processes_guard.insert(id, record);
+async fn run_commands(
+    commands: &CommandMap,
+    processes: &ProcessMap,
+    statuses: &StatusMap,
+) -> Result<()> {
+    for id in commands.keys() {
+        start(id, commands, processes, statuses).await?;
     }
     Ok(())
 }
 
-/// Start a process
-async fn start(id: &str, commands: &CommandMap, processes: &ProcessMap) -> Result<()> {
-    if processes.lock().await.contains_key(id) {
+/// Start supervising a process
+async fn start(
+    id: &str,
+    commands: &CommandMap,
+    processes: &ProcessMap,
+    statuses: &StatusMap,
+) -> Result<()> {
+    let mut processes_guard = processes.lock().await;
+    // A supervisor whose process exited for good can be replaced
+    if processes_guard
+        .get(id)
+        .is_some_and(|record| !record.task.is_finished())
+    {
         bail!("Process {} already running!", id);
     }
     let Some(command) = commands.get(id) else {
         bail!("Bad command {}", id);
     };
 
-    let (program, args) = command.clone();
-    let record = run_command(id, &program, args).await?;
-    let mut processes_guard = processes.lock().await;
-    processes_guard.insert(id.to_owned(), record);
+    let supervisor = &command.supervisor;
+    let log = RotatingLog::open(
+        &command.log_file,
+        supervisor.log_max_bytes,
+        supervisor.log_max_files,
+    )
+    .with_context(|| format!("Could not open log file {}", command.log_file.display()))?
+    .shared();
+    let (stop, stopped) = oneshot::channel();
+    let task = tokio::spawn(supervise(
+        id.to_owned(),
+        command.clone(),
+        log,
+        statuses.clone(),
+        stopped,
+    ));
+    processes_guard.insert(id.to_owned(), ProcessRecord { stop, task });
 
     Ok(())
 }
 
-/// Start a process
+/// Stop a process
 async fn stop(id: &str, processes: &ProcessMap) -> Result<()> {
     warn!("stopping {}...", id);
-    let mut processes_lock = processes.lock().await;
-    if !processes_lock.contains_key(id) {
+    let Some(process_record) = processes.lock().await.remove(id) else {
         info!("Cannot stop process that isn't running {}", id);
         return Ok(());
     };
-    if let Some(mut process_record) = processes_lock.remove(id) {
-        terminate_process_record(id, &mut process_record).await;
-    }
+    terminate_process_record(process_record).await;
     Ok(())
 }
 
-/// Terminate a process
-async fn terminate_process_record(id: &str, process_record: &mut ProcessRecord) {
-    info!("Terminating {}", id);
-    let (child, handlers) = process_record;
-    for handler in handlers.drain(..) {
-        // drop all stdout/in handlers
-        handler.abort();
-    }
-
-    if let Err(e) = child.kill().await {
-        error!("Failed to kill process {}: {}", id, e);
-    }
-
-    info!("Terminating process: {}...", id);
-    let _ = child.wait().await;
-    info!("Process {} terminated.", id);
+/// Stop supervising a process and wait for it to be terminated
+async fn terminate_process_record(process_record: ProcessRecord) {
+    let ProcessRecord { stop, task } = process_record;
+    let _ = stop.send(());
+    let _ = task.await;
 }
 
 /// Terminate all processes
 async fn terminate_processes(processes: &ProcessMap) {
     info!("starting to terminate processes...");
-    let keys: Vec<String> = {
-        processes
-            .lock()
-            .await
-            .keys()
-            .map(|k| k.to_string())
-            .collect()
-    };
-
-    let mut processes_guard = processes.lock().await;
-    for id in keys {
-        if let Some(mut process_record) = processes_guard.remove(&id) {
-            terminate_process_record(&id, &mut process_record).await;
-        }
+    let records: Vec<ProcessRecord> = processes
+        .lock()
+        .await
+        .drain()
+        .map(|(_, record)| record)
+        .collect();
+
+    for record in records {
+        terminate_process_record(record).await;
     }
 }
 
@@ -203,16 +445,22 @@ fn setup_signal_handlers(manager: &ProcessManager) -> JoinHandle<()> {
 pub struct ProcessManager {
     commands: CommandMap,
     processes: ProcessMap,
+    statuses: StatusMap,
 }
 
 impl ProcessManager {
+    /// Manage additional commands. Commands already managed under the same id are replaced.
+    pub fn add(&mut self, commands: CommandMap) {
+        self.commands.extend(commands);
+    }
+
     pub async fn start_all(&self) -> Result<()> {
-        run_commands(&self.commands, &self.processes).await?;
+        run_commands(&self.commands, &self.processes, &self.statuses).await?;
         Ok(())
     }
 
     pub async fn start(&self, id: &str) -> Result<()> {
-        start(id, &self.commands, &self.processes).await?;
+        start(id, &self.commands, &self.processes, &self.statuses).await?;
         Ok(())
     }
 
@@ -223,7 +471,7 @@ impl ProcessManager {
 
     pub async fn restart(&self, id: &str) -> Result<()> {
         stop(id, &self.processes).await?;
-        start(id, &self.commands, &self.processes).await?;
+        start(id, &self.commands, &self.processes, &self.statuses).await?;
         Ok(())
     }
 
@@ -236,13 +484,13 @@ impl ProcessManager {
         terminate_processes_and_exit(&self.processes).await;
     }
 
-    pub async fn status(&self, id: &str) -> ProcessStatus {
-        let processes = self.processes.lock().await;
-        if processes.contains_key(id) {
-            ProcessStatus::Started
-        } else {
-            ProcessStatus::Stopped
-        }
+    pub async fn status(&self, id: &str) -> ProcessInfo {
+        self.statuses
+            .lock()
+            .await
+            .get(id)
+            .cloned()
+            .unwrap_or_default()
     }
 
     pub async fn list(&self) -> SwarmStatus {
@@ -260,9 +508,11 @@ impl From<CommandMap> for ProcessManager {
     fn from(value: CommandMap) -> Self {
         // TODO: should probably implement a singleton pattern here but rn it doesn't matter
         let processes = Arc::new(Mutex::new(HashMap::new()));
+        let statuses = Arc::new(Mutex::new(HashMap::new()));
         let manager = Self {
             commands: value,
             processes,
+            statuses,
         };
 
         setup_signal_handlers(&manager);
@@ -270,3 +520,56 @@ impl From<CommandMap> for ProcessManager {
         manager
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(restart: RestartPolicy) -> SupervisorConfig {
+        SupervisorConfig {
+            restart,
+            backoff_initial_secs: 1,
+            backoff_max_secs: 5,
+            backoff_reset_secs: 60,
+            ..SupervisorConfig::default()
+        }
+    }
+
+    const SHORT: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn restart_policies() {
+        let mut never = RestartBackoff::new(&config(RestartPolicy::Never));
+        assert_eq!(never.next(true, SHORT), None);
+
+        let mut on_failure = RestartBackoff::new(&config(RestartPolicy::OnFailure));
+        assert_eq!(on_failure.next(false, SHORT), None);
+        assert!(on_failure.next(true, SHORT).is_some());
+
+        let mut always = RestartBackoff::new(&config(RestartPolicy::Always));
+        assert!(always.next(false, SHORT).is_some());
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max_and_resets_after_a_long_run() {
+        let mut backoff = RestartBackoff::new(&config(RestartPolicy::Always));
+        let delays: Vec<u64> = (0..5)
+            .map(|_| backoff.next(true, SHORT).unwrap().as_secs())
+            .collect();
+        assert_eq!(delays, vec![1, 2, 4, 5, 5]);
+
+        let delay = backoff.next(true, Duration::from_secs(60)).unwrap();
+        assert_eq!(delay, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn gives_up_after_max_restarts() {
+        let mut backoff = RestartBackoff::new(&SupervisorConfig {
+            max_restarts: Some(2),
+            ..config(RestartPolicy::OnFailure)
+        });
+        assert!(backoff.next(true, SHORT).is_some());
+        assert!(backoff.next(true, SHORT).is_some());
+        assert_eq!(backoff.next(true, SHORT), None);
+    }
+}
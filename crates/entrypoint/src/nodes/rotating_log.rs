@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use anyhow::*;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Output log shared by the stdout and stderr forwarders of a node
+pub type SharedLog = Arc<Mutex<RotatingLog>>;
+
+/// Append-only log file that is rotated once it reaches `max_bytes`. Rotated files are kept as
+/// `<file>.1` (newest) to `<file>.<max_files>` (oldest).
+#[derive(Debug)]
+pub struct RotatingLog {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: u32,
+    file: File,
+    len: u64,
+}
+
+impl RotatingLog {
+    pub fn open(path: &Path, max_bytes: u64, max_files: u32) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_owned(),
+            max_bytes,
+            max_files,
+            file,
+            len,
+        })
+    }
+
+    pub fn shared(self) -> SharedLog {
+        Arc::new(Mutex::new(self))
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        if self.len > 0 && self.len + bytes.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(bytes)?;
+        self.len += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn rotated(&self, index: u32) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        if self.max_files > 0 {
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated(index);
+                if from.exists() {
+                    fs::rename(&from, self.rotated(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated(1))?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.len = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn rotates_and_keeps_max_files() -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("node/output.log");
+        let mut log = RotatingLog::open(&path, 10, 2)?;
+
+        for line in ["aaaaaaaa\n", "bbbbbbbb\n", "cccccccc\n", "dddddddd\n"] {
+            log.write(line.as_bytes())?;
+        }
+
+        assert_eq!(fs::read_to_string(&path)?, "dddddddd\n");
+        assert_eq!(fs::read_to_string(log.rotated(1))?, "cccccccc\n");
+        assert_eq!(fs::read_to_string(log.rotated(2))?, "bbbbbbbb\n");
+        assert!(!log.rotated(3).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn appends_to_an_existing_log() -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("output.log");
+        RotatingLog::open(&path, 100, 1)?.write(b"first\n")?;
+        RotatingLog::open(&path, 100, 1)?.write(b"second\n")?;
+
+        assert_eq!(fs::read_to_string(&path)?, "first\nsecond\n");
+        Ok(())
+    }
+}
@@ -4,9 +4,10 @@
 // without even the implied warranty of MERCHANTABILITY
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
-use crate::nodes::daemon;
+use crate::nodes::{cluster::ClusterSpec, daemon};
 use anyhow::*;
 use e3_config::AppConfig;
+use std::path::Path;
 use tracing::instrument;
 
 use super::client;
@@ -25,7 +26,7 @@ pub async fn execute(
     }
 
     if detach {
-        client::start_daemon(verbose, &maybe_config_string, &exclude).await?;
+        client::start_daemon(verbose, &maybe_config_string, None, &exclude).await?;
         return Ok(());
     }
 
@@ -34,3 +35,29 @@ pub async fn execute(
 
     Ok(())
 }
+
+/// Bring up the development cluster described by a cluster file
+#[instrument(skip_all)]
+pub async fn execute_cluster(
+    cluster_file: &Path,
+    force: bool,
+    detach: bool,
+    exclude: Vec<String>,
+    verbose: u8,
+    maybe_otel: Option<String>,
+) -> Result<()> {
+    if client::is_ready().await? {
+        bail!("Swarm is already running!");
+    }
+
+    if detach {
+        // Check before detaching so the error reaches the user instead of the daemon log
+        ClusterSpec::load(cluster_file)?.ensure_resettable(force)?;
+        client::start_daemon(verbose, &None, Some((cluster_file, force)), &exclude).await?;
+        return Ok(());
+    }
+
+    daemon::execute_cluster(cluster_file, force, exclude, verbose, maybe_otel).await?;
+
+    Ok(())
+}
@@ -6,9 +6,10 @@
 
 use anyhow::{Context, Result};
 use e3_ciphernode_builder::{CiphernodeBuilder, CiphernodeHandle};
-use e3_config::AppConfig;
+use e3_config::{AppConfig, SortitionPolicyConfig};
 use e3_crypto::Cipher;
-use e3_zk_prover::ZkBackend;
+use e3_sortition::ReputationWeights;
+use e3_zk_prover::{RemoteProverPool, ZkBackend};
 use rand::SeedableRng;
 use rand_chacha::ChaCha20Rng;
 use std::sync::{Arc, Mutex};
@@ -20,7 +21,12 @@ pub async fn execute(config: &AppConfig) -> Result<CiphernodeHandle> {
         ChaCha20Rng::try_from_os_rng().context("failed to seed ChaCha20 RNG from OS")?,
     ));
     let cipher = Arc::new(Cipher::from_file(&config.key_file()).await?);
-    let backend = ZkBackend::new(config.bb_binary(), config.circuits_dir(), config.work_dir());
+    let mut backend = ZkBackend::new(config.bb_binary(), config.circuits_dir(), config.work_dir());
+    if let Some(workers) = config.prover_workers() {
+        let pool = RemoteProverPool::from_config(workers);
+        info!("ZK proving dispatched to prover workers: {:?}", pool.urls());
+        backend = backend.with_remote_workers(pool);
+    }
 
     let reserve = config.multithread_reserve_threads();
     let concurrent_jobs = config.multithread_concurrent_jobs();
@@ -31,9 +37,8 @@ pub async fn execute(config: &AppConfig) -> Result<CiphernodeHandle> {
             .unwrap_or_else(|| "auto (CPUs - reserve)".to_string())
     );
 
-    let node = CiphernodeBuilder::new(rng.clone(), cipher.clone())
+    let mut builder = CiphernodeBuilder::new(rng.clone(), cipher.clone())
         .with_persistence(&config.log_file(), &config.db_file())
-        .with_sortition_score()
         .with_chains(config.chains())
         .with_contract_interfold_full()
         .with_contract_bonding_registry()
@@ -42,13 +47,38 @@ pub async fn execute(config: &AppConfig) -> Result<CiphernodeHandle> {
         .with_contract_slashing_manager()
         .with_trbfv()
         .with_zkproof(backend)
+        .with_bb_engine(config.bb_engine())
         .with_pubkey_aggregation()
         .with_threshold_plaintext_aggregation()
         .with_net(config.peers(), config.quic_port())
+        .with_net_transport(config.transport().clone())
+        .with_net_discovery(config.discovery().clone())
+        .with_net_limits(config.net_limits().clone())
+        .with_peer_allowlist(config.peer_allowlist())
         .with_shared_store()
-        .with_shared_eventstore()
-        .build()
-        .await?;
+        .with_shared_eventstore();
+    let sortition = config.sortition();
+    builder = match sortition.policy {
+        SortitionPolicyConfig::Score => builder.with_sortition_score(),
+        SortitionPolicyConfig::Diverse { max_per_group } => {
+            builder.with_sortition_diverse(max_per_group)
+        }
+        SortitionPolicyConfig::Reputation {
+            slash_penalty_bps,
+            expulsion_penalty_bps,
+        } => builder.with_sortition_reputation(ReputationWeights {
+            slash_penalty_bps,
+            expulsion_penalty_bps,
+        }),
+    }
+    .with_operator_groups(sortition.operator_groups.clone());
+    info!("Sortition policy: {:?}", sortition.policy);
+    if let Some(timeout) = config.aggregator_failover_timeout() {
+        info!("Aggregator failover after {timeout:?} without on-chain progress");
+        builder = builder.with_aggregator_failover(timeout);
+    }
+
+    let node = builder.build().await?;
 
     Ok(node)
 }
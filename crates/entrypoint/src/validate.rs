@@ -195,7 +195,7 @@ pub async fn validate_node(config: &AppConfig) -> Result<ValidationReport> {
 /// The set of aggregate ids to inspect: the local aggregate (0) plus one per
 /// configured chain. Mirrors [`AggregateId::from_chain_id`] so the validator
 /// looks at exactly the aggregates the running node persists.
-fn aggregate_ids(config: &AppConfig) -> Vec<AggregateId> {
+pub(crate) fn aggregate_ids(config: &AppConfig) -> Vec<AggregateId> {
     let mut ids: Vec<AggregateId> = vec![AggregateId::new(0)];
     for chain in config.chains() {
         let id = AggregateId::from_chain_id(chain.chain_id);
@@ -425,7 +425,7 @@ fn collect_terminal_keys(events: &[InterfoldEvent], out: &mut HashSet<String>) {
 
 /// Read every event for a single aggregate from sequence 0, paginating until the
 /// store is exhausted.
-async fn read_all_events(
+pub(crate) async fn read_all_events(
     eventstore: &EventStoreReader,
     aggregate: AggregateId,
 ) -> Result<Vec<InterfoldEvent>> {
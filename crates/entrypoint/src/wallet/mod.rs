@@ -6,3 +6,4 @@
 
 pub mod get;
 pub mod set;
+pub mod signer;
@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use crate::helpers::datastore::get_repositories;
+use alloy::signers::local::PrivateKeySigner;
+use anyhow::{Context, Result};
+use e3_config::AppConfig;
+use e3_crypto::Cipher;
+use e3_evm::{load_signer_from_repository, EthPrivateKeyRepositoryFactory};
+
+/// The node wallet, for commands that sign artifacts outside of a running node.
+pub async fn execute(config: &AppConfig) -> Result<PrivateKeySigner> {
+    let repositories = get_repositories(config)?;
+    let cipher = Cipher::from_file(config.key_file()).await?;
+    load_signer_from_repository(repositories.eth_private_key(), &cipher)
+        .await
+        .context("No wallet has been set.")
+}
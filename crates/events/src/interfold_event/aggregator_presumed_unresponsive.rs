@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use crate::E3id;
+use actix::Message;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Local liveness signal that the aggregator at `party_id` missed its deadline for `e3_id`.
+///
+/// Emitted by the `FailoverMonitor` (or the simulator standing in for it) and consumed by the
+/// `CiphernodeSelector`, which records the party as unresponsive and re-evaluates which
+/// committee member should act as aggregator.
+/// This event is never gossiped; every node reaches the decision from its own view of time.
+#[derive(Message, Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[rtype(result = "()")]
+pub struct AggregatorPresumedUnresponsive {
+    pub e3_id: E3id,
+    pub party_id: u64,
+}
+
+impl Display for AggregatorPresumedUnresponsive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AggregatorPresumedUnresponsive {{ e3_id: {}, party_id: {} }}",
+            self.e3_id, self.party_id
+        )
+    }
+}
@@ -22,6 +22,8 @@ pub struct CiphernodeSelected {
     pub params_preset: BfvPreset,
     pub params: ArcBytes,
     pub party_id: u64,
+    /// Committee operator addresses in party-id order.
+    pub committee: Vec<String>,
 }
 
 impl Default for CiphernodeSelected {
@@ -35,6 +37,7 @@ impl Default for CiphernodeSelected {
             seed: Seed([0u8; 32]),
             threshold_m: 0,
             threshold_n: 0,
+            committee: Vec::new(),
         }
     }
 }
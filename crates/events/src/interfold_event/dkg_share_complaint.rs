@@ -0,0 +1,342 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Complaint/justification phase of the DKG.
+//!
+//! Once a node has verified the threshold shares addressed to it, it broadcasts a signed
+//! [`DkgShareComplaints`] ballot listing every dealer it complains about (an empty list means
+//! it is satisfied). Complaints about invalid proofs carry the dealer's own signed proofs as
+//! evidence; complaints about a missing share can be answered by the accused dealer with a
+//! signed [`DkgShareJustification`] that reveals the share and its proofs to the whole
+//! committee. Dealers that cannot be cleared are announced via [`DkgDealerDisqualified`], which
+//! escalates them to an accusation; only the resulting on-chain expulsion removes them from the
+//! key.
+
+use crate::{E3id, SignedProofPayload, ThresholdShare};
+use actix::Message;
+use alloy::primitives::{keccak256, Address, Signature, B256, U256};
+use alloy::signers::{local::PrivateKeySigner, SignerSync};
+use alloy::sol_types::SolValue;
+use anyhow::{anyhow, Result};
+use derivative::Derivative;
+use e3_utils::utility_types::ArcBytes;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display},
+    sync::Arc,
+};
+
+/// Why a receiver complains about a dealer.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DkgComplaintReason {
+    /// No share (or an incomplete proof set) arrived from the dealer before the deadline.
+    MissingShare = 0,
+    /// The dealer's signed C2/C3 proofs failed verification.
+    InvalidShareProofs = 1,
+}
+
+impl fmt::Display for DkgComplaintReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A single complaint against a dealer.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DkgComplaint {
+    /// Party id of the accused dealer.
+    pub accused_party_id: u64,
+    pub reason: DkgComplaintReason,
+    /// The dealer's signed proofs backing an `InvalidShareProofs` complaint. Empty for
+    /// `MissingShare`.
+    pub evidence: Vec<SignedProofPayload>,
+}
+
+/// Signed complaint ballot broadcast by every committee member after share verification.
+#[derive(Message, Derivative, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[rtype(result = "()")]
+#[derivative(Debug)]
+pub struct DkgShareComplaints {
+    pub e3_id: E3id,
+    /// Party id of the complaining node.
+    pub party_id: u64,
+    /// Complaints against dealers. Empty when the node accepts every share it received.
+    pub complaints: Vec<DkgComplaint>,
+    /// 65-byte ECDSA signature over [`DkgShareComplaints::digest`].
+    #[derivative(Debug(format_with = "e3_utils::formatters::hexf"))]
+    pub signature: ArcBytes,
+}
+
+impl DkgShareComplaints {
+    /// `keccak256("DkgShareComplaints(uint256 chainId,uint256 e3Id,uint256 partyId,bytes32 complaintsHash)")`
+    pub fn typehash() -> [u8; 32] {
+        keccak256(
+            "DkgShareComplaints(uint256 chainId,uint256 e3Id,uint256 partyId,bytes32 complaintsHash)",
+        )
+        .into()
+    }
+
+    /// Digest committing to the complaining party and every complaint, including the digests
+    /// of the attached evidence.
+    pub fn digest(e3_id: &E3id, party_id: u64, complaints: &[DkgComplaint]) -> Result<[u8; 32]> {
+        let e3_id_u256: U256 = e3_id
+            .clone()
+            .try_into()
+            .map_err(|_| anyhow!("E3id cannot be converted to U256"))?;
+
+        let entries = complaints
+            .iter()
+            .map(|c| {
+                let evidence = c
+                    .evidence
+                    .iter()
+                    .map(|p| p.payload.digest().map(B256::from))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((
+                    U256::from(c.accused_party_id),
+                    U256::from(c.reason as u8),
+                    evidence,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let encoded = (
+            Self::typehash(),
+            U256::from(e3_id.chain_id()),
+            e3_id_u256,
+            U256::from(party_id),
+            keccak256(entries.abi_encode()),
+        )
+            .abi_encode();
+
+        Ok(keccak256(&encoded).into())
+    }
+
+    /// Build and sign a complaint ballot with the node's ECDSA key.
+    pub fn sign(
+        e3_id: E3id,
+        party_id: u64,
+        complaints: Vec<DkgComplaint>,
+        signer: &PrivateKeySigner,
+    ) -> Result<Self> {
+        let digest = Self::digest(&e3_id, party_id, &complaints)?;
+        let sig = signer
+            .sign_message_sync(&digest)
+            .map_err(|e| anyhow!("Failed to sign share complaints: {e}"))?;
+
+        Ok(Self {
+            e3_id,
+            party_id,
+            complaints,
+            signature: ArcBytes::from_bytes(&sig.as_bytes()),
+        })
+    }
+
+    /// Recover the Ethereum address that signed this ballot.
+    pub fn recover_address(&self) -> Result<Address> {
+        let sig = Signature::try_from(&self.signature[..])
+            .map_err(|e| anyhow!("Invalid signature: {e}"))?;
+        let digest = Self::digest(&self.e3_id, self.party_id, &self.complaints)?;
+        sig.recover_address_from_msg(digest)
+            .map_err(|e| anyhow!("Failed to recover address: {e}"))
+    }
+}
+
+impl Display for DkgShareComplaints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DkgShareComplaints {{ e3_id: {}, party_id: {}, complaints: {} }}",
+            self.e3_id,
+            self.party_id,
+            self.complaints.len()
+        )
+    }
+}
+
+/// A dealer's public answer to a `MissingShare` complaint: the share it dealt to the accuser
+/// together with its signed proofs, so every committee member can check it.
+///
+/// The dealer signs the share together with its proofs, so a relayed justification cannot
+/// pair the dealer's proofs with a different share.
+#[derive(Message, Derivative, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[rtype(result = "()")]
+#[derivative(Debug)]
+pub struct DkgShareJustification {
+    pub e3_id: E3id,
+    /// Party id of the node that complained.
+    pub accuser_party_id: u64,
+    /// The share the dealer extracted for the accuser.
+    pub share: Arc<ThresholdShare>,
+    pub signed_c2a_proof: Option<SignedProofPayload>,
+    pub signed_c2b_proof: Option<SignedProofPayload>,
+    pub signed_c3a_proofs: Vec<SignedProofPayload>,
+    pub signed_c3b_proofs: Vec<SignedProofPayload>,
+    /// 65-byte ECDSA signature of the dealer over [`DkgShareJustification::digest`].
+    #[derivative(Debug(format_with = "e3_utils::formatters::hexf"))]
+    pub signature: ArcBytes,
+}
+
+impl DkgShareJustification {
+    /// `keccak256("DkgShareJustification(uint256 chainId,uint256 e3Id,uint256 dealerPartyId,uint256 accuserPartyId,bytes32 shareHash,bytes32 proofsHash)")`
+    pub fn typehash() -> [u8; 32] {
+        keccak256(
+            "DkgShareJustification(uint256 chainId,uint256 e3Id,uint256 dealerPartyId,uint256 accuserPartyId,bytes32 shareHash,bytes32 proofsHash)",
+        )
+        .into()
+    }
+
+    /// Digest committing to the revealed share and the digests of every attached proof.
+    pub fn digest(&self) -> Result<[u8; 32]> {
+        let e3_id_u256: U256 = self
+            .e3_id
+            .clone()
+            .try_into()
+            .map_err(|_| anyhow!("E3id cannot be converted to U256"))?;
+        let share = bincode::serialize(self.share.as_ref())
+            .map_err(|e| anyhow!("Failed to encode justified share: {e}"))?;
+        let proofs = self
+            .signed_proofs()
+            .iter()
+            .map(|p| p.payload.digest().map(B256::from))
+            .collect::<Result<Vec<_>>>()?;
+
+        let encoded = (
+            Self::typehash(),
+            U256::from(self.e3_id.chain_id()),
+            e3_id_u256,
+            U256::from(self.dealer_party_id()),
+            U256::from(self.accuser_party_id),
+            keccak256(share),
+            keccak256(proofs.abi_encode()),
+        )
+            .abi_encode();
+
+        Ok(keccak256(&encoded).into())
+    }
+
+    /// Sign the justification with the dealer's ECDSA key, replacing any previous signature.
+    pub fn sign(mut self, signer: &PrivateKeySigner) -> Result<Self> {
+        let digest = self.digest()?;
+        let sig = signer
+            .sign_message_sync(&digest)
+            .map_err(|e| anyhow!("Failed to sign share justification: {e}"))?;
+        self.signature = ArcBytes::from_bytes(&sig.as_bytes());
+        Ok(self)
+    }
+
+    /// Recover the Ethereum address that signed this justification.
+    pub fn recover_address(&self) -> Result<Address> {
+        let sig = Signature::try_from(&self.signature[..])
+            .map_err(|e| anyhow!("Invalid signature: {e}"))?;
+        sig.recover_address_from_msg(self.digest()?)
+            .map_err(|e| anyhow!("Failed to recover address: {e}"))
+    }
+
+    /// Party id of the dealer revealing the share.
+    pub fn dealer_party_id(&self) -> u64 {
+        self.share.party_id
+    }
+
+    /// All signed proofs, in C2a, C2b, C3a…, C3b… order.
+    pub fn signed_proofs(&self) -> Vec<SignedProofPayload> {
+        self.signed_c2a_proof
+            .iter()
+            .chain(self.signed_c2b_proof.iter())
+            .chain(self.signed_c3a_proofs.iter())
+            .chain(self.signed_c3b_proofs.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+impl Display for DkgShareJustification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DkgShareJustification {{ e3_id: {}, dealer: {}, accuser: {} }}",
+            self.e3_id,
+            self.dealer_party_id(),
+            self.accuser_party_id
+        )
+    }
+}
+
+/// Local verdict of the complaint phase against a dealer.
+///
+/// Nodes can reach different verdicts, so this only escalates the dealer to the slashing
+/// `AccusationManager`. The dealer's contribution is dropped once it is expelled on-chain.
+#[derive(Message, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[rtype(result = "()")]
+pub struct DkgDealerDisqualified {
+    pub e3_id: E3id,
+    pub party_id: u64,
+    /// Operator address of the disqualified dealer.
+    pub address: Address,
+    pub reason: DkgComplaintReason,
+    /// Party ids whose complaints led to the disqualification.
+    pub accusers: BTreeSet<u64>,
+}
+
+impl Display for DkgDealerDisqualified {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complaints_sign_and_recover_roundtrip() {
+        let signer = PrivateKeySigner::random();
+        let complaints = vec![DkgComplaint {
+            accused_party_id: 2,
+            reason: DkgComplaintReason::MissingShare,
+            evidence: Vec::new(),
+        }];
+        let ballot = DkgShareComplaints::sign(E3id::new("1", 42), 0, complaints, &signer)
+            .expect("signing should succeed");
+        assert_eq!(ballot.recover_address().unwrap(), signer.address());
+
+        let mut tampered = ballot.clone();
+        tampered.complaints.clear();
+        assert_ne!(tampered.recover_address().unwrap(), signer.address());
+    }
+
+    #[test]
+    fn justification_signature_binds_the_share() {
+        let signer = PrivateKeySigner::random();
+        let share = |pk: u8| {
+            Arc::new(ThresholdShare {
+                party_id: 3,
+                pk_share: ArcBytes::from_bytes(&[pk]),
+                sk_sss: Default::default(),
+                esi_sss: Vec::new(),
+            })
+        };
+        let justification = DkgShareJustification {
+            e3_id: E3id::new("1", 42),
+            accuser_party_id: 0,
+            share: share(1),
+            signed_c2a_proof: None,
+            signed_c2b_proof: None,
+            signed_c3a_proofs: Vec::new(),
+            signed_c3b_proofs: Vec::new(),
+            signature: ArcBytes::default(),
+        }
+        .sign(&signer)
+        .expect("signing should succeed");
+        assert_eq!(justification.recover_address().unwrap(), signer.address());
+
+        let mut swapped = justification.clone();
+        swapped.share = share(2);
+        assert_ne!(swapped.recover_address().unwrap(), signer.address());
+    }
+}
@@ -9,6 +9,7 @@ mod accusation_vote;
 mod aggregation_proof_pending;
 mod aggregation_proof_signed;
 mod aggregator_changed;
+mod aggregator_presumed_unresponsive;
 mod ciphernode_added;
 mod ciphernode_removed;
 mod ciphernode_selected;
@@ -28,6 +29,7 @@ mod die;
 mod dkg_fold_attestation;
 mod dkg_inner_proof_ready;
 mod dkg_recursive_aggregation_complete;
+mod dkg_share_complaint;
 mod e3_failed;
 mod e3_request_complete;
 mod e3_requested;
@@ -76,6 +78,7 @@ pub use accusation_vote::*;
 pub use aggregation_proof_pending::*;
 pub use aggregation_proof_signed::*;
 pub use aggregator_changed::*;
+pub use aggregator_presumed_unresponsive::*;
 pub use ciphernode_added::*;
 pub use ciphernode_removed::*;
 pub use ciphernode_selected::*;
@@ -95,6 +98,7 @@ pub use die::*;
 pub use dkg_fold_attestation::*;
 pub use dkg_inner_proof_ready::*;
 pub use dkg_recursive_aggregation_complete::*;
+pub use dkg_share_complaint::*;
 pub use e3_failed::*;
 pub use e3_request_complete::*;
 pub use e3_requested::*;
@@ -240,6 +244,7 @@ pub enum InterfoldEventData {
     AccusationQuorumReached(AccusationQuorumReached),
     AccusationVote(AccusationVote),
     AggregatorChanged(AggregatorChanged),
+    AggregatorPresumedUnresponsive(AggregatorPresumedUnresponsive),
     ProofFailureAccusation(ProofFailureAccusation),
     ProofVerificationFailed(ProofVerificationFailed),
     ProofVerificationPassed(ProofVerificationPassed),
@@ -288,6 +293,9 @@ pub enum InterfoldEventData {
     ShareVerificationComplete(ShareVerificationComplete),
     SlashExecuted(SlashExecuted),
     CommitteeMemberExpelled(CommitteeMemberExpelled),
+    DkgShareComplaints(DkgShareComplaints),
+    DkgShareJustification(DkgShareJustification),
+    DkgDealerDisqualified(DkgDealerDisqualified),
     OutgoingSyncRequested(OutgoingSyncRequested),
     HistoricalEvmSyncStart(HistoricalEvmSyncStart),
     HistoricalNetSyncStart(HistoricalNetSyncStart),
@@ -546,6 +554,9 @@ impl InterfoldEventData {
             InterfoldEventData::AccusationQuorumReached(ref data) => Some(data.e3_id.clone()),
             InterfoldEventData::AccusationVote(ref data) => Some(data.e3_id.clone()),
             InterfoldEventData::AggregatorChanged(ref data) => Some(data.e3_id.clone()),
+            InterfoldEventData::AggregatorPresumedUnresponsive(ref data) => {
+                Some(data.e3_id.clone())
+            }
             InterfoldEventData::ProofFailureAccusation(ref data) => Some(data.e3_id.clone()),
             InterfoldEventData::ProofVerificationFailed(ref data) => Some(data.e3_id.clone()),
             InterfoldEventData::ProofVerificationPassed(ref data) => Some(data.e3_id.clone()),
@@ -580,6 +591,9 @@ impl InterfoldEventData {
             InterfoldEventData::ShareVerificationComplete(ref data) => Some(data.e3_id.clone()),
             InterfoldEventData::SlashExecuted(ref data) => Some(data.e3_id.clone()),
             InterfoldEventData::CommitteeMemberExpelled(ref data) => Some(data.e3_id.clone()),
+            InterfoldEventData::DkgShareComplaints(ref data) => Some(data.e3_id.clone()),
+            InterfoldEventData::DkgShareJustification(ref data) => Some(data.e3_id.clone()),
+            InterfoldEventData::DkgDealerDisqualified(ref data) => Some(data.e3_id.clone()),
             InterfoldEventData::E3Failed(ref data) => Some(data.e3_id.clone()),
             InterfoldEventData::E3StageChanged(ref data) => Some(data.e3_id.clone()),
             InterfoldEventData::DecryptionShareProofSigned(ref data) => Some(data.e3_id.clone()),
@@ -629,6 +643,7 @@ impl_event_types!(
     AccusationQuorumReached,
     AccusationVote,
     AggregatorChanged,
+    AggregatorPresumedUnresponsive,
     ProofFailureAccusation,
     ProofVerificationFailed,
     ProofVerificationPassed,
@@ -678,6 +693,9 @@ impl_event_types!(
     ShareVerificationComplete,
     SlashExecuted,
     CommitteeMemberExpelled,
+    DkgShareComplaints,
+    DkgShareJustification,
+    DkgDealerDisqualified,
     OutgoingSyncRequested,
     HistoricalEvmSyncStart,
     HistoricalNetSyncStart,
@@ -29,6 +29,9 @@ pub enum VerificationKind {
     ThresholdDecryptionProofs,
     /// C1 PK generation proof verification (after all KeyshareCreated collected).
     PkGenerationProofs,
+    /// C2/C3 re-verification of complaint evidence and dealer justifications
+    /// (after the share complaint window closes).
+    ShareComplaints,
 }
 
 /// ThresholdKeyshare → ShareVerificationActor: verify party proofs.
@@ -36,7 +39,7 @@ pub enum VerificationKind {
 pub struct ShareVerificationDispatched {
     pub e3_id: E3id,
     pub kind: VerificationKind,
-    /// C2/C3 party proofs (when kind == ShareProofs).
+    /// C2/C3 party proofs (when kind == ShareProofs or ShareComplaints).
     pub share_proofs: Vec<PartyProofsToVerify>,
     /// C4 party proofs (when kind == DecryptionProofs).
     pub decryption_proofs: Vec<PartyShareDecryptionProofsToVerify>,
@@ -49,6 +49,9 @@ pub enum ProofType {
     C6ThresholdShareDecryption = 9,
     /// C7 — Decrypted shares aggregation proof (Proof 7).
     C7DecryptedSharesAggregation = 10,
+    /// Not a circuit: a dealer left a missing-share complaint from the DKG complaint round
+    /// unanswered. The evidence is the complaint round verdict itself.
+    DkgShareWithheld = 11,
 }
 
 impl ProofType {
@@ -69,6 +72,7 @@ impl ProofType {
                 vec![CircuitName::DecryptedSharesAggregation]
             }
             ProofType::C5PkAggregation => vec![CircuitName::PkAggregation],
+            ProofType::DkgShareWithheld => Vec::new(),
         }
     }
 
@@ -86,6 +90,7 @@ impl ProofType {
             ProofType::C6ThresholdShareDecryption => "E3_BAD_DECRYPTION_PROOF",
             ProofType::C7DecryptedSharesAggregation => "E3_BAD_AGGREGATION_PROOF",
             ProofType::C5PkAggregation => "E3_BAD_PK_AGGREGATION_PROOF",
+            ProofType::DkgShareWithheld => "E3_DKG_SHARE_WITHHELD",
         }
     }
 }
@@ -57,6 +57,14 @@ impl StoreKeys {
         String::from("//libp2p/keypair")
     }
 
+    pub fn libp2p_peer_identities() -> String {
+        String::from("//libp2p/peer_identities")
+    }
+
+    pub fn libp2p_bandwidth() -> String {
+        String::from("//libp2p/bandwidth")
+    }
+
     pub fn interfold_sol_reader(chain_id: u64) -> String {
         format!("//evm_readers/interfold/{chain_id}")
     }
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Read-only lookup of the Honk verifiers the Interfold checks aggregation proofs with.
+//!
+//! The Interfold registers a `pkVerifier` and a `decryptionVerifier` per encryption scheme. The
+//! BFV ones wrap the Honk verifiers of the `dkg_aggregator` and `decryption_aggregator` circuits,
+//! whose bytecode embeds the VK hash the node's circuits must match.
+
+use crate::contracts::{IBfvVerifier, IInterfold};
+use alloy::primitives::{keccak256, Address, B256};
+use alloy::providers::Provider;
+use anyhow::{Context, Result};
+
+/// `encryptionSchemeId` the BFV verifiers are registered under.
+pub fn bfv_encryption_scheme_id() -> B256 {
+    keccak256("fhe.rs:BFV")
+}
+
+/// Honk verifiers behind the Interfold's BFV scheme verifiers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CircuitVerifiers {
+    /// Verifies `dkg_aggregator` proofs, wrapped by `pkVerifiers[BFV]`.
+    pub dkg_aggregator: Option<Address>,
+    /// Verifies `decryption_aggregator` proofs, wrapped by `decryptionVerifiers[BFV]`.
+    pub decryption_aggregator: Option<Address>,
+}
+
+/// Reads the BFV scheme verifiers of the Interfold at `interfold` and the Honk verifiers they
+/// wrap.
+pub async fn fetch_circuit_verifiers<P: Provider>(
+    provider: &P,
+    interfold: Address,
+) -> Result<CircuitVerifiers> {
+    let contract = IInterfold::new(interfold, provider);
+    let scheme = bfv_encryption_scheme_id();
+    let pk_verifier = contract
+        .pkVerifiers(scheme)
+        .call()
+        .await
+        .context("pkVerifiers failed")?;
+    let decryption_verifier = contract
+        .decryptionVerifiers(scheme)
+        .call()
+        .await
+        .context("decryptionVerifiers failed")?;
+    Ok(CircuitVerifiers {
+        dkg_aggregator: circuit_verifier(provider, pk_verifier).await,
+        decryption_aggregator: circuit_verifier(provider, decryption_verifier).await,
+    })
+}
+
+/// The Honk verifier wrapped by a scheme verifier. `None` when the scheme verifier is unset or
+/// does not wrap one, like the mock verifiers of development deployments.
+async fn circuit_verifier<P: Provider>(provider: &P, scheme_verifier: Address) -> Option<Address> {
+    if scheme_verifier == Address::ZERO {
+        return None;
+    }
+    let verifier = IBfvVerifier::new(scheme_verifier, provider)
+        .circuitVerifier()
+        .call()
+        .await
+        .ok()?;
+    (verifier != Address::ZERO).then_some(verifier)
+}
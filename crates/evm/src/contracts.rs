@@ -50,10 +50,13 @@ sol! {
 
         // ── View functions ──────────────────────────────────────────────────
         function getE3(uint256 e3Id) external view returns (E3 memory e3);
+        function decryptionVerifiers(bytes32 encryptionSchemeId) external view returns (address);
+        function pkVerifiers(bytes32 encryptionSchemeId) external view returns (address);
 
         // ── Events ──────────────────────────────────────────────────────────
         event E3Requested(uint256 e3Id, E3 e3, address indexed e3Program);
         event CiphertextOutputPublished(uint256 indexed e3Id, bytes ciphertextOutput);
+        event PlaintextOutputPublished(uint256 indexed e3Id, bytes plaintextOutput, bytes proof);
         event E3Failed(uint256 e3Id, uint8 failedAtStage, uint8 reason);
         event E3StageChanged(uint256 indexed e3Id, uint8 previousStage, uint8 newStage);
 
@@ -186,6 +189,14 @@ sol! {
             uint256 committeeDeadline
         );
 
+        event CommitteePublished(
+            uint256 indexed e3Id,
+            address[] nodes,
+            bytes publicKey,
+            bytes32 pkCommitment,
+            bytes proof
+        );
+
         event SortitionCommitteeFinalized(
             uint256 indexed e3Id,
             address[] committee,
@@ -247,3 +258,14 @@ sol! {
         );
     }
 }
+
+// ── IBfvVerifier ────────────────────────────────────────────────────────────
+
+sol! {
+    /// `BfvPkVerifier` and `BfvDecryptionVerifier` wrap a Honk verifier of the aggregation circuit.
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IBfvVerifier {
+        function circuitVerifier() external view returns (address);
+    }
+}
@@ -12,7 +12,7 @@ use alloy::sol_types::SolEvent;
 use e3_events::E3id;
 use e3_events::InterfoldEventData;
 use e3_events::{E3Failed, E3Stage, E3StageChanged, FailureReason};
-use e3_fhe_params::{encode_bfv_params, BfvParamSet, BfvPreset};
+use e3_fhe_params::{encode_bfv_params, PresetRegistry};
 use e3_trbfv::helpers::calculate_error_size;
 use e3_utils::ArcBytes;
 use e3_zk_helpers::CiphernodesCommitteeSize;
@@ -22,7 +22,10 @@ use tracing::{error, info, trace, warn};
 struct E3RequestedWithChainId(pub IInterfold::E3Requested, pub u64);
 
 impl E3RequestedWithChainId {
-    fn try_into_e3_requested(self) -> anyhow::Result<e3_events::E3Requested> {
+    fn try_into_e3_requested(
+        self,
+        registry: &PresetRegistry,
+    ) -> anyhow::Result<e3_events::E3Requested> {
         // Derive threshold values from committee size enum
         let committee_size = match self.0.e3.committeeSize {
             0 => CiphernodesCommitteeSize::Minimum,
@@ -39,22 +42,24 @@ impl E3RequestedWithChainId {
         let threshold_m = committee.threshold;
         let threshold_n = committee.n;
 
-        // Map on-chain ParamSet enum to BfvPreset
+        // Map on-chain ParamSet enum to a built-in or preset file entry
         let param_set_value = self.0.e3.paramSet;
-        let params_preset = BfvPreset::from_on_chain_param_set(param_set_value).ok_or_else(|| {
+        let registered = registry.resolve_on_chain(param_set_value).ok_or_else(|| {
             anyhow::anyhow!(
-                "Unknown ParamSet enum value {} — this node's binary does not recognize this BFV \
-                 preset (likely a version skew with the on-chain contracts). Upgrade the ciphernode \
-                 to a version that supports this preset.",
+                "Unknown ParamSet enum value {} — this node's binary does not recognize this \
+                 BFV preset (likely a version skew with the on-chain contracts, or a preset \
+                 missing from the configured preset_file). Upgrade the ciphernode to a \
+                 version that supports this preset.",
                 param_set_value
             )
         })?;
+        let params_preset = registered.preset();
 
-        // Build BFV parameters from the preset
-        let params_arc = BfvParamSet::from(params_preset).build_arc();
+        // Build BFV parameters from the registry entry
+        let (params_arc, _) = registered.build_pair()?;
         let params_bytes = encode_bfv_params(&params_arc);
 
-        let lambda = params_preset.metadata().lambda;
+        let lambda = registered.lambda();
 
         let error_size = match calculate_error_size(params_arc, threshold_n, threshold_m, lambda) {
             Ok(size) => {
@@ -193,7 +198,9 @@ pub(crate) fn extractor(
                 error!("Error parsing event E3Requested after topic matched!");
                 return None;
             };
-            match E3RequestedWithChainId(event, chain_id).try_into_e3_requested() {
+            match E3RequestedWithChainId(event, chain_id)
+                .try_into_e3_requested(PresetRegistry::global())
+            {
                 Ok(payload) => Some(payload.into()),
                 Err(e) => {
                     error!(
@@ -272,7 +279,93 @@ pub(crate) fn extractor(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy::primitives::U256;
+    use alloy::primitives::{Address, U256};
+    use e3_fhe_params::constants::{insecure_512, insecure_search_defaults};
+    use e3_fhe_params::{
+        BfvPreset, ParamSetDefinition, PresetDefinition, SearchDefinition, SecurityTier,
+    };
+
+    /// Registry with a preset file entry under on-chain id 9 that differs from the built-in
+    /// insecure pair in its threshold plaintext modulus and λ.
+    fn registry_with_file_preset() -> PresetRegistry {
+        let mut registry = PresetRegistry::builtin();
+        registry
+            .register(PresetDefinition {
+                name: "INSECURE_FILE_512".to_string(),
+                on_chain_id: 9,
+                security: SecurityTier::INSECURE,
+                degree: insecure_512::DEGREE,
+                search: SearchDefinition {
+                    n: insecure_search_defaults::SEARCH_N as u64,
+                    z: insecure_search_defaults::SEARCH_Z as u64,
+                    k: insecure_search_defaults::SEARCH_K as u64,
+                    lambda: 3,
+                    b: insecure_search_defaults::B as u64,
+                    b_chi: insecure_search_defaults::B_CHI as u64,
+                },
+                threshold: ParamSetDefinition {
+                    plaintext_modulus: 1024,
+                    moduli: insecure_512::threshold::MODULI.to_vec(),
+                    error1_variance: Some(insecure_512::threshold::ERROR1_VARIANCE.to_string()),
+                },
+                dkg: ParamSetDefinition {
+                    plaintext_modulus: insecure_512::dkg::PLAINTEXT_MODULUS,
+                    moduli: insecure_512::dkg::MODULI.to_vec(),
+                    error1_variance: None,
+                },
+            })
+            .unwrap();
+        registry
+    }
+
+    fn decode_e3_requested(
+        param_set: u8,
+        registry: &PresetRegistry,
+    ) -> anyhow::Result<e3_events::E3Requested> {
+        let event = IInterfold::E3Requested {
+            e3Id: U256::from(5u64),
+            e3: IInterfold::E3 {
+                seed: U256::from(1u64),
+                committeeSize: 0,
+                paramSet: param_set,
+                ..Default::default()
+            },
+            e3Program: Address::ZERO,
+        };
+        let decoded = IInterfold::E3Requested::decode_log_data(&event.encode_log_data()).unwrap();
+        E3RequestedWithChainId(decoded, 7).try_into_e3_requested(registry)
+    }
+
+    #[test]
+    fn test_e3_requested_with_file_preset_uses_registry_entry() {
+        let registry = registry_with_file_preset();
+        let requested = decode_e3_requested(9, &registry).unwrap();
+
+        assert_eq!(requested.params_preset, BfvPreset::CustomThreshold(9));
+        let (threshold, _) = registry.resolve_on_chain(9).unwrap().build_pair().unwrap();
+        assert_eq!(threshold.plaintext(), 1024);
+        assert_eq!(
+            requested.params,
+            ArcBytes::from_bytes(&encode_bfv_params(&threshold))
+        );
+        let expected_error_size =
+            calculate_error_size(threshold, requested.threshold_n, requested.threshold_m, 3)
+                .unwrap();
+        assert_eq!(
+            requested.error_size,
+            ArcBytes::from_bytes(&expected_error_size.to_bytes_be())
+        );
+    }
+
+    #[test]
+    fn test_e3_requested_with_unknown_param_set_is_rejected() {
+        let registry = registry_with_file_preset();
+        assert_eq!(
+            decode_e3_requested(0, &registry).unwrap().params_preset,
+            BfvPreset::InsecureThreshold512
+        );
+        assert!(decode_e3_requested(10, &registry).is_err());
+    }
 
     #[test]
     fn test_convert_u8_to_e3_stage_known_and_unknown() {
@@ -28,14 +28,17 @@ use alloy::{
         Authorization,
     },
 };
-use alloy::{primitives::Bytes, sol_types::SolValue};
+use alloy::{
+    primitives::{Bytes, FixedBytes},
+    sol_types::SolValue,
+};
 use anyhow::{Context, Result};
 use base64::{engine::general_purpose::STANDARD, Engine};
 use e3_config::{RpcAuth, RPC};
 use e3_crypto::Cipher;
 use e3_data::Repository;
-use e3_events::Proof;
-use e3_utils::{retry_with_backoff, RetryError};
+use e3_events::{CircuitName, Proof};
+use e3_utils::{retry_with_backoff, ArcBytes, RetryError};
 use std::{env, future::Future, pin::Pin, sync::Arc};
 use tracing::info;
 use zeroize::{Zeroize, Zeroizing};
@@ -65,6 +68,19 @@ pub fn encode_zk_proof(proof: &Proof) -> Result<Bytes> {
     ))
 }
 
+/// Inverse of [`encode_zk_proof`]: decodes `abi.encode(rawProof, publicInputs)` into a
+/// [`Proof`] for `circuit`.
+pub fn decode_zk_proof(circuit: CircuitName, encoded: &[u8]) -> Result<Proof> {
+    let (data, inputs) = <(Bytes, Vec<FixedBytes<32>>)>::abi_decode_params(encoded)
+        .context("proof is not abi-encoded (bytes, bytes32[])")?;
+    let signals: Vec<u8> = inputs.iter().flat_map(|f| f.0).collect();
+    Ok(Proof::new(
+        circuit,
+        ArcBytes::from_bytes(&data),
+        ArcBytes::from_bytes(&signals),
+    ))
+}
+
 pub trait AuthConversions {
     fn to_header_value(&self) -> Option<HeaderValue>;
     fn to_ws_auth(&self) -> Option<Authorization>;
@@ -317,8 +333,6 @@ where
 mod tests {
     use super::*;
     use alloy_dyn_abi::DynSolType;
-    use e3_events::{CircuitName, Proof};
-    use e3_utils::ArcBytes;
 
     /// Verifies encode_zk_proof produces ABI: abi.decode(proof, (bytes, bytes32[]))
     #[test]
@@ -343,6 +357,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decode_zk_proof_round_trip() {
+        let public_signals: Vec<u8> = (0..96).map(|i| i as u8).collect();
+        let proof = Proof::new(
+            CircuitName::DecryptionAggregator,
+            ArcBytes::from_bytes(&[9, 8, 7]),
+            ArcBytes::from_bytes(&public_signals),
+        );
+        let encoded = encode_zk_proof(&proof).unwrap();
+        let decoded = decode_zk_proof(CircuitName::DecryptionAggregator, &encoded).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decode_zk_proof(CircuitName::DecryptionAggregator, &[1, 2, 3]).is_err());
+    }
+
     #[test]
     fn test_encode_zk_proof_rejects_invalid() {
         let proof = Proof::new(
@@ -13,9 +13,11 @@
 //! - [`messages`] holds the actix message and event types exchanged between them.
 
 mod actors;
+mod circuit_verifiers;
 mod contracts;
 mod domain;
 mod messages;
+mod published_decryption;
 mod repo;
 
 pub mod helpers;
@@ -24,7 +26,9 @@ pub mod helpers;
 pub use domain::error_decoder;
 
 pub use actors::*;
+pub use circuit_verifiers::{bfv_encryption_scheme_id, fetch_circuit_verifiers, CircuitVerifiers};
 pub use domain::encode_attestation_evidence;
 pub use helpers::*;
 pub use messages::*;
+pub use published_decryption::{fetch_published_decryption, PublishedDecryption};
 pub use repo::*;
@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Read-only fetch of what the chain records about an E3's decryption.
+//!
+//! Used by offline verification tooling: the published commitments come from `getE3`, the
+//! full public key, ciphertext and final proof from the publication logs.
+
+use crate::contracts::{ICiphernodeRegistry, IInterfold};
+use alloy::primitives::{Address, B256, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::Filter;
+use alloy::sol_types::SolEvent;
+use anyhow::{bail, Context, Result};
+use e3_utils::ArcBytes;
+use e3_zk_helpers::CiphernodesCommitteeSize;
+
+const GET_LOGS_CHUNK_SIZE: u64 = 10_000;
+
+/// On-chain record of an E3's decryption.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublishedDecryption {
+    /// On-chain `ParamSet` enum value (`E3.paramSet`).
+    pub param_set: u8,
+    /// On-chain `CommitteeSize` enum value (`E3.committeeSize`).
+    pub committee_size: u8,
+    /// Aggregated public-key commitment (`E3.committeePublicKey`).
+    pub pk_commitment: [u8; 32],
+    /// `keccak256(ciphertextOutput)` (`E3.ciphertextOutput`).
+    pub ciphertext_output_hash: [u8; 32],
+    /// Plaintext output (`E3.plaintextOutput`); empty until published.
+    pub plaintext_output: ArcBytes,
+    /// Public key from `CommitteePublished`, if the log was found.
+    pub public_key: Option<ArcBytes>,
+    /// Ciphertext from `CiphertextOutputPublished`, if the log was found.
+    pub ciphertext_output: Option<ArcBytes>,
+    /// ABI-encoded DecryptionAggregator proof from `PlaintextOutputPublished`, if the log was
+    /// found and non-empty.
+    pub decryption_proof: Option<ArcBytes>,
+}
+
+impl PublishedDecryption {
+    pub fn committee(&self) -> Result<CiphernodesCommitteeSize> {
+        Ok(match self.committee_size {
+            0 => CiphernodesCommitteeSize::Minimum,
+            1 => CiphernodesCommitteeSize::Micro,
+            2 => CiphernodesCommitteeSize::Small,
+            other => bail!("Unsupported committee size enum value {other}"),
+        })
+    }
+}
+
+/// Reads `getE3` and scans the publication logs for `e3_id` from `from_block` to head.
+pub async fn fetch_published_decryption<P: Provider>(
+    provider: &P,
+    interfold: Address,
+    ciphernode_registry: Address,
+    e3_id: U256,
+    from_block: u64,
+) -> Result<PublishedDecryption> {
+    let e3 = IInterfold::new(interfold, provider)
+        .getE3(e3_id)
+        .call()
+        .await
+        .context("getE3 failed")?;
+    let to_block = provider.get_block_number().await?;
+
+    let committee = last_log::<_, ICiphernodeRegistry::CommitteePublished>(
+        provider,
+        ciphernode_registry,
+        e3_id,
+        from_block,
+        to_block,
+    )
+    .await?;
+    let ciphertext = last_log::<_, IInterfold::CiphertextOutputPublished>(
+        provider, interfold, e3_id, from_block, to_block,
+    )
+    .await?;
+    let plaintext = last_log::<_, IInterfold::PlaintextOutputPublished>(
+        provider, interfold, e3_id, from_block, to_block,
+    )
+    .await?;
+
+    Ok(PublishedDecryption {
+        param_set: e3.paramSet,
+        committee_size: e3.committeeSize,
+        pk_commitment: e3.committeePublicKey.0,
+        ciphertext_output_hash: e3.ciphertextOutput.0,
+        plaintext_output: ArcBytes::from_bytes(&e3.plaintextOutput),
+        public_key: committee.map(|e| ArcBytes::from_bytes(&e.publicKey)),
+        ciphertext_output: ciphertext.map(|e| ArcBytes::from_bytes(&e.ciphertextOutput)),
+        decryption_proof: plaintext
+            .filter(|e| !e.proof.is_empty())
+            .map(|e| ArcBytes::from_bytes(&e.proof)),
+    })
+}
+
+/// Returns the most recent `E` log emitted by `address` with `e3_id` as its indexed topic.
+async fn last_log<P: Provider, E: SolEvent>(
+    provider: &P,
+    address: Address,
+    e3_id: U256,
+    from_block: u64,
+    to_block: u64,
+) -> Result<Option<E>> {
+    let mut found = None;
+    let mut start = from_block;
+    while start <= to_block {
+        let end = start.saturating_add(GET_LOGS_CHUNK_SIZE - 1).min(to_block);
+        let filter = Filter::new()
+            .address(address)
+            .event_signature(E::SIGNATURE_HASH)
+            .topic1(B256::from(e3_id))
+            .from_block(start)
+            .to_block(end);
+        for log in provider.get_logs(&filter).await? {
+            found = Some(
+                E::decode_log_data(log.data())
+                    .with_context(|| format!("Error parsing {} log", E::SIGNATURE))?,
+            );
+        }
+        start = end + 1;
+    }
+    Ok(found)
+}
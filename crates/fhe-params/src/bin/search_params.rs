@@ -13,7 +13,9 @@ use e3_fhe_params::search::bfv::{
     bfv_search, bfv_search_second_param, BfvSearchConfig, BfvSearchResult,
 };
 use e3_fhe_params::search::constants::K_MAX;
-use e3_fhe_params::search::utils::{approx_bits_from_log2, fmt_big_summary, log2_big};
+use e3_fhe_params::search::utils::{
+    approx_bits_from_log2, fmt_big_summary, log2_big, uniform_variance_str,
+};
 use num_bigint::BigUint;
 
 #[derive(Parser, Debug, Clone)]
@@ -67,12 +69,6 @@ fn variance_uniform_str(b: u128) -> String {
     var.to_str_radix(10)
 }
 
-fn variance_uniform_big_str(b: &BigUint) -> String {
-    let b_plus_one = b + BigUint::from(1u32);
-    let var = (b * &b_plus_one) / 3u32;
-    var.to_str_radix(10)
-}
-
 #[allow(clippy::too_many_arguments)]
 fn print_param_set(
     title: &str,
@@ -206,7 +202,7 @@ fn main() {
     };
 
     let (dist_b_chi, var_chi) = ("CBD", variance_cbd_str(args.b_chi));
-    let (dist_benc, var_benc) = ("Uniform", variance_uniform_big_str(&bfv.benc_min));
+    let (dist_benc, var_benc) = ("Uniform", uniform_variance_str(&bfv.benc_min));
 
     let bfv2_opt = bfv_search_second_param(&config, &bfv);
 
@@ -54,6 +54,9 @@ pub fn build_pair_for_preset(
 
             Ok((params_threshold, params_dkg))
         }
+        BfvPreset::CustomThreshold(id) => BfvPreset::custom_definition(id)
+            .build_pair()
+            .map_err(|e| PresetError::InvalidCustom(e.to_string())),
         other => Err(PresetError::MissingPair(other.name())),
     }
 }
@@ -0,0 +1,469 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Deployable preset bundles produced from a parameter search.
+//!
+//! A bundle directory holds everything needed to ship a new preset:
+//!
+//! - `preset.toml`: a [`PresetFile`] with the single [`PresetDefinition`], loadable by
+//!   [`PresetRegistry::load`](crate::PresetRegistry::load)
+//! - `metadata.toml`: [`BundleMetadata`] for the threshold and DKG parameter sets, mirroring
+//!   [`PresetMetadata`](crate::PresetMetadata) and [`PresetSearchDefaults`]
+//! - `security.toml`: the [`SecurityReport`]
+//! - `threshold.params` / `dkg.params`: the ABI-encoded `BfvParameters` (as `0x` hex) that
+//!   E3 requests pass to the contracts (`abi-encoding` feature only)
+//!
+//! The Noir `configs/<preset>/` module is generated by `e3-zk-helpers`, which depends on this
+//! crate, and written next to these files by its `preset_bundle` binary.
+
+use crate::presets::{ParameterType, PresetSearchDefaults, SecurityTier};
+use crate::registry::{
+    ParamSetDefinition, PresetDefinition, PresetFile, RegistryError, SearchDefinition,
+    PRESET_FILE_VERSION,
+};
+use crate::search::bfv::{
+    second_param_fresh_noise, threshold_noise, BfvSearchConfig, BfvSearchResult,
+};
+use crate::search::utils::{log2_big, product, uniform_variance_str};
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error as ThisError;
+
+/// Version of the bundle layout written by [`PresetBundle::write`].
+pub const PRESET_BUNDLE_VERSION: u32 = 1;
+
+/// Upper bound for the statistical λ scan in [`SecurityReport::evaluate`].
+const MAX_REPORTED_LAMBDA: u32 = 256;
+
+#[derive(ThisError, Debug)]
+pub enum BundleError {
+    #[error("Failed to access bundle file {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Failed to serialize bundle: {0}")]
+    Serialize(#[from] toml::ser::Error),
+    #[error("Failed to parse bundle file: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Search result cannot be bundled: {0}")]
+    Unsupported(String),
+    #[error("Bundle preset file must contain exactly one preset, found {0}")]
+    PresetCount(usize),
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
+}
+
+/// What to publish a search result as, and which workload to report the noise budget for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleSpec {
+    /// Canonical preset name (e.g. "SECURE_THRESHOLD_16384").
+    pub name: String,
+    /// Value of the on-chain `ParamSet` enum the preset will be published under.
+    pub on_chain_id: u8,
+    pub security: SecurityTier,
+    /// Additive depth of the E3 program: its output sums up to `2^program_depth` fresh
+    /// ciphertexts.
+    pub program_depth: u32,
+    /// Committee size N taking part in threshold decryption.
+    pub committee_size: u64,
+}
+
+/// Metadata of one parameter set of a bundled preset.
+///
+/// Owned counterpart of [`PresetMetadata`](crate::PresetMetadata), which only describes the
+/// built-in presets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParamSetMetadata {
+    pub name: String,
+    pub degree: usize,
+    pub num_moduli: usize,
+    pub num_parties: u64,
+    pub lambda: u32,
+    pub parameter_type: ParameterType,
+    pub security: SecurityTier,
+}
+
+/// Content of `metadata.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleMetadata {
+    pub version: u32,
+    pub on_chain_id: u8,
+    pub threshold: ParamSetMetadata,
+    pub dkg: ParamSetMetadata,
+    /// Search inputs, i.e. the preset's [`PresetSearchDefaults`].
+    pub search: SearchDefinition,
+}
+
+impl BundleMetadata {
+    pub fn from_definition(def: &PresetDefinition) -> Self {
+        let meta = |set: &ParamSetDefinition, parameter_type| ParamSetMetadata {
+            name: def.name.clone(),
+            degree: def.degree,
+            num_moduli: set.moduli.len(),
+            num_parties: def.search.n,
+            lambda: def.search.lambda,
+            parameter_type,
+            security: def.security,
+        };
+        Self {
+            version: PRESET_BUNDLE_VERSION,
+            on_chain_id: def.on_chain_id,
+            threshold: meta(&def.threshold, ParameterType::THRESHOLD),
+            dkg: meta(&def.dkg, ParameterType::DKG),
+            search: def.search,
+        }
+    }
+}
+
+/// Security figures of one parameter set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParamSetSecurity {
+    pub log2_q: f64,
+    /// Largest log2(q) allowed by Eq4 (d ≥ 37.5*log2(q/B) + 75) at this degree.
+    pub log2_q_limit: f64,
+    /// `log2_q_limit - log2_q`; negative when the lattice security bound is violated.
+    pub lattice_margin_bits: f64,
+    pub log2_delta: f64,
+    /// log2 of the decryption noise bound for the reported workload.
+    pub log2_noise: f64,
+    /// `log2_delta - log2_noise`; decryption is correct while this is positive.
+    pub noise_budget_bits: f64,
+}
+
+impl ParamSetSecurity {
+    fn new(degree: u64, b: u128, q: &BigUint, delta: &BigUint, noise: &BigUint) -> Self {
+        let log2_q = log2_big(q);
+        // Eq4: d ≥ 37.5*log2(q/B) + 75  =>  log2(q) ≤ log2(B) + (d-75)/37.5
+        let log2_q_limit = (b as f64).log2() + ((degree as f64) - 75.0) / 37.5;
+        let log2_delta = log2_big(delta);
+        let log2_noise = log2_big(noise);
+        Self {
+            log2_q,
+            log2_q_limit,
+            lattice_margin_bits: log2_q_limit - log2_q,
+            log2_delta,
+            log2_noise,
+            noise_budget_bits: log2_delta - log2_noise,
+        }
+    }
+
+    pub fn is_within_bounds(&self) -> bool {
+        self.lattice_margin_bits >= 0.0 && self.noise_budget_bits > 0.0
+    }
+}
+
+/// Lattice-security and noise-budget estimate of a preset for a given program and committee.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecurityReport {
+    pub degree: usize,
+    pub committee_size: u64,
+    pub program_depth: u32,
+    /// Statistical λ the preset was searched with.
+    pub lambda: u32,
+    /// Largest statistical λ for which Eq1 still holds for this workload, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_lambda: Option<u32>,
+    pub threshold: ParamSetSecurity,
+    pub dkg: ParamSetSecurity,
+}
+
+impl SecurityReport {
+    /// Evaluate `def` for a program of additive depth `program_depth` decrypted by a committee
+    /// of `committee_size` parties, using the noise equations of [`crate::search`].
+    pub fn evaluate(def: &PresetDefinition, program_depth: u32, committee_size: u64) -> Self {
+        let search = def.search_defaults();
+        let d = def.degree as u64;
+        let additions = 1u128.checked_shl(program_depth).unwrap_or(u128::MAX);
+
+        let config_at = |lambda| BfvSearchConfig {
+            n: committee_size.into(),
+            z: additions,
+            k: def.threshold.plaintext_modulus.into(),
+            lambda,
+            b: search.b,
+            b_chi: search.b_chi,
+            verbose: false,
+        };
+
+        let q = product(def.threshold.moduli.iter().copied().map(BigUint::from));
+        let t = BigUint::from(def.threshold.plaintext_modulus);
+        let rkq = (&q % &t).to_u128().unwrap_or(0);
+        let delta = &q / &t;
+        let noise = threshold_noise(&config_at(search.lambda), d, rkq).lhs;
+        let threshold = ParamSetSecurity::new(d, search.b, &q, &delta, &noise);
+
+        let max_lambda = (0..=MAX_REPORTED_LAMBDA)
+            .take_while(|lambda| threshold_noise(&config_at(*lambda), d, rkq).lhs < delta)
+            .last();
+
+        let q = product(def.dkg.moduli.iter().copied().map(BigUint::from));
+        let delta = &q / BigUint::from(def.dkg.plaintext_modulus);
+        // Each DKG ciphertext carries a single fresh share: 2*B_C with B_C = B_fresh.
+        let noise = second_param_fresh_noise(&config_at(search.lambda), d) << 1;
+        let dkg = ParamSetSecurity::new(d, search.b, &q, &delta, &noise);
+
+        Self {
+            degree: def.degree,
+            committee_size,
+            program_depth,
+            lambda: search.lambda,
+            max_lambda,
+            threshold,
+            dkg,
+        }
+    }
+
+    pub fn is_within_bounds(&self) -> bool {
+        self.threshold.is_within_bounds() && self.dkg.is_within_bounds()
+    }
+}
+
+/// A searched preset together with its security report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresetBundle {
+    pub definition: PresetDefinition,
+    pub report: SecurityReport,
+}
+
+impl PresetBundle {
+    pub const PRESET_FILE: &'static str = "preset.toml";
+    pub const METADATA_FILE: &'static str = "metadata.toml";
+    pub const SECURITY_FILE: &'static str = "security.toml";
+    pub const THRESHOLD_PARAMS_FILE: &'static str = "threshold.params";
+    pub const DKG_PARAMS_FILE: &'static str = "dkg.params";
+
+    /// Turn the two parameter sets found by [`crate::search::bfv::bfv_search`] and
+    /// [`crate::search::bfv::bfv_search_second_param`] into a validated preset.
+    pub fn from_search(
+        spec: BundleSpec,
+        config: &BfvSearchConfig,
+        threshold: &BfvSearchResult,
+        dkg: &BfvSearchResult,
+    ) -> Result<Self, BundleError> {
+        if dkg.d != threshold.d {
+            return Err(BundleError::Unsupported(format!(
+                "DKG parameters need degree {} but threshold parameters use {}",
+                dkg.d, threshold.d
+            )));
+        }
+        let to_u64 = |label: &str, value: u128| {
+            u64::try_from(value)
+                .map_err(|_| BundleError::Unsupported(format!("{label} {value} exceeds 64 bits")))
+        };
+
+        let definition = PresetDefinition {
+            name: spec.name,
+            on_chain_id: spec.on_chain_id,
+            security: spec.security,
+            degree: threshold.d as usize,
+            search: SearchDefinition {
+                n: to_u64("n", config.n)?,
+                z: to_u64("z", config.z)?,
+                k: to_u64("k", config.k)?,
+                lambda: config.lambda,
+                b: to_u64("B", config.b)?,
+                b_chi: to_u64("B_chi", config.b_chi)?,
+            },
+            threshold: ParamSetDefinition {
+                plaintext_modulus: to_u64("threshold plaintext modulus", threshold.k_plain_eff)?,
+                moduli: threshold.qi_values(),
+                error1_variance: Some(uniform_variance_str(&threshold.benc_min)),
+            },
+            dkg: ParamSetDefinition {
+                plaintext_modulus: to_u64("DKG plaintext modulus", dkg.k_plain_eff)?,
+                moduli: dkg.qi_values(),
+                error1_variance: None,
+            },
+        };
+        definition.validate()?;
+
+        let report = SecurityReport::evaluate(&definition, spec.program_depth, spec.committee_size);
+        Ok(Self { definition, report })
+    }
+
+    pub fn metadata(&self) -> BundleMetadata {
+        BundleMetadata::from_definition(&self.definition)
+    }
+
+    pub fn search_defaults(&self) -> PresetSearchDefaults {
+        self.definition.search_defaults()
+    }
+
+    /// Preset file containing only this bundle's preset.
+    pub fn preset_file(&self) -> PresetFile {
+        PresetFile {
+            version: PRESET_FILE_VERSION,
+            presets: vec![self.definition.clone()],
+        }
+    }
+
+    /// Write the bundle files into `dir` (created if missing) and return their paths.
+    pub fn write(&self, dir: &Path) -> Result<Vec<PathBuf>, BundleError> {
+        std::fs::create_dir_all(dir).map_err(|source| io_error(dir, source))?;
+
+        let mut files = vec![
+            (Self::PRESET_FILE, toml::to_string(&self.preset_file())?),
+            (Self::METADATA_FILE, toml::to_string(&self.metadata())?),
+            (Self::SECURITY_FILE, toml::to_string(&self.report)?),
+        ];
+        #[cfg(feature = "abi-encoding")]
+        {
+            use alloy_primitives::hex;
+            let (threshold, dkg) = self.definition.build_pair()?;
+            files.push((
+                Self::THRESHOLD_PARAMS_FILE,
+                hex::encode_prefixed(crate::encode_bfv_params(&threshold)),
+            ));
+            files.push((
+                Self::DKG_PARAMS_FILE,
+                hex::encode_prefixed(crate::encode_bfv_params(&dkg)),
+            ));
+        }
+
+        files
+            .into_iter()
+            .map(|(name, contents)| {
+                let path = dir.join(name);
+                std::fs::write(&path, contents).map_err(|source| io_error(&path, source))?;
+                Ok(path)
+            })
+            .collect()
+    }
+
+    /// Read a bundle written by [`PresetBundle::write`], re-validating its preset.
+    pub fn load(dir: &Path) -> Result<Self, BundleError> {
+        let read = |name: &str| {
+            let path = dir.join(name);
+            std::fs::read_to_string(&path).map_err(|source| io_error(&path, source))
+        };
+
+        let mut file = PresetFile::from_toml_str(&read(Self::PRESET_FILE)?)?;
+        if file.presets.len() != 1 {
+            return Err(BundleError::PresetCount(file.presets.len()));
+        }
+        let definition = file.presets.remove(0);
+        definition.validate()?;
+        let report = toml::from_str(&read(Self::SECURITY_FILE)?)?;
+        Ok(Self { definition, report })
+    }
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> BundleError {
+    BundleError::Io {
+        path: path.display().to_string(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{defaults::DEFAULT_SECURE_LAMBDA, search_defaults};
+    use crate::registry::PresetRegistry;
+    use crate::search::bfv::{bfv_search, bfv_search_second_param};
+    use tempfile::TempDir;
+
+    fn secure_search_config() -> BfvSearchConfig {
+        BfvSearchConfig {
+            n: search_defaults::SEARCH_N,
+            z: search_defaults::SEARCH_Z,
+            k: search_defaults::SEARCH_K,
+            lambda: DEFAULT_SECURE_LAMBDA as u32,
+            b: search_defaults::B,
+            b_chi: search_defaults::B_CHI,
+            verbose: false,
+        }
+    }
+
+    fn spec() -> BundleSpec {
+        BundleSpec {
+            name: "SEARCHED_SECURE".to_string(),
+            on_chain_id: 9,
+            security: SecurityTier::SECURE,
+            program_depth: 20,
+            committee_size: search_defaults::SEARCH_N as u64,
+        }
+    }
+
+    fn searched_bundle() -> (BfvSearchResult, BfvSearchResult, PresetBundle) {
+        let config = secure_search_config();
+        let threshold = bfv_search(&config).unwrap();
+        let dkg = bfv_search_second_param(&config, &threshold).unwrap();
+        let bundle = PresetBundle::from_search(spec(), &config, &threshold, &dkg).unwrap();
+        (threshold, dkg, bundle)
+    }
+
+    #[test]
+    fn searched_bundle_round_trips_through_registry() {
+        let (threshold, dkg, bundle) = searched_bundle();
+        let temp = TempDir::new().unwrap();
+        let temp = temp.path();
+        bundle.write(temp).unwrap();
+
+        let loaded = PresetBundle::load(temp).unwrap();
+        assert_eq!(loaded, bundle);
+
+        let registry = PresetRegistry::load(temp.join(PresetBundle::PRESET_FILE)).unwrap();
+        let preset = registry
+            .resolve_on_chain(9)
+            .expect("bundled preset registered");
+        let (threshold_params, dkg_params) = preset.build_pair().unwrap();
+        assert_eq!(threshold_params.degree() as u64, threshold.d);
+        assert_eq!(threshold_params.moduli(), threshold.qi_values());
+        assert_eq!(
+            u128::from(threshold_params.plaintext()),
+            threshold.k_plain_eff
+        );
+        assert_eq!(dkg_params.moduli(), dkg.qi_values());
+
+        let metadata = std::fs::read_to_string(temp.join(PresetBundle::METADATA_FILE)).unwrap();
+        let metadata: BundleMetadata = toml::from_str(&metadata).unwrap();
+        assert_eq!(metadata, bundle.metadata());
+        assert_eq!(metadata.dkg.parameter_type, ParameterType::DKG);
+
+        #[cfg(feature = "abi-encoding")]
+        {
+            let hex =
+                std::fs::read_to_string(temp.join(PresetBundle::THRESHOLD_PARAMS_FILE)).unwrap();
+            let bytes = alloy_primitives::hex::decode(hex.trim()).unwrap();
+            let decoded = crate::decode_bfv_params(&bytes).unwrap();
+            assert_eq!(decoded.degree(), threshold_params.degree());
+            assert_eq!(decoded.plaintext(), threshold_params.plaintext());
+            assert_eq!(decoded.moduli(), threshold_params.moduli());
+        }
+    }
+
+    #[test]
+    fn report_tracks_workload() {
+        let (_, _, bundle) = searched_bundle();
+        let report = &bundle.report;
+        assert!(report.is_within_bounds());
+        assert!(report.max_lambda.unwrap() >= report.lambda);
+
+        // Larger committees and deeper programs eat into the threshold noise budget.
+        let heavier = SecurityReport::evaluate(&bundle.definition, 24, 4 * report.committee_size);
+        assert!(heavier.threshold.noise_budget_bits < report.threshold.noise_budget_bits);
+        assert!(heavier.max_lambda.unwrap_or(0) <= report.max_lambda.unwrap());
+        assert_eq!(heavier.dkg, report.dkg);
+        assert_eq!(
+            heavier.threshold.lattice_margin_bits,
+            report.threshold.lattice_margin_bits
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_degrees() {
+        let (threshold, mut dkg, _) = searched_bundle();
+        dkg.d *= 2;
+        assert!(matches!(
+            PresetBundle::from_search(spec(), &secure_search_config(), &threshold, &dkg),
+            Err(BundleError::Unsupported(_))
+        ));
+    }
+}
@@ -7,17 +7,23 @@
 //! Preset definitions and builders for BFV FHE parameters.
 
 pub mod builder;
+pub mod bundle;
 pub mod constants;
 pub mod crp;
 #[cfg(feature = "abi-encoding")]
 pub mod encoding;
 pub mod presets;
+pub mod registry;
 pub mod search;
 
 pub use builder::{
     build_bfv_params, build_bfv_params_arc, build_bfv_params_from_set,
     build_bfv_params_from_set_arc, build_pair_for_preset,
 };
+pub use bundle::{
+    BundleError, BundleMetadata, BundleSpec, ParamSetMetadata, ParamSetSecurity, PresetBundle,
+    SecurityReport, PRESET_BUNDLE_VERSION,
+};
 pub use crp::{create_deterministic_crp_from_default_seed, create_deterministic_crp_from_seed};
 #[cfg(feature = "abi-encoding")]
 pub use encoding::{decode_bfv_params, decode_bfv_params_arc, encode_bfv_params, EncodingError};
@@ -25,3 +31,7 @@ pub use presets::{
     default_param_set, BfvParamSet, BfvPreset, ParameterType, PresetError, PresetMetadata,
     PresetSearchDefaults, SecurityTier, DEFAULT_BFV_PRESET,
 };
+pub use registry::{
+    ParamSetDefinition, PresetDefinition, PresetFile, PresetRegistry, RegisteredPreset,
+    RegistryError, SearchDefinition, PRESET_FILE_VERSION,
+};
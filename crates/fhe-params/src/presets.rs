@@ -17,6 +17,7 @@ use crate::constants::{
     search_defaults::{B, B_CHI, SEARCH_K, SEARCH_N, SEARCH_Z},
     secure_8192,
 };
+use crate::registry::{PresetDefinition, PresetRegistry, RegisteredPreset};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use thiserror::Error as ThisError;
@@ -60,11 +61,19 @@ pub enum BfvPreset {
     /// a standard BFV key-pair to encrypt secret shares. These are temporary keys used
     /// only during the key generation process.
     SecureDkg8192,
+    /// Threshold parameters of a preset loaded from the installed preset file, identified by its
+    /// on-chain `ParamSet` id and resolved through [`PresetRegistry::global`].
+    CustomThreshold(u8),
+    /// DKG parameters of the preset file entry with this on-chain `ParamSet` id.
+    CustomDkg(u8),
 }
 
 impl BfvPreset {
     /// Convert an on-chain `ParamSet` enum value (uint8) to the corresponding
     /// threshold `BfvPreset`. Returns `None` for unknown values.
+    ///
+    /// Only the built-in pairs are known here. Callers resolving ids seen on chain go through
+    /// [`PresetRegistry::global`], which also knows the ids of preset files.
     pub fn from_on_chain_param_set(value: u8) -> Option<Self> {
         match value {
             0 => Some(BfvPreset::InsecureThreshold512),
@@ -90,7 +99,8 @@ pub fn default_param_set() -> BfvParamSet {
 }
 
 /// Parameter type for BFV presets
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ParameterType {
     /// Threshold BFV (TRBFV) parameters
     THRESHOLD,
@@ -99,7 +109,8 @@ pub enum ParameterType {
 }
 
 /// Security tier for BFV presets
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SecurityTier {
     /// Insecure security tier
     INSECURE,
@@ -208,6 +219,8 @@ pub enum PresetError {
     UnknownPreset(String),
     #[error("Preset does not define a Threshold (trBFV) / DKG (BFV) pair: {0}")]
     MissingPair(&'static str),
+    #[error("Invalid preset file entry: {0}")]
+    InvalidCustom(String),
 }
 
 /// A complete BFV parameter set definition
@@ -281,6 +294,22 @@ impl BfvPreset {
             BfvPreset::InsecureDkg512 => "INSECURE_DKG_512",
             BfvPreset::SecureThreshold8192 => "SECURE_THRESHOLD_8192",
             BfvPreset::SecureDkg8192 => "SECURE_DKG_8192",
+            BfvPreset::CustomThreshold(id) | BfvPreset::CustomDkg(id) => {
+                &Self::custom_definition(*id).name
+            }
+        }
+    }
+
+    /// The preset file entry behind a custom preset.
+    ///
+    /// # Panics
+    ///
+    /// If the installed registry holds no preset file entry under `id`. Custom presets are only
+    /// created from the installed registry, so all nodes of an E3 must load the same preset file.
+    pub(crate) fn custom_definition(id: u8) -> &'static PresetDefinition {
+        match PresetRegistry::global().resolve_on_chain(id) {
+            Some(RegisteredPreset::Custom(def)) => def,
+            _ => panic!("No preset file entry is installed for on-chain param set id {id}"),
         }
     }
 
@@ -311,7 +340,7 @@ impl BfvPreset {
     }
 
     pub fn supports_pair(&self) -> bool {
-        Self::PAIR_PRESETS.contains(self)
+        Self::PAIR_PRESETS.contains(self) || matches!(self, BfvPreset::CustomThreshold(_))
     }
 
     /// Returns the DKG preset that pairs with this threshold preset.
@@ -323,7 +352,8 @@ impl BfvPreset {
         match self {
             BfvPreset::InsecureThreshold512 => Some(BfvPreset::InsecureDkg512),
             BfvPreset::SecureThreshold8192 => Some(BfvPreset::SecureDkg8192),
-            BfvPreset::InsecureDkg512 | BfvPreset::SecureDkg8192 => None,
+            BfvPreset::CustomThreshold(id) => Some(BfvPreset::CustomDkg(id)),
+            BfvPreset::InsecureDkg512 | BfvPreset::SecureDkg8192 | BfvPreset::CustomDkg(_) => None,
         }
     }
 
@@ -336,7 +366,10 @@ impl BfvPreset {
         match self {
             BfvPreset::InsecureDkg512 => Some(BfvPreset::InsecureThreshold512),
             BfvPreset::SecureDkg8192 => Some(BfvPreset::SecureThreshold8192),
-            BfvPreset::InsecureThreshold512 | BfvPreset::SecureThreshold8192 => None,
+            BfvPreset::CustomDkg(id) => Some(BfvPreset::CustomThreshold(id)),
+            BfvPreset::InsecureThreshold512
+            | BfvPreset::SecureThreshold8192
+            | BfvPreset::CustomThreshold(_) => None,
         }
     }
 
@@ -378,6 +411,27 @@ impl BfvPreset {
                 parameter_type: ParameterType::DKG,
                 security: SecurityTier::SECURE,
             },
+            BfvPreset::CustomThreshold(id) | BfvPreset::CustomDkg(id) => {
+                let def = Self::custom_definition(*id);
+                let parameter_type = self.parameter_type();
+                PresetMetadata {
+                    name: &def.name,
+                    degree: def.degree,
+                    num_moduli: def.param_set(parameter_type).moduli.len(),
+                    num_parties: def.search.n.into(),
+                    lambda: def.search.lambda as usize,
+                    parameter_type,
+                    security: def.security,
+                }
+            }
+        }
+    }
+
+    fn parameter_type(&self) -> ParameterType {
+        if self.dkg_counterpart().is_some() {
+            ParameterType::THRESHOLD
+        } else {
+            ParameterType::DKG
         }
     }
 
@@ -389,6 +443,9 @@ impl BfvPreset {
     /// Returns the base directory name for circuit artifacts (e.g. `"insecure-512"`, `"secure-8192"`).
     /// Threshold and DKG presets at the same degree share the same compiled circuits.
     pub fn artifacts_dir(&self) -> String {
+        if let BfvPreset::CustomThreshold(id) | BfvPreset::CustomDkg(id) = self {
+            return Self::custom_definition(*id).artifacts_dir();
+        }
         let meta = self.metadata();
         format!("{}-{}", meta.security.as_config_str(), meta.degree)
     }
@@ -419,6 +476,7 @@ impl BfvPreset {
                 b: B,
                 b_chi: B_CHI,
             }),
+            BfvPreset::CustomThreshold(id) => Some(Self::custom_definition(*id).search_defaults()),
             _ => None,
         }
     }
@@ -455,6 +513,16 @@ impl From<BfvPreset> for BfvParamSet {
                 moduli: secure_8192::dkg::MODULI,
                 error1_variance: Some(secure_8192::dkg::ERROR1_VARIANCE),
             },
+            BfvPreset::CustomThreshold(id) | BfvPreset::CustomDkg(id) => {
+                let def = BfvPreset::custom_definition(id);
+                let set = def.param_set(value.parameter_type());
+                BfvParamSet {
+                    degree: def.degree,
+                    plaintext_modulus: set.plaintext_modulus,
+                    moduli: &set.moduli,
+                    error1_variance: set.error1_variance.as_deref(),
+                }
+            }
         }
     }
 }
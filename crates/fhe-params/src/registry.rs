@@ -0,0 +1,706 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Registry of BFV presets beyond the built-in [`BfvPreset`]s.
+//!
+//! Additional presets are declared in a versioned TOML file:
+//!
+//! ```toml
+//! version = 1
+//!
+//! [[preset]]
+//! name = "SECURE_THRESHOLD_16384"
+//! on_chain_id = 2
+//! security = "secure"
+//! degree = 16384
+//! search = { n = 20, z = 1000000, k = 1000000, lambda = 50, b = 20, b_chi = 1 }
+//! threshold = { plaintext_modulus = 1000000, moduli = [...], error1_variance = "..." }
+//! dkg = { plaintext_modulus = ..., moduli = [...] }
+//! ```
+//!
+//! Every loaded preset is checked structurally (power-of-two degree, NTT-friendly prime moduli,
+//! DKG plaintext space large enough for threshold shares) and, for the `secure` tier, against the
+//! same noise and lattice-security equations the [`crate::search`] module uses to derive the
+//! built-in presets. On-chain ids 0 and 1 stay reserved for the built-in pairs.
+//!
+//! Nodes and the CLI [`install`](PresetRegistry::install) the registry of their configured
+//! `preset_file` at startup; on-chain ids are resolved through [`PresetRegistry::global`]. E3s
+//! requested with a file preset run with [`BfvPreset::CustomThreshold`].
+
+use crate::presets::{BfvPreset, ParameterType, PresetError, PresetSearchDefaults, SecurityTier};
+use crate::search::bfv::{finalize_bfv_candidate, finalize_second_param, BfvSearchConfig};
+use crate::search::constants::{D_POW2_MAX, D_POW2_START};
+use crate::search::prime::{is_ntt_friendly_prime, PrimeItem};
+use crate::search::utils::{log2_big, product};
+use fhe::bfv::{BfvParameters, BfvParametersBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use thiserror::Error as ThisError;
+
+/// Version of the preset file format understood by this crate.
+pub const PRESET_FILE_VERSION: u32 = 1;
+
+#[derive(ThisError, Debug)]
+pub enum RegistryError {
+    #[error("Failed to read preset file {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse preset file: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Unsupported preset file version {found} (expected {PRESET_FILE_VERSION})")]
+    UnsupportedVersion { found: u32 },
+    #[error("Preset name {0} is already registered")]
+    DuplicateName(String),
+    #[error("On-chain param set id {id} is already used by {existing}")]
+    DuplicateOnChainId { id: u8, existing: String },
+    #[error("Invalid preset {name}: {reason}")]
+    Invalid { name: String, reason: String },
+    #[error("Unknown on-chain param set id {0}")]
+    UnknownOnChainId(u8),
+    #[error("Unknown preset {0}")]
+    UnknownName(String),
+    #[error("A preset registry is already installed")]
+    AlreadyInstalled,
+    #[error(transparent)]
+    Preset(#[from] PresetError),
+}
+
+static GLOBAL_REGISTRY: OnceLock<PresetRegistry> = OnceLock::new();
+
+/// On-disk preset file: a format version and a list of preset definitions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresetFile {
+    pub version: u32,
+    #[serde(default, rename = "preset")]
+    pub presets: Vec<PresetDefinition>,
+}
+
+impl PresetFile {
+    pub fn from_toml_str(s: &str) -> Result<Self, RegistryError> {
+        let file: PresetFile = toml::from_str(s)?;
+        if file.version != PRESET_FILE_VERSION {
+            return Err(RegistryError::UnsupportedVersion {
+                found: file.version,
+            });
+        }
+        Ok(file)
+    }
+}
+
+/// One BFV parameter set (threshold or DKG) of a preset definition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParamSetDefinition {
+    pub plaintext_modulus: u64,
+    pub moduli: Vec<u64>,
+    /// Variance of the encryption error e1 as a decimal string. Defaults to the BFV default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error1_variance: Option<String>,
+}
+
+impl ParamSetDefinition {
+    fn build_arc(&self, degree: usize) -> Result<Arc<BfvParameters>, String> {
+        let mut builder = BfvParametersBuilder::new();
+        builder
+            .set_degree(degree)
+            .set_plaintext_modulus(self.plaintext_modulus)
+            .set_moduli(&self.moduli);
+        if let Some(error1) = &self.error1_variance {
+            builder
+                .set_error1_variance_str(error1)
+                .map_err(|e| format!("invalid error1_variance: {e}"))?;
+        }
+        builder.build_arc().map_err(|e| e.to_string())
+    }
+
+    fn prime_items(&self) -> Vec<PrimeItem> {
+        self.moduli
+            .iter()
+            .copied()
+            .map(PrimeItem::from_u64)
+            .collect()
+    }
+}
+
+/// Search inputs a preset was derived with (see [`PresetSearchDefaults`]).
+///
+/// Stored as 64-bit integers since TOML cannot represent 128-bit values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchDefinition {
+    pub n: u64,
+    pub z: u64,
+    pub k: u64,
+    pub lambda: u32,
+    pub b: u64,
+    pub b_chi: u64,
+}
+
+impl From<SearchDefinition> for PresetSearchDefaults {
+    fn from(value: SearchDefinition) -> Self {
+        PresetSearchDefaults {
+            n: value.n.into(),
+            z: value.z.into(),
+            k: value.k.into(),
+            lambda: value.lambda,
+            b: value.b.into(),
+            b_chi: value.b_chi.into(),
+        }
+    }
+}
+
+/// A named threshold/DKG parameter pair loaded from a preset file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresetDefinition {
+    /// Canonical name (e.g. "SECURE_THRESHOLD_16384"), matched case-insensitively.
+    pub name: String,
+    /// Value of the on-chain `ParamSet` enum this preset is published under.
+    pub on_chain_id: u8,
+    pub security: SecurityTier,
+    pub degree: usize,
+    /// Search inputs the parameters were derived from; used to re-check security.
+    pub search: SearchDefinition,
+    pub threshold: ParamSetDefinition,
+    pub dkg: ParamSetDefinition,
+}
+
+impl PresetDefinition {
+    /// Noir module name for the generated circuit configs (e.g. `secure_threshold_16384`).
+    pub fn config_module(&self) -> String {
+        self.name
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
+    /// Base directory name for circuit artifacts (e.g. `"secure-threshold-16384"`).
+    pub fn artifacts_dir(&self) -> String {
+        self.config_module().replace('_', "-")
+    }
+
+    /// Per-committee artifact directory, mirroring [`BfvPreset::artifacts_dir_for_committee`].
+    pub fn artifacts_dir_for_committee<C: AsRef<str>>(&self, committee: C) -> String {
+        format!("{}/{}", self.artifacts_dir(), committee.as_ref())
+    }
+
+    /// The threshold or DKG half of the pair.
+    pub fn param_set(&self, parameter_type: ParameterType) -> &ParamSetDefinition {
+        match parameter_type {
+            ParameterType::THRESHOLD => &self.threshold,
+            ParameterType::DKG => &self.dkg,
+        }
+    }
+
+    /// Search inputs as [`PresetSearchDefaults`], matching [`BfvPreset::search_defaults`].
+    pub fn search_defaults(&self) -> PresetSearchDefaults {
+        self.search.into()
+    }
+
+    /// Build the (threshold, DKG) parameter pair.
+    pub fn build_pair(&self) -> Result<(Arc<BfvParameters>, Arc<BfvParameters>), RegistryError> {
+        let threshold = self
+            .threshold
+            .build_arc(self.degree)
+            .map_err(|reason| self.invalid(format!("threshold parameters: {reason}")))?;
+        let dkg = self
+            .dkg
+            .build_arc(self.degree)
+            .map_err(|reason| self.invalid(format!("DKG parameters: {reason}")))?;
+        Ok((threshold, dkg))
+    }
+
+    /// Check the definition structurally and, for secure presets, against the search equations.
+    pub fn validate(&self) -> Result<(), RegistryError> {
+        if self.name.trim().is_empty() {
+            return Err(self.invalid("name must not be empty"));
+        }
+        let d = self.degree as u64;
+        if !d.is_power_of_two() || !(D_POW2_START..=D_POW2_MAX).contains(&d) {
+            return Err(self.invalid(format!(
+                "degree {d} must be a power of two in [{D_POW2_START}, {D_POW2_MAX}]"
+            )));
+        }
+
+        for (label, set) in [("threshold", &self.threshold), ("dkg", &self.dkg)] {
+            if set.moduli.is_empty() {
+                return Err(self.invalid(format!("{label} moduli must not be empty")));
+            }
+            if let Some(q) = set.moduli.iter().find(|q| !is_ntt_friendly_prime(**q, d)) {
+                return Err(self.invalid(format!(
+                    "{label} modulus {q:#x} is not an NTT-friendly prime for degree {d}"
+                )));
+            }
+            let mut sorted = set.moduli.clone();
+            sorted.sort_unstable();
+            sorted.dedup();
+            if sorted.len() != set.moduli.len() {
+                return Err(self.invalid(format!("{label} moduli must be distinct")));
+            }
+            if set.plaintext_modulus < 2 {
+                return Err(self.invalid(format!("{label} plaintext modulus must be at least 2")));
+            }
+        }
+
+        // DKG ciphertexts carry threshold secret shares, which live modulo each threshold q_i.
+        let max_threshold_qi = self.threshold.moduli.iter().copied().max().unwrap_or(0);
+        if self.dkg.plaintext_modulus < max_threshold_qi {
+            return Err(self.invalid(format!(
+                "DKG plaintext modulus {} is smaller than threshold modulus {max_threshold_qi:#x}",
+                self.dkg.plaintext_modulus
+            )));
+        }
+
+        if self.security == SecurityTier::SECURE {
+            self.validate_security()?;
+        }
+
+        self.build_pair().map(|_| ())
+    }
+
+    fn validate_security(&self) -> Result<(), RegistryError> {
+        let search = self.search_defaults();
+        let config = BfvSearchConfig {
+            n: search.n,
+            z: search.z,
+            k: search.k,
+            lambda: search.lambda,
+            b: search.b,
+            b_chi: search.b_chi,
+            verbose: false,
+        };
+        let d = self.degree as u64;
+
+        let k_plain_eff = search.k.max(search.z);
+        if u128::from(self.threshold.plaintext_modulus) != k_plain_eff {
+            return Err(self.invalid(format!(
+                "threshold plaintext modulus {} does not match search plaintext space {k_plain_eff}",
+                self.threshold.plaintext_modulus
+            )));
+        }
+
+        // Eq4: d ≥ 37.5*log2(q/B) + 75  =>  log2(q) ≤ log2(B) + (d-75)/37.5
+        let log2_q_limit = (search.b as f64).log2() + ((d as f64) - 75.0) / 37.5;
+        for (label, set) in [("threshold", &self.threshold), ("dkg", &self.dkg)] {
+            let log2_q = log2_big(&product(set.prime_items().into_iter().map(|p| p.value)));
+            if log2_q > log2_q_limit + 1e-12 {
+                return Err(self.invalid(format!(
+                    "{label} log2(q)={log2_q:.3} exceeds the lattice security limit {log2_q_limit:.3} for degree {d}"
+                )));
+            }
+        }
+
+        if finalize_bfv_candidate(&config, d, self.threshold.prime_items()).is_none() {
+            return Err(
+                self.invalid("threshold parameters fail the noise bound 2*(B_C + n*B_sm) < Δ")
+            );
+        }
+        if finalize_second_param(
+            &config,
+            d,
+            self.dkg.prime_items(),
+            u128::from(self.dkg.plaintext_modulus),
+        )
+        .is_none()
+        {
+            return Err(self.invalid("DKG parameters fail the noise bound 2*B_C < Δ"));
+        }
+        Ok(())
+    }
+
+    fn invalid(&self, reason: impl Into<String>) -> RegistryError {
+        RegistryError::Invalid {
+            name: self.name.clone(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// A preset resolved from the registry: either a built-in pair or a loaded definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegisteredPreset {
+    Builtin(BfvPreset),
+    Custom(Arc<PresetDefinition>),
+}
+
+impl RegisteredPreset {
+    pub fn name(&self) -> &str {
+        match self {
+            RegisteredPreset::Builtin(preset) => preset.name(),
+            RegisteredPreset::Custom(def) => &def.name,
+        }
+    }
+
+    pub fn security_tier(&self) -> SecurityTier {
+        match self {
+            RegisteredPreset::Builtin(preset) => preset.security_tier(),
+            RegisteredPreset::Custom(def) => def.security,
+        }
+    }
+
+    pub fn artifacts_dir(&self) -> String {
+        match self {
+            RegisteredPreset::Builtin(preset) => preset.artifacts_dir(),
+            RegisteredPreset::Custom(def) => def.artifacts_dir(),
+        }
+    }
+
+    /// Per-committee artifact directory: `"{preset}/{committee}"`.
+    pub fn artifacts_dir_for_committee<C: AsRef<str>>(&self, committee: C) -> String {
+        format!("{}/{}", self.artifacts_dir(), committee.as_ref())
+    }
+
+    /// Build the (threshold, DKG) parameter pair.
+    pub fn build_pair(&self) -> Result<(Arc<BfvParameters>, Arc<BfvParameters>), RegistryError> {
+        match self {
+            RegisteredPreset::Builtin(preset) => Ok(preset.build_pair()?),
+            RegisteredPreset::Custom(def) => def.build_pair(),
+        }
+    }
+
+    /// The built-in preset, if this is one.
+    pub fn as_builtin(&self) -> Option<BfvPreset> {
+        match self {
+            RegisteredPreset::Builtin(preset) => Some(*preset),
+            RegisteredPreset::Custom(_) => None,
+        }
+    }
+
+    /// The threshold preset an E3 runs with. Presets loaded from files become
+    /// [`BfvPreset::CustomThreshold`], which resolves through [`PresetRegistry::global`].
+    pub fn preset(&self) -> BfvPreset {
+        match self {
+            RegisteredPreset::Builtin(preset) => *preset,
+            RegisteredPreset::Custom(def) => BfvPreset::CustomThreshold(def.on_chain_id),
+        }
+    }
+
+    /// Statistical security parameter λ of the threshold parameters.
+    pub fn lambda(&self) -> usize {
+        match self {
+            RegisteredPreset::Builtin(preset) => preset.metadata().lambda,
+            RegisteredPreset::Custom(def) => def.search.lambda as usize,
+        }
+    }
+}
+
+/// Maps on-chain `ParamSet` ids and preset names to presets.
+///
+/// Starts with the built-in threshold pairs; further presets are added from preset files.
+#[derive(Debug, Clone)]
+pub struct PresetRegistry {
+    by_on_chain_id: BTreeMap<u8, RegisteredPreset>,
+}
+
+impl Default for PresetRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl PresetRegistry {
+    /// Registry holding only the built-in pairs (ids 0 and 1).
+    pub fn builtin() -> Self {
+        let by_on_chain_id = (0..=u8::MAX)
+            .filter_map(|id| {
+                BfvPreset::from_on_chain_param_set(id)
+                    .map(|preset| (id, RegisteredPreset::Builtin(preset)))
+            })
+            .collect();
+        Self { by_on_chain_id }
+    }
+
+    /// Built-in presets plus every preset in the given TOML document.
+    pub fn from_toml_str(s: &str) -> Result<Self, RegistryError> {
+        let mut registry = Self::builtin();
+        registry.extend(PresetFile::from_toml_str(s)?)?;
+        Ok(registry)
+    }
+
+    /// Built-in presets plus every preset in the file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RegistryError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|source| RegistryError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::from_toml_str(&contents)
+    }
+
+    /// Validate and add every preset of `file`. Nothing is added if any preset is rejected.
+    pub fn extend(&mut self, file: PresetFile) -> Result<(), RegistryError> {
+        let mut next = self.clone();
+        for def in file.presets {
+            next.register(def)?;
+        }
+        *self = next;
+        Ok(())
+    }
+
+    /// Validate and add a single preset definition.
+    pub fn register(&mut self, def: PresetDefinition) -> Result<(), RegistryError> {
+        if let Some(existing) = self.by_on_chain_id.get(&def.on_chain_id) {
+            return Err(RegistryError::DuplicateOnChainId {
+                id: def.on_chain_id,
+                existing: existing.name().to_string(),
+            });
+        }
+        if self.get(&def.name).is_some() || BfvPreset::from_name(&def.name).is_ok() {
+            return Err(RegistryError::DuplicateName(def.name));
+        }
+        def.validate()?;
+        self.by_on_chain_id
+            .insert(def.on_chain_id, RegisteredPreset::Custom(Arc::new(def)));
+        Ok(())
+    }
+
+    /// Resolve an on-chain `ParamSet` value. Built-in ids resolve even on an empty file.
+    pub fn resolve_on_chain(&self, id: u8) -> Option<&RegisteredPreset> {
+        self.by_on_chain_id.get(&id)
+    }
+
+    /// Look up a preset by name (case-insensitive).
+    pub fn get(&self, name: &str) -> Option<&RegisteredPreset> {
+        let normalized = name.trim().to_ascii_uppercase();
+        self.by_on_chain_id
+            .values()
+            .find(|p| p.name().to_ascii_uppercase() == normalized)
+    }
+
+    /// All registered presets ordered by on-chain id.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &RegisteredPreset)> {
+        self.by_on_chain_id.iter().map(|(id, p)| (*id, p))
+    }
+
+    /// Only the presets loaded from files.
+    pub fn custom(&self) -> impl Iterator<Item = &Arc<PresetDefinition>> {
+        self.by_on_chain_id.values().filter_map(|p| match p {
+            RegisteredPreset::Custom(def) => Some(def),
+            RegisteredPreset::Builtin(_) => None,
+        })
+    }
+
+    /// Resolve an on-chain `ParamSet` value to the preset an E3 runs with (see
+    /// [`RegisteredPreset::preset`]).
+    pub fn preset_on_chain(&self, id: u8) -> Result<BfvPreset, RegistryError> {
+        self.resolve_on_chain(id)
+            .map(RegisteredPreset::preset)
+            .ok_or(RegistryError::UnknownOnChainId(id))
+    }
+
+    /// Like [`Self::preset_on_chain`] for a preset name. Also accepts the names of the DKG
+    /// halves of the built-in pairs.
+    pub fn preset_by_name(&self, name: &str) -> Result<BfvPreset, RegistryError> {
+        match self.get(name) {
+            Some(preset) => Ok(preset.preset()),
+            None => {
+                BfvPreset::from_name(name).map_err(|_| RegistryError::UnknownName(name.to_string()))
+            }
+        }
+    }
+
+    /// Make this the registry returned by [`Self::global`]. Must happen before the first lookup
+    /// and only once per process.
+    pub fn install(self) -> Result<(), RegistryError> {
+        GLOBAL_REGISTRY
+            .set(self)
+            .map_err(|_| RegistryError::AlreadyInstalled)
+    }
+
+    /// The installed registry, or the built-in presets when none was installed.
+    pub fn global() -> &'static PresetRegistry {
+        GLOBAL_REGISTRY.get_or_init(Self::builtin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{defaults::DEFAULT_SECURE_LAMBDA, search_defaults, secure_8192};
+
+    /// The built-in secure pair expressed as a file definition.
+    fn secure_8192_definition(name: &str, on_chain_id: u8) -> PresetDefinition {
+        PresetDefinition {
+            name: name.to_string(),
+            on_chain_id,
+            security: SecurityTier::SECURE,
+            degree: secure_8192::DEGREE,
+            search: SearchDefinition {
+                n: search_defaults::SEARCH_N as u64,
+                z: search_defaults::SEARCH_Z as u64,
+                k: search_defaults::SEARCH_K as u64,
+                lambda: DEFAULT_SECURE_LAMBDA as u32,
+                b: search_defaults::B as u64,
+                b_chi: search_defaults::B_CHI as u64,
+            },
+            threshold: ParamSetDefinition {
+                plaintext_modulus: secure_8192::threshold::PLAINTEXT_MODULUS,
+                moduli: secure_8192::threshold::MODULI.to_vec(),
+                error1_variance: Some(secure_8192::threshold::ERROR1_VARIANCE.to_string()),
+            },
+            dkg: ParamSetDefinition {
+                plaintext_modulus: secure_8192::dkg::PLAINTEXT_MODULUS,
+                moduli: secure_8192::dkg::MODULI.to_vec(),
+                error1_variance: None,
+            },
+        }
+    }
+
+    fn file_with(defs: Vec<PresetDefinition>) -> String {
+        toml::to_string(&PresetFile {
+            version: PRESET_FILE_VERSION,
+            presets: defs,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn builtin_registry_matches_on_chain_mapping() {
+        let registry = PresetRegistry::builtin();
+        assert_eq!(
+            registry.resolve_on_chain(0).and_then(|p| p.as_builtin()),
+            Some(BfvPreset::InsecureThreshold512)
+        );
+        assert_eq!(
+            registry.resolve_on_chain(1).and_then(|p| p.as_builtin()),
+            Some(BfvPreset::SecureThreshold8192)
+        );
+        assert!(registry.resolve_on_chain(2).is_none());
+        assert_eq!(registry.custom().count(), 0);
+    }
+
+    #[test]
+    fn loads_and_resolves_custom_preset() {
+        let def = secure_8192_definition("CUSTOM_SECURE_8192", 7);
+        let registry = PresetRegistry::from_toml_str(&file_with(vec![def.clone()])).unwrap();
+
+        let resolved = registry.resolve_on_chain(7).expect("id 7 registered");
+        assert_eq!(resolved, &RegisteredPreset::Custom(Arc::new(def)));
+        assert_eq!(
+            registry.get("custom_secure_8192").map(|p| p.name()),
+            Some("CUSTOM_SECURE_8192")
+        );
+        assert_eq!(resolved.artifacts_dir(), "custom-secure-8192");
+
+        let (threshold, dkg) = resolved.build_pair().unwrap();
+        assert_eq!(threshold.moduli(), secure_8192::threshold::MODULI);
+        assert_eq!(dkg.plaintext(), secure_8192::dkg::PLAINTEXT_MODULUS);
+    }
+
+    #[test]
+    fn custom_presets_resolve_for_e3s() {
+        let def = secure_8192_definition("CUSTOM_SECURE_8192", 7);
+        let registry = PresetRegistry::from_toml_str(&file_with(vec![def])).unwrap();
+
+        assert_eq!(
+            registry.preset_on_chain(1).unwrap(),
+            BfvPreset::SecureThreshold8192
+        );
+        assert_eq!(
+            registry.preset_on_chain(7).unwrap(),
+            BfvPreset::CustomThreshold(7)
+        );
+        assert_eq!(
+            registry.resolve_on_chain(7).map(RegisteredPreset::lambda),
+            Some(DEFAULT_SECURE_LAMBDA)
+        );
+        assert!(matches!(
+            registry.preset_on_chain(8),
+            Err(RegistryError::UnknownOnChainId(8))
+        ));
+
+        assert_eq!(
+            registry.preset_by_name("secure_threshold_8192").unwrap(),
+            BfvPreset::SecureThreshold8192
+        );
+        assert_eq!(
+            registry.preset_by_name("SECURE_DKG_8192").unwrap(),
+            BfvPreset::SecureDkg8192
+        );
+        assert_eq!(
+            registry.preset_by_name("custom_secure_8192").unwrap(),
+            BfvPreset::CustomThreshold(7)
+        );
+        assert!(matches!(
+            registry.preset_by_name("nope"),
+            Err(RegistryError::UnknownName(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_unsupported_version() {
+        let err = PresetRegistry::from_toml_str("version = 99\n").unwrap_err();
+        assert!(matches!(
+            err,
+            RegistryError::UnsupportedVersion { found: 99 }
+        ));
+    }
+
+    #[test]
+    fn rejects_reserved_ids_and_duplicate_names() {
+        let err = PresetRegistry::from_toml_str(&file_with(vec![secure_8192_definition("A", 1)]))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RegistryError::DuplicateOnChainId { id: 1, .. }
+        ));
+
+        let err = PresetRegistry::from_toml_str(&file_with(vec![secure_8192_definition(
+            "secure_threshold_8192",
+            5,
+        )]))
+        .unwrap_err();
+        assert!(matches!(err, RegistryError::DuplicateName(_)));
+
+        let err = PresetRegistry::from_toml_str(&file_with(vec![
+            secure_8192_definition("A", 5),
+            secure_8192_definition("a", 6),
+        ]))
+        .unwrap_err();
+        assert!(matches!(err, RegistryError::DuplicateName(_)));
+    }
+
+    #[test]
+    fn rejects_presets_failing_security_checks() {
+        // A non-NTT-friendly modulus.
+        let mut def = secure_8192_definition("BAD_MODULUS", 5);
+        def.threshold.moduli[0] += 2;
+        assert!(matches!(def.validate(), Err(RegistryError::Invalid { .. })));
+
+        // Enlarging the plaintext space by 2^20 breaks the noise bound at the same q.
+        let mut def = secure_8192_definition("BAD_NOISE", 5);
+        def.search.k <<= 20;
+        def.search.z <<= 20;
+        def.threshold.plaintext_modulus <<= 20;
+        assert!(matches!(def.validate(), Err(RegistryError::Invalid { .. })));
+
+        // Too large a q for the degree violates the lattice security bound.
+        let mut def = secure_8192_definition("BAD_DEGREE", 5);
+        def.degree = 1024;
+        assert!(matches!(def.validate(), Err(RegistryError::Invalid { .. })));
+    }
+
+    #[test]
+    fn failed_file_leaves_registry_untouched() {
+        let mut registry = PresetRegistry::builtin();
+        let mut bad = secure_8192_definition("BAD", 6);
+        bad.degree = 1000;
+        let file = PresetFile {
+            version: PRESET_FILE_VERSION,
+            presets: vec![secure_8192_definition("GOOD", 5), bad],
+        };
+        assert!(registry.extend(file).is_err());
+        assert!(registry.resolve_on_chain(5).is_none());
+    }
+}
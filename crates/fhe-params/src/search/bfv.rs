@@ -161,30 +161,20 @@ pub fn bfv_search(bfv_search_config: &BfvSearchConfig) -> BfvParamsResult<BfvSea
     Err(SearchError::NoFeasibleParameters.into())
 }
 
-/// Validate a candidate parameter set and compute all noise bounds.
-///
-/// Computes noise budgets (B_Enc, B_fresh, B_C, B_sm) and checks if Eq1 is satisfied:
-/// 2*(B_C + n*B_sm) < Δ
-///
-/// Returns None if validation fails, otherwise returns the complete result.
-pub fn finalize_bfv_candidate(
-    bfv_search_config: &BfvSearchConfig,
-    d: u64,
-    chosen: Vec<PrimeItem>,
-) -> Option<BfvSearchResult> {
-    let q_bfv = product(chosen.iter().map(|pi| pi.value.clone()));
-
-    // Compute plaintext space: max of user-defined k and z
-    let k_plain_eff: u128 = bfv_search_config.k.max(bfv_search_config.z);
-
-    // r_k(q) = q mod k
-    let k_big = BigUint::from(k_plain_eff);
-    let rkq_big = &q_bfv % &k_big;
-    let rkq: u128 = rkq_big.to_u128().unwrap_or(0);
-
-    // Δ = floor(q / k)
-    let delta = &q_bfv / &k_big;
+/// Noise bounds of the threshold parameter set (Eq1-Eq3) at degree `d`.
+#[derive(Debug, Clone)]
+pub struct ThresholdNoise {
+    pub benc_min: BigUint,
+    pub b_fresh: BigUint,
+    pub b_c: BigUint,
+    pub b_sm_min: BigUint,
+    /// Left-hand side of Eq1: 2*(B_C + n*B_sm). Decryption is correct while this is below Δ.
+    pub lhs: BigUint,
+}
 
+/// Compute the threshold noise bounds for `n` parties, `z` fresh ciphertext additions and
+/// `r_k(q) = rkq`, without checking them against Δ.
+pub fn threshold_noise(bfv_search_config: &BfvSearchConfig, d: u64, rkq: u128) -> ThresholdNoise {
     // Eq2: 2 d n B B_chi ≤ B_Enc * 2^{-λ}  =>  B_Enc ≥ (2 d n B B_chi) * 2^{λ}
     let two_pow_lambda = big_shift_pow2(bfv_search_config.lambda);
     let benc_min = (BigUint::from(2u32)
@@ -212,6 +202,47 @@ pub fn finalize_bfv_candidate(
 
     // Eq1: 2*(B_C + n*B_sm) < Δ
     let lhs = (&b_c + BigUint::from(bfv_search_config.n) * &b_sm_min) << 1;
+
+    ThresholdNoise {
+        benc_min,
+        b_fresh,
+        b_c,
+        b_sm_min,
+        lhs,
+    }
+}
+
+/// Validate a candidate parameter set and compute all noise bounds.
+///
+/// Computes noise budgets (B_Enc, B_fresh, B_C, B_sm) and checks if Eq1 is satisfied:
+/// 2*(B_C + n*B_sm) < Δ
+///
+/// Returns None if validation fails, otherwise returns the complete result.
+pub fn finalize_bfv_candidate(
+    bfv_search_config: &BfvSearchConfig,
+    d: u64,
+    chosen: Vec<PrimeItem>,
+) -> Option<BfvSearchResult> {
+    let q_bfv = product(chosen.iter().map(|pi| pi.value.clone()));
+
+    // Compute plaintext space: max of user-defined k and z
+    let k_plain_eff: u128 = bfv_search_config.k.max(bfv_search_config.z);
+
+    // r_k(q) = q mod k
+    let k_big = BigUint::from(k_plain_eff);
+    let rkq_big = &q_bfv % &k_big;
+    let rkq: u128 = rkq_big.to_u128().unwrap_or(0);
+
+    // Δ = floor(q / k)
+    let delta = &q_bfv / &k_big;
+
+    let ThresholdNoise {
+        benc_min,
+        b_fresh,
+        b_c,
+        b_sm_min,
+        lhs,
+    } = threshold_noise(bfv_search_config, d, rkq);
     let lhs_log2 = log2_big(&lhs);
     let rhs_log2 = log2_big(&delta);
 
@@ -468,6 +499,16 @@ pub fn bfv_search_second_param(
     None
 }
 
+/// Fresh-ciphertext noise bound B_fresh of the second (DKG) parameter set at degree `d`.
+pub fn second_param_fresh_noise(bfv_search_config: &BfvSearchConfig, d: u64) -> BigUint {
+    // For second set: B_Enc = B (simpler), B_fresh = B_Enc + d*B*B_chi + d*B*B_chi
+    let benc = BigUint::from(bfv_search_config.b);
+    let term_d_bbchi = BigUint::from(d)
+        * BigUint::from(bfv_search_config.b)
+        * BigUint::from(bfv_search_config.b_chi);
+    &benc + &term_d_bbchi + &term_d_bbchi
+}
+
 /// Refine second parameter set at a fixed degree d by decreasing q.
 ///
 /// Collects all passing candidates as q decreases, then selects the one with
@@ -656,12 +697,8 @@ pub fn finalize_second_param(
     let rkq: u128 = rkq_big.to_u128().unwrap_or(0);
     let delta = &q_bfv / &k_big;
 
-    // For second set: B_Enc = B (simpler), B_fresh = B_Enc + d*B*B_chi + d*B*B_chi
     let benc = BigUint::from(bfv_search_config.b);
-    let term_d_bbchi = BigUint::from(d)
-        * BigUint::from(bfv_search_config.b)
-        * BigUint::from(bfv_search_config.b_chi);
-    let b_fresh = &benc + &term_d_bbchi + &term_d_bbchi;
+    let b_fresh = second_param_fresh_noise(bfv_search_config, d);
     let b_c = b_fresh.clone(); // B_C = B_fresh
 
     let lhs = &b_c << 1; // 2*B_C
@@ -23,6 +23,19 @@ pub struct PrimeItem {
     pub hex: String,
 }
 
+impl PrimeItem {
+    /// Build a prime item for an arbitrary 64-bit modulus (e.g. from a preset file).
+    pub fn from_u64(value: u64) -> Self {
+        let v = BigUint::from(value);
+        PrimeItem {
+            bitlen: v.bits() as u8,
+            log2: log2_big(&v),
+            hex: format!("0x{value:016x}"),
+            value: v,
+        }
+    }
+}
+
 /// Filter function type for excluding specific bit lengths
 type BitFilter = fn(u8) -> bool;
 
@@ -89,6 +102,54 @@ pub fn select_max_q_under_cap(limit_log2: f64, all: &[PrimeItem]) -> Vec<PrimeIt
     sel
 }
 
+/// Deterministic Miller-Rabin primality test for 64-bit integers.
+pub fn is_prime_u64(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    for p in WITNESSES {
+        if n % p == 0 {
+            return n == p;
+        }
+    }
+
+    let mul_mod = |a: u64, b: u64| ((a as u128 * b as u128) % n as u128) as u64;
+    let pow_mod = |mut base: u64, mut exp: u64| {
+        let mut acc = 1u64;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = mul_mod(acc, base);
+            }
+            base = mul_mod(base, base);
+            exp >>= 1;
+        }
+        acc
+    };
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'witness: for a in WITNESSES {
+        let mut x = pow_mod(a, d);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Whether `q` is a prime supporting the negacyclic NTT of the given degree (q ≡ 1 mod 2d).
+pub fn is_ntt_friendly_prime(q: u64, degree: u64) -> bool {
+    degree > 0 && q % (2 * degree) == 1 && is_prime_u64(q)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,4 +235,16 @@ mod tests {
         let selected = select_max_q_under_cap(100.0, &empty);
         assert!(selected.is_empty());
     }
+
+    #[test]
+    fn test_is_ntt_friendly_prime() {
+        // The search table only holds primes.
+        for item in build_prime_items_for_second() {
+            let q = u64::try_from(&item.value).unwrap();
+            assert!(is_prime_u64(q), "{} should be prime", item.hex);
+        }
+        assert!(is_ntt_friendly_prime(0xffffee001, 512));
+        assert!(!is_ntt_friendly_prime(0xffffee001 + 2, 512));
+        assert!(!is_prime_u64(0xffffee001 * 3));
+    }
 }
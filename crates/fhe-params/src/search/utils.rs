@@ -60,6 +60,15 @@ pub fn big_shift_pow2(exp: u32) -> BigUint {
     BigUint::one() << exp
 }
 
+/// Variance b(b+1)/3 of the uniform distribution on [-b, b], as a decimal string.
+///
+/// This is the `error1_variance` of a threshold parameter set whose e1 is bounded by B_Enc = b.
+pub fn uniform_variance_str(b: &BigUint) -> String {
+    let b_plus_one = b + BigUint::one();
+    let var = (b * &b_plus_one) / 3u32;
+    var.to_str_radix(10)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
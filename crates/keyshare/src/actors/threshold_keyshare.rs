@@ -5,6 +5,8 @@
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
 use actix::prelude::*;
+use alloy::primitives::Address;
+use alloy::signers::local::PrivateKeySigner;
 use anyhow::{anyhow, bail, Context, Result};
 use e3_crypto::{Cipher, SensitiveBytes};
 use e3_data::Persistable;
@@ -12,13 +14,15 @@ use e3_events::{
     prelude::*, trap, BusHandle, CiphernodeSelected, CiphertextOutputPublished,
     CommitteeMemberExpelled, ComputeRequest, ComputeResponse, ComputeResponseKind, CorrelationId,
     DecryptionKeyShared, DecryptionShareProofSigned, DecryptionShareProofsPending, Die,
-    DkgProofSigned, DkgShareDecryptionProofRequest, E3Failed, E3RequestComplete, E3Stage, EType,
-    EncryptionKey, EncryptionKeyCollectionFailed, EncryptionKeyCreated, EncryptionKeyPending,
-    EventContext, FailureReason, InterfoldEvent, InterfoldEventData, KeyshareCreated,
-    PartyProofsToVerify, PartyShareDecryptionProofsToVerify, PkGenerationProofSigned, ProofType,
-    Sequenced, ShareDecryptionProofPending, ShareVerificationComplete, ShareVerificationDispatched,
-    SignedProofPayload, ThresholdShare, ThresholdShareCollectionFailed, ThresholdShareCreated,
-    ThresholdShareDecryptionProofRequest, ThresholdSharePending, TypedEvent, VerificationKind,
+    DkgComplaint, DkgComplaintReason, DkgDealerDisqualified, DkgProofSigned, DkgShareComplaints,
+    DkgShareDecryptionProofRequest, DkgShareJustification, E3Failed, E3RequestComplete, E3Stage,
+    EType, EncryptionKey, EncryptionKeyCollectionFailed, EncryptionKeyCreated,
+    EncryptionKeyPending, EventContext, FailureReason, InterfoldEvent, InterfoldEventData,
+    KeyshareCreated, PartyProofsToVerify, PartyShareDecryptionProofsToVerify,
+    PkGenerationProofSigned, ProofType, Sequenced, ShareDecryptionProofPending,
+    ShareVerificationComplete, ShareVerificationDispatched, SignedProofPayload, ThresholdShare,
+    ThresholdShareCollectionFailed, ThresholdShareCreated, ThresholdShareDecryptionProofRequest,
+    ThresholdSharePending, TypedEvent, VerificationKind,
 };
 use e3_fhe_params::create_deterministic_crp_from_default_seed;
 use e3_fhe_params::BfvPreset;
@@ -50,14 +54,17 @@ use crate::actors::encryption_key_collector::{
     AllEncryptionKeysCollected, EncryptionKeyCollector, ExpelPartyFromKeyCollection,
 };
 use crate::actors::threshold_share_collector::{
-    ExpelPartyFromShareCollection, ThresholdShareCollector,
+    ExpelPartyFromShareCollection, ShareComplaintsClosed, ThresholdShareCollector,
+};
+use crate::domain::timeout_policy::{
+    resolve_justification_grace, resolve_timeout, DkgTimeoutPhase,
 };
-use crate::domain::timeout_policy::{resolve_timeout, DkgTimeoutPhase};
 use crate::domain::{
     build_decryption_key_plan, build_shares_generated_plan, generate_bfv_keypair,
-    AggregatingDecryptionKey, BfvKeypairMaterial, CollectingEncryptionKeysData, Decrypting,
-    DecryptionKeyPlan, GeneratingDecryptionProof, GeneratingThresholdShareData, KeyshareState,
-    ProofRequestData, ReadyForDecryption, ReceivedShareProofs, ThresholdKeyshareState,
+    AggregatingDecryptionKey, BfvKeypairMaterial, CollectingEncryptionKeysData, ComplaintDecision,
+    ComplaintVerificationPlan, Decrypting, DecryptionKeyPlan, ExpectedShareProofs,
+    GeneratingDecryptionProof, GeneratingThresholdShareData, KeyshareState, ProofRequestData,
+    ReadyForDecryption, ReceivedShareProofs, ThresholdKeyshareState,
 };
 
 #[derive(Message, Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
@@ -78,6 +85,8 @@ pub struct AllThresholdSharesCollected {
     shares: Vec<Arc<ThresholdShare>>,
     /// Proofs from each sender, ordered by party_id (parallel to shares).
     share_proofs: Vec<ReceivedShareProofs>,
+    /// Dealers whose share had not arrived when collection timed out.
+    missing_parties: BTreeSet<u64>,
 }
 
 impl AllThresholdSharesCollected {
@@ -102,13 +111,21 @@ impl AllThresholdSharesCollected {
         Self {
             shares,
             share_proofs,
+            missing_parties: BTreeSet::new(),
         }
     }
+
+    pub fn with_missing_parties(mut self, missing_parties: BTreeSet<u64>) -> Self {
+        self.missing_parties = missing_parties;
+        self
+    }
 }
 
 pub struct ThresholdKeyshareParams {
     pub bus: BusHandle,
     pub cipher: Arc<Cipher>,
+    /// Operator key used to sign DKG complaint ballots.
+    pub signer: PrivateKeySigner,
     pub state: Persistable<ThresholdKeyshareState>,
     pub share_enc_preset: BfvPreset,
 }
@@ -116,6 +133,7 @@ pub struct ThresholdKeyshareParams {
 pub struct ThresholdKeyshare {
     bus: BusHandle,
     cipher: Arc<Cipher>,
+    signer: PrivateKeySigner,
     decryption_key_collector: Option<Addr<ThresholdShareCollector>>,
     encryption_key_collector: Option<Addr<EncryptionKeyCollector>>,
     decryption_key_shared_collector: Option<Addr<DecryptionKeySharedCollector>>,
@@ -137,6 +155,19 @@ pub struct ThresholdKeyshare {
     pending_own_dkg_shares: Option<(SensitiveBytes, Vec<SensitiveBytes>)>,
     /// Set when C4 verification completes before `PkGenerationProofSigned` is applied.
     pending_keyshare_publish: bool,
+    /// Signed C2/C3 proofs per dealer, kept as complaint evidence until our ballot is sent.
+    pending_share_proofs: HashMap<u64, Vec<SignedProofPayload>>,
+    /// Dealers we hold no usable share from (missing, or missing/incomplete proofs).
+    pending_missing_dealers: BTreeSet<u64>,
+    /// Our own outgoing shares by recipient, revealed if a recipient reports them missing.
+    sent_shares: HashMap<u64, ThresholdShareCreated>,
+    /// Recipients we have already published a justification for.
+    justified_accusers: HashSet<u64>,
+    /// Closed complaint round waiting on proof re-verification.
+    pending_complaint_plan: Option<ComplaintVerificationPlan>,
+    /// Dealers our complaint round found faulty. The decryption key is computed once all of
+    /// them are expelled on-chain; if that never happens the E3 fails at its DKG deadline.
+    awaiting_expulsion: BTreeSet<u64>,
 }
 
 impl ThresholdKeyshare {
@@ -144,6 +175,7 @@ impl ThresholdKeyshare {
         Self {
             bus: params.bus,
             cipher: params.cipher,
+            signer: params.signer,
             decryption_key_collector: None,
             encryption_key_collector: None,
             decryption_key_shared_collector: None,
@@ -154,6 +186,12 @@ impl ThresholdKeyshare {
             pending_c4_verification_shares: None,
             pending_own_dkg_shares: None,
             pending_keyshare_publish: false,
+            pending_share_proofs: HashMap::new(),
+            pending_missing_dealers: BTreeSet::new(),
+            sent_shares: HashMap::new(),
+            justified_accusers: HashSet::new(),
+            pending_complaint_plan: None,
+            awaiting_expulsion: BTreeSet::new(),
         }
     }
 
@@ -233,24 +271,57 @@ impl ThresholdKeyshare {
         );
         let e3_id = state.e3_id.clone();
         let threshold_n = state.threshold_n;
+        let threshold_m = state.threshold_m;
         let own_party_id = state.party_id;
+        let committee = state
+            .committee
+            .iter()
+            .map(|addr| addr.parse::<Address>())
+            .collect::<Result<Vec<_>, _>>()
+            .context("Invalid committee address on keyshare state")?;
+        if committee.is_empty() {
+            bail!("Committee not known for E3 {e3_id}; complaint ballots cannot be checked");
+        }
+        let expelled: Vec<u64> = state.expelled_parties.iter().copied().collect();
         let timeout = resolve_timeout(
             DkgTimeoutPhase::ThresholdShareCollection,
             state.dkg_started_at_unix_secs,
         );
+        let complaint_timeout = resolve_timeout(
+            DkgTimeoutPhase::ShareComplaints,
+            state.dkg_started_at_unix_secs,
+        );
+        let justification_grace = resolve_justification_grace();
         info!(
             e3_id = %e3_id,
             timeout = ?timeout.duration,
             "{}",
             timeout.description
         );
+        info!(
+            e3_id = %e3_id,
+            timeout = ?complaint_timeout.duration,
+            "{}",
+            complaint_timeout.description
+        );
+        info!(
+            e3_id = %e3_id,
+            timeout = ?justification_grace.duration,
+            "{}",
+            justification_grace.description
+        );
         let addr = self.decryption_key_collector.get_or_insert_with(|| {
             ThresholdShareCollector::setup(
                 self_addr,
                 threshold_n,
                 own_party_id,
+                threshold_m,
+                committee,
+                expelled,
                 e3_id,
                 timeout.duration,
+                complaint_timeout.duration,
+                justification_grace.duration,
             )
         });
         Ok(addr.clone())
@@ -354,6 +425,8 @@ impl ThresholdKeyshare {
 
         // Clean transient coordination state for the expelled party
         self.pending_shares.retain(|s| s.party_id != party_id);
+        self.pending_share_proofs.remove(&party_id);
+        self.pending_missing_dealers.remove(&party_id);
 
         if let Some(ref mut pending_c4) = self.pending_c4_verification_shares {
             pending_c4.remove(&party_id);
@@ -374,7 +447,16 @@ impl ThresholdKeyshare {
         }
 
         if let Some(ref collector) = self.decryption_key_shared_collector {
-            collector.do_send(ExpelPartyFromDecryptionKeySharedCollection { party_id, ec });
+            collector.do_send(ExpelPartyFromDecryptionKeySharedCollection {
+                party_id,
+                ec: ec.clone(),
+            });
+        }
+
+        if self.awaiting_expulsion.remove(&party_id) && self.awaiting_expulsion.is_empty() {
+            if let Err(e) = self.proceed_after_complaints(ec) {
+                error!("Failed to resume decryption key calculation after expulsion: {e}");
+            }
         }
     }
 
@@ -401,6 +483,11 @@ impl ThresholdKeyshare {
 
         let my_party_id = state.party_id;
 
+        // Keep our own outgoing shares so we can justify them if a recipient complains.
+        if !msg.external && msg.share.party_id == my_party_id {
+            self.sent_shares.insert(msg.target_party_id, (*msg).clone());
+        }
+
         // Filter: only process shares intended for this party
         if msg.target_party_id != my_party_id {
             return Ok(());
@@ -579,6 +666,12 @@ impl ThresholdKeyshare {
         }
 
         info!("CiphernodeSelected received.");
+        let committee = msg.committee.clone();
+        self.state.try_mutate(&ec, |mut s| {
+            s.committee = committee;
+            Ok(s)
+        })?;
+
         // Ensure the collectors are created
         let _ = self.ensure_collector(address.clone());
         let _ = self.ensure_encryption_key_collector(address.clone());
@@ -924,7 +1017,29 @@ impl ThresholdKeyshare {
         Ok(())
     }
 
-    /// 5. AllThresholdSharesCollected - Verify C2/C3 proofs, then decrypt and aggregate
+    /// Proof counts every dealer must send us, derived from our own cached shares. The
+    /// collector excludes self from the shares it hands over, so they cannot be read from there.
+    fn expected_share_proofs(
+        &self,
+        current: &AggregatingDecryptionKey,
+    ) -> Result<ExpectedShareProofs> {
+        let own_sk_rows: Vec<Vec<u64>> =
+            bincode::deserialize(&current.own_sk_share_raw.access_raw(&self.cipher)?)
+                .context("Failed to deserialize own_sk_share_raw")?;
+        let mut c3b: usize = 0;
+        for esi_raw in current.own_esi_shares_raw.iter() {
+            let rows: Vec<Vec<u64>> = bincode::deserialize(&esi_raw.access_raw(&self.cipher)?)
+                .context("Failed to deserialize own esi share")?;
+            c3b += rows.len();
+        }
+        Ok(ExpectedShareProofs {
+            c3a: own_sk_rows.len(),
+            c3b,
+            num_esi: current.own_esi_shares_raw.len(),
+        })
+    }
+
+    /// 5. AllThresholdSharesCollected - Verify C2/C3 proofs, then run the complaint round
     pub fn handle_all_threshold_shares_collected(
         &mut self,
         msg: TypedEvent<AllThresholdSharesCollected>,
@@ -954,20 +1069,9 @@ impl ThresholdKeyshare {
                 .unzip()
         };
 
-        // Expected proof counts come from local cached own shares (trusted source); the
-        // collector excludes self from `shares`, so we cannot read them from there.
+        // Expected proof counts come from local cached own shares (trusted source).
         let current: AggregatingDecryptionKey = state.clone().try_into()?;
-        let own_sk_rows: Vec<Vec<u64>> =
-            bincode::deserialize(&current.own_sk_share_raw.access_raw(&self.cipher)?)
-                .context("Failed to deserialize own_sk_share_raw")?;
-        let expected_c3a = own_sk_rows.len();
-        let expected_num_esi = current.own_esi_shares_raw.len();
-        let mut expected_c3b: usize = 0;
-        for esi_raw in current.own_esi_shares_raw.iter() {
-            let rows: Vec<Vec<u64>> = bincode::deserialize(&esi_raw.access_raw(&self.cipher)?)
-                .context("Failed to deserialize own esi share")?;
-            expected_c3b += rows.len();
-        }
+        let expected = self.expected_share_proofs(&current)?;
 
         // Build verification requests for other parties' proofs
         let mut party_proofs_to_verify: Vec<PartyProofsToVerify> = Vec::new();
@@ -991,21 +1095,15 @@ impl ThresholdKeyshare {
             // Validate proof set completeness against trusted expected counts.
             // A malicious sender could omit proofs that would fail verification,
             // so we must check that all expected proofs are present.
-            let is_complete = proofs.signed_c2a_proof.is_some()
-                && proofs.signed_c2b_proof.is_some()
-                && proofs.signed_c3a_proofs.len() == expected_c3a
-                && proofs.signed_c3b_proofs.len() == expected_c3b
-                && share.esi_sss.len() == expected_num_esi;
-
-            if !is_complete {
+            if !expected.is_complete(share, proofs) {
                 warn!(
                     "Party {} has incomplete proof set (c2a={}, c2b={}, c3a={}/{}, c3b={}/{}, esi={}/{}), treating as dishonest",
                     share.party_id,
                     proofs.signed_c2a_proof.is_some(),
                     proofs.signed_c2b_proof.is_some(),
-                    proofs.signed_c3a_proofs.len(), expected_c3a,
-                    proofs.signed_c3b_proofs.len(), expected_c3b,
-                    share.esi_sss.len(), expected_num_esi,
+                    proofs.signed_c3a_proofs.len(), expected.c3a,
+                    proofs.signed_c3b_proofs.len(), expected.c3b,
+                    share.esi_sss.len(), expected.num_esi,
                 );
                 incomplete_proof_parties.insert(share.party_id);
                 continue;
@@ -1027,6 +1125,10 @@ impl ThresholdKeyshare {
 
         // Store shares on the actor for use after verification completes (keep Arc to avoid deep clone)
         self.pending_shares = shares.to_vec();
+        self.pending_share_proofs = party_proofs_to_verify
+            .iter()
+            .map(|p| (p.sender_party_id, p.signed_proofs.clone()))
+            .collect();
 
         // Merge no-proof and incomplete-proof parties — both are dishonest
         let mut pre_dishonest: BTreeSet<u64> = BTreeSet::new();
@@ -1041,32 +1143,19 @@ impl ThresholdKeyshare {
             );
         }
 
-        if party_proofs_to_verify.is_empty() {
-            // All non-self parties are dishonest (missing or incomplete proofs), none to verify
-            let threshold = state.threshold_m;
-            let total = state.threshold_n;
-            let dishonest_count = (pre_dishonest.len() as u64).min(total);
-            let honest_count = total - dishonest_count;
-
-            if honest_count <= threshold {
-                warn!(
-                    "Too few honest parties for E3 {} ({} honest, need at least {}) after C2/C3 pre-dishonest filtering — cannot proceed",
-                    e3_id, honest_count, threshold + 1
-                );
-                self.pending_shares.clear();
-                self.bus.publish(
-                    E3Failed {
-                        e3_id: e3_id.clone(),
-                        failed_at_stage: E3Stage::CommitteeFinalized,
-                        reason: FailureReason::InsufficientCommitteeMembers,
-                    },
-                    ec,
-                )?;
-                return Ok(());
-            }
+        // Dealers we got nothing usable from are complained about as missing; they may still
+        // clear themselves with a justification.
+        self.pending_missing_dealers = msg
+            .missing_parties
+            .iter()
+            .filter(|pid| !expelled.contains(*pid))
+            .chain(pre_dishonest.iter())
+            .copied()
+            .collect();
 
-            let dishonest_set: HashSet<u64> = pre_dishonest.into_iter().collect();
-            return self.proceed_with_decryption_key_calculation(Some(dishonest_set), ec);
+        if party_proofs_to_verify.is_empty() {
+            // Nothing to verify — go straight to the complaint round
+            return self.publish_share_complaints(BTreeSet::new(), ec);
         }
 
         info!(
@@ -1107,46 +1196,26 @@ impl ThresholdKeyshare {
 
         match msg.kind {
             VerificationKind::ShareProofs => {
-                // C2/C3 verification complete
-                if msg.dishonest_parties.is_empty() {
-                    info!(
-                        "All parties passed C2/C3 verification for E3 {} — proceeding",
+                // C2/C3 verification complete — complain about every failing dealer
+                let invalid: BTreeSet<u64> = msg
+                    .dishonest_parties
+                    .into_iter()
+                    .filter(|pid| !self.pending_missing_dealers.contains(pid))
+                    .collect();
+                if invalid.is_empty() {
+                    info!("All parties passed C2/C3 verification for E3 {}", e3_id);
+                }
+                self.publish_share_complaints(invalid, ec)
+            }
+            VerificationKind::ShareComplaints => {
+                let Some(plan) = self.pending_complaint_plan.take() else {
+                    warn!(
+                        "Complaint verification completed for E3 {} without a pending plan",
                         e3_id
                     );
-                    self.proceed_with_decryption_key_calculation(None, ec)
-                } else {
-                    let threshold = state.threshold_m;
-                    let total = state.threshold_n;
-                    let dishonest_count = (msg.dishonest_parties.len() as u64).min(total);
-                    let honest_count = total - dishonest_count;
-
-                    if honest_count <= threshold {
-                        warn!(
-                            "Too few honest parties for E3 {} ({} honest, need at least {}) — cannot proceed",
-                            e3_id, honest_count, threshold + 1
-                        );
-                        // Clear pending shares
-                        self.pending_shares.clear();
-                        self.bus.publish(
-                            E3Failed {
-                                e3_id: e3_id.clone(),
-                                failed_at_stage: E3Stage::CommitteeFinalized,
-                                reason: FailureReason::InsufficientCommitteeMembers,
-                            },
-                            ec,
-                        )?;
-                        return Ok(());
-                    }
-
-                    let dishonest_set: HashSet<u64> = msg.dishonest_parties.into_iter().collect();
-                    info!(
-                        "Proceeding with {} honest parties for E3 {} ({} dishonest excluded)",
-                        honest_count,
-                        e3_id,
-                        dishonest_set.len()
-                    );
-                    self.proceed_with_decryption_key_calculation(Some(dishonest_set), ec)
-                }
+                    return Ok(());
+                };
+                self.finish_complaint_round(plan.decide(&msg.dishonest_parties), ec)
             }
             VerificationKind::DecryptionProofs => {
                 // C4 verification complete — update honest set and publish KeyshareCreated
@@ -1201,6 +1270,307 @@ impl ThresholdKeyshare {
         }
     }
 
+    /// Sign and broadcast our complaint ballot: missing-share complaints for every dealer we
+    /// hold no usable share from, invalid-proof complaints (with the dealer's signed proofs as
+    /// evidence) for every dealer whose proofs failed. An empty ballot still has to be sent so
+    /// the round can close early.
+    fn publish_share_complaints(
+        &mut self,
+        invalid: BTreeSet<u64>,
+        ec: EventContext<Sequenced>,
+    ) -> Result<()> {
+        let state = self.state.try_get()?;
+        let mut complaints: Vec<DkgComplaint> = self
+            .pending_missing_dealers
+            .iter()
+            .map(|&accused_party_id| DkgComplaint {
+                accused_party_id,
+                reason: DkgComplaintReason::MissingShare,
+                evidence: Vec::new(),
+            })
+            .collect();
+        for accused_party_id in invalid {
+            let dealer = state
+                .committee
+                .get(accused_party_id as usize)
+                .and_then(|addr| addr.parse::<Address>().ok());
+            let evidence = self
+                .pending_share_proofs
+                .get(&accused_party_id)
+                .cloned()
+                .unwrap_or_default();
+            // Proofs not signed by the dealer are no evidence against it; ask it to reveal
+            // the share publicly instead.
+            let attributable = dealer.is_some_and(|dealer| {
+                !evidence.is_empty()
+                    && evidence.iter().all(|p| {
+                        p.payload.e3_id == state.e3_id && p.verify_address(&dealer).unwrap_or(false)
+                    })
+            });
+            complaints.push(if attributable {
+                DkgComplaint {
+                    accused_party_id,
+                    reason: DkgComplaintReason::InvalidShareProofs,
+                    evidence,
+                }
+            } else {
+                self.pending_missing_dealers.insert(accused_party_id);
+                DkgComplaint {
+                    accused_party_id,
+                    reason: DkgComplaintReason::MissingShare,
+                    evidence: Vec::new(),
+                }
+            });
+        }
+        self.pending_share_proofs.clear();
+
+        info!(
+            "Publishing DKG share complaints for E3 {} ({} complaints)",
+            state.e3_id,
+            complaints.len()
+        );
+        let ballot = DkgShareComplaints::sign(
+            state.e3_id.clone(),
+            state.party_id,
+            complaints,
+            &self.signer,
+        )?;
+        self.bus.publish(ballot, ec)?;
+        Ok(())
+    }
+
+    /// Answer missing-share complaints against us and hand the ballot to the collector.
+    pub fn handle_dkg_share_complaints(
+        &mut self,
+        msg: TypedEvent<DkgShareComplaints>,
+        self_addr: Addr<Self>,
+    ) -> Result<()> {
+        let state = self.state.try_get()?;
+        if !matches!(
+            state.state,
+            KeyshareState::CollectingEncryptionKeys(_)
+                | KeyshareState::GeneratingThresholdShare(_)
+                | KeyshareState::AggregatingDecryptionKey(_)
+        ) || state.honest_parties.is_some()
+        {
+            trace!(
+                e3_id = %state.e3_id,
+                state = state.variant_name(),
+                party_id = msg.party_id,
+                "Ignoring DkgShareComplaints outside the complaint round"
+            );
+            return Ok(());
+        }
+
+        let accused_of_missing = msg.complaints.iter().any(|c| {
+            c.accused_party_id == state.party_id && c.reason == DkgComplaintReason::MissingShare
+        });
+        if accused_of_missing && self.justified_accusers.insert(msg.party_id) {
+            match self.sent_shares.get(&msg.party_id) {
+                Some(sent) => {
+                    info!(
+                        "Party {} reported our share missing for E3 {} — publishing justification",
+                        msg.party_id, state.e3_id
+                    );
+                    let justification = DkgShareJustification {
+                        e3_id: state.e3_id.clone(),
+                        accuser_party_id: msg.party_id,
+                        share: sent.share.clone(),
+                        signed_c2a_proof: sent.signed_c2a_proof.clone(),
+                        signed_c2b_proof: sent.signed_c2b_proof.clone(),
+                        signed_c3a_proofs: sent.signed_c3a_proofs.clone(),
+                        signed_c3b_proofs: sent.signed_c3b_proofs.clone(),
+                        signature: ArcBytes::default(),
+                    }
+                    .sign(&self.signer)?;
+                    self.bus.publish(justification, msg.get_ctx().clone())?;
+                }
+                None => warn!(
+                    "Party {} reported our share missing for E3 {} but we have no record of it",
+                    msg.party_id, state.e3_id
+                ),
+            }
+        }
+
+        let collector = self.ensure_collector(self_addr)?;
+        collector.do_send(msg);
+        Ok(())
+    }
+
+    pub fn handle_dkg_share_justification(
+        &mut self,
+        msg: TypedEvent<DkgShareJustification>,
+        self_addr: Addr<Self>,
+    ) -> Result<()> {
+        let state = self.state.try_get()?;
+        if !matches!(
+            state.state,
+            KeyshareState::CollectingEncryptionKeys(_)
+                | KeyshareState::GeneratingThresholdShare(_)
+                | KeyshareState::AggregatingDecryptionKey(_)
+        ) || state.honest_parties.is_some()
+        {
+            trace!(
+                e3_id = %state.e3_id,
+                state = state.variant_name(),
+                dealer = msg.dealer_party_id(),
+                "Ignoring DkgShareJustification outside the complaint round"
+            );
+            return Ok(());
+        }
+        let collector = self.ensure_collector(self_addr)?;
+        collector.do_send(msg);
+        Ok(())
+    }
+
+    /// 5b. ShareComplaintsClosed - Re-verify complaint evidence and justifications
+    pub fn handle_share_complaints_closed(
+        &mut self,
+        msg: TypedEvent<ShareComplaintsClosed>,
+    ) -> Result<()> {
+        let (msg, ec) = msg.into_components();
+        let state = self.state.try_get()?;
+        let current: AggregatingDecryptionKey = state.clone().try_into()?;
+        let mut plan = msg.plan;
+        plan.retain_complete_justifications(&self.expected_share_proofs(&current)?);
+
+        let share_proofs = plan.to_verify();
+        if share_proofs.is_empty() {
+            return self.finish_complaint_round(plan.decide(&BTreeSet::new()), ec);
+        }
+
+        info!(
+            "Dispatching complaint verification for E3 {} ({} dealers)",
+            state.e3_id,
+            share_proofs.len()
+        );
+        self.pending_complaint_plan = Some(plan);
+        let committee_size = CiphernodesCommitteeSize::from_threshold(
+            state.threshold_m as usize,
+            state.threshold_n as usize,
+        )?;
+        self.bus.publish(
+            ShareVerificationDispatched {
+                e3_id: state.e3_id.clone(),
+                kind: VerificationKind::ShareComplaints,
+                share_proofs,
+                decryption_proofs: Vec::new(),
+                pre_dishonest: BTreeSet::new(),
+                params_preset: self.share_enc_preset,
+                committee_size,
+            },
+            ec,
+        )?;
+        Ok(())
+    }
+
+    /// Escalate the dealers our round found faulty, adopt shares revealed to us, and compute
+    /// the decryption key once every faulty dealer has been expelled on-chain.
+    ///
+    /// Nodes may reach different verdicts, so the only dealers left out of the key are the
+    /// chain-ordered `expelled_parties`; a dealer we could not clear is waited on until its
+    /// `CommitteeMemberExpelled` arrives.
+    fn finish_complaint_round(
+        &mut self,
+        decision: ComplaintDecision,
+        ec: EventContext<Sequenced>,
+    ) -> Result<()> {
+        let state = self.state.try_get()?;
+        let e3_id = state.get_e3_id();
+
+        for (&party_id, &reason) in &decision.disqualified {
+            let address = state
+                .committee
+                .get(party_id as usize)
+                .and_then(|addr| addr.parse::<Address>().ok())
+                .unwrap_or_default();
+            warn!(
+                "Escalating dealer {} ({}) for E3 {}: {}",
+                party_id, address, e3_id, reason
+            );
+            self.bus.publish(
+                DkgDealerDisqualified {
+                    e3_id: e3_id.clone(),
+                    party_id,
+                    address,
+                    reason,
+                    accusers: decision
+                        .accusers
+                        .get(&party_id)
+                        .cloned()
+                        .unwrap_or_default(),
+                },
+                ec.clone(),
+            )?;
+        }
+
+        for share in decision.recovered_shares {
+            info!(
+                "Adopting justified share from dealer {} for E3 {}",
+                share.party_id, e3_id
+            );
+            self.pending_shares.retain(|s| s.party_id != share.party_id);
+            self.pending_shares.push(share);
+        }
+        self.pending_shares.sort_by_key(|s| s.party_id);
+        self.pending_missing_dealers.clear();
+
+        self.awaiting_expulsion = decision
+            .disqualified
+            .into_keys()
+            .filter(|party_id| !state.expelled_parties.contains(party_id))
+            .collect();
+        if !self.awaiting_expulsion.is_empty() {
+            info!(
+                "Waiting for on-chain expulsion of dealers {:?} before computing the decryption key for E3 {}",
+                self.awaiting_expulsion, e3_id
+            );
+            return Ok(());
+        }
+        self.proceed_after_complaints(ec)
+    }
+
+    /// Compute the decryption key from every dealer not expelled on-chain, if at least
+    /// `threshold_m + 1` of them are left.
+    fn proceed_after_complaints(&mut self, ec: EventContext<Sequenced>) -> Result<()> {
+        let state = self.state.try_get()?;
+        let e3_id = state.get_e3_id();
+
+        let threshold = state.threshold_m;
+        let total = state.threshold_n;
+        let dishonest_set: HashSet<u64> = state.expelled_parties.clone();
+        let dishonest_count = (dishonest_set.len() as u64).min(total);
+        let honest_count = total - dishonest_count;
+        if honest_count <= threshold {
+            warn!(
+                "Too few honest dealers for E3 {} ({} honest, need at least {}) after the complaint round — cannot proceed",
+                e3_id, honest_count, threshold + 1
+            );
+            self.pending_shares.clear();
+            self.bus.publish(
+                E3Failed {
+                    e3_id: e3_id.clone(),
+                    failed_at_stage: E3Stage::CommitteeFinalized,
+                    reason: FailureReason::InsufficientCommitteeMembers,
+                },
+                ec,
+            )?;
+            return Ok(());
+        }
+
+        if dishonest_set.is_empty() {
+            info!("No dealers expelled for E3 {} — proceeding", e3_id);
+            return self.proceed_with_decryption_key_calculation(None, ec);
+        }
+        info!(
+            "Proceeding with {} honest dealers for E3 {} ({} expelled)",
+            honest_count,
+            e3_id,
+            dishonest_set.len()
+        );
+        self.proceed_with_decryption_key_calculation(Some(dishonest_set), ec)
+    }
+
     /// After verification, decrypt shares from honest parties and compute decryption key.
     /// C4 proof generation is deferred to ProofRequestActor via DecryptionShareProofsPending.
     fn proceed_with_decryption_key_calculation(
@@ -1900,6 +2270,20 @@ impl Handler<InterfoldEvent> for ThresholdKeyshare {
             InterfoldEventData::ShareVerificationComplete(data) => {
                 self.notify_sync(ctx, TypedEvent::new(data, ec))
             }
+            InterfoldEventData::DkgShareComplaints(data) => {
+                if let Err(err) =
+                    self.handle_dkg_share_complaints(TypedEvent::new(data, ec), ctx.address())
+                {
+                    error!("Failed to handle DkgShareComplaints: {err}");
+                }
+            }
+            InterfoldEventData::DkgShareJustification(data) => {
+                if let Err(err) =
+                    self.handle_dkg_share_justification(TypedEvent::new(data, ec), ctx.address())
+                {
+                    error!("Failed to handle DkgShareJustification: {err}");
+                }
+            }
             InterfoldEventData::ComputeResponse(data) => {
                 self.notify_sync(ctx, TypedEvent::new(data, ec))
             }
@@ -2008,6 +2392,21 @@ impl Handler<TypedEvent<AllThresholdSharesCollected>> for ThresholdKeyshare {
     }
 }
 
+impl Handler<TypedEvent<ShareComplaintsClosed>> for ThresholdKeyshare {
+    type Result = ();
+    fn handle(
+        &mut self,
+        msg: TypedEvent<ShareComplaintsClosed>,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        trap(
+            EType::KeyGeneration,
+            &self.bus.with_ec(msg.get_ctx()),
+            || self.handle_share_complaints_closed(msg),
+        )
+    }
+}
+
 impl Handler<TypedEvent<CiphertextOutputPublished>> for ThresholdKeyshare {
     type Result = ();
     fn handle(
@@ -2148,6 +2547,12 @@ impl Handler<E3RequestComplete> for ThresholdKeyshare {
         self.pending_share_decryption_data = None;
         self.pending_c4_verification_shares = None;
         self.pending_keyshare_publish = false;
+        self.pending_share_proofs.clear();
+        self.pending_missing_dealers.clear();
+        self.sent_shares.clear();
+        self.justified_accusers.clear();
+        self.pending_complaint_plan = None;
+        self.awaiting_expulsion.clear();
         self.notify_sync(ctx, Die);
     }
 }
@@ -2221,6 +2626,7 @@ mod tests {
         let actor = ThresholdKeyshare::new(ThresholdKeyshareParams {
             bus,
             cipher: Arc::new(Cipher::from_password("test-password").await?),
+            signer: PrivateKeySigner::random(),
             state: test_state(),
             share_enc_preset: DEFAULT_BFV_PRESET,
         })
@@ -7,22 +7,36 @@
 use std::time::Duration;
 
 use actix::{Actor, ActorContext, Addr, AsyncContext, Handler, Message, SpawnHandle};
+use alloy::primitives::Address;
 use e3_events::{
-    E3id, EventContext, Sequenced, ThresholdShareCollectionFailed, ThresholdShareCreated,
-    TypedEvent,
+    DkgShareComplaints, DkgShareJustification, E3id, EventContext, Sequenced,
+    ThresholdShareCollectionFailed, ThresholdShareCreated, TypedEvent,
 };
 use e3_trbfv::PartyId;
 use e3_utils::MAILBOX_LIMIT;
 use tracing::{info, warn};
 
 use crate::actors::threshold_keyshare::{AllThresholdSharesCollected, ThresholdKeyshare};
-use crate::domain::{ReceivedShareProofs, ShareCollectOutcome, ThresholdShareCollection};
+use crate::domain::{
+    ComplaintVerificationPlan, DkgComplaintRound, ReceivedShareProofs, ShareCollectOutcome,
+    ThresholdShareCollection,
+};
 
 /// Message sent when threshold share collection times out.
 #[derive(Message, Clone, Debug)]
 #[rtype(result = "()")]
 pub struct ThresholdShareCollectionTimeout;
 
+/// Message sent when the complaint window closes.
+#[derive(Message, Clone, Debug)]
+#[rtype(result = "()")]
+pub struct ShareComplaintWindowElapsed;
+
+/// Message sent when the grace period for accused dealers runs out.
+#[derive(Message, Clone, Debug)]
+#[rtype(result = "()")]
+pub struct JustificationGraceElapsed;
+
 /// Remove this party from `todo` so collection finishes without it.
 #[derive(Message, Clone, Debug)]
 #[rtype(result = "()")]
@@ -31,31 +45,61 @@ pub struct ExpelPartyFromShareCollection {
     pub ec: EventContext<Sequenced>,
 }
 
-/// Thin actix shell around [`ThresholdShareCollection`]; owns the mailbox,
-/// timeout timer and the handle to the parent keyshare actor.
+/// Sent to the parent once the complaint round is closed.
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct ShareComplaintsClosed {
+    pub plan: ComplaintVerificationPlan,
+}
+
+/// Thin actix shell around [`ThresholdShareCollection`] and the [`DkgComplaintRound`] that
+/// follows it; owns the mailbox, both timers and the handle to the parent keyshare actor.
 pub struct ThresholdShareCollector {
     e3_id: E3id,
     parent: Addr<ThresholdKeyshare>,
     collection: ThresholdShareCollection,
+    complaints: DkgComplaintRound,
+    threshold_m: u64,
     timeout: Duration,
+    complaint_timeout: Duration,
+    justification_grace: Duration,
     timeout_handle: Option<SpawnHandle>,
+    window_elapsed: bool,
+    /// `None` until only accused dealers are outstanding, then whether their grace ran out.
+    grace_elapsed: Option<bool>,
+    /// Context of the latest input, used for messages triggered by timers.
+    last_ec: Option<EventContext<Sequenced>>,
 }
 
 impl ThresholdShareCollector {
     /// Excludes `own_party_id` from `todo` (own share is consumed locally for C4).
+    /// `committee` holds the operator addresses in party-id order for ballot checks.
+    #[allow(clippy::too_many_arguments)]
     pub fn setup(
         parent: Addr<ThresholdKeyshare>,
         total: u64,
         own_party_id: u64,
+        threshold_m: u64,
+        committee: Vec<Address>,
+        expelled: Vec<PartyId>,
         e3_id: E3id,
         timeout: Duration,
+        complaint_timeout: Duration,
+        justification_grace: Duration,
     ) -> Addr<Self> {
         let collector = Self {
             collection: ThresholdShareCollection::new(e3_id.clone(), total, own_party_id),
+            complaints: DkgComplaintRound::new(e3_id.clone(), own_party_id, committee, expelled),
             e3_id,
             parent,
+            threshold_m,
             timeout,
+            complaint_timeout,
+            justification_grace,
             timeout_handle: None,
+            window_elapsed: false,
+            grace_elapsed: None,
+            last_ec: None,
         };
         collector.start()
     }
@@ -76,6 +120,38 @@ impl ThresholdShareCollector {
             self.parent.do_send(event);
         }
     }
+
+    /// Close the complaint round once our own ballot is in and either every ballot and
+    /// justification has arrived or the window has elapsed. Once only accused dealers are
+    /// outstanding they get [`Self::justification_grace`] instead of the rest of the window.
+    fn try_close_complaints(&mut self, ctx: &mut actix::Context<Self>) {
+        if !self.complaints.has_own_ballot() {
+            return;
+        }
+        if self.grace_elapsed.is_none() && self.complaints.waits_only_on_accused() {
+            info!(
+                e3_id = %self.e3_id,
+                grace = ?self.justification_grace,
+                "Only accused dealers outstanding, waiting for justifications"
+            );
+            self.grace_elapsed = Some(false);
+            ctx.notify_later(JustificationGraceElapsed, self.justification_grace);
+        }
+        if !(self.window_elapsed || self.grace_elapsed == Some(true) || self.complaints.is_ready())
+        {
+            return;
+        }
+        let Some(ec) = self.last_ec.clone() else {
+            return;
+        };
+        let Some(plan) = self.complaints.close() else {
+            return;
+        };
+        info!(e3_id = %self.e3_id, "Share complaint round closed");
+        self.parent
+            .do_send(TypedEvent::new(ShareComplaintsClosed { plan }, ec));
+        ctx.stop();
+    }
 }
 
 impl Actor for ThresholdShareCollector {
@@ -85,12 +161,14 @@ impl Actor for ThresholdShareCollector {
         ctx.set_mailbox_capacity(MAILBOX_LIMIT);
         info!(
             e3_id = %self.e3_id,
-            "ThresholdShareCollector started, scheduling timeout in {:?}",
-            self.timeout
+            "ThresholdShareCollector started, scheduling timeout in {:?} and complaint window in {:?}",
+            self.timeout,
+            self.complaint_timeout
         );
         // Schedule timeout
         let handle = ctx.notify_later(ThresholdShareCollectionTimeout, self.timeout);
         self.timeout_handle = Some(handle);
+        ctx.notify_later(ShareComplaintWindowElapsed, self.complaint_timeout);
     }
 }
 
@@ -103,6 +181,7 @@ impl Handler<TypedEvent<ThresholdShareCreated>> for ThresholdShareCollector {
     ) -> Self::Result {
         let (msg, ec) = msg.into_components();
         info!("ThresholdShareCollector: ThresholdShareCreated received by collector");
+        self.last_ec = Some(ec.clone());
         let proofs = ReceivedShareProofs {
             signed_c2a_proof: msg.signed_c2a_proof,
             signed_c2b_proof: msg.signed_c2b_proof,
@@ -132,6 +211,22 @@ impl Handler<ThresholdShareCollectionTimeout> for ThresholdShareCollector {
             missing_parties.len()
         );
 
+        // Missing dealers can still be complained about and justify themselves, as long as
+        // enough shares (ours included) arrived for the key to be viable without them.
+        let (shares, proofs) = self.collection.take_received();
+        if shares.len() as u64 + 1 > self.threshold_m {
+            if let Some(ec) = self.last_ec.clone() {
+                self.timeout_handle = None;
+                let event: TypedEvent<AllThresholdSharesCollected> = TypedEvent::new(
+                    AllThresholdSharesCollected::new(shares, proofs)
+                        .with_missing_parties(missing_parties.into_iter().collect()),
+                    ec,
+                );
+                self.parent.do_send(event);
+                return;
+            }
+        }
+
         self.parent.do_send(ThresholdShareCollectionFailed {
             e3_id: self.e3_id.clone(),
             reason: format!(
@@ -152,6 +247,7 @@ impl Handler<ExpelPartyFromShareCollection> for ThresholdShareCollector {
         msg: ExpelPartyFromShareCollection,
         ctx: &mut Self::Context,
     ) -> Self::Result {
+        self.complaints.expel(msg.party_id);
         let outcome = self.collection.expel(msg.party_id);
         if matches!(outcome, ShareCollectOutcome::Completed { .. }) {
             info!(
@@ -159,6 +255,56 @@ impl Handler<ExpelPartyFromShareCollection> for ThresholdShareCollector {
                 "All remaining threshold shares collected after party expulsion!"
             );
         }
+        self.last_ec = Some(msg.ec.clone());
         self.complete(ctx, msg.ec, outcome);
+        self.try_close_complaints(ctx);
+    }
+}
+
+impl Handler<TypedEvent<DkgShareComplaints>> for ThresholdShareCollector {
+    type Result = ();
+    fn handle(
+        &mut self,
+        msg: TypedEvent<DkgShareComplaints>,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let (msg, ec) = msg.into_components();
+        if self.complaints.on_ballot(&msg) {
+            self.last_ec = Some(ec);
+            self.try_close_complaints(ctx);
+        }
+    }
+}
+
+impl Handler<TypedEvent<DkgShareJustification>> for ThresholdShareCollector {
+    type Result = ();
+    fn handle(
+        &mut self,
+        msg: TypedEvent<DkgShareJustification>,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let (msg, ec) = msg.into_components();
+        if self.complaints.on_justification(msg) {
+            self.last_ec = Some(ec);
+            self.try_close_complaints(ctx);
+        }
+    }
+}
+
+impl Handler<JustificationGraceElapsed> for ThresholdShareCollector {
+    type Result = ();
+    fn handle(&mut self, _: JustificationGraceElapsed, ctx: &mut Self::Context) -> Self::Result {
+        warn!(e3_id = %self.e3_id, "Justification grace period elapsed");
+        self.grace_elapsed = Some(true);
+        self.try_close_complaints(ctx);
+    }
+}
+
+impl Handler<ShareComplaintWindowElapsed> for ThresholdShareCollector {
+    type Result = ();
+    fn handle(&mut self, _: ShareComplaintWindowElapsed, ctx: &mut Self::Context) -> Self::Result {
+        warn!(e3_id = %self.e3_id, "Share complaint window elapsed");
+        self.window_elapsed = true;
+        self.try_close_complaints(ctx);
     }
 }
@@ -0,0 +1,643 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Pure tally logic for the DKG complaint/justification phase.
+//!
+//! After verifying the shares addressed to it, every committee member broadcasts a signed
+//! ballot of complaints. A dealer accused of withholding a share may answer with a public
+//! justification signed over the share. Once every ballot is in (or the window elapses) the
+//! round is closed into a [`ComplaintVerificationPlan`]: the evidence and justification proofs
+//! are re-verified and [`ComplaintVerificationPlan::decide`] yields the local verdict. The
+//! verdict only escalates dealers to the slashing flow; they leave the key once expelled
+//! on-chain, so every honest node excludes the same set.
+//!
+//! No actix/timer/bus dependencies — plain synchronous state plus tracing.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use alloy::primitives::Address;
+use e3_events::{
+    DkgComplaintReason, DkgShareComplaints, DkgShareJustification, E3id, PartyId,
+    PartyProofsToVerify, ProofType, SignedProofPayload, ThresholdShare,
+};
+use tracing::{info, warn};
+
+use crate::domain::ReceivedShareProofs;
+
+/// Proof counts every dealer must provide per recipient, derived from our own (trusted) shares.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ExpectedShareProofs {
+    pub c3a: usize,
+    pub c3b: usize,
+    pub num_esi: usize,
+}
+
+impl ExpectedShareProofs {
+    pub fn is_complete(&self, share: &ThresholdShare, proofs: &ReceivedShareProofs) -> bool {
+        proofs.signed_c2a_proof.is_some()
+            && proofs.signed_c2b_proof.is_some()
+            && proofs.signed_c3a_proofs.len() == self.c3a
+            && proofs.signed_c3b_proofs.len() == self.c3b
+            && share.esi_sss.len() == self.num_esi
+    }
+}
+
+/// Complaints filed against a single dealer.
+#[derive(Clone, Debug, Default)]
+struct DealerComplaints {
+    /// Accuser party id → reason.
+    accusers: BTreeMap<PartyId, DkgComplaintReason>,
+    /// Deduplicated signed proofs attached as evidence.
+    evidence: Vec<SignedProofPayload>,
+}
+
+impl DealerComplaints {
+    fn missing_share_accusers(&self) -> impl Iterator<Item = PartyId> + '_ {
+        self.accusers
+            .iter()
+            .filter(|(_, r)| **r == DkgComplaintReason::MissingShare)
+            .map(|(a, _)| *a)
+    }
+}
+
+fn push_unique(
+    into: &mut Vec<SignedProofPayload>,
+    proofs: impl IntoIterator<Item = SignedProofPayload>,
+) {
+    for proof in proofs {
+        if !into.contains(&proof) {
+            into.push(proof);
+        }
+    }
+}
+
+fn is_share_proof(proof_type: ProofType) -> bool {
+    matches!(
+        proof_type,
+        ProofType::C2aSkShareComputation
+            | ProofType::C2bESmShareComputation
+            | ProofType::C3aSkShareEncryption
+            | ProofType::C3bESmShareEncryption
+    )
+}
+
+/// Pure tally for the complaint phase of one E3.
+pub(crate) struct DkgComplaintRound {
+    e3_id: E3id,
+    own_party_id: PartyId,
+    /// Operator addresses in party-id order.
+    committee: Vec<Address>,
+    /// Parties whose ballot has not arrived yet.
+    awaiting: BTreeSet<PartyId>,
+    complaints: BTreeMap<PartyId, DealerComplaints>,
+    /// `(dealer, accuser)` → justification.
+    justifications: BTreeMap<(PartyId, PartyId), DkgShareJustification>,
+    closed: bool,
+}
+
+impl DkgComplaintRound {
+    pub fn new(
+        e3_id: E3id,
+        own_party_id: PartyId,
+        committee: Vec<Address>,
+        expelled: impl IntoIterator<Item = PartyId>,
+    ) -> Self {
+        let mut awaiting: BTreeSet<PartyId> = (0..committee.len() as PartyId).collect();
+        for party_id in expelled {
+            awaiting.remove(&party_id);
+        }
+        Self {
+            e3_id,
+            own_party_id,
+            committee,
+            awaiting,
+            complaints: BTreeMap::new(),
+            justifications: BTreeMap::new(),
+            closed: false,
+        }
+    }
+
+    fn signed_by(&self, proof: &SignedProofPayload, party_id: PartyId) -> bool {
+        let Some(expected) = self.committee.get(party_id as usize) else {
+            return false;
+        };
+        proof.payload.e3_id == self.e3_id
+            && is_share_proof(proof.payload.proof_type)
+            && proof.verify_address(expected).unwrap_or(false)
+    }
+
+    /// Record a complaint ballot. Returns `false` if it was rejected.
+    pub fn on_ballot(&mut self, ballot: &DkgShareComplaints) -> bool {
+        if self.closed || !self.awaiting.contains(&ballot.party_id) {
+            return false;
+        }
+        let signer_ok = match (
+            self.committee.get(ballot.party_id as usize),
+            ballot.recover_address(),
+        ) {
+            (Some(expected), Ok(recovered)) => *expected == recovered,
+            _ => false,
+        };
+        if !signer_ok {
+            warn!(
+                e3_id = %self.e3_id,
+                party_id = ballot.party_id,
+                "Rejecting complaint ballot not signed by the committee member"
+            );
+            return false;
+        }
+
+        self.awaiting.remove(&ballot.party_id);
+        for complaint in &ballot.complaints {
+            let accused = complaint.accused_party_id;
+            if accused == ballot.party_id || accused as usize >= self.committee.len() {
+                continue;
+            }
+            if complaint.reason == DkgComplaintReason::InvalidShareProofs
+                && (complaint.evidence.is_empty()
+                    || !complaint
+                        .evidence
+                        .iter()
+                        .all(|p| self.signed_by(p, accused)))
+            {
+                warn!(
+                    e3_id = %self.e3_id,
+                    accuser = ballot.party_id,
+                    accused,
+                    "Ignoring invalid-proof complaint without evidence signed by the dealer"
+                );
+                continue;
+            }
+            info!(
+                e3_id = %self.e3_id,
+                accuser = ballot.party_id,
+                accused,
+                reason = %complaint.reason,
+                "Recorded DKG share complaint"
+            );
+            let entry = self.complaints.entry(accused).or_default();
+            entry.accusers.insert(ballot.party_id, complaint.reason);
+            push_unique(&mut entry.evidence, complaint.evidence.iter().cloned());
+        }
+        true
+    }
+
+    /// Record a dealer's justification. Returns `false` if it was rejected.
+    pub fn on_justification(&mut self, justification: DkgShareJustification) -> bool {
+        let dealer = justification.dealer_party_id();
+        if self.closed
+            || justification.e3_id != self.e3_id
+            || dealer as usize >= self.committee.len()
+        {
+            return false;
+        }
+        let signer_ok = match (
+            self.committee.get(dealer as usize),
+            justification.recover_address(),
+        ) {
+            (Some(expected), Ok(recovered)) => *expected == recovered,
+            _ => false,
+        };
+        if !signer_ok {
+            warn!(
+                e3_id = %self.e3_id,
+                dealer,
+                "Rejecting justification whose share is not signed by the dealer"
+            );
+            return false;
+        }
+        let proofs = justification.signed_proofs();
+        if proofs.is_empty() || !proofs.iter().all(|p| self.signed_by(p, dealer)) {
+            warn!(
+                e3_id = %self.e3_id,
+                dealer,
+                "Rejecting justification whose proofs are not signed by the dealer"
+            );
+            return false;
+        }
+        let key = (dealer, justification.accuser_party_id);
+        if let Some(existing) = self.justifications.get(&key) {
+            if *existing != justification {
+                warn!(
+                    e3_id = %self.e3_id,
+                    dealer,
+                    accuser = justification.accuser_party_id,
+                    "Dealer signed two different justifications; keeping the first"
+                );
+            }
+            return false;
+        }
+        self.justifications.insert(key, justification);
+        true
+    }
+
+    /// Stop waiting on an expelled party and discard complaints filed by it.
+    pub fn expel(&mut self, party_id: PartyId) {
+        self.awaiting.remove(&party_id);
+        self.complaints.remove(&party_id);
+        for entry in self.complaints.values_mut() {
+            entry.accusers.remove(&party_id);
+        }
+        self.complaints.retain(|_, c| !c.accusers.is_empty());
+    }
+
+    pub fn has_own_ballot(&self) -> bool {
+        !self.awaiting.contains(&self.own_party_id)
+    }
+
+    /// Every ballot is in and every missing-share complaint has been answered.
+    pub fn is_ready(&self) -> bool {
+        self.awaiting.is_empty()
+            && self.complaints.iter().all(|(dealer, c)| {
+                c.missing_share_accusers()
+                    .all(|accuser| self.justifications.contains_key(&(*dealer, accuser)))
+            })
+    }
+
+    /// Every outstanding ballot and justification is owed by an accused dealer.
+    ///
+    /// An offline dealer never answers, so once this holds the round only needs a short grace
+    /// period rather than the full complaint window.
+    pub fn waits_only_on_accused(&self) -> bool {
+        self.awaiting
+            .iter()
+            .all(|party_id| self.complaints.contains_key(party_id))
+    }
+
+    /// Close the round. Returns `None` if it was already closed.
+    pub fn close(&mut self) -> Option<ComplaintVerificationPlan> {
+        if self.closed {
+            return None;
+        }
+        self.closed = true;
+        if !self.awaiting.is_empty() {
+            warn!(
+                e3_id = %self.e3_id,
+                missing_ballots = ?self.awaiting,
+                "Closing complaint window without every ballot"
+            );
+        }
+        let complaints = std::mem::take(&mut self.complaints);
+        let justifications = std::mem::take(&mut self.justifications)
+            .into_iter()
+            .filter(|((dealer, accuser), _)| {
+                complaints
+                    .get(dealer)
+                    .and_then(|c| c.accusers.get(accuser))
+                    .is_some_and(|r| *r == DkgComplaintReason::MissingShare)
+            })
+            .collect();
+        Some(ComplaintVerificationPlan {
+            own_party_id: self.own_party_id,
+            complaints,
+            justifications,
+        })
+    }
+}
+
+/// Closed complaint round awaiting proof re-verification.
+#[derive(Debug)]
+pub struct ComplaintVerificationPlan {
+    own_party_id: PartyId,
+    complaints: BTreeMap<PartyId, DealerComplaints>,
+    justifications: BTreeMap<(PartyId, PartyId), DkgShareJustification>,
+}
+
+/// Outcome of the complaint phase.
+#[derive(Debug, Default)]
+pub(crate) struct ComplaintDecision {
+    pub disqualified: BTreeMap<PartyId, DkgComplaintReason>,
+    pub accusers: BTreeMap<PartyId, BTreeSet<PartyId>>,
+    /// Shares revealed to us by dealers that were cleared.
+    pub recovered_shares: Vec<Arc<ThresholdShare>>,
+}
+
+impl ComplaintVerificationPlan {
+    /// Drop justifications whose share or proof set is incomplete; they leave the complaint
+    /// unanswered.
+    pub(crate) fn retain_complete_justifications(&mut self, expected: &ExpectedShareProofs) {
+        self.justifications.retain(|(dealer, accuser), j| {
+            let proofs = ReceivedShareProofs {
+                signed_c2a_proof: j.signed_c2a_proof.clone(),
+                signed_c2b_proof: j.signed_c2b_proof.clone(),
+                signed_c3a_proofs: j.signed_c3a_proofs.clone(),
+                signed_c3b_proofs: j.signed_c3b_proofs.clone(),
+            };
+            let complete = expected.is_complete(&j.share, &proofs);
+            if !complete {
+                warn!(
+                    dealer,
+                    accuser, "Discarding justification with an incomplete proof set"
+                );
+            }
+            complete
+        });
+    }
+
+    /// Per-dealer proofs to re-verify: complaint evidence plus every justification.
+    pub(crate) fn to_verify(&self) -> Vec<PartyProofsToVerify> {
+        let mut by_dealer: BTreeMap<PartyId, Vec<SignedProofPayload>> = BTreeMap::new();
+        for (dealer, c) in &self.complaints {
+            push_unique(
+                by_dealer.entry(*dealer).or_default(),
+                c.evidence.iter().cloned(),
+            );
+        }
+        for ((dealer, _), j) in &self.justifications {
+            push_unique(by_dealer.entry(*dealer).or_default(), j.signed_proofs());
+        }
+        by_dealer
+            .into_iter()
+            .filter(|(_, proofs)| !proofs.is_empty())
+            .map(|(sender_party_id, signed_proofs)| PartyProofsToVerify {
+                sender_party_id,
+                signed_proofs,
+            })
+            .collect()
+    }
+
+    /// Decide the phase given the dealers whose re-verified proofs failed.
+    ///
+    /// A dealer is disqualified if any of its proofs failed, or if a missing-share complaint
+    /// against it was left unanswered. Complaints whose evidence verifies are dismissed.
+    pub(crate) fn decide(self, failed: &BTreeSet<PartyId>) -> ComplaintDecision {
+        let mut decision = ComplaintDecision::default();
+        for (dealer, c) in &self.complaints {
+            let reason = if failed.contains(dealer) {
+                Some(DkgComplaintReason::InvalidShareProofs)
+            } else if c
+                .missing_share_accusers()
+                .any(|accuser| !self.justifications.contains_key(&(*dealer, accuser)))
+            {
+                Some(DkgComplaintReason::MissingShare)
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                decision.disqualified.insert(*dealer, reason);
+                decision
+                    .accusers
+                    .insert(*dealer, c.accusers.keys().copied().collect());
+            }
+        }
+        decision.recovered_shares = self
+            .justifications
+            .into_iter()
+            .filter(|((dealer, accuser), _)| {
+                *accuser == self.own_party_id && !decision.disqualified.contains_key(dealer)
+            })
+            .map(|(_, j)| j.share)
+            .collect();
+        decision
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::local::PrivateKeySigner;
+    use e3_events::{CircuitName, DkgComplaint, Proof, ProofPayload};
+    use e3_utils::utility_types::ArcBytes;
+
+    fn e3_id() -> E3id {
+        E3id::new("1", 1)
+    }
+
+    fn signers() -> Vec<PrivateKeySigner> {
+        (0..4).map(|_| PrivateKeySigner::random()).collect()
+    }
+
+    fn committee(signers: &[PrivateKeySigner]) -> Vec<Address> {
+        signers.iter().map(|s| s.address()).collect()
+    }
+
+    fn proof(signer: &PrivateKeySigner, proof_type: ProofType, tag: u8) -> SignedProofPayload {
+        SignedProofPayload::sign(
+            ProofPayload {
+                e3_id: e3_id(),
+                proof_type,
+                proof: Proof::new(
+                    CircuitName::ShareEncryption,
+                    ArcBytes::from_bytes(&[tag]),
+                    ArcBytes::from_bytes(&[tag]),
+                ),
+            },
+            signer,
+        )
+        .unwrap()
+    }
+
+    fn ballot(
+        signer: &PrivateKeySigner,
+        party_id: u64,
+        complaints: Vec<DkgComplaint>,
+    ) -> DkgShareComplaints {
+        DkgShareComplaints::sign(e3_id(), party_id, complaints, signer).unwrap()
+    }
+
+    fn missing(accused: u64) -> DkgComplaint {
+        DkgComplaint {
+            accused_party_id: accused,
+            reason: DkgComplaintReason::MissingShare,
+            evidence: Vec::new(),
+        }
+    }
+
+    fn justification(
+        dealer: &PrivateKeySigner,
+        dealer_id: u64,
+        accuser: u64,
+    ) -> DkgShareJustification {
+        DkgShareJustification {
+            e3_id: e3_id(),
+            accuser_party_id: accuser,
+            share: Arc::new(ThresholdShare {
+                party_id: dealer_id,
+                pk_share: ArcBytes::from_bytes(&[]),
+                sk_sss: Default::default(),
+                esi_sss: Vec::new(),
+            }),
+            signed_c2a_proof: Some(proof(dealer, ProofType::C2aSkShareComputation, 1)),
+            signed_c2b_proof: Some(proof(dealer, ProofType::C2bESmShareComputation, 2)),
+            signed_c3a_proofs: vec![proof(dealer, ProofType::C3aSkShareEncryption, 3)],
+            signed_c3b_proofs: Vec::new(),
+            signature: ArcBytes::default(),
+        }
+        .sign(dealer)
+        .unwrap()
+    }
+
+    fn round(signers: &[PrivateKeySigner]) -> DkgComplaintRound {
+        DkgComplaintRound::new(e3_id(), 0, committee(signers), [])
+    }
+
+    #[test]
+    fn rejects_ballot_signed_by_another_member() {
+        let s = signers();
+        let mut r = round(&s);
+        assert!(!r.on_ballot(&ballot(&s[2], 1, vec![missing(3)])));
+        assert!(r.on_ballot(&ballot(&s[1], 1, vec![missing(3)])));
+        assert!(!r.on_ballot(&ballot(&s[1], 1, vec![])), "duplicate ballot");
+    }
+
+    #[test]
+    fn unanswered_missing_share_disqualifies_dealer() {
+        let s = signers();
+        let mut r = round(&s);
+        r.on_ballot(&ballot(&s[0], 0, vec![]));
+        r.on_ballot(&ballot(&s[1], 1, vec![missing(3)]));
+        r.on_ballot(&ballot(&s[2], 2, vec![]));
+        r.on_ballot(&ballot(&s[3], 3, vec![]));
+        assert!(!r.is_ready(), "waits for the dealer's justification");
+
+        let plan = r.close().expect("plan");
+        assert!(r.close().is_none());
+        let decision = plan.decide(&BTreeSet::new());
+        assert_eq!(
+            decision.disqualified.get(&3),
+            Some(&DkgComplaintReason::MissingShare)
+        );
+        assert_eq!(decision.accusers[&3], BTreeSet::from([1]));
+    }
+
+    #[test]
+    fn justified_missing_share_recovers_share_for_accuser() {
+        let s = signers();
+        let mut r = round(&s);
+        for (i, signer) in s.iter().enumerate() {
+            let complaints = if i == 0 { vec![missing(2)] } else { vec![] };
+            r.on_ballot(&ballot(signer, i as u64, complaints));
+        }
+        assert!(
+            !r.on_justification(justification(&s[3], 2, 0)),
+            "wrong signer"
+        );
+        assert!(r.on_justification(justification(&s[2], 2, 0)));
+        assert!(r.is_ready());
+
+        let mut plan = r.close().expect("plan");
+        plan.retain_complete_justifications(&ExpectedShareProofs {
+            c3a: 1,
+            c3b: 0,
+            num_esi: 0,
+        });
+        let to_verify = plan.to_verify();
+        assert_eq!(to_verify.len(), 1);
+        assert_eq!(to_verify[0].sender_party_id, 2);
+        assert_eq!(to_verify[0].signed_proofs.len(), 3);
+
+        let decision = plan.decide(&BTreeSet::new());
+        assert!(decision.disqualified.is_empty());
+        assert_eq!(decision.recovered_shares.len(), 1);
+        assert_eq!(decision.recovered_shares[0].party_id, 2);
+    }
+
+    #[test]
+    fn rejects_relayed_justification_with_a_swapped_share() {
+        let s = signers();
+        let mut r = round(&s);
+        r.on_ballot(&ballot(&s[0], 0, vec![missing(2)]));
+
+        // A peer replays the dealer's signed proofs next to a share of its own choosing.
+        let genuine = justification(&s[2], 2, 0);
+        let mut forged = genuine.clone();
+        forged.share = Arc::new(ThresholdShare {
+            party_id: 2,
+            pk_share: ArcBytes::from_bytes(&[0xff]),
+            sk_sss: Default::default(),
+            esi_sss: Vec::new(),
+        });
+        assert!(!r.on_justification(forged.clone()));
+        assert!(!r.on_justification(forged.sign(&s[1]).unwrap()));
+
+        assert!(r.on_justification(genuine.clone()));
+        let decision = r.close().unwrap().decide(&BTreeSet::new());
+        assert_eq!(decision.recovered_shares, vec![genuine.share]);
+    }
+
+    #[test]
+    fn waits_only_on_accused_dealers() {
+        let s = signers();
+        let mut r = round(&s);
+        r.on_ballot(&ballot(&s[0], 0, vec![missing(3)]));
+        r.on_ballot(&ballot(&s[1], 1, vec![missing(3)]));
+        assert!(!r.waits_only_on_accused(), "party 2 has not voted");
+        r.on_ballot(&ballot(&s[2], 2, vec![missing(3)]));
+        assert!(r.waits_only_on_accused());
+        assert!(!r.is_ready());
+    }
+
+    #[test]
+    fn incomplete_justification_leaves_complaint_unanswered() {
+        let s = signers();
+        let mut r = round(&s);
+        r.on_ballot(&ballot(&s[0], 0, vec![missing(2)]));
+        r.on_justification(justification(&s[2], 2, 0));
+        let mut plan = r.close().expect("plan");
+        plan.retain_complete_justifications(&ExpectedShareProofs {
+            c3a: 2,
+            c3b: 0,
+            num_esi: 0,
+        });
+        let decision = plan.decide(&BTreeSet::new());
+        assert!(decision.disqualified.contains_key(&2));
+        assert!(decision.recovered_shares.is_empty());
+    }
+
+    #[test]
+    fn invalid_proof_complaint_requires_dealer_signed_evidence() {
+        let s = signers();
+        let mut r = round(&s);
+        let forged = DkgComplaint {
+            accused_party_id: 3,
+            reason: DkgComplaintReason::InvalidShareProofs,
+            evidence: vec![proof(&s[1], ProofType::C2aSkShareComputation, 9)],
+        };
+        assert!(r.on_ballot(&ballot(&s[1], 1, vec![forged])));
+        let genuine = DkgComplaint {
+            accused_party_id: 3,
+            reason: DkgComplaintReason::InvalidShareProofs,
+            evidence: vec![proof(&s[3], ProofType::C2aSkShareComputation, 9)],
+        };
+        assert!(r.on_ballot(&ballot(&s[2], 2, vec![genuine])));
+
+        let plan = r.close().expect("plan");
+        let to_verify = plan.to_verify();
+        assert_eq!(to_verify.len(), 1);
+        assert_eq!(to_verify[0].signed_proofs.len(), 1);
+
+        // Evidence that verifies dismisses the complaint; failing evidence disqualifies.
+        let decision = plan.decide(&BTreeSet::from([3]));
+        assert_eq!(
+            decision.disqualified.get(&3),
+            Some(&DkgComplaintReason::InvalidShareProofs)
+        );
+        assert_eq!(decision.accusers[&3], BTreeSet::from([2]));
+    }
+
+    #[test]
+    fn expel_stops_waiting_and_drops_complaints_by_party() {
+        let s = signers();
+        let mut r = round(&s);
+        r.on_ballot(&ballot(&s[0], 0, vec![]));
+        r.on_ballot(&ballot(&s[1], 1, vec![missing(2)]));
+        r.on_ballot(&ballot(&s[2], 2, vec![]));
+        assert!(!r.is_ready());
+        r.expel(3);
+        assert!(!r.is_ready());
+        r.expel(1);
+        assert!(r.is_ready());
+        assert!(r
+            .close()
+            .unwrap()
+            .decide(&BTreeSet::new())
+            .disqualified
+            .is_empty());
+    }
+}
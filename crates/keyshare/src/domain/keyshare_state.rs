@@ -194,6 +194,8 @@ pub struct ThresholdKeyshareState {
     /// Honest party IDs in deterministic ascending order (`BTreeSet` guarantees this).
     /// Downstream proof circuits index parties by position in this sorted set.
     pub honest_parties: Option<BTreeSet<u64>>,
+    /// Committee operator addresses in party-id order, captured from `CiphernodeSelected`.
+    pub committee: Vec<String>,
     pub dkg_started_at_unix_secs: Option<u64>,
     pub proof_aggregation_enabled: bool,
     /// Set once `KeyshareCreated` has actually been published from an authorized
@@ -227,6 +229,7 @@ impl ThresholdKeyshareState {
             aggregated_pk: None,
             expelled_parties: HashSet::new(),
             honest_parties: None,
+            committee: Vec::new(),
             dkg_started_at_unix_secs: Some(now_unix_secs()),
             proof_aggregation_enabled,
             keyshare_published: false,
@@ -13,6 +13,7 @@
 mod bfv_keygen;
 mod decryption_key_calculation;
 mod decryption_key_shared_collection;
+mod dkg_complaints;
 mod encryption_key_collection;
 mod keyshare_state;
 mod share_generation;
@@ -27,6 +28,7 @@ pub use keyshare_state::*;
 pub(crate) use bfv_keygen::*;
 pub(crate) use decryption_key_calculation::*;
 pub(crate) use decryption_key_shared_collection::*;
+pub(crate) use dkg_complaints::*;
 pub(crate) use encryption_key_collection::*;
 pub(crate) use share_generation::*;
 pub(crate) use threshold_share_collection::*;
@@ -148,6 +148,19 @@ impl ThresholdShareCollection {
         Some(self.todo.iter().copied().collect())
     }
 
+    /// Hand over whatever arrived before the deadline. Only meaningful after [`Self::timeout`].
+    pub fn take_received(
+        &mut self,
+    ) -> (
+        HashMap<PartyId, Arc<ThresholdShare>>,
+        HashMap<PartyId, ReceivedShareProofs>,
+    ) {
+        (
+            std::mem::take(&mut self.shares),
+            std::mem::take(&mut self.share_proofs),
+        )
+    }
+
     fn finish_if_done(&mut self) -> ShareCollectOutcome {
         if self.todo.is_empty() {
             info!(e3_id = %self.e3_id, "We have received all threshold shares");
@@ -252,4 +265,14 @@ mod tests {
         assert_eq!(missing, vec![0, 2]);
         assert!(c.timeout().is_none());
     }
+
+    #[test]
+    fn take_received_after_timeout_returns_partial_shares() {
+        let mut c = collection();
+        c.receive(share(2), proofs());
+        assert_eq!(c.timeout().expect("missing"), vec![0]);
+        let (shares, proofs) = c.take_received();
+        assert_eq!(shares.keys().copied().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(proofs.len(), 1);
+    }
 }
@@ -11,8 +11,14 @@ pub(crate) const DEFAULT_DKG_WINDOW_SECS: u64 = 7200;
 
 const ENCRYPTION_KEY_CUTOFF_BPS: u64 = 1000;
 const THRESHOLD_SHARE_CUTOFF_BPS: u64 = 6000;
+const SHARE_COMPLAINT_CUTOFF_BPS: u64 = 8000;
 const DECRYPTION_KEY_SHARED_CUTOFF_BPS: u64 = 10000;
 
+pub(crate) const JUSTIFICATION_GRACE_ENV: &str = "E3_SHARE_JUSTIFICATION_GRACE_SECS";
+/// Grace period, as a share of the DKG window, granted to accused dealers once every other
+/// complaint ballot is in.
+const JUSTIFICATION_GRACE_BPS: u64 = 100;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 // Variant names mirror the DKG collection phases they gate; the shared `Collection`
 // suffix is intentional domain vocabulary, not redundant naming.
@@ -20,6 +26,8 @@ const DECRYPTION_KEY_SHARED_CUTOFF_BPS: u64 = 10000;
 pub(crate) enum DkgTimeoutPhase {
     EncryptionKeyCollection,
     ThresholdShareCollection,
+    /// Window for complaint ballots and dealer justifications after share collection.
+    ShareComplaints,
     DecryptionKeySharedCollection,
 }
 
@@ -28,6 +36,7 @@ impl DkgTimeoutPhase {
         match self {
             Self::EncryptionKeyCollection => "encryption-key collection",
             Self::ThresholdShareCollection => "threshold-share collection",
+            Self::ShareComplaints => "share-complaint window",
             Self::DecryptionKeySharedCollection => "decryption-key-shared collection",
         }
     }
@@ -36,6 +45,7 @@ impl DkgTimeoutPhase {
         match self {
             Self::EncryptionKeyCollection => "E3_ENCRYPTION_KEY_COLLECTION_TIMEOUT_SECS",
             Self::ThresholdShareCollection => "E3_THRESHOLD_SHARE_COLLECTION_TIMEOUT_SECS",
+            Self::ShareComplaints => "E3_SHARE_COMPLAINT_TIMEOUT_SECS",
             Self::DecryptionKeySharedCollection => {
                 "E3_DECRYPTION_KEY_SHARED_COLLECTION_TIMEOUT_SECS"
             }
@@ -46,6 +56,7 @@ impl DkgTimeoutPhase {
         match self {
             Self::EncryptionKeyCollection => ENCRYPTION_KEY_CUTOFF_BPS,
             Self::ThresholdShareCollection => THRESHOLD_SHARE_CUTOFF_BPS,
+            Self::ShareComplaints => SHARE_COMPLAINT_CUTOFF_BPS,
             Self::DecryptionKeySharedCollection => DECRYPTION_KEY_SHARED_CUTOFF_BPS,
         }
     }
@@ -129,6 +140,39 @@ pub(crate) fn resolve_timeout_from_inputs(
     }
 }
 
+/// How long the complaint round waits for accused dealers once they are the only ones it is
+/// still waiting on. Bounded by the regular complaint window.
+pub(crate) fn resolve_justification_grace() -> DerivedTimeout {
+    justification_grace_from_inputs(
+        parse_env_secs(JUSTIFICATION_GRACE_ENV),
+        parse_env_secs(DKG_WINDOW_ENV).unwrap_or(DEFAULT_DKG_WINDOW_SECS),
+    )
+}
+
+pub(crate) fn justification_grace_from_inputs(
+    override_secs: Option<u64>,
+    dkg_window_secs: u64,
+) -> DerivedTimeout {
+    if let Some(secs) = override_secs {
+        return DerivedTimeout {
+            duration: Duration::from_secs(secs),
+            description: format!(
+                "justification grace override from {}={}s",
+                JUSTIFICATION_GRACE_ENV, secs
+            ),
+        };
+    }
+    DerivedTimeout {
+        duration: Duration::from_secs(phase_cutoff_secs(dkg_window_secs, JUSTIFICATION_GRACE_BPS)),
+        description: format!(
+            "justification grace derived from {}={}s, {}% of DKG window",
+            DKG_WINDOW_ENV,
+            dkg_window_secs,
+            JUSTIFICATION_GRACE_BPS / 100
+        ),
+    }
+}
+
 fn parse_env_secs(name: &str) -> Option<u64> {
     std::env::var(name)
         .ok()
@@ -173,6 +217,37 @@ mod tests {
         assert_eq!(timeout.duration, Duration::from_secs(3320));
     }
 
+    #[test]
+    fn share_complaint_window_closes_after_share_collection() {
+        let collection = resolve_timeout_from_inputs(
+            DkgTimeoutPhase::ThresholdShareCollection,
+            None,
+            7200,
+            Some(1_000),
+            2_000,
+        );
+        let complaints = resolve_timeout_from_inputs(
+            DkgTimeoutPhase::ShareComplaints,
+            None,
+            7200,
+            Some(1_000),
+            2_000,
+        );
+
+        assert_eq!(complaints.duration, Duration::from_secs(4760));
+        assert!(complaints.duration > collection.duration);
+    }
+
+    #[test]
+    fn justification_grace_is_a_small_share_of_the_window() {
+        let grace = justification_grace_from_inputs(None, 7200);
+        assert_eq!(grace.duration, Duration::from_secs(72));
+        assert_eq!(
+            justification_grace_from_inputs(Some(5), 7200).duration,
+            Duration::from_secs(5)
+        );
+    }
+
     #[test]
     fn collector_override_wins_over_dkg_window() {
         let timeout = resolve_timeout_from_inputs(
@@ -9,28 +9,40 @@ use crate::{
     ThresholdKeyshareState,
 };
 use actix::Actor;
+use alloy::signers::local::PrivateKeySigner;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use e3_crypto::Cipher;
-use e3_data::{AutoPersist, RepositoriesFactory};
-use e3_events::{prelude::*, BusHandle, EType, InterfoldEvent, InterfoldEventData};
+use e3_data::{AutoPersist, RepositoriesFactory, Repository};
+use e3_events::{
+    prelude::*, BusHandle, Committee, E3id, EType, InterfoldEvent, InterfoldEventData, StoreKeys,
+};
 use e3_request::{E3Context, E3ContextSnapshot, E3Extension, META_KEY};
 
 use crate::KeyshareState;
+use std::collections::HashMap;
 use std::sync::Arc;
+use tracing::warn;
 
 pub struct ThresholdKeyshareExtension {
     bus: BusHandle,
     cipher: Arc<Cipher>,
     address: String,
+    signer: PrivateKeySigner,
 }
 
 impl ThresholdKeyshareExtension {
-    pub fn create(bus: &BusHandle, cipher: &Arc<Cipher>, address: &str) -> Box<Self> {
+    pub fn create(
+        bus: &BusHandle,
+        cipher: &Arc<Cipher>,
+        address: &str,
+        signer: PrivateKeySigner,
+    ) -> Box<Self> {
         Box::new(Self {
             bus: bus.clone(),
             cipher: cipher.to_owned(),
             address: address.to_owned(),
+            signer,
         })
     }
 }
@@ -76,6 +88,7 @@ impl E3Extension for ThresholdKeyshareExtension {
                 ThresholdKeyshare::new(ThresholdKeyshareParams {
                     bus: self.bus.clone(),
                     cipher: self.cipher.clone(),
+                    signer: self.signer.clone(),
                     state: container,
                     share_enc_preset: meta
                         .params_preset
@@ -94,7 +107,7 @@ impl E3Extension for ThresholdKeyshareExtension {
             return Ok(());
         };
         // Get the saved state as a persistable
-        let state = ctx
+        let mut state = ctx
             .repositories()
             .threshold_keyshare(&snapshot.e3_id)
             .load()
@@ -105,6 +118,33 @@ impl E3Extension for ThresholdKeyshareExtension {
             return Ok(());
         };
 
+        // State written before the committee was recorded on it: rebuild it from the
+        // committee the sortition stored when it was finalized.
+        if state.get().is_some_and(|s| s.committee.is_empty()) {
+            let committees: Repository<HashMap<E3id, Committee>> = Repository::new(
+                ctx.repositories()
+                    .store
+                    .scope(StoreKeys::finalized_committees()),
+            );
+            match committees
+                .read()
+                .await?
+                .and_then(|mut c| c.remove(&snapshot.e3_id))
+            {
+                Some(committee) => {
+                    let members = committee.members().to_vec();
+                    state.try_mutate_without_context(|mut s| {
+                        s.committee = members;
+                        Ok(s)
+                    })?;
+                }
+                None => warn!(
+                    e3_id = %snapshot.e3_id,
+                    "No finalized committee stored; DKG complaints cannot be checked for this E3"
+                ),
+            }
+        }
+
         // Derive DKG preset from persisted E3Meta
         let Some(meta) = ctx.get_dependency(META_KEY) else {
             return Err(anyhow!(ERROR_KEYSHARE_META_MISSING));
@@ -118,6 +158,7 @@ impl E3Extension for ThresholdKeyshareExtension {
         let value = ThresholdKeyshare::new(ThresholdKeyshareParams {
             bus: self.bus.clone(),
             cipher: self.cipher.clone(),
+            signer: self.signer.clone(),
             state,
             share_enc_preset,
         })
@@ -29,7 +29,7 @@ use e3_events::{
     ComputeResponse, DecryptedSharesAggregationProofRequest,
     DecryptedSharesAggregationProofResponse, DecryptionAggregationRequest,
     DecryptionAggregationResponse, DkgAggregationRequest, DkgAggregationResponse,
-    DkgShareDecryptionProofRequest, DkgShareDecryptionProofResponse, EventPublisher,
+    DkgShareDecryptionProofRequest, DkgShareDecryptionProofResponse, E3id, EventPublisher,
     EventSubscriber, EventType, InterfoldEvent, InterfoldEventData, NodeDkgFoldRequest,
     NodeDkgFoldResponse, NodesFoldStepRequest, NodesFoldStepResponse, PartyVerificationResult,
     PkAggregationProofRequest, PkAggregationProofResponse, PkBfvProofRequest, PkBfvProofResponse,
@@ -196,6 +196,19 @@ impl Multithread {
         addr
     }
 
+    /// Cached proofs and verifications are only reused within an E3's lifetime.
+    fn evict_proof_cache(&self, e3_id: &E3id) {
+        let Some(prover) = self.zk_prover.clone() else {
+            return;
+        };
+        let e3_id = e3_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = prover.evict_e3(&e3_id) {
+                error!("Failed to evict proof cache for E3 {}: {}", e3_id, e);
+            }
+        });
+    }
+
     pub fn create_taskpool(threads: usize, max_tasks: usize) -> TaskPool {
         TaskPool::new(threads, max_tasks)
     }
@@ -212,8 +225,11 @@ impl Handler<InterfoldEvent> for Multithread {
     type Result = ();
     fn handle(&mut self, msg: InterfoldEvent, ctx: &mut Self::Context) -> Self::Result {
         let (data, ec) = msg.into_components();
-        if let InterfoldEventData::ComputeRequest(data) = data {
-            ctx.notify(TypedEvent::new(data, ec))
+        match data {
+            InterfoldEventData::ComputeRequest(data) => ctx.notify(TypedEvent::new(data, ec)),
+            InterfoldEventData::E3RequestComplete(data) => self.evict_proof_cache(&data.e3_id),
+            InterfoldEventData::E3Failed(data) => self.evict_proof_cache(&data.e3_id),
+            _ => (),
         }
     }
 }
@@ -1332,15 +1348,87 @@ fn handle_dkg_share_decryption_proof(
     ))
 }
 
-/// ZK-verify a share proof (inner recursive circuits).
-fn zk_verify_share_proof_bundle(
+/// ZK-verifies every party's signed proofs in parallel (see [`ZkProver::verify_parallel`]).
+///
+/// A party fails on its first proof whose circuit does not match its proof type or that does
+/// not verify; that payload is returned for the accusation path. Results are in `parties` order.
+fn zk_verify_parties(
     prover: &ZkProver,
-    proof: &Proof,
     e3_id_str: &str,
-    party_id: u64,
     artifacts_dir: &str,
-) -> Result<bool, ZkError> {
-    prover.verify_proof(proof, e3_id_str, party_id, artifacts_dir)
+    parties: Vec<(u64, Vec<&e3_events::SignedProofPayload>)>,
+    label: &str,
+) -> Vec<PartyVerificationResult> {
+    let failed =
+        |sender_party_id, signed: Option<&e3_events::SignedProofPayload>| PartyVerificationResult {
+            sender_party_id,
+            all_verified: false,
+            failed_signed_payload: signed.cloned(),
+            recovered_address: None,
+        };
+
+    // 1. Validate CircuitName matches expected circuits for each ProofType
+    let mut results: Vec<Option<PartyVerificationResult>> = Vec::with_capacity(parties.len());
+    for (sender, signed_proofs) in &parties {
+        let mismatch = signed_proofs.iter().find(|signed| {
+            !signed
+                .payload
+                .proof_type
+                .circuit_names()
+                .contains(&signed.payload.proof.circuit)
+        });
+        results.push(mismatch.map(|signed| {
+            info!(
+                "{} circuit mismatch for party {} ({:?}): expected {:?}, got {:?}",
+                label,
+                sender,
+                signed.payload.proof_type,
+                signed.payload.proof_type.circuit_names(),
+                signed.payload.proof.circuit
+            );
+            failed(*sender, Some(signed))
+        }));
+    }
+
+    // 2. ZK proof verification, in parallel across parties
+    let mut owners = Vec::new();
+    let mut proofs = Vec::new();
+    for (party, (sender, signed_proofs)) in parties.iter().enumerate() {
+        if results[party].is_some() {
+            continue;
+        }
+        for (i, signed) in signed_proofs.iter().enumerate() {
+            owners.push((party, i));
+            proofs.push((*sender, &signed.payload.proof));
+        }
+    }
+    let offenders =
+        prover.verify_parallel(&proofs, e3_id_str, CircuitVariant::Recursive, artifacts_dir);
+    // Offenders are sorted, so each party's first failing proof comes first.
+    for index in offenders {
+        let (party, i) = owners[index];
+        if results[party].is_none() {
+            let (sender, signed_proofs) = &parties[party];
+            info!(
+                "{} ZK proof verification failed for party {} ({:?})",
+                label, sender, signed_proofs[i].payload.proof_type
+            );
+            results[party] = Some(failed(*sender, Some(signed_proofs[i])));
+        }
+    }
+
+    parties
+        .iter()
+        .zip(results)
+        .map(|((sender, _), result)| {
+            result.unwrap_or(PartyVerificationResult {
+                sender_party_id: *sender,
+                all_verified: true,
+                failed_signed_payload: None,
+                recovered_address: None,
+            })
+        })
+        .collect()
 }
 
 fn handle_verify_share_proofs(
@@ -1355,59 +1443,12 @@ fn handle_verify_share_proofs(
     // ECDSA validation (signature recovery, signer consistency, e3_id match)
     // is handled by ShareVerificationActor before dispatching to multithread.
     // This function performs ZK-only proof verification.
-    let party_results: Vec<PartyVerificationResult> = req
+    let parties = req
         .party_proofs
-        .into_iter()
-        .map(|party| {
-            let sender = party.sender_party_id;
-
-            for signed_proof in &party.signed_proofs {
-                // 1. Validate CircuitName matches expected circuits for this ProofType
-                let expected_circuits = signed_proof.payload.proof_type.circuit_names();
-                if !expected_circuits.contains(&signed_proof.payload.proof.circuit) {
-                    info!(
-                        "Circuit name mismatch for party {} ({:?}): expected {:?}, got {:?}",
-                        sender,
-                        signed_proof.payload.proof_type,
-                        expected_circuits,
-                        signed_proof.payload.proof.circuit
-                    );
-                    return PartyVerificationResult {
-                        sender_party_id: sender,
-                        all_verified: false,
-                        failed_signed_payload: Some(signed_proof.clone()),
-                        recovered_address: None,
-                    };
-                }
-
-                // 2. ZK proof verification
-                let proof = &signed_proof.payload.proof;
-                let result =
-                    zk_verify_share_proof_bundle(prover, proof, &e3_id_str, sender, &artifacts_dir);
-                match result {
-                    Ok(true) => continue,
-                    Ok(false) | Err(_) => {
-                        info!(
-                            "ZK proof verification failed for party {} ({:?})",
-                            sender, signed_proof.payload.proof_type
-                        );
-                        return PartyVerificationResult {
-                            sender_party_id: sender,
-                            all_verified: false,
-                            failed_signed_payload: Some(signed_proof.clone()),
-                            recovered_address: None,
-                        };
-                    }
-                }
-            }
-            PartyVerificationResult {
-                sender_party_id: sender,
-                all_verified: true,
-                failed_signed_payload: None,
-                recovered_address: None,
-            }
-        })
+        .iter()
+        .map(|party| (party.sender_party_id, party.signed_proofs.iter().collect()))
         .collect();
+    let party_results = zk_verify_parties(prover, &e3_id_str, &artifacts_dir, parties, "Share");
 
     Ok(ComputeResponse::zk(
         ZkResponse::VerifyShareProofs(VerifyShareProofsResponse { party_results }),
@@ -1428,70 +1469,40 @@ fn handle_verify_share_decryption_proofs(
     // ECDSA validation (signature recovery, signer consistency, e3_id match)
     // is handled by ShareVerificationActor before dispatching to multithread.
     // This function performs ZK-only proof verification.
-    let party_results: Vec<PartyVerificationResult> = req
+    //
+    // Guard: an empty esm_decryption_proofs vec would make verification
+    // vacuously true.  Defence-in-depth: reject any party with zero ESM proofs.
+    //
+    // Flatten all signed proofs (SK + ESMs) and verify uniformly.
+    let parties = req
         .party_proofs
-        .into_iter()
+        .iter()
+        .filter(|party| !party.signed_e_sm_decryption_proofs.is_empty())
+        .map(|party| {
+            let all_signed = std::iter::once(&party.signed_sk_decryption_proof)
+                .chain(party.signed_e_sm_decryption_proofs.iter())
+                .collect();
+            (party.sender_party_id, all_signed)
+        })
+        .collect();
+    let mut verified =
+        zk_verify_parties(prover, &e3_id_str, &artifacts_dir, parties, "C4").into_iter();
+    // Results follow the request's party order, with ESM-less parties rejected in place.
+    let party_results = req
+        .party_proofs
+        .iter()
         .map(|party| {
-            let sender = party.sender_party_id;
-
-            // Guard: an empty esm_decryption_proofs vec would make this loop
-            // vacuously true.  Defence-in-depth: reject any party with zero ESM proofs.
             if party.signed_e_sm_decryption_proofs.is_empty() {
-                return PartyVerificationResult {
-                    sender_party_id: sender,
+                PartyVerificationResult {
+                    sender_party_id: party.sender_party_id,
                     all_verified: false,
                     failed_signed_payload: None,
                     recovered_address: None,
-                };
-            }
-
-            // Flatten all signed proofs (SK + ESMs) and verify uniformly.
-            let all_signed: Vec<&e3_events::SignedProofPayload> =
-                std::iter::once(&party.signed_sk_decryption_proof)
-                    .chain(party.signed_e_sm_decryption_proofs.iter())
-                    .collect();
-
-            for signed_proof in &all_signed {
-                // 1. Validate CircuitName matches expected circuits for this ProofType
-                let expected_circuits = signed_proof.payload.proof_type.circuit_names();
-                if !expected_circuits.contains(&signed_proof.payload.proof.circuit) {
-                    info!(
-                        "C4 circuit mismatch for party {}: expected {:?}, got {:?}",
-                        sender, expected_circuits, signed_proof.payload.proof.circuit
-                    );
-                    return PartyVerificationResult {
-                        sender_party_id: sender,
-                        all_verified: false,
-                        failed_signed_payload: Some((*signed_proof).clone()),
-                        recovered_address: None,
-                    };
-                }
-
-                // 2. ZK proof verification
-                let proof = &signed_proof.payload.proof;
-                let result = prover.verify_proof(proof, &e3_id_str, sender, &artifacts_dir);
-                match result {
-                    Ok(true) => continue,
-                    Ok(false) | Err(_) => {
-                        info!(
-                            "C4 ZK proof verification failed for party {} ({:?})",
-                            sender, signed_proof.payload.proof_type
-                        );
-                        return PartyVerificationResult {
-                            sender_party_id: sender,
-                            all_verified: false,
-                            failed_signed_payload: Some((*signed_proof).clone()),
-                            recovered_address: None,
-                        };
-                    }
                 }
-            }
-
-            PartyVerificationResult {
-                sender_party_id: sender,
-                all_verified: true,
-                failed_signed_payload: None,
-                recovered_address: None,
+            } else {
+                verified
+                    .next()
+                    .expect("one result per party with ESM proofs")
             }
         })
         .collect();
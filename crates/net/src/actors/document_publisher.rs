@@ -6,16 +6,17 @@
 
 use crate::{
     domain::{
-        datetime_to_instant_from_now, DocumentPublishingService, EventConversionService,
-        IncomingDocument,
+        datetime_to_instant_from_now, ChunkAssembly, DocumentLayout, DocumentPublishingService,
+        EventConversionService, GossipTopics, IncomingDocument, DOCUMENT_CHUNK_SIZE,
     },
     events::{
-        call_and_await_response, DocumentPublishedNotification, GossipData, NetCommand, NetEvent,
+        call_and_await_response, ChunkManifest, DocumentPublishedNotification, GossipData,
+        NetCommand, NetEvent,
     },
     ContentHash,
 };
 use actix::prelude::*;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use e3_events::{
     prelude::*, trap, trap_fut, BusHandle, CiphernodeSelected, CorrelationId, DecryptionKeyShared,
     DocumentReceived, E3RequestComplete, E3id, EType, EncryptionKeyCreated, EventSource, EventType,
@@ -28,14 +29,30 @@ use e3_utils::{
     retry::{retry_with_backoff, to_retry},
     MAILBOX_LIMIT,
 };
-use futures::TryFutureExt;
-use std::{collections::HashMap, sync::Arc, time::Duration};
-use tokio::sync::{broadcast, mpsc};
-use tracing::{debug, info};
+use futures::{stream, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    sync::{broadcast, mpsc},
+    time::sleep,
+};
+use tracing::{debug, info, warn};
 
 const KADEMLIA_PUT_TIMEOUT: Duration = Duration::from_secs(30);
 const KADEMLIA_GET_TIMEOUT: Duration = Duration::from_secs(30);
 const KADEMLIA_BROADCAST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Number of chunks of one document that are put or fetched concurrently
+const MAX_PARALLEL_CHUNK_TRANSFERS: usize = 8;
+/// Number of rounds in which missing chunks are fetched before a chunked fetch gives up
+const CHUNK_FETCH_ROUNDS: u32 = 6;
+const CHUNK_FETCH_INITIAL_DELAY: Duration = Duration::from_secs(1);
+
+/// Partially fetched chunked documents keyed by E3, document hash and manifest hash. Keying by
+/// the manifest keeps a bogus manifest gossiped for a document from blocking the genuine one.
+type ChunkFetches = Arc<Mutex<HashMap<(E3id, ContentHash, ContentHash), ChunkAssembly>>>;
 
 /// DocumentPublisher is an actor that monitors events from both the Libp2pNetInterface and the
 /// Interfold EventBus in order to manage document publishing interactions. The decision/state logic
@@ -49,10 +66,13 @@ pub struct DocumentPublisher {
     /// NetEvent receiver to resubscribe for events from the Libp2pNetInterface. This is in an Arc
     /// so that we do not do excessive resubscribes without actually listening for events.
     rx: Arc<broadcast::Receiver<NetEvent>>,
-    /// The gossipsub broadcast topic
-    topic: String,
+    /// Gossipsub topics; notifications go to the topic of the document's E3
+    topics: GossipTopics,
     /// Pure decision/state service.
     service: DocumentPublishingService,
+    /// Chunked documents being fetched. Chunks received in one round are kept here so later
+    /// rounds only fetch the missing ones; the assembly is dropped when the fetch fails.
+    fetches: ChunkFetches,
 }
 
 impl DocumentPublisher {
@@ -67,8 +87,9 @@ impl DocumentPublisher {
             bus: bus.clone(),
             tx: tx.clone(),
             rx: rx.clone(),
-            topic: topic.into(),
+            topics: GossipTopics::new(topic),
             service: DocumentPublishingService::new(),
+            fetches: ChunkFetches::default(),
         }
     }
 
@@ -125,6 +146,10 @@ impl DocumentPublisher {
     }
 
     fn handle_e3_request_complete(&mut self, event: E3RequestComplete) -> Result<()> {
+        self.fetches
+            .lock()
+            .unwrap()
+            .retain(|(e3_id, _, _), _| e3_id != &event.e3_id);
         let keys = self.service.complete_e3(&event.e3_id);
         if !keys.is_empty() {
             info!(
@@ -174,16 +199,15 @@ impl Handler<TypedEvent<PublishDocumentRequested>> for DocumentPublisher {
         let tx = self.tx.clone();
         let (msg, ec) = msg.into_components();
 
-        self.service
-            .track_published_key(&msg.meta.e3_id, &msg.value);
+        let layout = self.service.plan_publication(&msg.meta.e3_id, &msg.value);
 
         let rx = self.rx.clone();
         let bus = self.bus.clone();
-        let topic = self.topic.clone();
+        let topic = self.topics.e3_topic(&msg.meta.e3_id);
         trap_fut(
             EType::IO,
             &bus.with_ec(&ec),
-            handle_publish_document_requested(tx, rx, msg, topic, bus),
+            handle_publish_document_requested(tx, rx, msg, layout, topic, bus),
         )
     }
 }
@@ -228,10 +252,11 @@ impl Handler<DocumentPublishedNotification> for DocumentPublisher {
         let bus = self.bus.clone();
         let tx = self.tx.clone();
         let rx = self.rx.clone();
+        let fetches = self.fetches.clone();
         trap_fut(
             EType::IO,
             &bus,
-            handle_document_published_notification(tx, rx, bus.clone(), ids, msg),
+            handle_document_published_notification(tx, rx, bus.clone(), ids, fetches, msg),
         )
     }
 }
@@ -241,23 +266,35 @@ pub async fn handle_publish_document_requested(
     tx: mpsc::Sender<NetCommand>,
     rx: Arc<broadcast::Receiver<NetEvent>>,
     event: PublishDocumentRequested,
+    layout: DocumentLayout,
     topic: impl Into<String>,
     bus: BusHandle,
 ) -> Result<()> {
-    let value = event.value;
-    let key = ContentHash::from_content(&value);
     let expires = datetime_to_instant_from_now(event.meta.expires_at);
 
-    retry_with_backoff(
-        || {
-            put_record(tx.clone(), rx.clone(), expires, value.clone(), key.clone())
-                .map_err(to_retry)
-        },
-        4,
-        1000,
-    )
-    .await?;
-    let notification = DocumentPublishedNotification::new(event.meta, key, bus.ts()?);
+    let notification = match layout {
+        DocumentLayout::Single(key) => {
+            let value = event.value;
+            retry_with_backoff(
+                || {
+                    put_record(tx.clone(), rx.clone(), expires, value.clone(), key.clone())
+                        .map_err(to_retry)
+                },
+                4,
+                1000,
+            )
+            .await?;
+            DocumentPublishedNotification::new(event.meta, key, bus.ts()?)
+        }
+        DocumentLayout::Chunked {
+            key,
+            manifest,
+            chunks,
+        } => {
+            put_chunks(&tx, &rx, expires, &manifest, chunks).await?;
+            DocumentPublishedNotification::new(event.meta, key, bus.ts()?).with_manifest(manifest)
+        }
+    };
     broadcast_document_published_notification(tx, rx, notification, topic).await?;
     Ok(())
 }
@@ -268,6 +305,7 @@ pub async fn handle_document_published_notification(
     net_events: Arc<broadcast::Receiver<NetEvent>>,
     bus: BusHandle,
     ids: HashMap<E3id, PartyId>,
+    fetches: ChunkFetches,
     event: DocumentPublishedNotification,
 ) -> Result<()> {
     let Some(party_id) = DocumentPublishingService::interest_in(&ids, &event) else {
@@ -280,12 +318,35 @@ pub async fn handle_document_published_notification(
         event, party_id
     );
 
-    let value = retry_with_backoff(
-        || get_record(net_cmds.clone(), net_events.clone(), event.key.clone()).map_err(to_retry),
-        4,
-        1000,
-    )
-    .await?;
+    let value = match &event.manifest {
+        None => {
+            retry_with_backoff(
+                || {
+                    get_record(net_cmds.clone(), net_events.clone(), event.key.clone())
+                        .map_err(to_retry)
+                },
+                4,
+                1000,
+            )
+            .await?
+        }
+        Some(manifest) => {
+            let Some(value) = fetch_chunks(
+                net_cmds,
+                net_events,
+                fetches,
+                &event.meta.e3_id,
+                &event.key,
+                manifest,
+            )
+            .await?
+            else {
+                debug!("Document {} was fetched by another notification", event.key);
+                return Ok(());
+            };
+            value
+        }
+    };
 
     debug!("Sending received event...");
     bus.publish_from_remote(
@@ -301,6 +362,110 @@ pub async fn handle_document_published_notification(
     Ok(())
 }
 
+/// Put every chunk of a document into the DHT, several at a time
+async fn put_chunks(
+    net_cmds: &mpsc::Sender<NetCommand>,
+    net_events: &Arc<broadcast::Receiver<NetEvent>>,
+    expires: Option<std::time::Instant>,
+    manifest: &ChunkManifest,
+    chunks: Vec<ArcBytes>,
+) -> Result<()> {
+    stream::iter(manifest.chunks.iter().cloned().zip(chunks))
+        .map(|(key, chunk)| {
+            retry_with_backoff(
+                move || {
+                    put_record(
+                        net_cmds.clone(),
+                        net_events.clone(),
+                        expires,
+                        chunk.clone(),
+                        key.clone(),
+                    )
+                    .map_err(to_retry)
+                },
+                4,
+                1000,
+            )
+        })
+        .buffer_unordered(MAX_PARALLEL_CHUNK_TRANSFERS)
+        .try_collect::<Vec<_>>()
+        .await?;
+    Ok(())
+}
+
+/// Fetch the chunks of a document in rounds. Every round fetches the chunks that are still
+/// missing in parallel and verifies each one against the manifest. Chunks that were received are
+/// kept in `fetches` across rounds and shared with other notifications of the same manifest, so
+/// an interrupted transfer resumes where it stopped. The assembly is dropped when the document
+/// fails to reassemble or the rounds run out. Returns `None` when the fetch was completed by
+/// another notification of the same document or cancelled because its E3 completed.
+async fn fetch_chunks(
+    net_cmds: mpsc::Sender<NetCommand>,
+    net_events: Arc<broadcast::Receiver<NetEvent>>,
+    fetches: ChunkFetches,
+    e3_id: &E3id,
+    key: &ContentHash,
+    manifest: &ChunkManifest,
+) -> Result<Option<ArcBytes>> {
+    let id = (e3_id.clone(), key.clone(), manifest.hash());
+    {
+        let mut fetches = fetches.lock().unwrap();
+        if !fetches.contains_key(&id) {
+            fetches.insert(
+                id.clone(),
+                ChunkAssembly::new(key.clone(), manifest.clone(), DOCUMENT_CHUNK_SIZE)?,
+            );
+        }
+    }
+
+    let mut delay = CHUNK_FETCH_INITIAL_DELAY;
+    for round in 1..=CHUNK_FETCH_ROUNDS {
+        let missing = match fetches.lock().unwrap().get(&id) {
+            Some(assembly) => assembly.missing(),
+            None => return Ok(None),
+        };
+
+        let fetched: Vec<_> = stream::iter(missing)
+            .map(|(index, chunk_key)| {
+                get_record(net_cmds.clone(), net_events.clone(), chunk_key)
+                    .map(move |result| (index, result))
+            })
+            .buffer_unordered(MAX_PARALLEL_CHUNK_TRANSFERS)
+            .collect()
+            .await;
+
+        let progress = {
+            let mut fetches = fetches.lock().unwrap();
+            let Some(assembly) = fetches.get_mut(&id) else {
+                return Ok(None);
+            };
+            for (index, result) in fetched {
+                if let Err(e) = result.and_then(|chunk| assembly.insert(index, chunk)) {
+                    debug!("Could not fetch chunk {index} of document {key}: {e}");
+                }
+            }
+            if assembly.is_complete() {
+                let value = assembly.assemble();
+                fetches.remove(&id);
+                return value.map(Some);
+            }
+            assembly.progress()
+        };
+
+        let (received, total) = progress;
+        warn!(
+            "Fetched {received}/{total} chunks of document {key} after round {round}/{CHUNK_FETCH_ROUNDS}"
+        );
+        if round < CHUNK_FETCH_ROUNDS {
+            sleep(delay).await;
+            delay *= 2;
+        }
+    }
+
+    fetches.lock().unwrap().remove(&id);
+    bail!("Could not fetch all chunks of document {key} after {CHUNK_FETCH_ROUNDS} rounds")
+}
+
 /// Call DhtPutRecord Command on the Libp2pNetInterface and handle the results
 async fn put_record(
     net_cmds: mpsc::Sender<NetCommand>,
@@ -545,6 +710,7 @@ mod tests {
     use std::{collections::HashMap, num::NonZero, time::Duration};
 
     use super::*;
+    use crate::domain::split_document;
     use crate::events::NetCommand;
     use actix::Addr;
     use anyhow::{bail, Result};
@@ -655,7 +821,7 @@ mod tests {
             message_id: libp2p::gossipsub::MessageId::new(&[1, 2, 3]),
         })?;
 
-        assert_eq!(topic, "topic");
+        assert_eq!(topic, "topic/e3/1/1243");
         assert_eq!(notification.meta.e3_id, E3id::new("1243", 1));
 
         assert_eq!(
@@ -694,6 +860,7 @@ mod tests {
                 key: cid.clone(),
                 meta: DocumentMeta::new(e3_id, DocumentKind::TrBFV, vec![], expires_at),
                 ts: 123,
+                manifest: None,
             }),
         ))?;
 
@@ -813,6 +980,7 @@ mod tests {
                     Some(expires_at),
                 ),
                 ts: 123,
+                manifest: None,
             }),
         ))?;
 
@@ -827,6 +995,7 @@ mod tests {
                 key: cid.clone(),
                 meta: DocumentMeta::new(e3_id, DocumentKind::TrBFV, vec![], Some(expires_at)),
                 ts: 100,
+                manifest: None,
             }),
         ))?;
 
@@ -875,6 +1044,160 @@ mod tests {
         Ok(())
     }
 
+    #[actix::test]
+    async fn test_publishes_large_document_in_chunks() -> Result<()> {
+        let (_guard, bus, _net_cmd_tx, mut net_cmd_rx, net_evt_tx, _net_evt_rx, _, _, _) =
+            setup_test()?;
+        let bytes: Vec<u8> = (0..DOCUMENT_CHUNK_SIZE * 5 / 2)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let e3_id = E3id::new("1243", 1);
+
+        bus.publish_without_context(PublishDocumentRequested {
+            meta: DocumentMeta::new(e3_id, DocumentKind::TrBFV, vec![], None),
+            value: ArcBytes::from_bytes(&bytes),
+        })?;
+
+        // Every chunk is put as its own record before anything is gossiped
+        let mut mykad: HashMap<ContentHash, ArcBytes> = HashMap::new();
+        for _ in 0..3 {
+            let Some(NetCommand::DhtPutRecord {
+                correlation_id,
+                value,
+                key,
+                ..
+            }) = timeout(Duration::from_secs(1), net_cmd_rx.recv())
+                .await
+                .expect("did not receive DhtPutRecord")
+            else {
+                bail!("msg not as expected");
+            };
+            assert!(value.size() <= DOCUMENT_CHUNK_SIZE);
+            mykad.insert(key.clone(), value);
+            net_evt_tx.send(NetEvent::DhtPutRecordSucceeded {
+                correlation_id,
+                key,
+            })?;
+        }
+
+        let Some(NetCommand::GossipPublish {
+            data: GossipData::DocumentPublishedNotification(notification),
+            ..
+        }) = timeout(Duration::from_secs(1), net_cmd_rx.recv())
+            .await
+            .expect("did not receive GossipPublish")
+        else {
+            bail!("msg not as expected");
+        };
+
+        let manifest = notification.manifest.expect("notification has no manifest");
+        assert_eq!(notification.key, ContentHash::from_content(&bytes));
+        assert_eq!(manifest.total_len, bytes.len() as u64);
+        let reassembled: Vec<u8> = manifest
+            .chunks
+            .iter()
+            .flat_map(|key| mykad[key].extract_bytes())
+            .collect();
+        assert_eq!(reassembled, bytes);
+
+        Ok(())
+    }
+
+    #[actix::test]
+    async fn test_chunked_fetch_only_retries_missing_chunks() -> Result<()> {
+        let (_guard, bus, _net_cmd_tx, mut net_cmd_rx, net_evt_tx, _net_evt_rx, history, _, _) =
+            setup_test()?;
+        let bytes: Vec<u8> = (0..DOCUMENT_CHUNK_SIZE * 5 / 2)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let (manifest, chunks) = split_document(&bytes, DOCUMENT_CHUNK_SIZE).unwrap();
+        let mykad: HashMap<ContentHash, ArcBytes> =
+            manifest.chunks.iter().cloned().zip(chunks).collect();
+        let e3_id = E3id::new("1243", 1);
+
+        bus.publish_without_context(CiphernodeSelected {
+            e3_id: e3_id.clone(),
+            threshold_m: 3,
+            threshold_n: 5,
+            ..CiphernodeSelected::default()
+        })?;
+
+        net_evt_tx.send(NetEvent::GossipData(
+            GossipData::DocumentPublishedNotification(
+                DocumentPublishedNotification::new(
+                    DocumentMeta::new(e3_id, DocumentKind::TrBFV, vec![], None),
+                    ContentHash::from_content(&bytes),
+                    100,
+                )
+                .with_manifest(manifest.clone()),
+            ),
+        ))?;
+
+        // All chunks are requested at once; the last one fails to arrive
+        let mut requested = vec![];
+        for _ in 0..3 {
+            let Some(NetCommand::DhtGetRecord {
+                correlation_id,
+                key,
+            }) = timeout(Duration::from_secs(1), net_cmd_rx.recv())
+                .await
+                .expect("did not receive DhtGetRecord")
+            else {
+                bail!("msg not as expected");
+            };
+            requested.push((correlation_id, key));
+        }
+        for (correlation_id, key) in requested {
+            if key == manifest.chunks[2] {
+                net_evt_tx.send(NetEvent::DhtGetRecordError {
+                    correlation_id,
+                    error: GetRecordError::Timeout {
+                        key: RecordKey::new(&key),
+                    },
+                })?;
+            } else {
+                net_evt_tx.send(NetEvent::DhtGetRecordSucceeded {
+                    correlation_id,
+                    value: mykad[&key].clone(),
+                    key,
+                })?;
+            }
+        }
+
+        // Only the missing chunk is fetched again
+        let Some(NetCommand::DhtGetRecord {
+            correlation_id,
+            key,
+        }) = timeout(Duration::from_secs(5), net_cmd_rx.recv())
+            .await
+            .expect("did not receive DhtGetRecord")
+        else {
+            bail!("msg not as expected");
+        };
+        assert_eq!(key, manifest.chunks[2]);
+        net_evt_tx.send(NetEvent::DhtGetRecordSucceeded {
+            correlation_id,
+            value: mykad[&key].clone(),
+            key,
+        })?;
+
+        sleep(Duration::from_millis(100)).await;
+
+        let events = history.send(GetEvents::new()).await?;
+        let Some(InterfoldEventData::DocumentReceived(DocumentReceived { value: doc, .. })) =
+            events
+                .iter()
+                .filter(|e| e.event_type() != "InterfoldError")
+                .last()
+                .map(|e| e.get_data())
+        else {
+            bail!("No event sent");
+        };
+        assert_eq!(doc.extract_bytes(), bytes, "document did not match");
+
+        Ok(())
+    }
+
     pub fn is_between(instant: Instant, start: Instant, end: Instant) -> bool {
         let (min, max) = if start <= end {
             (start, end)
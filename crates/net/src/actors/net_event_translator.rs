@@ -9,7 +9,7 @@ use actix::prelude::*;
 use anyhow::Result;
 use e3_events::{
     prelude::*, trap, BusHandle, CorrelationId, EType, EventContextAccessors, EventSource,
-    EventType, InterfoldEvent,
+    EventType, InterfoldEvent, InterfoldEventData,
 };
 use e3_utils::MAILBOX_LIMIT;
 use std::sync::Arc;
@@ -20,6 +20,9 @@ use tracing::{info, warn};
 /// NetEventTranslator Actor converts between EventBus events and Libp2p events forwarding them to a
 /// Libp2pNetInterface for propagation over the p2p network. All translation/dedup decisions live
 /// in [`EventTranslationService`].
+///
+/// It also manages per-E3 topic membership: the node joins an E3's topic on `CiphernodeSelected`
+/// and leaves it on `E3RequestComplete` or `E3Failed`.
 pub struct NetEventTranslator {
     bus: BusHandle,
     tx: mpsc::Sender<NetCommand>,
@@ -84,8 +87,23 @@ impl NetEventTranslator {
     }
 
     fn handle_interfold_event(&mut self, msg: InterfoldEvent) -> Result<()> {
-        if let Some(data) = self.service.prepare_outbound(msg)? {
-            let topic = self.service.topic().to_owned();
+        match msg.get_data() {
+            InterfoldEventData::CiphernodeSelected(data) => {
+                let topic = self.service.topics().e3_topic(&data.e3_id);
+                self.send_command(NetCommand::GossipSubscribe { topic });
+            }
+            InterfoldEventData::E3RequestComplete(data) => {
+                let topic = self.service.topics().e3_topic(&data.e3_id);
+                self.send_command(NetCommand::GossipUnsubscribe { topic });
+            }
+            InterfoldEventData::E3Failed(data) => {
+                let topic = self.service.topics().e3_topic(&data.e3_id);
+                self.send_command(NetCommand::GossipUnsubscribe { topic });
+            }
+            _ => (),
+        }
+
+        if let Some((topic, data)) = self.service.prepare_outbound(msg)? {
             if let Err(e) = self.tx.try_send(NetCommand::GossipPublish {
                 topic,
                 data,
@@ -97,6 +115,12 @@ impl NetEventTranslator {
         Ok(())
     }
 
+    fn send_command(&self, command: NetCommand) {
+        if let Err(e) = self.tx.try_send(command) {
+            warn!("Failed to send topic command (channel full or closed): {e}");
+        }
+    }
+
     fn handle_remote_event(&mut self, msg: LibP2pEvent) -> Result<()> {
         let event = self.service.prepare_inbound(msg.0)?;
         let (data, ec) = event.into_components();
@@ -7,7 +7,7 @@
 use actix::{Actor, Addr, AsyncContext, Handler, Message, Recipient, ResponseFuture};
 use anyhow::{bail, Context, Result};
 use e3_events::{
-    prelude::*, trap, trap_fut, AggregateId, BusHandle, CorrelationId, EType, EventSource,
+    prelude::*, trap, trap_fut, AggregateId, BusHandle, CorrelationId, E3id, EType, EventSource,
     EventStoreFilter, EventStoreQueryBy, EventStoreQueryResponse, EventType,
     HistoricalNetSyncEventsReceived, HistoricalNetSyncStart, InterfoldEvent, InterfoldEventData,
     NetReady, TsAgg, TypedEvent, Unsequenced,
@@ -29,7 +29,7 @@ use crate::{
     domain::{
         build_sync_batch,
         net_event_batch::{fetch_all_batched_events, FetchEventsSince},
-        EventTranslationService, NetReadiness, ReadinessDecision, SyncBatchOutcome,
+        EventTranslationService, GossipTopics, NetReadiness, ReadinessDecision, SyncBatchOutcome,
     },
     events::{await_event, GossipData, IncomingRequest, NetCommand, NetEvent, PeerTarget},
 };
@@ -87,8 +87,11 @@ pub struct NetSyncManager {
     requests: HashMap<CorrelationId, DirectResponder>,
     /// Pure readiness state machine.
     readiness: NetReadiness,
-    /// Gossipsub topic used to re-broadcast our own forwardable artifacts after a restart.
-    topic: String,
+    /// Gossipsub topics used to re-broadcast our own forwardable artifacts after a restart.
+    topics: GossipTopics,
+    /// E3s this node is selected for and that have neither completed nor failed, as seen during
+    /// the event store replay. Their topics are rejoined before the historical net sync.
+    active_e3s: HashSet<E3id>,
     /// Snapshot-cursor map captured from `HistoricalNetSyncStart`. Bounds the post-restart
     /// re-broadcast query to the in-flight (un-snapshotted) window.
     rebroadcast_since: Option<HashMap<AggregateId, u128>>,
@@ -116,7 +119,8 @@ impl NetSyncManager {
             eventstore,
             requests: HashMap::new(),
             readiness: NetReadiness::new(),
-            topic: topic.to_string(),
+            topics: GossipTopics::new(topic),
+            active_e3s: HashSet::new(),
             rebroadcast_since: None,
             rebroadcast_query_ids: HashSet::new(),
             net_ready: false,
@@ -163,6 +167,24 @@ impl NetSyncManager {
         }
     }
 
+    /// Rejoin the per-E3 topics of the E3s this node was still working on before the restart.
+    /// `NetEventTranslator` joins topics on live `CiphernodeSelected` events, but it is only
+    /// created on `EffectsEnabled`, after the event store replay has gone past.
+    fn rejoin_active_e3_topics(&self) {
+        for e3_id in &self.active_e3s {
+            let topic = self.topics.e3_topic(e3_id);
+            if let Err(e) = self.tx.try_send(NetCommand::GossipSubscribe { topic }) {
+                warn!("Failed to rejoin topic for E3 {e3_id} (channel full or closed): {e}");
+            }
+        }
+        if !self.active_e3s.is_empty() {
+            info!(
+                "NetSyncManager: rejoined {} in-flight E3 topic(s)",
+                self.active_e3s.len()
+            );
+        }
+    }
+
     /// Re-gossip the node's own forwardable artifacts returned by the re-broadcast query.
     fn handle_rebroadcast_response(&mut self, events: Vec<InterfoldEvent>) {
         let mut count = 0usize;
@@ -170,6 +192,7 @@ impl NetSyncManager {
             if !EventTranslationService::is_forwardable_event(&event) {
                 continue;
             }
+            let topic = self.topics.topic_for_event(&event);
             let data: GossipData = match event.try_into() {
                 Ok(data) => data,
                 Err(e) => {
@@ -178,7 +201,7 @@ impl NetSyncManager {
                 }
             };
             if let Err(e) = self.tx.try_send(NetCommand::GossipPublish {
-                topic: self.topic.clone(),
+                topic,
                 data,
                 correlation_id: CorrelationId::new(),
             }) {
@@ -230,6 +253,9 @@ impl NetSyncManager {
         let addr = Self::new(bus, tx, rx, eventstore, topic).start();
 
         bus.subscribe(EventType::HistoricalNetSyncStart, addr.clone().recipient());
+        bus.subscribe(EventType::CiphernodeSelected, addr.clone().recipient());
+        bus.subscribe(EventType::E3RequestComplete, addr.clone().recipient());
+        bus.subscribe(EventType::E3Failed, addr.clone().recipient());
 
         // Forward from NetEvent
         tokio::spawn({
@@ -267,13 +293,26 @@ impl Handler<InterfoldEvent> for NetSyncManager {
     type Result = ();
     fn handle(&mut self, msg: InterfoldEvent, ctx: &mut Self::Context) -> Self::Result {
         let (msg, ec) = msg.into_components();
-        // We are making a sync request of another node
-        if let InterfoldEventData::HistoricalNetSyncStart(data) = msg {
-            // Capture the snapshot-cursor map so we can bound the post-restart re-broadcast of our
-            // own forwardable artifacts to the in-flight window (H3/H11).
-            self.rebroadcast_since = Some(data.since.clone().into_iter().collect());
-            self.maybe_rebroadcast_own_artifacts(ctx);
-            ctx.notify(TypedEvent::new(data, ec))
+        match msg {
+            InterfoldEventData::CiphernodeSelected(data) => {
+                self.active_e3s.insert(data.e3_id);
+            }
+            InterfoldEventData::E3RequestComplete(data) => {
+                self.active_e3s.remove(&data.e3_id);
+            }
+            InterfoldEventData::E3Failed(data) => {
+                self.active_e3s.remove(&data.e3_id);
+            }
+            // We are making a sync request of another node
+            InterfoldEventData::HistoricalNetSyncStart(data) => {
+                self.rejoin_active_e3_topics();
+                // Capture the snapshot-cursor map so we can bound the post-restart re-broadcast
+                // of our own forwardable artifacts to the in-flight window (H3/H11).
+                self.rebroadcast_since = Some(data.since.clone().into_iter().collect());
+                self.maybe_rebroadcast_own_artifacts(ctx);
+                ctx.notify(TypedEvent::new(data, ec))
+            }
+            _ => (),
         }
     }
 }
@@ -633,7 +672,8 @@ mod tests {
     use actix::{Actor, Context as ActixContext, Handler};
     use e3_ciphernode_builder::EventSystem;
     use e3_events::{
-        E3id, EventSource, InterfoldEvent, PlaintextAggregated, TestEvent, Unsequenced,
+        CiphernodeSelected, E3Failed, E3RequestComplete, E3Stage, E3id, EventSource, FailureReason,
+        InterfoldEvent, PlaintextAggregated, TestEvent, Unsequenced,
     };
     use e3_utils::ArcBytes;
     use tokio::sync::{broadcast, mpsc};
@@ -692,7 +732,8 @@ mod tests {
             local_non_forwardable_event(),
         ]);
 
-        // Exactly one GossipPublish for the forwardable artifact, on the configured topic.
+        // Exactly one GossipPublish for the forwardable artifact; E3 results stay on the control
+        // topic.
         let cmd = rx.try_recv().expect("expected a GossipPublish command");
         let NetCommand::GossipPublish { topic, data, .. } = cmd else {
             panic!("expected GossipPublish, got {cmd:?}");
@@ -710,4 +751,72 @@ mod tests {
             "non-forwardable event should not be re-broadcast"
         );
     }
+
+    fn replayed(data: InterfoldEventData, seq: u64) -> InterfoldEvent {
+        InterfoldEvent::<Unsequenced>::new_with_timestamp(
+            data,
+            None,
+            seq as u128,
+            None,
+            EventSource::Local,
+        )
+        .into_sequenced(seq)
+    }
+
+    #[actix::test]
+    async fn rejoins_topics_of_in_flight_e3s_on_net_sync_start() {
+        let system = EventSystem::new().with_fresh_bus();
+        let bus = system.handle().unwrap().enable("test");
+        let (tx, mut rx) = mpsc::channel::<NetCommand>(100);
+        let (_evt_tx, evt_rx) = broadcast::channel::<NetEvent>(100);
+        let evt_rx = Arc::new(evt_rx);
+        let eventstore = NoopEventStore.start().recipient();
+
+        let addr = NetSyncManager::new(&bus, &tx, &evt_rx, eventstore, "my-topic").start();
+
+        // Replay: selected for three E3s, one of which has since completed and one failed.
+        for (seq, data) in [
+            CiphernodeSelected {
+                e3_id: E3id::new("1", 1),
+                ..CiphernodeSelected::default()
+            }
+            .into(),
+            CiphernodeSelected {
+                e3_id: E3id::new("2", 1),
+                ..CiphernodeSelected::default()
+            }
+            .into(),
+            CiphernodeSelected {
+                e3_id: E3id::new("3", 1),
+                ..CiphernodeSelected::default()
+            }
+            .into(),
+            E3RequestComplete {
+                e3_id: E3id::new("1", 1),
+            }
+            .into(),
+            E3Failed {
+                e3_id: E3id::new("3", 1),
+                failed_at_stage: E3Stage::CommitteeFinalized,
+                reason: FailureReason::DKGTimeout,
+            }
+            .into(),
+            HistoricalNetSyncStart::new(Default::default()).into(),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            addr.send(replayed(data, seq as u64 + 1)).await.unwrap();
+        }
+
+        let cmd = rx.try_recv().expect("expected a GossipSubscribe command");
+        let NetCommand::GossipSubscribe { topic } = cmd else {
+            panic!("expected GossipSubscribe, got {cmd:?}");
+        };
+        assert_eq!(topic, "my-topic/e3/1/2");
+        assert!(
+            !matches!(rx.try_recv(), Ok(NetCommand::GossipSubscribe { .. })),
+            "completed or failed E3 topics should not be rejoined"
+        );
+    }
 }
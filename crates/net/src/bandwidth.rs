@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Bandwidth accounting of the network interface.
+//!
+//! Bytes are counted per protocol and per peer. Inbound traffic is checked against the size caps
+//! and per-peer rate limits configured in `net_limits`; every message that exceeds them is
+//! dropped and counted as a violation of the sending peer. The interface periodically emits a
+//! [`BandwidthReport`], which is persisted for `interfold net bandwidth` and the dashboard.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Network protocols subject to accounting and limits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum NetProtocol {
+    Gossipsub,
+    RequestResponse,
+    Kademlia,
+}
+
+impl fmt::Display for NetProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetProtocol::Gossipsub => write!(f, "gossipsub"),
+            NetProtocol::RequestResponse => write!(f, "request-response"),
+            NetProtocol::Kademlia => write!(f, "kademlia"),
+        }
+    }
+}
+
+/// Why an inbound message of a peer was dropped.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LimitViolation {
+    /// The message is larger than the protocol's size cap
+    Oversized { bytes: u64, max: u64 },
+    /// The peer sent more than its rate limit allows
+    RateLimited { bytes: u64 },
+}
+
+impl fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitViolation::Oversized { bytes, max } => {
+                write!(f, "message of {bytes} bytes exceeds the {max} byte cap")
+            }
+            LimitViolation::RateLimited { bytes } => {
+                write!(f, "message of {bytes} bytes exceeds the peer's rate limit")
+            }
+        }
+    }
+}
+
+/// Byte counts and dropped messages of one protocol.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrafficCounters {
+    pub inbound_bytes: u64,
+    pub outbound_bytes: u64,
+    /// Inbound messages dropped for exceeding a limit
+    pub violations: u64,
+}
+
+/// Traffic exchanged with one peer.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerBandwidth {
+    pub connected: bool,
+    pub protocols: BTreeMap<NetProtocol, TrafficCounters>,
+}
+
+impl PeerBandwidth {
+    pub fn violations(&self) -> u64 {
+        self.protocols.values().map(|c| c.violations).sum()
+    }
+}
+
+/// Snapshot of the traffic since the node started. Peers are kept for a while after they
+/// disconnect so that repeat offenders stay visible.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BandwidthReport {
+    /// Unix timestamp (seconds) the snapshot was taken at
+    pub generated_at: i64,
+    /// Totals over all peers, including traffic not attributable to a single peer (gossip
+    /// publications, DHT puts)
+    pub protocols: BTreeMap<NetProtocol, TrafficCounters>,
+    /// Per-peer traffic keyed by PeerId
+    pub peers: BTreeMap<String, PeerBandwidth>,
+}
@@ -107,7 +107,7 @@ async fn wait_for_connection(
         select! {
             result = event_rx.recv() => {
                 match result.map_err(to_retry)? {
-                    NetEvent::ConnectionEstablished { connection_id } => {
+                    NetEvent::ConnectionEstablished { connection_id, .. } => {
                         if connection_id == dial_connection {
                             trace!("Connection Established");
                             return Ok(());
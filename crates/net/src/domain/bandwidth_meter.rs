@@ -0,0 +1,473 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use crate::bandwidth::{
+    BandwidthReport, LimitViolation, NetProtocol, PeerBandwidth, TrafficCounters,
+};
+use e3_config::{NetLimitsConfig, ProtocolLimitConfig};
+use libp2p::PeerId;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Disconnected peers are dropped from the accounting after this long.
+const PEER_RETENTION: Duration = Duration::from_secs(600);
+/// Violations older than this no longer count towards disconnecting a peer.
+pub(crate) const VIOLATION_WINDOW: Duration = Duration::from_secs(300);
+
+struct ProtocolLimit {
+    max_message_bytes: u64,
+    /// Refill rate (bytes per second) and capacity of each peer's token bucket
+    rate: Option<(f64, f64)>,
+}
+
+impl ProtocolLimit {
+    fn new(config: &ProtocolLimitConfig, ceiling: u64) -> Self {
+        let max_message_bytes = config
+            .max_message_bytes
+            .map_or(ceiling, |max| max.min(ceiling));
+        let rate = config
+            .peer_bytes_per_sec
+            .filter(|rate| *rate > 0)
+            .map(|rate| {
+                let burst = config.peer_burst_bytes.unwrap_or(rate);
+                // A message within the size cap must always fit into a full bucket
+                (rate as f64, burst.max(max_message_bytes) as f64)
+            });
+        Self {
+            max_message_bytes,
+            rate,
+        }
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn take(&mut self, bytes: u64, (rate, capacity): (f64, f64), now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(capacity);
+        self.updated = now;
+        if self.tokens < bytes as f64 {
+            return false;
+        }
+        self.tokens -= bytes as f64;
+        true
+    }
+}
+
+#[derive(Default)]
+struct PeerUsage {
+    protocols: BTreeMap<NetProtocol, TrafficCounters>,
+    buckets: HashMap<NetProtocol, TokenBucket>,
+    /// When each violation within the last [`VIOLATION_WINDOW`] happened
+    recent_violations: VecDeque<Instant>,
+    disconnected_at: Option<Instant>,
+}
+
+impl PeerUsage {
+    fn forget_violations_before(&mut self, now: Instant) {
+        while self
+            .recent_violations
+            .front()
+            .is_some_and(|at| now.saturating_duration_since(*at) >= VIOLATION_WINDOW)
+        {
+            self.recent_violations.pop_front();
+        }
+    }
+}
+
+/// Counts the bytes exchanged per protocol and peer and enforces the inbound size caps and
+/// per-peer token-bucket rate limits of `net_limits`.
+pub(crate) struct BandwidthMeter {
+    limits: HashMap<NetProtocol, ProtocolLimit>,
+    totals: BTreeMap<NetProtocol, TrafficCounters>,
+    peers: HashMap<PeerId, PeerUsage>,
+}
+
+impl BandwidthMeter {
+    /// `ceiling` is the largest message a protocol can carry at all; configured caps above it
+    /// are clamped.
+    pub fn new(config: &NetLimitsConfig, ceiling: impl Fn(NetProtocol) -> u64) -> Self {
+        let limits = [
+            (NetProtocol::Gossipsub, &config.gossipsub),
+            (NetProtocol::RequestResponse, &config.request_response),
+            (NetProtocol::Kademlia, &config.kademlia),
+        ]
+        .into_iter()
+        .map(|(protocol, config)| (protocol, ProtocolLimit::new(config, ceiling(protocol))))
+        .collect();
+        Self {
+            limits,
+            totals: BTreeMap::new(),
+            peers: HashMap::new(),
+        }
+    }
+
+    pub fn max_message_bytes(&self, protocol: NetProtocol) -> u64 {
+        self.limits[&protocol].max_message_bytes
+    }
+
+    /// Account for an unsolicited message `peer` sent us and check it against the size cap and
+    /// the peer's rate limit. The bytes are counted either way; a violation is recorded against
+    /// the peer when the message has to be dropped.
+    pub fn inbound(
+        &mut self,
+        peer: PeerId,
+        protocol: NetProtocol,
+        bytes: usize,
+        now: Instant,
+    ) -> Result<(), LimitViolation> {
+        self.check(peer, protocol, bytes, now, true)
+    }
+
+    /// Like [`Self::inbound`] for an answer to our own request: only the size cap applies.
+    pub fn solicited(
+        &mut self,
+        peer: PeerId,
+        protocol: NetProtocol,
+        bytes: usize,
+        now: Instant,
+    ) -> Result<(), LimitViolation> {
+        self.check(peer, protocol, bytes, now, false)
+    }
+
+    /// Like [`Self::inbound`] for a message `peer` relayed on behalf of its author, such as a
+    /// gossipsub message: only the size cap applies, since the relaying peer does not control
+    /// how much its mesh publishes.
+    pub fn forwarded(
+        &mut self,
+        peer: PeerId,
+        protocol: NetProtocol,
+        bytes: usize,
+        now: Instant,
+    ) -> Result<(), LimitViolation> {
+        self.check(peer, protocol, bytes, now, false)
+    }
+
+    /// Account for inbound bytes that are not checked at all, e.g. DHT records found by our own
+    /// queries, which the caller verifies instead.
+    pub fn record_inbound(&mut self, peer: Option<PeerId>, protocol: NetProtocol, bytes: usize) {
+        self.totals.entry(protocol).or_default().inbound_bytes += bytes as u64;
+        if let Some(peer) = peer {
+            let usage = self.peers.entry(peer).or_default();
+            usage.protocols.entry(protocol).or_default().inbound_bytes += bytes as u64;
+        }
+    }
+
+    pub fn record_outbound(&mut self, peer: Option<PeerId>, protocol: NetProtocol, bytes: usize) {
+        self.totals.entry(protocol).or_default().outbound_bytes += bytes as u64;
+        if let Some(peer) = peer {
+            let usage = self.peers.entry(peer).or_default();
+            usage.protocols.entry(protocol).or_default().outbound_bytes += bytes as u64;
+        }
+    }
+
+    /// Number of messages of `peer` dropped for exceeding a limit within the last
+    /// [`VIOLATION_WINDOW`]. The report keeps the lifetime counts.
+    pub fn recent_violations(&mut self, peer: &PeerId, now: Instant) -> u64 {
+        self.peers.get_mut(peer).map_or(0, |usage| {
+            usage.forget_violations_before(now);
+            usage.recent_violations.len() as u64
+        })
+    }
+
+    pub fn on_connected(&mut self, peer: PeerId) {
+        self.peers.entry(peer).or_default().disconnected_at = None;
+    }
+
+    pub fn on_disconnected(&mut self, peer: &PeerId, now: Instant) {
+        if let Some(usage) = self.peers.get_mut(peer) {
+            usage.disconnected_at = Some(now);
+            usage.buckets.clear();
+        }
+    }
+
+    /// Snapshot the counters, forgetting peers that disconnected more than [`PEER_RETENTION`]
+    /// ago. Their traffic stays part of the protocol totals.
+    pub fn report(&mut self, now: Instant, generated_at: i64) -> BandwidthReport {
+        self.peers.retain(|_, usage| {
+            usage
+                .disconnected_at
+                .is_none_or(|at| now.saturating_duration_since(at) < PEER_RETENTION)
+        });
+        BandwidthReport {
+            generated_at,
+            protocols: self.totals.clone(),
+            peers: self
+                .peers
+                .iter()
+                .map(|(peer_id, usage)| {
+                    (
+                        peer_id.to_string(),
+                        PeerBandwidth {
+                            connected: usage.disconnected_at.is_none(),
+                            protocols: usage.protocols.clone(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// The peer's rate limit is only applied when `rate_limited` is set.
+    fn check(
+        &mut self,
+        peer: PeerId,
+        protocol: NetProtocol,
+        bytes: usize,
+        now: Instant,
+        rate_limited: bool,
+    ) -> Result<(), LimitViolation> {
+        self.record_inbound(Some(peer), protocol, bytes);
+        let bytes = bytes as u64;
+        let limit = &self.limits[&protocol];
+        let usage = self.peers.entry(peer).or_default();
+        let admitted = if bytes > limit.max_message_bytes {
+            Err(LimitViolation::Oversized {
+                bytes,
+                max: limit.max_message_bytes,
+            })
+        } else {
+            match limit.rate.filter(|_| rate_limited) {
+                Some((rate, capacity)) => {
+                    let bucket = usage.buckets.entry(protocol).or_insert(TokenBucket {
+                        tokens: capacity,
+                        updated: now,
+                    });
+                    if bucket.take(bytes, (rate, capacity), now) {
+                        Ok(())
+                    } else {
+                        Err(LimitViolation::RateLimited { bytes })
+                    }
+                }
+                None => Ok(()),
+            }
+        };
+        if admitted.is_err() {
+            usage.forget_violations_before(now);
+            usage.recent_violations.push_back(now);
+            usage.protocols.entry(protocol).or_default().violations += 1;
+            self.totals.entry(protocol).or_default().violations += 1;
+        }
+        admitted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CEILING: u64 = 1000;
+
+    fn meter(gossipsub: ProtocolLimitConfig) -> BandwidthMeter {
+        let config = NetLimitsConfig {
+            gossipsub,
+            ..NetLimitsConfig::default()
+        };
+        BandwidthMeter::new(&config, |_| CEILING)
+    }
+
+    #[test]
+    fn traffic_is_counted_per_protocol_and_peer() {
+        let mut meter = meter(ProtocolLimitConfig::default());
+        let peer = PeerId::random();
+        let now = Instant::now();
+
+        assert!(meter
+            .inbound(peer, NetProtocol::Gossipsub, 100, now)
+            .is_ok());
+        meter.record_inbound(Some(peer), NetProtocol::Kademlia, 50);
+        meter.record_outbound(Some(peer), NetProtocol::RequestResponse, 30);
+        meter.record_outbound(None, NetProtocol::Gossipsub, 20);
+
+        let report = meter.report(now, 7);
+        assert_eq!(report.generated_at, 7);
+        assert_eq!(
+            report.protocols[&NetProtocol::Gossipsub],
+            TrafficCounters {
+                inbound_bytes: 100,
+                outbound_bytes: 20,
+                violations: 0,
+            }
+        );
+        let usage = &report.peers[&peer.to_string()];
+        assert_eq!(usage.protocols[&NetProtocol::Kademlia].inbound_bytes, 50);
+        assert_eq!(
+            usage.protocols[&NetProtocol::RequestResponse].outbound_bytes,
+            30
+        );
+        assert_eq!(usage.protocols[&NetProtocol::Gossipsub].outbound_bytes, 0);
+    }
+
+    #[test]
+    fn oversized_messages_are_rejected_and_caps_are_clamped() {
+        let mut meter = meter(ProtocolLimitConfig {
+            max_message_bytes: Some(100),
+            ..ProtocolLimitConfig::default()
+        });
+        let peer = PeerId::random();
+        let now = Instant::now();
+
+        assert!(meter
+            .inbound(peer, NetProtocol::Gossipsub, 100, now)
+            .is_ok());
+        assert_eq!(
+            meter.inbound(peer, NetProtocol::Gossipsub, 101, now),
+            Err(LimitViolation::Oversized {
+                bytes: 101,
+                max: 100
+            })
+        );
+        // Unconfigured protocols fall back to the ceiling
+        assert_eq!(meter.max_message_bytes(NetProtocol::Kademlia), CEILING);
+        assert!(meter
+            .inbound(peer, NetProtocol::Kademlia, CEILING as usize + 1, now)
+            .is_err());
+
+        let clamped = BandwidthMeter::new(
+            &NetLimitsConfig {
+                kademlia: ProtocolLimitConfig {
+                    max_message_bytes: Some(CEILING * 2),
+                    ..ProtocolLimitConfig::default()
+                },
+                ..NetLimitsConfig::default()
+            },
+            |_| CEILING,
+        );
+        assert_eq!(clamped.max_message_bytes(NetProtocol::Kademlia), CEILING);
+    }
+
+    #[test]
+    fn peers_exceeding_their_rate_are_limited_until_the_bucket_refills() {
+        let mut meter = meter(ProtocolLimitConfig {
+            max_message_bytes: Some(100),
+            peer_bytes_per_sec: Some(100),
+            peer_burst_bytes: Some(300),
+        });
+        let peer = PeerId::random();
+        let other = PeerId::random();
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert!(meter
+                .inbound(peer, NetProtocol::Gossipsub, 100, start)
+                .is_ok());
+        }
+        assert_eq!(
+            meter.inbound(peer, NetProtocol::Gossipsub, 100, start),
+            Err(LimitViolation::RateLimited { bytes: 100 })
+        );
+        // Buckets are per peer and per protocol
+        assert!(meter
+            .inbound(other, NetProtocol::Gossipsub, 100, start)
+            .is_ok());
+        assert!(meter
+            .inbound(peer, NetProtocol::RequestResponse, 100, start)
+            .is_ok());
+
+        let later = start + Duration::from_secs(1);
+        assert!(meter
+            .inbound(peer, NetProtocol::Gossipsub, 100, later)
+            .is_ok());
+        assert!(meter
+            .inbound(peer, NetProtocol::Gossipsub, 1, later)
+            .is_err());
+
+        assert_eq!(meter.recent_violations(&peer, later), 2);
+        assert_eq!(meter.recent_violations(&other, later), 0);
+        let report = meter.report(later, 0);
+        assert_eq!(report.protocols[&NetProtocol::Gossipsub].violations, 2);
+        assert_eq!(report.peers[&peer.to_string()].violations(), 2);
+    }
+
+    #[test]
+    fn solicited_messages_are_only_checked_against_the_size_cap() {
+        let mut meter = meter(ProtocolLimitConfig {
+            max_message_bytes: Some(100),
+            peer_bytes_per_sec: Some(100),
+            peer_burst_bytes: None,
+        });
+        let peer = PeerId::random();
+        let now = Instant::now();
+        for _ in 0..5 {
+            assert!(meter
+                .solicited(peer, NetProtocol::Gossipsub, 100, now)
+                .is_ok());
+            assert!(meter
+                .forwarded(peer, NetProtocol::Gossipsub, 100, now)
+                .is_ok());
+        }
+        assert!(meter
+            .solicited(peer, NetProtocol::Gossipsub, 101, now)
+            .is_err());
+        assert!(meter
+            .forwarded(peer, NetProtocol::Gossipsub, 101, now)
+            .is_err());
+        assert_eq!(meter.recent_violations(&peer, now), 2);
+    }
+
+    #[test]
+    fn violations_only_count_within_the_window() {
+        let mut meter = meter(ProtocolLimitConfig {
+            max_message_bytes: Some(100),
+            ..ProtocolLimitConfig::default()
+        });
+        let peer = PeerId::random();
+        let start = Instant::now();
+        let later = start + VIOLATION_WINDOW / 2;
+
+        assert!(meter
+            .inbound(peer, NetProtocol::Gossipsub, 101, start)
+            .is_err());
+        assert!(meter
+            .inbound(peer, NetProtocol::Gossipsub, 101, later)
+            .is_err());
+        assert_eq!(meter.recent_violations(&peer, later), 2);
+        assert_eq!(meter.recent_violations(&peer, start + VIOLATION_WINDOW), 1);
+        assert_eq!(meter.recent_violations(&peer, later + VIOLATION_WINDOW), 0);
+
+        // The report still shows every violation
+        let report = meter.report(later + VIOLATION_WINDOW, 0);
+        assert_eq!(report.peers[&peer.to_string()].violations(), 2);
+    }
+
+    #[test]
+    fn the_burst_always_fits_a_maximum_size_message() {
+        let mut meter = meter(ProtocolLimitConfig {
+            max_message_bytes: Some(500),
+            peer_bytes_per_sec: Some(10),
+            peer_burst_bytes: None,
+        });
+        assert!(meter
+            .inbound(
+                PeerId::random(),
+                NetProtocol::Gossipsub,
+                500,
+                Instant::now()
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn disconnected_peers_are_forgotten_after_the_retention_period() {
+        let mut meter = meter(ProtocolLimitConfig::default());
+        let peer = PeerId::random();
+        let start = Instant::now();
+        meter.on_connected(peer);
+        meter.record_inbound(Some(peer), NetProtocol::Gossipsub, 10);
+        meter.on_disconnected(&peer, start);
+
+        let report = meter.report(start + Duration::from_secs(1), 0);
+        assert!(!report.peers[&peer.to_string()].connected);
+
+        let report = meter.report(start + PEER_RETENTION, 0);
+        assert!(report.peers.is_empty());
+        assert_eq!(report.protocols[&NetProtocol::Gossipsub].inbound_bytes, 10);
+    }
+}
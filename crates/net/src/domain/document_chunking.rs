@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use anyhow::{bail, Result};
+use e3_utils::ArcBytes;
+
+use crate::{events::ChunkManifest, ContentHash};
+
+/// Documents larger than this are split into chunks of this size, each stored as its own DHT
+/// record.
+pub const DOCUMENT_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Largest number of chunks a manifest may list. Manifests arrive over gossip, so this bounds
+/// the memory a peer can make us reserve for a single document.
+pub const MAX_DOCUMENT_CHUNKS: usize = 256;
+
+/// Split `value` into chunks of `chunk_size` bytes. Returns `None` when the document fits into a
+/// single chunk and can be published as one record.
+pub fn split_document(value: &[u8], chunk_size: usize) -> Option<(ChunkManifest, Vec<ArcBytes>)> {
+    if value.len() <= chunk_size {
+        return None;
+    }
+    let chunks: Vec<_> = value.chunks(chunk_size).map(ArcBytes::from_bytes).collect();
+    let manifest = ChunkManifest {
+        total_len: value.len() as u64,
+        chunks: chunks
+            .iter()
+            .map(|chunk| ContentHash::from_content(chunk))
+            .collect(),
+    };
+    Some((manifest, chunks))
+}
+
+/// Reassembles a chunked document. Chunks are verified against the manifest as they arrive, so
+/// a fetch interrupted by a disconnect only needs to retrieve the chunks that are still missing.
+pub struct ChunkAssembly {
+    key: ContentHash,
+    manifest: ChunkManifest,
+    chunks: Vec<Option<ArcBytes>>,
+}
+
+impl ChunkAssembly {
+    /// Start reassembling the document `key` from a manifest that was split into chunks of
+    /// `chunk_size` bytes. The manifest is untrusted, so its chunk count is capped and its total
+    /// length must be consistent with the chunk count.
+    pub fn new(key: ContentHash, manifest: ChunkManifest, chunk_size: usize) -> Result<Self> {
+        let count = manifest.chunks.len();
+        if count == 0 {
+            bail!("Manifest of document {key} lists no chunks");
+        }
+        if count > MAX_DOCUMENT_CHUNKS {
+            bail!("Manifest of document {key} lists {count} chunks, at most {MAX_DOCUMENT_CHUNKS} are allowed");
+        }
+        let min_len = (count as u64 - 1).saturating_mul(chunk_size as u64);
+        let max_len = (count as u64).saturating_mul(chunk_size as u64);
+        if manifest.total_len <= min_len || manifest.total_len > max_len {
+            bail!(
+                "Manifest of document {key} lists {} bytes which does not fit {count} chunks of {chunk_size} bytes",
+                manifest.total_len
+            );
+        }
+        let chunks = vec![None; manifest.chunks.len()];
+        Ok(Self {
+            key,
+            manifest,
+            chunks,
+        })
+    }
+
+    /// Index and key of every chunk that has not been received yet.
+    pub fn missing(&self) -> Vec<(usize, ContentHash)> {
+        self.chunks
+            .iter()
+            .zip(&self.manifest.chunks)
+            .enumerate()
+            .filter(|(_, (chunk, _))| chunk.is_none())
+            .map(|(index, (_, key))| (index, key.clone()))
+            .collect()
+    }
+
+    /// Number of chunks received and total number of chunks.
+    pub fn progress(&self) -> (usize, usize) {
+        let received = self.chunks.iter().filter(|chunk| chunk.is_some()).count();
+        (received, self.chunks.len())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.chunks.iter().all(Option::is_some)
+    }
+
+    /// Store chunk `index` if it matches the hash listed in the manifest.
+    pub fn insert(&mut self, index: usize, chunk: ArcBytes) -> Result<()> {
+        let Some(expected) = self.manifest.chunks.get(index) else {
+            bail!(
+                "Chunk index {index} is out of range for document {}",
+                self.key
+            );
+        };
+        if ContentHash::from_content(&chunk) != *expected {
+            bail!("Chunk {index} of document {} failed verification", self.key);
+        }
+        self.chunks[index] = Some(chunk);
+        Ok(())
+    }
+
+    /// Concatenate the chunks and check the result against the document's content hash.
+    pub fn assemble(&self) -> Result<ArcBytes> {
+        let mut value = Vec::with_capacity(self.manifest.total_len as usize);
+        for (index, chunk) in self.chunks.iter().enumerate() {
+            let Some(chunk) = chunk else {
+                bail!("Chunk {index} of document {} is missing", self.key);
+            };
+            value.extend_from_slice(chunk);
+        }
+        if value.len() as u64 != self.manifest.total_len {
+            bail!(
+                "Document {} has {} bytes but its manifest lists {}",
+                self.key,
+                value.len(),
+                self.manifest.total_len
+            );
+        }
+        if ContentHash::from_content(&value) != self.key {
+            bail!("Reassembled document does not match its key {}", self.key);
+        }
+        Ok(ArcBytes::from_bytes(&value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn assembly_for(value: &[u8], chunk_size: usize) -> (ChunkAssembly, Vec<ArcBytes>) {
+        let (manifest, chunks) = split_document(value, chunk_size).unwrap();
+        let assembly =
+            ChunkAssembly::new(ContentHash::from_content(value), manifest, chunk_size).unwrap();
+        (assembly, chunks)
+    }
+
+    #[test]
+    fn small_documents_are_not_chunked() {
+        assert!(split_document(&document(10), 10).is_none());
+    }
+
+    #[test]
+    fn chunks_reassemble_in_any_order() {
+        let value = document(25);
+        let (mut assembly, chunks) = assembly_for(&value, 10);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(assembly.progress(), (0, 3));
+
+        for index in [2, 0, 1] {
+            assert!(!assembly.is_complete());
+            assembly.insert(index, chunks[index].clone()).unwrap();
+        }
+        assert!(assembly.is_complete());
+        assert_eq!(assembly.assemble().unwrap().extract_bytes(), value);
+    }
+
+    #[test]
+    fn only_missing_chunks_are_fetched_again() {
+        let value = document(25);
+        let (mut assembly, chunks) = assembly_for(&value, 10);
+        assembly.insert(1, chunks[1].clone()).unwrap();
+
+        let missing: Vec<_> = assembly.missing().into_iter().map(|(i, _)| i).collect();
+        assert_eq!(missing, vec![0, 2]);
+        assert_eq!(
+            assembly.missing()[1].1,
+            ContentHash::from_content(&chunks[2])
+        );
+        assert_eq!(assembly.progress(), (1, 3));
+    }
+
+    #[test]
+    fn corrupted_chunks_are_rejected() {
+        let value = document(25);
+        let (mut assembly, chunks) = assembly_for(&value, 10);
+
+        assert!(assembly.insert(0, chunks[1].clone()).is_err());
+        assert!(assembly.insert(3, chunks[0].clone()).is_err());
+        assert_eq!(assembly.progress(), (0, 3));
+        assert!(assembly.assemble().is_err());
+    }
+
+    #[test]
+    fn manifests_not_matching_the_document_key_fail_to_assemble() {
+        let value = document(25);
+        let (manifest, chunks) = split_document(&value, 10).unwrap();
+        let mut assembly =
+            ChunkAssembly::new(ContentHash::from_content(b"other document"), manifest, 10).unwrap();
+        for (index, chunk) in chunks.into_iter().enumerate() {
+            assembly.insert(index, chunk).unwrap();
+        }
+        assert!(assembly.assemble().is_err());
+    }
+
+    #[test]
+    fn manifests_with_inconsistent_lengths_are_rejected() {
+        let value = document(25);
+        let (manifest, _) = split_document(&value, 10).unwrap();
+        let key = ContentHash::from_content(&value);
+
+        for total_len in [20, 31, u64::MAX] {
+            let manifest = ChunkManifest {
+                total_len,
+                ..manifest.clone()
+            };
+            assert!(ChunkAssembly::new(key.clone(), manifest, 10).is_err());
+        }
+        for total_len in [21, 30] {
+            let manifest = ChunkManifest {
+                total_len,
+                ..manifest.clone()
+            };
+            assert!(ChunkAssembly::new(key.clone(), manifest, 10).is_ok());
+        }
+    }
+
+    #[test]
+    fn manifests_with_too_many_chunks_are_rejected() {
+        let value = document(10 * (MAX_DOCUMENT_CHUNKS + 1));
+        let (manifest, _) = split_document(&value, 10).unwrap();
+        assert!(ChunkAssembly::new(ContentHash::from_content(&value), manifest, 10).is_err());
+    }
+}
@@ -10,12 +10,30 @@ use chrono::{DateTime, Utc};
 use e3_events::{E3id, PartyId};
 use e3_utils::ArcBytes;
 
-use crate::{events::DocumentPublishedNotification, ContentHash};
+use super::document_chunking::{split_document, DOCUMENT_CHUNK_SIZE};
+use crate::{
+    events::{ChunkManifest, DocumentPublishedNotification},
+    ContentHash,
+};
+
+/// How a document is stored in the DHT.
+#[derive(Debug)]
+pub enum DocumentLayout {
+    /// A single record under the document's content hash
+    Single(ContentHash),
+    /// One record per chunk; the manifest travels with the publish notification
+    Chunked {
+        key: ContentHash,
+        manifest: ChunkManifest,
+        chunks: Vec<ArcBytes>,
+    },
+}
 
 /// Pure decision/state service backing the `DocumentPublisher` actor.
 ///
 /// Owns the bookkeeping that decides:
 /// - which E3s this node is interested in (so it knows which published documents to fetch),
+/// - whether a document is published as one DHT record or in chunks,
 /// - which DHT content hashes belong to each E3 (so they can be pruned on completion),
 /// - whether an incoming publish notification is relevant to this node.
 ///
@@ -45,15 +63,25 @@ impl DocumentPublishingService {
         self.dht_keys.remove(e3_id).unwrap_or_default()
     }
 
-    /// Compute the content hash for a value being published and record it against `e3_id`
-    /// so it can be pruned when the E3 completes.
-    pub fn track_published_key(&mut self, e3_id: &E3id, value: &ArcBytes) -> ContentHash {
+    /// Decide how a value being published is laid out in the DHT and record the keys of its
+    /// records against `e3_id` so they can be pruned when the E3 completes.
+    pub fn plan_publication(&mut self, e3_id: &E3id, value: &ArcBytes) -> DocumentLayout {
         let key = ContentHash::from_content(value);
-        self.dht_keys
-            .entry(e3_id.clone())
-            .or_default()
-            .push(key.clone());
-        key
+        let dht_keys = self.dht_keys.entry(e3_id.clone()).or_default();
+        match split_document(value, DOCUMENT_CHUNK_SIZE) {
+            None => {
+                dht_keys.push(key.clone());
+                DocumentLayout::Single(key)
+            }
+            Some((manifest, chunks)) => {
+                dht_keys.extend(manifest.chunks.iter().cloned());
+                DocumentLayout::Chunked {
+                    key,
+                    manifest,
+                    chunks,
+                }
+            }
+        }
     }
 
     /// Return our party id for a published document if (and only if) we are interested in it.
@@ -143,12 +171,19 @@ mod tests {
         assert_eq!(svc.interested_party(&n), Some(2));
     }
 
+    fn single_key(layout: DocumentLayout) -> ContentHash {
+        match layout {
+            DocumentLayout::Single(key) => key,
+            DocumentLayout::Chunked { .. } => panic!("expected a single record"),
+        }
+    }
+
     #[test]
     fn track_and_prune_keys_round_trip() {
         let mut svc = DocumentPublishingService::new();
         let e3 = E3id::new("1", 1);
-        let k1 = svc.track_published_key(&e3, &ArcBytes::from_bytes(b"one"));
-        let k2 = svc.track_published_key(&e3, &ArcBytes::from_bytes(b"two"));
+        let k1 = single_key(svc.plan_publication(&e3, &ArcBytes::from_bytes(b"one")));
+        let k2 = single_key(svc.plan_publication(&e3, &ArcBytes::from_bytes(b"two")));
         let pruned = svc.complete_e3(&e3);
         assert_eq!(pruned, vec![k1, k2]);
         // After completion the E3 is forgotten and yields nothing further.
@@ -156,6 +191,26 @@ mod tests {
         assert!(svc.interested_party(&notification("1", vec![])).is_none());
     }
 
+    #[test]
+    fn large_documents_are_chunked_and_every_chunk_is_pruned() {
+        let mut svc = DocumentPublishingService::new();
+        let e3 = E3id::new("1", 1);
+        let value = ArcBytes::from_bytes(&vec![7u8; DOCUMENT_CHUNK_SIZE * 2 + 1]);
+
+        let DocumentLayout::Chunked {
+            key,
+            manifest,
+            chunks,
+        } = svc.plan_publication(&e3, &value)
+        else {
+            panic!("expected a chunked layout");
+        };
+        assert_eq!(key, ContentHash::from_content(&value));
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(manifest.total_len, value.size() as u64);
+        assert_eq!(svc.complete_e3(&e3), manifest.chunks);
+    }
+
     #[test]
     fn datetime_helper_is_none_for_past() {
         assert!(datetime_to_instant_from_now(Utc::now() - chrono::Duration::days(1)).is_none());
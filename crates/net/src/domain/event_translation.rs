@@ -9,29 +9,31 @@ use bloom::{BloomFilter, ASMS};
 use e3_events::{prelude::*, Event, InterfoldEvent, InterfoldEventData, Unsequenced};
 use tracing::{trace, warn};
 
+use crate::domain::GossipTopics;
 use crate::events::GossipData;
 
 /// Pure translation/dedup logic backing the `NetEventTranslator` actor.
 ///
-/// Decides which local events should be gossiped to the network (and dedups them so the same
-/// event is never rebroadcast), and decodes inbound gossip into the internal event to publish.
+/// Decides which local events should be gossiped to the network and on which topic (and dedups
+/// them so the same event is never rebroadcast), and decodes inbound gossip into the internal
+/// event to publish.
 ///
 /// Holds no actix/bus/channel state — the actor performs the actual publish I/O.
 pub struct EventTranslationService {
     sent_events: BloomFilter,
-    topic: String,
+    topics: GossipTopics,
 }
 
 impl EventTranslationService {
     pub fn new(topic: &str) -> Self {
         Self {
             sent_events: BloomFilter::with_rate(0.001, 10_000),
-            topic: topic.to_string(),
+            topics: GossipTopics::new(topic),
         }
     }
 
-    pub fn topic(&self) -> &str {
-        &self.topic
+    pub fn topics(&self) -> &GossipTopics {
+        &self.topics
     }
 
     /// Function to determine which events are allowed to be automatically broadcast to the
@@ -46,14 +48,19 @@ impl EventTranslationService {
                 | InterfoldEventData::PublicKeyAggregated(_)
                 | InterfoldEventData::ProofFailureAccusation(_)
                 | InterfoldEventData::AccusationVote(_)
+                | InterfoldEventData::DkgShareComplaints(_)
+                | InterfoldEventData::DkgShareJustification(_)
         )
     }
 
     /// Decide whether a local event should be gossiped.
     ///
-    /// Returns `Some(GossipData)` to publish over the network, or `None` when the event is not
-    /// forwardable or has already been broadcast.
-    pub fn prepare_outbound(&mut self, event: InterfoldEvent) -> Result<Option<GossipData>> {
+    /// Returns `Some((topic, GossipData))` to publish over the network, or `None` when the event
+    /// is not forwardable or has already been broadcast.
+    pub fn prepare_outbound(
+        &mut self,
+        event: InterfoldEvent,
+    ) -> Result<Option<(String, GossipData)>> {
         if !Self::is_forwardable_event(&event) {
             let id = event.event_id();
             trace!(evt_id=%id, "Local events should not be rebroadcast so ignoring");
@@ -68,8 +75,9 @@ impl EventTranslationService {
         self.sent_events.insert(&id);
 
         warn!("GossipPublish event: {}", event.event_type());
+        let topic = self.topics.topic_for_event(&event);
         let data: GossipData = event.try_into()?;
-        Ok(Some(data))
+        Ok(Some((topic, data)))
     }
 
     /// Decode an inbound gossip payload into the internal event to publish locally, recording it
@@ -111,6 +119,28 @@ mod tests {
         assert!(svc.prepare_outbound(local_test_event()).unwrap().is_none());
     }
 
+    #[test]
+    fn e3_results_are_published_on_the_control_topic() {
+        let mut svc = EventTranslationService::new("topic");
+        let event: InterfoldEvent<Unsequenced> = InterfoldEvent::new_with_timestamp(
+            e3_events::PlaintextAggregated {
+                e3_id: e3_events::E3id::new("5", 1),
+                decrypted_output: vec![],
+                decryption_aggregator_proofs: vec![],
+            }
+            .into(),
+            None,
+            42,
+            None,
+            EventSource::Local,
+        );
+        let (topic, _) = svc
+            .prepare_outbound(event.into_sequenced(1))
+            .unwrap()
+            .expect("forwardable event should be gossiped");
+        assert_eq!(topic, "topic");
+    }
+
     #[test]
     fn inbound_gossip_round_trips_to_event() {
         let mut svc = EventTranslationService::new("topic");
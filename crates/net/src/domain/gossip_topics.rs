@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use e3_events::{prelude::*, E3id, InterfoldEvent, InterfoldEventData};
+
+/// Gossipsub topic layout.
+///
+/// Every node subscribes to the control topic (the configured base topic). Committee traffic —
+/// keyshares, decryption shares, proofs, accusations and document notifications — goes to a
+/// per-E3 topic that a node only joins while it is selected for that E3, so nodes no longer
+/// receive the traffic of every E3 on the network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GossipTopics {
+    control: String,
+}
+
+impl GossipTopics {
+    pub fn new(control: impl Into<String>) -> Self {
+        Self {
+            control: control.into(),
+        }
+    }
+
+    /// Topic every node is subscribed to.
+    pub fn control(&self) -> &str {
+        &self.control
+    }
+
+    /// Topic carrying the committee traffic of a single E3.
+    pub fn e3_topic(&self, e3_id: &E3id) -> String {
+        format!("{}/e3/{}/{}", self.control, e3_id.chain_id(), e3_id.e3_id())
+    }
+
+    /// Topic an outbound event is published on: its E3 topic when the event belongs to an E3,
+    /// otherwise the control topic. E3 results (`PublicKeyAggregated`, `PlaintextAggregated`)
+    /// stay on the control topic because nodes outside the committee consume them too.
+    pub fn topic_for_event(&self, event: &InterfoldEvent) -> String {
+        let data = event.get_data();
+        match data.get_e3_id() {
+            Some(_)
+                if matches!(
+                    data,
+                    InterfoldEventData::PublicKeyAggregated(_)
+                        | InterfoldEventData::PlaintextAggregated(_)
+                ) =>
+            {
+                self.control.clone()
+            }
+            Some(e3_id) => self.e3_topic(&e3_id),
+            None => self.control.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use e3_events::{
+        E3Failed, E3Stage, EventConstructorWithTimestamp, EventSource, FailureReason,
+        PlaintextAggregated, TestEvent, Unsequenced,
+    };
+
+    fn event(data: e3_events::InterfoldEventData) -> InterfoldEvent {
+        InterfoldEvent::<Unsequenced>::new_with_timestamp(data, None, 1, None, EventSource::Local)
+            .into_sequenced(1)
+    }
+
+    #[test]
+    fn e3_topics_are_scoped_by_chain_and_id() {
+        let topics = GossipTopics::new("interfold");
+        assert_eq!(topics.control(), "interfold");
+        assert_eq!(
+            topics.e3_topic(&E3id::new("42", 31337)),
+            "interfold/e3/31337/42"
+        );
+        assert_ne!(
+            topics.e3_topic(&E3id::new("42", 1)),
+            topics.e3_topic(&E3id::new("42", 31337))
+        );
+    }
+
+    #[test]
+    fn events_route_to_their_e3_topic_or_control() {
+        let topics = GossipTopics::new("interfold");
+        let e3_event = event(
+            E3Failed {
+                e3_id: E3id::new("7", 1),
+                failed_at_stage: E3Stage::None,
+                reason: FailureReason::None,
+            }
+            .into(),
+        );
+        assert_eq!(topics.topic_for_event(&e3_event), "interfold/e3/1/7");
+
+        let result = event(
+            PlaintextAggregated {
+                e3_id: E3id::new("7", 1),
+                decrypted_output: vec![],
+                decryption_aggregator_proofs: vec![],
+            }
+            .into(),
+        );
+        assert_eq!(topics.topic_for_event(&result), "interfold");
+
+        let control_event = event(TestEvent::new("no-e3", 1).into());
+        assert_eq!(topics.topic_for_event(&control_event), "interfold");
+    }
+}
@@ -9,16 +9,26 @@
 //! These contain all decision/state logic that the actix actors and transport layer rely on.
 //! Nothing here touches actix, the event bus, channels, or libp2p directly.
 
+pub(crate) mod bandwidth_meter;
 pub(crate) mod correlator;
+pub(crate) mod document_chunking;
 pub(crate) mod document_publishing;
 pub(crate) mod event_conversion;
 pub(crate) mod event_translation;
+pub(crate) mod gossip_topics;
 pub(crate) mod net_buffer;
 pub(crate) mod net_event_batch;
+pub(crate) mod operator_discovery;
 pub(crate) mod peer_failure_tracker;
+pub(crate) mod peer_identity_registry;
+pub(crate) mod pending_publishes;
 pub(crate) mod sync_coordinator;
 
-pub use document_publishing::{datetime_to_instant_from_now, DocumentPublishingService};
+pub use document_chunking::{split_document, ChunkAssembly, DOCUMENT_CHUNK_SIZE};
+pub use document_publishing::{
+    datetime_to_instant_from_now, DocumentLayout, DocumentPublishingService,
+};
 pub use event_conversion::{EventConversionService, IncomingDocument};
 pub use event_translation::EventTranslationService;
+pub use gossip_topics::GossipTopics;
 pub use sync_coordinator::{build_sync_batch, NetReadiness, ReadinessDecision, SyncBatchOutcome};
@@ -0,0 +1,250 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use crate::operator_record::OperatorAddressRecord;
+use alloy::primitives::Address;
+use libp2p::{kad::QueryId, Multiaddr, PeerId};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Operators are looked up again at most this often.
+const RESOLVE_INTERVAL: Duration = Duration::from_secs(300);
+/// Our record is republished at least this often even when our addresses did not change.
+const REPUBLISH_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Bookkeeping for publishing our own operator address record and resolving the records of the
+/// other registry members.
+///
+/// The network layer starts the DHT queries; this tracks which queries belong to discovery,
+/// throttles lookups and publications, and keeps the newest valid record per operator.
+pub(crate) struct OperatorDiscovery {
+    /// Our own operator, never looked up
+    local_operator: Option<Address>,
+    lookups: HashMap<QueryId, Address>,
+    publications: HashSet<QueryId>,
+    /// Per operator: `issued_at` of the newest accepted record and when it was last resolved
+    resolved: HashMap<Address, (i64, Instant)>,
+    last_published: Option<(Vec<Multiaddr>, Instant)>,
+}
+
+impl OperatorDiscovery {
+    pub fn new(local_operator: Option<Address>) -> Self {
+        Self {
+            local_operator,
+            lookups: HashMap::new(),
+            publications: HashSet::new(),
+            resolved: HashMap::new(),
+            last_published: None,
+        }
+    }
+
+    /// Whether `operator` should be looked up now: it is not us, no lookup is in flight and it
+    /// has not been resolved recently.
+    pub fn needs_lookup(&self, operator: &Address, now: Instant) -> bool {
+        if self.local_operator.as_ref() == Some(operator) {
+            return false;
+        }
+        let in_flight = self.lookups.values().any(|pending| pending == operator);
+        let fresh = self
+            .resolved
+            .get(operator)
+            .is_some_and(|(_, at)| now.duration_since(*at) < RESOLVE_INTERVAL);
+        !in_flight && !fresh
+    }
+
+    pub fn lookup_started(&mut self, query_id: QueryId, operator: Address) {
+        self.lookups.insert(query_id, operator);
+    }
+
+    pub fn is_lookup(&self, query_id: &QueryId) -> bool {
+        self.lookups.contains_key(query_id)
+    }
+
+    pub fn lookup_finished(&mut self, query_id: &QueryId) {
+        self.lookups.remove(query_id);
+    }
+
+    /// Accept a record found by lookup `query_id`. Returns the PeerId and addresses to dial when
+    /// the record is valid for the looked-up operator and newer than what we already know.
+    pub fn on_record(
+        &mut self,
+        query_id: &QueryId,
+        record: &OperatorAddressRecord,
+        now: Instant,
+    ) -> Option<(PeerId, Vec<Multiaddr>)> {
+        let operator = *self.lookups.get(query_id)?;
+        if record.operator != operator {
+            debug!(
+                "Ignoring address record of {} found under the key of {operator}",
+                record.operator
+            );
+            return None;
+        }
+        let (peer_id, addrs) = match record.verify() {
+            Ok(verified) => verified,
+            Err(e) => {
+                debug!("Ignoring invalid address record for {operator}: {e}");
+                return None;
+            }
+        };
+        match self.resolved.get(&operator) {
+            Some((issued_at, _)) if *issued_at >= record.issued_at => {
+                self.resolved.insert(operator, (*issued_at, now));
+                None
+            }
+            _ => {
+                self.resolved.insert(operator, (record.issued_at, now));
+                Some((peer_id, addrs))
+            }
+        }
+    }
+
+    /// Whether our record should be (re)published with `addrs`.
+    pub fn should_publish(&self, addrs: &[Multiaddr], now: Instant) -> bool {
+        if addrs.is_empty() {
+            return false;
+        }
+        match &self.last_published {
+            Some((published, at)) => {
+                published.as_slice() != addrs || now.duration_since(*at) >= REPUBLISH_INTERVAL
+            }
+            None => true,
+        }
+    }
+
+    pub fn publication_started(&mut self, query_id: QueryId, addrs: Vec<Multiaddr>, now: Instant) {
+        self.publications.insert(query_id);
+        self.last_published = Some((addrs, now));
+    }
+
+    /// A failed publication (e.g. no peers to store the record yet) is retried on the next check.
+    pub fn publication_finished(&mut self, query_id: &QueryId, success: bool) {
+        self.publications.remove(query_id);
+        if !success {
+            self.last_published = None;
+        }
+    }
+
+    pub fn is_publication(&self, query_id: &QueryId) -> bool {
+        self.publications.contains(query_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::local::PrivateKeySigner;
+    use libp2p::kad::{store::MemoryStore, Behaviour, Config, RecordKey};
+    use libp2p::StreamProtocol;
+
+    /// `QueryId`s can only be minted by a Kademlia behaviour, so start real (local) queries.
+    fn query_ids(n: usize) -> Vec<QueryId> {
+        let local = PeerId::random();
+        let mut kad = Behaviour::with_config(
+            local,
+            MemoryStore::new(local),
+            Config::new(StreamProtocol::new("/test/kad")),
+        );
+        (0..n)
+            .map(|i| kad.get_record(RecordKey::new(&i.to_be_bytes())))
+            .collect()
+    }
+
+    fn addr(port: u16) -> Multiaddr {
+        format!("/ip4/203.0.113.10/udp/{port}/quic-v1")
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn lookups_are_throttled_per_operator() {
+        let mut discovery = OperatorDiscovery::new(None);
+        let signer = PrivateKeySigner::random();
+        let operator = signer.address();
+        let now = Instant::now();
+        let [query] = query_ids(1).try_into().unwrap();
+
+        assert!(discovery.needs_lookup(&operator, now));
+        discovery.lookup_started(query, operator);
+        assert!(!discovery.needs_lookup(&operator, now), "lookup in flight");
+
+        let record =
+            OperatorAddressRecord::sign(&signer, &PeerId::random(), &[addr(9091)], 1).unwrap();
+        assert!(discovery.on_record(&query, &record, now).is_some());
+        discovery.lookup_finished(&query);
+
+        assert!(!discovery.needs_lookup(&operator, now), "recently resolved");
+        assert!(discovery.needs_lookup(&operator, now + RESOLVE_INTERVAL));
+    }
+
+    #[test]
+    fn the_local_operator_is_never_looked_up() {
+        let operator = PrivateKeySigner::random().address();
+        let discovery = OperatorDiscovery::new(Some(operator));
+        assert!(!discovery.needs_lookup(&operator, Instant::now()));
+    }
+
+    #[test]
+    fn only_newer_valid_records_of_the_looked_up_operator_are_dialed() {
+        let mut discovery = OperatorDiscovery::new(None);
+        let signer = PrivateKeySigner::random();
+        let peer_id = PeerId::random();
+        let now = Instant::now();
+        let [query] = query_ids(1).try_into().unwrap();
+        discovery.lookup_started(query, signer.address());
+
+        let newer = OperatorAddressRecord::sign(&signer, &peer_id, &[addr(2)], 2).unwrap();
+        let older = OperatorAddressRecord::sign(&signer, &peer_id, &[addr(1)], 1).unwrap();
+        assert_eq!(
+            discovery.on_record(&query, &newer, now),
+            Some((peer_id, vec![addr(2)]))
+        );
+        assert_eq!(discovery.on_record(&query, &older, now), None);
+
+        // A record of another operator planted under this operator's key
+        let planted =
+            OperatorAddressRecord::sign(&PrivateKeySigner::random(), &peer_id, &[addr(3)], 3)
+                .unwrap();
+        assert_eq!(discovery.on_record(&query, &planted, now), None);
+
+        // A forged record claiming to be this operator
+        let mut forged = OperatorAddressRecord::sign(&signer, &peer_id, &[addr(4)], 4).unwrap();
+        forged.addrs = vec![addr(5).to_string()];
+        assert_eq!(discovery.on_record(&query, &forged, now), None);
+    }
+
+    #[test]
+    fn records_are_republished_on_change_or_after_the_interval() {
+        let mut discovery = OperatorDiscovery::new(None);
+        let now = Instant::now();
+        let [query] = query_ids(1).try_into().unwrap();
+
+        assert!(!discovery.should_publish(&[], now));
+        assert!(discovery.should_publish(&[addr(1)], now));
+        discovery.publication_started(query, vec![addr(1)], now);
+        assert!(discovery.is_publication(&query));
+
+        assert!(!discovery.should_publish(&[addr(1)], now));
+        assert!(discovery.should_publish(&[addr(2)], now));
+        assert!(discovery.should_publish(&[addr(1)], now + REPUBLISH_INTERVAL));
+
+        discovery.publication_finished(&query, true);
+        assert!(!discovery.is_publication(&query));
+        assert!(!discovery.should_publish(&[addr(1)], now));
+    }
+
+    #[test]
+    fn failed_publications_are_retried() {
+        let mut discovery = OperatorDiscovery::new(None);
+        let now = Instant::now();
+        let [query] = query_ids(1).try_into().unwrap();
+
+        discovery.publication_started(query, vec![addr(1)], now);
+        discovery.publication_finished(&query, false);
+        assert!(discovery.should_publish(&[addr(1)], now));
+    }
+}
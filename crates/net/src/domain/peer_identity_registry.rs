@@ -0,0 +1,259 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use alloy::primitives::Address;
+use libp2p::PeerId;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Outcome of checking a peer's identity claim.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum IdentityDecision {
+    /// The peer is bound to `operator` and may stay connected.
+    Verified(Address),
+    /// The peer must be disconnected.
+    Rejected {
+        operator: Option<Address>,
+        reason: String,
+    },
+}
+
+/// Tracks which operator address every connected peer proved to be bound to and decides who
+/// may stay connected.
+///
+/// The allowlist is only enforced once one has been set (see [`Self::set_allowlist`]); until
+/// then every peer with a valid (or no) claim is admitted. Exempt peers — configured bootstrap
+/// peers and relays, which need not be operators — are never checked.
+pub(crate) struct PeerIdentityRegistry {
+    bindings: HashMap<PeerId, Address>,
+    pending: HashMap<PeerId, Instant>,
+    allowlist: Option<HashSet<Address>>,
+    exempt: HashSet<PeerId>,
+}
+
+impl PeerIdentityRegistry {
+    pub fn new(exempt: impl IntoIterator<Item = PeerId>) -> Self {
+        Self {
+            bindings: HashMap::new(),
+            pending: HashMap::new(),
+            allowlist: None,
+            exempt: exempt.into_iter().collect(),
+        }
+    }
+
+    pub fn is_enforcing(&self) -> bool {
+        self.allowlist.is_some()
+    }
+
+    /// Whether some connected peer is bound to `operator`.
+    pub fn has_operator(&self, operator: &Address) -> bool {
+        self.bindings.values().any(|bound| bound == operator)
+    }
+
+    /// A new peer connected; it has to present a claim before [`Self::expired_pending`] drops it.
+    pub fn on_connected(&mut self, peer_id: PeerId, now: Instant) {
+        if !self.exempt.contains(&peer_id) && !self.bindings.contains_key(&peer_id) {
+            self.pending.entry(peer_id).or_insert(now);
+        }
+    }
+
+    /// Record the result of verifying the claim `peer_id` presented.
+    pub fn on_claim(
+        &mut self,
+        peer_id: PeerId,
+        claim: anyhow::Result<Address>,
+    ) -> IdentityDecision {
+        self.pending.remove(&peer_id);
+        let operator = match claim {
+            Ok(operator) => operator,
+            Err(e) => {
+                self.bindings.remove(&peer_id);
+                return IdentityDecision::Rejected {
+                    operator: None,
+                    reason: format!("invalid identity claim: {e}"),
+                };
+            }
+        };
+        if !self.exempt.contains(&peer_id) && !self.is_allowed(&operator) {
+            self.bindings.remove(&peer_id);
+            return IdentityDecision::Rejected {
+                operator: Some(operator),
+                reason: format!("operator {operator} is not bonded"),
+            };
+        }
+        self.bindings.insert(peer_id, operator);
+        IdentityDecision::Verified(operator)
+    }
+
+    /// The peer answered without a claim (e.g. a node without a configured operator wallet).
+    /// Returns a rejection only while the allowlist is enforced.
+    pub fn on_missing_claim(&mut self, peer_id: PeerId) -> Option<IdentityDecision> {
+        if !self.is_enforcing() || self.exempt.contains(&peer_id) {
+            return None;
+        }
+        self.pending.remove(&peer_id);
+        Some(IdentityDecision::Rejected {
+            operator: None,
+            reason: "peer presented no identity claim".to_string(),
+        })
+    }
+
+    pub fn on_disconnected(&mut self, peer_id: &PeerId) {
+        self.pending.remove(peer_id);
+        self.bindings.remove(peer_id);
+    }
+
+    /// Replace the set of allowed operators and return the bound peers that are no longer
+    /// allowed, together with their operator.
+    pub fn set_allowlist(&mut self, operators: HashSet<Address>) -> Vec<(PeerId, Address)> {
+        self.allowlist = Some(operators);
+        let revoked: Vec<_> = self
+            .bindings
+            .iter()
+            .filter(|(peer_id, operator)| {
+                !self.exempt.contains(peer_id) && !self.is_allowed(operator)
+            })
+            .map(|(peer_id, operator)| (*peer_id, *operator))
+            .collect();
+        for (peer_id, _) in &revoked {
+            self.bindings.remove(peer_id);
+        }
+        revoked
+    }
+
+    /// Peers that have not presented a claim within `timeout`. Empty while the allowlist is not
+    /// enforced, so that nodes without an operator wallet can still join permissive networks.
+    pub fn expired_pending(&mut self, now: Instant, timeout: Duration) -> Vec<PeerId> {
+        if !self.is_enforcing() {
+            return vec![];
+        }
+        let expired: Vec<_> = self
+            .pending
+            .iter()
+            .filter(|(_, since)| now.duration_since(**since) >= timeout)
+            .map(|(peer_id, _)| *peer_id)
+            .collect();
+        for peer_id in &expired {
+            self.pending.remove(peer_id);
+        }
+        expired
+    }
+
+    fn is_allowed(&self, operator: &Address) -> bool {
+        self.allowlist
+            .as_ref()
+            .is_none_or(|allowed| allowed.contains(operator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operator(byte: u8) -> Address {
+        Address::repeat_byte(byte)
+    }
+
+    #[test]
+    fn valid_claims_are_admitted_until_an_allowlist_is_set() {
+        let mut registry = PeerIdentityRegistry::new([]);
+        let peer = PeerId::random();
+        registry.on_connected(peer, Instant::now());
+
+        assert_eq!(
+            registry.on_claim(peer, Ok(operator(1))),
+            IdentityDecision::Verified(operator(1))
+        );
+        assert_eq!(registry.bindings.get(&peer), Some(&operator(1)));
+        assert!(registry.has_operator(&operator(1)));
+        assert!(registry.on_missing_claim(PeerId::random()).is_none());
+    }
+
+    #[test]
+    fn invalid_claims_are_always_rejected() {
+        let mut registry = PeerIdentityRegistry::new([]);
+        let peer = PeerId::random();
+        let decision = registry.on_claim(peer, Err(anyhow::anyhow!("bad signature")));
+        assert!(matches!(
+            decision,
+            IdentityDecision::Rejected { operator: None, .. }
+        ));
+        assert_eq!(registry.bindings.get(&peer), None);
+    }
+
+    #[test]
+    fn allowlist_rejects_unbonded_operators_and_revokes_bound_peers() {
+        let mut registry = PeerIdentityRegistry::new([]);
+        let bonded = PeerId::random();
+        let unbonded = PeerId::random();
+        registry.on_claim(bonded, Ok(operator(1)));
+        registry.on_claim(unbonded, Ok(operator(2)));
+
+        let revoked = registry.set_allowlist(HashSet::from([operator(1)]));
+        assert_eq!(revoked, vec![(unbonded, operator(2))]);
+        assert_eq!(registry.bindings.get(&bonded), Some(&operator(1)));
+
+        let late = PeerId::random();
+        assert!(matches!(
+            registry.on_claim(late, Ok(operator(3))),
+            IdentityDecision::Rejected {
+                operator: Some(_),
+                ..
+            }
+        ));
+        assert!(registry.on_missing_claim(PeerId::random()).is_some());
+    }
+
+    #[test]
+    fn exempt_peers_bypass_the_allowlist() {
+        let relay = PeerId::random();
+        let mut registry = PeerIdentityRegistry::new([relay]);
+        registry.set_allowlist(HashSet::new());
+
+        registry.on_connected(relay, Instant::now());
+        assert!(registry.on_missing_claim(relay).is_none());
+        assert!(registry
+            .expired_pending(
+                Instant::now() + Duration::from_secs(60),
+                Duration::from_secs(1)
+            )
+            .is_empty());
+    }
+
+    #[test]
+    fn unauthenticated_peers_expire_only_while_enforcing() {
+        let mut registry = PeerIdentityRegistry::new([]);
+        let start = Instant::now();
+        let peer = PeerId::random();
+        registry.on_connected(peer, start);
+        let later = start + Duration::from_secs(30);
+
+        assert!(registry
+            .expired_pending(later, Duration::from_secs(10))
+            .is_empty());
+
+        registry.set_allowlist(HashSet::new());
+        assert!(registry
+            .expired_pending(start + Duration::from_secs(5), Duration::from_secs(10))
+            .is_empty());
+        assert_eq!(
+            registry.expired_pending(later, Duration::from_secs(10)),
+            vec![peer]
+        );
+        assert!(registry
+            .expired_pending(later, Duration::from_secs(10))
+            .is_empty());
+    }
+
+    #[test]
+    fn disconnect_forgets_the_peer() {
+        let mut registry = PeerIdentityRegistry::new([]);
+        let peer = PeerId::random();
+        registry.on_claim(peer, Ok(operator(1)));
+        registry.on_disconnected(&peer);
+        assert_eq!(registry.bindings.get(&peer), None);
+    }
+}
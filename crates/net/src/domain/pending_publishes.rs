@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use e3_events::CorrelationId;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long a publish may wait for topic peers before it is reported as failed.
+pub(crate) const PENDING_PUBLISH_TTL: Duration = Duration::from_secs(30);
+
+/// Upper bound on queued publishes; beyond it publishes fail immediately.
+const MAX_PENDING_PUBLISHES: usize = 256;
+
+/// A gossip publish that found no peers on its topic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PendingPublish {
+    pub topic: String,
+    pub bytes: Vec<u8>,
+    pub correlation_id: CorrelationId,
+    queued_at: Instant,
+}
+
+/// Publishes waiting for their topic mesh to form.
+///
+/// Right after joining an E3 topic the node knows no peers subscribed to it yet, so gossipsub
+/// rejects publishes with `InsufficientPeers`. Instead of dropping them, the network layer parks
+/// them here and retries them until peers show up or [`PENDING_PUBLISH_TTL`] elapses.
+pub(crate) struct PendingPublishes {
+    queue: VecDeque<PendingPublish>,
+}
+
+impl PendingPublishes {
+    pub fn new() -> Self {
+        Self {
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Queue a publish for retry. Returns `false` when the queue is full and the publish should
+    /// be reported as failed right away.
+    pub fn push(
+        &mut self,
+        topic: String,
+        bytes: Vec<u8>,
+        correlation_id: CorrelationId,
+        now: Instant,
+    ) -> bool {
+        if self.queue.len() >= MAX_PENDING_PUBLISHES {
+            return false;
+        }
+        self.queue.push_back(PendingPublish {
+            topic,
+            bytes,
+            correlation_id,
+            queued_at: now,
+        });
+        true
+    }
+
+    /// Drain the queue into publishes to retry now and publishes that waited past
+    /// [`PENDING_PUBLISH_TTL`], both in queue order.
+    pub fn take(&mut self, now: Instant) -> (Vec<PendingPublish>, Vec<PendingPublish>) {
+        self.queue
+            .drain(..)
+            .partition(|p| now.duration_since(p.queued_at) < PENDING_PUBLISH_TTL)
+    }
+
+    /// Put back a retried publish that still found no peers, keeping its original deadline.
+    pub fn requeue(&mut self, publish: PendingPublish) {
+        self.queue.push_back(publish);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_until_the_deadline_then_expires() {
+        let mut pending = PendingPublishes::new();
+        let start = Instant::now();
+        let id = CorrelationId::new();
+        assert!(pending.push("t/e3/1/1".to_string(), vec![1], id, start));
+
+        let (retry, expired) = pending.take(start + Duration::from_secs(1));
+        assert_eq!(retry.len(), 1);
+        assert!(expired.is_empty());
+        assert!(pending.is_empty());
+
+        pending.requeue(retry.into_iter().next().unwrap());
+        let (retry, expired) = pending.take(start + PENDING_PUBLISH_TTL);
+        assert!(retry.is_empty());
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].correlation_id, id);
+    }
+
+    #[test]
+    fn rejects_publishes_beyond_capacity() {
+        let mut pending = PendingPublishes::new();
+        let now = Instant::now();
+        for _ in 0..MAX_PENDING_PUBLISHES {
+            assert!(pending.push("t".to_string(), vec![], CorrelationId::new(), now));
+        }
+        assert!(!pending.push("t".to_string(), vec![], CorrelationId::new(), now));
+    }
+}
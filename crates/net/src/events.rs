@@ -4,8 +4,13 @@
 // without even the implied warranty of MERCHANTABILITY
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
-use crate::{direct_responder::DirectResponder, ContentHash};
+use crate::{
+    bandwidth::{BandwidthReport, LimitViolation, NetProtocol},
+    direct_responder::DirectResponder,
+    ContentHash,
+};
 use actix::Message;
+use alloy::primitives::Address;
 use anyhow::{anyhow, bail, Context, Result};
 use e3_events::{
     CorrelationId, DocumentMeta, EventContextAccessors, EventSource, InterfoldEvent, Sequenced,
@@ -17,10 +22,11 @@ use libp2p::{
     kad::{store, GetRecordError, PutRecordError},
     request_response::ResponseChannel,
     swarm::{dial_opts::DialOpts, ConnectionId, DialError},
+    Multiaddr,
 };
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     hash::Hash,
     sync::Arc,
     time::{Duration, Instant},
@@ -82,6 +88,16 @@ pub enum ProtocolResponse {
     Error(String),
 }
 
+impl ProtocolResponse {
+    /// Size of the payload or error message in bytes.
+    pub fn payload_len(&self) -> usize {
+        match self {
+            ProtocolResponse::Ok(bytes) => bytes.len(),
+            ProtocolResponse::BadRequest(msg) | ProtocolResponse::Error(msg) => msg.len(),
+        }
+    }
+}
+
 pub type ProtocolResponseChannel = ResponseChannel<ProtocolResponse>;
 
 #[derive(Message, Clone, Debug)]
@@ -157,6 +173,14 @@ pub enum NetCommand {
         data: GossipData,
         correlation_id: CorrelationId,
     },
+    /// Join a gossipsub topic (e.g. the topic of an E3 this node was selected for)
+    GossipSubscribe {
+        topic: String,
+    },
+    /// Leave a gossipsub topic
+    GossipUnsubscribe {
+        topic: String,
+    },
     /// Dial peer
     Dial(OnceTake<DialOpts>),
     /// Command to PublishDocument to Kademlia
@@ -175,6 +199,15 @@ pub enum NetCommand {
     DhtRemoveRecords {
         keys: Vec<ContentHash>,
     },
+    /// Only admit peers whose verified operator is in this set (bonded operators). Peers already
+    /// bound to an operator outside the set are disconnected.
+    SetOperatorAllowlist {
+        operators: HashSet<Address>,
+    },
+    /// Look up the signed address records of these operators in the DHT and dial them
+    ResolveOperators {
+        operators: HashSet<Address>,
+    },
     /// Shutdown signal
     Shutdown,
     /// Send a request to a peer and await response
@@ -218,7 +251,41 @@ pub enum NetEvent {
     /// A connection was established to a peer
     ConnectionEstablished {
         connection_id: ConnectionId,
+        peer_id: PeerId,
+        /// Whether the connection runs over a circuit relay
+        relayed: bool,
+    },
+    /// A circuit relay accepted (or renewed) our reservation, so peers can reach us through it
+    RelayReservationAccepted {
+        relay_peer_id: PeerId,
     },
+    /// A peer proved that its PeerId is bound to an operator address
+    PeerIdentityVerified {
+        peer_id: PeerId,
+        operator: Address,
+    },
+    /// A peer failed identity verification or is not an allowed operator and was disconnected
+    PeerRejected {
+        peer_id: PeerId,
+        operator: Option<Address>,
+        reason: String,
+    },
+    /// A signed address record of an operator was found in the DHT and its node is being dialed
+    OperatorResolved {
+        operator: Address,
+        peer_id: PeerId,
+        addrs: Vec<Multiaddr>,
+    },
+    /// Our signed operator address record was stored in the DHT
+    OperatorRecordPublished,
+    /// A message of a peer exceeded a size cap or the peer's rate limit and was dropped
+    PeerLimitExceeded {
+        peer_id: PeerId,
+        protocol: NetProtocol,
+        violation: LimitViolation,
+    },
+    /// Periodic snapshot of the traffic per protocol and peer
+    BandwidthReport(BandwidthReport),
     /// There was an error creating a connection
     OutgoingConnectionError {
         connection_id: ConnectionId,
@@ -287,19 +354,54 @@ impl NetEvent {
     }
 }
 
+/// Layout of a document published in chunks. Every chunk is stored in the DHT under its own
+/// content hash, so chunks can be fetched in parallel from different holders, verified one by
+/// one and re-fetched individually.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChunkManifest {
+    /// Length of the whole document in bytes
+    pub total_len: u64,
+    /// Content hashes of the chunks in document order
+    pub chunks: Vec<ContentHash>,
+}
+
+impl ChunkManifest {
+    /// Content hash over the length and chunk hashes, identifying this exact manifest
+    pub fn hash(&self) -> ContentHash {
+        let mut bytes = self.total_len.to_be_bytes().to_vec();
+        for chunk in &self.chunks {
+            bytes.extend_from_slice(chunk.as_ref());
+        }
+        ContentHash::from_content(&bytes)
+    }
+}
+
 /// Payload that is dispatched as a net -> net gossip event from Kademlia. This event signals that
 /// a document was published and that this node might be interested in it.
 #[derive(Message, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[rtype(result = "()")]
 pub struct DocumentPublishedNotification {
     pub meta: DocumentMeta,
+    /// Content hash of the whole document
     pub key: ContentHash,
     pub ts: u128,
+    /// Set when the document was too large for one DHT record and was published in chunks
+    pub manifest: Option<ChunkManifest>,
 }
 
 impl DocumentPublishedNotification {
     pub fn new(meta: DocumentMeta, key: ContentHash, ts: u128) -> Self {
-        Self { meta, key, ts }
+        Self {
+            meta,
+            key,
+            ts,
+            manifest: None,
+        }
+    }
+
+    pub fn with_manifest(mut self, manifest: ChunkManifest) -> Self {
+        self.manifest = Some(manifest);
+        self
     }
 
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
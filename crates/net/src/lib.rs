@@ -5,6 +5,7 @@
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
 mod actors;
+pub mod bandwidth;
 mod cid;
 mod dialer;
 pub mod direct_requester;
@@ -14,18 +15,27 @@ pub mod events;
 mod keypair;
 mod net_interface;
 mod net_interface_handle;
+pub mod operator_record;
+pub mod peer_identity;
 mod repo;
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use actix::Recipient;
+use alloy::signers::local::PrivateKeySigner;
 use anyhow::bail;
 use anyhow::Result;
+use bandwidth::BandwidthReport;
+use e3_config::{NetDiscoveryConfig, NetLimitsConfig, NetTransportConfig};
 use e3_crypto::Cipher;
 use e3_data::Repository;
 use e3_events::{run_once, BusHandle, EffectsEnabled, EventStoreQueryBy, EventSubscriber, TsAgg};
+use events::NetEvent;
+use peer_identity::{PeerIdentityClaim, PeerIdentityRecord};
+use tokio::sync::broadcast::error::RecvError;
 use tracing::error;
-use tracing::{info, instrument};
+use tracing::{info, instrument, warn};
 
 use actors::{NetEventBuffer, NetSyncManager};
 
@@ -51,13 +61,25 @@ pub async fn setup_libp2p_keypair(
     Libp2pKeypair::try_from_bytes(&mut bytes)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn setup_net_interface(
     topic: &str,
     keypair: Libp2pKeypair,
     peers: Vec<String>,
     quic_port: u16,
+    transport: NetTransportConfig,
+    discovery: NetDiscoveryConfig,
+    limits: NetLimitsConfig,
+    operator_signer: Option<PrivateKeySigner>,
 ) -> Result<NetInterfaceHandle> {
-    let mut interface = Libp2pNetInterface::new(keypair, peers, Some(quic_port), topic)?;
+    let peer_id = keypair.peer_id();
+    let mut interface =
+        Libp2pNetInterface::with_transport(keypair, peers, Some(quic_port), transport, topic)?
+            .with_discovery(discovery, operator_signer.clone())
+            .with_limits(limits);
+    if let Some(signer) = operator_signer {
+        interface = interface.with_identity(PeerIdentityClaim::sign(&signer, &peer_id)?);
+    }
 
     let handle = interface.handle();
 
@@ -70,6 +92,64 @@ pub fn setup_net_interface(
     Ok(handle)
 }
 
+/// Persist every verified PeerId -> operator binding so it can be inspected with
+/// `interfold net peers`.
+pub fn record_peer_identities(
+    repository: Repository<BTreeMap<String, PeerIdentityRecord>>,
+    interface: &impl NetInterface,
+) {
+    let mut rx = interface.rx();
+    tokio::spawn(async move {
+        loop {
+            let (peer_id, operator) = match rx.recv().await {
+                Ok(NetEvent::PeerIdentityVerified { peer_id, operator }) => (peer_id, operator),
+                Ok(_) => continue,
+                Err(RecvError::Lagged(n)) => {
+                    warn!("Peer identity recorder lagged, skipped {n} events");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+            let mut identities = match repository.read().await {
+                Ok(identities) => identities.unwrap_or_default(),
+                Err(e) => {
+                    error!("Failed to read peer identities: {e}");
+                    continue;
+                }
+            };
+            let peer_id = peer_id.to_string();
+            identities.insert(
+                peer_id.clone(),
+                PeerIdentityRecord {
+                    peer_id,
+                    operator,
+                    verified_at: chrono::Utc::now().timestamp(),
+                },
+            );
+            repository.write(&identities);
+        }
+    });
+}
+
+/// Persist the periodic bandwidth reports of the interface so the latest one can be inspected
+/// with `interfold net bandwidth`.
+pub fn record_bandwidth(repository: Repository<BandwidthReport>, interface: &impl NetInterface) {
+    let mut rx = interface.rx();
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(NetEvent::BandwidthReport(report)) => repository.write(&report),
+                Ok(_) => continue,
+                Err(RecvError::Lagged(n)) => {
+                    warn!("Bandwidth recorder lagged, skipped {n} events");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
 /// Spawn a Libp2p interface and hook it up to this actor
 #[instrument(name = "libp2p", skip_all)]
 pub fn setup_net(
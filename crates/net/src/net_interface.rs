@@ -5,6 +5,7 @@
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
 use crate::{
+    bandwidth::{LimitViolation, NetProtocol},
     dialer::dial_peers,
     events::{
         GossipData, IncomingRequest, NetCommand, NetEvent, OutgoingRequestFailed,
@@ -14,33 +15,50 @@ use crate::{
 };
 use crate::{
     direct_responder::{ChannelType, DirectResponder},
-    domain::{correlator::Correlator, peer_failure_tracker::PeerFailureTracker},
+    domain::{
+        bandwidth_meter::BandwidthMeter,
+        correlator::Correlator,
+        operator_discovery::OperatorDiscovery,
+        peer_failure_tracker::PeerFailureTracker,
+        peer_identity_registry::{IdentityDecision, PeerIdentityRegistry},
+        pending_publishes::{PendingPublish, PendingPublishes},
+    },
     events::{IncomingResponse, OutgoingRequest, ProtocolResponse},
     keypair::Libp2pKeypair,
     net_interface_handle::NetInterfaceHandle,
+    operator_record::{check_record_put, operator_record_key, OperatorAddressRecord},
+    peer_identity::{PeerIdentityClaim, PEER_IDENTITY_PROTOCOL},
 };
+use alloy::primitives::Address;
+use alloy::signers::local::PrivateKeySigner;
 use anyhow::{bail, Context, Result};
+use e3_config::{NetDiscoveryConfig, NetLimitsConfig, NetTransportConfig};
 use e3_events::CorrelationId;
 use e3_utils::ArcBytes;
 use libp2p::{
+    autonat,
     connection_limits::{self, ConnectionLimits},
+    dcutr,
     futures::StreamExt,
-    gossipsub,
+    gossipsub::{self, PublishError},
     identify::{Behaviour as IdentifyBehaviour, Config as IdentifyConfig},
     identity::Keypair,
     kad::{
         self,
         store::{MemoryStore, MemoryStoreConfig, RecordStore},
-        Behaviour as KademliaBehaviour, Config as KademliaConfig, GetRecordOk, QueryResult, Quorum,
-        Record, RecordKey,
+        Behaviour as KademliaBehaviour, Config as KademliaConfig, GetRecordOk, InboundRequest,
+        QueryResult, Quorum, Record, RecordKey, StoreInserts,
     },
     multiaddr::Protocol,
+    noise, relay,
     request_response::{
         self, cbor, Event as RequestResponseEvent, Message as RequestResponseMessage,
         ProtocolSupport,
     },
-    swarm::{dial_opts::DialOpts, DialError, NetworkBehaviour, SwarmEvent},
-    Multiaddr, StreamProtocol, Swarm,
+    swarm::{
+        behaviour::toggle::Toggle, dial_opts::DialOpts, DialError, NetworkBehaviour, SwarmEvent,
+    },
+    tcp, yamux, Multiaddr, PeerId, StreamProtocol, Swarm,
 };
 use rand::prelude::IteratorRandom;
 use std::{
@@ -59,9 +77,34 @@ const MAX_KADEMLIA_PAYLOAD_MB: usize = 100;
 const MAX_KADEMLIA_RECORD_MB: usize = 25; // Largest record: ~21MB ThresholdShare with prod params
 const DHT_MAX_RECORDS: usize = 4096;
 const MAX_GOSSIP_MSG_SIZE_KB: usize = 10240; // 10MB — prod params C6 proofs are ~4.6MB
+const MAX_REQUEST_RESPONSE_MB: usize = 10; // Response size limit of the cbor codec
 const MAX_CONSECUTIVE_DIAL_FAILURES: u32 = 40;
 const EVENT_CHANNEL_SIZE: usize = 1000;
 const CMD_CHANNEL_SIZE: usize = 1000;
+/// How long a peer may stay connected without proving its operator identity while the operator
+/// allowlist is enforced.
+const PEER_IDENTITY_TIMEOUT: Duration = Duration::from_secs(30);
+const PEER_IDENTITY_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// How often we check whether our operator address record needs to be (re)published
+const OPERATOR_RECORD_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const OPERATOR_RECORD_INITIAL_DELAY: Duration = Duration::from_secs(5);
+const OPERATOR_RECORD_TTL: Duration = Duration::from_secs(3600);
+const BANDWIDTH_REPORT_INTERVAL: Duration = Duration::from_secs(30);
+/// How often publishes that found no topic peers are retried.
+const PENDING_PUBLISH_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+/// Peers are disconnected once this many of their messages were dropped for exceeding limits
+/// within the bandwidth meter's violation window
+const MAX_PEER_LIMIT_VIOLATIONS: u64 = 20;
+
+/// Largest message each protocol can carry; configured size caps are clamped to it.
+fn protocol_ceiling(protocol: NetProtocol) -> u64 {
+    let bytes = match protocol {
+        NetProtocol::Gossipsub => MAX_GOSSIP_MSG_SIZE_KB * 1024,
+        NetProtocol::RequestResponse => MAX_REQUEST_RESPONSE_MB * 1024 * 1024,
+        NetProtocol::Kademlia => MAX_KADEMLIA_RECORD_MB * 1024 * 1024,
+    };
+    bytes as u64
+}
 
 /// Returns true if the multiaddr contains a loopback IP (127.0.0.0/8 or ::1).
 /// Loopback addresses are only meaningful on the local machine and must not be
@@ -114,6 +157,16 @@ pub struct NodeBehaviour {
     identify: IdentifyBehaviour,
     /// Send bytes reply with enumeration for errors
     request_response: cbor::Behaviour<Vec<u8>, ProtocolResponse>,
+    /// Reserve slots on relays so NATed nodes stay reachable
+    relay_client: relay::client::Behaviour,
+    /// Relay connections for other nodes when `relay_server` is enabled
+    relay_server: Toggle<relay::Behaviour>,
+    /// Reachability probing
+    autonat: Toggle<autonat::Behaviour>,
+    /// Hole punching to upgrade relayed connections to direct ones
+    dcutr: Toggle<dcutr::Behaviour>,
+    /// Exchange of operator-signed PeerId bindings
+    peer_identity: cbor::Behaviour<PeerIdentityClaim, Option<PeerIdentityClaim>>,
 }
 
 /// Manage the peer to peer connection. This struct wraps a libp2p Swarm and enables communication
@@ -125,8 +178,18 @@ pub struct Libp2pNetInterface {
     peers: Vec<String>,
     /// The UDP port that the peer listens to over QUIC
     udp_port: Option<u16>,
+    /// Additional transports (TCP, relays) and NAT traversal options
+    transport: NetTransportConfig,
     /// The gossipsub topic that the peer should listen on
     topic: gossipsub::IdentTopic,
+    /// Our operator's signature over our PeerId, presented to every peer we connect to
+    identity: Option<PeerIdentityClaim>,
+    /// DHT bootstrap, random walk and operator address record options
+    discovery: NetDiscoveryConfig,
+    /// Operator wallet signing our address records when `discovery.publish_addrs` is set
+    record_signer: Option<PrivateKeySigner>,
+    /// Inbound size caps and per-peer rate limits
+    limits: NetLimitsConfig,
     /// Broadcast channel to report NetEvents to listeners
     event_tx: broadcast::Sender<NetEvent>,
     /// Transmission channel to send NetCommands to the Libp2pNetInterface
@@ -141,32 +204,84 @@ impl Libp2pNetInterface {
         peers: Vec<String>,
         udp_port: Option<u16>,
         topic: &str,
+    ) -> Result<Self> {
+        Self::with_transport(id, peers, udp_port, NetTransportConfig::default(), topic)
+    }
+
+    /// Create an interface that, besides QUIC, listens on TCP and/or behind circuit relays as
+    /// configured in `transport`.
+    pub fn with_transport(
+        id: Libp2pKeypair,
+        peers: Vec<String>,
+        udp_port: Option<u16>,
+        transport: NetTransportConfig,
+        topic: &str,
     ) -> Result<Self> {
         let (event_tx, _) = broadcast::channel(EVENT_CHANNEL_SIZE);
         let (cmd_tx, cmd_rx) = mpsc::channel(CMD_CHANNEL_SIZE);
 
+        // TCP and the relay client transport are always available for dialing so that nodes
+        // behind UDP-hostile networks remain reachable; the config only decides which listeners
+        // are opened and which NAT traversal behaviours run.
         let swarm = libp2p::SwarmBuilder::with_existing_identity(id.into_keypair())
             .with_tokio()
+            .with_tcp(
+                tcp::Config::default().nodelay(true),
+                noise::Config::new,
+                yamux::Config::default,
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to enable TCP: {e}"))?
             .with_quic()
             .with_dns()
             .map_err(|e| anyhow::anyhow!("Failed to enable DNS: {e}"))?
-            .with_behaviour(create_behaviour)?
+            .with_relay_client(noise::Config::new, yamux::Config::default)
+            .map_err(|e| anyhow::anyhow!("Failed to enable relay client: {e}"))?
+            .with_behaviour(|key, relay_client| create_behaviour(key, relay_client, &transport))?
             .build();
 
-        // TODO: Use topics to manage network traffic instead of just using a single topic
+        // Control topic; per-E3 topics are joined and left via GossipSubscribe/GossipUnsubscribe
         let topic = gossipsub::IdentTopic::new(topic);
 
         Ok(Self {
             swarm,
             peers,
             udp_port,
+            transport,
             topic,
+            identity: None,
+            discovery: NetDiscoveryConfig::default(),
+            record_signer: None,
+            limits: NetLimitsConfig::default(),
             event_tx,
             cmd_tx,
             cmd_rx,
         })
     }
 
+    /// Present `claim` to peers so they can bind our PeerId to our operator address.
+    pub fn with_identity(mut self, claim: PeerIdentityClaim) -> Self {
+        self.identity = Some(claim);
+        self
+    }
+
+    /// Configure DHT bootstrap, random walks and operator address records. `signer` (the
+    /// operator wallet) is required to publish our own record.
+    pub fn with_discovery(
+        mut self,
+        discovery: NetDiscoveryConfig,
+        signer: Option<PrivateKeySigner>,
+    ) -> Self {
+        self.discovery = discovery;
+        self.record_signer = signer;
+        self
+    }
+
+    /// Configure the message size caps and per-peer rate limits applied to inbound traffic.
+    pub fn with_limits(mut self, limits: NetLimitsConfig) -> Self {
+        self.limits = limits;
+        self
+    }
+
     pub fn handle(&self) -> NetInterfaceHandle {
         NetInterfaceHandle::new(self.cmd_tx.clone(), self.event_tx.subscribe())
     }
@@ -184,12 +299,52 @@ impl Libp2pNetInterface {
         let mut peer_id_mismatches = PeerFailureTracker::new();
         // This is to make sure we dont spam warnings in the logs
         let mut last_backpressure_warn = Instant::now();
+        // Configured peers and relays are trusted and need not be operators
+        let mut identities = PeerIdentityRegistry::new(
+            self.peers
+                .iter()
+                .chain(&self.transport.relays)
+                .filter_map(|addr| addr.parse::<Multiaddr>().ok())
+                .filter_map(|addr| match addr.iter().last() {
+                    Some(Protocol::P2p(peer_id)) => Some(peer_id),
+                    _ => None,
+                }),
+        );
+        let mut identity_check = tokio::time::interval(PEER_IDENTITY_CHECK_INTERVAL);
+        let mut bandwidth = BandwidthMeter::new(&self.limits, protocol_ceiling);
+        let mut bandwidth_report = tokio::time::interval_at(
+            tokio::time::Instant::now() + BANDWIDTH_REPORT_INTERVAL,
+            BANDWIDTH_REPORT_INTERVAL,
+        );
+        let mut operators = OperatorDiscovery::new(self.identity.as_ref().map(|c| c.operator));
+        let mut pending_publishes = PendingPublishes::new();
+        let mut pending_publish_retry = tokio::time::interval(PENDING_PUBLISH_RETRY_INTERVAL);
+        let record_signer = self
+            .record_signer
+            .clone()
+            .filter(|_| self.discovery.publish_addrs);
+        if self.discovery.publish_addrs && record_signer.is_none() {
+            warn!("discovery.publish_addrs is set but no operator signer is available");
+        }
+        // Give the seed peers a moment to connect so the first record has somewhere to go
+        let mut record_check = tokio::time::interval_at(
+            tokio::time::Instant::now() + OPERATOR_RECORD_INITIAL_DELAY,
+            OPERATOR_RECORD_CHECK_INTERVAL,
+        );
+        let random_walk_period =
+            Duration::from_secs(self.discovery.random_walk_interval_secs.max(1));
+        let mut random_walk = tokio::time::interval_at(
+            tokio::time::Instant::now() + random_walk_period,
+            random_walk_period,
+        );
+        let bootstrap_period = Duration::from_secs(self.discovery.bootstrap_interval_secs.max(1));
+        let mut bootstrap = tokio::time::interval_at(
+            tokio::time::Instant::now() + bootstrap_period,
+            bootstrap_period,
+        );
 
         // Subscribe to topic
-        self.swarm
-            .behaviour_mut()
-            .gossipsub
-            .subscribe(&self.topic)?;
+        join_topic(&mut self.swarm.behaviour_mut().gossipsub, &self.topic)?;
 
         // Listen on the quic port
         let addr = match self.udp_port {
@@ -200,6 +355,34 @@ impl Libp2pNetInterface {
         trace!("Requesting node.listen_on('{}')", addr);
         self.swarm.listen_on(addr.parse()?)?;
 
+        // Listen on TCP as a fallback for networks that block UDP
+        if let Some(port) = self.transport.tcp_port {
+            let addr = format!("/ip4/0.0.0.0/tcp/{}", port);
+            trace!("Requesting node.listen_on('{}')", addr);
+            self.swarm.listen_on(addr.parse()?)?;
+        }
+
+        // Announce configured public addresses (relay servers hand these out in reservations)
+        for addr in &self.transport.external_addrs {
+            let addr: Multiaddr = addr
+                .parse()
+                .with_context(|| format!("Invalid external address '{addr}'"))?;
+            self.swarm.add_external_address(addr);
+        }
+
+        // Reserve a slot on each relay so peers can reach us via /p2p-circuit
+        for relay in &self.transport.relays {
+            let relay_addr: Multiaddr = relay
+                .parse()
+                .with_context(|| format!("Invalid relay address '{relay}'"))?;
+            if !matches!(relay_addr.iter().last(), Some(Protocol::P2p(_))) {
+                bail!("Relay address '{relay}' must end with /p2p/<relay-peer-id>");
+            }
+            let circuit_addr = relay_addr.with(Protocol::P2pCircuit);
+            info!("Requesting relay reservation via {circuit_addr}");
+            self.swarm.listen_on(circuit_addr)?;
+        }
+
         trace!("Peers to dial: {:?}", self.peers);
         if self.peers.is_empty() {
             info!("Found 0 peers to dial");
@@ -232,13 +415,13 @@ impl Libp2pNetInterface {
                         break;
                     }
 
-                    if let Err(e) = process_swarm_command(&mut self.swarm, &event_tx, &mut correlator, command).await {
+                    if let Err(e) = process_swarm_command(&mut self.swarm, &event_tx, &mut correlator, &mut identities, &mut operators, &mut bandwidth, &mut pending_publishes, command).await {
                         error!("Error processing NetCommand: {e}")
                     }
                 }
                 // Process events
                 event = self.swarm.select_next_some() =>  {
-                    match process_swarm_event(&mut self.swarm, &event_tx, &cmd_tx, &mut correlator, &mut peer_failures, &mut peer_id_mismatches, event).await {
+                    match process_swarm_event(&mut self.swarm, &event_tx, &cmd_tx, &mut correlator, &mut peer_failures, &mut peer_id_mismatches, self.identity.as_ref(), &mut identities, &mut operators, &mut bandwidth, event).await {
                         Ok(_) => (),
                         Err(e) => error!("Error processing NetEvent: {e}")
                     }
@@ -250,6 +433,43 @@ impl Libp2pNetInterface {
                         last_backpressure_warn = Instant::now();
                     }
                 }
+                // Keep our signed address record in the DHT current
+                _ = record_check.tick(), if record_signer.is_some() => {
+                    if let Some(signer) = &record_signer {
+                        if let Err(e) = publish_operator_record(&mut self.swarm, &mut operators, signer) {
+                            warn!("Could not publish operator address record: {e}");
+                        }
+                    }
+                }
+                // Look up a random PeerId to refresh distant buckets of the routing table
+                _ = random_walk.tick() => {
+                    self.swarm.behaviour_mut().kademlia.get_closest_peers(PeerId::random());
+                }
+                _ = bootstrap.tick() => {
+                    if let Err(e) = self.swarm.behaviour_mut().kademlia.bootstrap() {
+                        debug!("Periodic Kademlia bootstrap not possible: {e}");
+                    }
+                }
+                _ = bandwidth_report.tick() => {
+                    let report = bandwidth.report(Instant::now(), chrono::Utc::now().timestamp());
+                    if let Err(e) = event_tx.send(NetEvent::BandwidthReport(report)) {
+                        debug!("No listeners for the bandwidth report: {e}");
+                    }
+                }
+                // Retry publishes made before the topic mesh formed (e.g. right after joining an E3 topic)
+                _ = pending_publish_retry.tick(), if !pending_publishes.is_empty() => {
+                    if let Err(e) = retry_pending_publishes(&mut self.swarm, &event_tx, &mut pending_publishes) {
+                        error!("Error retrying pending publishes: {e}");
+                    }
+                }
+                // Drop peers that did not authenticate in time
+                _ = identity_check.tick() => {
+                    for peer_id in identities.expired_pending(Instant::now(), PEER_IDENTITY_TIMEOUT) {
+                        if let Err(e) = reject_peer(&mut self.swarm, &event_tx, peer_id, None, "identity claim timed out".to_string()) {
+                            error!("Error rejecting peer: {e}");
+                        }
+                    }
+                }
 
             }
         }
@@ -262,6 +482,8 @@ impl Libp2pNetInterface {
 /// Create the libp2p behaviour
 fn create_behaviour(
     key: &Keypair,
+    relay_client: relay::client::Behaviour,
+    transport: &NetTransportConfig,
 ) -> std::result::Result<NodeBehaviour, Box<dyn std::error::Error + Send + Sync + 'static>> {
     let peer_id = key.public().to_peer_id();
     let connection_limits = connection_limits::Behaviour::new(ConnectionLimits::default());
@@ -274,13 +496,19 @@ fn create_behaviour(
         .heartbeat_interval(Duration::from_secs(10))
         .max_transmit_size(MAX_GOSSIP_MSG_SIZE_KB * 1024)
         .validation_mode(gossipsub::ValidationMode::Strict)
+        // Messages are only forwarded once they passed our size and rate limits
+        .validate_messages()
         .build()
         .map_err(Error::other)?;
 
-    let gossipsub = gossipsub::Behaviour::new(
+    let mut gossipsub = gossipsub::Behaviour::new(
         gossipsub::MessageAuthenticity::Signed(key.clone()),
         gossipsub_config,
     )?;
+    let (score_params, score_thresholds) = gossip_peer_score();
+    gossipsub
+        .with_peer_score(score_params, score_thresholds)
+        .map_err(Error::other)?;
     let request_response_config =
         request_response::Config::default().with_request_timeout(Duration::from_secs(30));
 
@@ -294,7 +522,9 @@ fn create_behaviour(
     let mut config = KademliaConfig::new(PROTOCOL_NAME);
     config
         .set_max_packet_size(MAX_KADEMLIA_PAYLOAD_MB * 1024 * 1024)
-        .set_query_timeout(Duration::from_secs(30));
+        .set_query_timeout(Duration::from_secs(30))
+        // Inbound records are stored by us after checking the sender's limits
+        .set_record_filtering(StoreInserts::FilterBoth);
     let store_config = MemoryStoreConfig {
         max_records: DHT_MAX_RECORDS,
         max_value_bytes: MAX_KADEMLIA_RECORD_MB * 1024 * 1024,
@@ -305,16 +535,71 @@ fn create_behaviour(
     let mut kademlia = KademliaBehaviour::with_config(peer_id, store, config);
     kademlia.set_mode(Some(kad::Mode::Server));
 
+    let relay_server = transport
+        .relay_server
+        .then(|| relay::Behaviour::new(peer_id, relay::Config::default()))
+        .into();
+    let autonat = transport
+        .autonat
+        .then(|| autonat::Behaviour::new(peer_id, autonat::Config::default()))
+        .into();
+    let dcutr = transport
+        .hole_punching
+        .then(|| dcutr::Behaviour::new(peer_id))
+        .into();
+    let peer_identity = cbor::Behaviour::<PeerIdentityClaim, Option<PeerIdentityClaim>>::new(
+        [(PEER_IDENTITY_PROTOCOL, ProtocolSupport::Full)],
+        request_response::Config::default().with_request_timeout(Duration::from_secs(10)),
+    );
+
     Ok(NodeBehaviour {
         gossipsub,
         kademlia,
         connection_limits,
         identify,
         request_response,
+        relay_client,
+        relay_server,
+        autonat,
+        dcutr,
+        peer_identity,
     })
 }
 
+/// Gossipsub peer scoring. Messages we reject as oversized or malformed count against the peer
+/// that forwarded them, and the router stops exchanging gossip with peers whose score falls
+/// below the graylist threshold. IP colocation is not scored since local clusters run every node
+/// on one host.
+fn gossip_peer_score() -> (gossipsub::PeerScoreParams, gossipsub::PeerScoreThresholds) {
+    let params = gossipsub::PeerScoreParams {
+        ip_colocation_factor_weight: 0.0,
+        ..Default::default()
+    };
+    (params, gossipsub::PeerScoreThresholds::default())
+}
+
+/// Score parameters of every topic we join. Mesh delivery rates are not scored because E3 topics
+/// are quiet most of the time; four rejected messages in quick succession graylist a peer.
+fn gossip_topic_score_params() -> gossipsub::TopicScoreParams {
+    gossipsub::TopicScoreParams {
+        mesh_message_deliveries_weight: 0.0,
+        mesh_failure_penalty_weight: 0.0,
+        invalid_message_deliveries_weight: -10.0,
+        invalid_message_deliveries_decay: 0.99,
+        ..Default::default()
+    }
+}
+
+/// Subscribe to `topic` with our topic score parameters. Returns false if already subscribed.
+fn join_topic(gossipsub: &mut gossipsub::Behaviour, topic: &gossipsub::IdentTopic) -> Result<bool> {
+    gossipsub
+        .set_topic_params(topic.clone(), gossip_topic_score_params())
+        .map_err(|e| anyhow::anyhow!("Could not score gossip topic {topic}: {e}"))?;
+    Ok(gossipsub.subscribe(topic)?)
+}
+
 /// Process all swarm events
+#[allow(clippy::too_many_arguments)]
 async fn process_swarm_event(
     swarm: &mut Swarm<NodeBehaviour>,
     event_tx: &broadcast::Sender<NetEvent>,
@@ -322,6 +607,10 @@ async fn process_swarm_event(
     correlator: &mut Correlator,
     peer_failures: &mut PeerFailureTracker,
     peer_id_mismatches: &mut PeerFailureTracker,
+    local_identity: Option<&PeerIdentityClaim>,
+    identities: &mut PeerIdentityRegistry,
+    operators: &mut OperatorDiscovery,
+    bandwidth: &mut BandwidthMeter,
     event: SwarmEvent<NodeBehaviourEvent>,
 ) -> Result<()> {
     match event {
@@ -338,7 +627,18 @@ async fn process_swarm_event(
             if num_established.get() == 1 {
                 let total = swarm.connected_peers().count();
                 info!("Peer connected: {peer_id} (total: {total})");
+
+                bandwidth.on_connected(peer_id);
+                // The dialer opens the identity exchange; the listener answers with its own claim
+                identities.on_connected(peer_id, Instant::now());
+                if let Some(claim) = local_identity.filter(|_| endpoint.is_dialer()) {
+                    swarm
+                        .behaviour_mut()
+                        .peer_identity
+                        .send_request(&peer_id, claim.clone());
+                }
             }
+            let relayed = endpoint.is_relayed();
             let remote_addr = endpoint.get_remote_address().clone();
             if !(should_filter_loopback(swarm) && is_loopback_addr(&remote_addr)) {
                 swarm
@@ -354,7 +654,11 @@ async fn process_swarm_event(
                 }
             }
 
-            event_tx.send(NetEvent::ConnectionEstablished { connection_id })?;
+            event_tx.send(NetEvent::ConnectionEstablished {
+                connection_id,
+                peer_id,
+                relayed,
+            })?;
         }
 
         SwarmEvent::OutgoingConnectionError {
@@ -469,6 +773,67 @@ async fn process_swarm_event(
             }
         }
 
+        SwarmEvent::Behaviour(NodeBehaviourEvent::Kademlia(
+            kad::Event::OutboundQueryProgressed {
+                id,
+                result: QueryResult::GetRecord(result),
+                step,
+                ..
+            },
+        )) if operators.is_lookup(&id) => {
+            match result {
+                Ok(GetRecordOk::FoundRecord(found)) => {
+                    bandwidth.record_inbound(
+                        found.peer,
+                        NetProtocol::Kademlia,
+                        found.record.value.len(),
+                    );
+                    match OperatorAddressRecord::from_bytes(&found.record.value) {
+                        Ok(record) => {
+                            if let Some((peer_id, addrs)) =
+                                operators.on_record(&id, &record, Instant::now())
+                            {
+                                dial_operator(swarm, event_tx, record.operator, peer_id, addrs)?;
+                            }
+                        }
+                        Err(e) => debug!("Ignoring malformed operator address record: {e}"),
+                    }
+                }
+                Ok(GetRecordOk::FinishedWithNoAdditionalRecord { .. }) => {}
+                Err(e) => debug!("Operator address lookup failed: {e}"),
+            }
+            if step.last {
+                operators.lookup_finished(&id);
+            }
+        }
+
+        SwarmEvent::Behaviour(NodeBehaviourEvent::Kademlia(
+            kad::Event::OutboundQueryProgressed {
+                id,
+                result: QueryResult::PutRecord(result),
+                ..
+            },
+        )) if operators.is_publication(&id) => {
+            operators.publication_finished(&id, result.is_ok());
+            match result {
+                Ok(_) => {
+                    info!("Published operator address record");
+                    event_tx.send(NetEvent::OperatorRecordPublished)?;
+                }
+                Err(e) => warn!("Failed to publish operator address record, will retry: {e}"),
+            }
+        }
+
+        SwarmEvent::Behaviour(NodeBehaviourEvent::Kademlia(
+            kad::Event::OutboundQueryProgressed {
+                result: QueryResult::GetClosestPeers(result),
+                ..
+            },
+        )) => match result {
+            Ok(ok) => debug!("Random walk found {} peers", ok.peers.len()),
+            Err(e) => debug!("Random walk failed: {e}"),
+        },
+
         SwarmEvent::Behaviour(NodeBehaviourEvent::Kademlia(
             kad::Event::OutboundQueryProgressed {
                 id,
@@ -478,6 +843,11 @@ async fn process_swarm_event(
             },
         )) => match result {
             Ok(GetRecordOk::FoundRecord(record)) => {
+                bandwidth.record_inbound(
+                    record.peer,
+                    NetProtocol::Kademlia,
+                    record.record.value.len(),
+                );
                 let key = ContentHash(record.record.key.to_vec());
                 let record_bytes = record.record.value;
                 let check_key = ContentHash::from_content(&record_bytes);
@@ -541,14 +911,78 @@ async fn process_swarm_event(
             }
         }
 
+        SwarmEvent::Behaviour(NodeBehaviourEvent::Kademlia(kad::Event::InboundRequest {
+            request:
+                InboundRequest::PutRecord {
+                    source,
+                    record: Some(record),
+                    ..
+                },
+        })) => {
+            let bytes = record.value.len();
+            if let Err(violation) =
+                bandwidth.inbound(source, NetProtocol::Kademlia, bytes, Instant::now())
+            {
+                return on_limit_exceeded(
+                    swarm,
+                    event_tx,
+                    bandwidth,
+                    source,
+                    NetProtocol::Kademlia,
+                    violation,
+                );
+            }
+            let store = swarm.behaviour_mut().kademlia.store_mut();
+            let stored = store.get(&record.key).map(|stored| stored.value.clone());
+            if let Err(e) = check_record_put(record.key.as_ref(), &record.value, stored.as_deref())
+            {
+                debug!("Refusing DHT record from {source}: {e}");
+                return Ok(());
+            }
+            if let Err(e) = store.put(record) {
+                debug!("Could not store DHT record from {source}: {e}");
+            }
+        }
+
         SwarmEvent::Behaviour(NodeBehaviourEvent::Gossipsub(gossipsub::Event::Message {
             propagation_source: peer_id,
             message_id: id,
             message,
         })) => {
             trace!("Got message with id: {id} from peer: {peer_id}");
-            let gossip_data = GossipData::from_bytes(&message.data)?;
-            event_tx.send(NetEvent::GossipData(gossip_data))?;
+            // The propagation source only relays the message, so it is not rate limited here.
+            // Oversized and malformed messages are rejected, which lowers the relaying peer's
+            // gossipsub score until the router stops exchanging gossip with it.
+            let admitted = bandwidth.forwarded(
+                peer_id,
+                NetProtocol::Gossipsub,
+                message.data.len(),
+                Instant::now(),
+            );
+            let gossip_data = admitted
+                .is_ok()
+                .then(|| GossipData::from_bytes(&message.data))
+                .transpose();
+            let acceptance = match (&admitted, &gossip_data) {
+                (Ok(()), Ok(Some(_))) => gossipsub::MessageAcceptance::Accept,
+                _ => gossipsub::MessageAcceptance::Reject,
+            };
+            let _ = swarm
+                .behaviour_mut()
+                .gossipsub
+                .report_message_validation_result(&id, &peer_id, acceptance);
+            if let Err(violation) = admitted {
+                debug!("Rejected gossip message relayed by {peer_id}: {violation}");
+                event_tx.send(NetEvent::PeerLimitExceeded {
+                    peer_id,
+                    protocol: NetProtocol::Gossipsub,
+                    violation,
+                })?;
+                return Ok(());
+            }
+            if let Some(gossip_data) = gossip_data? {
+                event_tx.send(NetEvent::GossipData(gossip_data))?;
+            }
         }
 
         SwarmEvent::NewListenAddr { address, .. } => {
@@ -566,6 +1000,7 @@ async fn process_swarm_event(
 
         SwarmEvent::Behaviour(NodeBehaviourEvent::RequestResponse(
             RequestResponseEvent::Message {
+                peer,
                 message:
                     RequestResponseMessage::Request {
                         request,
@@ -576,6 +1011,35 @@ async fn process_swarm_event(
             },
         )) => {
             debug!("Incoming request received (id={})", request_id);
+            if let Err(violation) = bandwidth.inbound(
+                peer,
+                NetProtocol::RequestResponse,
+                request.len(),
+                Instant::now(),
+            ) {
+                let response = ProtocolResponse::BadRequest(violation.to_string());
+                bandwidth.record_outbound(
+                    Some(peer),
+                    NetProtocol::RequestResponse,
+                    response.payload_len(),
+                );
+                if swarm
+                    .behaviour_mut()
+                    .request_response
+                    .send_response(channel, response)
+                    .is_err()
+                {
+                    debug!("Peer {peer} closed the request before our rejection");
+                }
+                return on_limit_exceeded(
+                    swarm,
+                    event_tx,
+                    bandwidth,
+                    peer,
+                    NetProtocol::RequestResponse,
+                    violation,
+                );
+            }
             let responder = DirectResponder::new(request_id, ChannelType::Channel(channel), cmd_tx)
                 .with_request(request);
 
@@ -585,6 +1049,7 @@ async fn process_swarm_event(
 
         SwarmEvent::Behaviour(NodeBehaviourEvent::RequestResponse(
             RequestResponseEvent::Message {
+                peer,
                 message:
                     RequestResponseMessage::Response {
                         request_id,
@@ -597,6 +1062,25 @@ async fn process_swarm_event(
             debug!("Response received (id={request_id})");
             let correlation_id = correlator.expire(request_id)?;
             debug!("Correlated response: {correlation_id}");
+            if let Err(violation) = bandwidth.solicited(
+                peer,
+                NetProtocol::RequestResponse,
+                response.payload_len(),
+                Instant::now(),
+            ) {
+                event_tx.send(NetEvent::OutgoingRequestFailed(OutgoingRequestFailed {
+                    correlation_id,
+                    error: format!("Response from {peer} dropped: {violation}"),
+                }))?;
+                return on_limit_exceeded(
+                    swarm,
+                    event_tx,
+                    bandwidth,
+                    peer,
+                    NetProtocol::RequestResponse,
+                    violation,
+                );
+            }
             event_tx.send(NetEvent::OutgoingRequestSucceeded(
                 OutgoingRequestSucceeded {
                     payload: response,
@@ -660,6 +1144,85 @@ async fn process_swarm_event(
             }
         }
 
+        SwarmEvent::Behaviour(NodeBehaviourEvent::RelayClient(
+            relay::client::Event::ReservationReqAccepted {
+                relay_peer_id,
+                renewal,
+                ..
+            },
+        )) => {
+            if renewal {
+                debug!("Relay reservation renewed on {relay_peer_id}");
+            } else {
+                info!("Relay reservation accepted by {relay_peer_id}");
+            }
+            event_tx.send(NetEvent::RelayReservationAccepted { relay_peer_id })?;
+        }
+
+        SwarmEvent::Behaviour(NodeBehaviourEvent::Autonat(autonat::Event::StatusChanged {
+            old,
+            new,
+        })) => {
+            info!("NAT status changed from {old:?} to {new:?}");
+        }
+
+        SwarmEvent::Behaviour(NodeBehaviourEvent::Dcutr(dcutr::Event {
+            remote_peer_id,
+            result,
+        })) => match result {
+            Ok(_) => {
+                info!("Hole punch to {remote_peer_id} succeeded, upgraded to direct connection")
+            }
+            Err(e) => debug!("Hole punch to {remote_peer_id} failed, staying relayed: {e}"),
+        },
+
+        SwarmEvent::Behaviour(NodeBehaviourEvent::PeerIdentity(
+            RequestResponseEvent::Message {
+                peer,
+                message:
+                    RequestResponseMessage::Request {
+                        request, channel, ..
+                    },
+                ..
+            },
+        )) => {
+            let decision = identities.on_claim(peer, request.verify(&peer));
+            if matches!(decision, IdentityDecision::Verified(_)) {
+                if swarm
+                    .behaviour_mut()
+                    .peer_identity
+                    .send_response(channel, local_identity.cloned())
+                    .is_err()
+                {
+                    debug!("Peer {peer} closed the identity exchange before our response");
+                }
+            }
+            apply_identity_decision(swarm, event_tx, peer, decision)?;
+        }
+
+        SwarmEvent::Behaviour(NodeBehaviourEvent::PeerIdentity(
+            RequestResponseEvent::Message {
+                peer,
+                message: RequestResponseMessage::Response { response, .. },
+                ..
+            },
+        )) => {
+            let decision = match response {
+                Some(claim) => Some(identities.on_claim(peer, claim.verify(&peer))),
+                None => identities.on_missing_claim(peer),
+            };
+            if let Some(decision) = decision {
+                apply_identity_decision(swarm, event_tx, peer, decision)?;
+            }
+        }
+
+        SwarmEvent::Behaviour(NodeBehaviourEvent::PeerIdentity(
+            RequestResponseEvent::OutboundFailure { peer, error, .. },
+        )) => {
+            // Without a response the peer stays pending and times out if the allowlist is enforced
+            debug!("Identity exchange with {peer} failed: {error}");
+        }
+
         SwarmEvent::ConnectionClosed {
             peer_id,
             num_established,
@@ -667,6 +1230,8 @@ async fn process_swarm_event(
             ..
         } => {
             if num_established == 0 {
+                identities.on_disconnected(&peer_id);
+                bandwidth.on_disconnected(&peer_id, Instant::now());
                 let total = swarm.connected_peers().count();
                 info!("Peer disconnected: {peer_id} (total: {total}, cause: {cause:?})");
             }
@@ -694,6 +1259,10 @@ async fn process_swarm_command(
     swarm: &mut Swarm<NodeBehaviour>,
     event_tx: &broadcast::Sender<NetEvent>,
     correlator: &mut Correlator,
+    identities: &mut PeerIdentityRegistry,
+    operators: &mut OperatorDiscovery,
+    bandwidth: &mut BandwidthMeter,
+    pending_publishes: &mut PendingPublishes,
     command: NetCommand,
 ) -> Result<()> {
     match command {
@@ -702,7 +1271,44 @@ async fn process_swarm_command(
             topic,
             correlation_id,
         } => {
-            handle_gossip_publish(swarm, event_tx, data, topic, correlation_id)?;
+            handle_gossip_publish(
+                swarm,
+                event_tx,
+                bandwidth,
+                pending_publishes,
+                data,
+                topic,
+                correlation_id,
+            )?;
+            Ok(())
+        }
+        NetCommand::GossipSubscribe { topic } => {
+            let topic = gossipsub::IdentTopic::new(topic);
+            if join_topic(&mut swarm.behaviour_mut().gossipsub, &topic)? {
+                info!("Joined gossip topic {topic}");
+            }
+            Ok(())
+        }
+        NetCommand::GossipUnsubscribe { topic } => {
+            let topic = gossipsub::IdentTopic::new(topic);
+            if swarm.behaviour_mut().gossipsub.unsubscribe(&topic) {
+                info!("Left gossip topic {topic}");
+            }
+            Ok(())
+        }
+        NetCommand::ResolveOperators { operators: members } => {
+            let now = Instant::now();
+            for operator in members {
+                // Operators we are already connected to need no lookup
+                if identities.has_operator(&operator) || !operators.needs_lookup(&operator, now) {
+                    continue;
+                }
+                let query_id = swarm
+                    .behaviour_mut()
+                    .kademlia
+                    .get_record(RecordKey::new(&operator_record_key(&operator)));
+                operators.lookup_started(query_id, operator);
+            }
             Ok(())
         }
         NetCommand::Dial(env) => {
@@ -716,6 +1322,7 @@ async fn process_swarm_command(
             expires,
             value,
         } => {
+            bandwidth.record_outbound(None, NetProtocol::Kademlia, value.size());
             handle_put_record(
                 swarm,
                 event_tx,
@@ -738,14 +1345,32 @@ async fn process_swarm_command(
             handle_remove_records(swarm, keys);
             Ok(())
         }
+        NetCommand::SetOperatorAllowlist { operators } => {
+            debug!("Operator allowlist updated ({} operators)", operators.len());
+            for (peer_id, operator) in identities.set_allowlist(operators) {
+                reject_peer(
+                    swarm,
+                    event_tx,
+                    peer_id,
+                    Some(operator),
+                    format!("operator {operator} is no longer bonded"),
+                )?;
+            }
+            Ok(())
+        }
         NetCommand::OutgoingRequest(OutgoingRequest {
             correlation_id,
             payload,
             target,
         }) => {
-            if let Err(e) =
-                handle_outgoing_request(swarm, correlator, correlation_id, payload, target)
-            {
+            if let Err(e) = handle_outgoing_request(
+                swarm,
+                correlator,
+                bandwidth,
+                correlation_id,
+                payload,
+                target,
+            ) {
                 event_tx.send(NetEvent::OutgoingRequestFailed(OutgoingRequestFailed {
                     correlation_id,
                     error: e.to_string(),
@@ -754,7 +1379,7 @@ async fn process_swarm_command(
             Ok(())
         }
         NetCommand::IncomingResponse(IncomingResponse { responder }) => {
-            handle_response(swarm, responder)?;
+            handle_response(swarm, bandwidth, responder)?;
             Ok(())
         }
         NetCommand::Shutdown => {
@@ -763,23 +1388,177 @@ async fn process_swarm_command(
     }
 }
 
+/// Addresses other operators can reach us at: confirmed or configured external addresses and our
+/// relay circuits.
+fn publishable_addrs(swarm: &Swarm<NodeBehaviour>) -> Vec<Multiaddr> {
+    let mut addrs: Vec<Multiaddr> = swarm
+        .external_addresses()
+        .chain(
+            swarm
+                .listeners()
+                .filter(|addr| addr.iter().any(|p| matches!(p, Protocol::P2pCircuit))),
+        )
+        .cloned()
+        .collect();
+    addrs.sort();
+    addrs.dedup();
+    addrs
+}
+
+fn publish_operator_record(
+    swarm: &mut Swarm<NodeBehaviour>,
+    operators: &mut OperatorDiscovery,
+    signer: &PrivateKeySigner,
+) -> Result<()> {
+    let addrs = publishable_addrs(swarm);
+    let now = Instant::now();
+    if !operators.should_publish(&addrs, now) {
+        return Ok(());
+    }
+    let record = OperatorAddressRecord::sign(
+        signer,
+        swarm.local_peer_id(),
+        &addrs,
+        chrono::Utc::now().timestamp(),
+    )?;
+    let kad_record = Record {
+        key: RecordKey::new(&operator_record_key(&record.operator)),
+        value: record.to_bytes()?,
+        publisher: None,
+        expires: Some(now + OPERATOR_RECORD_TTL),
+    };
+    let query_id = swarm
+        .behaviour_mut()
+        .kademlia
+        .put_record(kad_record, Quorum::One)?;
+    debug!("Publishing operator address record with {addrs:?}");
+    operators.publication_started(query_id, addrs, now);
+    Ok(())
+}
+
+fn dial_operator(
+    swarm: &mut Swarm<NodeBehaviour>,
+    event_tx: &broadcast::Sender<NetEvent>,
+    operator: Address,
+    peer_id: PeerId,
+    addrs: Vec<Multiaddr>,
+) -> Result<()> {
+    if peer_id == *swarm.local_peer_id() {
+        return Ok(());
+    }
+    info!("Resolved operator {operator} to {peer_id} at {addrs:?}");
+    let filter = should_filter_loopback(swarm);
+    for addr in &addrs {
+        if !(filter && is_loopback_addr(addr)) {
+            swarm
+                .behaviour_mut()
+                .kademlia
+                .add_address(&peer_id, addr.clone());
+        }
+    }
+    if !swarm.is_connected(&peer_id) {
+        let opts = DialOpts::peer_id(peer_id).addresses(addrs.clone()).build();
+        if let Err(e) = swarm.dial(opts) {
+            debug!("Dial of operator {operator} skipped: {e}");
+        }
+    }
+    event_tx.send(NetEvent::OperatorResolved {
+        operator,
+        peer_id,
+        addrs,
+    })?;
+    Ok(())
+}
+
+fn apply_identity_decision(
+    swarm: &mut Swarm<NodeBehaviour>,
+    event_tx: &broadcast::Sender<NetEvent>,
+    peer_id: PeerId,
+    decision: IdentityDecision,
+) -> Result<()> {
+    match decision {
+        IdentityDecision::Verified(operator) => {
+            debug!("Peer {peer_id} verified as operator {operator}");
+            event_tx.send(NetEvent::PeerIdentityVerified { peer_id, operator })?;
+            Ok(())
+        }
+        IdentityDecision::Rejected { operator, reason } => {
+            reject_peer(swarm, event_tx, peer_id, operator, reason)
+        }
+    }
+}
+
+fn reject_peer(
+    swarm: &mut Swarm<NodeBehaviour>,
+    event_tx: &broadcast::Sender<NetEvent>,
+    peer_id: PeerId,
+    operator: Option<Address>,
+    reason: String,
+) -> Result<()> {
+    warn!("Disconnecting peer {peer_id}: {reason}");
+    let _ = swarm.disconnect_peer_id(peer_id);
+    event_tx.send(NetEvent::PeerRejected {
+        peer_id,
+        operator,
+        reason,
+    })?;
+    Ok(())
+}
+
+/// Report a dropped message and disconnect peers that keep exceeding their limits.
+fn on_limit_exceeded(
+    swarm: &mut Swarm<NodeBehaviour>,
+    event_tx: &broadcast::Sender<NetEvent>,
+    bandwidth: &mut BandwidthMeter,
+    peer_id: PeerId,
+    protocol: NetProtocol,
+    violation: LimitViolation,
+) -> Result<()> {
+    debug!("Dropped {protocol} message from {peer_id}: {violation}");
+    event_tx.send(NetEvent::PeerLimitExceeded {
+        peer_id,
+        protocol,
+        violation,
+    })?;
+    let violations = bandwidth.recent_violations(&peer_id, Instant::now());
+    if violations >= MAX_PEER_LIMIT_VIOLATIONS && swarm.is_connected(&peer_id) {
+        reject_peer(
+            swarm,
+            event_tx,
+            peer_id,
+            None,
+            format!("{violations} recent messages exceeded the bandwidth limits"),
+        )?;
+    }
+    Ok(())
+}
+
 fn handle_gossip_publish(
     swarm: &mut Swarm<NodeBehaviour>,
     event_tx: &broadcast::Sender<NetEvent>,
+    bandwidth: &mut BandwidthMeter,
+    pending_publishes: &mut PendingPublishes,
     data: GossipData,
     topic: String,
     correlation_id: CorrelationId,
 ) -> Result<()> {
     let bytes = data.to_bytes()?;
     debug!("Publishing gossip message ({} bytes)", bytes.len());
+    bandwidth.record_outbound(None, NetProtocol::Gossipsub, bytes.len());
     let gossipsub_behaviour = &mut swarm.behaviour_mut().gossipsub;
-    match gossipsub_behaviour.publish(gossipsub::IdentTopic::new(topic), bytes) {
+    match gossipsub_behaviour.publish(gossipsub::IdentTopic::new(topic.clone()), bytes.clone()) {
         Ok(message_id) => {
             event_tx.send(NetEvent::GossipPublished {
                 correlation_id,
                 message_id,
             })?;
         }
+        // No peers on the topic yet, most likely because we only just joined it
+        Err(PublishError::InsufficientPeers)
+            if pending_publishes.push(topic.clone(), bytes, correlation_id, Instant::now()) =>
+        {
+            debug!("No peers on topic {topic} yet; queued publish for retry");
+        }
         Err(e) => {
             error!(error=?e, "Could not GossipPublish.");
             event_tx.send(NetEvent::GossipPublishError {
@@ -791,6 +1570,52 @@ fn handle_gossip_publish(
     Ok(())
 }
 
+fn retry_pending_publishes(
+    swarm: &mut Swarm<NodeBehaviour>,
+    event_tx: &broadcast::Sender<NetEvent>,
+    pending_publishes: &mut PendingPublishes,
+) -> Result<()> {
+    let (retry, expired) = pending_publishes.take(Instant::now());
+    for publish in expired {
+        warn!(
+            "Giving up on publish to {}: no peers joined the topic in time",
+            publish.topic
+        );
+        event_tx.send(NetEvent::GossipPublishError {
+            correlation_id: publish.correlation_id,
+            error: Arc::new(PublishError::InsufficientPeers),
+        })?;
+    }
+    for publish in retry {
+        let PendingPublish {
+            topic,
+            bytes,
+            correlation_id,
+            ..
+        } = &publish;
+        let gossipsub_behaviour = &mut swarm.behaviour_mut().gossipsub;
+        match gossipsub_behaviour.publish(gossipsub::IdentTopic::new(topic.clone()), bytes.clone())
+        {
+            Ok(message_id) => {
+                debug!("Published queued gossip message on {topic}");
+                event_tx.send(NetEvent::GossipPublished {
+                    correlation_id: *correlation_id,
+                    message_id,
+                })?;
+            }
+            Err(PublishError::InsufficientPeers) => pending_publishes.requeue(publish),
+            Err(e) => {
+                error!(error=?e, "Could not GossipPublish queued message.");
+                event_tx.send(NetEvent::GossipPublishError {
+                    correlation_id: *correlation_id,
+                    error: Arc::new(e),
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
 fn handle_dial(
     swarm: &mut Swarm<NodeBehaviour>,
     event_tx: &broadcast::Sender<NetEvent>,
@@ -956,6 +1781,7 @@ async fn handle_shutdown(swarm: &mut Swarm<NodeBehaviour>) -> Result<()> {
 fn handle_outgoing_request(
     swarm: &mut Swarm<NodeBehaviour>,
     correlator: &mut Correlator,
+    bandwidth: &mut BandwidthMeter,
     correlation_id: CorrelationId,
     payload: Vec<u8>,
     target: PeerTarget,
@@ -970,6 +1796,7 @@ fn handle_outgoing_request(
     };
 
     debug!("Outgoing request payload size: {:?}", payload.len());
+    bandwidth.record_outbound(Some(peer), NetProtocol::RequestResponse, payload.len());
 
     // Request events
     let query_id = swarm
@@ -984,12 +1811,18 @@ fn handle_outgoing_request(
     Ok(())
 }
 
-fn handle_response(swarm: &mut Swarm<NodeBehaviour>, responder: DirectResponder) -> Result<()> {
+fn handle_response(
+    swarm: &mut Swarm<NodeBehaviour>,
+    bandwidth: &mut BandwidthMeter,
+    responder: DirectResponder,
+) -> Result<()> {
     debug!("Sending response to {}", responder.id());
     let (channel, response) = responder.to_response()?;
     let ChannelType::Channel(channel) = channel else {
         bail!("responder did not return the correct type of channel");
     };
+    // The channel does not expose the requesting peer, so only the protocol total is updated
+    bandwidth.record_outbound(None, NetProtocol::RequestResponse, response.payload_len());
     swarm
         .behaviour_mut()
         .request_response
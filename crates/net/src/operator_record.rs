@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Operator address records published in the Kademlia DHT.
+//!
+//! An operator announces where its node can be reached by storing an [`OperatorAddressRecord`]
+//! under [`operator_record_key`]. The record is signed by the operator wallet, so nodes that
+//! resolve the addresses of registry members can reject records planted by anyone else.
+//! Nodes holding records for other peers run [`check_record_put`] before storing one, so a
+//! forged or stale record cannot displace the operator's own.
+
+use alloy::primitives::{Address, Signature};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::SignerSync;
+use anyhow::{bail, Context, Result};
+use libp2p::{Multiaddr, PeerId};
+use serde::{Deserialize, Serialize};
+
+const RECORD_KEY_PREFIX: &[u8] = b"/interfold/operator/";
+const RECORD_DOMAIN: &[u8] = b"interfold operator address record:";
+
+/// DHT key under which `operator` publishes its addresses.
+pub fn operator_record_key(operator: &Address) -> Vec<u8> {
+    [RECORD_KEY_PREFIX, operator.as_slice()].concat()
+}
+
+/// Check a record another peer asks us to store under `key`. Records outside the operator
+/// namespace are not checked. An operator record must carry a valid signature of the operator
+/// its key belongs to and be newer than the record `stored` under that key; storing the same
+/// record again is allowed so holders can replicate it.
+pub fn check_record_put(key: &[u8], value: &[u8], stored: Option<&[u8]>) -> Result<()> {
+    let Some(operator) = key.strip_prefix(RECORD_KEY_PREFIX) else {
+        return Ok(());
+    };
+    let operator = Address::try_from(operator).context("malformed operator record key")?;
+    let record = OperatorAddressRecord::from_bytes(value)?;
+    if record.operator != operator {
+        bail!(
+            "address record of {} offered under the key of {operator}",
+            record.operator
+        );
+    }
+    record.verify()?;
+    let Some(stored) = stored else {
+        return Ok(());
+    };
+    if stored == value {
+        return Ok(());
+    }
+    if let Ok(stored) = OperatorAddressRecord::from_bytes(stored) {
+        if stored.issued_at >= record.issued_at {
+            bail!(
+                "address record of {operator} issued at {} is not newer than the stored one issued at {}",
+                record.issued_at,
+                stored.issued_at
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Signed announcement of the PeerId and multiaddrs an operator's node is reachable at.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperatorAddressRecord {
+    pub operator: Address,
+    pub peer_id: Vec<u8>,
+    pub addrs: Vec<String>,
+    /// Unix timestamp (seconds); the newest valid record wins
+    pub issued_at: i64,
+    pub signature: Vec<u8>,
+}
+
+impl OperatorAddressRecord {
+    pub fn sign(
+        signer: &PrivateKeySigner,
+        peer_id: &PeerId,
+        addrs: &[Multiaddr],
+        issued_at: i64,
+    ) -> Result<Self> {
+        let mut record = Self {
+            operator: signer.address(),
+            peer_id: peer_id.to_bytes(),
+            addrs: addrs.iter().map(|addr| addr.to_string()).collect(),
+            issued_at,
+            signature: vec![],
+        };
+        let signature = signer
+            .sign_message_sync(&record.signing_message())
+            .context("failed to sign operator address record")?;
+        record.signature = signature.as_bytes().to_vec();
+        Ok(record)
+    }
+
+    /// Checks the record was signed by [`Self::operator`] and returns its PeerId and addresses.
+    pub fn verify(&self) -> Result<(PeerId, Vec<Multiaddr>)> {
+        let signature =
+            Signature::try_from(self.signature.as_slice()).context("malformed signature")?;
+        let recovered = signature
+            .recover_address_from_msg(self.signing_message())
+            .context("invalid signature")?;
+        if recovered != self.operator {
+            bail!(
+                "address record is signed by {recovered}, not the claimed {}",
+                self.operator
+            );
+        }
+        let peer_id = PeerId::from_bytes(&self.peer_id).context("invalid peer id")?;
+        let addrs = self
+            .addrs
+            .iter()
+            .map(|addr| {
+                addr.parse()
+                    .with_context(|| format!("invalid address '{addr}'"))
+            })
+            .collect::<Result<_>>()?;
+        Ok((peer_id, addrs))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        bincode::serialize(self).context("Could not serialize OperatorAddressRecord")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        bincode::deserialize(bytes).context("Could not deserialize OperatorAddressRecord")
+    }
+
+    fn signing_message(&self) -> Vec<u8> {
+        let mut message = RECORD_DOMAIN.to_vec();
+        message.extend_from_slice(self.operator.as_slice());
+        message.extend_from_slice(&(self.peer_id.len() as u32).to_be_bytes());
+        message.extend_from_slice(&self.peer_id);
+        message.extend_from_slice(&self.issued_at.to_be_bytes());
+        for addr in &self.addrs {
+            message.extend_from_slice(&(addr.len() as u32).to_be_bytes());
+            message.extend_from_slice(addr.as_bytes());
+        }
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addrs() -> Vec<Multiaddr> {
+        vec!["/ip4/203.0.113.10/udp/9091/quic-v1".parse().unwrap()]
+    }
+
+    #[test]
+    fn signed_record_round_trips_and_verifies() {
+        let signer = PrivateKeySigner::random();
+        let peer_id = PeerId::random();
+        let record = OperatorAddressRecord::sign(&signer, &peer_id, &addrs(), 42).unwrap();
+
+        let decoded = OperatorAddressRecord::from_bytes(&record.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded, record);
+        assert_eq!(decoded.verify().unwrap(), (peer_id, addrs()));
+    }
+
+    #[test]
+    fn tampered_record_is_rejected() {
+        let signer = PrivateKeySigner::random();
+        let record = OperatorAddressRecord::sign(&signer, &PeerId::random(), &addrs(), 42).unwrap();
+
+        let mut redirected = record.clone();
+        redirected.addrs = vec!["/ip4/198.51.100.1/udp/9091/quic-v1".to_string()];
+        assert!(redirected.verify().is_err());
+
+        let mut hijacked = record;
+        hijacked.peer_id = PeerId::random().to_bytes();
+        assert!(hijacked.verify().is_err());
+    }
+
+    #[test]
+    fn record_puts_are_checked_against_the_key_and_stored_record() {
+        let signer = PrivateKeySigner::random();
+        let key = operator_record_key(&signer.address());
+        let peer_id = PeerId::random();
+        let older = OperatorAddressRecord::sign(&signer, &peer_id, &addrs(), 1)
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        let newer = OperatorAddressRecord::sign(&signer, &peer_id, &addrs(), 2)
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+
+        assert!(check_record_put(&key, &older, None).is_ok());
+        assert!(check_record_put(&key, &newer, Some(&older)).is_ok());
+        assert!(check_record_put(&key, &newer, Some(&newer)).is_ok());
+        assert!(check_record_put(&key, &older, Some(&newer)).is_err());
+
+        let other = PrivateKeySigner::random();
+        let foreign = OperatorAddressRecord::sign(&other, &peer_id, &addrs(), 3)
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        assert!(check_record_put(&key, &foreign, Some(&newer)).is_err());
+
+        let mut forged = OperatorAddressRecord::sign(&signer, &peer_id, &addrs(), 4).unwrap();
+        forged.addrs = vec!["/ip4/198.51.100.1/udp/9091/quic-v1".to_string()];
+        assert!(check_record_put(&key, &forged.to_bytes().unwrap(), None).is_err());
+        assert!(check_record_put(&key, b"garbage", None).is_err());
+
+        assert!(check_record_put(b"some document", b"anything", None).is_ok());
+    }
+
+    #[test]
+    fn record_keys_are_per_operator() {
+        let a = PrivateKeySigner::random().address();
+        let b = PrivateKeySigner::random().address();
+        assert_ne!(operator_record_key(&a), operator_record_key(&b));
+        assert!(operator_record_key(&a).starts_with(RECORD_KEY_PREFIX));
+    }
+}
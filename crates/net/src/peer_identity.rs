@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Binding between a libp2p `PeerId` and the operator address registered on chain.
+//!
+//! Every node's operator wallet signs (EIP-191) its own `PeerId`. The resulting
+//! [`PeerIdentityClaim`] is exchanged over a dedicated request-response protocol right after a
+//! connection is established. The remote `PeerId` is taken from the authenticated connection,
+//! so a claim cannot be replayed by another peer.
+
+use alloy::primitives::{Address, Signature};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::SignerSync;
+use anyhow::{bail, Context, Result};
+use libp2p::{PeerId, StreamProtocol};
+use serde::{Deserialize, Serialize};
+
+pub const PEER_IDENTITY_PROTOCOL: StreamProtocol = StreamProtocol::new("/interfold/identity/0.0.1");
+
+const BINDING_DOMAIN: &[u8] = b"interfold peer identity binding:";
+
+/// Message the operator signs to bind `peer_id` to its address.
+pub fn binding_message(peer_id: &PeerId) -> Vec<u8> {
+    [BINDING_DOMAIN, &peer_id.to_bytes()].concat()
+}
+
+/// Operator signature over a node's `PeerId`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerIdentityClaim {
+    pub operator: Address,
+    pub signature: Vec<u8>,
+}
+
+impl PeerIdentityClaim {
+    pub fn sign(signer: &PrivateKeySigner, peer_id: &PeerId) -> Result<Self> {
+        let signature = signer
+            .sign_message_sync(&binding_message(peer_id))
+            .context("failed to sign peer identity binding")?;
+        Ok(Self {
+            operator: signer.address(),
+            signature: signature.as_bytes().to_vec(),
+        })
+    }
+
+    /// Checks the claim was signed by [`Self::operator`] for `peer_id` and returns the operator.
+    pub fn verify(&self, peer_id: &PeerId) -> Result<Address> {
+        let signature =
+            Signature::try_from(self.signature.as_slice()).context("malformed signature")?;
+        let recovered = signature
+            .recover_address_from_msg(binding_message(peer_id))
+            .context("invalid signature")?;
+        if recovered != self.operator {
+            bail!(
+                "peer {peer_id} binding is signed by {recovered}, not the claimed {}",
+                self.operator
+            );
+        }
+        Ok(recovered)
+    }
+}
+
+/// Verified binding as persisted for `interfold net peers`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerIdentityRecord {
+    pub peer_id: String,
+    pub operator: Address,
+    /// Unix timestamp (seconds) of the last successful verification.
+    pub verified_at: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claim_verifies_for_the_signed_peer_only() {
+        let signer = PrivateKeySigner::random();
+        let peer = PeerId::random();
+        let claim = PeerIdentityClaim::sign(&signer, &peer).unwrap();
+
+        assert_eq!(claim.verify(&peer).unwrap(), signer.address());
+        assert!(
+            claim.verify(&PeerId::random()).is_err(),
+            "claim must not be replayable by another peer"
+        );
+    }
+
+    #[test]
+    fn claim_for_a_different_operator_is_rejected() {
+        let signer = PrivateKeySigner::random();
+        let peer = PeerId::random();
+        let mut claim = PeerIdentityClaim::sign(&signer, &peer).unwrap();
+        claim.operator = PrivateKeySigner::random().address();
+
+        assert!(claim.verify(&peer).is_err());
+    }
+}
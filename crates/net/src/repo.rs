@@ -4,15 +4,32 @@
 // without even the implied warranty of MERCHANTABILITY
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
+use std::collections::BTreeMap;
+
 use e3_data::{Repositories, Repository};
 use e3_events::StoreKeys;
 
+use crate::bandwidth::BandwidthReport;
+use crate::peer_identity::PeerIdentityRecord;
+
 pub trait NetRepositoryFactory {
     fn libp2p_keypair(&self) -> Repository<Vec<u8>>;
+    /// Verified PeerId -> operator bindings keyed by PeerId
+    fn peer_identities(&self) -> Repository<BTreeMap<String, PeerIdentityRecord>>;
+    /// Latest traffic snapshot of the net interface
+    fn bandwidth(&self) -> Repository<BandwidthReport>;
 }
 
 impl NetRepositoryFactory for Repositories {
     fn libp2p_keypair(&self) -> Repository<Vec<u8>> {
         Repository::new(self.store.scope(StoreKeys::libp2p_keypair()))
     }
+
+    fn peer_identities(&self) -> Repository<BTreeMap<String, PeerIdentityRecord>> {
+        Repository::new(self.store.scope(StoreKeys::libp2p_peer_identities()))
+    }
+
+    fn bandwidth(&self) -> Repository<BandwidthReport> {
+        Repository::new(self.store.scope(StoreKeys::libp2p_bandwidth()))
+    }
 }
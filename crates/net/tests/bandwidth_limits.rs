@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Integration test for the per-protocol message size caps.
+//!
+//! B caps request-response messages at 1 KiB. An oversized request of A must be rejected with a
+//! `BadRequest` and recorded as a violation of A, while a request within the cap still reaches B.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use e3_config::{NetLimitsConfig, ProtocolLimitConfig};
+use e3_events::CorrelationId;
+use e3_net::bandwidth::{LimitViolation, NetProtocol};
+use e3_net::events::{NetCommand, NetEvent, OutgoingRequest, PeerTarget, ProtocolResponse};
+use e3_net::{Libp2pKeypair, Libp2pNetInterface, NetInterface};
+use tokio::time::{sleep, timeout};
+
+/// Grab a free UDP port by binding to port 0 and dropping the socket.
+fn free_udp_port() -> u16 {
+    let socket = std::net::UdpSocket::bind("127.0.0.1:0").expect("bind udp");
+    socket.local_addr().expect("local addr").port()
+}
+
+#[tokio::test]
+async fn oversized_requests_are_rejected_and_recorded() -> Result<()> {
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .try_init();
+
+    let key_b = Libp2pKeypair::generate();
+    let peer_b = key_b.peer_id();
+    let port_b = free_udp_port();
+    let mut node_b = Libp2pNetInterface::new(key_b, vec![], Some(port_b), "test")?.with_limits(
+        NetLimitsConfig {
+            request_response: ProtocolLimitConfig {
+                max_message_bytes: Some(1024),
+                ..ProtocolLimitConfig::default()
+            },
+            ..NetLimitsConfig::default()
+        },
+    );
+    let handle_b = node_b.handle();
+    let mut rx_b = handle_b.rx();
+    tokio::spawn(async move { node_b.start().await });
+
+    // Give B a moment to bind its QUIC listener.
+    sleep(Duration::from_millis(500)).await;
+
+    let key_a = Libp2pKeypair::generate();
+    let peer_a = key_a.peer_id();
+    let addr_b = format!("/ip4/127.0.0.1/udp/{port_b}/quic-v1/p2p/{peer_b}");
+    let mut node_a = Libp2pNetInterface::new(key_a, vec![addr_b], None, "test")?;
+    let handle_a = node_a.handle();
+    let mut rx_a = handle_a.rx();
+    tokio::spawn(async move { node_a.start().await });
+
+    timeout(Duration::from_secs(30), async {
+        loop {
+            if let NetEvent::ConnectionEstablished { peer_id, .. } = rx_a.recv().await? {
+                if peer_id == peer_b {
+                    return anyhow::Ok(());
+                }
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for A to connect to B")?;
+
+    let oversized = CorrelationId::new();
+    handle_a
+        .tx()
+        .send(NetCommand::OutgoingRequest(OutgoingRequest {
+            correlation_id: oversized,
+            payload: vec![0; 4096],
+            target: PeerTarget::Specific(peer_b),
+        }))
+        .await?;
+
+    let response = timeout(Duration::from_secs(30), async {
+        loop {
+            if let NetEvent::OutgoingRequestSucceeded(succeeded) = rx_a.recv().await? {
+                if succeeded.correlation_id == oversized {
+                    return anyhow::Ok(succeeded.payload);
+                }
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for B's rejection")?;
+    assert!(
+        matches!(response, ProtocolResponse::BadRequest(_)),
+        "{response:?}"
+    );
+
+    let (peer_id, protocol, violation) = timeout(Duration::from_secs(5), async {
+        loop {
+            if let NetEvent::PeerLimitExceeded {
+                peer_id,
+                protocol,
+                violation,
+            } = rx_b.recv().await?
+            {
+                return anyhow::Ok((peer_id, protocol, violation));
+            }
+        }
+    })
+    .await
+    .expect("B did not record the violation")?;
+    assert_eq!(peer_id, peer_a);
+    assert_eq!(protocol, NetProtocol::RequestResponse);
+    assert_eq!(
+        violation,
+        LimitViolation::Oversized {
+            bytes: 4096,
+            max: 1024
+        }
+    );
+
+    // Requests within the cap are still delivered.
+    handle_a
+        .tx()
+        .send(NetCommand::OutgoingRequest(OutgoingRequest {
+            correlation_id: CorrelationId::new(),
+            payload: vec![0; 512],
+            target: PeerTarget::Specific(peer_b),
+        }))
+        .await?;
+    timeout(Duration::from_secs(30), async {
+        loop {
+            if let NetEvent::IncomingRequest(_) = rx_b.recv().await? {
+                return anyhow::Ok(());
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for B to receive the small request")?;
+
+    handle_a.tx().send(NetCommand::Shutdown).await?;
+    handle_b.tx().send(NetCommand::Shutdown).await?;
+    Ok(())
+}
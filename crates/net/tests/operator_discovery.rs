@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Integration test for operator address discovery through the DHT.
+//!
+//! Operator A publishes a signed address record via the seed node S and goes offline. A node B
+//! that only knows S must still resolve A's PeerId and address from the record S stores.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use alloy::signers::local::PrivateKeySigner;
+use anyhow::Result;
+use e3_config::{NetDiscoveryConfig, NetTransportConfig};
+use e3_net::events::{NetCommand, NetEvent};
+use e3_net::{Libp2pKeypair, Libp2pNetInterface, NetInterface};
+use tokio::time::{sleep, timeout};
+
+/// Grab a free UDP port by binding to port 0 and dropping the socket.
+fn free_udp_port() -> u16 {
+    let socket = std::net::UdpSocket::bind("127.0.0.1:0").expect("bind udp");
+    socket.local_addr().expect("local addr").port()
+}
+
+#[tokio::test]
+async fn operator_addresses_resolve_from_the_dht() -> Result<()> {
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .try_init();
+
+    // Seed S: the only statically configured peer.
+    let key_s = Libp2pKeypair::generate();
+    let peer_s = key_s.peer_id();
+    let port_s = free_udp_port();
+    let mut seed = Libp2pNetInterface::new(key_s, vec![], Some(port_s), "test")?;
+    let handle_s = seed.handle();
+    tokio::spawn(async move { seed.start().await });
+    sleep(Duration::from_millis(500)).await;
+    let seed_addr = format!("/ip4/127.0.0.1/udp/{port_s}/quic-v1/p2p/{peer_s}");
+
+    // Operator A publishes its (configured) external address.
+    let operator_a = PrivateKeySigner::random();
+    let key_a = Libp2pKeypair::generate();
+    let peer_a = key_a.peer_id();
+    let port_a = free_udp_port();
+    let addr_a = format!("/ip4/127.0.0.1/udp/{port_a}/quic-v1");
+    let mut node_a = Libp2pNetInterface::with_transport(
+        key_a,
+        vec![seed_addr.clone()],
+        Some(port_a),
+        NetTransportConfig {
+            external_addrs: vec![addr_a.clone()],
+            autonat: false,
+            hole_punching: false,
+            ..NetTransportConfig::default()
+        },
+        "test",
+    )?
+    .with_discovery(
+        NetDiscoveryConfig {
+            publish_addrs: true,
+            ..NetDiscoveryConfig::default()
+        },
+        Some(operator_a.clone()),
+    );
+    let handle_a = node_a.handle();
+    let mut rx_a = handle_a.rx();
+    tokio::spawn(async move { node_a.start().await });
+
+    timeout(Duration::from_secs(60), async {
+        loop {
+            if let NetEvent::OperatorRecordPublished = rx_a.recv().await? {
+                return anyhow::Ok(());
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for A to publish its address record")?;
+    handle_a.tx().send(NetCommand::Shutdown).await?;
+
+    // Node B only knows the seed and resolves A by its operator address.
+    let mut node_b =
+        Libp2pNetInterface::new(Libp2pKeypair::generate(), vec![seed_addr], None, "test")?;
+    let handle_b = node_b.handle();
+    let mut rx_b = handle_b.rx();
+    tokio::spawn(async move { node_b.start().await });
+
+    let (peer_id, addrs) = timeout(Duration::from_secs(60), async {
+        loop {
+            handle_b
+                .tx()
+                .send(NetCommand::ResolveOperators {
+                    operators: HashSet::from([operator_a.address()]),
+                })
+                .await?;
+            let deadline = tokio::time::Instant::now() + Duration::from_secs(2);
+            while let Ok(event) = tokio::time::timeout_at(deadline, rx_b.recv()).await {
+                if let NetEvent::OperatorResolved {
+                    operator,
+                    peer_id,
+                    addrs,
+                } = event?
+                {
+                    assert_eq!(operator, operator_a.address());
+                    return anyhow::Ok((peer_id, addrs));
+                }
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for B to resolve A")?;
+    assert_eq!(peer_id, peer_a);
+    assert_eq!(addrs, vec![addr_a.parse()?]);
+
+    handle_b.tx().send(NetCommand::Shutdown).await?;
+    handle_s.tx().send(NetCommand::Shutdown).await?;
+    Ok(())
+}
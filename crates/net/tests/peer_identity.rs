@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Integration test for the operator-signed PeerId handshake.
+//!
+//! A dials B and both learn each other's operator address. B then restricts its peers to an
+//! allowlist that does not contain A's operator and must drop A.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use alloy::primitives::Address;
+use alloy::signers::local::PrivateKeySigner;
+use anyhow::Result;
+use e3_net::events::{NetCommand, NetEvent};
+use e3_net::peer_identity::PeerIdentityClaim;
+use e3_net::{Libp2pKeypair, Libp2pNetInterface, NetInterface};
+use libp2p::PeerId;
+use tokio::sync::broadcast;
+use tokio::time::{sleep, timeout};
+
+/// Grab a free UDP port by binding to port 0 and dropping the socket.
+fn free_udp_port() -> u16 {
+    let socket = std::net::UdpSocket::bind("127.0.0.1:0").expect("bind udp");
+    socket.local_addr().expect("local addr").port()
+}
+
+async fn wait_for_verified(
+    rx: &mut broadcast::Receiver<NetEvent>,
+    expected_peer: PeerId,
+) -> Result<Address> {
+    timeout(Duration::from_secs(30), async {
+        loop {
+            if let NetEvent::PeerIdentityVerified { peer_id, operator } = rx.recv().await? {
+                if peer_id == expected_peer {
+                    return anyhow::Ok(operator);
+                }
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for peer identity verification")
+}
+
+#[tokio::test]
+async fn peers_bind_operators_and_unbonded_operators_are_dropped() -> Result<()> {
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .try_init();
+
+    let operator_a = PrivateKeySigner::random();
+    let operator_b = PrivateKeySigner::random();
+
+    let key_b = Libp2pKeypair::generate();
+    let peer_b = key_b.peer_id();
+    let port_b = free_udp_port();
+    let mut node_b = Libp2pNetInterface::new(key_b, vec![], Some(port_b), "test")?
+        .with_identity(PeerIdentityClaim::sign(&operator_b, &peer_b)?);
+    let handle_b = node_b.handle();
+    let mut rx_b = handle_b.rx();
+    tokio::spawn(async move { node_b.start().await });
+
+    // Give B a moment to bind its QUIC listener.
+    sleep(Duration::from_millis(500)).await;
+
+    let key_a = Libp2pKeypair::generate();
+    let peer_a = key_a.peer_id();
+    let addr_b = format!("/ip4/127.0.0.1/udp/{port_b}/quic-v1/p2p/{peer_b}");
+    let mut node_a = Libp2pNetInterface::new(key_a, vec![addr_b], None, "test")?
+        .with_identity(PeerIdentityClaim::sign(&operator_a, &peer_a)?);
+    let handle_a = node_a.handle();
+    let mut rx_a = handle_a.rx();
+    tokio::spawn(async move { node_a.start().await });
+
+    // Without an allowlist both sides verify and admit each other.
+    assert_eq!(
+        wait_for_verified(&mut rx_b, peer_a).await?,
+        operator_a.address()
+    );
+    assert_eq!(
+        wait_for_verified(&mut rx_a, peer_b).await?,
+        operator_b.address()
+    );
+
+    // B only admits an unrelated operator from now on.
+    handle_b
+        .tx()
+        .send(NetCommand::SetOperatorAllowlist {
+            operators: HashSet::from([PrivateKeySigner::random().address()]),
+        })
+        .await?;
+
+    let (operator, reason) = timeout(Duration::from_secs(30), async {
+        loop {
+            if let NetEvent::PeerRejected {
+                peer_id,
+                operator,
+                reason,
+            } = rx_b.recv().await?
+            {
+                if peer_id == peer_a {
+                    return anyhow::Ok((operator, reason));
+                }
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for B to reject A")?;
+    assert_eq!(operator, Some(operator_a.address()));
+    assert!(reason.contains("no longer bonded"), "{reason}");
+
+    handle_a.tx().send(NetCommand::Shutdown).await?;
+    handle_b.tx().send(NetCommand::Shutdown).await?;
+    Ok(())
+}
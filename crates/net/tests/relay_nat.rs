@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Integration test for NAT traversal via circuit relay v2.
+//!
+//! Two "NATed" nodes never learn each other's direct addresses: B only reserves a
+//! slot on a public relay node R (over TCP), and A dials B through
+//! `<R>/p2p-circuit/p2p/<B>`. The nodes must connect through the relay and
+//! complete a request/response round trip (the sync protocol) over the circuit.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use e3_config::NetTransportConfig;
+use e3_net::events::{NetCommand, NetEvent, OutgoingRequest, ProtocolResponse};
+use e3_net::{Libp2pKeypair, Libp2pNetInterface, NetInterface};
+use tokio::time::{sleep, timeout};
+
+/// Grab a free TCP port by binding to port 0 and dropping the listener.
+fn free_tcp_port() -> u16 {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind tcp");
+    listener.local_addr().expect("local addr").port()
+}
+
+/// NAT traversal for the "NATed" nodes: no AutoNAT and no hole punching. They still listen on a
+/// random local QUIC port but never learn each other's direct addresses, so the relayed
+/// connection is the only path between them.
+fn nated(relays: Vec<String>) -> NetTransportConfig {
+    NetTransportConfig {
+        relays,
+        autonat: false,
+        hole_punching: false,
+        ..NetTransportConfig::default()
+    }
+}
+
+#[tokio::test]
+async fn nated_peers_sync_through_relay() -> Result<()> {
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .try_init();
+
+    // Relay R: publicly reachable over TCP.
+    let relay_key = Libp2pKeypair::generate();
+    let relay_id = relay_key.peer_id();
+    let relay_port = free_tcp_port();
+    let mut relay = Libp2pNetInterface::with_transport(
+        relay_key,
+        vec![],
+        None,
+        NetTransportConfig {
+            tcp_port: Some(relay_port),
+            relay_server: true,
+            external_addrs: vec![format!("/ip4/127.0.0.1/tcp/{relay_port}")],
+            autonat: false,
+            hole_punching: false,
+            ..NetTransportConfig::default()
+        },
+        "test",
+    )?;
+    let handle_relay = relay.handle();
+    tokio::spawn(async move { relay.start().await });
+
+    // Give R a moment to bind its TCP listener.
+    sleep(Duration::from_millis(500)).await;
+    let relay_addr = format!("/ip4/127.0.0.1/tcp/{relay_port}/p2p/{relay_id}");
+
+    // Node B: only reachable through its reservation on R.
+    let key_b = Libp2pKeypair::generate();
+    let peer_b = key_b.peer_id();
+    let mut node_b = Libp2pNetInterface::with_transport(
+        key_b,
+        vec![],
+        None,
+        nated(vec![relay_addr.clone()]),
+        "test",
+    )?;
+    let handle_b = node_b.handle();
+    let mut rx_b = handle_b.rx();
+    tokio::spawn(async move { node_b.start().await });
+
+    timeout(Duration::from_secs(30), async {
+        loop {
+            if let NetEvent::RelayReservationAccepted { relay_peer_id } = rx_b.recv().await? {
+                assert_eq!(relay_peer_id, relay_id);
+                break;
+            }
+        }
+        anyhow::Ok(())
+    })
+    .await
+    .expect("timed out waiting for B's relay reservation")?;
+
+    // B answers sync requests.
+    tokio::spawn(async move {
+        loop {
+            if let Ok(NetEvent::IncomingRequest(request)) = rx_b.recv().await {
+                assert_eq!(request.responder.request(), b"sync?".to_vec());
+                request.responder.ok(b"synced".to_vec())?;
+                break;
+            }
+        }
+        anyhow::Ok(())
+    });
+
+    // Node A: knows B only by its circuit address.
+    let circuit_addr = format!("{relay_addr}/p2p-circuit/p2p/{peer_b}");
+    let mut node_a = Libp2pNetInterface::with_transport(
+        Libp2pKeypair::generate(),
+        vec![circuit_addr],
+        None,
+        nated(vec![]),
+        "test",
+    )?;
+    let handle_a = node_a.handle();
+    let mut rx_a = handle_a.rx();
+    tokio::spawn(async move { node_a.start().await });
+
+    let relayed = timeout(Duration::from_secs(30), async {
+        loop {
+            if let NetEvent::ConnectionEstablished {
+                peer_id, relayed, ..
+            } = rx_a.recv().await?
+            {
+                if peer_id == peer_b {
+                    return anyhow::Ok(relayed);
+                }
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for A to connect to B")?;
+    assert!(relayed, "A should reach B through the relay circuit");
+
+    let request = OutgoingRequest::new(peer_b, b"sync?".to_vec())?;
+    let correlation_id = request.correlation_id;
+    handle_a
+        .tx()
+        .send(NetCommand::OutgoingRequest(request))
+        .await?;
+
+    let response = timeout(Duration::from_secs(30), async {
+        loop {
+            match rx_a.recv().await? {
+                NetEvent::OutgoingRequestSucceeded(succeeded)
+                    if succeeded.correlation_id == correlation_id =>
+                {
+                    return anyhow::Ok(succeeded.payload);
+                }
+                NetEvent::OutgoingRequestFailed(failed)
+                    if failed.correlation_id == correlation_id =>
+                {
+                    anyhow::bail!("sync request over relay failed: {}", failed.error);
+                }
+                _ => {}
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for B's sync response")?;
+    assert!(matches!(response, ProtocolResponse::Ok(bytes) if bytes == b"synced"));
+
+    handle_a.tx().send(NetCommand::Shutdown).await?;
+    handle_b.tx().send(NetCommand::Shutdown).await?;
+    handle_relay.tx().send(NetCommand::Shutdown).await?;
+    Ok(())
+}
@@ -8,7 +8,7 @@ mod types;
 
 use actix_web::{middleware::Logger, web, App, HttpResponse, HttpServer, Result as ActixResult};
 use anyhow::Result;
-use e3_compute_provider::FHEInputs;
+use e3_compute_provider::{FHEInputs, InputProof, InputProofVerifier, InputValidator};
 use serde::Serialize;
 use std::{future::Future, pin::Pin, sync::Arc};
 use types::{ComputeRequest, WebhookPayload};
@@ -28,6 +28,7 @@ pub struct E3ProgramServerBuilder {
     port: Option<u16>,
     host: Option<String>,
     localhost_rewrite: Option<String>,
+    input_verifier: Option<Arc<dyn InputProofVerifier>>,
 }
 
 impl E3ProgramServerBuilder {
@@ -42,6 +43,7 @@ impl E3ProgramServerBuilder {
             port: None,
             host: None,
             localhost_rewrite: None,
+            input_verifier: None,
         }
     }
 
@@ -63,6 +65,13 @@ impl E3ProgramServerBuilder {
         self
     }
 
+    /// Only compute over ciphertexts whose `input_proofs` verify with `verifier` against the
+    /// request's `pk_commitment`; other inputs are dropped before the runner sees them
+    pub fn with_input_verifier(mut self, verifier: Arc<dyn InputProofVerifier>) -> Self {
+        self.input_verifier = Some(verifier);
+        self
+    }
+
     /// Build the E3ProgramServer
     pub fn build(self) -> E3ProgramServer {
         E3ProgramServer {
@@ -70,6 +79,7 @@ impl E3ProgramServerBuilder {
             port: self.port.unwrap_or(13151),
             host: self.host.unwrap_or_else(|| "0.0.0.0".to_string()),
             localhost_rewrite: self.localhost_rewrite,
+            input_verifier: self.input_verifier,
         }
     }
 }
@@ -80,6 +90,7 @@ pub struct E3ProgramServer {
     port: u16,
     host: String,
     localhost_rewrite: Option<String>,
+    input_verifier: Option<Arc<dyn InputProofVerifier>>,
 }
 
 impl E3ProgramServer {
@@ -113,6 +124,7 @@ impl E3ProgramServer {
         let config = AppConfig {
             runner: Arc::clone(&self.runner),
             localhost_rewrite: self.localhost_rewrite.clone(),
+            input_verifier: self.input_verifier.clone(),
         };
         let server = HttpServer::new(move || {
             App::new()
@@ -134,6 +146,7 @@ impl E3ProgramServer {
 pub struct AppConfig {
     pub runner: Arc<Runner>,
     pub localhost_rewrite: Option<String>,
+    pub input_verifier: Option<Arc<dyn InputProofVerifier>>,
 }
 
 async fn call_webhook(callback_url: &str, payload: WebhookPayload) -> Result<()> {
@@ -192,7 +205,38 @@ async fn process_computation_background(
     e3_id: u64,
     callback_url: &str,
     fhe_inputs: FHEInputs,
+    validation: Option<(InputValidator, Vec<InputProof>)>,
 ) -> Result<()> {
+    let fhe_inputs = match validation {
+        Some((validator, proofs)) => {
+            let (accepted, report) =
+                tokio::task::spawn_blocking(move || validator.validate(&fhe_inputs, &proofs))
+                    .await?;
+            for rejected in &report.rejected {
+                eprintln!(
+                    "E3 {}: rejected input {}: {}",
+                    e3_id, rejected.index, rejected.reason
+                );
+            }
+            println!(
+                "E3 {}: {} of {} inputs verified",
+                e3_id,
+                report.verified.len(),
+                report.verified.len() + report.rejected.len()
+            );
+            if accepted.ciphertexts.is_empty() {
+                let payload = WebhookPayload::Failed {
+                    e3_id,
+                    error: "No input passed validation".to_string(),
+                };
+                handle_webhook_delivery(callback_url, payload).await?;
+                return Err(anyhow::anyhow!("No input passed validation"));
+            }
+            accepted
+        }
+        None => fhe_inputs,
+    };
+
     match runner(fhe_inputs).await {
         Ok((proof, ciphertext)) => {
             println!("computation finished!");
@@ -240,6 +284,20 @@ async fn handle_compute(
         ciphertexts: req.ciphertext_inputs.clone(),
     };
 
+    let validation = match &config.input_verifier {
+        Some(verifier) => {
+            let pk_commitment: [u8; 32] =
+                req.pk_commitment.as_slice().try_into().map_err(|_| {
+                    actix_web::error::ErrorBadRequest(
+                        "pk_commitment (32 bytes) is required when input validation is enabled",
+                    )
+                })?;
+            let proofs = req.input_proofs.iter().cloned().map(Into::into).collect();
+            Some((InputValidator::new(verifier.clone(), pk_commitment), proofs))
+        }
+        None => None,
+    };
+
     println!("fhe_inputs.params = {:?}", fhe_inputs.params);
     let callback_url = if let Some(new_host) = config.localhost_rewrite.clone() {
         callback_url
@@ -252,7 +310,8 @@ async fn handle_compute(
     let runner = config.runner.clone();
     tokio::spawn(async move {
         if let Err(e) =
-            process_computation_background(runner, e3_id, &callback_url, fhe_inputs).await
+            process_computation_background(runner, e3_id, &callback_url, fhe_inputs, validation)
+                .await
         {
             eprintln!("✗ Background computation failed for E3 {}: {:?}", e3_id, e);
         }
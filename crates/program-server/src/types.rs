@@ -6,6 +6,7 @@
 
 use anyhow::Result;
 use derivative::Derivative;
+use e3_compute_provider::InputProof;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[allow(dead_code)]
@@ -23,6 +24,29 @@ pub struct ComputeRequest {
     #[serde(deserialize_with = "deserialize_hex_tuple")]
     pub ciphertext_inputs: Vec<(Vec<u8>, u64)>,
     pub callback_url: Option<String>,
+    /// `user_data_encryption` proofs, one per entry of `ciphertext_inputs`.
+    #[serde(default)]
+    pub input_proofs: Vec<InputProofPayload>,
+    /// Commitment to the E3 public key the inputs must be encrypted under.
+    #[serde(default, deserialize_with = "deserialize_hex_string")]
+    pub pk_commitment: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct InputProofPayload {
+    #[serde(deserialize_with = "deserialize_hex_string")]
+    pub proof: Vec<u8>,
+    #[serde(deserialize_with = "deserialize_hex_string")]
+    pub public_inputs: Vec<u8>,
+}
+
+impl From<InputProofPayload> for InputProof {
+    fn from(payload: InputProofPayload) -> Self {
+        InputProof {
+            proof: payload.proof,
+            public_inputs: payload.public_inputs,
+        }
+    }
 }
 
 #[derive(Derivative, Serialize, Deserialize)]
@@ -149,6 +173,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_deserialize_compute_request_with_input_proofs() {
+        let json = r#"
+        {
+            "e3_id": 12345,
+            "params": "0x12345ffa",
+            "ciphertext_inputs": [["0xffabc123", 100]],
+            "callback_url": "https://example.com/callback",
+            "input_proofs": [{ "proof": "0xdead", "public_inputs": "0xbeef" }],
+            "pk_commitment": "0x0102"
+        }
+        "#;
+
+        let payload: ComputeRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(payload.input_proofs.len(), 1);
+        assert_eq!(payload.input_proofs[0].proof, vec![0xde, 0xad]);
+        assert_eq!(payload.input_proofs[0].public_inputs, vec![0xbe, 0xef]);
+        assert_eq!(payload.pk_commitment, vec![0x01, 0x02]);
+    }
+
     #[test]
     fn test_webhook_payload_serialization_completed() {
         let payload = WebhookPayload::Completed {
@@ -44,8 +44,8 @@ use alloy::primitives::{Address, Bytes};
 use alloy::signers::local::PrivateKeySigner;
 use e3_events::{
     AccusationVote, BusHandle, CommitmentConsistencyViolation, ComputeRequestError,
-    ComputeResponse, E3id, EventPublisher, EventSubscriber, EventType, InterfoldEvent,
-    InterfoldEventData, ProofFailureAccusation, ProofType, ProofVerificationFailed,
+    ComputeResponse, DkgDealerDisqualified, E3id, EventPublisher, EventSubscriber, EventType,
+    InterfoldEvent, InterfoldEventData, ProofFailureAccusation, ProofType, ProofVerificationFailed,
     ProofVerificationPassed, TypedEvent,
 };
 use e3_utils::NotifySync;
@@ -95,6 +95,7 @@ impl Clock for SystemClock {
 /// - [`ProofFailureAccusation`] — incoming accusations from other nodes via gossip
 /// - [`AccusationVote`] — incoming votes from other nodes via gossip
 /// - [`SlashExecuted`] — on-chain slash confirmation for committee updates
+/// - [`DkgDealerDisqualified`] — DKG complaint verdict, escalated to an accusation
 ///
 /// Publishes:
 /// - [`ProofFailureAccusation`] — broadcast own accusations via gossip
@@ -105,6 +106,7 @@ impl Clock for SystemClock {
 /// [`CommitteeFinalized`]: e3_events::CommitteeFinalized
 /// [`AccusationQuorumReached`]: e3_events::AccusationQuorumReached
 /// [`SlashExecuted`]: e3_events::SlashExecuted
+/// [`DkgDealerDisqualified`]: e3_events::DkgDealerDisqualified
 pub struct AccusationManager {
     bus: BusHandle,
     /// Plain, synchronous protocol core. Owns all accusation/vote state.
@@ -207,6 +209,7 @@ impl AccusationManager {
         bus.subscribe(EventType::ComputeResponse, addr.clone().into());
         bus.subscribe(EventType::ComputeRequestError, addr.clone().into());
         bus.subscribe(EventType::SlashExecuted, addr.clone().into());
+        bus.subscribe(EventType::DkgDealerDisqualified, addr.clone().into());
         bus.subscribe(
             EventType::CommitmentConsistencyViolation,
             addr.clone().into(),
@@ -333,6 +336,9 @@ impl Handler<InterfoldEvent> for AccusationManager {
             InterfoldEventData::SlashExecuted(data) => {
                 self.voting.on_slash_executed(data);
             }
+            InterfoldEventData::DkgDealerDisqualified(data) => {
+                self.notify_sync(ctx, TypedEvent::new(data, ec))
+            }
             InterfoldEventData::CommitmentConsistencyViolation(data) => {
                 self.notify_sync(ctx, TypedEvent::new(data, ec))
             }
@@ -355,6 +361,20 @@ impl Handler<TypedEvent<ProofVerificationFailed>> for AccusationManager {
     }
 }
 
+impl Handler<TypedEvent<DkgDealerDisqualified>> for AccusationManager {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: TypedEvent<DkgDealerDisqualified>,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let (data, ec) = msg.into_components();
+        let actions = self.voting.on_dealer_disqualified(data, &ec);
+        self.apply_actions(actions, ctx);
+    }
+}
+
 impl Handler<TypedEvent<ProofVerificationPassed>> for AccusationManager {
     type Result = ();
 
@@ -34,11 +34,11 @@ use alloy::signers::SignerSync;
 use alloy::sol_types::SolValue;
 use e3_events::{
     AccusationOutcome, AccusationQuorumReached, AccusationVote, CommitmentConsistencyViolation,
-    ComputeRequest, ComputeRequestError, ComputeResponse, ComputeResponseKind, CorrelationId, E3id,
-    EventContext, PartyProofsToVerify, ProofFailureAccusation, ProofType, ProofVerificationFailed,
-    ProofVerificationPassed, Sequenced, SignedProofPayload, SlashExecuted, TypedEvent,
-    VerifyShareProofsRequest, ZkRequest, ZkResponse, VOTE_DOMAIN_NAME, VOTE_DOMAIN_VERSION,
-    VOTE_TYPEHASH_STR,
+    ComputeRequest, ComputeRequestError, ComputeResponse, ComputeResponseKind, CorrelationId,
+    DkgComplaintReason, DkgDealerDisqualified, E3id, EventContext, PartyProofsToVerify,
+    ProofFailureAccusation, ProofType, ProofVerificationFailed, ProofVerificationPassed, Sequenced,
+    SignedProofPayload, SlashExecuted, TypedEvent, VerifyShareProofsRequest, ZkRequest, ZkResponse,
+    VOTE_DOMAIN_NAME, VOTE_DOMAIN_VERSION, VOTE_TYPEHASH_STR,
 };
 use e3_utils::ArcBytes;
 use e3_zk_helpers::CiphernodesCommitteeSize;
@@ -1226,6 +1226,75 @@ impl AccusationVoting {
         }
     }
 
+    /// Handle a [`DkgDealerDisqualified`] verdict of our DKG complaint round.
+    ///
+    /// Invalid-proof verdicts are already accused through the [`ProofVerificationFailed`]
+    /// events that re-verified the complaint evidence. A withheld share has no proof to
+    /// re-verify, so it is accused as [`ProofType::DkgShareWithheld`]: members whose own round
+    /// reached the same verdict vote for it, and a quorum gets the dealer slashed and expelled
+    /// on-chain. Until then the dealer keeps its votes like any other member.
+    pub(crate) fn on_dealer_disqualified(
+        &mut self,
+        data: DkgDealerDisqualified,
+        ec: &EventContext<Sequenced>,
+    ) -> Vec<VoteAction> {
+        if data.e3_id != self.e3_id || !self.committee.contains(&data.address) {
+            return Vec::new();
+        }
+        if data.reason == DkgComplaintReason::InvalidShareProofs {
+            info!(
+                "Dealer {} (party {}) failed share proof re-verification; accused via the proof failure",
+                data.address, data.party_id
+            );
+            return Vec::new();
+        }
+
+        let Some(evidence) = self.withheld_share_evidence(data.party_id) else {
+            return Vec::new();
+        };
+        let data_hash: [u8; 32] = keccak256(&evidence).into();
+        self.received_data.insert(
+            (data.address, ProofType::DkgShareWithheld),
+            ReceivedProofData {
+                data_hash,
+                verification_passed: false,
+                evidence,
+            },
+        );
+
+        let mut actions = Vec::new();
+        self.initiate_accusation(
+            data.address,
+            data.party_id,
+            ProofType::DkgShareWithheld,
+            data_hash,
+            None,
+            ec,
+            &mut actions,
+        );
+        actions
+    }
+
+    /// Evidence for a withheld share: `abi.encode(chainId, e3Id, partyId)`. Every member that
+    /// reached the verdict derives the same bytes, so their votes share one `dataHash`.
+    fn withheld_share_evidence(&self, party_id: u64) -> Option<Bytes> {
+        let e3_id: U256 = match self.e3_id.clone().try_into() {
+            Ok(id) => id,
+            Err(_) => {
+                error!("E3id {} cannot be converted to U256", self.e3_id);
+                return None;
+            }
+        };
+        Some(Bytes::from(
+            (
+                U256::from(self.e3_id.chain_id()),
+                e3_id,
+                U256::from(party_id),
+            )
+                .abi_encode(),
+        ))
+    }
+
     /// Handle ZK re-verification response for a forwarded C3a/C3b proof.
     pub(crate) fn handle_reverification_response(
         &mut self,
@@ -1663,4 +1732,59 @@ mod tests {
             CiphernodesCommitteeSize::Micro
         );
     }
+
+    /// A withheld-share verdict is escalated to an accusation, and a member that reached
+    /// the same verdict votes for it.
+    #[test]
+    fn withheld_share_verdict_is_accused() {
+        let me = signer(1);
+        let other = signer(2);
+        let dealer = signer(3);
+        let committee = vec![me.address(), other.address(), dealer.address()];
+        let verdict = DkgDealerDisqualified {
+            e3_id: E3id::new("42", CHAIN_ID),
+            party_id: 2,
+            address: dealer.address(),
+            reason: DkgComplaintReason::MissingShare,
+            accusers: [0u64, 1].into_iter().collect(),
+        };
+
+        let mut v = voting_with(&me, committee.clone(), 2);
+        let actions = v.on_dealer_disqualified(verdict.clone(), &ctx());
+        let accusation = actions
+            .iter()
+            .find_map(|a| match a {
+                VoteAction::PublishAccusation { accusation, .. } => Some(accusation.clone()),
+                _ => None,
+            })
+            .expect("accusation published");
+        assert_eq!(accusation.proof_type, ProofType::DkgShareWithheld);
+        assert_eq!(accusation.accused, dealer.address());
+        assert_eq!(v.committee.len(), 3, "the dealer stays until expelled");
+
+        // A member without the verdict abstains. Once its own round reaches the verdict it
+        // votes on the same accusation id with the same data hash.
+        let mut peer = voting_with(&other, committee, 2);
+        assert!(peer
+            .on_accusation_received(accusation.clone(), &ctx())
+            .is_empty());
+        let votes = peer.on_dealer_disqualified(verdict, &ctx());
+        assert!(votes.iter().any(|a| matches!(
+            a,
+            VoteAction::PublishVote { vote, .. }
+                if vote.accusation_id == AccusationVoting::accusation_id(&accusation)
+                    && vote.data_hash == accusation.data_hash
+        )));
+
+        // Invalid-proof verdicts were already accused through the failed proofs.
+        let mut v = voting_with(&me, vec![me.address(), dealer.address()], 1);
+        let invalid = DkgDealerDisqualified {
+            e3_id: E3id::new("42", CHAIN_ID),
+            party_id: 1,
+            address: dealer.address(),
+            reason: DkgComplaintReason::InvalidShareProofs,
+            accusers: [0u64].into_iter().collect(),
+        };
+        assert!(v.on_dealer_disqualified(invalid, &ctx()).is_empty());
+    }
 }
@@ -14,9 +14,9 @@ use e3_events::EventContext;
 use e3_events::Sequenced;
 use e3_events::TypedEvent;
 use e3_events::{
-    prelude::*, trap, AggregatorChanged, BusHandle, CiphernodeSelected, Committee,
-    CommitteeFinalized, CommitteeMemberExpelled, E3Requested, E3id, EType, EventType,
-    InterfoldEvent, InterfoldEventData, Shutdown, TicketGenerated, TicketId,
+    prelude::*, trap, AggregatorChanged, AggregatorPresumedUnresponsive, BusHandle,
+    CiphernodeSelected, Committee, CommitteeFinalized, CommitteeMemberExpelled, E3Requested, E3id,
+    EType, EventType, InterfoldEvent, InterfoldEventData, Shutdown, TicketGenerated, TicketId,
 };
 use e3_request::E3Meta;
 use e3_utils::NotifySync;
@@ -97,6 +97,10 @@ impl CiphernodeSelector {
         bus.subscribe(EventType::E3RequestComplete, addr.clone().recipient());
         bus.subscribe(EventType::CommitteeFinalized, addr.clone().recipient());
         bus.subscribe(EventType::CommitteeMemberExpelled, addr.clone().recipient());
+        bus.subscribe(
+            EventType::AggregatorPresumedUnresponsive,
+            addr.clone().recipient(),
+        );
         bus.subscribe(EventType::Shutdown, addr.clone().recipient());
 
         info!("CiphernodeSelector listening!");
@@ -161,6 +165,9 @@ impl Handler<InterfoldEvent> for CiphernodeSelector {
             InterfoldEventData::CommitteeMemberExpelled(data) => {
                 self.notify_sync(ctx, TypedEvent::new(data, ec))
             }
+            InterfoldEventData::AggregatorPresumedUnresponsive(data) => {
+                self.notify_sync(ctx, TypedEvent::new(data, ec))
+            }
             InterfoldEventData::Shutdown(data) => self.notify_sync(ctx, data),
             _ => (),
         }
@@ -322,6 +329,7 @@ impl Handler<TypedEvent<CommitteeFinalized>> for CiphernodeSelector {
                             params_preset: e3_meta.params_preset,
                             params: e3_meta.params.clone(),
                             seed: e3_meta.seed,
+                            committee: msg.committee.clone(),
                         },
                         ec.clone(),
                     )?;
@@ -365,6 +373,44 @@ impl Handler<TypedEvent<CommitteeMemberExpelled>> for CiphernodeSelector {
     }
 }
 
+impl Handler<TypedEvent<AggregatorPresumedUnresponsive>> for CiphernodeSelector {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: TypedEvent<AggregatorPresumedUnresponsive>,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        trap(EType::Sortition, &self.bus.with_ec(msg.get_ctx()), || {
+            let (msg, ec) = msg.into_components();
+            let Some(state) = self.state.get() else {
+                bail!("Could not get selector state");
+            };
+            if !state.committees.contains_key(&msg.e3_id) {
+                // The E3 already completed or the committee was never finalized locally.
+                return Ok(());
+            }
+
+            info!(
+                e3_id = %msg.e3_id,
+                party_id = msg.party_id,
+                "Aggregator presumed unresponsive, re-evaluating aggregator role"
+            );
+
+            self.state.try_mutate(&ec, |mut state| {
+                let unresponsive = state.unresponsive.entry(msg.e3_id.clone()).or_default();
+                if !unresponsive.contains(&msg.party_id) {
+                    unresponsive.push(msg.party_id);
+                    unresponsive.sort_unstable();
+                }
+                Ok(state)
+            })?;
+
+            self.update_aggregator_status(&msg.e3_id, &ec, false)
+        })
+    }
+}
+
 impl Handler<EmitPersistedAggregatorState> for CiphernodeSelector {
     type Result = ();
 
@@ -9,22 +9,23 @@ use crate::domain::node_registry::{NodeRegistry, NodeStateStore};
 use crate::domain::ticket_sortition;
 use crate::messages::{
     CommitteeMembersResponse, E3CommitteeContainsRequest, E3CommitteeContainsResponse,
-    GetCommitteeMembersRequest, WithSortitionTicket,
+    GetBondedOperators, GetCommitteeMembersRequest, WithSortitionTicket,
 };
 use crate::CiphernodeSelector;
 use actix::prelude::*;
+use alloy::primitives::Address;
 use anyhow::{anyhow, Result};
 use e3_data::{AutoPersist, Persistable, Repository};
 use e3_events::{
     prelude::*, trap, CiphernodeAdded, CiphernodeRemoved, Committee, CommitteeFinalized,
     CommitteeMemberExpelled, CommitteePublished, ConfigurationUpdated, E3Failed, E3Requested,
     E3Stage, E3StageChanged, EType, EventContext, EventType, InterfoldEvent,
-    OperatorActivationChanged, PlaintextOutputPublished, Seed, Sequenced, TicketBalanceUpdated,
-    TypedEvent,
+    OperatorActivationChanged, PlaintextOutputPublished, Seed, Sequenced, SlashExecuted,
+    TicketBalanceUpdated, TypedEvent,
 };
 use e3_events::{BusHandle, E3id, InterfoldEventData};
 use e3_utils::{NotifySync, MAILBOX_LIMIT};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::{info, instrument, warn};
 
 /// Sortition actor that manages the sortition algorithm and the node state.
@@ -45,6 +46,8 @@ pub struct Sortition {
     /// committee was finalized (e.g. out-of-order live delivery or a reorg). Drained when the
     /// `CommitteeFinalized` event for the same E3 is processed so early expulsions are not lost.
     pending_expulsions: HashMap<E3id, Vec<(CommitteeMemberExpelled, EventContext<Sequenced>)>>,
+    /// Declared operator groups applied to the node state for diversity constrained sortition.
+    operator_groups: HashMap<Address, String>,
 }
 
 /// Parameters for constructing a `Sortition` actor.
@@ -62,6 +65,8 @@ pub struct SortitionParams {
     pub ciphernode_selector: Addr<CiphernodeSelector>,
     /// Address for the current node
     pub address: String,
+    /// Declared operator groups
+    pub operator_groups: HashMap<Address, String>,
 }
 
 impl Sortition {
@@ -74,6 +79,7 @@ impl Sortition {
             ciphernode_selector: params.ciphernode_selector,
             address: params.address,
             pending_expulsions: HashMap::new(),
+            operator_groups: params.operator_groups,
         }
     }
 
@@ -84,17 +90,28 @@ impl Sortition {
         node_state_store: Repository<HashMap<u64, NodeStateStore>>,
         committees_store: Repository<HashMap<e3_events::E3id, Committee>>,
         default_backend: SortitionBackend,
+        operator_groups: HashMap<Address, String>,
         ciphernode_selector: Addr<CiphernodeSelector>,
         address: &str,
     ) -> Result<Addr<Self>> {
         let mut backends = backends_store.load_or_default(HashMap::new()).await?;
-        let node_state = node_state_store.load_or_default(HashMap::new()).await?;
+        let mut node_state = node_state_store.load_or_default(HashMap::new()).await?;
         let finalized_committees = committees_store.load_or_default(HashMap::new()).await?;
 
-        backends.try_mutate_without_context(|mut list| {
+        // The configured policy may have changed since the backends were persisted
+        backends.try_mutate_without_context(|list| {
+            let mut list: HashMap<u64, SortitionBackend> = list
+                .into_iter()
+                .map(|(chain_id, backend)| (chain_id, backend.with_policy_of(&default_backend)))
+                .collect();
             list.insert(u64::MAX, default_backend);
             Ok(list)
         })?;
+        // Group declarations may have changed since the node state was persisted
+        node_state.try_mutate_without_context(|mut state_map| {
+            NodeRegistry::set_operator_groups(&mut state_map, &operator_groups);
+            Ok(state_map)
+        })?;
 
         let addr = Sortition::new(SortitionParams {
             bus: bus.clone(),
@@ -103,6 +120,7 @@ impl Sortition {
             finalized_committees,
             ciphernode_selector,
             address: address.to_owned(),
+            operator_groups,
         })
         .start();
 
@@ -118,6 +136,7 @@ impl Sortition {
                 EventType::PlaintextOutputPublished,
                 EventType::CommitteeFinalized,
                 EventType::CommitteeMemberExpelled,
+                EventType::SlashExecuted,
                 EventType::E3Failed,
                 EventType::E3StageChanged,
             ],
@@ -291,6 +310,9 @@ impl Handler<InterfoldEvent> for Sortition {
             InterfoldEventData::CommitteeMemberExpelled(data) => {
                 self.notify_sync(ctx, TypedEvent::new(data, ec))
             }
+            InterfoldEventData::SlashExecuted(data) => {
+                self.notify_sync(ctx, TypedEvent::new(data, ec))
+            }
             InterfoldEventData::E3Failed(data) => self.notify_sync(ctx, TypedEvent::new(data, ec)),
             InterfoldEventData::E3StageChanged(data) => {
                 self.notify_sync(ctx, TypedEvent::new(data, ec))
@@ -355,8 +377,10 @@ impl Handler<TypedEvent<CiphernodeAdded>> for Sortition {
             let chain_id = msg.chain_id;
             let addr = msg.address.clone();
 
+            let operator_groups = &self.operator_groups;
             self.node_state.try_mutate(&ec, |mut state_map| {
                 NodeRegistry::add_node(&mut state_map, chain_id, addr.clone());
+                NodeRegistry::set_operator_groups(&mut state_map, operator_groups);
                 Ok(state_map)
             })?;
             self.backends.try_mutate(&ec, move |mut list_map| {
@@ -507,6 +531,24 @@ impl Handler<GetCommitteeMembersRequest> for Sortition {
     }
 }
 
+impl Handler<GetBondedOperators> for Sortition {
+    type Result = MessageResult<GetBondedOperators>;
+
+    fn handle(&mut self, _: GetBondedOperators, _: &mut Self::Context) -> Self::Result {
+        let operators: HashSet<String> = self
+            .node_state
+            .get()
+            .map(|state_map| {
+                state_map
+                    .values()
+                    .flat_map(|state| state.bonded_operators().cloned())
+                    .collect()
+            })
+            .unwrap_or_default();
+        MessageResult(operators)
+    }
+}
+
 impl<T> Handler<E3CommitteeContainsRequest<T>> for Sortition
 where
     T: Clone + Send + Sync + 'static,
@@ -625,6 +667,24 @@ impl Handler<TypedEvent<CommitteeFinalized>> for Sortition {
     }
 }
 
+impl Handler<TypedEvent<SlashExecuted>> for Sortition {
+    type Result = ();
+
+    fn handle(&mut self, msg: TypedEvent<SlashExecuted>, _: &mut Self::Context) -> Self::Result {
+        let (msg, ec) = msg.into_components();
+        trap(EType::Sortition, &self.bus.with_ec(&ec), || {
+            self.node_state.try_mutate(&ec, |mut state_map| {
+                NodeRegistry::record_slash(
+                    &mut state_map,
+                    msg.e3_id.chain_id(),
+                    msg.operator.to_string(),
+                );
+                Ok(state_map)
+            })
+        })
+    }
+}
+
 impl Handler<TypedEvent<CommitteeMemberExpelled>> for Sortition {
     type Result = ();
 
@@ -642,6 +702,15 @@ impl Handler<TypedEvent<CommitteeMemberExpelled>> for Sortition {
         }
 
         trap(EType::Sortition, &self.bus.with_ec(&ec), || {
+            self.node_state.try_mutate(&ec, |mut state_map| {
+                NodeRegistry::record_expulsion(
+                    &mut state_map,
+                    data.e3_id.chain_id(),
+                    data.node.to_string(),
+                );
+                Ok(state_map)
+            })?;
+
             if self.try_resolve_and_publish_expulsion(data.clone(), ec.clone())? {
                 return Ok(());
             }
@@ -4,9 +4,11 @@
 // without even the implied warranty of MERCHANTABILITY
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
+use crate::domain::committee_selection::{
+    candidates_from_state, Candidate, ReputationWeights, SelectionPolicy,
+};
 use crate::domain::node_registry::NodeStateStore;
-use crate::domain::ticket::{RegisteredNode, Ticket};
-use crate::domain::ticket_sortition::ScoreSortition;
+use crate::domain::ticket::{RegisteredNode, WinnerTicket};
 use alloy::primitives::Address;
 use anyhow::Result;
 use e3_events::{E3id, Seed};
@@ -66,95 +68,31 @@ pub struct ScoreBackend {
 }
 
 impl ScoreBackend {
-    /// Build a vector of ephemeral nodes from the node state.
-    ///
-    /// The nodes are built from the node state and the registered nodes.
-    fn build_nodes_from_state(
-        &self,
-        chain_id: u64,
-        node_state: &NodeStateStore,
-    ) -> Vec<RegisteredNode> {
-        info!(
-            chain_id = chain_id,
-            registered_count = self.registered.len(),
-            node_state_count = node_state.nodes.len(),
-            "Building nodes from state for score sortition"
-        );
-
-        self.registered
-            .iter()
-            .filter_map(|n| {
-                let addr_str = n.address.to_string();
-                let Some(ns) = node_state.nodes.get(&addr_str) else {
-                    info!(
-                        address = %addr_str,
-                        chain_id = chain_id,
-                        "Node not found in NodeStateStore"
-                    );
-                    return None;
-                };
-                if !ns.active {
-                    info!(
-                        address = %addr_str,
-                        "Node is not active"
-                    );
-                    return None;
-                }
-
-                let count = node_state.available_tickets(&addr_str);
-                let total_tickets = if node_state.ticket_price.is_zero() {
-                    0u64
-                } else {
-                    (ns.ticket_balance / node_state.ticket_price)
-                        .try_into()
-                        .unwrap_or(0u64)
-                };
-
-                if count == 0 {
-                    info!(
-                        address = %addr_str,
-                        ticket_balance = ?ns.ticket_balance,
-                        ticket_price = ?node_state.ticket_price,
-                        total_tickets = total_tickets,
-                        active_jobs = ns.active_jobs,
-                        "Node has no available tickets"
-                    );
-                    return None;
-                }
-
-                let tickets = (1..=count).map(|i| Ticket { ticket_id: i }).collect();
-                Some(RegisteredNode {
-                    address: n.address,
-                    tickets,
-                })
-            })
-            .collect()
+    /// Build the selection candidates from the node state and the registered nodes.
+    pub fn candidates(&self, chain_id: u64, node_state: &NodeStateStore) -> Vec<Candidate> {
+        candidates_from_state(&self.registered, chain_id, node_state)
     }
-}
 
-impl SortitionList<String> for ScoreBackend {
-    /// Compute score-based winners (`ScoreSortition`) and check if `address` is included.
-    ///
-    /// Returns `Ok(false)` if there are no nodes or `size == 0`.
-    fn contains(
+    /// Select the committee of `size` members under `policy`.
+    fn committee(
         &self,
+        policy: &SelectionPolicy,
         e3_id: E3id,
         seed: Seed,
         size: usize,
-        address: String,
         chain_id: u64,
         node_state: &NodeStateStore,
-    ) -> anyhow::Result<bool> {
+    ) -> Result<Vec<WinnerTicket>> {
         if size == 0 {
-            return Ok(false);
+            return Ok(Vec::new());
         }
 
-        let nodes = self.build_nodes_from_state(chain_id, node_state);
-        if nodes.is_empty() {
-            return Ok(false);
+        let candidates = self.candidates(chain_id, node_state);
+        if candidates.is_empty() {
+            return Ok(Vec::new());
         }
 
-        let winners = ScoreSortition::new(size).get_committee(e3_id.clone(), seed, &nodes)?;
+        let winners = policy.select(e3_id.clone(), seed, size, &candidates)?;
 
         let selected_nodes: Vec<String> = winners
             .iter()
@@ -164,53 +102,58 @@ impl SortitionList<String> for ScoreBackend {
             e3_id = %e3_id,
             chain_id = chain_id,
             committee_size = size,
+            policy = ?policy,
             selected_count = winners.len(),
             nodes = ?selected_nodes,
             "Sortition completed - selected nodes"
         );
 
-        let want: Address = address.parse()?;
-        Ok(winners.iter().any(|w| w.address == want))
+        Ok(winners)
     }
 
-    /// Compute score-based winners (`ScoreSortition`) and check if `address` is included.
+    /// Check if `address` is in the committee selected under `policy`.
     ///
-    /// Returns `Ok(None)` if there are no nodes or `size == 0`.
-    fn get_index(
+    /// Returns `Ok(false)` if there are no nodes or `size == 0`.
+    #[allow(clippy::too_many_arguments)]
+    fn committee_contains(
         &self,
+        policy: &SelectionPolicy,
         e3_id: E3id,
         seed: Seed,
         size: usize,
         address: String,
         chain_id: u64,
         node_state: &NodeStateStore,
-    ) -> anyhow::Result<Option<(u64, Option<u64>)>> {
-        if size == 0 {
-            return Ok(None);
+    ) -> Result<bool> {
+        let winners = self.committee(policy, e3_id, seed, size, chain_id, node_state)?;
+        if winners.is_empty() {
+            return Ok(false);
         }
 
-        let nodes: Vec<RegisteredNode> = self.build_nodes_from_state(chain_id, node_state);
+        let want: Address = address.parse()?;
+        Ok(winners.iter().any(|w| w.address == want))
+    }
 
-        if nodes.is_empty() {
+    /// Return the party index and ticket of `address` in the committee selected under `policy`.
+    ///
+    /// Returns `Ok(None)` if there are no nodes or `size == 0`.
+    #[allow(clippy::too_many_arguments)]
+    fn committee_index(
+        &self,
+        policy: &SelectionPolicy,
+        e3_id: E3id,
+        seed: Seed,
+        size: usize,
+        address: String,
+        chain_id: u64,
+        node_state: &NodeStateStore,
+    ) -> Result<Option<(u64, Option<u64>)>> {
+        let winners = self.committee(policy, e3_id, seed, size, chain_id, node_state)?;
+        if winners.is_empty() {
             return Ok(None);
         }
 
-        let winners = ScoreSortition::new(size).get_committee(e3_id.clone(), seed, &nodes)?;
-
-        let selected_nodes: Vec<String> = winners
-            .iter()
-            .map(|w| format!("{}(ticket:{})", w.address, w.ticket_id))
-            .collect();
-        info!(
-            e3_id = %e3_id,
-            chain_id = chain_id,
-            committee_size = size,
-            selected_count = winners.len(),
-            nodes = ?selected_nodes,
-            "Sortition completed - selected nodes"
-        );
-
-        let want: alloy::primitives::Address = address.parse()?;
+        let want: Address = address.parse()?;
 
         let maybe = winners
             .iter()
@@ -218,6 +161,54 @@ impl SortitionList<String> for ScoreBackend {
             .find_map(|(i, w)| (w.address == want).then_some((i as u64, Some(w.ticket_id))));
         Ok(maybe)
     }
+}
+
+impl SortitionList<String> for ScoreBackend {
+    /// Compute score-based winners (`ScoreSortition`) and check if `address` is included.
+    ///
+    /// Returns `Ok(false)` if there are no nodes or `size == 0`.
+    fn contains(
+        &self,
+        e3_id: E3id,
+        seed: Seed,
+        size: usize,
+        address: String,
+        chain_id: u64,
+        node_state: &NodeStateStore,
+    ) -> anyhow::Result<bool> {
+        self.committee_contains(
+            &SelectionPolicy::Score,
+            e3_id,
+            seed,
+            size,
+            address,
+            chain_id,
+            node_state,
+        )
+    }
+
+    /// Compute score-based winners (`ScoreSortition`) and check if `address` is included.
+    ///
+    /// Returns `Ok(None)` if there are no nodes or `size == 0`.
+    fn get_index(
+        &self,
+        e3_id: E3id,
+        seed: Seed,
+        size: usize,
+        address: String,
+        chain_id: u64,
+        node_state: &NodeStateStore,
+    ) -> anyhow::Result<Option<(u64, Option<u64>)>> {
+        self.committee_index(
+            &SelectionPolicy::Score,
+            e3_id,
+            seed,
+            size,
+            address,
+            chain_id,
+            node_state,
+        )
+    }
 
     /// Add a node, creating an empty ticket set when first seen.
     fn add(&mut self, address: String) {
@@ -257,6 +248,117 @@ impl SortitionList<String> for ScoreBackend {
     }
 }
 
+/// Diversity constrained backend.
+///
+/// Selects like [`ScoreBackend`] but admits at most `max_per_group` members sharing a declared
+/// operator group (see [`SelectionPolicy::Diverse`]).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DiverseBackend {
+    nodes: ScoreBackend,
+    max_per_group: usize,
+}
+
+impl DiverseBackend {
+    pub fn new(max_per_group: usize) -> Self {
+        Self {
+            nodes: ScoreBackend::default(),
+            max_per_group,
+        }
+    }
+
+    pub fn policy(&self) -> SelectionPolicy {
+        SelectionPolicy::Diverse {
+            max_per_group: self.max_per_group,
+        }
+    }
+}
+
+/// Reputation weighted backend.
+///
+/// Selects like [`ScoreBackend`] with every node's score scaled by its slashing and committee
+/// expulsion history (see [`SelectionPolicy::Reputation`]).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ReputationBackend {
+    nodes: ScoreBackend,
+    weights: ReputationWeights,
+}
+
+impl ReputationBackend {
+    pub fn new(weights: ReputationWeights) -> Self {
+        Self {
+            nodes: ScoreBackend::default(),
+            weights,
+        }
+    }
+
+    pub fn policy(&self) -> SelectionPolicy {
+        SelectionPolicy::Reputation(self.weights.clone())
+    }
+}
+
+/// Implement [`SortitionList`] for a backend that wraps a [`ScoreBackend`] in `nodes` and
+/// selects under its own `policy()`.
+macro_rules! impl_policy_sortition_list {
+    ($backend:ty) => {
+        impl SortitionList<String> for $backend {
+            fn contains(
+                &self,
+                e3_id: E3id,
+                seed: Seed,
+                size: usize,
+                address: String,
+                chain_id: u64,
+                node_state: &NodeStateStore,
+            ) -> anyhow::Result<bool> {
+                self.nodes.committee_contains(
+                    &self.policy(),
+                    e3_id,
+                    seed,
+                    size,
+                    address,
+                    chain_id,
+                    node_state,
+                )
+            }
+
+            fn get_index(
+                &self,
+                e3_id: E3id,
+                seed: Seed,
+                size: usize,
+                address: String,
+                chain_id: u64,
+                node_state: &NodeStateStore,
+            ) -> anyhow::Result<Option<(u64, Option<u64>)>> {
+                self.nodes.committee_index(
+                    &self.policy(),
+                    e3_id,
+                    seed,
+                    size,
+                    address,
+                    chain_id,
+                    node_state,
+                )
+            }
+
+            fn add(&mut self, address: String) {
+                self.nodes.add(address)
+            }
+
+            fn remove(&mut self, address: String) {
+                self.nodes.remove(address)
+            }
+
+            fn nodes(&self) -> Vec<String> {
+                self.nodes.nodes()
+            }
+        }
+    };
+}
+
+impl_policy_sortition_list!(DiverseBackend);
+impl_policy_sortition_list!(ReputationBackend);
+
 /// Enum wrapper around the supported backends.
 ///
 /// New chains default to `Score` sortition.
@@ -264,6 +366,10 @@ impl SortitionList<String> for ScoreBackend {
 pub enum SortitionBackend {
     /// Score-based selection (stores `RegisteredNode`s with tickets).
     Score(ScoreBackend),
+    /// Score-based selection capping the members per operator group.
+    Diverse(DiverseBackend),
+    /// Score-based selection weighted by slashing and expulsion history.
+    Reputation(ReputationBackend),
 }
 
 impl Default for SortitionBackend {
@@ -276,6 +382,54 @@ impl SortitionBackend {
     pub fn score() -> Self {
         SortitionBackend::Score(ScoreBackend::default())
     }
+
+    pub fn diverse(max_per_group: usize) -> Self {
+        SortitionBackend::Diverse(DiverseBackend::new(max_per_group))
+    }
+
+    pub fn reputation(weights: ReputationWeights) -> Self {
+        SortitionBackend::Reputation(ReputationBackend::new(weights))
+    }
+
+    /// The policy committees are selected under.
+    pub fn policy(&self) -> SelectionPolicy {
+        match self {
+            SortitionBackend::Score(_) => SelectionPolicy::Score,
+            SortitionBackend::Diverse(b) => b.policy(),
+            SortitionBackend::Reputation(b) => b.policy(),
+        }
+    }
+
+    /// Keep the registered nodes of this backend but select them under the policy of `other`.
+    pub fn with_policy_of(self, other: &SortitionBackend) -> Self {
+        let nodes = match self {
+            SortitionBackend::Score(b) => b,
+            SortitionBackend::Diverse(b) => b.nodes,
+            SortitionBackend::Reputation(b) => b.nodes,
+        };
+        match other {
+            SortitionBackend::Score(_) => SortitionBackend::Score(nodes),
+            SortitionBackend::Diverse(b) => SortitionBackend::Diverse(DiverseBackend {
+                nodes,
+                max_per_group: b.max_per_group,
+            }),
+            SortitionBackend::Reputation(b) => SortitionBackend::Reputation(ReputationBackend {
+                nodes,
+                weights: b.weights.clone(),
+            }),
+        }
+    }
+
+    /// The selection candidates of the chain, as used by [`verify_committee`].
+    ///
+    /// [`verify_committee`]: crate::verify_committee
+    pub fn candidates(&self, chain_id: u64, node_state: &NodeStateStore) -> Vec<Candidate> {
+        match self {
+            SortitionBackend::Score(b) => b.candidates(chain_id, node_state),
+            SortitionBackend::Diverse(b) => b.nodes.candidates(chain_id, node_state),
+            SortitionBackend::Reputation(b) => b.nodes.candidates(chain_id, node_state),
+        }
+    }
 }
 
 impl SortitionList<String> for SortitionBackend {
@@ -292,6 +446,12 @@ impl SortitionList<String> for SortitionBackend {
             SortitionBackend::Score(b) => {
                 b.contains(e3_id, seed, size, address, chain_id, node_state)
             }
+            SortitionBackend::Diverse(b) => {
+                b.contains(e3_id, seed, size, address, chain_id, node_state)
+            }
+            SortitionBackend::Reputation(b) => {
+                b.contains(e3_id, seed, size, address, chain_id, node_state)
+            }
         }
     }
 
@@ -308,24 +468,36 @@ impl SortitionList<String> for SortitionBackend {
             SortitionBackend::Score(b) => {
                 b.get_index(e3_id, seed, size, address, chain_id, node_state)
             }
+            SortitionBackend::Diverse(b) => {
+                b.get_index(e3_id, seed, size, address, chain_id, node_state)
+            }
+            SortitionBackend::Reputation(b) => {
+                b.get_index(e3_id, seed, size, address, chain_id, node_state)
+            }
         }
     }
 
     fn add(&mut self, address: String) {
         match self {
             SortitionBackend::Score(backend) => backend.add(address),
+            SortitionBackend::Diverse(backend) => backend.add(address),
+            SortitionBackend::Reputation(backend) => backend.add(address),
         }
     }
 
     fn remove(&mut self, address: String) {
         match self {
             SortitionBackend::Score(backend) => backend.remove(address),
+            SortitionBackend::Diverse(backend) => backend.remove(address),
+            SortitionBackend::Reputation(backend) => backend.remove(address),
         }
     }
 
     fn nodes(&self) -> Vec<String> {
         match self {
             SortitionBackend::Score(backend) => backend.nodes(),
+            SortitionBackend::Diverse(backend) => backend.nodes(),
+            SortitionBackend::Reputation(backend) => backend.nodes(),
         }
     }
 }
@@ -0,0 +1,481 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Offline committee selection audit.
+//!
+//! Answers *"why was (or wasn't) this node selected for E3 X?"* by replaying the events the
+//! [`Sortition`](crate::Sortition) actor builds its state from up to the `E3Requested` event and
+//! ranking the nodes with [`SelectionPolicy::rank`] under the policy the node selects with. The
+//! recomputed ranking is compared with the committee the chain published.
+
+use crate::domain::committee_selection::{candidates_from_state, SelectionPolicy};
+use crate::domain::node_registry::{NodeRegistry, NodeStateStore};
+use crate::domain::ticket::{RegisteredNode, WinnerTicket};
+use crate::domain::ticket_sortition::calculate_buffer_size;
+use alloy::primitives::{Address, U256};
+use anyhow::{anyhow, Result};
+use e3_events::{E3Stage, E3id, InterfoldEventData, Seed};
+use num_bigint::BigUint;
+use std::collections::HashMap;
+
+/// Rebuilds the sortition node state from events, mirroring the state transitions of the
+/// `Sortition` actor.
+#[derive(Clone, Debug, Default)]
+pub struct SortitionReplay {
+    state: HashMap<u64, NodeStateStore>,
+    /// Registered nodes per chain in registration order, like the sortition backends.
+    registered: HashMap<u64, Vec<String>>,
+}
+
+impl SortitionReplay {
+    pub fn apply(&mut self, event: &InterfoldEventData) {
+        let state = &mut self.state;
+        match event {
+            InterfoldEventData::CiphernodeAdded(d) => {
+                NodeRegistry::add_node(state, d.chain_id, d.address.clone());
+                let registered = self.registered.entry(d.chain_id).or_default();
+                if !registered.contains(&d.address) {
+                    registered.push(d.address.clone());
+                }
+            }
+            InterfoldEventData::CiphernodeRemoved(d) => {
+                NodeRegistry::remove_node(state, d.chain_id, &d.address);
+                if let Some(registered) = self.registered.get_mut(&d.chain_id) {
+                    registered.retain(|address| *address != d.address);
+                }
+            }
+            InterfoldEventData::TicketBalanceUpdated(d) => {
+                NodeRegistry::set_ticket_balance(
+                    state,
+                    d.chain_id,
+                    d.operator.clone(),
+                    d.new_balance,
+                );
+            }
+            InterfoldEventData::OperatorActivationChanged(d) => {
+                NodeRegistry::set_operator_active(state, d.operator.clone(), d.active);
+            }
+            InterfoldEventData::ConfigurationUpdated(d) if d.parameter == "ticketPrice" => {
+                NodeRegistry::set_ticket_price(state, d.chain_id, d.new_value);
+            }
+            InterfoldEventData::CommitteePublished(d) => {
+                NodeRegistry::record_committee_published(state, &d.e3_id, &d.nodes);
+            }
+            InterfoldEventData::SlashExecuted(d) => {
+                NodeRegistry::record_slash(state, d.e3_id.chain_id(), d.operator.to_string());
+            }
+            InterfoldEventData::CommitteeMemberExpelled(d) if d.party_id.is_none() => {
+                NodeRegistry::record_expulsion(state, d.e3_id.chain_id(), d.node.to_string());
+            }
+            InterfoldEventData::PlaintextOutputPublished(d) => {
+                NodeRegistry::release_committee_jobs(state, &d.e3_id, "PlaintextOutputPublished");
+            }
+            InterfoldEventData::E3Failed(d) => {
+                NodeRegistry::release_committee_jobs(state, &d.e3_id, "E3Failed");
+            }
+            InterfoldEventData::E3StageChanged(d)
+                if matches!(d.new_stage, E3Stage::Complete | E3Stage::Failed) =>
+            {
+                let reason = format!("E3StageChanged to {:?}", d.new_stage);
+                NodeRegistry::release_committee_jobs(state, &d.e3_id, &reason);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn state(&self) -> &HashMap<u64, NodeStateStore> {
+        &self.state
+    }
+
+    fn registered_nodes(&self, chain_id: u64) -> Vec<RegisteredNode> {
+        self.registered
+            .get(&chain_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|address| address.parse().ok())
+            .map(|address| RegisteredNode {
+                address,
+                tickets: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// A node as seen by sortition when the E3 was requested.
+#[derive(Clone, Debug)]
+pub struct AuditedNode {
+    pub address: String,
+    /// Registered in the `CiphernodeRegistry`.
+    pub registered: bool,
+    pub active: bool,
+    pub ticket_balance: U256,
+    /// Tickets left after subtracting the node's active jobs.
+    pub available_tickets: u64,
+    /// Best ticket and its score under the selection policy. `None` when the node was not
+    /// eligible.
+    pub best_ticket: Option<(u64, BigUint)>,
+    /// 0-based position among the nodes the policy admits, lowest score first.
+    pub rank: Option<usize>,
+    /// Whether the node is on the published committee.
+    pub published: bool,
+}
+
+/// A difference between the recomputed ranking and the published committee.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CommitteeMismatch {
+    /// A committee member that is not within the selection (threshold plus buffer).
+    NotSelected { address: String },
+    /// A node ranked within the first `threshold_n` that is not on the committee. Usually a node
+    /// that failed to submit its ticket in time.
+    NotPublished { address: String, rank: usize },
+}
+
+/// The recomputed committee selection of an E3.
+#[derive(Clone, Debug)]
+pub struct CommitteeAudit {
+    pub e3_id: E3id,
+    pub seed: Seed,
+    /// The policy the nodes were ranked under.
+    pub policy: SelectionPolicy,
+    pub threshold_m: usize,
+    pub threshold_n: usize,
+    /// Backup nodes selected on top of `threshold_n`, see [`calculate_buffer_size`].
+    pub buffer: usize,
+    pub ticket_price: U256,
+    /// Eligible nodes in rank order followed by every other known node.
+    pub nodes: Vec<AuditedNode>,
+    /// The committee from `CommitteePublished`, if it was published yet.
+    pub published: Option<Vec<String>>,
+}
+
+impl CommitteeAudit {
+    /// Number of nodes allowed to submit tickets.
+    pub fn selection_size(&self) -> usize {
+        self.threshold_n + self.buffer
+    }
+
+    pub fn node(&self, address: &str) -> Option<&AuditedNode> {
+        let address: Address = address.parse().ok()?;
+        self.nodes
+            .iter()
+            .find(|n| n.address.parse::<Address>().ok() == Some(address))
+    }
+
+    pub fn mismatches(&self) -> Vec<CommitteeMismatch> {
+        let Some(published) = &self.published else {
+            return Vec::new();
+        };
+        let mut mismatches: Vec<CommitteeMismatch> = published
+            .iter()
+            .filter(|address| {
+                self.node(address)
+                    .and_then(|n| n.rank)
+                    .is_none_or(|rank| rank >= self.selection_size())
+            })
+            .map(|address| CommitteeMismatch::NotSelected {
+                address: address.clone(),
+            })
+            .collect();
+        mismatches.extend(self.nodes.iter().filter_map(|n| match n.rank {
+            Some(rank) if rank < self.threshold_n && !n.published => {
+                Some(CommitteeMismatch::NotPublished {
+                    address: n.address.clone(),
+                    rank,
+                })
+            }
+            _ => None,
+        }));
+        mismatches
+    }
+}
+
+/// Replay `events` in order and recompute the committee selection of `e3_id` at request time
+/// under `policy`, with the operator `groups` the node declared.
+pub fn audit_committee<'a>(
+    events: impl IntoIterator<Item = &'a InterfoldEventData>,
+    e3_id: &E3id,
+    policy: &SelectionPolicy,
+    groups: &HashMap<Address, String>,
+) -> Result<CommitteeAudit> {
+    let chain_id = e3_id.chain_id();
+    let mut replay = SortitionReplay::default();
+    let mut requested = None;
+    let mut published = None;
+
+    for event in events {
+        match event {
+            InterfoldEventData::E3Requested(d) if d.e3_id == *e3_id && requested.is_none() => {
+                requested = Some((d.clone(), replay.clone()));
+            }
+            InterfoldEventData::CommitteePublished(d) if d.e3_id == *e3_id => {
+                published = Some(d.nodes.clone());
+            }
+            _ => {}
+        }
+        if requested.is_some() && published.is_some() {
+            break;
+        }
+        replay.apply(event);
+    }
+
+    let (request, mut snapshot) =
+        requested.ok_or_else(|| anyhow!("No E3Requested event found for E3 {}", e3_id))?;
+    NodeRegistry::set_operator_groups(&mut snapshot.state, groups);
+    let state = snapshot.state.get(&chain_id).cloned().unwrap_or_default();
+    let registered = snapshot.registered_nodes(chain_id);
+
+    let candidates = candidates_from_state(&registered, chain_id, &state);
+    let ranked = policy.rank(e3_id.clone(), request.seed, &candidates)?;
+
+    let published_addresses: Vec<Address> = published
+        .iter()
+        .flatten()
+        .filter_map(|address| address.parse().ok())
+        .collect();
+    let audited = |address: &str, rank: Option<usize>, best: Option<&WinnerTicket>| {
+        let node = state.nodes.get(address).cloned().unwrap_or_default();
+        let parsed = address.parse::<Address>().ok();
+        AuditedNode {
+            address: address.to_string(),
+            registered: registered.iter().any(|r| Some(r.address) == parsed),
+            active: node.active,
+            ticket_balance: node.ticket_balance,
+            available_tickets: state.available_tickets(address),
+            best_ticket: best.map(|w| (w.ticket_id, w.score.clone())),
+            rank,
+            published: parsed.is_some_and(|a| published_addresses.contains(&a)),
+        }
+    };
+
+    let mut nodes: Vec<AuditedNode> = ranked
+        .iter()
+        .enumerate()
+        .map(|(rank, w)| audited(&w.address.to_string(), Some(rank), Some(w)))
+        .collect();
+    let mut others: Vec<String> = state
+        .nodes
+        .keys()
+        .filter(|address| !ranked.iter().any(|w| w.address.to_string() == **address))
+        .cloned()
+        .collect();
+    others.sort();
+    nodes.extend(others.iter().map(|address| audited(address, None, None)));
+
+    Ok(CommitteeAudit {
+        e3_id: e3_id.clone(),
+        seed: request.seed,
+        policy: policy.clone(),
+        threshold_m: request.threshold_m,
+        threshold_n: request.threshold_n,
+        buffer: calculate_buffer_size(request.threshold_m, request.threshold_n),
+        ticket_price: state.ticket_price,
+        nodes,
+        published,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ticket_sortition::ScoreSortition;
+    use alloy::primitives::{keccak256, Uint};
+    use e3_events::{
+        CiphernodeAdded, CommitteePublished, ConfigurationUpdated, E3Requested,
+        OperatorActivationChanged, TicketBalanceUpdated,
+    };
+
+    fn address(i: u64) -> Address {
+        let h = keccak256([b"addr".as_slice(), &i.to_be_bytes()].concat());
+        Address::from_slice(&h.0[12..32])
+    }
+
+    fn e3_id() -> E3id {
+        E3id::new("7", 1)
+    }
+
+    fn seed() -> Seed {
+        Seed::from(Uint::from(0xA1B2_C3D4_E5F6_7789u64))
+    }
+
+    /// Ten registered nodes on chain 1 with 1 to 5 tickets each. Node 9 never activates.
+    fn setup_events() -> Vec<InterfoldEventData> {
+        let mut events = vec![InterfoldEventData::ConfigurationUpdated(
+            ConfigurationUpdated {
+                parameter: "ticketPrice".to_string(),
+                old_value: U256::ZERO,
+                new_value: U256::from(10),
+                chain_id: 1,
+            },
+        )];
+        for i in 0..10u64 {
+            let operator = address(i).to_string();
+            events.push(InterfoldEventData::CiphernodeAdded(CiphernodeAdded {
+                address: operator.clone(),
+                index: i as usize,
+                num_nodes: i as usize + 1,
+                chain_id: 1,
+            }));
+            events.push(InterfoldEventData::TicketBalanceUpdated(
+                TicketBalanceUpdated {
+                    operator: operator.clone(),
+                    delta: Default::default(),
+                    new_balance: U256::from(10 * (i % 5 + 1)),
+                    reason: Default::default(),
+                    chain_id: 1,
+                },
+            ));
+            if i != 9 {
+                events.push(InterfoldEventData::OperatorActivationChanged(
+                    OperatorActivationChanged {
+                        operator,
+                        active: true,
+                        chain_id: 1,
+                    },
+                ));
+            }
+        }
+        events
+    }
+
+    fn request() -> InterfoldEventData {
+        InterfoldEventData::E3Requested(E3Requested {
+            e3_id: e3_id(),
+            threshold_m: 2,
+            threshold_n: 3,
+            seed: seed(),
+            ..Default::default()
+        })
+    }
+
+    fn published(nodes: Vec<String>) -> InterfoldEventData {
+        InterfoldEventData::CommitteePublished(CommitteePublished {
+            e3_id: e3_id(),
+            nodes,
+            public_key: Default::default(),
+            proof: Default::default(),
+        })
+    }
+
+    fn ranked(audit: &CommitteeAudit) -> Vec<Address> {
+        audit
+            .nodes
+            .iter()
+            .filter(|n| n.rank.is_some())
+            .map(|n| n.address.parse().unwrap())
+            .collect()
+    }
+
+    fn expected_ranking() -> Vec<Address> {
+        let nodes: Vec<RegisteredNode> = (0..9u64)
+            .map(|i| RegisteredNode {
+                address: address(i),
+                tickets: (1..=i % 5 + 1)
+                    .map(|ticket_id| crate::Ticket { ticket_id })
+                    .collect(),
+            })
+            .collect();
+        ScoreSortition::new(nodes.len())
+            .get_committee(e3_id(), seed(), &nodes)
+            .unwrap()
+            .iter()
+            .map(|w| w.address)
+            .collect()
+    }
+
+    #[test]
+    fn ranks_nodes_like_score_sortition() -> Result<()> {
+        let expected = expected_ranking();
+        let committee: Vec<String> = expected[..3].iter().map(|a| a.to_string()).collect();
+        let mut events = setup_events();
+        events.push(request());
+        events.push(published(committee));
+
+        let audit = audit_committee(&events, &e3_id(), &SelectionPolicy::Score, &HashMap::new())?;
+
+        assert_eq!(audit.buffer, calculate_buffer_size(2, 3));
+        assert_eq!(audit.nodes.len(), 10);
+        assert_eq!(ranked(&audit), expected);
+
+        let inactive = audit.node(&address(9).to_string()).unwrap();
+        assert!(!inactive.active && inactive.registered);
+        assert_eq!(inactive.rank, None);
+        assert!(audit.mismatches().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn flags_mismatches_with_the_published_committee() -> Result<()> {
+        let expected = expected_ranking();
+        let mut events = setup_events();
+        events.push(request());
+        // The best node is replaced by the inactive one
+        events.push(published(vec![
+            address(9).to_string(),
+            expected[1].to_string(),
+            expected[2].to_string(),
+        ]));
+
+        let audit = audit_committee(&events, &e3_id(), &SelectionPolicy::Score, &HashMap::new())?;
+
+        assert_eq!(
+            audit.mismatches(),
+            vec![
+                CommitteeMismatch::NotSelected {
+                    address: address(9).to_string()
+                },
+                CommitteeMismatch::NotPublished {
+                    address: expected[0].to_string(),
+                    rank: 0
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ranks_nodes_under_the_configured_policy() -> Result<()> {
+        let mut events = setup_events();
+        events.push(request());
+        let eu: Vec<Address> = (0..9).step_by(2).map(address).collect();
+        let groups = eu.iter().map(|a| (*a, "eu".to_string())).collect();
+        let policy = SelectionPolicy::Diverse { max_per_group: 1 };
+
+        let audit = audit_committee(&events, &e3_id(), &policy, &groups)?;
+
+        // Only the best node of the capped group is ranked
+        let mut seen_eu = false;
+        let expected: Vec<Address> = expected_ranking()
+            .into_iter()
+            .filter(|a| !eu.contains(a) || !std::mem::replace(&mut seen_eu, true))
+            .collect();
+        assert_eq!(audit.policy, policy);
+        assert_eq!(ranked(&audit), expected);
+        assert_eq!(audit.nodes.len(), 10);
+        Ok(())
+    }
+
+    #[test]
+    fn uses_the_state_at_request_time() -> Result<()> {
+        let mut events = setup_events();
+        events.push(request());
+        // Deactivating a node after the request does not change the audit
+        events.push(InterfoldEventData::OperatorActivationChanged(
+            OperatorActivationChanged {
+                operator: address(0).to_string(),
+                active: false,
+                chain_id: 1,
+            },
+        ));
+
+        let (policy, groups) = (SelectionPolicy::Score, HashMap::new());
+        let audit = audit_committee(&events, &e3_id(), &policy, &groups)?;
+
+        assert!(audit.node(&address(0).to_string()).unwrap().active);
+        assert!(audit.published.is_none());
+        assert!(audit_committee(&events, &E3id::new("8", 1), &policy, &groups).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,462 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Committee selection policies.
+//!
+//! Every policy starts from the per-node best ticket of [`ScoreSortition`] and is a pure function
+//! of the E3 id, the `Seed` and the candidate set. Anyone holding the same inputs can recompute a
+//! committee with [`verify_committee`] to audit it.
+
+use crate::domain::node_registry::NodeStateStore;
+use crate::domain::ticket::{RegisteredNode, Ticket, WinnerTicket};
+use crate::domain::ticket_sortition::ScoreSortition;
+use alloy::primitives::Address;
+use anyhow::{bail, Result};
+use e3_events::{E3id, Seed};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Denominator of the reputation penalties, which are given in basis points.
+const BPS: u64 = 10_000;
+
+/// A node eligible for selection together with the attributes the policies use.
+#[derive(Clone, Debug)]
+pub struct Candidate {
+    pub node: RegisteredNode,
+    /// Declared operator group, see [`NodeState::group`](crate::NodeState::group).
+    pub group: Option<String>,
+    pub slashes: u64,
+    pub expulsions: u64,
+}
+
+/// Build the candidates of a chain: the registered nodes that are active and have at least one
+/// available ticket.
+pub fn candidates_from_state(
+    registered: &[RegisteredNode],
+    chain_id: u64,
+    node_state: &NodeStateStore,
+) -> Vec<Candidate> {
+    info!(
+        chain_id = chain_id,
+        registered_count = registered.len(),
+        node_state_count = node_state.nodes.len(),
+        "Building nodes from state for score sortition"
+    );
+
+    registered
+        .iter()
+        .filter_map(|n| {
+            let addr_str = n.address.to_string();
+            let Some(ns) = node_state.nodes.get(&addr_str) else {
+                info!(
+                    address = %addr_str,
+                    chain_id = chain_id,
+                    "Node not found in NodeStateStore"
+                );
+                return None;
+            };
+            if !ns.active {
+                info!(
+                    address = %addr_str,
+                    "Node is not active"
+                );
+                return None;
+            }
+
+            let count = node_state.available_tickets(&addr_str);
+            if count == 0 {
+                let total_tickets = if node_state.ticket_price.is_zero() {
+                    0u64
+                } else {
+                    (ns.ticket_balance / node_state.ticket_price)
+                        .try_into()
+                        .unwrap_or(0u64)
+                };
+                info!(
+                    address = %addr_str,
+                    ticket_balance = ?ns.ticket_balance,
+                    ticket_price = ?node_state.ticket_price,
+                    total_tickets = total_tickets,
+                    active_jobs = ns.active_jobs,
+                    "Node has no available tickets"
+                );
+                return None;
+            }
+
+            let tickets = (1..=count).map(|i| Ticket { ticket_id: i }).collect();
+            Some(Candidate {
+                node: RegisteredNode {
+                    address: n.address,
+                    tickets,
+                },
+                group: ns.group.clone(),
+                slashes: ns.slashes,
+                expulsions: ns.expulsions,
+            })
+        })
+        .collect()
+}
+
+/// Penalties applied to a node's ticket score per recorded incident, in basis points. A node with
+/// one slash under the default weights competes with a score 1.5 times as high as its ticket
+/// score. Lower scores win.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReputationWeights {
+    pub slash_penalty_bps: u64,
+    pub expulsion_penalty_bps: u64,
+}
+
+impl Default for ReputationWeights {
+    fn default() -> Self {
+        Self {
+            slash_penalty_bps: 5_000,
+            expulsion_penalty_bps: 2_500,
+        }
+    }
+}
+
+impl ReputationWeights {
+    /// Score multiplier of a candidate in basis points.
+    pub fn weight_bps(&self, candidate: &Candidate) -> u64 {
+        BPS.saturating_add(candidate.slashes.saturating_mul(self.slash_penalty_bps))
+            .saturating_add(
+                candidate
+                    .expulsions
+                    .saturating_mul(self.expulsion_penalty_bps),
+            )
+    }
+
+    fn weigh(&self, candidate: &Candidate, score: BigUint) -> BigUint {
+        score * self.weight_bps(candidate) / BPS
+    }
+}
+
+/// How a committee is drawn from the candidates.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelectionPolicy {
+    /// The `size` nodes with the lowest best-ticket score.
+    Score,
+    /// Like `Score` but at most `max_per_group` members may share a declared group. Nodes without
+    /// a group are not constrained. Selection fails when the constraint leaves fewer eligible
+    /// candidates than seats.
+    Diverse { max_per_group: usize },
+    /// Like `Score` with every node's score scaled by its slashing and expulsion history.
+    Reputation(ReputationWeights),
+}
+
+/// Sort ascending by `(score, ticket_id, address)`, the order used by [`ScoreSortition`].
+fn sort_winners(items: &mut [WinnerTicket]) {
+    items.sort_unstable_by(|a, b| {
+        a.score
+            .cmp(&b.score)
+            .then(a.ticket_id.cmp(&b.ticket_id))
+            .then(a.address.as_slice().cmp(b.address.as_slice()))
+    });
+}
+
+/// Number of candidates the `Diverse` policy can admit: up to `max_per_group` from every declared
+/// group plus every candidate without a group.
+fn diverse_capacity(max_per_group: usize, candidates: &[Candidate]) -> usize {
+    let mut groups: HashMap<&str, usize> = HashMap::new();
+    let mut ungrouped = 0;
+    for candidate in candidates {
+        match &candidate.group {
+            Some(group) => *groups.entry(group.as_str()).or_default() += 1,
+            None => ungrouped += 1,
+        }
+    }
+    groups
+        .values()
+        .map(|count| (*count).min(max_per_group))
+        .sum::<usize>()
+        + ungrouped
+}
+
+impl SelectionPolicy {
+    /// Select the committee of `size` members, in party order.
+    pub fn select(
+        &self,
+        e3_id: E3id,
+        seed: Seed,
+        size: usize,
+        candidates: &[Candidate],
+    ) -> Result<Vec<WinnerTicket>> {
+        if candidates.is_empty() || size == 0 {
+            return Ok(Vec::new());
+        }
+        if let SelectionPolicy::Diverse { max_per_group } = self {
+            let capacity = diverse_capacity(*max_per_group, candidates);
+            if capacity < size.min(candidates.len()) {
+                bail!(
+                    "Diverse selection admits at most {} of {} candidates with max_per_group {}, fewer than the committee size {}",
+                    capacity,
+                    candidates.len(),
+                    max_per_group,
+                    size
+                );
+            }
+        }
+        let mut committee = self.rank(e3_id, seed, candidates)?;
+        committee.truncate(size);
+        Ok(committee)
+    }
+
+    /// Rank every candidate the policy admits, best first. A committee of any size is a prefix
+    /// of this ranking. `Diverse` leaves out the candidates beyond their group's cap.
+    pub fn rank(
+        &self,
+        e3_id: E3id,
+        seed: Seed,
+        candidates: &[Candidate],
+    ) -> Result<Vec<WinnerTicket>> {
+        let nodes: Vec<RegisteredNode> = candidates.iter().map(|c| c.node.clone()).collect();
+        let ranked = ScoreSortition::new(nodes.len()).get_committee(e3_id, seed, &nodes)?;
+
+        match self {
+            SelectionPolicy::Score => Ok(ranked),
+            SelectionPolicy::Diverse { max_per_group } => {
+                let groups: HashMap<Address, &Option<String>> = candidates
+                    .iter()
+                    .map(|c| (c.node.address, &c.group))
+                    .collect();
+                let mut members: HashMap<&str, usize> = HashMap::new();
+                Ok(ranked
+                    .into_iter()
+                    .filter(|winner| {
+                        let Some(group) = groups.get(&winner.address).and_then(|g| g.as_ref())
+                        else {
+                            return true;
+                        };
+                        let count = members.entry(group.as_str()).or_default();
+                        if *count >= *max_per_group {
+                            return false;
+                        }
+                        *count += 1;
+                        true
+                    })
+                    .collect())
+            }
+            SelectionPolicy::Reputation(weights) => {
+                let candidates: HashMap<Address, &Candidate> =
+                    candidates.iter().map(|c| (c.node.address, c)).collect();
+                let mut weighted: Vec<WinnerTicket> = ranked
+                    .into_iter()
+                    .map(|winner| {
+                        let score = weights.weigh(candidates[&winner.address], winner.score);
+                        WinnerTicket { score, ..winner }
+                    })
+                    .collect();
+                sort_winners(&mut weighted);
+                Ok(weighted)
+            }
+        }
+    }
+}
+
+/// The committee recomputed from public inputs next to the committee that was claimed.
+#[derive(Clone, Debug)]
+pub struct CommitteeVerification {
+    pub expected: Vec<WinnerTicket>,
+    pub claimed: Vec<Address>,
+}
+
+impl CommitteeVerification {
+    /// The claimed committee has exactly the expected members in the expected order.
+    pub fn is_valid(&self) -> bool {
+        self.expected.len() == self.claimed.len()
+            && self
+                .expected
+                .iter()
+                .zip(&self.claimed)
+                .all(|(winner, claimed)| winner.address == *claimed)
+    }
+
+    /// Expected members missing from the claimed committee.
+    pub fn missing(&self) -> Vec<Address> {
+        self.expected
+            .iter()
+            .map(|w| w.address)
+            .filter(|address| !self.claimed.contains(address))
+            .collect()
+    }
+
+    /// Claimed members that should not be on the committee.
+    pub fn unexpected(&self) -> Vec<Address> {
+        self.claimed
+            .iter()
+            .filter(|address| !self.expected.iter().any(|w| w.address == **address))
+            .copied()
+            .collect()
+    }
+}
+
+/// Recompute the committee for `e3_id` under `policy` and compare it with `claimed`.
+pub fn verify_committee(
+    policy: &SelectionPolicy,
+    e3_id: E3id,
+    seed: Seed,
+    size: usize,
+    candidates: &[Candidate],
+    claimed: &[Address],
+) -> Result<CommitteeVerification> {
+    Ok(CommitteeVerification {
+        expected: policy.select(e3_id, seed, size, candidates)?,
+        claimed: claimed.to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{keccak256, Uint};
+
+    fn address(i: u64) -> Address {
+        let h = keccak256([b"addr".as_slice(), &i.to_be_bytes()].concat());
+        Address::from_slice(&h.0[12..32])
+    }
+
+    fn candidate(i: u64, group: Option<&str>, slashes: u64) -> Candidate {
+        Candidate {
+            node: RegisteredNode {
+                address: address(i),
+                tickets: (1..=3).map(|ticket_id| Ticket { ticket_id }).collect(),
+            },
+            group: group.map(String::from),
+            slashes,
+            expulsions: 0,
+        }
+    }
+
+    fn inputs() -> (E3id, Seed) {
+        (
+            E3id::new("42", 42),
+            Seed::from(Uint::from(0xA1B2_C3D4_E5F6_7789u64)),
+        )
+    }
+
+    fn addresses(winners: &[WinnerTicket]) -> Vec<Address> {
+        winners.iter().map(|w| w.address).collect()
+    }
+
+    #[test]
+    fn diverse_caps_members_per_group() -> Result<()> {
+        let (e3_id, seed) = inputs();
+        let candidates: Vec<Candidate> = (0..10)
+            .map(|i| candidate(i, Some(if i % 3 == 0 { "eu" } else { "us" }), 0))
+            .chain([candidate(10, None, 0)])
+            .collect();
+
+        let unconstrained = SelectionPolicy::Score.select(e3_id.clone(), seed, 11, &candidates)?;
+        let committee = SelectionPolicy::Diverse { max_per_group: 2 }.select(
+            e3_id.clone(),
+            seed,
+            5,
+            &candidates,
+        )?;
+
+        // Two per group plus the ungrouped node is all the constraint allows
+        assert_eq!(committee.len(), 5);
+        let group_of = |a: &Address| candidates.iter().find(|c| c.node.address == *a).unwrap();
+        for group in ["eu", "us"] {
+            let count = committee
+                .iter()
+                .filter(|w| group_of(&w.address).group.as_deref() == Some(group))
+                .count();
+            assert_eq!(count, 2);
+        }
+        // Members keep their relative score order
+        let ranks: Vec<usize> = committee
+            .iter()
+            .map(|w| {
+                unconstrained
+                    .iter()
+                    .position(|u| u.address == w.address)
+                    .unwrap()
+            })
+            .collect();
+        assert!(ranks.windows(2).all(|r| r[0] < r[1]));
+        Ok(())
+    }
+
+    #[test]
+    fn diverse_rejects_committees_the_groups_cannot_fill() -> Result<()> {
+        let (e3_id, seed) = inputs();
+        // Two groups capped at two members each plus one ungrouped node fill at most five seats
+        let candidates: Vec<Candidate> = (0..10)
+            .map(|i| candidate(i, Some(if i % 3 == 0 { "eu" } else { "us" }), 0))
+            .chain([candidate(10, None, 0)])
+            .collect();
+        let policy = SelectionPolicy::Diverse { max_per_group: 2 };
+
+        assert_eq!(diverse_capacity(2, &candidates), 5);
+        assert!(policy.select(e3_id.clone(), seed, 6, &candidates).is_err());
+        assert_eq!(policy.select(e3_id, seed, 5, &candidates)?.len(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn reputation_penalises_slashed_nodes() -> Result<()> {
+        let (e3_id, seed) = inputs();
+        let clean: Vec<Candidate> = (0..6).map(|i| candidate(i, None, 0)).collect();
+        let best = SelectionPolicy::Score.select(e3_id.clone(), seed, 1, &clean)?[0].address;
+
+        let penalised: Vec<Candidate> = clean
+            .iter()
+            .cloned()
+            .map(|mut c| {
+                if c.node.address == best {
+                    c.slashes = u64::MAX;
+                }
+                c
+            })
+            .collect();
+        let weights = SelectionPolicy::Reputation(ReputationWeights::default());
+
+        // Without any history the reputation policy matches score sortition
+        assert_eq!(
+            addresses(&weights.select(e3_id.clone(), seed, 6, &clean)?),
+            addresses(&SelectionPolicy::Score.select(e3_id.clone(), seed, 6, &clean)?)
+        );
+        let committee = weights.select(e3_id, seed, 6, &penalised)?;
+        assert_eq!(committee.last().unwrap().address, best);
+        Ok(())
+    }
+
+    #[test]
+    fn verification_recomputes_the_committee() -> Result<()> {
+        let (e3_id, seed) = inputs();
+        let candidates: Vec<Candidate> = (0..6)
+            .map(|i| candidate(i, Some(if i < 3 { "a" } else { "b" }), i % 2))
+            .collect();
+        let policies = [
+            SelectionPolicy::Score,
+            SelectionPolicy::Diverse { max_per_group: 1 },
+            SelectionPolicy::Reputation(ReputationWeights::default()),
+        ];
+
+        for policy in policies {
+            let committee = addresses(&policy.select(e3_id.clone(), seed, 2, &candidates)?);
+            let valid = verify_committee(&policy, e3_id.clone(), seed, 2, &candidates, &committee)?;
+            assert!(valid.is_valid());
+
+            let mut reordered = committee.clone();
+            reordered.reverse();
+            let invalid =
+                verify_committee(&policy, e3_id.clone(), seed, 2, &candidates, &reordered)?;
+            assert!(!invalid.is_valid());
+            assert!(invalid.missing().is_empty());
+
+            let outsider = address(99);
+            let forged = vec![committee[0], outsider];
+            let invalid = verify_committee(&policy, e3_id.clone(), seed, 2, &candidates, &forged)?;
+            assert_eq!(invalid.missing(), vec![committee[1]]);
+            assert_eq!(invalid.unexpected(), vec![outsider]);
+        }
+        Ok(())
+    }
+}
@@ -5,11 +5,15 @@
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
 pub mod backends;
+pub mod committee_audit;
+pub mod committee_selection;
 pub mod node_registry;
 pub mod ticket;
 pub mod ticket_sortition;
 
 pub use backends::*;
+pub use committee_audit::*;
+pub use committee_selection::*;
 pub use node_registry::*;
 pub use ticket::*;
 pub use ticket_sortition::*;
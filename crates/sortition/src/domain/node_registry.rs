@@ -13,7 +13,7 @@
 //! is a thin shell that loads persisted state, calls into [`NodeRegistry`], and
 //! writes the result back.
 
-use alloy::primitives::U256;
+use alloy::primitives::{Address, U256};
 use e3_events::E3id;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -28,6 +28,16 @@ pub struct NodeState {
     pub active_jobs: u64,
     /// Whether this node is active (has met minimum requirements).
     pub active: bool,
+    /// Declared operator group (eg. a region). Diversity constrained sortition caps the committee
+    /// members sharing a group.
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Number of slashes executed on-chain against this node.
+    #[serde(default)]
+    pub slashes: u64,
+    /// Number of times this node was expelled from a committee on-chain after an accusation.
+    #[serde(default)]
+    pub expulsions: u64,
 }
 
 impl Default for NodeState {
@@ -36,6 +46,9 @@ impl Default for NodeState {
             ticket_balance: U256::ZERO,
             active_jobs: 0,
             active: false,
+            group: None,
+            slashes: 0,
+            expulsions: 0,
         }
     }
 }
@@ -75,6 +88,14 @@ impl NodeStateStore {
         total_tickets.saturating_sub(node.active_jobs)
     }
 
+    /// Addresses of the nodes that are currently bonded and active, regardless of free tickets.
+    pub fn bonded_operators(&self) -> impl Iterator<Item = &String> {
+        self.nodes
+            .iter()
+            .filter(|(_, node_state)| node_state.active)
+            .map(|(addr, _)| addr)
+    }
+
     /// Get all active nodes that currently have at least one available ticket.
     pub fn get_nodes_with_tickets(&self) -> Vec<(String, u64)> {
         self.nodes
@@ -94,6 +115,15 @@ pub fn committee_key(e3_id: &E3id) -> String {
     format!("{}:{}", e3_id.chain_id(), e3_id.e3_id())
 }
 
+/// The state of a node the chain has registered, if any.
+fn registered_node<'a>(
+    store: &'a mut HashMap<u64, NodeStateStore>,
+    chain_id: u64,
+    operator: &str,
+) -> Option<&'a mut NodeState> {
+    store.get_mut(&chain_id)?.nodes.get_mut(operator)
+}
+
 /// Pure transition logic over the per-chain [`NodeStateStore`] map.
 ///
 /// Every method takes the full `chain_id -> NodeStateStore` map by mutable
@@ -153,6 +183,66 @@ impl NodeRegistry {
         }
     }
 
+    /// Apply the declared operator groups to every node on every chain. Nodes missing from
+    /// `groups` have no group.
+    pub fn set_operator_groups(
+        store: &mut HashMap<u64, NodeStateStore>,
+        groups: &HashMap<Address, String>,
+    ) {
+        for chain_state in store.values_mut() {
+            for (address, node) in chain_state.nodes.iter_mut() {
+                node.group = address
+                    .parse::<Address>()
+                    .ok()
+                    .and_then(|address| groups.get(&address).cloned());
+            }
+        }
+    }
+
+    /// Record a slash executed against an operator on a chain.
+    /// Operators the chain has not registered are skipped.
+    pub fn record_slash(store: &mut HashMap<u64, NodeStateStore>, chain_id: u64, operator: String) {
+        let Some(node) = registered_node(store, chain_id, &operator) else {
+            warn!(
+                operator = %operator,
+                chain_id = chain_id,
+                "Ignoring slash against unknown operator"
+            );
+            return;
+        };
+        node.slashes += 1;
+        info!(
+            operator = %operator,
+            chain_id = chain_id,
+            slashes = node.slashes,
+            "Recorded slash against operator"
+        );
+    }
+
+    /// Record the expulsion of an operator from a committee on a chain. Operators the chain has
+    /// not registered are skipped.
+    pub fn record_expulsion(
+        store: &mut HashMap<u64, NodeStateStore>,
+        chain_id: u64,
+        operator: String,
+    ) {
+        let Some(node) = registered_node(store, chain_id, &operator) else {
+            warn!(
+                operator = %operator,
+                chain_id = chain_id,
+                "Ignoring expulsion of unknown operator"
+            );
+            return;
+        };
+        node.expulsions += 1;
+        info!(
+            operator = %operator,
+            chain_id = chain_id,
+            expulsions = node.expulsions,
+            "Recorded committee expulsion of operator"
+        );
+    }
+
     /// Set the ticket price for a chain.
     pub fn set_ticket_price(
         store: &mut HashMap<u64, NodeStateStore>,
@@ -317,6 +407,47 @@ mod tests {
         assert_eq!(store[&1].nodes["0xabc"].active_jobs, 1);
     }
 
+    #[test]
+    fn bonded_operators_are_the_active_nodes() {
+        let mut store = HashMap::new();
+        NodeRegistry::add_node(&mut store, 1, "0xabc".into());
+        NodeRegistry::add_node(&mut store, 1, "0xdef".into());
+        NodeRegistry::set_operator_active(&mut store, "0xabc".into(), true);
+
+        let bonded: Vec<&String> = store[&1].bonded_operators().collect();
+        assert_eq!(bonded, vec!["0xabc"]);
+    }
+
+    #[test]
+    fn slashes_expulsions_and_groups_are_recorded() {
+        let eu = Address::repeat_byte(1);
+        let undeclared = Address::repeat_byte(2);
+        let mut store = HashMap::new();
+        for chain_id in [1, 2] {
+            NodeRegistry::add_node(&mut store, chain_id, eu.to_string());
+            NodeRegistry::add_node(&mut store, chain_id, undeclared.to_string());
+        }
+        NodeRegistry::record_slash(&mut store, 1, eu.to_string());
+        NodeRegistry::record_expulsion(&mut store, 1, eu.to_string());
+        NodeRegistry::record_expulsion(&mut store, 1, eu.to_string());
+        NodeRegistry::set_operator_groups(&mut store, &HashMap::from([(eu, "eu".to_string())]));
+        // Incidents of operators the chain never registered do not create node state
+        let unknown = Address::repeat_byte(3).to_string();
+        NodeRegistry::record_slash(&mut store, 1, unknown.clone());
+        NodeRegistry::record_expulsion(&mut store, 3, unknown.clone());
+        assert!(!store[&1].nodes.contains_key(&unknown));
+        assert!(!store.contains_key(&3));
+
+        let node = &store[&1].nodes[&eu.to_string()];
+        assert_eq!((node.slashes, node.expulsions), (1, 2));
+        assert_eq!(store[&2].nodes[&eu.to_string()].slashes, 0);
+        for chain_id in [1, 2] {
+            let nodes = &store[&chain_id].nodes;
+            assert_eq!(nodes[&eu.to_string()].group.as_deref(), Some("eu"));
+            assert_eq!(nodes[&undeclared.to_string()].group, None);
+        }
+    }
+
     #[test]
     fn zero_price_yields_no_tickets() {
         let mut store = HashMap::new();
@@ -6,6 +6,7 @@
 
 use actix::prelude::*;
 use e3_events::E3id;
+use std::collections::HashSet;
 use std::ops::Deref;
 
 #[derive(Message, Clone, Debug, PartialEq, Eq)]
@@ -129,3 +130,8 @@ pub struct CommitteeMembersResponse {
     /// `None` when the E3 committee is not finalized in sortition yet.
     pub members: Option<Vec<String>>,
 }
+
+/// Request the addresses of all bonded, active operators across every chain.
+#[derive(Message, Clone, Debug)]
+#[rtype(result = "HashSet<String>")]
+pub struct GetBondedOperators;
@@ -320,8 +320,9 @@ async fn boundless_prove_inner(input: &ComputeInput) -> Result<BoundlessOutput>
         }
     };
 
-    let decoded_journal: ComputeResult = risc0_zkvm::serde::from_slice(&journal)
+    let mut decoded_journal: ComputeResult = risc0_zkvm::serde::from_slice(&journal)
         .map_err(|e| anyhow::anyhow!("Failed to decode journal: {}", e))?;
+    decoded_journal.input_validation = input.input_validation.clone();
 
     Ok(BoundlessOutput::Success {
         result: decoded_journal,
@@ -359,7 +360,8 @@ impl ComputeProvider for Risc0Provider {
             .unwrap()
             .receipt;
 
-        let decoded_journal = receipt.journal.decode().unwrap();
+        let mut decoded_journal: ComputeResult = receipt.journal.decode().unwrap();
+        decoded_journal.input_validation = input.input_validation.clone();
 
         // Check if RISC0_DEV_MODE is set to "1" (dev mode)
         // If dev mode: return empty seal (fake proof)
@@ -9,6 +9,7 @@ pub mod ciphernode_system;
 pub mod libp2p_mock;
 mod plaintext_writer;
 mod public_key_writer;
+pub mod sim;
 pub mod usecase_helpers;
 mod utils;
 use actix::prelude::*;
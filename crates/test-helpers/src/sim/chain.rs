@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use super::VirtualClock;
+use anyhow::Result;
+use e3_ciphernode_builder::EventSystem;
+use e3_events::{
+    prelude::*, BusHandle, CiphertextOutputPublished, Committee, CommitteeFinalized,
+    CommitteePublished, E3Requested, E3id, PlaintextAggregated, PlaintextOutputPublished,
+    PublicKeyAggregated,
+};
+use e3_utils::ArcBytes;
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::info;
+
+/// Observable on-chain progress of an E3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChainMilestone {
+    E3Requested,
+    CommitteeFinalized,
+    /// The aggregated public key was accepted by the registry (`CommitteePublished`).
+    PublicKeyPublished,
+    CiphertextPublished,
+    /// The aggregated plaintext was accepted (`PlaintextOutputPublished`).
+    PlaintextPublished,
+}
+
+/// One accepted transaction on the stubbed chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainRecord {
+    /// Virtual time at which the transaction was accepted.
+    pub at: Duration,
+    pub e3_id: E3id,
+    pub milestone: ChainMilestone,
+    /// Index of the node whose submission was accepted, for aggregator submissions.
+    pub by: Option<usize>,
+}
+
+/// A stubbed EVM chain for the simulator.
+///
+/// Owns the "trigger" bus that every simulated node forks from, so publishing a chain event
+/// here is equivalent to the EVM readers of every node observing it. Aggregator results are
+/// submitted by the simulation and accepted single-shot per E3, mirroring the contracts: the
+/// first `PublicKeyAggregated` and `PlaintextAggregated` win and later submissions (for
+/// example from a promoted standby overlapping a slow aggregator) are ignored.
+pub struct SimChain {
+    chain_id: u64,
+    clock: VirtualClock,
+    bus: BusHandle,
+    _system: EventSystem,
+    committees: HashMap<E3id, Committee>,
+    public_keys: HashMap<E3id, PublicKeyAggregated>,
+    plaintexts: HashMap<E3id, PlaintextAggregated>,
+    log: Vec<ChainRecord>,
+}
+
+impl SimChain {
+    pub fn new(chain_id: u64, clock: &VirtualClock) -> Result<Self> {
+        let system = EventSystem::new().with_fresh_bus();
+        let bus = system.handle()?.enable("sim-chain");
+        Ok(Self {
+            chain_id,
+            clock: clock.clone(),
+            bus,
+            _system: system,
+            committees: HashMap::new(),
+            public_keys: HashMap::new(),
+            plaintexts: HashMap::new(),
+            log: Vec::new(),
+        })
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    pub fn clock(&self) -> &VirtualClock {
+        &self.clock
+    }
+
+    /// The bus nodes should fork from (`CiphernodeBuilder::with_forked_bus`).
+    pub fn bus(&self) -> &BusHandle {
+        &self.bus
+    }
+
+    pub fn request_e3(&mut self, event: E3Requested) -> Result<()> {
+        let e3_id = event.e3_id.clone();
+        self.bus.publish_without_context(event)?;
+        self.record(e3_id, ChainMilestone::E3Requested, None);
+        Ok(())
+    }
+
+    pub fn finalize_committee(&mut self, event: CommitteeFinalized) -> Result<()> {
+        let e3_id = event.e3_id.clone();
+        // Party ids follow the same score ordering the CiphernodeSelector applies.
+        let mut sorted = event.clone();
+        sorted.sort_by_score();
+        self.committees
+            .insert(e3_id.clone(), Committee::new(sorted.committee));
+        self.bus.publish_without_context(event)?;
+        self.record(e3_id, ChainMilestone::CommitteeFinalized, None);
+        Ok(())
+    }
+
+    pub fn publish_ciphertext_output(&mut self, event: CiphertextOutputPublished) -> Result<()> {
+        let e3_id = event.e3_id.clone();
+        self.bus.publish_without_context(event)?;
+        self.record(e3_id, ChainMilestone::CiphertextPublished, None);
+        Ok(())
+    }
+
+    /// Submit an aggregated public key on behalf of node `by`. Returns `false` if a key was
+    /// already accepted for this E3.
+    pub fn submit_public_key(&mut self, by: usize, event: PublicKeyAggregated) -> Result<bool> {
+        if self.public_keys.contains_key(&event.e3_id) {
+            return Ok(false);
+        }
+        info!(e3_id = %event.e3_id, by, "SimChain accepted public key");
+        let e3_id = event.e3_id.clone();
+        self.bus.publish_without_context(CommitteePublished {
+            e3_id: e3_id.clone(),
+            nodes: event
+                .committee_addresses
+                .iter()
+                .map(|addr| addr.to_string())
+                .collect(),
+            public_key: event.pubkey.clone(),
+            proof: ArcBytes::from_bytes(&[]),
+        })?;
+        self.public_keys.insert(e3_id.clone(), event);
+        self.record(e3_id, ChainMilestone::PublicKeyPublished, Some(by));
+        Ok(true)
+    }
+
+    /// Submit an aggregated plaintext on behalf of node `by`. Returns `false` if a plaintext
+    /// was already accepted for this E3.
+    pub fn submit_plaintext(&mut self, by: usize, event: PlaintextAggregated) -> Result<bool> {
+        if self.plaintexts.contains_key(&event.e3_id) {
+            return Ok(false);
+        }
+        info!(e3_id = %event.e3_id, by, "SimChain accepted plaintext");
+        let e3_id = event.e3_id.clone();
+        self.bus.publish_without_context(PlaintextOutputPublished {
+            e3_id: e3_id.clone(),
+            plaintext_output: event
+                .decrypted_output
+                .first()
+                .cloned()
+                .unwrap_or_else(|| ArcBytes::from_bytes(&[])),
+            proof: ArcBytes::from_bytes(&[]),
+        })?;
+        self.plaintexts.insert(e3_id.clone(), event);
+        self.record(e3_id, ChainMilestone::PlaintextPublished, Some(by));
+        Ok(true)
+    }
+
+    /// Committee in party-id order, once finalized.
+    pub fn committee(&self, e3_id: &E3id) -> Option<&Committee> {
+        self.committees.get(e3_id)
+    }
+
+    pub fn public_key(&self, e3_id: &E3id) -> Option<&PublicKeyAggregated> {
+        self.public_keys.get(e3_id)
+    }
+
+    pub fn plaintext(&self, e3_id: &E3id) -> Option<&PlaintextAggregated> {
+        self.plaintexts.get(e3_id)
+    }
+
+    /// First accepted record for a milestone of an E3.
+    pub fn milestone(&self, e3_id: &E3id, milestone: ChainMilestone) -> Option<&ChainRecord> {
+        self.log
+            .iter()
+            .find(|r| &r.e3_id == e3_id && r.milestone == milestone)
+    }
+
+    /// Every accepted transaction in acceptance order.
+    pub fn log(&self) -> &[ChainRecord] {
+        &self.log
+    }
+
+    fn record(&mut self, e3_id: E3id, milestone: ChainMilestone, by: Option<usize>) {
+        self.log.push(ChainRecord {
+            at: self.clock.now(),
+            e3_id,
+            milestone,
+            by,
+        });
+    }
+}
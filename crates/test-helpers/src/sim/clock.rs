@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A shared, manually advanced clock. Time only moves when the simulation calls
+/// [`VirtualClock::advance`], so message delays, fault triggers and failover
+/// timeouts are all expressed against the same deterministic timeline.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualClock {
+    now: Arc<Mutex<Duration>>,
+}
+
+impl VirtualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current virtual time since the start of the simulation.
+    pub fn now(&self) -> Duration {
+        *self.now.lock().expect("virtual clock poisoned")
+    }
+
+    /// Move the clock forward and return the new time.
+    pub fn advance(&self, by: Duration) -> Duration {
+        let mut now = self.now.lock().expect("virtual clock poisoned");
+        *now += by;
+        *now
+    }
+
+    /// Move the clock forward to `to` if it lies in the future. Never moves backwards.
+    pub fn advance_to(&self, to: Duration) -> Duration {
+        let mut now = self.now.lock().expect("virtual clock poisoned");
+        if to > *now {
+            *now = to;
+        }
+        *now
+    }
+}
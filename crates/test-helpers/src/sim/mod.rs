@@ -0,0 +1,437 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Deterministic multi-node simulator.
+//!
+//! Runs whole E3 rounds for N in-process ciphernodes against:
+//! - a [`VirtualClock`] that only advances when the simulation steps,
+//! - a [`SimNetwork`] that delivers gossip according to per-link delays, a seeded drop rate,
+//!   partitions and killed nodes,
+//! - a [`SimChain`] stub that plays the EVM: it owns the bus nodes fork from, accepts
+//!   aggregator results single-shot and emits the corresponding on-chain events.
+//!
+//! On top of that the simulation stands in for every node's `FailoverMonitor`: it drives a
+//! [`e3_aggregator::failover::FailoverTracker`] from virtual time and, when the active
+//! aggregator misses its deadline, publishes `AggregatorPresumedUnresponsive` to every live
+//! node so their `CiphernodeSelector`s promote the next standby. Simulated nodes must therefore
+//! be built without `with_aggregator_failover`. Faults are scripted with
+//! [`SimFault`], e.g. killing the active aggregator once the public key is on-chain.
+//!
+//! ```ignore
+//! let clock = VirtualClock::new();
+//! let chain = SimChain::new(1, &clock)?;
+//! let nodes = CiphernodeSystemBuilder::new()
+//!     .add_group(5, || async { builder().with_forked_bus(chain.bus().event_bus()).build().await })
+//!     .build()
+//!     .await?;
+//! let mut sim = Simulation::new(SimConfig::default(), chain, nodes)?;
+//! sim.schedule(SimFault::after(&e3_id, ChainMilestone::PublicKeyPublished, SimAction::KillActiveAggregator(e3_id.clone())));
+//! sim.chain_mut().request_e3(e3_requested)?;
+//! ```
+//!
+//! Virtual time is decoupled from the real time nodes spend computing: each step waits a
+//! short real `settle` period for actors to make progress and then advances the clock by one
+//! `tick`. Pick failover timeouts with enough ticks of headroom for the slowest honest phase.
+
+mod chain;
+mod clock;
+mod network;
+
+pub use chain::*;
+pub use clock::*;
+pub use network::*;
+
+use crate::ciphernode_system::CiphernodeSystem;
+use actix::prelude::*;
+use anyhow::{anyhow, bail, Result};
+use e3_aggregator::failover::{FailoverDecision, FailoverPolicy, FailoverTracker};
+use e3_events::{
+    prelude::*, AggregatorPresumedUnresponsive, E3id, EventSource, EventType, InterfoldEvent,
+    InterfoldEventData, Shutdown,
+};
+use e3_utils::MAILBOX_LIMIT;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::info;
+
+/// Simulation parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct SimConfig {
+    /// Seed for every random decision the simulator makes (currently message drops).
+    pub seed: u64,
+    /// Virtual time added per step.
+    pub tick: Duration,
+    /// Real time each step waits for actors to process what was delivered.
+    pub settle: Duration,
+    /// How long the active aggregator may fail to move its phase on-chain before the next
+    /// standby is promoted.
+    pub failover_timeout: Duration,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            tick: Duration::from_secs(1),
+            settle: Duration::from_millis(50),
+            failover_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// When a scripted fault fires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimTrigger {
+    /// Once the virtual clock reaches the given time.
+    At(Duration),
+    /// As soon as the chain accepts the milestone for the E3.
+    Milestone {
+        e3_id: E3id,
+        milestone: ChainMilestone,
+    },
+}
+
+/// What a scripted fault does.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimAction {
+    /// Crash the node at the given index: disconnect it and shut its actors down.
+    Kill(usize),
+    /// Crash whichever node is currently the active aggregator for the E3.
+    KillActiveAggregator(E3id),
+    /// See [`SimNetwork::partition`].
+    Partition(Vec<Vec<usize>>),
+    Heal,
+    SetDropRate(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimFault {
+    pub trigger: SimTrigger,
+    pub action: SimAction,
+}
+
+impl SimFault {
+    pub fn at(time: Duration, action: SimAction) -> Self {
+        Self {
+            trigger: SimTrigger::At(time),
+            action,
+        }
+    }
+
+    pub fn after(e3_id: &E3id, milestone: ChainMilestone, action: SimAction) -> Self {
+        Self {
+            trigger: SimTrigger::Milestone {
+                e3_id: e3_id.clone(),
+                milestone,
+            },
+            action,
+        }
+    }
+}
+
+/// A failover decision taken by the simulated monitor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailoverRecord {
+    pub at: Duration,
+    pub e3_id: E3id,
+    pub decision: FailoverDecision,
+}
+
+/// Forwards aggregator results seen on a node's bus to the simulation, which submits them to
+/// the stubbed chain on the node's behalf.
+struct ChainSubmitter {
+    node: usize,
+    tx: mpsc::UnboundedSender<(usize, InterfoldEventData)>,
+}
+
+impl Actor for ChainSubmitter {
+    type Context = Context<Self>;
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.set_mailbox_capacity(MAILBOX_LIMIT);
+    }
+}
+
+impl Handler<InterfoldEvent> for ChainSubmitter {
+    type Result = ();
+    fn handle(&mut self, msg: InterfoldEvent, _: &mut Self::Context) -> Self::Result {
+        // Only the node that produced a result submits it, as its EVM writer would; copies
+        // received over gossip are ignored.
+        if msg.source() != EventSource::Local {
+            return;
+        }
+        let _ = self.tx.send((self.node, msg.into_data()));
+    }
+}
+
+pub struct Simulation {
+    config: SimConfig,
+    clock: VirtualClock,
+    net: SimNetwork,
+    chain: SimChain,
+    nodes: CiphernodeSystem,
+    submissions: mpsc::UnboundedReceiver<(usize, InterfoldEventData)>,
+    faults: Vec<SimFault>,
+    chain_cursor: usize,
+    tracker: FailoverTracker,
+    failovers: Vec<FailoverRecord>,
+}
+
+impl Simulation {
+    /// Wire `nodes` to a simulated network and to `chain`. Nodes must have been built with a
+    /// channel-bridge network interface and forked from [`SimChain::bus`].
+    pub fn new(config: SimConfig, chain: SimChain, nodes: CiphernodeSystem) -> Result<Self> {
+        let clock = chain.clock().clone();
+        let net = SimNetwork::new(config.seed, &clock);
+        let (tx, submissions) = mpsc::unbounded_channel();
+
+        for node in nodes.iter() {
+            let index = net.add_node(node.channel_bridge()?);
+            let submitter = ChainSubmitter {
+                node: index,
+                tx: tx.clone(),
+            }
+            .start();
+            node.bus().subscribe_all(
+                &[
+                    EventType::PublicKeyAggregated,
+                    EventType::PlaintextAggregated,
+                ],
+                submitter.recipient(),
+            );
+        }
+
+        Ok(Self {
+            config,
+            clock,
+            net,
+            chain,
+            nodes,
+            submissions,
+            faults: Vec::new(),
+            chain_cursor: 0,
+            tracker: FailoverTracker::new(),
+            failovers: Vec::new(),
+        })
+    }
+
+    pub fn clock(&self) -> &VirtualClock {
+        &self.clock
+    }
+
+    pub fn net(&self) -> &SimNetwork {
+        &self.net
+    }
+
+    pub fn chain(&self) -> &SimChain {
+        &self.chain
+    }
+
+    pub fn chain_mut(&mut self) -> &mut SimChain {
+        &mut self.chain
+    }
+
+    pub fn nodes(&self) -> &CiphernodeSystem {
+        &self.nodes
+    }
+
+    /// Every failover decision other than `Hold`, in the order they were taken.
+    pub fn failovers(&self) -> &[FailoverRecord] {
+        &self.failovers
+    }
+
+    pub fn node_index(&self, address: &str) -> Option<usize> {
+        self.nodes
+            .iter()
+            .position(|node| node.address().eq_ignore_ascii_case(address))
+    }
+
+    /// Address of the node the monitor currently expects to aggregate for the E3.
+    pub fn active_aggregator(&self, e3_id: &E3id) -> Option<String> {
+        self.tracker.active_aggregator(e3_id)
+    }
+
+    /// Add a scripted fault. Faults fire at most once, during the step in which their
+    /// trigger is first satisfied.
+    pub fn schedule(&mut self, fault: SimFault) {
+        self.faults.push(fault);
+    }
+
+    /// Advance the simulation by one tick.
+    pub async fn step(&mut self) -> Result<()> {
+        tokio::time::sleep(self.config.settle).await;
+        self.net.route();
+        self.net.deliver_due();
+        self.submit_results()?;
+        self.apply_chain_progress()?;
+        self.apply_timed_faults()?;
+        self.check_failover()?;
+        self.clock.advance(self.config.tick);
+        Ok(())
+    }
+
+    /// Step until `done` holds, failing once `limit` of virtual time has passed.
+    /// Returns the virtual time at which the condition was met.
+    pub async fn run_until<F>(&mut self, limit: Duration, mut done: F) -> Result<Duration>
+    where
+        F: FnMut(&Self) -> bool,
+    {
+        loop {
+            if done(self) {
+                return Ok(self.clock.now());
+            }
+            if self.clock.now() >= limit {
+                bail!(
+                    "Simulation did not reach its goal within {:?} of virtual time",
+                    limit
+                );
+            }
+            self.step().await?;
+        }
+    }
+
+    fn submit_results(&mut self) -> Result<()> {
+        while let Ok((node, data)) = self.submissions.try_recv() {
+            // A killed node's actors are shut down, but results it produced before the crash
+            // may still be queued; they never reached the chain.
+            if self.net.is_killed(node) {
+                continue;
+            }
+            match data {
+                InterfoldEventData::PublicKeyAggregated(event) => {
+                    self.chain.submit_public_key(node, event)?;
+                }
+                InterfoldEventData::PlaintextAggregated(event) => {
+                    self.chain.submit_plaintext(node, event)?;
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_chain_progress(&mut self) -> Result<()> {
+        let records = self.chain.log()[self.chain_cursor..].to_vec();
+        self.chain_cursor += records.len();
+
+        for record in records {
+            self.update_watch(&record)?;
+
+            let (due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.faults)
+                .into_iter()
+                .partition(|fault| match &fault.trigger {
+                    SimTrigger::Milestone { e3_id, milestone } => {
+                        e3_id == &record.e3_id && milestone == &record.milestone
+                    }
+                    SimTrigger::At(_) => false,
+                });
+            self.faults = pending;
+            for fault in due {
+                self.apply(fault.action)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn update_watch(&mut self, record: &ChainRecord) -> Result<()> {
+        let now = self.clock.now();
+        match record.milestone {
+            ChainMilestone::CommitteeFinalized => {
+                let committee = self
+                    .chain
+                    .committee(&record.e3_id)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("No committee recorded for {}", record.e3_id))?;
+                self.tracker
+                    .committee_finalized(&record.e3_id, committee, now);
+            }
+            ChainMilestone::PublicKeyPublished => self.tracker.public_key_published(&record.e3_id),
+            ChainMilestone::CiphertextPublished => {
+                self.tracker.ciphertext_published(&record.e3_id, now)
+            }
+            ChainMilestone::PlaintextPublished => self.tracker.plaintext_published(&record.e3_id),
+            _ => (),
+        }
+        Ok(())
+    }
+
+    fn apply_timed_faults(&mut self) -> Result<()> {
+        let now = self.clock.now();
+        let (due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.faults)
+            .into_iter()
+            .partition(|fault| matches!(fault.trigger, SimTrigger::At(at) if at <= now));
+        self.faults = pending;
+        for fault in due {
+            self.apply(fault.action)?;
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, action: SimAction) -> Result<()> {
+        info!(at = ?self.clock.now(), ?action, "Simulation applying fault");
+        match action {
+            SimAction::Kill(node) => self.kill(node),
+            SimAction::KillActiveAggregator(e3_id) => {
+                let address = self
+                    .active_aggregator(&e3_id)
+                    .ok_or_else(|| anyhow!("No active aggregator known for {e3_id}"))?;
+                let node = self.node_index(&address).ok_or_else(|| {
+                    anyhow!("Active aggregator {address} is not a simulated node")
+                })?;
+                self.kill(node)
+            }
+            SimAction::Partition(groups) => {
+                self.net.partition(&groups);
+                Ok(())
+            }
+            SimAction::Heal => {
+                self.net.heal();
+                Ok(())
+            }
+            SimAction::SetDropRate(rate) => {
+                self.net.set_drop_rate(rate);
+                Ok(())
+            }
+        }
+    }
+
+    fn kill(&mut self, node: usize) -> Result<()> {
+        let handle = self
+            .nodes
+            .get(node)
+            .ok_or_else(|| anyhow!("No simulated node at index {node}"))?;
+        self.net.kill(node);
+        handle.bus().publish_without_context(Shutdown)?;
+        Ok(())
+    }
+
+    fn check_failover(&mut self) -> Result<()> {
+        let now = self.clock.now();
+        let policy = FailoverPolicy::new(self.config.failover_timeout);
+
+        for (e3_id, decision) in self.tracker.check(&policy, now) {
+            if let FailoverDecision::Promote { demote, .. } = &decision {
+                for (index, node) in self.nodes.iter().enumerate() {
+                    if self.net.is_killed(index) {
+                        continue;
+                    }
+                    node.bus()
+                        .publish_without_context(AggregatorPresumedUnresponsive {
+                            e3_id: e3_id.clone(),
+                            party_id: *demote,
+                        })?;
+                }
+            }
+
+            info!(at = ?now, e3_id = %e3_id, ?decision, "Simulated failover decision");
+            self.failovers.push(FailoverRecord {
+                at: now,
+                e3_id,
+                decision,
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,519 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use super::VirtualClock;
+use e3_net::{
+    events::{GossipData, NetCommand, NetEvent},
+    ContentHash, NetChannelBridge, NetInterfaceInverted,
+};
+use e3_utils::ArcBytes;
+use libp2p::{gossipsub::MessageId, kad::GetRecordError};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{error, warn};
+
+/// Counters describing what happened to gossip on the simulated network.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SimNetStats {
+    /// Messages published by live nodes.
+    pub published: u64,
+    /// Per-recipient copies handed to a node's net interface.
+    pub delivered: u64,
+    /// Per-recipient copies lost to the random drop rate.
+    pub dropped: u64,
+    /// Per-recipient copies blocked by a partition or a killed endpoint.
+    pub blocked: u64,
+}
+
+/// A gossip message waiting to be routed. `seq` is the per-sender publish counter, which
+/// together with the sender index gives every message an identity that does not depend on
+/// how the tokio scheduler interleaved the node tasks.
+#[derive(Debug)]
+struct Outbound {
+    sent_at: Duration,
+    src: usize,
+    seq: u64,
+    data: GossipData,
+}
+
+/// Delivery key ordered by (deliver_at, src, seq, dst).
+type InFlightKey = (Duration, usize, u64, usize);
+
+#[derive(Debug, Default)]
+struct NetState {
+    default_delay: Duration,
+    link_delays: HashMap<(usize, usize), Duration>,
+    drop_rate: f64,
+    /// Partition group per node. `None` means the network is fully connected.
+    groups: Option<HashMap<usize, usize>>,
+    killed: BTreeSet<usize>,
+    outbox: Vec<Outbound>,
+    in_flight: BTreeMap<InFlightKey, GossipData>,
+    stats: SimNetStats,
+}
+
+impl NetState {
+    fn reachable(&self, src: usize, dst: usize) -> bool {
+        if self.killed.contains(&src) || self.killed.contains(&dst) {
+            return false;
+        }
+        match &self.groups {
+            None => true,
+            Some(groups) => match (groups.get(&src), groups.get(&dst)) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+
+    fn delay(&self, src: usize, dst: usize) -> Duration {
+        self.link_delays
+            .get(&(src, dst))
+            .copied()
+            .unwrap_or(self.default_delay)
+    }
+}
+
+/// Simulated gossip network connecting the [`NetChannelBridge`]s of in-process nodes.
+///
+/// Unlike [`crate::libp2p_mock::Libp2pMock`], which forwards gossip immediately, published
+/// messages are held until the virtual clock reaches their delivery time. Delays can be set
+/// per link, messages can be dropped at a seeded random rate, the network can be partitioned
+/// and nodes can be killed. Drop decisions are derived from the seed and the message identity
+/// only, so a given seed produces the same losses on every run. DHT records are kept in a
+/// shared store and answered immediately for live nodes.
+#[derive(Debug, Clone)]
+pub struct SimNetwork {
+    seed: u64,
+    clock: VirtualClock,
+    nodes: Arc<Mutex<Vec<NetChannelBridge>>>,
+    state: Arc<Mutex<NetState>>,
+    store: Arc<Mutex<HashMap<ContentHash, ArcBytes>>>,
+}
+
+impl SimNetwork {
+    pub fn new(seed: u64, clock: &VirtualClock) -> Self {
+        Self {
+            seed,
+            clock: clock.clone(),
+            nodes: Arc::new(Mutex::new(Vec::new())),
+            state: Arc::new(Mutex::new(NetState::default())),
+            store: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Attach a node's channel bridge and return its index on the network.
+    pub fn add_node(&self, bridge: NetChannelBridge) -> usize {
+        let index = {
+            let mut nodes = self.nodes.lock().expect("sim nodes poisoned");
+            nodes.push(bridge.clone());
+            nodes.len() - 1
+        };
+
+        let src_event_tx = bridge.event_tx();
+        let mut src_cmd_rx = bridge.cmd_rx();
+        let state = self.state.clone();
+        let store = self.store.clone();
+        let clock = self.clock.clone();
+
+        tokio::spawn(async move {
+            let mut seq = 0u64;
+            loop {
+                let cmd = match src_cmd_rx.recv().await {
+                    Ok(cmd) => cmd,
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("SimNetwork: node {index} cmd receiver lagged by {n} messages");
+                        continue;
+                    }
+                    Err(_) => break,
+                };
+
+                if state
+                    .lock()
+                    .expect("sim state poisoned")
+                    .killed
+                    .contains(&index)
+                {
+                    continue;
+                }
+
+                match cmd {
+                    NetCommand::GossipPublish {
+                        data,
+                        correlation_id,
+                        ..
+                    } => {
+                        {
+                            let mut s = state.lock().expect("sim state poisoned");
+                            s.outbox.push(Outbound {
+                                sent_at: clock.now(),
+                                src: index,
+                                seq,
+                                data,
+                            });
+                            s.stats.published += 1;
+                        }
+                        seq += 1;
+
+                        let message_id =
+                            MessageId::new(&format!("{correlation_id:?}").into_bytes());
+                        if let Err(e) = src_event_tx.send(NetEvent::GossipPublished {
+                            correlation_id,
+                            message_id,
+                        }) {
+                            error!("SimNetwork: failed to send GossipPublished: {e}");
+                        }
+                    }
+                    NetCommand::DhtPutRecord {
+                        correlation_id,
+                        key,
+                        value,
+                        ..
+                    } => {
+                        store
+                            .lock()
+                            .expect("sim store poisoned")
+                            .insert(key.clone(), value);
+                        if let Err(e) = src_event_tx.send(NetEvent::DhtPutRecordSucceeded {
+                            key,
+                            correlation_id,
+                        }) {
+                            error!("SimNetwork: failed to send DhtPutRecordSucceeded: {e}");
+                        }
+                    }
+                    NetCommand::DhtGetRecord {
+                        correlation_id,
+                        key,
+                    } => {
+                        let maybe_value =
+                            store.lock().expect("sim store poisoned").get(&key).cloned();
+                        let event = match maybe_value {
+                            Some(value) => NetEvent::DhtGetRecordSucceeded {
+                                key,
+                                correlation_id,
+                                value,
+                            },
+                            None => NetEvent::DhtGetRecordError {
+                                correlation_id,
+                                error: GetRecordError::NotFound {
+                                    key: libp2p::kad::RecordKey::new(&key.into_inner()),
+                                    closest_peers: vec![],
+                                },
+                            },
+                        };
+                        if let Err(e) = src_event_tx.send(event) {
+                            error!("SimNetwork: failed to answer DhtGetRecord: {e}");
+                        }
+                    }
+                    NetCommand::DhtRemoveRecords { keys } => {
+                        let mut s = store.lock().expect("sim store poisoned");
+                        for key in keys {
+                            s.remove(&key);
+                        }
+                    }
+                    _ => continue,
+                }
+            }
+        });
+
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.lock().expect("sim nodes poisoned").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Latency applied to every link without an explicit override.
+    pub fn set_default_delay(&self, delay: Duration) {
+        self.state.lock().expect("sim state poisoned").default_delay = delay;
+    }
+
+    /// Latency for messages sent from `from` to `to`.
+    pub fn set_link_delay(&self, from: usize, to: usize, delay: Duration) {
+        self.state
+            .lock()
+            .expect("sim state poisoned")
+            .link_delays
+            .insert((from, to), delay);
+    }
+
+    /// Probability in `[0, 1]` that any single per-recipient copy is lost.
+    pub fn set_drop_rate(&self, rate: f64) {
+        self.state.lock().expect("sim state poisoned").drop_rate = rate.clamp(0.0, 1.0);
+    }
+
+    /// Split the network into groups that can only reach members of their own group.
+    /// Nodes not listed in any group are isolated. Messages already in flight across the
+    /// new boundary are lost when they come due.
+    pub fn partition(&self, groups: &[Vec<usize>]) {
+        let mut assignment = HashMap::new();
+        for (group, members) in groups.iter().enumerate() {
+            for node in members {
+                assignment.insert(*node, group);
+            }
+        }
+        self.state.lock().expect("sim state poisoned").groups = Some(assignment);
+    }
+
+    /// Remove any partition.
+    pub fn heal(&self) {
+        self.state.lock().expect("sim state poisoned").groups = None;
+    }
+
+    /// Permanently disconnect a node. It neither sends nor receives from now on.
+    pub fn kill(&self, node: usize) {
+        self.state
+            .lock()
+            .expect("sim state poisoned")
+            .killed
+            .insert(node);
+    }
+
+    pub fn is_killed(&self, node: usize) -> bool {
+        self.state
+            .lock()
+            .expect("sim state poisoned")
+            .killed
+            .contains(&node)
+    }
+
+    pub fn stats(&self) -> SimNetStats {
+        self.state.lock().expect("sim state poisoned").stats
+    }
+
+    /// Number of routed messages that have not been delivered yet.
+    pub fn in_flight(&self) -> usize {
+        self.state
+            .lock()
+            .expect("sim state poisoned")
+            .in_flight
+            .len()
+    }
+
+    /// Virtual time of the next pending delivery, if any.
+    pub fn next_delivery(&self) -> Option<Duration> {
+        self.state
+            .lock()
+            .expect("sim state poisoned")
+            .in_flight
+            .keys()
+            .next()
+            .map(|(at, ..)| *at)
+    }
+
+    /// Route everything published since the last call: apply partitions, kills, the drop
+    /// rate and link delays, and schedule the surviving copies for delivery.
+    pub fn route(&self) {
+        let node_count = self.len();
+        let mut state = self.state.lock().expect("sim state poisoned");
+        let mut outbox = std::mem::take(&mut state.outbox);
+        outbox.sort_by_key(|o| (o.sent_at, o.src, o.seq));
+
+        for msg in outbox {
+            for dst in (0..node_count).filter(|dst| *dst != msg.src) {
+                if !state.reachable(msg.src, dst) {
+                    state.stats.blocked += 1;
+                    continue;
+                }
+                if self.should_drop(state.drop_rate, msg.src, msg.seq, dst) {
+                    state.stats.dropped += 1;
+                    continue;
+                }
+                let deliver_at = msg.sent_at + state.delay(msg.src, dst);
+                state
+                    .in_flight
+                    .insert((deliver_at, msg.src, msg.seq, dst), msg.data.clone());
+            }
+        }
+    }
+
+    /// Deliver every routed message whose delivery time has been reached, in delivery order.
+    /// Returns the number of messages handed to nodes.
+    pub fn deliver_due(&self) -> usize {
+        let now = self.clock.now();
+        let nodes = self.nodes.lock().expect("sim nodes poisoned").clone();
+        let mut state = self.state.lock().expect("sim state poisoned");
+        let mut delivered = 0;
+
+        while let Some(entry) = state.in_flight.first_entry() {
+            let (deliver_at, src, _, dst) = *entry.key();
+            if deliver_at > now {
+                break;
+            }
+            let data = entry.remove();
+            if !state.reachable(src, dst) {
+                state.stats.blocked += 1;
+                continue;
+            }
+            if let Err(e) = nodes[dst].event_tx().send(NetEvent::GossipData(data)) {
+                error!("SimNetwork: failed to deliver GossipData to node {dst}: {e}");
+                continue;
+            }
+            state.stats.delivered += 1;
+            delivered += 1;
+        }
+
+        delivered
+    }
+
+    fn should_drop(&self, rate: f64, src: usize, seq: u64, dst: usize) -> bool {
+        if rate <= 0.0 {
+            return false;
+        }
+        let mut seed = [0u8; 32];
+        seed[0..8].copy_from_slice(&self.seed.to_le_bytes());
+        seed[8..16].copy_from_slice(&(src as u64).to_le_bytes());
+        seed[16..24].copy_from_slice(&seq.to_le_bytes());
+        seed[24..32].copy_from_slice(&(dst as u64).to_le_bytes());
+        ChaCha20Rng::from_seed(seed).random_bool(rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use e3_events::CorrelationId;
+    use e3_net::{create_channel_bridge, NetInterface, NetInterfaceHandle};
+    use tokio::sync::broadcast::error::TryRecvError;
+
+    async fn network(seed: u64, count: usize) -> (SimNetwork, Vec<NetInterfaceHandle>) {
+        let clock = VirtualClock::new();
+        let net = SimNetwork::new(seed, &clock);
+        let mut handles = Vec::new();
+        for _ in 0..count {
+            let (handle, bridge) = create_channel_bridge();
+            net.add_node(bridge);
+            handles.push(handle);
+        }
+        (net, handles)
+    }
+
+    async fn publish(handle: &NetInterfaceHandle, byte: u8) {
+        handle
+            .tx()
+            .send(NetCommand::GossipPublish {
+                topic: "test".to_string(),
+                data: GossipData::GossipBytes(vec![byte]),
+                correlation_id: CorrelationId::new(),
+            })
+            .await
+            .unwrap();
+        // Let the bridge and the simulator pump pick the command up.
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    fn received(rx: &mut broadcast::Receiver<NetEvent>) -> Vec<u8> {
+        let mut out = Vec::new();
+        loop {
+            match rx.try_recv() {
+                Ok(NetEvent::GossipData(GossipData::GossipBytes(bytes))) => out.extend(bytes),
+                Ok(_) => continue,
+                Err(TryRecvError::Lagged(_)) => continue,
+                Err(_) => break,
+            }
+        }
+        out
+    }
+
+    #[actix::test]
+    async fn holds_messages_until_delay_elapses() {
+        let (net, handles) = network(1, 2).await;
+        let mut rx = handles[1].rx();
+        net.set_default_delay(Duration::from_millis(500));
+
+        publish(&handles[0], 7).await;
+        net.route();
+        assert_eq!(net.deliver_due(), 0);
+        assert_eq!(net.next_delivery(), Some(Duration::from_millis(500)));
+
+        net.clock.advance(Duration::from_millis(500));
+        assert_eq!(net.deliver_due(), 1);
+        assert_eq!(received(&mut rx), vec![7]);
+    }
+
+    #[actix::test]
+    async fn delivers_in_delay_order_not_publish_order() {
+        let (net, handles) = network(1, 3).await;
+        let mut rx = handles[2].rx();
+        net.set_link_delay(0, 2, Duration::from_millis(300));
+        net.set_link_delay(1, 2, Duration::from_millis(100));
+
+        publish(&handles[0], 1).await;
+        publish(&handles[1], 2).await;
+        net.route();
+        net.clock.advance(Duration::from_secs(1));
+        net.deliver_due();
+
+        assert_eq!(received(&mut rx), vec![2, 1]);
+    }
+
+    #[actix::test]
+    async fn partition_blocks_cross_group_traffic_until_healed() {
+        let (net, handles) = network(1, 3).await;
+        let mut rx1 = handles[1].rx();
+        let mut rx2 = handles[2].rx();
+        net.partition(&[vec![0, 1], vec![2]]);
+
+        publish(&handles[0], 1).await;
+        net.route();
+        net.deliver_due();
+        assert_eq!(received(&mut rx1), vec![1]);
+        assert!(received(&mut rx2).is_empty());
+        assert_eq!(net.stats().blocked, 1);
+
+        net.heal();
+        publish(&handles[0], 2).await;
+        net.route();
+        net.deliver_due();
+        assert_eq!(received(&mut rx2), vec![2]);
+    }
+
+    #[actix::test]
+    async fn killed_node_neither_sends_nor_receives() {
+        let (net, handles) = network(1, 2).await;
+        let mut rx0 = handles[0].rx();
+        let mut rx1 = handles[1].rx();
+        net.kill(1);
+
+        publish(&handles[1], 1).await;
+        publish(&handles[0], 2).await;
+        net.route();
+        net.deliver_due();
+
+        assert!(received(&mut rx0).is_empty());
+        assert!(received(&mut rx1).is_empty());
+        assert_eq!(net.stats().published, 1);
+    }
+
+    #[actix::test]
+    async fn drops_are_reproducible_for_a_seed() {
+        async fn run(seed: u64) -> (SimNetStats, Vec<u8>) {
+            let (net, handles) = network(seed, 2).await;
+            let mut rx = handles[1].rx();
+            net.set_drop_rate(0.5);
+            for byte in 0..32 {
+                publish(&handles[0], byte).await;
+            }
+            net.route();
+            net.deliver_due();
+            (net.stats(), received(&mut rx))
+        }
+
+        let (stats_a, got_a) = run(42).await;
+        let (stats_b, got_b) = run(42).await;
+        assert_eq!(stats_a, stats_b);
+        assert_eq!(got_a, got_b);
+        assert!(stats_a.dropped > 0 && stats_a.delivered > 0);
+    }
+}
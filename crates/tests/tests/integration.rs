@@ -8,6 +8,7 @@ use actix::Actor;
 use alloy::primitives::{Address, FixedBytes, I256, U256};
 use alloy::signers::local::PrivateKeySigner;
 use anyhow::{bail, Context, Result};
+use e3_aggregator::failover::FailoverDecision;
 use e3_bfv_client::decode_bytes_to_vec_u64;
 use e3_ciphernode_builder::{CiphernodeBuilder, EventSystem};
 use e3_config::BBPath;
@@ -27,6 +28,9 @@ use e3_sortition::{calculate_buffer_size, RegisteredNode, ScoreSortition, Ticket
 use e3_test_helpers::ciphernode_system::{
     CiphernodeHistory, CiphernodeSystem, CiphernodeSystemBuilder,
 };
+use e3_test_helpers::sim::{
+    ChainMilestone, SimAction, SimChain, SimConfig, SimFault, Simulation, VirtualClock,
+};
 use e3_test_helpers::{
     create_seed_from_u64, derive_shared_rng, find_bb, with_tracing, AddToCommittee,
 };
@@ -263,6 +267,40 @@ fn benchmark_slashing_manager_address() -> Address {
         .expect("BENCHMARK_SLASHING_MANAGER must be a valid address")
 }
 
+/// Minimal chain config for in-process benchmarks (no RPC needed).
+/// Provides slashing_manager address for EIP-712 accusation vote signatures.
+fn in_process_chain_config(slashing_manager_addr: Address) -> e3_config::chain_config::ChainConfig {
+    e3_config::chain_config::ChainConfig {
+        enabled: Some(false),
+        name: "bench".into(),
+        rpc_url: "http://localhost:8545".into(),
+        rpc_auth: Default::default(),
+        contracts: e3_config::ContractAddresses {
+            interfold: e3_config::Contract::AddressOnly(
+                "0x0000000000000000000000000000000000000000".into(),
+            ),
+            ciphernode_registry: e3_config::Contract::AddressOnly(
+                "0x0000000000000000000000000000000000000000".into(),
+            ),
+            bonding_registry: e3_config::Contract::AddressOnly(
+                "0x0000000000000000000000000000000000000000".into(),
+            ),
+            e3_program: None,
+            fee_token: None,
+            slashing_manager: Some(e3_config::Contract::AddressOnly(
+                slashing_manager_addr.to_string(),
+            )),
+            dkg_fold_attestation_verifier: benchmark_dkg_fold_attestation_verifier_address()
+                .map(|a| e3_config::Contract::AddressOnly(a.to_string())),
+            dkg_aggregator_verifier: None,
+            decryption_aggregator_verifier: None,
+        },
+        finalization_ms: None,
+        reorg_confirmations: None,
+        chain_id: Some(1),
+    }
+}
+
 /// RAII guard that restores the benchmark-specific collector-timeout env vars on scope exit.
 /// This prevents leaking secure-mode tuning into other tests/processes.
 struct EnvTimeoutVarsGuard {
@@ -1392,35 +1430,7 @@ async fn test_trbfv_actor() -> Result<()> {
     let task_pool = Multithread::create_taskpool(pool_threads, concurrent_jobs);
     let multithread_report = MultithreadReport::new(pool_threads, concurrent_jobs).start();
 
-    // Minimal chain config for in-process benchmarks (no RPC needed).
-    // Provides slashing_manager address for EIP-712 accusation vote signatures.
-    let bench_chain_config = e3_config::chain_config::ChainConfig {
-        enabled: Some(false),
-        name: "bench".into(),
-        rpc_url: "http://localhost:8545".into(),
-        rpc_auth: Default::default(),
-        contracts: e3_config::ContractAddresses {
-            interfold: e3_config::Contract::AddressOnly(
-                "0x0000000000000000000000000000000000000000".into(),
-            ),
-            ciphernode_registry: e3_config::Contract::AddressOnly(
-                "0x0000000000000000000000000000000000000000".into(),
-            ),
-            bonding_registry: e3_config::Contract::AddressOnly(
-                "0x0000000000000000000000000000000000000000".into(),
-            ),
-            e3_program: None,
-            fee_token: None,
-            slashing_manager: Some(e3_config::Contract::AddressOnly(
-                slashing_manager_addr.to_string(),
-            )),
-            dkg_fold_attestation_verifier: benchmark_dkg_fold_attestation_verifier_address()
-                .map(|a| e3_config::Contract::AddressOnly(a.to_string())),
-        },
-        finalization_ms: None,
-        reorg_confirmations: None,
-        chain_id: Some(1),
-    };
+    let bench_chain_config = in_process_chain_config(slashing_manager_addr);
 
     // Setup ZK backend for proof generation/verification
     let (zk_backend, _zk_temp) = setup_test_zk_backend(benchmark_params.preset_subdir).await?;
@@ -2256,6 +2266,206 @@ async fn test_trbfv_actor() -> Result<()> {
     Ok(())
 }
 
+/// Runs a full trBFV round on the deterministic simulator and crashes the active aggregator
+/// as soon as the public key is on-chain. The simulated failover monitor must presume it
+/// unresponsive once the decryption deadline passes, the next standby must take over and its
+/// plaintext must be the one the stub chain accepts. Runs on the insecure preset unless
+/// `BENCHMARK_MODE=secure`; the missed deadline is skipped in virtual time, so the cost is one
+/// honest round.
+#[actix::test]
+#[serial_test::serial]
+async fn test_aggregator_failover_after_public_key_published() -> Result<()> {
+    let _guard = with_tracing("info");
+
+    const NODE_RNG_BASE: u64 = 4242;
+    let benchmark_params = select_benchmark_params();
+    let params_raw = BfvParamSet::from(benchmark_params.bfv_preset).build_arc();
+    let params = ArcBytes::from_bytes(&encode_bfv_params(&params_raw.clone()));
+    let committee = active_committee(benchmark_params.preset_subdir).values();
+    let threshold_m = committee.threshold;
+    let threshold_n = committee.n;
+    let participant_count = benchmark_participant_node_count(threshold_m, threshold_n);
+    let seed = create_seed_from_u64(123);
+    let error_size = ArcBytes::from_bytes(&BigUint::to_bytes_be(&calculate_error_size(
+        params_raw.clone(),
+        threshold_n,
+        threshold_m,
+        benchmark_params.lambda,
+    )?));
+
+    let cipher = Arc::new(Cipher::from_password("I am the music man.").await?);
+    let chain_config = in_process_chain_config(benchmark_slashing_manager_address());
+    let (zk_backend, _zk_temp) = setup_test_zk_backend(benchmark_params.preset_subdir).await?;
+
+    let chain_id = 1u64;
+    let clock = VirtualClock::new();
+    let chain = SimChain::new(chain_id, &clock)?;
+    let nodes = CiphernodeSystemBuilder::new()
+        .add_group(
+            u32::try_from(participant_count).expect("participant count fits in u32"),
+            || async {
+                CiphernodeBuilder::new(next_benchmark_node_rng(NODE_RNG_BASE), cipher.clone())
+                    .with_history_collector()
+                    .with_trbfv()
+                    .with_zkproof(zk_backend.clone())
+                    .with_signer(PrivateKeySigner::random())
+                    .with_pubkey_aggregation()
+                    .with_sortition_score()
+                    .with_threshold_plaintext_aggregation()
+                    .with_forked_bus(chain.bus().event_bus())
+                    .with_chains(std::slice::from_ref(&chain_config))
+                    .build()
+                    .await
+            },
+        )
+        .build()
+        .await?;
+
+    let eth_addrs: Vec<String> = nodes.iter().map(|n| n.address()).collect();
+    setup_score_sortition_environment(chain.bus(), &eth_addrs, chain_id).await?;
+
+    // Virtual time tracks real time closely (one tick per settle period) so the failover
+    // deadline can be expressed with the same budgets the benchmark uses for honest phases.
+    let failover_timeout = benchmark_params
+        .pubkey_flow_timeout
+        .max(benchmark_params.plaintext_flow_timeout);
+    let mut sim = Simulation::new(
+        SimConfig {
+            seed: 7,
+            tick: Duration::from_millis(250),
+            settle: Duration::from_millis(250),
+            failover_timeout,
+        },
+        chain,
+        nodes,
+    )?;
+    sim.net().set_default_delay(Duration::from_millis(100));
+
+    let e3_id = E3id::new("0", chain_id);
+    sim.schedule(SimFault::after(
+        &e3_id,
+        ChainMilestone::PublicKeyPublished,
+        SimAction::KillActiveAggregator(e3_id.clone()),
+    ));
+
+    sim.chain_mut().request_e3(E3Requested {
+        e3_id: e3_id.clone(),
+        threshold_m,
+        threshold_n,
+        seed,
+        error_size,
+        params_preset: benchmark_params.bfv_preset,
+        params,
+        proof_aggregation_enabled: benchmark_proof_aggregation_enabled(),
+    })?;
+    for _ in 0..4 {
+        sim.step().await?;
+    }
+
+    let (committee, committee_scores, _) = determine_committee(
+        &e3_id,
+        seed,
+        threshold_m,
+        threshold_n,
+        &eth_addrs,
+        &Address::ZERO.to_string(),
+    )?;
+    sim.chain_mut().finalize_committee(CommitteeFinalized {
+        e3_id: e3_id.clone(),
+        committee: committee.clone(),
+        scores: committee_scores.clone(),
+        chain_id,
+    })?;
+    sim.step().await?;
+
+    let original_aggregator =
+        active_aggregator_address(&committee, &committee_scores, &e3_id, chain_id);
+    assert_eq!(
+        sim.active_aggregator(&e3_id).as_deref(),
+        Some(original_aggregator.as_str())
+    );
+
+    let limit = sim.clock().now() + failover_timeout;
+    sim.run_until(limit, |s| s.chain().public_key(&e3_id).is_some())
+        .await?;
+    let original_index = sim
+        .node_index(&original_aggregator)
+        .context("original aggregator is a simulated node")?;
+    assert!(
+        sim.net().is_killed(original_index),
+        "active aggregator should be crashed once the public key is on-chain"
+    );
+
+    let pubkey_bytes = sim.chain().public_key(&e3_id).unwrap().pubkey.clone();
+    let pubkey = PublicKey::from_bytes(&pubkey_bytes, &params_raw)?;
+    let num_votes_per_voter = 3;
+    let (inputs, numbers) =
+        e3_test_helpers::application::generate_ciphertexts(&pubkey, 10, num_votes_per_voter);
+    let outputs =
+        e3_test_helpers::application::run_application(&inputs, &pubkey, num_votes_per_voter);
+    sim.chain_mut()
+        .publish_ciphertext_output(CiphertextOutputPublished {
+            e3_id: e3_id.clone(),
+            ciphertext_output: outputs
+                .into_iter()
+                .map(|ct| ArcBytes::from_bytes(&(*ct).clone().to_bytes()))
+                .collect(),
+        })?;
+
+    // Arm the decryption deadline, then jump past it: the crashed aggregator can only miss it,
+    // so waiting it out in real time proves nothing. The promoted standby then decrypts within
+    // a fresh budget.
+    sim.step().await?;
+    sim.clock().advance(failover_timeout);
+    let limit = sim.clock().now() + failover_timeout * 2;
+    sim.run_until(limit, |s| s.chain().plaintext(&e3_id).is_some())
+        .await?;
+
+    let promotions: Vec<_> = sim
+        .failovers()
+        .iter()
+        .filter(|f| f.e3_id == e3_id)
+        .map(|f| f.decision.clone())
+        .collect();
+    let [FailoverDecision::Promote {
+        demote, new_addr, ..
+    }] = promotions.as_slice()
+    else {
+        panic!("expected exactly one promotion, got {promotions:?}");
+    };
+    assert_eq!(*demote, 0, "party 0 is the original aggregator");
+    let publisher = sim
+        .chain()
+        .milestone(&e3_id, ChainMilestone::PlaintextPublished)
+        .and_then(|record| record.by)
+        .context("plaintext accepted on-chain")?;
+    assert!(
+        sim.nodes()[publisher]
+            .address()
+            .eq_ignore_ascii_case(new_addr),
+        "plaintext must be published by the promoted standby {new_addr}"
+    );
+
+    let plaintext_modulus = params_raw.plaintext();
+    let mut expected = [0u64; 3];
+    for vals in &numbers {
+        for j in 0..num_votes_per_voter {
+            expected[j] = (expected[j] + vals[j]) % plaintext_modulus;
+        }
+    }
+    let results: Vec<u64> = sim
+        .chain()
+        .plaintext(&e3_id)
+        .unwrap()
+        .decrypted_output
+        .iter()
+        .map(|a| decode_bytes_to_vec_u64(&a.extract_bytes()).expect("error decoding bytes")[0])
+        .collect();
+    assert_eq!(results, expected.to_vec());
+
+    Ok(())
+}
+
 // ============================================================================
 // Networking and P2P Tests
 // ============================================================================
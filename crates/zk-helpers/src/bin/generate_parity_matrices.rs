@@ -77,6 +77,10 @@ fn file_for(root: &Path, committee: &str, preset: BfvPreset) -> PathBuf {
         // DKG-only variants don't need their own file.
         BfvPreset::InsecureDkg512 => "insecure",
         BfvPreset::SecureDkg8192 => "secure",
+        // Preset file entries get their parity matrix from `zk_cli`.
+        BfvPreset::CustomThreshold(_) | BfvPreset::CustomDkg(_) => {
+            unreachable!("parity matrices are only generated here for built-in presets")
+        }
     };
     root.join(committee).join(format!("parity_{suffix}.nr"))
 }
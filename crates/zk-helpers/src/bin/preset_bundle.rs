@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Preset bundle CLI — searches BFV parameters and writes a deployable preset bundle.
+//!
+//! Runs the same search as `search_params` and writes, into `--output`:
+//! `preset.toml` (loadable with `--preset-file` / [`e3_fhe_params::PresetRegistry::load`]),
+//! `metadata.toml`, `security.toml`, the ABI-encoded `threshold.params` / `dkg.params` and the
+//! Noir `configs/<preset>/` module for `--committee`. The security report evaluates the noise
+//! budget for `--program-depth` (the program sums up to 2^depth fresh ciphertexts) and
+//! `--committee-size`. Refuses to write a bundle whose on-chain id is taken by a built-in preset
+//! or whose search `--n` is smaller than the `--committee`.
+
+use anyhow::{anyhow, bail, Result};
+use clap::Parser;
+use e3_fhe_params::search::bfv::{bfv_search, bfv_search_second_param, BfvSearchConfig};
+use e3_fhe_params::search::constants::K_MAX;
+use e3_fhe_params::{BundleSpec, ParamSetSecurity, PresetBundle, SecurityTier};
+use e3_zk_helpers::ciphernodes_committee::CiphernodesCommitteeSize;
+use e3_zk_helpers::preset_configs::write_preset_bundle;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug, Clone)]
+#[command(
+    version,
+    about = "Search BFV params and emit a deployable preset bundle"
+)]
+struct Args {
+    /// Canonical preset name (e.g. SECURE_THRESHOLD_16384).
+    #[arg(long)]
+    name: String,
+
+    /// On-chain `ParamSet` id to publish the preset under; must not be a built-in preset's id.
+    #[arg(long)]
+    on_chain_id: u8,
+
+    /// Security tier: "secure" or "insecure".
+    #[arg(long, default_value = "secure")]
+    security: SecurityTier,
+
+    /// Output directory for the bundle.
+    #[arg(long, short)]
+    output: PathBuf,
+
+    /// Number of parties n (e.g. ciphernodes).
+    #[arg(long, default_value_t = 1000u128)]
+    n: u128,
+
+    /// Number of fresh ciphertext z, i.e. number of votes.
+    #[arg(long, default_value_t = 1000u128)]
+    z: u128,
+
+    /// Plaintext modulus k (plaintext space).
+    #[arg(long, default_value_t = 1000u128)]
+    k: u128,
+
+    /// Statistical Security parameter λ (negl(λ)=2^{-λ}).
+    #[arg(long, default_value_t = 80u32)]
+    lambda: u32,
+
+    /// Bound B on the error distribution \psi used to generate e1 when encrypting.
+    #[arg(long, default_value_t = 20u128)]
+    b: u128,
+
+    /// Bound B_{\chi} on the distribution \chi used to generate the secret key sk_i.
+    #[arg(long, default_value_t = 1u128)]
+    b_chi: u128,
+
+    /// Additive depth of the program for the noise report (default: ceil(log2(z))).
+    #[arg(long)]
+    program_depth: Option<u32>,
+
+    /// Committee size N for the noise report (default: n).
+    #[arg(long)]
+    committee_size: Option<u64>,
+
+    /// Circuit committee the Noir configs are generated for: minimum, micro or small.
+    #[arg(long, default_value = "minimum")]
+    committee: CiphernodesCommitteeSize,
+}
+
+fn print_security(label: &str, security: &ParamSetSecurity) {
+    println!(
+        "  {label:<9} log2(q) = {:.2} (limit {:.2}, margin {:.2} bits), noise budget = {:.2} bits",
+        security.log2_q,
+        security.log2_q_limit,
+        security.lattice_margin_bits,
+        security.noise_budget_bits
+    );
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    if args.z == 0 || args.z > K_MAX {
+        bail!("z must be in [1, {K_MAX}], got {}", args.z);
+    }
+    if args.k == 0 {
+        bail!("plaintext space k must be positive");
+    }
+
+    let config = BfvSearchConfig {
+        n: args.n,
+        z: args.z,
+        k: args.k,
+        lambda: args.lambda,
+        b: args.b,
+        b_chi: args.b_chi,
+        verbose: false,
+    };
+    let threshold =
+        bfv_search(&config).map_err(|e| anyhow!("no feasible threshold parameter set: {e}"))?;
+    let dkg = bfv_search_second_param(&config, &threshold)
+        .ok_or_else(|| anyhow!("no feasible DKG parameter set"))?;
+
+    let committee_size = match args.committee_size {
+        Some(size) => size,
+        None => u64::try_from(args.n)?,
+    };
+    let spec = BundleSpec {
+        name: args.name,
+        on_chain_id: args.on_chain_id,
+        security: args.security,
+        program_depth: args
+            .program_depth
+            .unwrap_or_else(|| args.z.next_power_of_two().trailing_zeros()),
+        committee_size,
+    };
+    let bundle = PresetBundle::from_search(spec, &config, &threshold, &dkg)?;
+    let files = write_preset_bundle(&bundle, &args.output, args.committee)?;
+
+    let def = &bundle.definition;
+    let report = &bundle.report;
+    println!(
+        "Preset {} (on-chain id {}, degree {}, {} threshold / {} DKG moduli)",
+        def.name,
+        def.on_chain_id,
+        def.degree,
+        def.threshold.moduli.len(),
+        def.dkg.moduli.len()
+    );
+    println!(
+        "Security report for program depth {} and committee size {}:",
+        report.program_depth, report.committee_size
+    );
+    print_security("threshold", &report.threshold);
+    print_security("dkg", &report.dkg);
+    match report.max_lambda {
+        Some(max) => println!("  statistical λ = {} (holds up to {max})", report.lambda),
+        None => println!(
+            "  statistical λ = {} (Eq1 fails for this workload)",
+            report.lambda
+        ),
+    }
+    if !report.is_within_bounds() {
+        eprintln!("WARNING: the preset does not meet its bounds for this workload");
+    }
+
+    println!("Wrote {} files to {}", files.len(), args.output.display());
+    for file in files {
+        println!("  {}", file.display());
+    }
+    Ok(())
+}
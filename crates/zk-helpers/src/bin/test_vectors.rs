@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Generate or check seeded test vectors for every registered circuit (see
+//! `e3_zk_helpers::test_vectors`).
+//!
+//! Without `--check`, writes `vector.json`, `Prover.toml` and `negative-<k>/Prover.toml` per case
+//! under `--output`. With `--check`, regenerates every stored vector under `--output` and fails on
+//! the first drift, without running `nargo`.
+
+use anyhow::{anyhow, bail, Result};
+use clap::Parser;
+use e3_fhe_params::BfvPreset;
+use e3_zk_helpers::ciphernodes_committee::CiphernodesCommitteeSize;
+use e3_zk_helpers::test_vectors::{
+    check_vector, check_vector_files, generate_vector, load_vectors, vector_cases, write_vector,
+    DEFAULT_VECTOR_SEED, VECTOR_CIRCUITS,
+};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Parser)]
+#[command(name = "test-vectors")]
+#[command(about = "Generate or check seeded circuit test vectors")]
+struct Args {
+    /// Vector root; versioned subdirectories are created below it.
+    #[arg(long, default_value = "test-vectors")]
+    output: PathBuf,
+    /// Seed for sampling; every case of a run uses the same seed.
+    #[arg(long, default_value_t = DEFAULT_VECTOR_SEED)]
+    seed: u64,
+    /// Preset: "insecure"|"secure" or λ (2|80). Default: every threshold preset.
+    #[arg(long)]
+    preset: Option<String>,
+    /// Only this circuit (e.g. `pk`, `share-computation`). Default: every registered circuit.
+    #[arg(long)]
+    circuit: Option<String>,
+    /// Committee size: minimum, micro, or small.
+    #[arg(long, default_value = "minimum")]
+    committee: String,
+    /// Check the vectors under --output instead of writing new ones.
+    #[arg(long)]
+    check: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    if args.check {
+        let vectors = load_vectors(&args.output)?;
+        if vectors.is_empty() {
+            bail!("no test vectors under {}", args.output.display());
+        }
+        vectors.par_iter().try_for_each(|vector| {
+            check_vector(vector)?;
+            check_vector_files(vector, &args.output)
+        })?;
+        println!("  ✓ {} vectors match", vectors.len());
+        return Ok(());
+    }
+
+    let presets = match &args.preset {
+        Some(preset) => vec![BfvPreset::from_security_config_name(preset)?],
+        None => BfvPreset::PAIR_PRESETS.to_vec(),
+    };
+    let circuits: Vec<&str> = match &args.circuit {
+        Some(circuit) => vec![VECTOR_CIRCUITS
+            .iter()
+            .copied()
+            .find(|name| name == circuit)
+            .ok_or_else(|| {
+                anyhow!(
+                    "unknown circuit: {}. Available: {}",
+                    circuit,
+                    VECTOR_CIRCUITS.join(", ")
+                )
+            })?],
+        None => VECTOR_CIRCUITS.to_vec(),
+    };
+    let committee = CiphernodesCommitteeSize::from_str(&args.committee)?;
+
+    for case in vector_cases(&circuits, &presets, committee, args.seed) {
+        let vector = generate_vector(&case)?;
+        let dir = write_vector(&vector, &args.output)?;
+        println!("  ✓ {} written to {}", case.id(), dir.display());
+    }
+    Ok(())
+}
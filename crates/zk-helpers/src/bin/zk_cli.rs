@@ -9,6 +9,8 @@
 //! This binary lists available circuits and generates Prover.toml and configs.nr
 //! for use with the Noir prover. Use `--list_circuits` to see circuits and
 //! `--circuit <name> --preset insecure|secure|2|80 [--committee minimum|micro|small]` to generate artifacts.
+//! `--preset-file <path>` writes the `configs/<preset>/` modules for every preset in a preset file
+//! (see `e3_fhe_params::registry`) for `--committee`, and registers them in `--output/mod.nr`.
 //!
 //! **Share-computation (C2) configs.nr:** set `INTERFOLD_CIRCUITS_ROOT` to the repo `circuits`
 //! directory (or run from the Interfold repo so it is auto-discovered). After `pnpm build:circuits`,
@@ -22,7 +24,7 @@
 
 use anyhow::{anyhow, Context, Result};
 use clap::{arg, command, Parser};
-use e3_fhe_params::{BfvPreset, ParameterType};
+use e3_fhe_params::{BfvPreset, ParameterType, PresetRegistry};
 use e3_zk_helpers::ciphernodes_committee::CiphernodesCommitteeSize;
 use e3_zk_helpers::circuits::dkg::pk::circuit::{PkCircuit, PkCircuitData};
 use e3_zk_helpers::circuits::dkg::share_computation::circuit::{
@@ -35,6 +37,7 @@ use e3_zk_helpers::dkg::share_decryption::{
     ShareDecryptionCircuitData as DkgShareDecryptionCircuitData,
 };
 use e3_zk_helpers::dkg::share_encryption::{ShareEncryptionCircuit, ShareEncryptionCircuitData};
+use e3_zk_helpers::preset_configs::{generate_preset_configs, write_preset_configs};
 use e3_zk_helpers::registry::{Circuit, CircuitRegistry};
 use e3_zk_helpers::threshold::decrypted_shares_aggregation::{
     DecryptedSharesAggregationCircuit, DecryptedSharesAggregationCircuitData,
@@ -168,10 +171,10 @@ struct Cli {
     #[arg(long)]
     list_circuits: bool,
     /// Circuit name to generate artifacts for (e.g. `pk`, `share-computation`).
-    #[arg(long, required_unless_present = "list_circuits")]
+    #[arg(long, required_unless_present_any = ["list_circuits", "preset_file"])]
     circuit: Option<String>,
     /// Preset: "insecure"|"secure" or λ (2|80). Drives both threshold and DKG params.
-    #[arg(long, required_unless_present = "list_circuits")]
+    #[arg(long, required_unless_present_any = ["list_circuits", "preset_file"])]
     preset: Option<String>,
     /// Select the witness family when sample generation depends on it.
     #[arg(long)]
@@ -188,6 +191,10 @@ struct Cli {
     /// When used with --toml: do not write configs.nr (e.g. for benchmarks where circuits use lib configs).
     #[arg(long, default_value = "false")]
     no_configs: bool,
+    /// Preset file with additional presets: write their config modules for --committee to --output
+    /// (the `circuits/lib/src/configs` directory) and exit.
+    #[arg(long)]
+    preset_file: Option<PathBuf>,
 }
 
 fn main() -> Result<()> {
@@ -221,6 +228,17 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if let Some(preset_file) = &args.preset_file {
+        let registry = PresetRegistry::load(preset_file)
+            .with_context(|| format!("failed to load presets from {}", preset_file.display()))?;
+        for preset in registry.custom() {
+            let configs = generate_preset_configs(preset, parse_committee(&args.committee)?)?;
+            let dir = write_preset_configs(&configs, &args.output)?;
+            println!("  ✓ {} configs written to {}", preset.name, dir.display());
+        }
+        return Ok(());
+    }
+
     // Unwrap required arguments (clap ensures they're present when list_circuits is false).
     let circuit = args.circuit.unwrap();
     let preset = BfvPreset::from_security_config_name(&args.preset.unwrap())?;
@@ -9,8 +9,13 @@
 //! [`Computation`] is a generic trait for computing values from parameters and input.
 //! [`CircuitComputation`] extends it for circuits that produce inputs/bounds/bits.
 //! [`Toml`] and [`Configs`] are the string types used for Prover.toml and configs.nr.
+//! [`PresetParams`] carries the BFV parameters config-level values are derived from.
 
+use crate::CircuitsErrors;
+use e3_fhe_params::{BfvPreset, PresetDefinition, PresetSearchDefaults};
+use fhe::bfv::BfvParameters;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// Variant for input types for DKG.
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize, Serialize, Hash)]
@@ -21,6 +26,48 @@ pub enum DkgInputType {
     SmudgingNoise,
 }
 
+/// Threshold/DKG parameter pair and search defaults behind a preset.
+///
+/// Circuit `Configs`, `Bounds` and `Bits` only depend on these, so they can be computed for
+/// presets loaded from a preset file as well as for the built-in [`BfvPreset`]s.
+#[derive(Debug, Clone)]
+pub struct PresetParams {
+    pub threshold: Arc<BfvParameters>,
+    pub dkg: Arc<BfvParameters>,
+    /// `None` for the DKG-only built-in presets.
+    pub search: Option<PresetSearchDefaults>,
+}
+
+impl PresetParams {
+    pub fn for_preset(preset: BfvPreset) -> Result<Self, CircuitsErrors> {
+        let (threshold, dkg) = preset
+            .build_pair()
+            .map_err(|e| CircuitsErrors::Other(e.to_string()))?;
+        Ok(Self {
+            threshold,
+            dkg,
+            search: preset.search_defaults(),
+        })
+    }
+
+    pub fn from_definition(definition: &PresetDefinition) -> Result<Self, CircuitsErrors> {
+        let (threshold, dkg) = definition
+            .build_pair()
+            .map_err(|e| CircuitsErrors::Other(e.to_string()))?;
+        Ok(Self {
+            threshold,
+            dkg,
+            search: Some(definition.search_defaults()),
+        })
+    }
+
+    /// Search defaults (`z`, `lambda`) used for the smudging bounds.
+    pub fn search_defaults(&self) -> Result<PresetSearchDefaults, CircuitsErrors> {
+        self.search
+            .ok_or_else(|| CircuitsErrors::Other("missing search defaults".to_string()))
+    }
+}
+
 /// Generic computation from parameters and input to a result.
 pub trait Computation: Sized {
     type Preset;
@@ -15,7 +15,7 @@ use crate::compute_max_modulus;
 use crate::crt_polynomial_to_toml_json;
 use crate::utils::compute_modulus_bit;
 use crate::CircuitsErrors;
-use crate::{CircuitComputation, Computation};
+use crate::{CircuitComputation, Computation, PresetParams};
 use e3_fhe_params::build_pair_for_preset;
 use e3_fhe_params::BfvPreset;
 use e3_polynomial::CrtPolynomial;
@@ -80,27 +80,46 @@ pub struct Inputs {
     pub pk1is: CrtPolynomial,
 }
 
+impl Configs {
+    /// Computes the configs from an explicit parameter pair.
+    pub fn from_params(params: &PresetParams) -> Self {
+        let dkg_params = &params.dkg;
+        let moduli = dkg_params.moduli().to_vec();
+
+        Configs {
+            n: dkg_params.degree(),
+            l: moduli.len(),
+            moduli,
+            bits: Bits::from_params(params),
+            bounds: Bounds::from_params(params),
+        }
+    }
+}
+
+impl Bits {
+    pub fn from_params(params: &PresetParams) -> Self {
+        Bits {
+            pk_bit: compute_modulus_bit(&params.dkg),
+        }
+    }
+}
+
+impl Bounds {
+    pub fn from_params(params: &PresetParams) -> Self {
+        let max_mod = compute_max_modulus(params.dkg.moduli());
+        let pk_bound = (BigUint::from(max_mod) - 1u32) / 2u32;
+
+        Bounds { pk_bound }
+    }
+}
+
 impl Computation for Configs {
     type Preset = BfvPreset;
     type Data = ();
     type Error = CircuitsErrors;
 
     fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, CircuitsErrors> {
-        let (_, dkg_params) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Sample(e.to_string()))?;
-
-        let moduli = dkg_params.moduli().to_vec();
-        let l = moduli.len();
-        let bounds = Bounds::compute(preset, &())?;
-        let bits = Bits::compute(preset, &())?;
-
-        Ok(Configs {
-            n: dkg_params.degree(),
-            l,
-            moduli,
-            bits,
-            bounds,
-        })
+        Ok(Self::from_params(&PresetParams::for_preset(preset)?))
     }
 }
 
@@ -110,12 +129,7 @@ impl Computation for Bits {
     type Error = CircuitsErrors;
 
     fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, Self::Error> {
-        let (_, dkg_params) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Sample(e.to_string()))?;
-
-        Ok(Bits {
-            pk_bit: compute_modulus_bit(&dkg_params),
-        })
+        Ok(Self::from_params(&PresetParams::for_preset(preset)?))
     }
 }
 
@@ -125,14 +139,7 @@ impl Computation for Bounds {
     type Error = CircuitsErrors;
 
     fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, Self::Error> {
-        let (_, dkg_params) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Sample(e.to_string()))?;
-
-        let moduli = dkg_params.moduli();
-        let max_mod = compute_max_modulus(moduli);
-        let pk_bound = (BigUint::from(max_mod) - 1u32) / 2u32;
-
-        Ok(Bounds { pk_bound })
+        Ok(Self::from_params(&PresetParams::for_preset(preset)?))
     }
 }
 
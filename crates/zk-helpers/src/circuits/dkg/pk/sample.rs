@@ -11,17 +11,25 @@ use crate::CircuitsErrors;
 use e3_fhe_params::build_pair_for_preset;
 use e3_fhe_params::BfvPreset;
 use fhe::bfv::{PublicKey, SecretKey};
+use rand::{CryptoRng, RngCore};
 
 impl PkCircuitData {
     /// Generates sample data for the pk circuit.
     pub fn generate_sample(preset: BfvPreset) -> Result<Self, CircuitsErrors> {
+        Self::generate_sample_with_rng(preset, &mut rand::rng())
+    }
+
+    /// Same as [`Self::generate_sample`], drawing all randomness from `rng`.
+    pub fn generate_sample_with_rng<R: RngCore + CryptoRng>(
+        preset: BfvPreset,
+        rng: &mut R,
+    ) -> Result<Self, CircuitsErrors> {
         let (_, dkg_params) = build_pair_for_preset(preset).map_err(|e| {
             CircuitsErrors::Sample(format!("Failed to build pair for preset: {:?}", e))
         })?;
 
-        let mut rng = rand::rng();
-        let dkg_secret_key = SecretKey::random(&dkg_params, &mut rng);
-        let dkg_public_key = PublicKey::new(&dkg_secret_key, &mut rng);
+        let dkg_secret_key = SecretKey::random(&dkg_params, rng);
+        let dkg_public_key = PublicKey::new(&dkg_secret_key, rng);
 
         Ok(Self {
             public_key: dkg_public_key,
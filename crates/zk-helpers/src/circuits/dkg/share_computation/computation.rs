@@ -19,7 +19,7 @@ use crate::dkg::share_computation::ShareComputationCircuit;
 use crate::dkg::share_computation::ShareComputationCircuitData;
 use crate::CircuitsErrors;
 use crate::{calculate_bit_width, crt_polynomial_to_toml_json, poly_coefficients_to_toml_json};
-use crate::{CircuitComputation, Computation};
+use crate::{CircuitComputation, Computation, PresetParams};
 use e3_fhe_params::build_pair_for_preset;
 use e3_fhe_params::BfvPreset;
 use e3_polynomial::{reduce, CrtPolynomial};
@@ -97,23 +97,17 @@ pub struct Inputs {
     pub dkg_input_type: DkgInputType,
 }
 
-impl Computation for Configs {
-    type Preset = BfvPreset;
-    type Data = ShareComputationCircuitData;
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, CircuitsErrors> {
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Sample(e.to_string()))?;
-
+impl Configs {
+    /// Computes the configs for a committee of `n_parties` from an explicit parameter pair.
+    pub fn from_params(params: &PresetParams, n_parties: usize) -> Result<Self, CircuitsErrors> {
+        let threshold_params = &params.threshold;
         let moduli = threshold_params.moduli().to_vec();
-        let l = moduli.len();
-        let bounds = Bounds::compute(preset, data)?;
-        let bits = Bits::compute(preset, &bounds)?;
+        let bounds = Bounds::from_params(params, n_parties)?;
+        let bits = Bits::from_params(params, &bounds);
 
         Ok(Configs {
             n: threshold_params.degree(),
-            l,
+            l: moduli.len(),
             moduli,
             bits,
             bounds,
@@ -121,49 +115,34 @@ impl Computation for Configs {
     }
 }
 
-impl Computation for Bits {
-    type Preset = BfvPreset;
-    type Data = Bounds;
-    type Error = crate::utils::ZkHelpersUtilsError;
-
-    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
-        let (threshold_params, _) = build_pair_for_preset(preset)
-            .map_err(|e| crate::utils::ZkHelpersUtilsError::ParseBound(e.to_string()))?;
-
+impl Bits {
+    pub fn from_params(params: &PresetParams, bounds: &Bounds) -> Self {
         let mut bit_share = 0;
-        for &qi in threshold_params.moduli() {
+        for &qi in params.threshold.moduli() {
             let share_bound = BigUint::from(qi - 1);
             let bit_width = calculate_bit_width(BigInt::from(share_bound));
             bit_share = bit_share.max(bit_width);
         }
 
-        Ok(Bits {
-            bit_sk_secret: calculate_bit_width(BigInt::from(data.sk_bound.clone())),
-            bit_e_sm_secret: calculate_bit_width(BigInt::from(data.e_sm_bound.clone())),
+        Bits {
+            bit_sk_secret: calculate_bit_width(BigInt::from(bounds.sk_bound.clone())),
+            bit_e_sm_secret: calculate_bit_width(BigInt::from(bounds.e_sm_bound.clone())),
             bit_share,
-        })
+        }
     }
 }
 
-impl Computation for Bounds {
-    type Preset = BfvPreset;
-    type Data = ShareComputationCircuitData;
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Sample(e.to_string()))?;
-        let defaults = preset
-            .search_defaults()
-            .ok_or_else(|| CircuitsErrors::Sample("missing search defaults".to_string()))?;
+impl Bounds {
+    pub fn from_params(params: &PresetParams, n_parties: usize) -> Result<Self, CircuitsErrors> {
+        let defaults = params.search_defaults()?;
         let num_ciphertexts = defaults.z;
         let lambda = defaults.lambda;
 
         // Use the same committee size as C1 (pk_generation) so smudging bounds and
         // bit widths match PK_GENERATION_BIT_E_SM / SHARE_COMPUTATION_E_SM_BIT_SECRET.
         let e_sm_config = SmudgingBoundCalculatorConfig::new(
-            threshold_params,
-            data.n_parties as usize,
+            params.threshold.clone(),
+            n_parties,
             num_ciphertexts as usize,
             lambda as usize,
         );
@@ -179,6 +158,39 @@ impl Computation for Bounds {
     }
 }
 
+impl Computation for Configs {
+    type Preset = BfvPreset;
+    type Data = ShareComputationCircuitData;
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, CircuitsErrors> {
+        Self::from_params(&PresetParams::for_preset(preset)?, data.n_parties as usize)
+    }
+}
+
+impl Computation for Bits {
+    type Preset = BfvPreset;
+    type Data = Bounds;
+    type Error = crate::utils::ZkHelpersUtilsError;
+
+    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
+        let params = PresetParams::for_preset(preset)
+            .map_err(|e| crate::utils::ZkHelpersUtilsError::ParseBound(e.to_string()))?;
+
+        Ok(Self::from_params(&params, data))
+    }
+}
+
+impl Computation for Bounds {
+    type Preset = BfvPreset;
+    type Data = ShareComputationCircuitData;
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
+        Self::from_params(&PresetParams::for_preset(preset)?, data.n_parties as usize)
+    }
+}
+
 impl Computation for Inputs {
     type Preset = BfvPreset;
     type Data = ShareComputationCircuitData;
@@ -19,6 +19,7 @@ use e3_polynomial::CrtPolynomial;
 use fhe::bfv::SecretKey;
 use fhe::trbfv::{ShareManager, TRBFV};
 use num_bigint::BigInt;
+use rand::{CryptoRng, RngCore};
 
 pub type SecretShares = Vec<ndarray::Array2<BigInt>>;
 
@@ -28,6 +29,16 @@ impl ShareComputationCircuitData {
         preset: BfvPreset,
         committee: CiphernodesCommittee,
         dkg_input_type: DkgInputType,
+    ) -> Result<Self, CircuitsErrors> {
+        Self::generate_sample_with_rng(preset, committee, dkg_input_type, &mut rand::rng())
+    }
+
+    /// Same as [`Self::generate_sample`], drawing all randomness from `rng`.
+    pub fn generate_sample_with_rng<R: RngCore + CryptoRng>(
+        preset: BfvPreset,
+        committee: CiphernodesCommittee,
+        dkg_input_type: DkgInputType,
+        rng: &mut R,
     ) -> Result<Self, CircuitsErrors> {
         let (threshold_params, _) = build_pair_for_preset(preset).map_err(|e| {
             CircuitsErrors::Sample(format!("Failed to build pair for preset: {:?}", e))
@@ -35,8 +46,6 @@ impl ShareComputationCircuitData {
         let sd = preset
             .search_defaults()
             .ok_or_else(|| CircuitsErrors::Sample("Preset has no search defaults".into()))?;
-        let mut rng = rand::rng();
-
         let trbfv = TRBFV::new(committee.n, committee.threshold, threshold_params.clone())
             .map_err(|e| CircuitsErrors::Sample(format!("Failed to create TRBFV: {:?}", e)))?;
         let mut share_manager =
@@ -50,7 +59,7 @@ impl ShareComputationCircuitData {
 
         let (secret, secret_sss) = match dkg_input_type {
             DkgInputType::SecretKey => {
-                let threshold_secret_key = SecretKey::random(&threshold_params, &mut rng);
+                let threshold_secret_key = SecretKey::random(&threshold_params, rng);
 
                 let sk_poly = share_manager
                     .coeffs_to_poly_level0(threshold_secret_key.coeffs.clone().as_ref())
@@ -62,7 +71,7 @@ impl ShareComputationCircuitData {
                     })?;
 
                 let sk_sss_u64 = share_manager
-                    .generate_secret_shares_from_poly(sk_poly.clone(), &mut rng)
+                    .generate_secret_shares_from_poly(sk_poly.clone(), rng)
                     .map_err(|e| {
                         CircuitsErrors::Sample(format!("Failed to generate secret shares: {:?}", e))
                     })?;
@@ -87,7 +96,7 @@ impl ShareComputationCircuitData {
             }
             DkgInputType::SmudgingNoise => {
                 let esi_coeffs = trbfv
-                    .generate_smudging_error(committee.n, sd.lambda as usize, &mut rng)
+                    .generate_smudging_error(committee.n, sd.lambda as usize, rng)
                     .map_err(|e| {
                         CircuitsErrors::Sample(format!(
                             "Failed to generate smudging error: {:?}",
@@ -97,7 +106,7 @@ impl ShareComputationCircuitData {
                     .unwrap();
                 let esi_poly = share_manager.bigints_to_poly(&esi_coeffs).unwrap();
                 let esi_sss_u64 = share_manager
-                    .generate_secret_shares_from_poly(esi_poly.clone(), &mut rng)
+                    .generate_secret_shares_from_poly(esi_poly.clone(), rng)
                     .map_err(|e| {
                         CircuitsErrors::Sample(format!("Failed to generate error shares: {:?}", e))
                     })
@@ -26,7 +26,7 @@ use crate::math::plaintext_poly_u64;
 use crate::CircuitsErrors;
 use crate::{bigint_2d_to_json_values, poly_coefficients_to_toml_json};
 use crate::{compute_modulus_bit, compute_msg_bit};
-use crate::{CircuitComputation, Computation};
+use crate::{CircuitComputation, Computation, PresetParams};
 use e3_fhe_params::build_pair_for_preset;
 use e3_fhe_params::BfvPreset;
 use e3_polynomial::Polynomial;
@@ -104,29 +104,37 @@ pub struct Inputs {
     pub decrypted_shares: Vec<Vec<Vec<BigInt>>>, // [H][L][N]
 }
 
+impl Configs {
+    /// Computes the configs for `h` honest parties from an explicit parameter pair.
+    pub fn from_params(params: &PresetParams, h: usize) -> Self {
+        Configs {
+            n: params.dkg.degree(),
+            l: params.dkg.moduli().len(),
+            h,
+            bits: Bits::from_params(params),
+            bounds: Bounds {},
+        }
+    }
+}
+
+impl Bits {
+    pub fn from_params(params: &PresetParams) -> Self {
+        Bits {
+            msg_bit: compute_msg_bit(&params.dkg),
+            agg_bit: compute_modulus_bit(&params.threshold),
+        }
+    }
+}
+
 impl Computation for Configs {
     type Preset = BfvPreset;
     type Data = ShareDecryptionCircuitData;
     type Error = CircuitsErrors;
 
     fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, CircuitsErrors> {
-        let (_, dkg_params) = build_pair_for_preset(preset)
-            .map_err(|e| crate::utils::ZkHelpersUtilsError::ParseBound(e.to_string()))?;
+        let params = PresetParams::for_preset(preset)?;
 
-        let n = dkg_params.degree();
-        let l = dkg_params.moduli().len();
-        let h = data.honest_ciphertexts.len();
-
-        let bounds = Bounds::compute(preset, data)?;
-        let bits = Bits::compute(preset, &bounds)?;
-
-        Ok(Configs {
-            n,
-            l,
-            h,
-            bits,
-            bounds,
-        })
+        Ok(Self::from_params(&params, data.honest_ciphertexts.len()))
     }
 }
 
@@ -136,13 +144,10 @@ impl Computation for Bits {
     type Error = crate::utils::ZkHelpersUtilsError;
 
     fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, Self::Error> {
-        let (threshold_params, dkg_params) = build_pair_for_preset(preset)
+        let params = PresetParams::for_preset(preset)
             .map_err(|e| crate::utils::ZkHelpersUtilsError::ParseBound(e.to_string()))?;
 
-        Ok(Bits {
-            msg_bit: compute_msg_bit(&dkg_params),
-            agg_bit: compute_modulus_bit(&threshold_params),
-        })
+        Ok(Self::from_params(&params))
     }
 }
 
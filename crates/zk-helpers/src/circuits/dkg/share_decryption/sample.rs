@@ -19,6 +19,7 @@ use fhe::bfv::{PublicKey, SecretKey};
 use fhe::trbfv::{ShareManager, TRBFV};
 use fhe_traits::FheEncoder;
 use fhe_traits::FheEncrypter;
+use rand::{CryptoRng, RngCore};
 
 impl ShareDecryptionCircuitData {
     /// Generates sample data for the share-decryption circuit (decrypts a sum of honest ciphertexts under DKG secret key).
@@ -26,6 +27,16 @@ impl ShareDecryptionCircuitData {
         preset: BfvPreset,
         committee: CiphernodesCommittee,
         dkg_input_type: DkgInputType,
+    ) -> Result<Self, CircuitsErrors> {
+        Self::generate_sample_with_rng(preset, committee, dkg_input_type, &mut rand::rng())
+    }
+
+    /// Same as [`Self::generate_sample`], drawing all randomness from `rng`.
+    pub fn generate_sample_with_rng<R: RngCore + CryptoRng + Clone>(
+        preset: BfvPreset,
+        committee: CiphernodesCommittee,
+        dkg_input_type: DkgInputType,
+        rng: &mut R,
     ) -> Result<Self, CircuitsErrors> {
         let (threshold_params, dkg_params) = build_pair_for_preset(preset).map_err(|e| {
             CircuitsErrors::Sample(format!("Failed to build pair for preset: {:?}", e))
@@ -34,10 +45,8 @@ impl ShareDecryptionCircuitData {
             .search_defaults()
             .ok_or_else(|| CircuitsErrors::Sample("Preset has no search defaults".into()))?;
 
-        let mut rng = rand::rng();
-
-        let dkg_secret_key = SecretKey::random(&dkg_params, &mut rng);
-        let dkg_public_key = PublicKey::new(&dkg_secret_key, &mut rng);
+        let dkg_secret_key = SecretKey::random(&dkg_params, rng);
+        let dkg_public_key = PublicKey::new(&dkg_secret_key, rng);
 
         let trbfv = TRBFV::new(committee.n, committee.threshold, threshold_params.clone())
             .map_err(|e| CircuitsErrors::Sample(format!("Failed to create TRBFV: {:?}", e)))?;
@@ -57,7 +66,7 @@ impl ShareDecryptionCircuitData {
             for _ in 0..threshold_params.moduli().len() {
                 let share_row = match dkg_input_type {
                     DkgInputType::SecretKey => {
-                        let threshold_secret_key = SecretKey::random(&threshold_params, &mut rng);
+                        let threshold_secret_key = SecretKey::random(&threshold_params, rng);
 
                         let sk_poly = share_manager
                             .coeffs_to_poly_level0(threshold_secret_key.coeffs.clone().as_ref())
@@ -69,7 +78,7 @@ impl ShareDecryptionCircuitData {
                             })?;
 
                         let sk_sss_u64 = share_manager
-                            .generate_secret_shares_from_poly(sk_poly.clone(), &mut rng)
+                            .generate_secret_shares_from_poly(sk_poly.clone(), rng)
                             .map_err(|e| {
                                 CircuitsErrors::Sample(format!(
                                     "Failed to generate secret shares: {:?}",
@@ -81,7 +90,7 @@ impl ShareDecryptionCircuitData {
                     }
                     DkgInputType::SmudgingNoise => {
                         let esi_coeffs = trbfv
-                            .generate_smudging_error(sd.z as usize, sd.lambda as usize, &mut rng)
+                            .generate_smudging_error(sd.z as usize, sd.lambda as usize, rng)
                             .map_err(|e| {
                                 CircuitsErrors::Sample(format!(
                                     "Failed to generate smudging error: {:?}",
@@ -128,7 +137,7 @@ impl ShareDecryptionCircuitData {
                     |e| CircuitsErrors::Sample(format!("Failed to encode plaintext: {:?}", e)),
                 )?;
 
-                let ct = dkg_public_key.try_encrypt(&pt, &mut rng).map_err(|e| {
+                let ct = dkg_public_key.try_encrypt(&pt, rng).map_err(|e| {
                     CircuitsErrors::Sample(format!("Failed to encrypt plaintext: {:?}", e))
                 })?;
 
@@ -22,7 +22,7 @@ use crate::utils::{compute_modulus_bit, compute_msg_bit};
 use crate::CircuitsErrors;
 use crate::{calculate_bit_width, crt_polynomial_to_toml_json};
 use crate::{compute_q_mod_t, compute_q_product};
-use crate::{CircuitComputation, Computation};
+use crate::{CircuitComputation, Computation, PresetParams};
 use e3_fhe_params::build_pair_for_preset;
 use e3_fhe_params::BfvPreset;
 use e3_polynomial::CrtPolynomial;
@@ -140,14 +140,10 @@ pub struct Inputs {
     pub msg_commitment: BigInt,
 }
 
-impl Computation for Configs {
-    type Preset = BfvPreset;
-    type Data = ShareEncryptionCircuitData;
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, CircuitsErrors> {
-        let (_, dkg_params) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Sample(e.to_string()))?;
+impl Configs {
+    /// Computes the configs from an explicit parameter pair.
+    pub fn from_params(params: &PresetParams) -> Result<Self, CircuitsErrors> {
+        let dkg_params = &params.dkg;
 
         let moduli = dkg_params.moduli().to_vec();
         let t = dkg_params.plaintext();
@@ -156,8 +152,8 @@ impl Computation for Configs {
         let q_mod_t_centered = compute_q_mod_t_centered(&moduli, t);
         let k0is = compute_k0is(&moduli, t)?;
 
-        let bounds = Bounds::compute(preset, data)?;
-        let bits = Bits::compute(preset, &bounds)?;
+        let bounds = Bounds::from_params(params)?;
+        let bits = Bits::from_bounds(&bounds);
 
         Ok(Configs {
             t: t as usize,
@@ -171,12 +167,28 @@ impl Computation for Configs {
     }
 }
 
+impl Computation for Configs {
+    type Preset = BfvPreset;
+    type Data = ShareEncryptionCircuitData;
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, CircuitsErrors> {
+        Self::from_params(&PresetParams::for_preset(preset)?)
+    }
+}
+
 impl Computation for Bits {
     type Preset = BfvPreset;
     type Data = Bounds;
     type Error = crate::utils::ZkHelpersUtilsError;
 
     fn compute(_: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
+        Ok(Self::from_bounds(data))
+    }
+}
+
+impl Bits {
+    pub fn from_bounds(data: &Bounds) -> Self {
         let max_pk_bound = data.pk_bounds.iter().max().unwrap();
         let max_r2_bound = data.r2_bounds.iter().max().unwrap();
         let max_p1_bound = data.p1_bounds.iter().max().unwrap();
@@ -200,7 +212,7 @@ impl Computation for Bits {
         let p1_bit = calculate_bit_width(BigInt::from(max_p1_bound.clone()));
         let p2_bit = calculate_bit_width(BigInt::from(max_p2_bound.clone()));
 
-        Ok(Bits {
+        Bits {
             pk_bit,
             ct_bit,
             u_bit,
@@ -211,7 +223,7 @@ impl Computation for Bits {
             r2_bit,
             p1_bit,
             p2_bit,
-        })
+        }
     }
 }
 
@@ -221,8 +233,13 @@ impl Computation for Bounds {
     type Error = CircuitsErrors;
 
     fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, Self::Error> {
-        let (_, dkg_params) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Sample(e.to_string()))?;
+        Self::from_params(&PresetParams::for_preset(preset)?)
+    }
+}
+
+impl Bounds {
+    pub fn from_params(params: &PresetParams) -> Result<Self, CircuitsErrors> {
+        let dkg_params = &params.dkg;
 
         let n = BigInt::from(dkg_params.degree());
         let ctx = dkg_params.context_at_level(0)?;
@@ -18,6 +18,7 @@ use fhe::bfv::Plaintext;
 use fhe::bfv::{PublicKey, SecretKey};
 use fhe::trbfv::{ShareManager, TRBFV};
 use fhe_traits::FheEncoder;
+use rand::{CryptoRng, RngCore};
 
 impl ShareEncryptionCircuitData {
     /// Generates sample data for the share-encryption circuit (encrypts a share row under DKG pk).
@@ -27,15 +28,32 @@ impl ShareEncryptionCircuitData {
         dkg_input_type: DkgInputType,
         num_ciphertexts: u128, // z in the search defaults
         lambda: u32,
+    ) -> Result<Self, CircuitsErrors> {
+        Self::generate_sample_with_rng(
+            preset,
+            committee,
+            dkg_input_type,
+            num_ciphertexts,
+            lambda,
+            &mut rand::rng(),
+        )
+    }
+
+    /// Same as [`Self::generate_sample`], drawing all randomness from `rng`.
+    pub fn generate_sample_with_rng<R: RngCore + CryptoRng + Clone>(
+        preset: BfvPreset,
+        committee: CiphernodesCommittee,
+        dkg_input_type: DkgInputType,
+        num_ciphertexts: u128, // z in the search defaults
+        lambda: u32,
+        rng: &mut R,
     ) -> Result<Self, CircuitsErrors> {
         let (threshold_params, dkg_params) = build_pair_for_preset(preset).map_err(|e| {
             CircuitsErrors::Sample(format!("Failed to build pair for preset: {:?}", e))
         })?;
 
-        let mut rng = rand::rng();
-
-        let dkg_secret_key = SecretKey::random(&dkg_params, &mut rng);
-        let dkg_public_key = PublicKey::new(&dkg_secret_key, &mut rng);
+        let dkg_secret_key = SecretKey::random(&dkg_params, rng);
+        let dkg_public_key = PublicKey::new(&dkg_secret_key, rng);
 
         let trbfv = TRBFV::new(committee.n, committee.threshold, threshold_params.clone())
             .map_err(|e| CircuitsErrors::Sample(format!("Failed to create TRBFV: {:?}", e)))?;
@@ -44,7 +62,7 @@ impl ShareEncryptionCircuitData {
 
         let share_row = match dkg_input_type {
             DkgInputType::SecretKey => {
-                let threshold_secret_key = SecretKey::random(&threshold_params, &mut rng);
+                let threshold_secret_key = SecretKey::random(&threshold_params, rng);
 
                 let sk_poly = share_manager
                     .coeffs_to_poly_level0(threshold_secret_key.coeffs.clone().as_ref())
@@ -56,7 +74,7 @@ impl ShareEncryptionCircuitData {
                     })?;
 
                 let sk_sss_u64 = share_manager
-                    .generate_secret_shares_from_poly(sk_poly.clone(), &mut rng)
+                    .generate_secret_shares_from_poly(sk_poly.clone(), rng)
                     .map_err(|e| {
                         CircuitsErrors::Sample(format!("Failed to generate secret shares: {:?}", e))
                     })?;
@@ -65,7 +83,7 @@ impl ShareEncryptionCircuitData {
             }
             DkgInputType::SmudgingNoise => {
                 let esi_coeffs = trbfv
-                    .generate_smudging_error(num_ciphertexts as usize, lambda as usize, &mut rng)
+                    .generate_smudging_error(num_ciphertexts as usize, lambda as usize, rng)
                     .map_err(|e| {
                         CircuitsErrors::Sample(format!(
                             "Failed to generate smudging error: {:?}",
@@ -95,7 +113,7 @@ impl ShareEncryptionCircuitData {
             .map_err(|e| CircuitsErrors::Sample(format!("Failed to encode plaintext: {:?}", e)))?;
 
         let (_ct, u_rns, e0_rns, e1_rns) = dkg_public_key
-            .try_encrypt_extended(&pt, &mut rng)
+            .try_encrypt_extended(&pt, rng)
             .map_err(|e| CircuitsErrors::Sample(format!("Failed to encrypt extended: {:?}", e)))?;
 
         Ok(ShareEncryptionCircuitData {
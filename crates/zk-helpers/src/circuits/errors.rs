@@ -25,6 +25,8 @@ pub enum CircuitsErrors {
     ZkHelpers(#[from] ZkHelpersUtilsError),
     #[error("Sample error: {0}")]
     Sample(String),
+    #[error("Preset bundle error: {0}")]
+    Bundle(#[from] e3_fhe_params::BundleError),
     #[error("Serde JSON error: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error("Unexpected error: {0}")]
@@ -9,14 +9,20 @@ pub mod commitments;
 pub mod computation;
 pub mod errors;
 pub mod output_layout;
+pub mod preset_configs;
+pub mod program;
 
 pub use codegen::{
     write_artifacts, write_toml, Artifacts, CircuitCodegen, CodegenConfigs, CodegenToml,
 };
 pub use commitments::*;
-pub use computation::{CircuitComputation, Computation};
+pub use computation::{CircuitComputation, Computation, PresetParams};
 pub use errors::CircuitsErrors;
 pub use output_layout::*;
+pub use preset_configs::{
+    generate_preset_configs, write_preset_bundle, write_preset_configs, PresetConfigs,
+};
+pub use program::ProgramCircuit;
 
 pub mod dkg;
 pub mod threshold;
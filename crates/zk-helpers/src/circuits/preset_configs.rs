@@ -0,0 +1,819 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Noir configs for presets loaded from a preset file.
+//!
+//! Emits `configs/<preset>/{mod,threshold,dkg,parity}.nr` laid out like the checked-in
+//! `configs/secure` module: the parameter globals, the CRP, every circuit's bit widths, bounds and
+//! `*_CONFIGS`, and the parity matrix for the selected committee. [`write_preset_configs`] also
+//! registers the module in `configs/mod.nr`; [`write_preset_bundle`] adds it to a searched
+//! [`PresetBundle`].
+
+use crate::ciphernodes_committee::CiphernodesCommitteeSize;
+use crate::dkg::share_computation::utils::parity_matrix_constant_string;
+use crate::math::{
+    compute_q_inverse_mod_t, compute_q_mod_t, compute_q_mod_t_centered, compute_q_product,
+};
+use crate::threshold::pk_generation::utils::crp_matrix_constant_string;
+use crate::utils::join_display;
+use crate::{dkg, threshold};
+use crate::{CircuitsErrors, PresetParams};
+use e3_fhe_params::{ParamSetDefinition, PresetBundle, PresetDefinition, PresetRegistry};
+use num_bigint::BigUint;
+use std::path::{Path, PathBuf};
+
+const LICENSE_HEADER: &str = r#"// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+"#;
+
+/// Modules already checked in under `circuits/lib/src/configs`; a preset may not overwrite them.
+const RESERVED_CONFIG_MODULES: [&str; 4] = ["committee", "default", "insecure", "secure"];
+
+/// Files of a generated preset module, relative to its directory.
+pub const PRESET_CONFIG_FILES: [&str; 4] = ["mod.nr", "threshold.nr", "dkg.nr", "parity.nr"];
+
+/// Generated config module for one preset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetConfigs {
+    /// Noir module name (directory under `configs/`).
+    pub module: String,
+    /// `mod.nr` content.
+    pub mod_nr: String,
+    /// `threshold.nr` content.
+    pub threshold: String,
+    /// `dkg.nr` content.
+    pub dkg: String,
+    /// `parity.nr` content: the parity matrix for the committee the configs were generated for.
+    pub parity: String,
+}
+
+/// Builds the threshold and DKG configs for `preset` and `committee`.
+///
+/// The smudging bounds and the parity matrix depend on the committee, so the module has to be
+/// regenerated when the active committee changes.
+pub fn generate_preset_configs(
+    preset: &PresetDefinition,
+    committee: CiphernodesCommitteeSize,
+) -> Result<PresetConfigs, CircuitsErrors> {
+    let module = preset.config_module();
+    if RESERVED_CONFIG_MODULES.contains(&module.as_str()) {
+        return Err(CircuitsErrors::Other(format!(
+            "preset {} maps to the checked-in config module `{module}`; pick another name",
+            preset.name
+        )));
+    }
+    let params = PresetParams::from_definition(preset)?;
+
+    Ok(PresetConfigs {
+        mod_nr: format!("{LICENSE_HEADER}\npub mod dkg;\npub mod parity;\npub mod threshold;\n"),
+        threshold: format!(
+            "{LICENSE_HEADER}\n{}",
+            threshold_configs(preset, &params, committee)?
+        ),
+        dkg: format!(
+            "{LICENSE_HEADER}\n{}",
+            dkg_configs(preset, &module, &params, committee)?
+        ),
+        parity: format!(
+            "{LICENSE_HEADER}{}",
+            parity_configs(preset, &module, &params, committee)?
+        ),
+        module,
+    })
+}
+
+fn parity_configs(
+    preset: &PresetDefinition,
+    module: &str,
+    params: &PresetParams,
+    committee: CiphernodesCommitteeSize,
+) -> Result<String, CircuitsErrors> {
+    let values = committee.values();
+    let matrix = parity_matrix_constant_string(&params.threshold, values.n, values.threshold)?;
+
+    Ok(format!(
+        r#"//
+// Reed-Solomon parity matrix for the {committee} committee under the {name} preset.
+// Dimensions: [L_THRESHOLD][N_PARTIES - T][N_PARTIES + 1].
+
+use crate::configs::committee::{committee}::{{N_PARTIES, T}};
+pub use crate::configs::{module}::threshold::L as L_THRESHOLD;
+
+{matrix}
+"#,
+        committee = committee.as_str(),
+        name = preset.name,
+    ))
+}
+
+fn section(title: &str) -> String {
+    format!(
+        r#"/************************************
+-------------------------------------
+{title}
+-------------------------------------
+************************************/
+"#
+    )
+}
+
+fn threshold_configs(
+    preset: &PresetDefinition,
+    params: &PresetParams,
+    committee: CiphernodesCommitteeSize,
+) -> Result<String, CircuitsErrors> {
+    let q = compute_q_product(&preset.threshold.moduli);
+    let q_inverse_mod_t = compute_q_inverse_mod_t(&q, preset.threshold.plaintext_modulus)?;
+
+    let pk_generation =
+        threshold::pk_generation::computation::Configs::from_params(params, &committee.values())?;
+    let pk_aggregation = threshold::pk_aggregation::computation::Configs::from_params(params);
+    let user_data_encryption =
+        threshold::user_data_encryption::computation::Configs::from_params(params)?;
+    let share_decryption = threshold::share_decryption::computation::Configs::from_params(params)?;
+    let decrypted_shares_aggregation =
+        threshold::decrypted_shares_aggregation::computation::Configs::from_params(params)?;
+
+    // B_enc ≈ sqrt(3 * error1_variance)
+    let b_enc = (BigUint::from(3u32) * params.threshold.get_error1_variance()).sqrt();
+
+    let pk = &pk_generation;
+    let ude = &user_data_encryption;
+    let sd = &share_decryption;
+    let dsa = &decrypted_shares_aggregation;
+
+    Ok(format!(
+        r#"use crate::core::threshold::decrypted_shares_aggregation::Configs as DecryptedSharesAggregationConfigs;
+use crate::core::threshold::pk_aggregation::Configs as PkAggregationConfigs;
+use crate::core::threshold::pk_generation::Configs as PkGenerationConfigs;
+use crate::core::threshold::share_decryption::Configs as ShareDecryptionConfigs;
+use crate::core::threshold::user_data_encryption_ct0::Configs as UserDataEncryptionCt0Configs;
+use crate::core::threshold::user_data_encryption_ct1::Configs as UserDataEncryptionCt1Configs;
+use crate::math::polynomial::Polynomial;
+
+/// Threshold BFV parameter set search defaults configurations.
+/// These are for the {name} preset.
+pub global PARAMS_SEARCH_N: Field = {search_n};
+pub global PARAMS_SEARCH_Z: Field = {search_z};
+
+{crp}
+
+{globals}pub global Q_INVERSE_MOD_T: Field = {q_inverse_mod_t};
+
+{pk_generation_section}
+pub global PK_GENERATION_BIT_EEK: u32 = {pk_bit_eek};
+pub global PK_GENERATION_BIT_SK: u32 = {pk_bit_sk};
+pub global PK_GENERATION_BIT_E_SM: u32 = {pk_bit_e_sm};
+pub global PK_GENERATION_BIT_R1: u32 = {pk_bit_r1};
+pub global PK_GENERATION_BIT_R2: u32 = {pk_bit_r2};
+pub global PK_GENERATION_BIT_PK: u32 = {pk_bit_pk};
+
+pub global PK_GENERATION_EEK_BOUND: Field = {pk_eek_bound};
+pub global PK_GENERATION_SK_BOUND: Field = {pk_sk_bound};
+pub global PK_GENERATION_E_SM_BOUND: Field = {pk_e_sm_bound};
+pub global PK_GENERATION_R1_BOUNDS: [Field; L] = [{pk_r1_bounds}];
+pub global PK_GENERATION_R2_BOUNDS: [Field; L] = [{pk_r2_bounds}];
+
+pub global PK_GENERATION_B_ENC: Field = {b_enc};
+
+pub global PK_GENERATION_CONFIGS: PkGenerationConfigs<N, L> = PkGenerationConfigs::new(
+    QIS,
+    PK_GENERATION_EEK_BOUND,
+    PK_GENERATION_SK_BOUND,
+    PK_GENERATION_E_SM_BOUND,
+    PK_GENERATION_R1_BOUNDS,
+    PK_GENERATION_R2_BOUNDS,
+);
+
+{pk_aggregation_section}
+pub global PK_AGGREGATION_BIT_PK: u32 = {pk_aggregation_bit_pk};
+pub global PK_AGGREGATION_CONFIGS: PkAggregationConfigs<L> = PkAggregationConfigs::new(QIS);
+
+{user_data_encryption_section}
+pub global USER_DATA_ENCRYPTION_BIT_PK: u32 = {ude_bit_pk};
+pub global USER_DATA_ENCRYPTION_BIT_CT: u32 = {ude_bit_ct};
+pub global USER_DATA_ENCRYPTION_BIT_U: u32 = {ude_bit_u};
+pub global USER_DATA_ENCRYPTION_BIT_E0: u32 = {ude_bit_e0};
+pub global USER_DATA_ENCRYPTION_BIT_E1: u32 = {ude_bit_e1};
+pub global USER_DATA_ENCRYPTION_BIT_K: u32 = {ude_bit_k};
+pub global USER_DATA_ENCRYPTION_BIT_R1: u32 = {ude_bit_r1};
+pub global USER_DATA_ENCRYPTION_BIT_R2: u32 = {ude_bit_r2};
+pub global USER_DATA_ENCRYPTION_BIT_P1: u32 = {ude_bit_p1};
+pub global USER_DATA_ENCRYPTION_BIT_P2: u32 = {ude_bit_p2};
+
+pub global USER_DATA_ENCRYPTION_K0IS: [Field; L] = [{ude_k0is}];
+pub global USER_DATA_ENCRYPTION_PK_BOUNDS: [Field; L] = [{ude_pk_bounds}];
+pub global USER_DATA_ENCRYPTION_E0_BOUND: Field = {ude_e0_bound};
+pub global USER_DATA_ENCRYPTION_E1_BOUND: Field = {ude_e1_bound};
+pub global USER_DATA_ENCRYPTION_U_BOUND: Field = {ude_u_bound};
+pub global USER_DATA_ENCRYPTION_K1_LOW_BOUND: Field = {ude_k1_low_bound};
+pub global USER_DATA_ENCRYPTION_K1_UP_BOUND: Field = {ude_k1_up_bound};
+pub global USER_DATA_ENCRYPTION_R1_LOW_BOUNDS: [Field; L] = [{ude_r1_low_bounds}];
+pub global USER_DATA_ENCRYPTION_R1_UP_BOUNDS: [Field; L] = [{ude_r1_up_bounds}];
+pub global USER_DATA_ENCRYPTION_R2_BOUNDS: [Field; L] = [{ude_r2_bounds}];
+pub global USER_DATA_ENCRYPTION_P1_BOUNDS: [Field; L] = [{ude_p1_bounds}];
+pub global USER_DATA_ENCRYPTION_P2_BOUNDS: [Field; L] = [{ude_p2_bounds}];
+
+{ct0_section}
+pub global USER_DATA_ENCRYPTION_CT0_CONFIGS: UserDataEncryptionCt0Configs<N, L> = UserDataEncryptionCt0Configs::new(
+    QIS,
+    USER_DATA_ENCRYPTION_K0IS,
+    USER_DATA_ENCRYPTION_E0_BOUND,
+    USER_DATA_ENCRYPTION_U_BOUND,
+    USER_DATA_ENCRYPTION_R1_LOW_BOUNDS,
+    USER_DATA_ENCRYPTION_R1_UP_BOUNDS,
+    USER_DATA_ENCRYPTION_R2_BOUNDS,
+    USER_DATA_ENCRYPTION_K1_LOW_BOUND,
+    USER_DATA_ENCRYPTION_K1_UP_BOUND,
+);
+
+{ct1_section}
+pub global USER_DATA_ENCRYPTION_CT1_CONFIGS: UserDataEncryptionCt1Configs<N, L> = UserDataEncryptionCt1Configs::new(
+    QIS,
+    USER_DATA_ENCRYPTION_E1_BOUND,
+    USER_DATA_ENCRYPTION_U_BOUND,
+    USER_DATA_ENCRYPTION_P1_BOUNDS,
+    USER_DATA_ENCRYPTION_P2_BOUNDS,
+);
+
+{share_decryption_section}
+pub global THRESHOLD_SHARE_DECRYPTION_BIT_CT: u32 = {sd_bit_ct};
+pub global THRESHOLD_SHARE_DECRYPTION_BIT_SK: u32 = {sd_bit_sk};
+pub global THRESHOLD_SHARE_DECRYPTION_BIT_E_SM: u32 = {sd_bit_e_sm};
+pub global THRESHOLD_SHARE_DECRYPTION_BIT_R1: u32 = {sd_bit_r1};
+pub global THRESHOLD_SHARE_DECRYPTION_BIT_R2: u32 = {sd_bit_r2};
+pub global THRESHOLD_SHARE_DECRYPTION_BIT_D: u32 = {sd_bit_d};
+pub global THRESHOLD_SHARE_DECRYPTION_BIT_D_NATIVE: u32 = {sd_bit_d_native};
+
+pub global THRESHOLD_SHARE_DECRYPTION_R1_BOUNDS: [Field; L] = [{sd_r1_bounds}];
+pub global THRESHOLD_SHARE_DECRYPTION_R2_BOUNDS: [Field; L] = [{sd_r2_bounds}];
+
+pub global THRESHOLD_SHARE_DECRYPTION_CONFIGS: ShareDecryptionConfigs<L> = ShareDecryptionConfigs::new(
+    QIS,
+    THRESHOLD_SHARE_DECRYPTION_R1_BOUNDS,
+    THRESHOLD_SHARE_DECRYPTION_R2_BOUNDS,
+);
+
+{decrypted_shares_aggregation_section}
+pub global DECRYPTED_SHARES_AGGREGATION_BIT_NOISE: u32 = {dsa_bit_noise};
+pub global DECRYPTED_SHARES_AGGREGATION_BIT_D_NATIVE: u32 = {dsa_bit_d_native};
+
+pub global DECRYPTED_SHARES_AGGREGATION_CONFIGS: DecryptedSharesAggregationConfigs<L> =
+    DecryptedSharesAggregationConfigs::new(QIS, PLAINTEXT_MODULUS, Q_INVERSE_MOD_T);
+"#,
+        name = preset.name,
+        search_n = preset.search.n,
+        search_z = preset.search.z,
+        crp = crp_matrix_constant_string(&params.threshold)?,
+        globals = param_set_globals(
+            &format!("threshold {} preset", preset.name),
+            preset.degree,
+            &preset.threshold,
+        ),
+        pk_generation_section = section("pk_generation (CIRCUIT 1 - PUBLIC KEY THRESHOLD BFV)"),
+        pk_bit_eek = pk.bits.eek_bit,
+        pk_bit_sk = pk.bits.sk_bit,
+        pk_bit_e_sm = pk.bits.e_sm_bit,
+        pk_bit_r1 = pk.bits.r1_bit,
+        pk_bit_r2 = pk.bits.r2_bit,
+        pk_bit_pk = pk.bits.pk_bit,
+        pk_eek_bound = pk.bounds.eek_bound,
+        pk_sk_bound = pk.bounds.sk_bound,
+        pk_e_sm_bound = pk.bounds.e_sm_bound,
+        pk_r1_bounds = join_display(&pk.bounds.r1_bounds, ", "),
+        pk_r2_bounds = join_display(&pk.bounds.r2_bounds, ", "),
+        pk_aggregation_section = section("pk_aggregation (CIRCUIT 5)"),
+        pk_aggregation_bit_pk = pk_aggregation.bits.pk_bit,
+        user_data_encryption_section = section("user_data_encryption (USED FOR DATA ENCRYPTION)"),
+        ude_bit_pk = ude.bits.pk_bit,
+        ude_bit_ct = ude.bits.ct_bit,
+        ude_bit_u = ude.bits.u_bit,
+        ude_bit_e0 = ude.bits.e0_bit,
+        ude_bit_e1 = ude.bits.e1_bit,
+        ude_bit_k = ude.bits.k_bit,
+        ude_bit_r1 = ude.bits.r1_bit,
+        ude_bit_r2 = ude.bits.r2_bit,
+        ude_bit_p1 = ude.bits.p1_bit,
+        ude_bit_p2 = ude.bits.p2_bit,
+        ude_k0is = join_display(&ude.k0is, ", "),
+        ude_pk_bounds = join_display(&ude.bounds.pk_bounds, ", "),
+        ude_e0_bound = ude.bounds.e0_bound,
+        ude_e1_bound = ude.bounds.e1_bound,
+        ude_u_bound = ude.bounds.u_bound,
+        ude_k1_low_bound = ude.bounds.k1_low_bound,
+        ude_k1_up_bound = ude.bounds.k1_up_bound,
+        ude_r1_low_bounds = join_display(&ude.bounds.r1_low_bounds, ", "),
+        ude_r1_up_bounds = join_display(&ude.bounds.r1_up_bounds, ", "),
+        ude_r2_bounds = join_display(&ude.bounds.r2_bounds, ", "),
+        ude_p1_bounds = join_display(&ude.bounds.p1_bounds, ", "),
+        ude_p2_bounds = join_display(&ude.bounds.p2_bounds, ", "),
+        ct0_section = section("user_data_encryption_ct0 (CIRCUIT A - CT0 ENCRYPTION)"),
+        ct1_section = section("user_data_encryption_ct1 (CIRCUIT B - CT1 ENCRYPTION)"),
+        share_decryption_section =
+            section("share_decryption (CIRCUIT 6 - THRESHOLD BFV SHARE DECRYPTION)"),
+        sd_bit_ct = sd.bits.ct_bit,
+        sd_bit_sk = sd.bits.sk_bit,
+        sd_bit_e_sm = sd.bits.e_sm_bit,
+        sd_bit_r1 = sd.bits.r1_bit,
+        sd_bit_r2 = sd.bits.r2_bit,
+        sd_bit_d = sd.bits.d_bit,
+        sd_bit_d_native = sd.bits.d_native_bit,
+        sd_r1_bounds = join_display(&sd.bounds.r1_bounds, ", "),
+        sd_r2_bounds = join_display(&sd.bounds.r2_bounds, ", "),
+        decrypted_shares_aggregation_section = section("decrypted_shares_aggregation (CIRCUIT 7)"),
+        dsa_bit_noise = dsa.bits.noise_bit,
+        dsa_bit_d_native = dsa.bits.d_native_bit,
+    ))
+}
+
+fn dkg_configs(
+    preset: &PresetDefinition,
+    module: &str,
+    params: &PresetParams,
+    committee: CiphernodesCommitteeSize,
+) -> Result<String, CircuitsErrors> {
+    let pk = dkg::pk::computation::Configs::from_params(params);
+    let share_computation =
+        dkg::share_computation::computation::Configs::from_params(params, committee.values().n)?;
+    let share_encryption = dkg::share_encryption::computation::Configs::from_params(params)?;
+    let share_decryption = dkg::share_decryption::computation::Bits::from_params(params);
+
+    let sc = &share_computation.bits;
+    let se = &share_encryption;
+
+    Ok(format!(
+        r#"pub use crate::configs::{module}::threshold::{{
+    L as L_THRESHOLD, QIS as QIS_THRESHOLD,
+    THRESHOLD_SHARE_DECRYPTION_BIT_SK as SHARE_DECRYPTION_BIT_AGG,
+}};
+use crate::core::dkg::share_computation::Configs as ShareComputationConfigs;
+use crate::core::dkg::share_encryption::Configs as ShareEncryptionConfigs;
+
+{globals}
+// Parity matrix is sized for the {committee} committee and this preset's threshold QIS;
+// see `parity.nr`.
+pub use crate::configs::{module}::parity::PARITY_MATRIX;
+
+{pk_section}
+// pk - bit parameters
+
+pub global PK_BIT_PK: u32 = {pk_bit_pk};
+
+{share_computation_sk_section}
+// share_computation_sk - bit parameters
+pub global SHARE_COMPUTATION_BIT_SHARE: u32 = {sc_bit_share};
+pub global SHARE_COMPUTATION_SK_BIT_SECRET: u32 = {sc_bit_sk_secret};
+
+// share_computation_sk - configs
+pub global SHARE_COMPUTATION_SK_CONFIGS: ShareComputationConfigs<L_THRESHOLD> =
+    ShareComputationConfigs::new(QIS_THRESHOLD);
+
+{share_computation_e_sm_section}
+// share_computation_e_sm - bit parameters
+pub global SHARE_COMPUTATION_E_SM_BIT_SECRET: u32 = {sc_bit_e_sm_secret};
+
+// verify_shares - configs
+pub global SHARE_COMPUTATION_E_SM_CONFIGS: ShareComputationConfigs<L_THRESHOLD> =
+    ShareComputationConfigs::new(QIS_THRESHOLD);
+
+{share_encryption_section}
+pub global SHARE_ENCRYPTION_BIT_PK: u32 = {se_bit_pk};
+pub global SHARE_ENCRYPTION_BIT_CT: u32 = {se_bit_ct};
+pub global SHARE_ENCRYPTION_BIT_U: u32 = {se_bit_u};
+pub global SHARE_ENCRYPTION_BIT_E0: u32 = {se_bit_e0};
+pub global SHARE_ENCRYPTION_BIT_E1: u32 = {se_bit_e1};
+pub global SHARE_ENCRYPTION_BIT_MSG: u32 = {se_bit_msg};
+pub global SHARE_ENCRYPTION_BIT_R1: u32 = {se_bit_r1};
+pub global SHARE_ENCRYPTION_BIT_R2: u32 = {se_bit_r2};
+pub global SHARE_ENCRYPTION_BIT_P1: u32 = {se_bit_p1};
+pub global SHARE_ENCRYPTION_BIT_P2: u32 = {se_bit_p2};
+
+pub global SHARE_ENCRYPTION_K0IS: [Field; L] = [{se_k0is}];
+pub global SHARE_ENCRYPTION_PK_BOUNDS: [Field; L] = [{se_pk_bounds}];
+pub global SHARE_ENCRYPTION_E0_BOUND: Field = {se_e0_bound};
+pub global SHARE_ENCRYPTION_E1_BOUND: Field = {se_e1_bound};
+pub global SHARE_ENCRYPTION_U_BOUND: Field = {se_u_bound};
+pub global SHARE_ENCRYPTION_R1_LOW_BOUNDS: [Field; L] = [{se_r1_low_bounds}];
+pub global SHARE_ENCRYPTION_R1_UP_BOUNDS: [Field; L] = [{se_r1_up_bounds}];
+pub global SHARE_ENCRYPTION_R2_BOUNDS: [Field; L] = [{se_r2_bounds}];
+pub global SHARE_ENCRYPTION_P1_BOUNDS: [Field; L] = [{se_p1_bounds}];
+pub global SHARE_ENCRYPTION_P2_BOUNDS: [Field; L] = [{se_p2_bounds}];
+pub global SHARE_ENCRYPTION_MSG_BOUND: Field = {se_msg_bound};
+
+pub global SHARE_ENCRYPTION_CONFIGS: ShareEncryptionConfigs<L> = ShareEncryptionConfigs::new(
+    PLAINTEXT_MODULUS,
+    Q_MOD_T,
+    QIS,
+    SHARE_ENCRYPTION_K0IS,
+    SHARE_ENCRYPTION_PK_BOUNDS,
+    SHARE_ENCRYPTION_E0_BOUND,
+    SHARE_ENCRYPTION_E1_BOUND,
+    SHARE_ENCRYPTION_U_BOUND,
+    SHARE_ENCRYPTION_R1_LOW_BOUNDS,
+    SHARE_ENCRYPTION_R1_UP_BOUNDS,
+    SHARE_ENCRYPTION_R2_BOUNDS,
+    SHARE_ENCRYPTION_P1_BOUNDS,
+    SHARE_ENCRYPTION_P2_BOUNDS,
+    SHARE_ENCRYPTION_MSG_BOUND,
+);
+
+{share_decryption_section}
+pub global SHARE_DECRYPTION_BIT_MSG: u32 = {sd_bit_msg};
+// SHARE_DECRYPTION_BIT_AGG: see `pub use` of `THRESHOLD_SHARE_DECRYPTION_BIT_SK` (C6 `BIT_SK`).
+"#,
+        globals = param_set_globals(
+            &format!("DKG {} preset", preset.name),
+            preset.degree,
+            &preset.dkg,
+        ),
+        committee = committee.as_str(),
+        pk_section = section("pk (CIRCUIT 0)"),
+        pk_bit_pk = pk.bits.pk_bit,
+        share_computation_sk_section = section("share_computation_sk (CIRCUIT 2a)"),
+        sc_bit_share = sc.bit_share,
+        sc_bit_sk_secret = sc.bit_sk_secret,
+        share_computation_e_sm_section = section("share_computation_e_sm (CIRCUIT 2b)"),
+        sc_bit_e_sm_secret = sc.bit_e_sm_secret,
+        share_encryption_section =
+            section("share_encryption_sk (CIRCUIT 3a)\nshare_encryption_e_sm (CIRCUIT 3b)"),
+        se_bit_pk = se.bits.pk_bit,
+        se_bit_ct = se.bits.ct_bit,
+        se_bit_u = se.bits.u_bit,
+        se_bit_e0 = se.bits.e0_bit,
+        se_bit_e1 = se.bits.e1_bit,
+        se_bit_msg = se.bits.msg_bit,
+        se_bit_r1 = se.bits.r1_bit,
+        se_bit_r2 = se.bits.r2_bit,
+        se_bit_p1 = se.bits.p1_bit,
+        se_bit_p2 = se.bits.p2_bit,
+        se_k0is = join_display(&se.k0is, ", "),
+        se_pk_bounds = join_display(&se.bounds.pk_bounds, ", "),
+        se_e0_bound = se.bounds.e0_bound,
+        se_e1_bound = se.bounds.e1_bound,
+        se_u_bound = se.bounds.u_bound,
+        se_r1_low_bounds = join_display(&se.bounds.r1_low_bounds, ", "),
+        se_r1_up_bounds = join_display(&se.bounds.r1_up_bounds, ", "),
+        se_r2_bounds = join_display(&se.bounds.r2_bounds, ", "),
+        se_p1_bounds = join_display(&se.bounds.p1_bounds, ", "),
+        se_p2_bounds = join_display(&se.bounds.p2_bounds, ", "),
+        se_msg_bound = se.bounds.msg_bound,
+        share_decryption_section = section(
+            "share_decryption_sk (CIRCUIT 4a - BFV DECRYPTION SK)\nshare_decryption_e_sm (CIRCUIT 4b - BFV DECRYPTION E_SM)"
+        ),
+        sd_bit_msg = share_decryption.msg_bit,
+    ))
+}
+
+fn param_set_globals(label: &str, degree: usize, set: &ParamSetDefinition) -> String {
+    let t = set.plaintext_modulus;
+    let q = compute_q_product(&set.moduli);
+    let qis = set
+        .moduli
+        .iter()
+        .map(|q| q.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        r#"// Global configs for {label}
+pub global N: u32 = {degree};
+pub global L: u32 = {};
+pub global QIS: [Field; L] = [{qis}];
+pub global PLAINTEXT_MODULUS: Field = {t};
+pub global Q_MOD_T: Field = {};
+pub global Q_MOD_T_CENTERED: Field = {};
+"#,
+        set.moduli.len(),
+        compute_q_mod_t(&q, t),
+        compute_q_mod_t_centered(&set.moduli, t),
+    )
+}
+
+/// Writes the preset module to `configs_root/<module>/`, registers it in `configs_root/mod.nr`
+/// and returns the module directory.
+pub fn write_preset_configs(
+    configs: &PresetConfigs,
+    configs_root: &Path,
+) -> Result<PathBuf, CircuitsErrors> {
+    let dir = configs_root.join(&configs.module);
+    std::fs::create_dir_all(&dir)?;
+    for (file, content) in PRESET_CONFIG_FILES.into_iter().zip([
+        &configs.mod_nr,
+        &configs.threshold,
+        &configs.dkg,
+        &configs.parity,
+    ]) {
+        std::fs::write(dir.join(file), content)?;
+    }
+    register_config_module(configs_root, &configs.module)?;
+    Ok(dir)
+}
+
+/// Adds `pub mod <module>;` to `configs_root/mod.nr`, creating the file if needed.
+fn register_config_module(configs_root: &Path, module: &str) -> Result<PathBuf, CircuitsErrors> {
+    let path = configs_root.join("mod.nr");
+    let mut content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => format!("{LICENSE_HEADER}\n"),
+        Err(e) => return Err(e.into()),
+    };
+    let declaration = format!("pub mod {module};");
+    if !content.lines().any(|line| line.trim() == declaration) {
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(&declaration);
+        content.push('\n');
+        std::fs::write(&path, content)?;
+    }
+    Ok(path)
+}
+
+/// Checks that `preset` can be deployed next to the built-in presets with `committee`: its
+/// on-chain id must be free and its parameters must have been searched for at least as many
+/// parties as the committee has.
+pub fn check_bundle_target(
+    preset: &PresetDefinition,
+    committee: CiphernodesCommitteeSize,
+) -> Result<(), CircuitsErrors> {
+    if let Some(existing) = PresetRegistry::default().resolve_on_chain(preset.on_chain_id) {
+        return Err(CircuitsErrors::Other(format!(
+            "on-chain id {} is already used by the built-in preset {}",
+            preset.on_chain_id,
+            existing.name()
+        )));
+    }
+    let parties = committee.values().n as u64;
+    if preset.search.n < parties {
+        return Err(CircuitsErrors::Other(format!(
+            "preset {} was searched for n = {} parties but the {committee} committee has {parties}",
+            preset.name, preset.search.n
+        )));
+    }
+    Ok(())
+}
+
+/// Writes `bundle` into `dir` together with its Noir configs for `committee` under
+/// `dir/configs/`, after [`check_bundle_target`].
+///
+/// Returns every file written.
+pub fn write_preset_bundle(
+    bundle: &PresetBundle,
+    dir: &Path,
+    committee: CiphernodesCommitteeSize,
+) -> Result<Vec<PathBuf>, CircuitsErrors> {
+    check_bundle_target(&bundle.definition, committee)?;
+    let mut files = bundle.write(dir)?;
+    let configs = generate_preset_configs(&bundle.definition, committee)?;
+    let configs_dir = write_preset_configs(&configs, &dir.join("configs"))?;
+    files.extend(
+        PRESET_CONFIG_FILES
+            .into_iter()
+            .map(|file| configs_dir.join(file)),
+    );
+    files.push(dir.join("configs").join("mod.nr"));
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use e3_fhe_params::constants::{defaults::DEFAULT_SECURE_LAMBDA, search_defaults, secure_8192};
+    use e3_fhe_params::search::bfv::{bfv_search, bfv_search_second_param, BfvSearchConfig};
+    use e3_fhe_params::{BundleSpec, SearchDefinition, SecurityReport, SecurityTier};
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+
+    fn secure_8192_definition() -> PresetDefinition {
+        PresetDefinition {
+            name: "CUSTOM_SECURE_8192".to_string(),
+            on_chain_id: 2,
+            security: SecurityTier::SECURE,
+            degree: secure_8192::DEGREE,
+            search: SearchDefinition {
+                n: 10,
+                z: search_defaults::SEARCH_Z as u64,
+                k: search_defaults::SEARCH_K as u64,
+                lambda: 50,
+                b: search_defaults::B as u64,
+                b_chi: search_defaults::B_CHI as u64,
+            },
+            threshold: ParamSetDefinition {
+                plaintext_modulus: secure_8192::threshold::PLAINTEXT_MODULUS,
+                moduli: secure_8192::threshold::MODULI.to_vec(),
+                error1_variance: Some(secure_8192::threshold::ERROR1_VARIANCE.to_string()),
+            },
+            dkg: ParamSetDefinition {
+                plaintext_modulus: secure_8192::dkg::PLAINTEXT_MODULUS,
+                moduli: secure_8192::dkg::MODULI.to_vec(),
+                error1_variance: None,
+            },
+        }
+    }
+
+    fn checked_in(path: &str) -> String {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../circuits/lib/src/configs");
+        std::fs::read_to_string(root.join(path)).unwrap()
+    }
+
+    /// `pub global` statements keyed by name, with whitespace and trailing commas removed so
+    /// `nargo fmt` line wrapping does not matter.
+    fn globals(source: &str) -> BTreeMap<String, String> {
+        let mut globals = BTreeMap::new();
+        let mut rest = source;
+        while let Some(start) = rest.find("pub global ") {
+            rest = &rest[start + "pub global ".len()..];
+            let mut depth = 0i32;
+            let end = rest
+                .char_indices()
+                .find(|&(_, c)| {
+                    match c {
+                        '[' | '(' => depth += 1,
+                        ']' | ')' => depth -= 1,
+                        _ => {}
+                    }
+                    c == ';' && depth == 0
+                })
+                .map(|(i, _)| i)
+                .unwrap();
+            let statement: String = rest[..end].split_whitespace().collect();
+            let statement = statement.replace(",]", "]").replace(",)", ")");
+            let name = statement.split(':').next().unwrap().to_string();
+            globals.insert(name, statement);
+            rest = &rest[end..];
+        }
+        globals
+    }
+
+    /// Generating configs for the secure-8192 parameters must reproduce the checked-in
+    /// `configs/secure` module and the `minimum` committee parity matrix.
+    #[test]
+    fn matches_checked_in_secure_configs() {
+        let configs =
+            generate_preset_configs(&secure_8192_definition(), CiphernodesCommitteeSize::Minimum)
+                .unwrap();
+        assert_eq!(configs.module, "custom_secure_8192");
+
+        assert_eq!(
+            globals(&configs.threshold),
+            globals(&checked_in("secure/threshold.nr"))
+        );
+        assert_eq!(globals(&configs.dkg), globals(&checked_in("secure/dkg.nr")));
+        assert_eq!(
+            globals(&configs.parity),
+            globals(&checked_in("committee/minimum/parity_secure.nr"))
+        );
+        assert!(configs
+            .dkg
+            .contains("pub use crate::configs::custom_secure_8192::parity::PARITY_MATRIX;"));
+        assert!(configs
+            .parity
+            .contains("use crate::configs::committee::minimum::{N_PARTIES, T};"));
+    }
+
+    #[test]
+    fn writes_module_directory_and_registers_it() {
+        let configs =
+            generate_preset_configs(&secure_8192_definition(), CiphernodesCommitteeSize::Minimum)
+                .unwrap();
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("mod.nr"),
+            format!("{LICENSE_HEADER}\npub mod default;\npub mod secure;\n"),
+        )
+        .unwrap();
+
+        let dir = write_preset_configs(&configs, temp.path()).unwrap();
+        assert_eq!(dir, temp.path().join("custom_secure_8192"));
+        for file in PRESET_CONFIG_FILES {
+            assert!(dir.join(file).exists(), "{file} not written");
+        }
+
+        write_preset_configs(&configs, temp.path()).unwrap();
+        let mod_nr = std::fs::read_to_string(temp.path().join("mod.nr")).unwrap();
+        assert!(mod_nr.contains("pub mod default;\npub mod secure;\n"));
+        assert_eq!(mod_nr.matches("pub mod custom_secure_8192;").count(), 1);
+    }
+
+    #[test]
+    fn writes_bundle_with_configs() {
+        let definition = secure_8192_definition();
+        let report = SecurityReport::evaluate(&definition, 20, definition.search.n);
+        let bundle = PresetBundle { definition, report };
+        let temp = TempDir::new().unwrap();
+        let files =
+            write_preset_bundle(&bundle, temp.path(), CiphernodesCommitteeSize::Minimum).unwrap();
+
+        for file in [
+            PresetBundle::PRESET_FILE,
+            PresetBundle::SECURITY_FILE,
+            PresetBundle::THRESHOLD_PARAMS_FILE,
+            "configs/mod.nr",
+            "configs/custom_secure_8192/threshold.nr",
+            "configs/custom_secure_8192/parity.nr",
+        ] {
+            assert!(files.contains(&temp.path().join(file)), "{file} not listed");
+        }
+        assert_eq!(PresetBundle::load(temp.path()).unwrap(), bundle);
+
+        let threshold =
+            std::fs::read_to_string(temp.path().join("configs/custom_secure_8192/threshold.nr"))
+                .unwrap();
+        assert_eq!(
+            threshold,
+            generate_preset_configs(&bundle.definition, CiphernodesCommitteeSize::Minimum)
+                .unwrap()
+                .threshold
+        );
+    }
+
+    /// A bundle written to disk carries the checked-in secure configs for the secure-8192
+    /// parameters.
+    #[test]
+    fn written_bundle_matches_checked_in_secure_configs() {
+        let definition = secure_8192_definition();
+        let report = SecurityReport::evaluate(&definition, 20, definition.search.n);
+        let bundle = PresetBundle { definition, report };
+        let temp = TempDir::new().unwrap();
+        write_preset_bundle(&bundle, temp.path(), CiphernodesCommitteeSize::Minimum).unwrap();
+
+        let written = |file: &str| {
+            std::fs::read_to_string(temp.path().join("configs/custom_secure_8192").join(file))
+                .unwrap()
+        };
+        assert_eq!(
+            globals(&written("threshold.nr")),
+            globals(&checked_in("secure/threshold.nr"))
+        );
+        assert_eq!(
+            globals(&written("dkg.nr")),
+            globals(&checked_in("secure/dkg.nr"))
+        );
+        assert_eq!(
+            globals(&written("parity.nr")),
+            globals(&checked_in("committee/minimum/parity_secure.nr"))
+        );
+    }
+
+    /// A freshly searched bundle defines every global the circuits read from `configs/secure`,
+    /// so its module can stand in for it.
+    #[test]
+    fn searched_bundle_defines_every_checked_in_global() {
+        let config = BfvSearchConfig {
+            n: search_defaults::SEARCH_N,
+            z: search_defaults::SEARCH_Z,
+            k: search_defaults::SEARCH_K,
+            lambda: DEFAULT_SECURE_LAMBDA as u32,
+            b: search_defaults::B,
+            b_chi: search_defaults::B_CHI,
+            verbose: false,
+        };
+        let threshold = bfv_search(&config).unwrap();
+        let dkg = bfv_search_second_param(&config, &threshold).unwrap();
+        let spec = BundleSpec {
+            name: "SEARCHED_SECURE".to_string(),
+            on_chain_id: 9,
+            security: SecurityTier::SECURE,
+            program_depth: 20,
+            committee_size: search_defaults::SEARCH_N as u64,
+        };
+        let bundle = PresetBundle::from_search(spec, &config, &threshold, &dkg).unwrap();
+        let configs =
+            generate_preset_configs(&bundle.definition, CiphernodesCommitteeSize::Minimum).unwrap();
+
+        for (generated, checked_in_file) in [
+            (&configs.threshold, "secure/threshold.nr"),
+            (&configs.dkg, "secure/dkg.nr"),
+            (&configs.parity, "committee/minimum/parity_secure.nr"),
+        ] {
+            let generated = globals(generated);
+            let missing: Vec<_> = globals(&checked_in(checked_in_file))
+                .into_keys()
+                .filter(|name| !generated.contains_key(name))
+                .collect();
+            assert!(missing.is_empty(), "{checked_in_file}: missing {missing:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_undeployable_bundle_targets() {
+        let mut definition = secure_8192_definition();
+        definition.on_chain_id = 1;
+        assert!(check_bundle_target(&definition, CiphernodesCommitteeSize::Minimum).is_err());
+
+        let mut definition = secure_8192_definition();
+        definition.search.n = 5;
+        assert!(check_bundle_target(&definition, CiphernodesCommitteeSize::Minimum).is_ok());
+        assert!(check_bundle_target(&definition, CiphernodesCommitteeSize::Micro).is_err());
+
+        let mut definition = secure_8192_definition();
+        definition.name = "secure".to_string();
+        assert!(generate_preset_configs(&definition, CiphernodesCommitteeSize::Minimum).is_err());
+    }
+}
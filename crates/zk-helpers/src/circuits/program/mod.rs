@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Circuits supplied by E3 programs.
+//!
+//! Built-in circuits constrain the protocol; a program's own circuits constrain what users may
+//! encrypt (e.g. "the ballot is one-hot"). A [`ProgramCircuit`] derives its inputs from the
+//! user data encryption [`Inputs`] of the ciphertext it speaks about, which `e3-bfv-client`
+//! computes while encrypting, and is registered with a [`crate::CircuitRegistry`] like any
+//! other [`Circuit`]. See [`one_hot_ballot`] for a worked example.
+
+pub mod one_hot_ballot;
+
+pub use one_hot_ballot::OneHotBallotCircuit;
+
+use crate::threshold::user_data_encryption::Inputs;
+use crate::{Artifacts, Circuit, CircuitsErrors, CodegenConfigs};
+use e3_fhe_params::BfvPreset;
+
+/// A circuit that constrains the plaintext of a user ciphertext.
+pub trait ProgramCircuit: Circuit {
+    /// Inputs for the circuit's `main`, in the JSON layout of [`crate::Computation::to_json`].
+    fn inputs(
+        &self,
+        preset: BfvPreset,
+        encryption: &Inputs,
+    ) -> Result<serde_json::Value, CircuitsErrors>;
+
+    /// `configs.nr` the circuit is compiled with for `preset`.
+    fn configs(&self, preset: BfvPreset) -> Result<CodegenConfigs, CircuitsErrors>;
+
+    /// Prover.toml and configs.nr, as [`crate::CircuitCodegen::codegen`] produces them for
+    /// built-in circuits.
+    fn artifacts(
+        &self,
+        preset: BfvPreset,
+        encryption: &Inputs,
+    ) -> Result<Artifacts, CircuitsErrors> {
+        let toml = toml::to_string(&self.inputs(preset, encryption)?)?;
+        let configs = self.configs(preset)?;
+        Ok(Artifacts { toml, configs })
+    }
+}
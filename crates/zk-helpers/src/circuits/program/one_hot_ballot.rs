@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! One-hot ballot circuit (`circuits/bin/program/one_hot_ballot`).
+//!
+//! Proves that exactly one of the first `options` plaintext slots of a user ciphertext is 1 and
+//! every other slot is 0, and outputs the `k1_commitment` that ties the proof to the
+//! ciphertext's user data encryption proof.
+
+use crate::computation::DkgInputType;
+use crate::math::compute_q_mod_t_centered;
+use crate::program::ProgramCircuit;
+use crate::registry::Circuit;
+use crate::threshold::user_data_encryption::Inputs;
+use crate::{polynomial_to_toml_json, CircuitsErrors, CodegenConfigs};
+use e3_fhe_params::{build_pair_for_preset, BfvPreset, ParameterType};
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+#[derive(Debug, Clone, Copy)]
+pub struct OneHotBallotCircuit {
+    /// Number of ballot options, i.e. plaintext slots that may hold the vote.
+    pub options: usize,
+}
+
+impl Circuit for OneHotBallotCircuit {
+    const NAME: &'static str = "one-hot-ballot";
+    const PREFIX: &'static str = "ONE_HOT_BALLOT";
+    const SUPPORTED_PARAMETER: ParameterType = ParameterType::THRESHOLD;
+    const DKG_INPUT_TYPE: Option<DkgInputType> = None;
+}
+
+impl OneHotBallotCircuit {
+    /// `k1` coefficient of a slot holding 1: `k1 = [Q * m]_t`, centered. The circuit compares
+    /// slots with the same value, `Q_MOD_T_CENTERED` of the preset's threshold configs.
+    pub fn k1_one(preset: BfvPreset) -> Result<BigInt, CircuitsErrors> {
+        let (threshold_params, _) =
+            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Sample(e.to_string()))?;
+        Ok(compute_q_mod_t_centered(
+            threshold_params.moduli(),
+            threshold_params.plaintext(),
+        ))
+    }
+}
+
+impl ProgramCircuit for OneHotBallotCircuit {
+    /// Rejects ballots the circuit would not accept, so clients fail before proving.
+    fn inputs(
+        &self,
+        preset: BfvPreset,
+        encryption: &Inputs,
+    ) -> Result<serde_json::Value, CircuitsErrors> {
+        let coefficients = encryption.k1.coefficients();
+        if self.options > coefficients.len() {
+            return Err(CircuitsErrors::Other(format!(
+                "{} ballot options do not fit in {} plaintext slots",
+                self.options,
+                coefficients.len()
+            )));
+        }
+
+        let k1_one = Self::k1_one(preset)?;
+        let mut votes = 0;
+        // Coefficients are stored highest degree first; slot `i` is the coefficient of X^i.
+        for (i, slot) in coefficients.iter().rev().enumerate() {
+            if i < self.options && *slot == k1_one {
+                votes += 1;
+            } else if !slot.is_zero() {
+                return Err(CircuitsErrors::Other(format!(
+                    "ballot slot {i} is neither 0 nor 1"
+                )));
+            }
+        }
+        if votes != 1 {
+            return Err(CircuitsErrors::Other(format!(
+                "ballot must hold exactly one vote, found {votes}"
+            )));
+        }
+
+        Ok(serde_json::json!({ "k1": polynomial_to_toml_json(&encryption.k1) }))
+    }
+
+    /// The ballot's `k1` value of 1 comes from the preset's `lib` configs, so only the options
+    /// are circuit specific.
+    fn configs(&self, _preset: BfvPreset) -> Result<CodegenConfigs, CircuitsErrors> {
+        let prefix = <OneHotBallotCircuit as Circuit>::PREFIX;
+
+        Ok(format!(
+            r#"// Global configs for One-Hot Ballot circuit
+pub global {prefix}_OPTIONS: u32 = {};
+"#,
+            self.options,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computation::Computation;
+    use crate::registry::CircuitRegistry;
+    use crate::threshold::user_data_encryption::UserDataEncryptionCircuitData;
+    use fhe::bfv::{Encoding, Plaintext, PublicKey, SecretKey};
+    use fhe_traits::FheEncoder;
+    use std::sync::Arc;
+
+    const PRESET: BfvPreset = BfvPreset::InsecureThreshold512;
+
+    fn encrypt(ballot: &[u64]) -> Inputs {
+        let (threshold_params, _) = build_pair_for_preset(PRESET).unwrap();
+        let mut rng = rand::rng();
+        let secret_key = SecretKey::random(&threshold_params, &mut rng);
+        let public_key = PublicKey::new(&secret_key, &mut rng);
+        let plaintext = Plaintext::try_encode(ballot, Encoding::poly(), &threshold_params).unwrap();
+
+        Inputs::compute(
+            PRESET,
+            &UserDataEncryptionCircuitData {
+                public_key,
+                plaintext,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_one_hot_ballot_inputs() {
+        let circuit = OneHotBallotCircuit { options: 4 };
+
+        let inputs = circuit.inputs(PRESET, &encrypt(&[0, 0, 1, 0])).unwrap();
+        let k1 = inputs["k1"]["coefficients"].as_array().unwrap();
+        assert_eq!(k1.len(), PRESET.metadata().degree);
+
+        let artifacts = circuit.artifacts(PRESET, &encrypt(&[1])).unwrap();
+        assert!(artifacts.toml.contains("[k1]"));
+
+        for ballot in [&[0, 0, 0, 0][..], &[1, 1, 0, 0], &[0, 0, 0, 0, 1], &[0, 2]] {
+            assert!(
+                circuit.inputs(PRESET, &encrypt(ballot)).is_err(),
+                "accepted {ballot:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_one_hot_ballot_configs() {
+        // Matches the checked-in `circuits/bin/program/one_hot_ballot/src/configs.nr`.
+        let configs = OneHotBallotCircuit { options: 4 }.configs(PRESET).unwrap();
+        assert!(configs.contains("ONE_HOT_BALLOT_OPTIONS: u32 = 4;"));
+    }
+
+    #[test]
+    fn test_one_hot_ballot_k1_one_matches_lib_configs() {
+        // The circuit compares slots with `Q_MOD_T_CENTERED` of the preset's threshold configs
+        let threshold = std::fs::read_to_string(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../../circuits/lib/src/configs/insecure/threshold.nr"),
+        )
+        .unwrap();
+        let k1_one = OneHotBallotCircuit::k1_one(PRESET).unwrap();
+        assert!(threshold.contains(&format!("pub global Q_MOD_T_CENTERED: Field = {k1_one};")));
+    }
+
+    #[test]
+    fn test_one_hot_ballot_registers() {
+        let mut registry = CircuitRegistry::new();
+        registry.register(Arc::new(OneHotBallotCircuit { options: 4 }));
+
+        let circuit = registry
+            .get(<OneHotBallotCircuit as Circuit>::NAME)
+            .unwrap();
+        assert_eq!(circuit.supported_parameter(), ParameterType::THRESHOLD);
+        assert!(circuit.dkg_input_type().is_none());
+    }
+}
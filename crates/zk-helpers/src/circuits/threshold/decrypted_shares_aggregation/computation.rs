@@ -23,7 +23,7 @@ use crate::threshold::decrypted_shares_aggregation::circuit::DecryptedSharesAggr
 use crate::threshold::decrypted_shares_aggregation::circuit::DecryptedSharesAggregationCircuitData;
 use crate::threshold::decrypted_shares_aggregation::utils;
 use crate::CircuitsErrors;
-use crate::{CircuitComputation, Computation};
+use crate::{CircuitComputation, Computation, PresetParams};
 use e3_fhe_params::build_pair_for_preset;
 use e3_fhe_params::BfvPreset;
 use e3_polynomial::reduce;
@@ -109,33 +109,22 @@ pub struct Inputs {
     pub crt_quotients: CrtPolynomial,
 }
 
-impl Computation for Bounds {
-    type Preset = BfvPreset;
-    type Data = ();
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, Self::Error> {
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Other(e.to_string()))?;
-        let moduli = threshold_params.moduli();
-        let t = threshold_params.plaintext();
+impl Bounds {
+    pub fn from_params(params: &PresetParams) -> Self {
+        let moduli = params.threshold.moduli();
+        let t = params.threshold.plaintext();
         let q = utils::compute_q_product(moduli);
         let delta = utils::compute_delta(&q, t);
         let delta_half = utils::compute_delta_half(&delta);
-        Ok(Bounds { delta, delta_half })
+        Bounds { delta, delta_half }
     }
 }
 
-impl Computation for Bits {
-    type Preset = BfvPreset;
-    type Data = Bounds;
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
+impl Bits {
+    pub fn from_params(params: &PresetParams, data: &Bounds) -> Result<Self, CircuitsErrors> {
         let noise_bit = calculate_bit_width(BigInt::from(data.delta_half.clone()));
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Other(e.to_string()))?;
-        let ctx = threshold_params
+        let ctx = params
+            .threshold
             .context_at_level(0)
             .map_err(|e| CircuitsErrors::Other(format!("context_at_level: {:?}", e)))?;
         let mut d_native_bit = 0u32;
@@ -150,22 +139,17 @@ impl Computation for Bits {
     }
 }
 
-impl Computation for Configs {
-    type Preset = BfvPreset;
-    type Data = ();
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, Self::Error> {
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Other(e.to_string()))?;
-        let moduli = threshold_params.moduli().to_vec();
-        let t = threshold_params.plaintext();
+impl Configs {
+    /// Computes the configs from an explicit parameter pair.
+    pub fn from_params(params: &PresetParams) -> Result<Self, CircuitsErrors> {
+        let moduli = params.threshold.moduli().to_vec();
+        let t = params.threshold.plaintext();
         let q = utils::compute_q_product(&moduli);
         let q_mod_t = compute_q_mod_t(&q, t);
         let q_mod_t_centered = compute_q_mod_t_centered(&moduli, t);
         let q_inverse_mod_t = utils::compute_q_inverse_mod_t(&q, t)?;
-        let bounds = Bounds::compute(preset, &())?;
-        let bits = Bits::compute(preset, &bounds)?;
+        let bounds = Bounds::from_params(params);
+        let bits = Bits::from_params(params, &bounds)?;
         Ok(Configs {
             threshold: 0, // Not derived from preset; set by caller if needed.
             l: moduli.len(),
@@ -182,6 +166,36 @@ impl Computation for Configs {
     }
 }
 
+impl Computation for Bounds {
+    type Preset = BfvPreset;
+    type Data = ();
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, Self::Error> {
+        Ok(Self::from_params(&PresetParams::for_preset(preset)?))
+    }
+}
+
+impl Computation for Bits {
+    type Preset = BfvPreset;
+    type Data = Bounds;
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
+        Self::from_params(&PresetParams::for_preset(preset)?, data)
+    }
+}
+
+impl Computation for Configs {
+    type Preset = BfvPreset;
+    type Data = ();
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, Self::Error> {
+        Self::from_params(&PresetParams::for_preset(preset)?)
+    }
+}
+
 /// Truncate to first max_len coefficients (index 0 = constant term, ascending order).
 fn truncate_to_max_coeffs(v: &[BigInt], max_len: usize) -> Vec<BigInt> {
     v.iter().take(max_len).cloned().collect()
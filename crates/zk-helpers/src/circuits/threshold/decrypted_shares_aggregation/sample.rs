@@ -25,6 +25,7 @@ use fhe_math::rq::{Poly, PowerBasis};
 use fhe_traits::FheDecoder;
 use fhe_traits::{FheEncoder, FheEncrypter};
 use ndarray::Array2;
+use rand::{CryptoRng, RngCore};
 use std::sync::Arc;
 
 struct Party {
@@ -44,6 +45,15 @@ impl DecryptedSharesAggregationCircuitData {
     pub fn generate_sample(
         preset: BfvPreset,
         committee: CiphernodesCommittee,
+    ) -> Result<Self, CircuitsErrors> {
+        Self::generate_sample_with_rng(preset, committee, &mut rand::rng())
+    }
+
+    /// Same as [`Self::generate_sample`], drawing all randomness from `rng`.
+    pub fn generate_sample_with_rng<R: RngCore + CryptoRng>(
+        preset: BfvPreset,
+        committee: CiphernodesCommittee,
+        rng: &mut R,
     ) -> Result<Self, CircuitsErrors> {
         let (threshold_params, _) = build_pair_for_preset(preset).map_err(|e| {
             CircuitsErrors::Sample(format!("Failed to build pair for preset: {:?}", e))
@@ -60,22 +70,16 @@ impl DecryptedSharesAggregationCircuitData {
 
         let trbfv = TRBFV::new(num_parties, threshold, threshold_params.clone())
             .map_err(|e| CircuitsErrors::Sample(format!("Failed to create TRBFV: {:?}", e)))?;
-        let mut rng = rand::rng();
-
         let crp = create_deterministic_crp_from_default_seed(&threshold_params);
 
         let ctx = threshold_params.context_at_level(0).unwrap();
 
         let mut parties: Vec<Party> = (0..num_parties)
             .map(|_| -> Result<Party, CircuitsErrors> {
-                let sk_share = SecretKey::random(&threshold_params, &mut rng);
-                let pk_share =
-                    PublicKeyShare::new(&sk_share, crp.clone(), &mut rng).map_err(|e| {
-                        CircuitsErrors::Sample(format!(
-                            "Failed to create public key share: {:?}",
-                            e
-                        ))
-                    })?;
+                let sk_share = SecretKey::random(&threshold_params, rng);
+                let pk_share = PublicKeyShare::new(&sk_share, crp.clone(), rng).map_err(|e| {
+                    CircuitsErrors::Sample(format!("Failed to create public key share: {:?}", e))
+                })?;
 
                 let mut share_manager =
                     ShareManager::new(num_parties, threshold, threshold_params.clone());
@@ -89,13 +93,13 @@ impl DecryptedSharesAggregationCircuitData {
                     })?;
 
                 let sk_sss = share_manager
-                    .generate_secret_shares_from_poly(sk_poly, &mut rng)
+                    .generate_secret_shares_from_poly(sk_poly, rng)
                     .map_err(|e| {
                         CircuitsErrors::Sample(format!("Failed to generate secret shares: {:?}", e))
                     })?;
 
                 let esi_coeffs = trbfv
-                    .generate_smudging_error(sd.z as usize, sd.lambda as usize, &mut rng)
+                    .generate_smudging_error(sd.z as usize, sd.lambda as usize, rng)
                     .map_err(|e| {
                         CircuitsErrors::Sample(format!(
                             "Failed to generate smudging error: {:?}",
@@ -106,7 +110,7 @@ impl DecryptedSharesAggregationCircuitData {
                     CircuitsErrors::Sample(format!("Failed to convert error to poly: {:?}", e))
                 })?;
                 let esi_sss = share_manager
-                    .generate_secret_shares_from_poly(esi_poly, &mut rng)
+                    .generate_secret_shares_from_poly(esi_poly, rng)
                     .map_err(|e| {
                         CircuitsErrors::Sample(format!("Failed to generate error shares: {:?}", e))
                     })?;
@@ -211,7 +215,7 @@ impl DecryptedSharesAggregationCircuitData {
         let pt = Plaintext::try_encode(&message, Encoding::poly(), &threshold_params)
             .map_err(|e| CircuitsErrors::Sample(format!("Failed to encode plaintext: {:?}", e)))?;
         let ciphertext = public_key
-            .try_encrypt(&pt, &mut rng)
+            .try_encrypt(&pt, rng)
             .map_err(|e| CircuitsErrors::Sample(format!("Failed to encrypt: {:?}", e)))?;
 
         let ciphertext = Arc::new(ciphertext);
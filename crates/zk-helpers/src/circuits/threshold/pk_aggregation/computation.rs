@@ -17,7 +17,7 @@ use crate::threshold::pk_aggregation::circuit::PkAggregationCircuit;
 use crate::threshold::pk_aggregation::circuit::PkAggregationCircuitData;
 use crate::threshold::pk_generation::utils::deterministic_crp_crt_polynomial;
 use crate::CircuitsErrors;
-use crate::{CircuitComputation, Computation};
+use crate::{CircuitComputation, Computation, PresetParams};
 use e3_fhe_params::build_pair_for_preset;
 use e3_fhe_params::BfvPreset;
 use e3_polynomial::CrtPolynomial;
@@ -80,27 +80,55 @@ pub struct Inputs {
     pub crp: CrtPolynomial,
 }
 
+impl Configs {
+    /// Computes the configs from an explicit parameter pair.
+    pub fn from_params(params: &PresetParams) -> Self {
+        let threshold_params = &params.threshold;
+        let moduli = threshold_params.moduli().to_vec();
+
+        Configs {
+            n: threshold_params.degree(),
+            l: moduli.len(),
+            moduli,
+            bits: Bits::from_params(params),
+            bounds: Bounds::from_params(params),
+        }
+    }
+}
+
+impl Bits {
+    pub fn from_params(params: &PresetParams) -> Self {
+        Bits {
+            pk_bit: compute_modulus_bit(&params.threshold),
+        }
+    }
+}
+
+impl Bounds {
+    pub fn from_params(params: &PresetParams) -> Self {
+        let mut pk_bound_max = BigUint::from(0u32);
+
+        for &qi in params.threshold.moduli() {
+            let qi_bound: BigUint = (BigUint::from(qi) - 1u32) / 2u32;
+
+            if qi_bound > pk_bound_max {
+                pk_bound_max = qi_bound;
+            }
+        }
+
+        Bounds {
+            pk_bound: pk_bound_max,
+        }
+    }
+}
+
 impl Computation for Configs {
     type Preset = BfvPreset;
     type Data = ();
     type Error = CircuitsErrors;
 
     fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, CircuitsErrors> {
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Other(e.to_string()))?;
-
-        let moduli = threshold_params.moduli().to_vec();
-
-        let bounds = Bounds::compute(preset, &())?;
-        let bits = Bits::compute(preset, &())?;
-
-        Ok(Configs {
-            n: threshold_params.degree(),
-            l: moduli.len(),
-            moduli,
-            bits,
-            bounds,
-        })
+        Ok(Self::from_params(&PresetParams::for_preset(preset)?))
     }
 }
 
@@ -110,12 +138,7 @@ impl Computation for Bits {
     type Error = CircuitsErrors;
 
     fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, Self::Error> {
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Other(e.to_string()))?;
-
-        let pk_bit = compute_modulus_bit(&threshold_params);
-
-        Ok(Bits { pk_bit })
+        Ok(Self::from_params(&PresetParams::for_preset(preset)?))
     }
 }
 
@@ -125,24 +148,7 @@ impl Computation for Bounds {
     type Error = CircuitsErrors;
 
     fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, Self::Error> {
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Other(e.to_string()))?;
-
-        let mut pk_bound_max = BigUint::from(0u32);
-
-        for &qi in threshold_params.moduli() {
-            let qi_bound: BigUint = (BigUint::from(qi) - 1u32) / 2u32;
-
-            if qi_bound > pk_bound_max {
-                pk_bound_max = qi_bound;
-            }
-        }
-
-        let bounds = Bounds {
-            pk_bound: pk_bound_max,
-        };
-
-        Ok(bounds)
+        Ok(Self::from_params(&PresetParams::for_preset(preset)?))
     }
 }
 
@@ -16,18 +16,26 @@ use e3_fhe_params::{build_pair_for_preset, create_deterministic_crp_from_default
 use e3_polynomial::CrtPolynomial;
 use fhe::bfv::{PublicKey, SecretKey};
 use fhe::mbfv::{AggregateIter, PublicKeyShare};
+use rand::{CryptoRng, RngCore};
 
 impl PkAggregationCircuitData {
     pub fn generate_sample(
         preset: BfvPreset,
         committee: CiphernodesCommittee,
+    ) -> Result<Self, CircuitsErrors> {
+        Self::generate_sample_with_rng(preset, committee, &mut rand::rng())
+    }
+
+    /// Same as [`Self::generate_sample`], drawing all randomness from `rng`.
+    pub fn generate_sample_with_rng<R: RngCore + CryptoRng>(
+        preset: BfvPreset,
+        committee: CiphernodesCommittee,
+        rng: &mut R,
     ) -> Result<Self, CircuitsErrors> {
         let (threshold_params, _) = build_pair_for_preset(preset).map_err(|e| {
             CircuitsErrors::Sample(format!("Failed to build pair for preset: {:?}", e))
         })?;
 
-        let mut rng = rand::rng();
-
         let crp = create_deterministic_crp_from_default_seed(&threshold_params);
 
         // Generate public key shares for each party
@@ -35,9 +43,9 @@ impl PkAggregationCircuitData {
         let mut pk0_shares = Vec::new();
 
         for _ in 0..committee.h {
-            let sk = SecretKey::random(&threshold_params, &mut rng);
+            let sk = SecretKey::random(&threshold_params, rng);
             // Create PublicKeyShare - this generates the p0_share with a specific error term
-            let pk_share = PublicKeyShare::new(&sk, crp.clone(), &mut rng).map_err(|e| {
+            let pk_share = PublicKeyShare::new(&sk, crp.clone(), rng).map_err(|e| {
                 CircuitsErrors::Sample(format!("Failed to create public key share: {:?}", e))
             })?;
 
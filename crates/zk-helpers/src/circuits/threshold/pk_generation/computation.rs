@@ -18,7 +18,7 @@ use crate::threshold::pk_generation::circuit::PkGenerationCircuit;
 use crate::threshold::pk_generation::circuit::PkGenerationCircuitData;
 use crate::threshold::pk_generation::utils::deterministic_crp_crt_polynomial;
 use crate::CircuitsErrors;
-use crate::{CircuitComputation, Computation};
+use crate::{CircuitComputation, Computation, PresetParams};
 use e3_fhe_params::build_pair_for_preset;
 use e3_fhe_params::BfvPreset;
 use e3_polynomial::CrtPolynomial;
@@ -101,19 +101,17 @@ pub struct Inputs {
     pub pk0is: CrtPolynomial,
 }
 
-impl Computation for Configs {
-    type Preset = BfvPreset;
-    type Data = CiphernodesCommittee;
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, committee: &Self::Data) -> Result<Self, CircuitsErrors> {
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Other(e.to_string()))?;
-
+impl Configs {
+    /// Computes the configs for `committee` from an explicit parameter pair.
+    pub fn from_params(
+        params: &PresetParams,
+        committee: &CiphernodesCommittee,
+    ) -> Result<Self, CircuitsErrors> {
+        let threshold_params = &params.threshold;
         let moduli = threshold_params.moduli().to_vec();
 
-        let bounds = Bounds::compute(preset, committee)?;
-        let bits = Bits::compute(preset, &bounds)?;
+        let bounds = Bounds::from_params(params, committee)?;
+        let bits = Bits::from_params(params, &bounds);
 
         Ok(Configs {
             n: threshold_params.degree(),
@@ -125,12 +123,8 @@ impl Computation for Configs {
     }
 }
 
-impl Computation for Bits {
-    type Preset = BfvPreset;
-    type Data = Bounds;
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
+impl Bits {
+    pub fn from_params(params: &PresetParams, data: &Bounds) -> Self {
         // Calculate bit widths for each bound type
         let eek_bit = calculate_bit_width(BigInt::from(data.eek_bound.clone()));
         let sk_bit = calculate_bit_width(BigInt::from(data.sk_bound.clone()));
@@ -138,9 +132,7 @@ impl Computation for Bits {
 
         // pk_bit: centered representation uses (max(qi) - 1) / 2 as the bound,
         // matching compute_modulus_bit() used in C5 (pk_aggregation).
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Other(e.to_string()))?;
-        let pk_bit = crate::compute_modulus_bit(&threshold_params);
+        let pk_bit = crate::compute_modulus_bit(&params.threshold);
 
         // For r1, use the maximum of all low and up bounds
         let mut r1_bit = 0;
@@ -154,25 +146,23 @@ impl Computation for Bits {
             r2_bit = r2_bit.max(calculate_bit_width(BigInt::from(bound.clone())));
         }
 
-        Ok(Bits {
+        Bits {
             eek_bit,
             sk_bit,
             e_sm_bit,
             r1_bit,
             r2_bit,
             pk_bit,
-        })
+        }
     }
 }
 
-impl Computation for Bounds {
-    type Preset = BfvPreset;
-    type Data = CiphernodesCommittee;
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, committee: &Self::Data) -> Result<Self, Self::Error> {
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Other(e.to_string()))?;
+impl Bounds {
+    pub fn from_params(
+        params: &PresetParams,
+        committee: &CiphernodesCommittee,
+    ) -> Result<Self, CircuitsErrors> {
+        let threshold_params = &params.threshold;
 
         let committee = crate::ciphernodes_committee::canonical_committee_for_circuit(committee)
             .map_err(|e| CircuitsErrors::Other(e.to_string()))?;
@@ -186,15 +176,13 @@ impl Computation for Bounds {
         let sk_bound = SecretKey::sk_bound();
         let eek_bound = cbd_bound;
 
-        let sd = preset
-            .search_defaults()
-            .ok_or_else(|| CircuitsErrors::Other("missing search defaults".to_string()))?;
+        let sd = params.search_defaults()?;
 
         let smudging_config = SmudgingBoundCalculatorConfig::new(
             threshold_params.clone(),
             committee_n,
             sd.z as usize,
-            preset.metadata().lambda,
+            sd.lambda as usize,
         );
         let smudging_calculator = SmudgingBoundCalculator::new(smudging_config);
         let e_sm_bound = smudging_calculator.calculate_sm_bound().map_err(|e| {
@@ -245,6 +233,36 @@ impl Computation for Bounds {
     }
 }
 
+impl Computation for Configs {
+    type Preset = BfvPreset;
+    type Data = CiphernodesCommittee;
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, committee: &Self::Data) -> Result<Self, CircuitsErrors> {
+        Self::from_params(&PresetParams::for_preset(preset)?, committee)
+    }
+}
+
+impl Computation for Bits {
+    type Preset = BfvPreset;
+    type Data = Bounds;
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
+        Ok(Self::from_params(&PresetParams::for_preset(preset)?, data))
+    }
+}
+
+impl Computation for Bounds {
+    type Preset = BfvPreset;
+    type Data = CiphernodesCommittee;
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, committee: &Self::Data) -> Result<Self, Self::Error> {
+        Self::from_params(&PresetParams::for_preset(preset)?, committee)
+    }
+}
+
 impl Computation for Inputs {
     type Preset = BfvPreset;
     type Data = PkGenerationCircuitData;
@@ -19,24 +19,32 @@ use fhe::{
     bfv::SecretKey,
     trbfv::{ShareManager, TRBFV},
 };
+use rand::{CryptoRng, RngCore};
 use std::ops::Deref;
 
 impl PkGenerationCircuitData {
     pub fn generate_sample(
         preset: BfvPreset,
         committee: CiphernodesCommittee,
+    ) -> Result<Self, CircuitsErrors> {
+        Self::generate_sample_with_rng(preset, committee, &mut rand::rng())
+    }
+
+    /// Same as [`Self::generate_sample`], drawing all randomness from `rng`.
+    pub fn generate_sample_with_rng<R: RngCore + CryptoRng>(
+        preset: BfvPreset,
+        committee: CiphernodesCommittee,
+        rng: &mut R,
     ) -> Result<Self, CircuitsErrors> {
         let (threshold_params, _) = build_pair_for_preset(preset).map_err(|e| {
             CircuitsErrors::Sample(format!("Failed to build pair for preset: {:?}", e))
         })?;
 
-        let mut rng = rand::rng();
-
-        let secret_key = SecretKey::random(&threshold_params, &mut rng);
+        let secret_key = SecretKey::random(&threshold_params, rng);
         let crp = create_deterministic_crp_from_default_seed(&threshold_params);
 
-        let (pk0_share, _, sk, e) =
-            PublicKeyShare::new_extended(&secret_key, crp.clone(), &mut rng).map_err(|e| {
+        let (pk0_share, _, sk, e) = PublicKeyShare::new_extended(&secret_key, crp.clone(), rng)
+            .map_err(|e| {
                 CircuitsErrors::Sample(format!("Failed to create public key share: {:?}", e))
             })?;
 
@@ -53,11 +61,8 @@ impl PkGenerationCircuitData {
         let share_manager = ShareManager::new(num_parties, threshold, threshold_params);
 
         // Generate smudging error coefficients
-        let esi_coeffs = trbfv.generate_smudging_error(
-            num_ciphertexts as usize,
-            preset_metadata.lambda,
-            &mut rng,
-        )?;
+        let esi_coeffs =
+            trbfv.generate_smudging_error(num_ciphertexts as usize, preset_metadata.lambda, rng)?;
 
         // Convert to polynomial in RNS representation
         // bigints_to_poly returns Zeroizing<Poly>, we need to clone the inner Poly
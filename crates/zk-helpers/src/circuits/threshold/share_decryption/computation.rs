@@ -22,7 +22,7 @@ use crate::decompose_residue;
 use crate::threshold::share_decryption::circuit::ShareDecryptionCircuit;
 use crate::threshold::share_decryption::circuit::ShareDecryptionCircuitData;
 use crate::CircuitsErrors;
-use crate::{CircuitComputation, Computation};
+use crate::{CircuitComputation, Computation, PresetParams};
 use e3_fhe_params::build_pair_for_preset;
 use e3_fhe_params::BfvPreset;
 use e3_polynomial::CrtPolynomial;
@@ -134,19 +134,14 @@ pub struct Inputs {
     pub ct_commitment: BigInt,
 }
 
-impl Computation for Configs {
-    type Preset = BfvPreset;
-    type Data = ();
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, CircuitsErrors> {
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Other(e.to_string()))?;
-
+impl Configs {
+    /// Computes the configs from an explicit parameter pair.
+    pub fn from_params(params: &PresetParams) -> Result<Self, CircuitsErrors> {
+        let threshold_params = &params.threshold;
         let moduli = threshold_params.moduli().to_vec();
 
-        let bounds = Bounds::compute(preset, &())?;
-        let bits = Bits::compute(preset, &bounds)?;
+        let bounds = Bounds::from_params(params)?;
+        let bits = Bits::from_params(params, &bounds);
 
         Ok(Configs {
             n: threshold_params.degree(),
@@ -158,12 +153,8 @@ impl Computation for Configs {
     }
 }
 
-impl Computation for Bits {
-    type Preset = BfvPreset;
-    type Data = Bounds;
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
+impl Bits {
+    pub fn from_params(params: &PresetParams, data: &Bounds) -> Self {
         // For r1, use the maximum of all low and up bounds
         let mut r1_bit = 0;
         for bound in data.r1_bounds.iter() {
@@ -176,11 +167,9 @@ impl Computation for Bits {
             r2_bit = r2_bit.max(calculate_bit_width(BigInt::from(bound.clone())));
         }
 
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Other(e.to_string()))?;
-        let d_native_bit = compute_native_crt_coeff_bit(threshold_params.moduli());
+        let d_native_bit = compute_native_crt_coeff_bit(params.threshold.moduli());
 
-        Ok(Bits {
+        Bits {
             ct_bit: r2_bit,
             sk_bit: r2_bit,
             e_sm_bit: r2_bit,
@@ -188,18 +177,13 @@ impl Computation for Bits {
             r2_bit,
             d_bit: r2_bit,
             d_native_bit,
-        })
+        }
     }
 }
 
-impl Computation for Bounds {
-    type Preset = BfvPreset;
-    type Data = ();
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, Self::Error> {
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Other(e.to_string()))?;
+impl Bounds {
+    pub fn from_params(params: &PresetParams) -> Result<Self, CircuitsErrors> {
+        let threshold_params = &params.threshold;
 
         let n = BigInt::from(threshold_params.degree());
         // Get cyclotomic degree and context at provided level
@@ -243,6 +227,36 @@ impl Computation for Bounds {
     }
 }
 
+impl Computation for Configs {
+    type Preset = BfvPreset;
+    type Data = ();
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, CircuitsErrors> {
+        Self::from_params(&PresetParams::for_preset(preset)?)
+    }
+}
+
+impl Computation for Bits {
+    type Preset = BfvPreset;
+    type Data = Bounds;
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
+        Ok(Self::from_params(&PresetParams::for_preset(preset)?, data))
+    }
+}
+
+impl Computation for Bounds {
+    type Preset = BfvPreset;
+    type Data = ();
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, Self::Error> {
+        Self::from_params(&PresetParams::for_preset(preset)?)
+    }
+}
+
 impl Computation for Inputs {
     type Preset = BfvPreset;
     type Data = ShareDecryptionCircuitData;
@@ -23,11 +23,21 @@ use fhe::{
 };
 use fhe_traits::{FheEncoder, FheEncrypter};
 use ndarray::ArrayView;
+use rand::{CryptoRng, RngCore};
 impl ShareDecryptionCircuitData {
     /// Generates a random secret key, public key, and plaintext for the given BFV parameters.
     pub fn generate_sample(
         preset: BfvPreset,
         committee: CiphernodesCommittee,
+    ) -> Result<Self, CircuitsErrors> {
+        Self::generate_sample_with_rng(preset, committee, &mut rand::rng())
+    }
+
+    /// Same as [`Self::generate_sample`], drawing all randomness from `rng`.
+    pub fn generate_sample_with_rng<R: RngCore + CryptoRng>(
+        preset: BfvPreset,
+        committee: CiphernodesCommittee,
+        rng: &mut R,
     ) -> Result<Self, CircuitsErrors> {
         let (threshold_params, _) = build_pair_for_preset(preset).map_err(|e| {
             CircuitsErrors::Sample(format!("Failed to build pair for preset: {:?}", e))
@@ -37,8 +47,6 @@ impl ShareDecryptionCircuitData {
             .search_defaults()
             .ok_or_else(|| CircuitsErrors::Sample("Preset has no search defaults".into()))?;
 
-        let mut rng = rand::rng();
-
         let num_parties = committee.n;
         let threshold = committee.threshold;
         let num_ciphertexts = sd.z as usize;
@@ -57,8 +65,8 @@ impl ShareDecryptionCircuitData {
         let mut pk_shares = Vec::new();
 
         for _ in 0..num_parties {
-            let sk = fhe::bfv::SecretKey::random(&threshold_params, &mut rng);
-            let pk_share = PublicKeyShare::new(&sk, crp.clone(), &mut rng).map_err(|e| {
+            let sk = fhe::bfv::SecretKey::random(&threshold_params, rng);
+            let pk_share = PublicKeyShare::new(&sk, crp.clone(), rng).map_err(|e| {
                 CircuitsErrors::Sample(format!("Failed to create public key share: {:?}", e))
             })?;
             party_secret_keys.push(sk);
@@ -74,7 +82,7 @@ impl ShareDecryptionCircuitData {
         let message = 1u64;
         let pt = Plaintext::try_encode(&[message], Encoding::poly(), &threshold_params)
             .map_err(|e| CircuitsErrors::Sample(format!("Failed to encode plaintext: {:?}", e)))?;
-        let ciphertext = public_key.try_encrypt(&pt, &mut rng)?;
+        let ciphertext = public_key.try_encrypt(&pt, rng)?;
 
         // Simulate party 0's perspective:
         // - Each party has their own secret key
@@ -99,7 +107,7 @@ impl ShareDecryptionCircuitData {
 
             let temp_trbfv = trbfv.clone();
             let sk_sss = temp_trbfv
-                .generate_secret_shares_from_poly(sk_poly, &mut rng)
+                .generate_secret_shares_from_poly(sk_poly, rng)
                 .map_err(|e| {
                     CircuitsErrors::Sample(format!("Failed to generate SK shares: {:?}", e))
                 })?;
@@ -107,7 +115,7 @@ impl ShareDecryptionCircuitData {
             all_party_sk_shares.push(sk_sss);
 
             let esi_coeffs = trbfv
-                .generate_smudging_error(num_ciphertexts, lambda, &mut rng)
+                .generate_smudging_error(num_ciphertexts, lambda, rng)
                 .map_err(|e| {
                     CircuitsErrors::Sample(format!("Failed to generate smudging error: {:?}", e))
                 })?;
@@ -115,7 +123,7 @@ impl ShareDecryptionCircuitData {
                 CircuitsErrors::Sample(format!("Failed to convert error to poly: {:?}", e))
             })?;
             let esi_sss = share_manager
-                .generate_secret_shares_from_poly(esi_poly, &mut rng)
+                .generate_secret_shares_from_poly(esi_poly, rng)
                 .map_err(|e| {
                     CircuitsErrors::Sample(format!("Failed to generate error shares: {:?}", e))
                 })?;
@@ -16,7 +16,7 @@ use crate::math::{cyclotomic_polynomial, decompose_residue, plaintext_poly_u64};
 use crate::threshold::user_data_encryption::circuit::UserDataEncryptionCircuit;
 use crate::threshold::user_data_encryption::circuit::UserDataEncryptionCircuitData;
 use crate::CircuitsErrors;
-use crate::{CircuitComputation, Computation};
+use crate::{CircuitComputation, Computation, PresetParams};
 use e3_fhe_params::build_pair_for_preset;
 use e3_fhe_params::BfvPreset;
 use e3_polynomial::CrtPolynomial;
@@ -30,6 +30,7 @@ use num_bigint::BigUint;
 use num_bigint::ToBigInt;
 use num_traits::Signed;
 use num_traits::ToPrimitive;
+use rand::{CryptoRng, RngCore};
 use rayon::iter::ParallelIterator;
 use rayon::prelude::ParallelBridge;
 use serde::{Deserialize, Serialize};
@@ -119,20 +120,16 @@ pub struct Inputs {
     pub ciphertext: Vec<u8>,
 }
 
-impl Computation for Configs {
-    type Preset = BfvPreset;
-    type Data = ();
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, CircuitsErrors> {
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Sample(e.to_string()))?;
+impl Configs {
+    /// Computes the configs from an explicit parameter pair.
+    pub fn from_params(params: &PresetParams) -> Result<Self, CircuitsErrors> {
+        let threshold_params = &params.threshold;
 
         let moduli = threshold_params.moduli().to_vec();
         let k0is = compute_k0is(threshold_params.moduli(), threshold_params.plaintext())?;
 
-        let bounds = Bounds::compute(preset, &())?;
-        let bits = Bits::compute(preset, &bounds)?;
+        let bounds = Bounds::from_params(params)?;
+        let bits = Bits::from_bounds(&bounds);
 
         Ok(Configs {
             n: threshold_params.degree(),
@@ -145,12 +142,28 @@ impl Computation for Configs {
     }
 }
 
+impl Computation for Configs {
+    type Preset = BfvPreset;
+    type Data = ();
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, CircuitsErrors> {
+        Self::from_params(&PresetParams::for_preset(preset)?)
+    }
+}
+
 impl Computation for Bits {
     type Preset = BfvPreset;
     type Data = Bounds;
     type Error = CircuitsErrors;
 
     fn compute(_: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
+        Ok(Self::from_bounds(data))
+    }
+}
+
+impl Bits {
+    pub fn from_bounds(data: &Bounds) -> Self {
         let max_pk_bound = data.pk_bounds.iter().max().unwrap();
 
         let pk_bit = calculate_bit_width(BigInt::from(max_pk_bound.clone()));
@@ -189,7 +202,7 @@ impl Computation for Bits {
             p2_bit = p2_bit.max(calculate_bit_width(BigInt::from(bound.clone())));
         }
 
-        Ok(Bits {
+        Bits {
             pk_bit,
             ct_bit,
             u_bit,
@@ -200,7 +213,7 @@ impl Computation for Bits {
             r2_bit,
             p1_bit,
             p2_bit,
-        })
+        }
     }
 }
 
@@ -210,8 +223,13 @@ impl Computation for Bounds {
     type Error = CircuitsErrors;
 
     fn compute(preset: Self::Preset, _: &Self::Data) -> Result<Self, Self::Error> {
-        let (threshold_params, _) =
-            build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Sample(e.to_string()))?;
+        Self::from_params(&PresetParams::for_preset(preset)?)
+    }
+}
+
+impl Bounds {
+    pub fn from_params(params: &PresetParams) -> Result<Self, CircuitsErrors> {
+        let threshold_params = &params.threshold;
 
         let n = BigInt::from(threshold_params.degree());
         let ctx = threshold_params.context_at_level(0)?;
@@ -323,12 +341,14 @@ impl Computation for Bounds {
     }
 }
 
-impl Computation for Inputs {
-    type Preset = BfvPreset;
-    type Data = UserDataEncryptionCircuitData;
-    type Error = CircuitsErrors;
-
-    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
+impl Inputs {
+    /// Like [`Computation::compute`], but encrypts `data.plaintext` with randomness drawn from
+    /// `rng`, so a seeded `rng` reproduces the same witness.
+    pub fn compute_with_rng<R: RngCore + CryptoRng>(
+        preset: BfvPreset,
+        data: &UserDataEncryptionCircuitData,
+        rng: &mut R,
+    ) -> Result<Self, CircuitsErrors> {
         let (threshold_params, _) =
             build_pair_for_preset(preset).map_err(|e| CircuitsErrors::Sample(e.to_string()))?;
 
@@ -350,9 +370,7 @@ impl Computation for Inputs {
         let cyclo = cyclotomic_polynomial(n);
 
         // Encrypt using the provided public key to ensure ciphertext matches the key.
-        let (ct, u, e0, e1) = data
-            .public_key
-            .try_encrypt_extended(&data.plaintext, &mut rand::rng())?;
+        let (ct, u, e0, e1) = data.public_key.try_encrypt_extended(&data.plaintext, rng)?;
 
         // Reconstruct e0 coefficients mod Q (CRT) for e0_quotient computation.
         let mut e0_mod_q = Polynomial::from_fhe_polynomial(&e0);
@@ -543,6 +561,16 @@ impl Computation for Inputs {
             ciphertext: ct.to_bytes(),
         })
     }
+}
+
+impl Computation for Inputs {
+    type Preset = BfvPreset;
+    type Data = UserDataEncryptionCircuitData;
+    type Error = CircuitsErrors;
+
+    fn compute(preset: Self::Preset, data: &Self::Data) -> Result<Self, Self::Error> {
+        Self::compute_with_rng(preset, data, &mut rand::rng())
+    }
 
     // Used as input for Nargo execution. Coefficients are JSON numbers when they fit in i64, else strings.
     fn to_json(&self) -> serde_json::Result<serde_json::Value> {
@@ -15,18 +15,25 @@ use crate::{
 use e3_fhe_params::{build_pair_for_preset, BfvPreset};
 use fhe::bfv::{Encoding, Plaintext, PublicKey, SecretKey};
 use fhe_traits::FheEncoder;
+use rand::{CryptoRng, RngCore};
 
 impl UserDataEncryptionCircuitData {
     /// Generates a random secret key, public key, and plaintext for the given BFV parameters.
     pub fn generate_sample(preset: BfvPreset) -> Result<Self, CircuitsErrors> {
+        Self::generate_sample_with_rng(preset, &mut rand::rng())
+    }
+
+    /// Same as [`Self::generate_sample`], drawing all randomness from `rng`.
+    pub fn generate_sample_with_rng<R: RngCore + CryptoRng>(
+        preset: BfvPreset,
+        rng: &mut R,
+    ) -> Result<Self, CircuitsErrors> {
         let (threshold_params, _) = build_pair_for_preset(preset).map_err(|e| {
             CircuitsErrors::Sample(format!("Failed to build pair for preset: {:?}", e))
         })?;
 
-        let mut rng = rand::rng();
-
-        let secret_key = SecretKey::random(&threshold_params, &mut rng);
-        let public_key = PublicKey::new(&secret_key, &mut rng);
+        let secret_key = SecretKey::random(&threshold_params, rng);
+        let public_key = PublicKey::new(&secret_key, rng);
 
         let plaintext = Plaintext::try_encode(&[1u64], Encoding::poly(), &threshold_params)
             .map_err(|e| CircuitsErrors::Sample(format!("Failed to encode plaintext: {:?}", e)))?;
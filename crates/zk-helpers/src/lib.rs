@@ -9,6 +9,7 @@ pub mod circuits;
 pub mod math;
 pub mod packing;
 pub mod registry;
+pub mod test_vectors;
 pub mod utils;
 
 pub use ciphernodes_committee::*;
@@ -16,4 +17,5 @@ pub use circuits::*;
 pub use math::*;
 pub use packing::*;
 pub use registry::*;
+pub use test_vectors::*;
 pub use utils::*;
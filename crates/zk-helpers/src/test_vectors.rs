@@ -0,0 +1,587 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Seeded, reproducible test vectors for every registered circuit.
+//!
+//! A [`VectorCase`] names a circuit, a threshold [`BfvPreset`], a committee size, the DKG input
+//! type (for circuits whose witness depends on it) and a seed. [`generate_vector`] draws the
+//! circuit's sample data from a ChaCha20 stream seeded with that seed and records what
+//! `computation.rs` derives from it: the prover inputs (`Prover.toml`) and the preset's
+//! `Configs`. Each vector also carries [`NegativeVector`]s, copies of the inputs with a single
+//! coefficient incremented by one, which must not reproduce the positive vector's execution.
+//!
+//! Vectors live under `<root>/v{TEST_VECTORS_VERSION}/<preset>/<committee>/<case>/`. Bump
+//! [`TEST_VECTORS_VERSION`] whenever sampling changes in a way that invalidates stored vectors,
+//! so stale directories are never compared against.
+
+use crate::ciphernodes_committee::CiphernodesCommitteeSize;
+use crate::computation::DkgInputType;
+use crate::dkg::{pk, share_computation, share_decryption, share_encryption};
+use crate::registry::Circuit;
+use crate::threshold::{
+    decrypted_shares_aggregation, pk_aggregation, pk_generation,
+    share_decryption as threshold_share_decryption, user_data_encryption,
+};
+use crate::{CircuitsErrors, Computation};
+use e3_fhe_params::BfvPreset;
+use num_bigint::BigInt;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Layout version of stored vectors; part of the directory path.
+pub const TEST_VECTORS_VERSION: u32 = 1;
+/// Number of single-coefficient corruptions recorded per vector.
+pub const NEGATIVE_VECTORS_PER_CASE: usize = 3;
+pub const DEFAULT_VECTOR_SEED: u64 = 1;
+
+const VECTOR_FILE: &str = "vector.json";
+
+/// Every circuit registered with `zk_cli`, in generation order.
+pub const VECTOR_CIRCUITS: [&str; 9] = [
+    <pk::PkCircuit as Circuit>::NAME,
+    <share_computation::ShareComputationCircuit as Circuit>::NAME,
+    <share_encryption::ShareEncryptionCircuit as Circuit>::NAME,
+    <share_decryption::ShareDecryptionCircuit as Circuit>::NAME,
+    <pk_generation::PkGenerationCircuit as Circuit>::NAME,
+    <pk_aggregation::PkAggregationCircuit as Circuit>::NAME,
+    <user_data_encryption::UserDataEncryptionCircuit as Circuit>::NAME,
+    <threshold_share_decryption::ShareDecryptionCircuit as Circuit>::NAME,
+    <decrypted_shares_aggregation::DecryptedSharesAggregationCircuit as Circuit>::NAME,
+];
+
+/// Everything needed to regenerate one vector.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VectorCase {
+    pub circuit: String,
+    /// Threshold preset; DKG circuits use its DKG counterpart.
+    pub preset: BfvPreset,
+    pub committee: CiphernodesCommitteeSize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dkg_input_type: Option<DkgInputType>,
+    pub seed: u64,
+}
+
+impl VectorCase {
+    /// Directory name, e.g. `share-computation-smudging-noise`.
+    pub fn id(&self) -> String {
+        match self.dkg_input_type {
+            Some(DkgInputType::SecretKey) => format!("{}-secret-key", self.circuit),
+            Some(DkgInputType::SmudgingNoise) => format!("{}-smudging-noise", self.circuit),
+            None => self.circuit.clone(),
+        }
+    }
+
+    pub fn dir(&self, root: &Path) -> PathBuf {
+        root.join(format!("v{TEST_VECTORS_VERSION}"))
+            .join(self.preset.name().to_lowercase())
+            .join(self.committee.as_str())
+            .join(self.id())
+    }
+}
+
+/// One coefficient of [`CircuitVector::inputs`], incremented by one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NegativeVector {
+    /// JSON pointer (RFC 6901) of the corrupted coefficient.
+    pub pointer: String,
+    pub original: Value,
+    pub corrupted: Value,
+}
+
+impl NegativeVector {
+    /// The corrupted copy of `inputs`.
+    pub fn apply(&self, inputs: &Value) -> Value {
+        let mut corrupted = inputs.clone();
+        if let Some(slot) = corrupted.pointer_mut(&self.pointer) {
+            *slot = self.corrupted.clone();
+        }
+        corrupted
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CircuitVector {
+    pub version: u32,
+    #[serde(flatten)]
+    pub case: VectorCase,
+    /// Circuit inputs, as written to `Prover.toml`.
+    pub inputs: Value,
+    /// The preset's `Configs` (moduli, bounds, bit widths), as emitted to `configs.nr`.
+    pub configs: Value,
+    pub negatives: Vec<NegativeVector>,
+}
+
+/// Circuits whose sample data depends on [`DkgInputType`] get one case per input type.
+fn dkg_input_types(circuit: &str) -> Vec<Option<DkgInputType>> {
+    let per_input_type = [
+        <share_computation::ShareComputationCircuit as Circuit>::NAME,
+        <share_encryption::ShareEncryptionCircuit as Circuit>::NAME,
+        <share_decryption::ShareDecryptionCircuit as Circuit>::NAME,
+    ];
+    if per_input_type.contains(&circuit) {
+        vec![
+            Some(DkgInputType::SecretKey),
+            Some(DkgInputType::SmudgingNoise),
+        ]
+    } else {
+        vec![None]
+    }
+}
+
+/// All cases for `circuits` × `presets` at one committee size and seed.
+pub fn vector_cases(
+    circuits: &[&str],
+    presets: &[BfvPreset],
+    committee: CiphernodesCommitteeSize,
+    seed: u64,
+) -> Vec<VectorCase> {
+    presets
+        .iter()
+        .flat_map(|&preset| {
+            circuits.iter().flat_map(move |&circuit| {
+                dkg_input_types(circuit)
+                    .into_iter()
+                    .map(move |dkg_input_type| VectorCase {
+                        circuit: circuit.to_string(),
+                        preset,
+                        committee,
+                        dkg_input_type,
+                        seed,
+                    })
+            })
+        })
+        .collect()
+}
+
+/// Samples the case's data from its seed and records the circuit inputs, configs and
+/// negative vectors.
+pub fn generate_vector(case: &VectorCase) -> Result<CircuitVector, CircuitsErrors> {
+    let mut rng = ChaCha20Rng::seed_from_u64(case.seed);
+    let (inputs, configs) = compute(case, &mut rng)?;
+    let negatives = negative_vectors(&inputs, case.seed);
+    Ok(CircuitVector {
+        version: TEST_VECTORS_VERSION,
+        case: case.clone(),
+        inputs,
+        configs,
+        negatives,
+    })
+}
+
+fn compute(case: &VectorCase, rng: &mut ChaCha20Rng) -> Result<(Value, Value), CircuitsErrors> {
+    let preset = case.preset;
+    let committee = case.committee.values();
+    let dkg_input_type = || {
+        case.dkg_input_type
+            .ok_or_else(|| CircuitsErrors::Other(format!("{} needs a DKG input type", case.id())))
+    };
+
+    match case.circuit.as_str() {
+        name if name == <pk::PkCircuit as Circuit>::NAME => {
+            let data = pk::PkCircuitData::generate_sample_with_rng(preset, rng)?;
+            to_values(
+                pk::Inputs::compute(preset, &data)?,
+                pk::Configs::compute(preset, &())?,
+            )
+        }
+        name if name == <share_computation::ShareComputationCircuit as Circuit>::NAME => {
+            let data = share_computation::ShareComputationCircuitData::generate_sample_with_rng(
+                preset,
+                committee,
+                dkg_input_type()?,
+                rng,
+            )?;
+            to_values(
+                share_computation::Inputs::compute(preset, &data)?,
+                share_computation::Configs::compute(preset, &data)?,
+            )
+        }
+        name if name == <share_encryption::ShareEncryptionCircuit as Circuit>::NAME => {
+            let sd = preset
+                .search_defaults()
+                .ok_or_else(|| CircuitsErrors::Sample("Preset has no search defaults".into()))?;
+            let data = share_encryption::ShareEncryptionCircuitData::generate_sample_with_rng(
+                preset,
+                committee,
+                dkg_input_type()?,
+                sd.z,
+                sd.lambda,
+                rng,
+            )?;
+            to_values(
+                share_encryption::Inputs::compute(preset, &data)?,
+                share_encryption::Configs::compute(preset, &data)?,
+            )
+        }
+        name if name == <share_decryption::ShareDecryptionCircuit as Circuit>::NAME => {
+            let data = share_decryption::ShareDecryptionCircuitData::generate_sample_with_rng(
+                preset,
+                committee,
+                dkg_input_type()?,
+                rng,
+            )?;
+            to_values(
+                share_decryption::Inputs::compute(preset, &data)?,
+                share_decryption::Configs::compute(preset, &data)?,
+            )
+        }
+        name if name == <pk_generation::PkGenerationCircuit as Circuit>::NAME => {
+            let data = pk_generation::PkGenerationCircuitData::generate_sample_with_rng(
+                preset,
+                committee.clone(),
+                rng,
+            )?;
+            to_values(
+                pk_generation::Inputs::compute(preset, &data)?,
+                pk_generation::Configs::compute(preset, &committee)?,
+            )
+        }
+        name if name == <pk_aggregation::PkAggregationCircuit as Circuit>::NAME => {
+            let data = pk_aggregation::PkAggregationCircuitData::generate_sample_with_rng(
+                preset, committee, rng,
+            )?;
+            to_values(
+                pk_aggregation::Inputs::compute(preset, &data)?,
+                pk_aggregation::Configs::compute(preset, &())?,
+            )
+        }
+        name if name == <user_data_encryption::UserDataEncryptionCircuit as Circuit>::NAME => {
+            let data =
+                user_data_encryption::UserDataEncryptionCircuitData::generate_sample_with_rng(
+                    preset, rng,
+                )?;
+            to_values(
+                user_data_encryption::Inputs::compute_with_rng(preset, &data, rng)?,
+                user_data_encryption::Configs::compute(preset, &())?,
+            )
+        }
+        name if name == <threshold_share_decryption::ShareDecryptionCircuit as Circuit>::NAME => {
+            let data =
+                threshold_share_decryption::ShareDecryptionCircuitData::generate_sample_with_rng(
+                    preset, committee, rng,
+                )?;
+            to_values(
+                threshold_share_decryption::Inputs::compute(preset, &data)?,
+                threshold_share_decryption::Configs::compute(preset, &())?,
+            )
+        }
+        name if name
+            == <decrypted_shares_aggregation::DecryptedSharesAggregationCircuit as Circuit>::NAME =>
+        {
+            let data = decrypted_shares_aggregation::DecryptedSharesAggregationCircuitData::generate_sample_with_rng(
+                preset, committee, rng,
+            )?;
+            to_values(
+                decrypted_shares_aggregation::Inputs::compute(preset, &data)?,
+                decrypted_shares_aggregation::Configs::compute(preset, &())?,
+            )
+        }
+        name => Err(CircuitsErrors::Other(format!(
+            "no test vectors for circuit {name}"
+        ))),
+    }
+}
+
+fn to_values<I, C>(inputs: I, configs: C) -> Result<(Value, Value), CircuitsErrors>
+where
+    I: Computation + Serialize,
+    C: Serialize,
+{
+    Ok((inputs.to_json()?, serde_json::to_value(configs)?))
+}
+
+/// Picks [`NEGATIVE_VECTORS_PER_CASE`] coefficients of `inputs` from a stream separate from the
+/// sampling one, so adding negatives never shifts the positive vector.
+fn negative_vectors(inputs: &Value, seed: u64) -> Vec<NegativeVector> {
+    let mut coefficients = Vec::new();
+    collect_coefficients(inputs, String::new(), &mut coefficients);
+
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    rng.set_stream(1);
+    let amount = NEGATIVE_VECTORS_PER_CASE.min(coefficients.len());
+    let mut picked = rand::seq::index::sample(&mut rng, coefficients.len(), amount).into_vec();
+    picked.sort_unstable();
+
+    picked
+        .into_iter()
+        .map(|i| {
+            let (pointer, original) = coefficients[i].clone();
+            let corrupted = increment(&original);
+            NegativeVector {
+                pointer,
+                original,
+                corrupted,
+            }
+        })
+        .collect()
+}
+
+/// Integer leaves of `value` (JSON numbers or decimal strings) with their JSON pointers.
+fn collect_coefficients(value: &Value, pointer: String, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                collect_coefficients(child, format!("{pointer}/{}", escape(key)), out);
+            }
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                collect_coefficients(child, format!("{pointer}/{i}"), out);
+            }
+        }
+        leaf if as_bigint(leaf).is_some() => out.push((pointer, leaf.clone())),
+        _ => {}
+    }
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn as_bigint(value: &Value) -> Option<BigInt> {
+    match value {
+        Value::Number(n) => n
+            .as_i64()
+            .map(BigInt::from)
+            .or_else(|| n.as_u64().map(BigInt::from)),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// `value + 1`, keeping the representation `to_json` uses: numbers while they fit in `i64`.
+fn increment(value: &Value) -> Value {
+    let next = as_bigint(value).unwrap_or_default() + 1;
+    match (value, i64::try_from(&next)) {
+        (Value::Number(_), Ok(n)) => Value::from(n),
+        _ => Value::String(next.to_string()),
+    }
+}
+
+/// `Prover.toml` files of a vector relative to its case directory: the positive inputs first,
+/// then one `negative-<k>/Prover.toml` per negative vector.
+fn prover_files(vector: &CircuitVector) -> Result<Vec<(PathBuf, String)>, CircuitsErrors> {
+    let mut files = vec![(
+        PathBuf::from("Prover.toml"),
+        toml::to_string(&vector.inputs)?,
+    )];
+    for (k, negative) in vector.negatives.iter().enumerate() {
+        files.push((
+            PathBuf::from(format!("negative-{k}")).join("Prover.toml"),
+            toml::to_string(&negative.apply(&vector.inputs))?,
+        ));
+    }
+    Ok(files)
+}
+
+/// Writes `vector.json`, the positive `Prover.toml` and one `negative-<k>/Prover.toml` per
+/// negative vector into the case directory under `root`.
+pub fn write_vector(vector: &CircuitVector, root: &Path) -> Result<PathBuf, CircuitsErrors> {
+    let dir = vector.case.dir(root);
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(VECTOR_FILE), serde_json::to_string_pretty(vector)?)?;
+    for (path, contents) in prover_files(vector)? {
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)?;
+    }
+    Ok(dir)
+}
+
+/// Loads every vector of the current [`TEST_VECTORS_VERSION`] under `root`, sorted by path.
+/// A missing directory yields no vectors.
+pub fn load_vectors(root: &Path) -> Result<Vec<CircuitVector>, CircuitsErrors> {
+    let mut paths = Vec::new();
+    find_vector_files(&root.join(format!("v{TEST_VECTORS_VERSION}")), &mut paths)?;
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| {
+            let vector: CircuitVector = serde_json::from_slice(&fs::read(path)?)?;
+            if vector.version != TEST_VECTORS_VERSION {
+                return Err(CircuitsErrors::Other(format!(
+                    "{}: version {} in a v{TEST_VECTORS_VERSION} directory",
+                    path.display(),
+                    vector.version
+                )));
+            }
+            Ok(vector)
+        })
+        .collect()
+}
+
+fn find_vector_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), CircuitsErrors> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_vector_files(&path, out)?;
+        } else if path.file_name().is_some_and(|name| name == VECTOR_FILE) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Regenerates `expected` from its case and fails with the first JSON pointer that drifted.
+pub fn check_vector(expected: &CircuitVector) -> Result<(), CircuitsErrors> {
+    let actual = generate_vector(&expected.case)?;
+    let drift = first_difference(&expected.inputs, &actual.inputs, "/inputs")
+        .or_else(|| first_difference(&expected.configs, &actual.configs, "/configs"))
+        .or_else(|| (expected.negatives != actual.negatives).then(|| "/negatives".to_string()));
+    match drift {
+        Some(pointer) => Err(CircuitsErrors::Other(format!(
+            "{} ({}, seed {}) drifted at {pointer}",
+            expected.case.id(),
+            expected.case.preset.name(),
+            expected.case.seed
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Checks the stored negative vectors of `vector` and the `Prover.toml` files next to it under
+/// `root`: every negative must change exactly the coefficient it names, by one, and every
+/// `Prover.toml` must hold what [`write_vector`] writes for the vector.
+pub fn check_vector_files(vector: &CircuitVector, root: &Path) -> Result<(), CircuitsErrors> {
+    let id = vector.case.id();
+    if vector.negatives.is_empty() {
+        return Err(CircuitsErrors::Other(format!(
+            "{id} has no negative vectors"
+        )));
+    }
+    for negative in &vector.negatives {
+        let corrupted = negative.apply(&vector.inputs);
+        let valid = vector.inputs.pointer(&negative.pointer) == Some(&negative.original)
+            && negative.corrupted == increment(&negative.original)
+            && first_difference(&vector.inputs, &corrupted, "").as_ref() == Some(&negative.pointer);
+        if !valid {
+            return Err(CircuitsErrors::Other(format!(
+                "{id}: negative vector at {} does not corrupt that coefficient by one",
+                negative.pointer
+            )));
+        }
+    }
+
+    let dir = vector.case.dir(root);
+    for (path, expected) in prover_files(vector)? {
+        let stored = fs::read_to_string(dir.join(&path)).map_err(|e| {
+            CircuitsErrors::Other(format!("{id}: cannot read {}: {e}", path.display()))
+        })?;
+        if stored != expected {
+            return Err(CircuitsErrors::Other(format!(
+                "{id}: {} does not match vector.json",
+                path.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn first_difference(expected: &Value, actual: &Value, pointer: &str) -> Option<String> {
+    match (expected, actual) {
+        (Value::Object(a), Value::Object(b)) => {
+            if a.len() != b.len() || a.keys().ne(b.keys()) {
+                return Some(pointer.to_string());
+            }
+            a.iter().find_map(|(key, child)| {
+                first_difference(child, &b[key], &format!("{pointer}/{}", escape(key)))
+            })
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            if a.len() != b.len() {
+                return Some(pointer.to_string());
+            }
+            a.iter()
+                .zip(b)
+                .enumerate()
+                .find_map(|(i, (x, y))| first_difference(x, y, &format!("{pointer}/{i}")))
+        }
+        (a, b) => (a != b).then(|| pointer.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pk_case(seed: u64) -> VectorCase {
+        VectorCase {
+            circuit: <pk::PkCircuit as Circuit>::NAME.to_string(),
+            preset: BfvPreset::InsecureThreshold512,
+            committee: CiphernodesCommitteeSize::Minimum,
+            dkg_input_type: None,
+            seed,
+        }
+    }
+
+    #[test]
+    fn test_vectors_are_reproducible_and_round_trip() {
+        let vector = generate_vector(&pk_case(7)).unwrap();
+        assert_eq!(vector, generate_vector(&pk_case(7)).unwrap());
+        assert_ne!(vector.inputs, generate_vector(&pk_case(8)).unwrap().inputs);
+
+        assert_eq!(vector.negatives.len(), NEGATIVE_VECTORS_PER_CASE);
+        for negative in &vector.negatives {
+            let corrupted = negative.apply(&vector.inputs);
+            assert_eq!(
+                vector.inputs.pointer(&negative.pointer),
+                Some(&negative.original)
+            );
+            assert_eq!(
+                first_difference(&vector.inputs, &corrupted, ""),
+                Some(negative.pointer.clone())
+            );
+        }
+
+        let root = tempfile::tempdir().unwrap();
+        write_vector(&vector, root.path()).unwrap();
+        let loaded = load_vectors(root.path()).unwrap();
+        assert_eq!(loaded, vec![vector.clone()]);
+        check_vector(&loaded[0]).unwrap();
+        check_vector_files(&loaded[0], root.path()).unwrap();
+
+        let negative = vector.case.dir(root.path()).join("negative-0/Prover.toml");
+        fs::write(&negative, toml::to_string(&vector.inputs).unwrap()).unwrap();
+        assert!(check_vector_files(&vector, root.path()).is_err());
+
+        let mut noop = vector.clone();
+        noop.negatives[0].corrupted = noop.negatives[0].original.clone();
+        assert!(check_vector_files(&noop, root.path()).is_err());
+
+        let mut drifted = vector;
+        drifted.configs["n"] = Value::from(1);
+        let err = check_vector(&drifted).unwrap_err().to_string();
+        assert!(err.contains("/configs/n"), "{err}");
+    }
+
+    #[test]
+    fn test_vector_cases_split_dkg_input_types() {
+        let cases = vector_cases(
+            &VECTOR_CIRCUITS,
+            &BfvPreset::PAIR_PRESETS,
+            CiphernodesCommitteeSize::Minimum,
+            DEFAULT_VECTOR_SEED,
+        );
+        assert_eq!(
+            cases.len(),
+            BfvPreset::PAIR_PRESETS.len() * (VECTOR_CIRCUITS.len() + 3)
+        );
+        let ids: std::collections::HashSet<_> = cases
+            .iter()
+            .map(|case| case.dir(Path::new("root")))
+            .collect();
+        assert_eq!(ids.len(), cases.len());
+    }
+}
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Checks `computation.rs` against the stored vectors in `test-vectors/`, regenerated with
+//! `cargo run -p e3-zk-helpers --bin test-vectors -- --output crates/zk-helpers/test-vectors`.
+//! The negative vectors are executed against the compiled circuits by the `e3-zk-prover`
+//! test `test_vector_tests`.
+
+use e3_zk_helpers::test_vectors::{check_vector, check_vector_files, load_vectors};
+use rayon::prelude::*;
+use std::path::PathBuf;
+
+#[test]
+fn test_stored_vectors_match_computation() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-vectors");
+    let vectors = load_vectors(&root).unwrap();
+    assert!(
+        !vectors.is_empty(),
+        "no test vectors under {}; generate them with the test-vectors binary",
+        root.display()
+    );
+
+    let drifted: Vec<String> = vectors
+        .par_iter()
+        .filter_map(|vector| {
+            check_vector(vector)
+                .and_then(|()| check_vector_files(vector, &root))
+                .err()
+                .map(|e| e.to_string())
+        })
+        .collect();
+    assert!(drifted.is_empty(), "{}", drifted.join("\n"));
+}
@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Prove/verify the `dummy` fixture circuit with each Barretenberg engine.
+//!
+//! Needs a `bb` binary (`$BB` or `~/.interfold/noir/bin/bb`); the FFI engine is included when
+//! built with `--features bb-ffi`:
+//!
+//! ```text
+//! BB_LIB_DIR=/path/to/barretenberg/lib cargo bench -p e3-zk-prover --features bb-ffi
+//! ```
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use e3_zk_prover::bb::{ProveJob, VerifyJob};
+use e3_zk_prover::{input_map, BbEngine, CircuitVariant, CompiledCircuit, WitnessGenerator};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const VARIANT: CircuitVariant = CircuitVariant::Recursive;
+
+fn bb_binary() -> Option<PathBuf> {
+    let bb = std::env::var_os("BB").map(PathBuf::from).or_else(|| {
+        std::env::var_os("HOME").map(|home| {
+            PathBuf::from(home)
+                .join(".interfold")
+                .join("noir")
+                .join("bin")
+                .join("bb")
+        })
+    })?;
+    bb.exists().then_some(bb)
+}
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+/// Writes a VK for [`VARIANT`] so the fixture VK's provenance does not matter.
+fn write_vk(bb: &Path, circuit: &Path, out_dir: &Path) -> PathBuf {
+    let status = Command::new(bb)
+        .args(["write_vk", "-b"])
+        .arg(circuit)
+        .arg("-o")
+        .arg(out_dir)
+        .args(["-t", VARIANT.verifier_target()])
+        .status()
+        .expect("failed to run bb write_vk");
+    assert!(status.success(), "bb write_vk failed");
+    out_dir.join("vk")
+}
+
+fn bench_engines(c: &mut Criterion) {
+    let Some(bb) = bb_binary() else {
+        eprintln!("bb binary not found; skipping bb engine benchmarks");
+        return;
+    };
+
+    let circuit_path = fixture("dummy.json");
+    let temp = tempfile::tempdir().unwrap();
+    let vk_path = write_vk(&bb, &circuit_path, temp.path());
+    let circuit = CompiledCircuit::from_file(&circuit_path).unwrap();
+    let inputs = input_map([("x", "5"), ("y", "3"), ("_sum", "8")]).unwrap();
+    let witness = WitnessGenerator::new()
+        .generate_witness(&circuit, inputs)
+        .unwrap();
+
+    #[allow(unused_mut)]
+    let mut engines = vec![BbEngine::Cli(bb.clone())];
+    #[cfg(feature = "bb-ffi")]
+    engines.push(BbEngine::ffi());
+
+    let mut group = c.benchmark_group("bb_dummy");
+    group.sample_size(10);
+    for engine in engines {
+        let job_dir = temp.path().join(engine.name());
+        let prove_job = ProveJob {
+            circuit_path: &circuit_path,
+            vk_path: &vk_path,
+            witness: &witness,
+            variant: VARIANT,
+            job_dir: &job_dir,
+        };
+        let output = engine.prove(&prove_job).unwrap();
+
+        group.bench_function(format!("prove_{}", engine.name()), |b| {
+            b.iter(|| engine.prove(&prove_job).unwrap())
+        });
+        group.bench_function(format!("verify_{}", engine.name()), |b| {
+            b.iter(|| {
+                let verified = engine
+                    .verify(&VerifyJob {
+                        vk_path: &vk_path,
+                        proof: &output.proof,
+                        public_inputs: &output.public_inputs,
+                        variant: VARIANT,
+                        job_dir: &job_dir,
+                    })
+                    .unwrap();
+                assert!(verified);
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_engines);
+criterion_main!(benches);
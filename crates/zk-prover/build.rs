@@ -16,4 +16,22 @@ fn main() {
         .success());
 
     println!("cargo:rerun-if-changed=./scripts/build_fixtures.sh");
+
+    if std::env::var_os("CARGO_FEATURE_BB_FFI").is_some() {
+        link_barretenberg();
+    }
+}
+
+/// Links the Barretenberg static library for the `bb-ffi` feature.
+fn link_barretenberg() {
+    println!("cargo:rerun-if-env-changed=BB_LIB_DIR");
+    let lib_dir = std::env::var("BB_LIB_DIR").expect(
+        "the bb-ffi feature needs BB_LIB_DIR pointing at the directory with libbarretenberg",
+    );
+    println!("cargo:rustc-link-search=native={lib_dir}");
+    println!("cargo:rustc-link-lib=static=barretenberg");
+    match std::env::var("CARGO_CFG_TARGET_OS").as_deref() {
+        Ok("macos") => println!("cargo:rustc-link-lib=dylib=c++"),
+        _ => println!("cargo:rustc-link-lib=dylib=stdc++"),
+    }
 }
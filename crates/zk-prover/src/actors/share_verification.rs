@@ -98,6 +98,7 @@ impl ShareVerificationActor {
         let committee_size = msg.committee_size;
         match msg.kind {
             VerificationKind::ShareProofs
+            | VerificationKind::ShareComplaints
             | VerificationKind::ThresholdDecryptionProofs
             | VerificationKind::PkGenerationProofs => {
                 let kind = msg.kind.clone();
@@ -258,6 +259,7 @@ impl ShareVerificationActor {
 
         let (request, dispatched_party_ids) = match pending.kind {
             VerificationKind::ShareProofs
+            | VerificationKind::ShareComplaints
             | VerificationKind::ThresholdDecryptionProofs
             | VerificationKind::PkGenerationProofs => {
                 let Some((passed, ids)) =
@@ -386,6 +388,7 @@ impl ShareVerificationActor {
         let zk_results: Vec<PartyVerificationResult> = match (&pending.kind, msg.response) {
             (
                 VerificationKind::ShareProofs
+                | VerificationKind::ShareComplaints
                 | VerificationKind::ThresholdDecryptionProofs
                 | VerificationKind::PkGenerationProofs,
                 ComputeResponseKind::Zk(ZkResponse::VerifyShareProofs(r)),
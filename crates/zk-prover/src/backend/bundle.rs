@@ -0,0 +1,400 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Signed offline bundles of `bb` and the circuit tree, for nodes that cannot reach the
+//! download URLs.
+//!
+//! A bundle is a `.tar.gz` holding `manifest.json`, its EIP-191 signature in `manifest.sig`,
+//! `bin/bb` and everything under the circuits dir as `circuits/...`. The manifest pins the
+//! `bb` and circuits versions of the exporting build and the SHA-256 of every other file, so an
+//! import fails on any tampered, missing or extra file and on any version this build does not
+//! require.
+
+use crate::config::{verify_checksum, BbTarget, VersionInfo};
+use crate::error::ZkError;
+use alloy::primitives::{Address, Signature};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::SignerSync;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use tar::{Archive, Builder, Header};
+use tokio::fs;
+use tracing::{info, warn};
+use walkdir::WalkDir;
+
+use super::{SetupStatus, ZkBackend};
+
+const BUNDLE_MANIFEST: &str = "manifest.json";
+const BUNDLE_SIGNATURE: &str = "manifest.sig";
+const BUNDLE_FORMAT: u32 = 1;
+const BB_ENTRY: &str = "bin/bb";
+const CIRCUITS_PREFIX: &str = "circuits";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format: u32,
+    pub bb_version: String,
+    /// [`BbTarget`] the bundled `bb` was built for.
+    pub bb_target: String,
+    pub circuits_version: String,
+    pub created: String,
+    /// SHA-256 of every bundled file, keyed by its path inside the bundle.
+    pub files: BTreeMap<String, String>,
+}
+
+/// Signature over the exact `manifest.json` bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleSignature {
+    pub signer: Address,
+    pub signature: String,
+}
+
+impl BundleSignature {
+    pub fn sign(manifest: &[u8], signer: &PrivateKeySigner) -> Result<Self, ZkError> {
+        let signature = signer
+            .sign_message_sync(manifest)
+            .map_err(|e| ZkError::BundleError(format!("signing manifest: {e}")))?;
+        Ok(Self {
+            signer: signer.address(),
+            signature: hex::encode(signature.as_bytes()),
+        })
+    }
+
+    /// Checks the signature recovers to [`Self::signer`] and that the signer is trusted.
+    pub fn verify(&self, manifest: &[u8], trusted: &[Address]) -> Result<(), ZkError> {
+        let bytes = hex::decode(self.signature.trim_start_matches("0x"))
+            .map_err(|e| ZkError::BundleError(format!("malformed signature: {e}")))?;
+        let signature = Signature::try_from(bytes.as_slice())
+            .map_err(|e| ZkError::BundleError(format!("malformed signature: {e}")))?;
+        let recovered = signature
+            .recover_address_from_msg(manifest)
+            .map_err(|e| ZkError::BundleError(format!("invalid signature: {e}")))?;
+        if recovered != self.signer {
+            return Err(ZkError::BundleError(format!(
+                "manifest is signed by {recovered}, not the claimed {}",
+                self.signer
+            )));
+        }
+        if !trusted.contains(&recovered) {
+            return Err(ZkError::BundleError(format!(
+                "bundle signer {recovered} is not trusted"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl ZkBackend {
+    /// Writes the installed `bb` and circuits to `out`, signed by `signer`.
+    ///
+    /// Only a fully set up backend can be exported, so the bundle always carries the versions
+    /// this build pins in `versions.json`.
+    pub async fn export_bundle(
+        &self,
+        out: &Path,
+        signer: &PrivateKeySigner,
+    ) -> Result<BundleManifest, ZkError> {
+        if !matches!(self.check_status().await, SetupStatus::Ready) {
+            return Err(ZkError::BundleError(
+                "ZK prover is not set up; run `interfold noir setup` before exporting".to_string(),
+            ));
+        }
+        let target = BbTarget::current().ok_or_else(|| ZkError::UnsupportedPlatform {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+        })?;
+
+        let mut entries = vec![(BB_ENTRY.to_string(), self.bb_binary.clone())];
+        for entry in WalkDir::new(&self.circuits_dir).sort_by_file_name() {
+            let entry = entry.map_err(|e| ZkError::IoError(e.into()))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(&self.circuits_dir)
+                .expect("walkdir yields paths under its root");
+            entries.push((
+                format!("{CIRCUITS_PREFIX}/{}", bundle_path(rel)),
+                entry.path().to_path_buf(),
+            ));
+        }
+
+        let mut files = BTreeMap::new();
+        for (name, path) in &entries {
+            files.insert(name.clone(), sha256_hex(&fs::read(path).await?));
+        }
+        let manifest = BundleManifest {
+            format: BUNDLE_FORMAT,
+            bb_version: self.config.required_bb_version.clone(),
+            bb_target: target.to_string(),
+            circuits_version: self.config.required_circuits_version.clone(),
+            created: chrono::Utc::now().to_rfc3339(),
+            files,
+        };
+        let manifest_bytes = serde_json::to_vec_pretty(&manifest)?;
+        let signature = BundleSignature::sign(&manifest_bytes, signer)?;
+
+        let mut builder = Builder::new(GzEncoder::new(File::create(out)?, Compression::default()));
+        append_bytes(&mut builder, BUNDLE_MANIFEST, &manifest_bytes)?;
+        append_bytes(
+            &mut builder,
+            BUNDLE_SIGNATURE,
+            &serde_json::to_vec_pretty(&signature)?,
+        )?;
+        for (name, path) in &entries {
+            builder.append_path_with_name(path, name)?;
+        }
+        builder.into_inner()?.finish()?;
+
+        info!(
+            "exported {} files (bb {}, circuits {}) to {}",
+            manifest.files.len(),
+            manifest.bb_version,
+            manifest.circuits_version,
+            out.display()
+        );
+        Ok(manifest)
+    }
+
+    /// Verifies the bundle at `bundle` against `trusted` signers and installs it, replacing the
+    /// current circuits. The signature is checked before anything is unpacked.
+    pub async fn import_bundle(
+        &self,
+        bundle: &Path,
+        trusted: &[Address],
+    ) -> Result<BundleManifest, ZkError> {
+        if trusted.is_empty() {
+            return Err(ZkError::BundleError(
+                "at least one trusted bundle signer is required".to_string(),
+            ));
+        }
+
+        // Nothing is written to disk before the manifest signature and pins check out.
+        let (manifest, signature) = read_signed_manifest(bundle, trusted)?;
+        self.check_bundle_pins(&manifest)?;
+
+        fs::create_dir_all(&self.base_dir).await?;
+        // Unpack next to the install so the circuits can be moved into place with a rename.
+        let staging = tempfile::tempdir_in(&self.base_dir)?;
+        unpack_listed_files(bundle, staging.path(), &manifest)?;
+        verify_bundle_files(staging.path(), &manifest).await?;
+
+        if self.using_custom_bb {
+            warn!("using a custom bb binary; not installing the bundled one");
+        } else {
+            fs::create_dir_all(self.base_dir.join("bin")).await?;
+            fs::copy(staging.path().join(BB_ENTRY), &self.bb_binary).await?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mut perms = fs::metadata(&self.bb_binary).await?.permissions();
+                perms.set_mode(0o755);
+                fs::set_permissions(&self.bb_binary, perms).await?;
+            }
+        }
+
+        if self.circuits_dir.exists() {
+            fs::remove_dir_all(&self.circuits_dir).await?;
+        }
+        let staged_circuits = staging.path().join(CIRCUITS_PREFIX);
+        if staged_circuits.exists() {
+            fs::rename(&staged_circuits, &self.circuits_dir).await?;
+        } else {
+            fs::create_dir_all(&self.circuits_dir).await?;
+        }
+
+        let mut version_info = if self.using_custom_bb {
+            self.load_version_info().await
+        } else {
+            VersionInfo {
+                bb_version: Some(manifest.bb_version.clone()),
+                bb_checksum: manifest.files.get(BB_ENTRY).cloned(),
+                ..Default::default()
+            }
+        };
+        version_info.circuits = self.verify_circuits().await?;
+        version_info.circuits_version = Some(manifest.circuits_version.clone());
+        version_info.last_updated = Some(chrono::Utc::now().to_rfc3339());
+        version_info.save(&self.version_file()).await?;
+
+        info!(
+            "imported bundle (bb {}, circuits {}) signed by {}",
+            manifest.bb_version, manifest.circuits_version, signature.signer
+        );
+        Ok(manifest)
+    }
+
+    /// A bundle must carry exactly the versions this build requires, for this platform.
+    fn check_bundle_pins(&self, manifest: &BundleManifest) -> Result<(), ZkError> {
+        if manifest.format != BUNDLE_FORMAT {
+            return Err(ZkError::BundleError(format!(
+                "unsupported bundle format {}",
+                manifest.format
+            )));
+        }
+        if manifest.bb_version != self.config.required_bb_version {
+            return Err(ZkError::VersionMismatch {
+                installed: format!("bb {}", manifest.bb_version),
+                required: self.config.required_bb_version.clone(),
+            });
+        }
+        if manifest.circuits_version != self.config.required_circuits_version {
+            return Err(ZkError::VersionMismatch {
+                installed: format!("circuits {}", manifest.circuits_version),
+                required: self.config.required_circuits_version.clone(),
+            });
+        }
+        if !self.using_custom_bb
+            && BbTarget::current().map(|t| t.to_string()).as_deref()
+                != Some(manifest.bb_target.as_str())
+        {
+            return Err(ZkError::BundleError(format!(
+                "bundle bb is built for {}, this host is {}-{}",
+                manifest.bb_target,
+                std::env::consts::OS,
+                std::env::consts::ARCH
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Reads `manifest.json` and `manifest.sig` straight from the archive and verifies the signature
+/// against `trusted` signers.
+fn read_signed_manifest(
+    bundle: &Path,
+    trusted: &[Address],
+) -> Result<(BundleManifest, BundleSignature), ZkError> {
+    let mut manifest_bytes = None;
+    let mut signature_bytes = None;
+    let mut archive = Archive::new(GzDecoder::new(File::open(bundle)?));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = bundle_path(&entry.path()?);
+        let slot = match name.as_str() {
+            BUNDLE_MANIFEST => &mut manifest_bytes,
+            BUNDLE_SIGNATURE => &mut signature_bytes,
+            _ => continue,
+        };
+        if slot.is_some() {
+            return Err(ZkError::BundleError(format!("duplicate {name} in bundle")));
+        }
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        *slot = Some(bytes);
+    }
+
+    let missing = |name: &str| ZkError::BundleError(format!("bundle has no {name}"));
+    let manifest_bytes = manifest_bytes.ok_or_else(|| missing(BUNDLE_MANIFEST))?;
+    let signature: BundleSignature =
+        serde_json::from_slice(&signature_bytes.ok_or_else(|| missing(BUNDLE_SIGNATURE))?)?;
+    signature.verify(&manifest_bytes, trusted)?;
+    Ok((serde_json::from_slice(&manifest_bytes)?, signature))
+}
+
+/// Unpacks the regular files listed in the verified manifest into `root`. Any other entry fails
+/// the import before it is written.
+fn unpack_listed_files(
+    bundle: &Path,
+    root: &Path,
+    manifest: &BundleManifest,
+) -> Result<(), ZkError> {
+    let mut archive = Archive::new(GzDecoder::new(File::open(bundle)?));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = bundle_path(&entry.path()?);
+        if name == BUNDLE_MANIFEST || name == BUNDLE_SIGNATURE {
+            continue;
+        }
+        if !entry.header().entry_type().is_file() || !manifest.files.contains_key(&name) {
+            return Err(ZkError::BundleError(format!(
+                "entry not listed in the manifest: {name}"
+            )));
+        }
+        entry.unpack_in(root)?;
+    }
+    Ok(())
+}
+
+/// Every unpacked file must be listed in the manifest with a matching hash, and vice versa.
+async fn verify_bundle_files(root: &Path, manifest: &BundleManifest) -> Result<(), ZkError> {
+    let mut unlisted = Vec::new();
+    let mut seen = 0;
+    for entry in WalkDir::new(root) {
+        let entry = entry.map_err(|e| ZkError::IoError(e.into()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let name = bundle_path(
+            entry
+                .path()
+                .strip_prefix(root)
+                .expect("walkdir yields paths under its root"),
+        );
+        if name == BUNDLE_MANIFEST || name == BUNDLE_SIGNATURE {
+            continue;
+        }
+        let Some(expected) = manifest.files.get(&name) else {
+            unlisted.push(name);
+            continue;
+        };
+        verify_checksum(&name, &fs::read(entry.path()).await?, Some(expected))?;
+        seen += 1;
+    }
+
+    if !unlisted.is_empty() {
+        return Err(ZkError::BundleError(format!(
+            "files not listed in the manifest: {}",
+            unlisted.join(", ")
+        )));
+    }
+    if seen != manifest.files.len() {
+        let missing: Vec<_> = manifest
+            .files
+            .keys()
+            .filter(|name| !root.join(name).is_file())
+            .cloned()
+            .collect();
+        return Err(ZkError::BundleError(format!(
+            "files missing from the bundle: {}",
+            missing.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn append_bytes<W: std::io::Write>(
+    builder: &mut Builder<W>,
+    name: &str,
+    data: &[u8],
+) -> std::io::Result<()> {
+    let mut header = Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(0);
+    header.set_cksum();
+    builder.append_data(&mut header, name, data)
+}
+
+/// `/`-separated path, independent of the host platform.
+fn bundle_path(rel: &Path) -> String {
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
@@ -228,7 +228,7 @@ impl ZkBackend {
         Ok(version)
     }
 
-    async fn verify_circuits(&self) -> Result<HashMap<String, CircuitInfo>, ZkError> {
+    pub(super) async fn verify_circuits(&self) -> Result<HashMap<String, CircuitInfo>, ZkError> {
         let manifest_path = self.circuits_dir.join("checksums.json");
         if !manifest_path.exists() {
             warn!("checksums.json not found, skipping circuit verification");
@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! What circuits are installed, per preset and committee size, and whether their EVM
+//! verification keys are the ones deployed on chain.
+
+use crate::error::ZkError;
+use e3_events::{CircuitName, CircuitVariant};
+use e3_fhe_params::BfvPreset;
+use e3_zk_helpers::CiphernodesCommitteeSize;
+use std::collections::BTreeMap;
+use std::fs;
+use walkdir::WalkDir;
+
+use super::ZkBackend;
+
+/// Circuits whose EVM VK hash is compiled into an on-chain Honk verifier contract.
+pub const ONCHAIN_VERIFIED_CIRCUITS: [CircuitName; 2] = [
+    CircuitName::DkgAggregator,
+    CircuitName::DecryptionAggregator,
+];
+
+const COMMITTEE_SIZES: [CiphernodesCommitteeSize; 3] = [
+    CiphernodesCommitteeSize::Minimum,
+    CiphernodesCommitteeSize::Micro,
+    CiphernodesCommitteeSize::Small,
+];
+
+const VARIANTS: [CircuitVariant; 3] = [
+    CircuitVariant::Default,
+    CircuitVariant::Recursive,
+    CircuitVariant::Evm,
+];
+
+/// Circuit artifacts installed for one preset and committee size.
+#[derive(Debug, Clone)]
+pub struct InstalledCircuits {
+    pub preset: BfvPreset,
+    pub committee: CiphernodesCommitteeSize,
+    /// `{preset}/{committee}` under the circuits dir.
+    pub artifacts_dir: String,
+    /// Compiled circuits per variant dir.
+    pub circuits: BTreeMap<&'static str, usize>,
+    /// `0x`-prefixed EVM VK hashes of the [`ONCHAIN_VERIFIED_CIRCUITS`] present.
+    pub evm_vk_hashes: Vec<(CircuitName, String)>,
+}
+
+impl ZkBackend {
+    /// Installed artifact sets for every known preset and committee size. Threshold and DKG
+    /// presets of the same degree share artifacts, so both list the same directory.
+    pub fn installed_circuits(&self) -> Vec<InstalledCircuits> {
+        let mut installed = Vec::new();
+        for preset in BfvPreset::ALL {
+            for committee in COMMITTEE_SIZES {
+                let artifacts_dir = preset.artifacts_dir_for_committee(committee.as_str());
+                let root = self.circuits_dir.join(&artifacts_dir);
+                if !root.is_dir() {
+                    continue;
+                }
+                let circuits = VARIANTS
+                    .iter()
+                    .map(|variant| {
+                        let count = WalkDir::new(root.join(variant.as_str()))
+                            .into_iter()
+                            .filter_map(|e| e.ok())
+                            .filter(|e| {
+                                e.file_type().is_file()
+                                    && e.path().extension().is_some_and(|ext| ext == "json")
+                            })
+                            .count();
+                        (variant.as_str(), count)
+                    })
+                    .collect();
+                let evm_vk_hashes = ONCHAIN_VERIFIED_CIRCUITS
+                    .into_iter()
+                    .filter_map(|circuit| {
+                        self.evm_vk_hash(&artifacts_dir, circuit)
+                            .map(|hash| (circuit, format!("0x{}", hex::encode(hash))))
+                    })
+                    .collect();
+                installed.push(InstalledCircuits {
+                    preset,
+                    committee,
+                    artifacts_dir,
+                    circuits,
+                    evm_vk_hashes,
+                });
+            }
+        }
+        installed
+    }
+
+    /// The `.vk_hash` written next to `circuit`'s EVM artifacts, if installed.
+    pub fn evm_vk_hash(&self, artifacts_dir: &str, circuit: CircuitName) -> Option<[u8; 32]> {
+        let path = self
+            .circuits_dir
+            .join(artifacts_dir)
+            .join(CircuitVariant::Evm.as_str())
+            .join(circuit.dir_path())
+            .join(format!("{}.vk_hash", circuit.as_str()));
+        fs::read(path).ok()?.try_into().ok()
+    }
+
+    /// Checks that `deployed_code` (the runtime bytecode of `circuit`'s on-chain verifier)
+    /// embeds the VK hash of at least one installed artifact set, and returns the matching
+    /// artifacts dirs.
+    pub fn check_onchain_vk(
+        &self,
+        circuit: CircuitName,
+        deployed_code: &[u8],
+    ) -> Result<Vec<String>, ZkError> {
+        let mut local = Vec::new();
+        let mut matching = Vec::new();
+        for preset in BfvPreset::ALL {
+            for committee in COMMITTEE_SIZES {
+                let artifacts_dir = preset.artifacts_dir_for_committee(committee.as_str());
+                if local.iter().any(|(dir, _)| dir == &artifacts_dir) {
+                    continue;
+                }
+                let Some(hash) = self.evm_vk_hash(&artifacts_dir, circuit) else {
+                    continue;
+                };
+                if embeds_word(deployed_code, &hash) {
+                    matching.push(artifacts_dir.clone());
+                }
+                local.push((artifacts_dir, hash));
+            }
+        }
+
+        if local.is_empty() {
+            return Err(ZkError::CircuitNotFound(format!(
+                "{} (evm verification key)",
+                circuit.as_str()
+            )));
+        }
+        if matching.is_empty() {
+            return Err(ZkError::VkMismatch {
+                circuit: circuit.as_str().to_string(),
+                local: local
+                    .iter()
+                    .map(|(dir, hash)| format!("{dir}: 0x{}", hex::encode(hash)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            });
+        }
+        Ok(matching)
+    }
+}
+
+/// Whether EVM `code` pushes `word` as an immediate. Solidity emits constants with the
+/// shortest `PUSHn` that fits, so leading zero bytes are dropped.
+fn embeds_word(code: &[u8], word: &[u8; 32]) -> bool {
+    let start = word.iter().position(|b| *b != 0).unwrap_or(word.len());
+    let value = &word[start..];
+    if value.is_empty() {
+        return false;
+    }
+    // PUSH1 is 0x60, PUSH32 is 0x7f.
+    let push = 0x5f + value.len() as u8;
+    code.windows(value.len() + 1)
+        .any(|w| w[0] == push && &w[1..] == value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embeds_word_matches_push_immediates() {
+        let mut hash = [0u8; 32];
+        hash[1..].copy_from_slice(&[0xab; 31]);
+        let mut code = vec![0x60, 0x80];
+        code.push(0x7e);
+        code.extend_from_slice(&[0xab; 31]);
+        assert!(embeds_word(&code, &hash));
+
+        // The same bytes behind the wrong opcode are data, not the constant.
+        code[2] = 0x7f;
+        assert!(!embeds_word(&code, &hash));
+        assert!(!embeds_word(&code, &[0u8; 32]));
+    }
+}
@@ -4,15 +4,28 @@
 // without even the implied warranty of MERCHANTABILITY
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
+mod bundle;
 mod download;
+mod inventory;
 mod setup;
 use e3_config::BBPath;
 
 #[cfg(test)]
 mod tests;
+use crate::bb::BbEngine;
 use crate::config::ZkConfig;
 use crate::error::ZkError;
+use crate::remote::RemoteProverPool;
 use std::path::PathBuf;
+use std::sync::Arc;
+
+pub use bundle::{BundleManifest, BundleSignature};
+pub use inventory::{InstalledCircuits, ONCHAIN_VERIFIED_CIRCUITS};
+
+/// Proof cache location inside the node's work dir.
+const PROOF_CACHE_DIR: &str = ".proof-cache";
+/// Per-E3 proof profiles inside the node's work dir.
+const PROFILE_DIR: &str = ".profiles";
 
 #[derive(Debug, Clone)]
 pub enum SetupStatus {
@@ -36,6 +49,14 @@ pub struct ZkBackend {
     pub work_dir: PathBuf,
     pub config: ZkConfig,
     pub using_custom_bb: bool,
+    /// Prover workers to dispatch proving/verification to before using the local `bb`.
+    pub remote_workers: Option<Arc<RemoteProverPool>>,
+    /// Where generated proofs and successful verifications are cached; `None` disables caching.
+    pub proof_cache_dir: Option<PathBuf>,
+    /// Where per-proof timings and sizes are recorded; `None` disables profiling.
+    pub profile_dir: Option<PathBuf>,
+    /// Barretenberg engine override; `None` spawns [`Self::bb_binary`].
+    pub bb_engine: Option<BbEngine>,
 }
 
 impl ZkBackend {
@@ -56,6 +77,9 @@ impl ZkBackend {
             .expect("circuits_dir should have a parent")
             .to_path_buf();
 
+        let proof_cache_dir = Some(work_dir.join(PROOF_CACHE_DIR));
+        let profile_dir = Some(work_dir.join(PROFILE_DIR));
+
         Self {
             bb_binary: bb_binary.path(),
             circuits_dir,
@@ -63,6 +87,49 @@ impl ZkBackend {
             base_dir,
             config,
             using_custom_bb: bb_binary.is_custom(),
+            remote_workers: None,
+            proof_cache_dir,
+            profile_dir,
+            bb_engine: None,
+        }
+    }
+
+    /// Run Barretenberg through `engine` instead of spawning [`Self::bb_binary`].
+    pub fn with_bb_engine(mut self, engine: BbEngine) -> Self {
+        self.bb_engine = Some(engine);
+        self
+    }
+
+    /// The engine provers built from this backend use.
+    pub fn engine(&self) -> BbEngine {
+        self.bb_engine
+            .clone()
+            .unwrap_or_else(|| BbEngine::Cli(self.bb_binary.clone()))
+    }
+
+    /// Dispatch proofs to out-of-process prover workers first.
+    pub fn with_remote_workers(mut self, pool: RemoteProverPool) -> Self {
+        self.remote_workers = Some(Arc::new(pool));
+        self
+    }
+
+    /// Always run `bb`, never consult or fill the proof cache.
+    pub fn without_proof_cache(mut self) -> Self {
+        self.proof_cache_dir = None;
+        self
+    }
+
+    /// Don't record proof profiles.
+    pub fn without_profiling(mut self) -> Self {
+        self.profile_dir = None;
+        self
+    }
+
+    /// Same backend, proving only with the local `bb`.
+    pub fn without_remote_workers(&self) -> Self {
+        Self {
+            remote_workers: None,
+            ..self.clone()
         }
     }
 
@@ -83,7 +150,7 @@ impl ZkBackend {
         Ok(Self::new(bb_binary, circuits_dir, work_dir))
     }
 
-    fn sanitize_e3_id(e3_id: &str) -> Result<&str, ZkError> {
+    pub(crate) fn sanitize_e3_id(e3_id: &str) -> Result<&str, ZkError> {
         // Sanitize e3_id to prevent path traversal
         if e3_id.is_empty()
             || e3_id.contains('\0')
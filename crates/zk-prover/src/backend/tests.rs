@@ -1,169 +1,296 @@
-// SPDX-License-Identifier: LGPL-3.0-only
-//
-// This file is provided WITHOUT ANY WARRANTY;
-// without even the implied warranty of MERCHANTABILITY
-// or FITNESS FOR A PARTICULAR PURPOSE.
-
-use std::env;
-
-use super::*;
-use crate::{config::VersionInfo, test_utils::get_tempdir};
-use tokio::fs;
-
-fn test_backend(temp_path: &std::path::Path, config: ZkConfig) -> ZkBackend {
-    let noir_dir = temp_path.join("noir");
-    let bb_binary = BBPath::check(noir_dir.join("bin").join("bb")).unwrap();
-    let circuits_dir = noir_dir.join("circuits");
-    let work_dir = noir_dir.join("work").join("test_node");
-    ZkBackend::with_config(bb_binary, circuits_dir, work_dir, config)
-}
-
-#[tokio::test]
-async fn test_backend_creates_directories() {
-    let temp = get_tempdir().unwrap();
-    let backend = test_backend(temp.path(), ZkConfig::default());
-
-    fs::create_dir_all(&backend.base_dir).await.unwrap();
-    fs::create_dir_all(&backend.circuits_dir).await.unwrap();
-    fs::create_dir_all(&backend.work_dir).await.unwrap();
-
-    assert!(backend.base_dir.exists());
-    assert!(backend.circuits_dir.exists());
-    assert!(backend.work_dir.exists());
-
-    let temp_path = temp.path().to_path_buf();
-    drop(temp);
-    assert!(!temp_path.exists());
-}
-
-#[tokio::test]
-async fn test_version_info_roundtrip() {
-    let temp = get_tempdir().unwrap();
-    let path = temp.path().join("version.json");
-
-    let info = VersionInfo {
-        bb_version: Some("0.87.0".to_string()),
-        circuits_version: Some("0.1.0".to_string()),
-        ..Default::default()
-    };
-
-    info.save(&path).await.unwrap();
-    let loaded = VersionInfo::load(&path).await.unwrap();
-
-    assert_eq!(loaded.bb_version, info.bb_version);
-    assert_eq!(loaded.circuits_version, info.circuits_version);
-
-    let temp_path = temp.path().to_path_buf();
-    drop(temp);
-    assert!(!temp_path.exists());
-}
-
-#[tokio::test]
-async fn test_check_status_full_setup_needed() {
-    if env::var("E3_CUSTOM_BB").is_ok() {
-        return;
-    }
-
-    let temp = get_tempdir().unwrap();
-    let backend = test_backend(temp.path(), ZkConfig::default());
-
-    let status = backend.check_status().await;
-    assert!(matches!(status, SetupStatus::FullSetupNeeded));
-
-    let temp_path = temp.path().to_path_buf();
-    drop(temp);
-    assert!(!temp_path.exists());
-}
-
-#[tokio::test]
-async fn test_check_status_ready_when_installed() {
-    if env::var("E3_CUSTOM_BB").is_ok() {
-        return;
-    }
-
-    let temp = get_tempdir().unwrap();
-    let config = ZkConfig::default();
-    let backend = test_backend(temp.path(), config.clone());
-
-    fs::create_dir_all(&backend.base_dir.join("bin"))
-        .await
-        .unwrap();
-    fs::create_dir_all(&backend.circuits_dir).await.unwrap();
-    fs::write(&backend.bb_binary, b"fake bb binary")
-        .await
-        .unwrap();
-
-    let info = VersionInfo {
-        bb_version: Some(config.required_bb_version.clone()),
-        circuits_version: Some(config.required_circuits_version.clone()),
-        ..Default::default()
-    };
-    info.save(&backend.version_file()).await.unwrap();
-
-    let status = backend.check_status().await;
-    assert!(matches!(status, SetupStatus::Ready));
-
-    let temp_path = temp.path().to_path_buf();
-    drop(temp);
-    assert!(!temp_path.exists());
-}
-
-#[tokio::test]
-async fn test_check_status_bb_needs_update() {
-    if env::var("E3_CUSTOM_BB").is_ok() {
-        return;
-    }
-
-    let temp = get_tempdir().unwrap();
-    let config = ZkConfig::default();
-    let backend = test_backend(temp.path(), config.clone());
-
-    fs::create_dir_all(&backend.base_dir.join("bin"))
-        .await
-        .unwrap();
-    fs::create_dir_all(&backend.circuits_dir).await.unwrap();
-    fs::write(&backend.bb_binary, b"fake bb binary")
-        .await
-        .unwrap();
-
-    let info = VersionInfo {
-        bb_version: Some("0.0.1".to_string()),
-        circuits_version: Some(config.required_circuits_version.clone()),
-        ..Default::default()
-    };
-    info.save(&backend.version_file()).await.unwrap();
-
-    let status = backend.check_status().await;
-    assert!(matches!(status, SetupStatus::BbNeedsUpdate { .. }));
-
-    let temp_path = temp.path().to_path_buf();
-    drop(temp);
-    assert!(!temp_path.exists());
-}
-
-#[tokio::test]
-async fn test_work_dir_cleanup() {
-    let temp = get_tempdir().unwrap();
-    let backend = test_backend(temp.path(), ZkConfig::default());
-
-    fs::create_dir_all(&backend.work_dir).await.unwrap();
-
-    let e3_id = "test-e3-123";
-    let work_dir = backend.work_dir_for(e3_id).unwrap();
-
-    fs::create_dir_all(&work_dir).await.unwrap();
-    fs::write(work_dir.join("proof.bin"), b"fake proof")
-        .await
-        .unwrap();
-    fs::write(work_dir.join("witness.bin"), b"fake witness")
-        .await
-        .unwrap();
-    assert!(work_dir.exists());
-
-    backend.cleanup_work_dir(e3_id).await.unwrap();
-    assert!(!work_dir.exists());
-
-    let temp_path = temp.path().to_path_buf();
-    drop(temp);
-    assert!(!temp_path.exists());
-}
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use std::env;
+
+use super::*;
+use crate::{config::VersionInfo, test_utils::get_tempdir};
+use alloy::signers::local::PrivateKeySigner;
+use tokio::fs;
+
+fn test_backend(temp_path: &std::path::Path, config: ZkConfig) -> ZkBackend {
+    let noir_dir = temp_path.join("noir");
+    let bb_binary = BBPath::check(noir_dir.join("bin").join("bb")).unwrap();
+    let circuits_dir = noir_dir.join("circuits");
+    let work_dir = noir_dir.join("work").join("test_node");
+    ZkBackend::with_config(bb_binary, circuits_dir, work_dir, config)
+}
+
+#[tokio::test]
+async fn test_backend_creates_directories() {
+    let temp = get_tempdir().unwrap();
+    let backend = test_backend(temp.path(), ZkConfig::default());
+
+    fs::create_dir_all(&backend.base_dir).await.unwrap();
+    fs::create_dir_all(&backend.circuits_dir).await.unwrap();
+    fs::create_dir_all(&backend.work_dir).await.unwrap();
+
+    assert!(backend.base_dir.exists());
+    assert!(backend.circuits_dir.exists());
+    assert!(backend.work_dir.exists());
+
+    let temp_path = temp.path().to_path_buf();
+    drop(temp);
+    assert!(!temp_path.exists());
+}
+
+#[tokio::test]
+async fn test_version_info_roundtrip() {
+    let temp = get_tempdir().unwrap();
+    let path = temp.path().join("version.json");
+
+    let info = VersionInfo {
+        bb_version: Some("0.87.0".to_string()),
+        circuits_version: Some("0.1.0".to_string()),
+        ..Default::default()
+    };
+
+    info.save(&path).await.unwrap();
+    let loaded = VersionInfo::load(&path).await.unwrap();
+
+    assert_eq!(loaded.bb_version, info.bb_version);
+    assert_eq!(loaded.circuits_version, info.circuits_version);
+
+    let temp_path = temp.path().to_path_buf();
+    drop(temp);
+    assert!(!temp_path.exists());
+}
+
+#[tokio::test]
+async fn test_check_status_full_setup_needed() {
+    if env::var("E3_CUSTOM_BB").is_ok() {
+        return;
+    }
+
+    let temp = get_tempdir().unwrap();
+    let backend = test_backend(temp.path(), ZkConfig::default());
+
+    let status = backend.check_status().await;
+    assert!(matches!(status, SetupStatus::FullSetupNeeded));
+
+    let temp_path = temp.path().to_path_buf();
+    drop(temp);
+    assert!(!temp_path.exists());
+}
+
+#[tokio::test]
+async fn test_check_status_ready_when_installed() {
+    if env::var("E3_CUSTOM_BB").is_ok() {
+        return;
+    }
+
+    let temp = get_tempdir().unwrap();
+    let config = ZkConfig::default();
+    let backend = test_backend(temp.path(), config.clone());
+
+    fs::create_dir_all(&backend.base_dir.join("bin"))
+        .await
+        .unwrap();
+    fs::create_dir_all(&backend.circuits_dir).await.unwrap();
+    fs::write(&backend.bb_binary, b"fake bb binary")
+        .await
+        .unwrap();
+
+    let info = VersionInfo {
+        bb_version: Some(config.required_bb_version.clone()),
+        circuits_version: Some(config.required_circuits_version.clone()),
+        ..Default::default()
+    };
+    info.save(&backend.version_file()).await.unwrap();
+
+    let status = backend.check_status().await;
+    assert!(matches!(status, SetupStatus::Ready));
+
+    let temp_path = temp.path().to_path_buf();
+    drop(temp);
+    assert!(!temp_path.exists());
+}
+
+#[tokio::test]
+async fn test_check_status_bb_needs_update() {
+    if env::var("E3_CUSTOM_BB").is_ok() {
+        return;
+    }
+
+    let temp = get_tempdir().unwrap();
+    let config = ZkConfig::default();
+    let backend = test_backend(temp.path(), config.clone());
+
+    fs::create_dir_all(&backend.base_dir.join("bin"))
+        .await
+        .unwrap();
+    fs::create_dir_all(&backend.circuits_dir).await.unwrap();
+    fs::write(&backend.bb_binary, b"fake bb binary")
+        .await
+        .unwrap();
+
+    let info = VersionInfo {
+        bb_version: Some("0.0.1".to_string()),
+        circuits_version: Some(config.required_circuits_version.clone()),
+        ..Default::default()
+    };
+    info.save(&backend.version_file()).await.unwrap();
+
+    let status = backend.check_status().await;
+    assert!(matches!(status, SetupStatus::BbNeedsUpdate { .. }));
+
+    let temp_path = temp.path().to_path_buf();
+    drop(temp);
+    assert!(!temp_path.exists());
+}
+
+#[tokio::test]
+async fn test_work_dir_cleanup() {
+    let temp = get_tempdir().unwrap();
+    let backend = test_backend(temp.path(), ZkConfig::default());
+
+    fs::create_dir_all(&backend.work_dir).await.unwrap();
+
+    let e3_id = "test-e3-123";
+    let work_dir = backend.work_dir_for(e3_id).unwrap();
+
+    fs::create_dir_all(&work_dir).await.unwrap();
+    fs::write(work_dir.join("proof.bin"), b"fake proof")
+        .await
+        .unwrap();
+    fs::write(work_dir.join("witness.bin"), b"fake witness")
+        .await
+        .unwrap();
+    assert!(work_dir.exists());
+
+    backend.cleanup_work_dir(e3_id).await.unwrap();
+    assert!(!work_dir.exists());
+
+    let temp_path = temp.path().to_path_buf();
+    drop(temp);
+    assert!(!temp_path.exists());
+}
+
+async fn install_fake(backend: &ZkBackend, config: &ZkConfig) {
+    fs::create_dir_all(&backend.base_dir.join("bin"))
+        .await
+        .unwrap();
+    let evm_dir = backend
+        .circuits_dir
+        .join("insecure-512/minimum/evm/recursive_aggregation/decryption_aggregator");
+    fs::create_dir_all(&evm_dir).await.unwrap();
+    fs::write(&backend.bb_binary, b"fake bb binary")
+        .await
+        .unwrap();
+    fs::write(evm_dir.join("decryption_aggregator.json"), b"{}")
+        .await
+        .unwrap();
+    fs::write(evm_dir.join("decryption_aggregator.vk_hash"), [7u8; 32])
+        .await
+        .unwrap();
+
+    let info = VersionInfo {
+        bb_version: Some(config.required_bb_version.clone()),
+        circuits_version: Some(config.required_circuits_version.clone()),
+        ..Default::default()
+    };
+    info.save(&backend.version_file()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_bundle_round_trip() {
+    if env::var("E3_CUSTOM_BB").is_ok() || crate::BbTarget::current().is_none() {
+        return;
+    }
+
+    let temp = get_tempdir().unwrap();
+    let config = ZkConfig::default();
+    let source = test_backend(&temp.path().join("online"), config.clone());
+    install_fake(&source, &config).await;
+
+    let signer = PrivateKeySigner::random();
+    let bundle = temp.path().join("bundle.tar.gz");
+    let manifest = source.export_bundle(&bundle, &signer).await.unwrap();
+    assert!(manifest.files.contains_key("bin/bb"));
+
+    let target = test_backend(&temp.path().join("airgapped"), config.clone());
+    let other = PrivateKeySigner::random();
+    let err = target
+        .import_bundle(&bundle, &[other.address()])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ZkError::BundleError(_)));
+    // Rejected before anything was unpacked
+    assert!(!target.base_dir.exists());
+
+    // A file added to a signed bundle is rejected
+    let tampered = temp.path().join("tampered.tar.gz");
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        std::fs::File::create(&tampered).unwrap(),
+        flate2::Compression::default(),
+    ));
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(
+        std::fs::File::open(&bundle).unwrap(),
+    ));
+    for entry in archive.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let header = entry.header().clone();
+        builder.append(&header, &mut entry).unwrap();
+    }
+    let mut header = tar::Header::new_gnu();
+    header.set_size(2);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, "circuits/extra.json", &b"{}"[..])
+        .unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+    let err = target
+        .import_bundle(&tampered, &[signer.address()])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ZkError::BundleError(_)));
+    assert!(!target.bb_binary.exists());
+
+    target
+        .import_bundle(&bundle, &[signer.address()])
+        .await
+        .unwrap();
+    assert!(matches!(target.check_status().await, SetupStatus::Ready));
+    assert_eq!(
+        fs::read(&target.bb_binary).await.unwrap(),
+        b"fake bb binary"
+    );
+
+    let installed = target.installed_circuits();
+    // The threshold and DKG presets share the insecure-512 artifacts.
+    assert_eq!(installed.len(), 2);
+    assert!(installed
+        .iter()
+        .all(|c| c.artifacts_dir == "insecure-512/minimum" && c.circuits.get("evm") == Some(&1)));
+}
+
+#[tokio::test]
+async fn test_check_onchain_vk() {
+    let temp = get_tempdir().unwrap();
+    let config = ZkConfig::default();
+    let backend = test_backend(temp.path(), config.clone());
+    install_fake(&backend, &config).await;
+
+    let mut code = vec![0x60, 0x80, 0x7f];
+    code.extend_from_slice(&[7u8; 32]);
+    assert_eq!(
+        backend
+            .check_onchain_vk(e3_events::CircuitName::DecryptionAggregator, &code)
+            .unwrap(),
+        vec!["insecure-512/minimum".to_string()]
+    );
+
+    code[3] = 8;
+    assert!(matches!(
+        backend.check_onchain_vk(e3_events::CircuitName::DecryptionAggregator, &code),
+        Err(ZkError::VkMismatch { .. })
+    ));
+    assert!(matches!(
+        backend.check_onchain_vk(e3_events::CircuitName::DkgAggregator, &code),
+        Err(ZkError::CircuitNotFound(_))
+    ));
+}
@@ -0,0 +1,426 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! In-process Barretenberg through its msgpack C entry point (`bbapi`).
+//!
+//! The library is linked at build time from `BB_LIB_DIR` (see `build.rs`) and must match
+//! `required_bb_version` in `versions.json`. Each circuit's proving key input — the decompressed
+//! bytecode together with its verification key, already msgpack-encoded — and every verification
+//! key are loaded once per file and kept resident, so repeated folds skip the process spawn, the
+//! file round-trip and re-encoding the circuit. The library's SRS is initialized from the CRS the
+//! CLI downloads (`~/.bb-crs`) and re-initialized when that file grows.
+
+use super::{ProveJob, ProveOutput, VerifyJob};
+use crate::error::ZkError;
+use base64::engine::{general_purpose, Engine};
+use e3_events::CircuitVariant;
+use flate2::read::GzDecoder;
+use serde::de::{self, DeserializeOwned, IgnoredAny, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tracing::{debug, warn};
+
+const FIELD_BYTES: usize = 32;
+/// Size of one affine G1 point in `bn254_g1.dat`.
+const G1_POINT_BYTES: u64 = 64;
+
+extern "C" {
+    fn bbapi(
+        input_in: *const u8,
+        input_len_in: usize,
+        output_out: *mut *mut u8,
+        output_len_out: *mut usize,
+    );
+    fn bbfree(ptr: *mut c_void);
+}
+
+/// Byte string encoded as msgpack `bin` rather than an array of integers.
+#[derive(Clone, Debug, Default)]
+struct Bin(Vec<u8>);
+
+impl Serialize for Bin {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bin {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BinVisitor;
+        impl<'de> Visitor<'de> for BinVisitor {
+            type Value = Bin;
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("msgpack bin")
+            }
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Bin, E> {
+                Ok(Bin(v.to_vec()))
+            }
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Bin, E> {
+                Ok(Bin(v))
+            }
+        }
+        deserializer.deserialize_bytes(BinVisitor)
+    }
+}
+
+#[derive(Serialize)]
+struct ProofSystemSettings {
+    ipa_accumulation: bool,
+    oracle_hash_type: &'static str,
+    disable_zk: bool,
+    optimized_solidity_verifier: bool,
+}
+
+impl ProofSystemSettings {
+    /// Mirrors `bb -t <verifier_target>` for each [`CircuitVariant`].
+    fn for_variant(variant: CircuitVariant) -> Self {
+        let (oracle_hash_type, disable_zk) = match variant {
+            CircuitVariant::Default => ("poseidon2", true),
+            CircuitVariant::Recursive => ("poseidon2", false),
+            CircuitVariant::Evm => ("keccak", false),
+        };
+        Self {
+            ipa_accumulation: false,
+            oracle_hash_type,
+            disable_zk,
+            optimized_solidity_verifier: false,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CircuitInput<'a> {
+    name: &'a str,
+    bytecode: &'a Bin,
+    verification_key: &'a Bin,
+}
+
+#[derive(Deserialize)]
+struct CircuitProveResponse {
+    public_inputs: Vec<Bin>,
+    proof: Vec<Bin>,
+}
+
+#[derive(Serialize)]
+struct CircuitVerify<'a> {
+    verification_key: &'a Bin,
+    public_inputs: Vec<Bin>,
+    proof: Vec<Bin>,
+    settings: ProofSystemSettings,
+}
+
+#[derive(Deserialize)]
+struct CircuitVerifyResponse {
+    verified: bool,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    message: String,
+}
+
+#[derive(Serialize)]
+struct SrsInitSrs {
+    points_buf: Bin,
+    num_points: u32,
+    g2_point: Bin,
+}
+
+/// Proving key input of one circuit: the msgpack-encoded `CircuitInput` (name, decompressed
+/// bytecode and verification key), spliced into every `CircuitProve` for it.
+struct ResidentProvingKey {
+    circuit_modified: Option<SystemTime>,
+    vk_modified: Option<SystemTime>,
+    encoded: Vec<u8>,
+}
+
+struct ResidentVk {
+    modified: Option<SystemTime>,
+    vk: Bin,
+}
+
+/// Barretenberg linked into this process.
+///
+/// Calls into the library are serialized: bb parallelizes each proof internally, and the
+/// library's global state (CRS, thread pool) is not documented as re-entrant.
+pub struct FfiEngine {
+    proving_keys: Mutex<HashMap<(PathBuf, PathBuf), Arc<ResidentProvingKey>>>,
+    vks: Mutex<HashMap<PathBuf, Arc<ResidentVk>>>,
+    /// Number of G1 points the library's SRS was initialized with.
+    srs_points: Mutex<u64>,
+    call_lock: Mutex<()>,
+}
+
+impl Default for FfiEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FfiEngine {
+    pub fn new() -> Self {
+        Self {
+            proving_keys: Mutex::new(HashMap::new()),
+            vks: Mutex::new(HashMap::new()),
+            srs_points: Mutex::new(0),
+            call_lock: Mutex::new(()),
+        }
+    }
+
+    /// Load a circuit's proving key input and verification key ahead of the first proof.
+    pub fn preload(&self, circuit_path: &Path, vk_path: &Path) -> Result<(), ZkError> {
+        self.proving_key(circuit_path, vk_path)?;
+        self.vk(vk_path)?;
+        Ok(())
+    }
+
+    pub fn prove(&self, job: &ProveJob) -> Result<ProveOutput, ZkError> {
+        let key = self.proving_key(job.circuit_path, job.vk_path)?;
+        let witness = gunzip(job.witness)
+            .map_err(|e| ZkError::ProveFailed(format!("witness decompression: {e}")))?;
+        self.ensure_srs().map_err(ZkError::ProveFailed)?;
+
+        // `["CircuitProve", {circuit, witness, settings}]` with the resident circuit spliced in.
+        let encode = |e: rmp_serde::encode::Error| ZkError::SerializationError(e.to_string());
+        let mut input = vec![0x92];
+        input.extend(rmp_serde::to_vec("CircuitProve").map_err(encode)?);
+        input.push(0x83);
+        input.extend(rmp_serde::to_vec("circuit").map_err(encode)?);
+        input.extend_from_slice(&key.encoded);
+        input.extend(rmp_serde::to_vec("witness").map_err(encode)?);
+        input.extend(rmp_serde::to_vec(&Bin(witness)).map_err(encode)?);
+        input.extend(rmp_serde::to_vec("settings").map_err(encode)?);
+        input.extend(
+            rmp_serde::to_vec_named(&ProofSystemSettings::for_variant(job.variant))
+                .map_err(encode)?,
+        );
+
+        let response: CircuitProveResponse = self
+            .call_encoded("CircuitProve", &input)
+            .map_err(ZkError::ProveFailed)?;
+
+        Ok(ProveOutput {
+            proof: join_fields(response.proof),
+            public_inputs: join_fields(response.public_inputs),
+            peak_rss_kb: None,
+        })
+    }
+
+    /// Like `bb verify`, a proof that bb cannot parse or check simply does not verify; only a
+    /// missing verification key or SRS is an error.
+    pub fn verify(&self, job: &VerifyJob) -> Result<bool, ZkError> {
+        let vk = self.vk(job.vk_path)?;
+        let (Ok(public_inputs), Ok(proof)) =
+            (split_fields(job.public_inputs), split_fields(job.proof))
+        else {
+            warn!(
+                "malformed proof or public inputs for VK {}",
+                job.vk_path.display()
+            );
+            return Ok(false);
+        };
+        self.ensure_srs().map_err(ZkError::VerifyFailed)?;
+
+        let response: Result<CircuitVerifyResponse, String> = self.call(
+            "CircuitVerify",
+            &CircuitVerify {
+                verification_key: &vk.vk,
+                public_inputs,
+                proof,
+                settings: ProofSystemSettings::for_variant(job.variant),
+            },
+        );
+        match response {
+            Ok(response) => Ok(response.verified),
+            Err(e) => {
+                warn!(
+                    "bb verification failed:\nVK: {}\nerror: {e}",
+                    job.vk_path.display()
+                );
+                Ok(false)
+            }
+        }
+    }
+
+    /// Initializes the library's SRS from the CLI's CRS download, again whenever the download
+    /// has grown since (the CLI fetches more points for larger circuits).
+    fn ensure_srs(&self) -> Result<(), String> {
+        let dir = crs_dir().ok_or("could not determine the home directory for ~/.bb-crs")?;
+        let g1_path = dir.join("bn254_g1.dat");
+        let available = fs::metadata(&g1_path)
+            .map_err(|e| {
+                format!(
+                    "CRS {} unavailable ({e}); run `interfold noir setup` or any bb proof once",
+                    g1_path.display()
+                )
+            })?
+            .len()
+            / G1_POINT_BYTES;
+
+        let mut srs_points = self.srs_points.lock().unwrap();
+        if available <= *srs_points {
+            return Ok(());
+        }
+        let num_points = u32::try_from(available).unwrap_or(u32::MAX);
+        let points_buf = fs::read(&g1_path).map_err(|e| e.to_string())?;
+        let g2_point = fs::read(dir.join("bn254_g2.dat")).map_err(|e| e.to_string())?;
+        let _: IgnoredAny = self.call(
+            "SrsInitSrs",
+            &SrsInitSrs {
+                points_buf: Bin(
+                    points_buf[..num_points as usize * G1_POINT_BYTES as usize].to_vec()
+                ),
+                num_points,
+                g2_point: Bin(g2_point),
+            },
+        )?;
+        debug!("initialized bb SRS with {num_points} points");
+        *srs_points = u64::from(num_points);
+        Ok(())
+    }
+
+    fn proving_key(
+        &self,
+        circuit_path: &Path,
+        vk_path: &Path,
+    ) -> Result<Arc<ResidentProvingKey>, ZkError> {
+        let circuit_modified = modified(circuit_path);
+        let vk_modified = modified(vk_path);
+        let cache_key = (circuit_path.to_path_buf(), vk_path.to_path_buf());
+        let mut proving_keys = self.proving_keys.lock().unwrap();
+        if let Some(key) = proving_keys.get(&cache_key) {
+            if key.circuit_modified == circuit_modified && key.vk_modified == vk_modified {
+                return Ok(key.clone());
+            }
+        }
+
+        #[derive(Deserialize)]
+        struct CircuitJson {
+            bytecode: String,
+        }
+        let json: CircuitJson = serde_json::from_slice(&fs::read(circuit_path)?)?;
+        let compressed = general_purpose::STANDARD
+            .decode(&json.bytecode)
+            .map_err(|e| ZkError::SerializationError(format!("base64 decode: {}", e)))?;
+        let bytecode = gunzip(&compressed)
+            .map_err(|e| ZkError::SerializationError(format!("bytecode decompression: {e}")))?;
+        let name = circuit_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let vk = self.vk(vk_path)?;
+        let encoded = rmp_serde::to_vec_named(&CircuitInput {
+            name: &name,
+            bytecode: &Bin(bytecode),
+            verification_key: &vk.vk,
+        })
+        .map_err(|e| ZkError::SerializationError(e.to_string()))?;
+
+        let key = Arc::new(ResidentProvingKey {
+            circuit_modified,
+            vk_modified,
+            encoded,
+        });
+        proving_keys.insert(cache_key, key.clone());
+        Ok(key)
+    }
+
+    fn vk(&self, path: &Path) -> Result<Arc<ResidentVk>, ZkError> {
+        let modified = modified(path);
+        let mut vks = self.vks.lock().unwrap();
+        if let Some(vk) = vks.get(path) {
+            if vk.modified == modified {
+                return Ok(vk.clone());
+            }
+        }
+        let vk = Arc::new(ResidentVk {
+            modified,
+            vk: Bin(fs::read(path)?),
+        });
+        vks.insert(path.to_path_buf(), vk.clone());
+        Ok(vk)
+    }
+
+    /// Sends one `[name, payload]` command and decodes the `[name, payload]` response.
+    fn call<Req: Serialize, Res: DeserializeOwned>(
+        &self,
+        command: &str,
+        payload: &Req,
+    ) -> Result<Res, String> {
+        let input = rmp_serde::to_vec_named(&(command, payload)).map_err(|e| e.to_string())?;
+        self.call_encoded(command, &input)
+    }
+
+    /// Like [`Self::call`] for an already encoded `[name, payload]` command.
+    fn call_encoded<Res: DeserializeOwned>(
+        &self,
+        command: &str,
+        input: &[u8],
+    ) -> Result<Res, String> {
+        let output = {
+            let _guard = self.call_lock.lock().unwrap();
+            let mut out_ptr: *mut u8 = std::ptr::null_mut();
+            let mut out_len: usize = 0;
+            // SAFETY: `input` outlives the call; bb allocates the output buffer, which we copy
+            // and release with `bbfree`.
+            unsafe {
+                bbapi(input.as_ptr(), input.len(), &mut out_ptr, &mut out_len);
+                if out_ptr.is_null() {
+                    return Err(format!("bbapi {command} returned no output"));
+                }
+                let output = std::slice::from_raw_parts(out_ptr, out_len).to_vec();
+                bbfree(out_ptr as *mut c_void);
+                output
+            }
+        };
+
+        let (name, _): (String, IgnoredAny) =
+            rmp_serde::from_slice(&output).map_err(|e| e.to_string())?;
+        if name == "ErrorResponse" {
+            let (_, error): (String, ErrorResponse) =
+                rmp_serde::from_slice(&output).map_err(|e| e.to_string())?;
+            return Err(format!("bbapi {command}: {}", error.message));
+        }
+        let (_, response): (String, Res) =
+            rmp_serde::from_slice(&output).map_err(|e| e.to_string())?;
+        Ok(response)
+    }
+}
+
+/// Where the `bb` CLI keeps its downloaded CRS.
+fn crs_dir() -> Option<PathBuf> {
+    directories::BaseDirs::new().map(|dirs| dirs.home_dir().join(".bb-crs"))
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn gunzip(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    GzDecoder::new(data).read_to_end(&mut out)?;
+    Ok(out)
+}
+
+fn join_fields(fields: Vec<Bin>) -> Vec<u8> {
+    fields.into_iter().flat_map(|f| f.0).collect()
+}
+
+fn split_fields(bytes: &[u8]) -> Result<Vec<Bin>, ZkError> {
+    if bytes.len() % FIELD_BYTES != 0 {
+        return Err(ZkError::InvalidInput(format!(
+            "expected a multiple of {FIELD_BYTES} bytes, got {}",
+            bytes.len()
+        )));
+    }
+    Ok(bytes.chunks(FIELD_BYTES).map(|c| Bin(c.to_vec())).collect())
+}
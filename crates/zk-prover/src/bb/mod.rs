@@ -0,0 +1,310 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Barretenberg engines behind [`crate::ZkProver`].
+//!
+//! [`BbEngine::Cli`] spawns the `bb` binary and round-trips witnesses, proofs and public inputs
+//! through a job directory. With the `bb-ffi` feature, [`BbEngine::Ffi`] calls a linked
+//! Barretenberg library in-process instead (see [`ffi`]). Both produce proof and public-input
+//! bytes in the same layout, so callers can switch engines without touching anything else.
+
+#[cfg(feature = "bb-ffi")]
+pub mod ffi;
+
+use crate::error::ZkError;
+use e3_config::BbEngineKind;
+use e3_events::CircuitVariant;
+use rayon::prelude::*;
+use std::fmt;
+use std::fs;
+#[cfg(unix)]
+use std::io::Read;
+use std::path::{Path, PathBuf};
+#[cfg(unix)]
+use std::process::Stdio;
+use std::process::{Command as StdCommand, Output};
+#[cfg(feature = "bb-ffi")]
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Inputs for one proof.
+pub struct ProveJob<'a> {
+    /// Compiled Noir circuit JSON.
+    pub circuit_path: &'a Path,
+    pub vk_path: &'a Path,
+    /// Gzipped witness as produced by [`crate::WitnessGenerator`].
+    pub witness: &'a [u8],
+    pub variant: CircuitVariant,
+    /// Scratch directory; only the CLI engine writes to it.
+    pub job_dir: &'a Path,
+}
+
+/// Inputs for one verification.
+pub struct VerifyJob<'a> {
+    pub vk_path: &'a Path,
+    pub proof: &'a [u8],
+    pub public_inputs: &'a [u8],
+    pub variant: CircuitVariant,
+    /// Scratch directory; only the CLI engine writes to it.
+    pub job_dir: &'a Path,
+}
+
+/// Raw `bb` output: concatenated 32-byte field elements, as in `bb prove`'s output files.
+pub struct ProveOutput {
+    pub proof: Vec<u8>,
+    pub public_inputs: Vec<u8>,
+    /// Peak resident set of the `bb` child in KiB; `None` when proving in-process.
+    pub peak_rss_kb: Option<u64>,
+}
+
+#[derive(Clone)]
+pub enum BbEngine {
+    /// Spawn the `bb` binary for every call.
+    Cli(PathBuf),
+    /// Call the linked Barretenberg library, keeping circuits and keys resident.
+    #[cfg(feature = "bb-ffi")]
+    Ffi(Arc<ffi::FfiEngine>),
+}
+
+impl fmt::Debug for BbEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BbEngine::Cli(path) => f.debug_tuple("Cli").field(path).finish(),
+            #[cfg(feature = "bb-ffi")]
+            BbEngine::Ffi(_) => f.write_str("Ffi"),
+        }
+    }
+}
+
+impl BbEngine {
+    /// In-process engine backed by the linked Barretenberg library.
+    #[cfg(feature = "bb-ffi")]
+    pub fn ffi() -> Self {
+        BbEngine::Ffi(Arc::new(ffi::FfiEngine::new()))
+    }
+
+    /// Engine selected by a node's `bb_engine` setting. `Ffi` needs the `bb-ffi` feature.
+    pub fn for_kind(kind: BbEngineKind, bb_binary: PathBuf) -> Result<Self, ZkError> {
+        match kind {
+            BbEngineKind::Cli => Ok(BbEngine::Cli(bb_binary)),
+            #[cfg(feature = "bb-ffi")]
+            BbEngineKind::Ffi => Ok(BbEngine::ffi()),
+            #[cfg(not(feature = "bb-ffi"))]
+            BbEngineKind::Ffi => Err(ZkError::EngineUnavailable(
+                "bb_engine is ffi but this build lacks the bb-ffi feature".to_string(),
+            )),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BbEngine::Cli(_) => "cli",
+            #[cfg(feature = "bb-ffi")]
+            BbEngine::Ffi(_) => "ffi",
+        }
+    }
+
+    /// Fails with [`ZkError::BbNotInstalled`] when the engine cannot run at all.
+    pub fn ensure_available(&self) -> Result<(), ZkError> {
+        match self {
+            BbEngine::Cli(bb) if !bb.exists() => Err(ZkError::BbNotInstalled),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn prove(&self, job: &ProveJob) -> Result<ProveOutput, ZkError> {
+        match self {
+            BbEngine::Cli(bb) => cli_prove(bb, job),
+            #[cfg(feature = "bb-ffi")]
+            BbEngine::Ffi(engine) => engine.prove(job),
+        }
+    }
+
+    pub fn verify(&self, job: &VerifyJob) -> Result<bool, ZkError> {
+        match self {
+            BbEngine::Cli(bb) => cli_verify(bb, job),
+            #[cfg(feature = "bb-ffi")]
+            BbEngine::Ffi(engine) => engine.verify(job),
+        }
+    }
+
+    /// Verifies every job and returns one result per job, in job order. `bb` has no multi-proof
+    /// verify command, so the CLI engine runs the jobs concurrently.
+    pub fn verify_all(&self, jobs: &[VerifyJob]) -> Vec<Result<bool, ZkError>> {
+        match self {
+            BbEngine::Cli(bb) => jobs.par_iter().map(|job| cli_verify(bb, job)).collect(),
+            // The engine serializes library calls, so there is nothing to run concurrently.
+            #[cfg(feature = "bb-ffi")]
+            BbEngine::Ffi(engine) => jobs.iter().map(|job| engine.verify(job)).collect(),
+        }
+    }
+}
+
+fn cli_prove(bb: &Path, job: &ProveJob) -> Result<ProveOutput, ZkError> {
+    let witness_path = job.job_dir.join("witness.gz");
+    let output_dir = job.job_dir.join("out");
+    fs::create_dir_all(job.job_dir)?;
+
+    fs::write(&witness_path, job.witness)?;
+
+    let circuit_path_s = job.circuit_path.to_string_lossy();
+    let witness_path_s = witness_path.to_string_lossy();
+    let vk_path_s = job.vk_path.to_string_lossy();
+    let output_dir_s = output_dir.to_string_lossy();
+
+    let args = vec![
+        "prove",
+        "-b",
+        circuit_path_s.as_ref(),
+        "-w",
+        witness_path_s.as_ref(),
+        "-k",
+        vk_path_s.as_ref(),
+        "-o",
+        output_dir_s.as_ref(),
+        "-v",
+        "-t",
+        job.variant.verifier_target(),
+    ];
+
+    let (output, peak_rss_kb) = output_with_peak_rss(StdCommand::new(bb).args(&args))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        return Err(ZkError::ProveFailed(format!(
+            "bb prove failed:\nstderr: {}\nstdout: {}",
+            stderr, stdout
+        )));
+    }
+
+    let proof_path = output_dir.join("proof");
+    let public_inputs_path = output_dir.join("public_inputs");
+    let proof = fs::read(&proof_path).map_err(|e| {
+        ZkError::OutputReadError(format!(
+            "bb output {}: {} (if bb exited 0, check bb version / prover flags)",
+            proof_path.display(),
+            e
+        ))
+    })?;
+    let public_inputs = fs::read(&public_inputs_path).map_err(|e| {
+        ZkError::OutputReadError(format!("bb output {}: {}", public_inputs_path.display(), e))
+    })?;
+
+    Ok(ProveOutput {
+        proof,
+        public_inputs,
+        peak_rss_kb,
+    })
+}
+
+fn cli_verify(bb: &Path, job: &VerifyJob) -> Result<bool, ZkError> {
+    let out_dir = job.job_dir.join("out");
+    fs::create_dir_all(&out_dir)?;
+
+    let proof_path = job.job_dir.join("proof");
+    let public_inputs_path = out_dir.join("public_inputs");
+
+    fs::write(&proof_path, job.proof)?;
+    fs::write(&public_inputs_path, job.public_inputs)?;
+
+    let public_inputs_s = public_inputs_path.to_string_lossy();
+    let proof_s = proof_path.to_string_lossy();
+    let vk_s = job.vk_path.to_string_lossy();
+
+    let args = vec![
+        "verify",
+        "--scheme",
+        "ultra_honk",
+        "-i",
+        public_inputs_s.as_ref(),
+        "-p",
+        proof_s.as_ref(),
+        "-k",
+        vk_s.as_ref(),
+        "-t",
+        job.variant.verifier_target(),
+    ];
+
+    debug!("running bb verify with VK {}", job.vk_path.display());
+    let output = StdCommand::new(bb).args(&args).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        warn!(
+            "bb verification failed:\nVK: {}\nstderr: {}\nstdout: {}",
+            job.vk_path.display(),
+            stderr,
+            stdout
+        );
+    }
+
+    Ok(output.status.success())
+}
+
+/// Runs `command` to completion like [`StdCommand::output`], also returning the child's peak
+/// resident set in KiB where the platform reports it.
+#[cfg(unix)]
+fn output_with_peak_rss(command: &mut StdCommand) -> std::io::Result<(Output, Option<u64>)> {
+    use std::os::unix::process::ExitStatusExt;
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Drain both pipes concurrently so a chatty `bb` cannot block on a full buffer.
+    let stdout = child.stdout.take().map(read_to_end);
+    let stderr = child.stderr.take().map(read_to_end);
+
+    let pid = child.id() as libc::pid_t;
+    let mut status = 0;
+    // SAFETY: `rusage` is plain data that `wait4` fills in.
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    loop {
+        // SAFETY: `pid` is our unreaped child; both out-pointers are valid for the call.
+        let reaped = unsafe { libc::wait4(pid, &mut status, 0, &mut usage) };
+        if reaped == pid {
+            break;
+        }
+        let err = std::io::Error::last_os_error();
+        if err.kind() != std::io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+
+    let join = |reader: Option<std::thread::JoinHandle<Vec<u8>>>| {
+        reader.and_then(|r| r.join().ok()).unwrap_or_default()
+    };
+    let output = Output {
+        status: std::process::ExitStatus::from_raw(status),
+        stdout: join(stdout),
+        stderr: join(stderr),
+    };
+    // `ru_maxrss` is in KiB on Linux and in bytes on macOS.
+    let max_rss = usage.ru_maxrss.max(0) as u64;
+    let peak_rss_kb = if cfg!(target_os = "macos") {
+        max_rss / 1024
+    } else {
+        max_rss
+    };
+    Ok((output, Some(peak_rss_kb)))
+}
+
+#[cfg(not(unix))]
+fn output_with_peak_rss(command: &mut StdCommand) -> std::io::Result<(Output, Option<u64>)> {
+    Ok((command.output()?, None))
+}
+
+#[cfg(unix)]
+fn read_to_end(mut pipe: impl Read + Send + 'static) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
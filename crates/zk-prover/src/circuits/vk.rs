@@ -21,23 +21,13 @@ pub struct VkArtifacts {
 
 fn load_vk_from_dir(circuit_dir: &Path, circuit_name: &str) -> Result<VkArtifacts, ZkError> {
     let vk_path = circuit_dir.join(format!("{}.vk", circuit_name));
-    let vk_hash_path = circuit_dir.join(format!("{}.vk_hash", circuit_name));
 
     let vk_bytes = fs::read(&vk_path)
         .map_err(|e| ZkError::CircuitNotFound(format!("{}: {}", vk_path.display(), e)))?;
-    let vk_hash_bytes = fs::read(&vk_hash_path)
-        .map_err(|e| ZkError::CircuitNotFound(format!("{}: {}", vk_hash_path.display(), e)))?;
-
-    if vk_hash_bytes.len() != 32 {
-        return Err(ZkError::InvalidInput(format!(
-            "{}: expected 32 bytes, got {}",
-            vk_hash_path.display(),
-            vk_hash_bytes.len()
-        )));
-    }
+    let vk_hash_bytes = load_vk_hash(circuit_dir, circuit_name)?;
 
     let verification_key = bytes_to_field_strings(&vk_bytes)?;
-    let key_hash = format!("0x{}", hex::encode(&vk_hash_bytes));
+    let key_hash = format!("0x{}", hex::encode(vk_hash_bytes));
 
     Ok(VkArtifacts {
         verification_key,
@@ -45,6 +35,21 @@ fn load_vk_from_dir(circuit_dir: &Path, circuit_name: &str) -> Result<VkArtifact
     })
 }
 
+/// Loads the 32-byte `.vk_hash` of `circuit_name` from `circuit_dir`.
+pub fn load_vk_hash(circuit_dir: &Path, circuit_name: &str) -> Result<[u8; 32], ZkError> {
+    let vk_hash_path = circuit_dir.join(format!("{}.vk_hash", circuit_name));
+    let vk_hash_bytes = fs::read(&vk_hash_path)
+        .map_err(|e| ZkError::CircuitNotFound(format!("{}: {}", vk_hash_path.display(), e)))?;
+
+    vk_hash_bytes.as_slice().try_into().map_err(|_| {
+        ZkError::InvalidInput(format!(
+            "{}: expected 32 bytes, got {}",
+            vk_hash_path.display(),
+            vk_hash_bytes.len()
+        ))
+    })
+}
+
 /// Loads VK artifacts from `.vk` and `.vk_hash` in the variant-specific circuits directory.
 /// The caller is responsible for passing the correct circuits_dir:
 /// - `circuits_dir(CircuitVariant::Recursive)` for inner/base proofs embedded in a wrapper
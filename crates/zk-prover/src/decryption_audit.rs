@@ -0,0 +1,643 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Offline verification of a published threshold decryption.
+//!
+//! A consumer holding the committee public key, the ciphertext output, the plaintext output and
+//! the C6/C7 proofs of an E3 can re-check the whole commitment chain without trusting the
+//! aggregator:
+//!
+//! ```text
+//! pk ──▶ on-chain pk commitment
+//! ct ──▶ on-chain keccak(ct) ──▶ C6 ct_commitment (per party)
+//!                                C6 d_commitment ──▶ C7 expected_d_commitments
+//!                                                    C7 message ──▶ plaintext output
+//! ```
+//!
+//! [`DecryptionEvidence`] collects the inputs (from exported node events and/or on-chain data),
+//! [`audit_decryption`] runs every check it has inputs for and returns a [`DecryptionAuditReport`].
+//! ZK verification is delegated to a [`DecryptionProofVerifier`] so the checks stay pure;
+//! [`ProverDecryptionVerifier`] is the `bb`-backed implementation.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use alloy::primitives::keccak256;
+use e3_bfv_client::{compute_ct_commitment, compute_pk_commitment, decode_bytes_to_vec_u64};
+use e3_events::{
+    CircuitName, CommitmentLink, E3id, InterfoldEventData, Proof, ProofType, SignedProofPayload,
+};
+use e3_utils::utility_types::ArcBytes;
+use e3_zk_helpers::circuits::threshold::decrypted_shares_aggregation::MAX_MSG_NON_ZERO_COEFFS;
+use e3_zk_helpers::FIELD_BYTE_LEN;
+use fhe::bfv::BfvParameters;
+use serde::{Deserialize, Serialize};
+
+use crate::actors::commitment_links::c6_to_c7::C6ToC7DCommitmentLink;
+use crate::error::ZkError;
+use crate::prover::ZkProver;
+
+/// Everything needed to audit one E3's decryption.
+///
+/// Every field is optional: checks whose inputs are missing are reported as skipped rather than
+/// failed. Only the first ciphertext index is tracked, matching single-output on-chain
+/// publication.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecryptionEvidence {
+    pub e3_id: Option<E3id>,
+    /// Aggregated committee public key (serialized BFV public key).
+    pub public_key: Option<ArcBytes>,
+    /// Published aggregated public-key commitment (`E3.committeePublicKey`).
+    pub pk_commitment: Option<[u8; 32]>,
+    /// Serialized ciphertext output.
+    pub ciphertext_output: Option<ArcBytes>,
+    /// Published `keccak256(ciphertextOutput)` (`E3.ciphertextOutput`).
+    pub ciphertext_output_hash: Option<[u8; 32]>,
+    /// Published plaintext output (`MAX_MSG_NON_ZERO_COEFFS` little-endian `u64` words).
+    pub plaintext_output: Option<ArcBytes>,
+    /// C6 decryption-share proofs keyed by party id.
+    pub share_proofs: BTreeMap<u64, Proof>,
+    /// C7 decrypted-shares aggregation proof.
+    pub aggregation_proof: Option<Proof>,
+    /// Final DecryptionAggregator (EVM) proof submitted with the plaintext.
+    pub decryption_aggregator_proof: Option<Proof>,
+}
+
+impl DecryptionEvidence {
+    pub fn new(e3_id: E3id) -> Self {
+        Self {
+            e3_id: Some(e3_id),
+            ..Default::default()
+        }
+    }
+
+    /// Builds evidence for `e3_id` from a stream of exported events, ignoring other E3s.
+    pub fn from_events(e3_id: E3id, events: impl IntoIterator<Item = InterfoldEventData>) -> Self {
+        let mut evidence = Self::new(e3_id);
+        for event in events {
+            evidence.ingest(&event);
+        }
+        evidence
+    }
+
+    /// Folds a single event into the evidence. Returns `true` when the event contributed.
+    pub fn ingest(&mut self, event: &InterfoldEventData) -> bool {
+        if event.get_e3_id().is_none() || event.get_e3_id() != self.e3_id {
+            return false;
+        }
+
+        match event {
+            InterfoldEventData::PublicKeyAggregated(data) => {
+                self.public_key = Some(data.pubkey.clone());
+                self.pk_commitment = Some(data.pk_commitment);
+            }
+            InterfoldEventData::CiphertextOutputPublished(data) => {
+                // The published hash is only taken from chain; hashing our own copy here would
+                // make the check vacuous.
+                let Some(ct) = data.ciphertext_output.first() else {
+                    return false;
+                };
+                self.ciphertext_output = Some(ct.clone());
+            }
+            InterfoldEventData::DecryptionshareCreated(data) => {
+                let Some(proof) = first_proof(
+                    &data.signed_decryption_proofs,
+                    ProofType::C6ThresholdShareDecryption,
+                ) else {
+                    return false;
+                };
+                self.share_proofs.insert(data.party_id, proof);
+            }
+            InterfoldEventData::AggregationProofSigned(data) => {
+                let Some(proof) =
+                    first_proof(&data.signed_proofs, ProofType::C7DecryptedSharesAggregation)
+                else {
+                    return false;
+                };
+                self.aggregation_proof = Some(proof);
+            }
+            InterfoldEventData::PlaintextAggregated(data) => {
+                // The on-chain `PlaintextOutputPublished` value takes precedence when present.
+                if self.plaintext_output.is_none() {
+                    self.plaintext_output = data.decrypted_output.first().cloned();
+                }
+                if let Some(proof) = data.decryption_aggregator_proofs.first() {
+                    self.decryption_aggregator_proof = Some(proof.clone());
+                }
+            }
+            InterfoldEventData::PlaintextOutputPublished(data) => {
+                self.plaintext_output = Some(data.plaintext_output.clone());
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+fn first_proof(signed: &[SignedProofPayload], proof_type: ProofType) -> Option<Proof> {
+    signed
+        .iter()
+        .find(|s| s.payload.proof_type == proof_type)
+        .map(|s| s.payload.proof.clone())
+}
+
+/// Outcome of a single audit check.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "reason", rename_all = "lowercase")]
+pub enum CheckStatus {
+    Passed,
+    Failed(String),
+    /// The check could not run because an input was missing.
+    Skipped(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditCheck {
+    pub name: String,
+    pub status: CheckStatus,
+}
+
+/// Pass/fail report produced by [`audit_decryption`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecryptionAuditReport {
+    pub e3_id: Option<E3id>,
+    pub checks: Vec<AuditCheck>,
+}
+
+impl DecryptionAuditReport {
+    /// `true` only when every check ran and passed; a skipped check means the chain was not
+    /// fully verified.
+    pub fn passed(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|c| matches!(c.status, CheckStatus::Passed))
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &AuditCheck> {
+        self.checks
+            .iter()
+            .filter(|c| matches!(c.status, CheckStatus::Failed(_)))
+    }
+
+    fn record(&mut self, name: impl Into<String>, status: CheckStatus) {
+        self.checks.push(AuditCheck {
+            name: name.into(),
+            status,
+        });
+    }
+}
+
+impl fmt::Display for DecryptionAuditReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(e3_id) = &self.e3_id {
+            writeln!(f, "Decryption audit for E3 {e3_id}")?;
+        }
+        for check in &self.checks {
+            match &check.status {
+                CheckStatus::Passed => writeln!(f, "  PASS  {}", check.name)?,
+                CheckStatus::Failed(reason) => writeln!(f, "  FAIL  {}: {reason}", check.name)?,
+                CheckStatus::Skipped(reason) => writeln!(f, "  SKIP  {}: {reason}", check.name)?,
+            }
+        }
+        write!(f, "Result: {}", if self.passed() { "PASS" } else { "FAIL" })
+    }
+}
+
+/// ZK verification backend used by [`audit_decryption`].
+pub trait DecryptionProofVerifier {
+    /// Verifies a C6 threshold share decryption proof from `party_id`.
+    fn verify_share_proof(&self, proof: &Proof, party_id: u64) -> Result<bool, ZkError>;
+    /// Verifies the C7 decrypted-shares aggregation proof.
+    fn verify_aggregation_proof(&self, proof: &Proof) -> Result<bool, ZkError>;
+    /// Verifies the final DecryptionAggregator (EVM) proof.
+    fn verify_decryption_aggregator_proof(&self, proof: &Proof) -> Result<bool, ZkError>;
+}
+
+/// [`DecryptionProofVerifier`] that shells out to `bb` through [`ZkProver`].
+pub struct ProverDecryptionVerifier<'a> {
+    prover: &'a ZkProver,
+    e3_id: String,
+    artifacts_dir: String,
+}
+
+impl<'a> ProverDecryptionVerifier<'a> {
+    /// `artifacts_dir` is the per-committee directory, e.g.
+    /// `preset.artifacts_dir_for_committee("minimum")`.
+    pub fn new(prover: &'a ZkProver, e3_id: &E3id, artifacts_dir: impl Into<String>) -> Self {
+        Self {
+            prover,
+            e3_id: e3_id.to_string(),
+            artifacts_dir: artifacts_dir.into(),
+        }
+    }
+}
+
+impl DecryptionProofVerifier for ProverDecryptionVerifier<'_> {
+    fn verify_share_proof(&self, proof: &Proof, party_id: u64) -> Result<bool, ZkError> {
+        self.prover
+            .verify_proof(proof, &self.e3_id, party_id, &self.artifacts_dir)
+    }
+
+    fn verify_aggregation_proof(&self, proof: &Proof) -> Result<bool, ZkError> {
+        self.prover
+            .verify_proof(proof, &self.e3_id, 0, &self.artifacts_dir)
+    }
+
+    fn verify_decryption_aggregator_proof(&self, proof: &Proof) -> Result<bool, ZkError> {
+        self.prover
+            .verify_evm_proof(proof, &self.e3_id, 0, &self.artifacts_dir)
+    }
+}
+
+/// Runs every check `evidence` has inputs for against the threshold parameters of the E3's
+/// preset (see [`e3_fhe_params::RegisteredPreset::build_pair`]).
+pub fn audit_decryption(
+    evidence: &DecryptionEvidence,
+    params: &BfvParameters,
+    verifier: &impl DecryptionProofVerifier,
+) -> DecryptionAuditReport {
+    let mut report = DecryptionAuditReport {
+        e3_id: evidence.e3_id.clone(),
+        checks: Vec::new(),
+    };
+
+    report.record(
+        "public key commitment",
+        check_pk_commitment(evidence, params),
+    );
+    report.record("ciphertext hash", check_ciphertext_hash(evidence));
+
+    let ct_commitment = evidence.ciphertext_output.as_ref().map(|ct| {
+        compute_ct_commitment(
+            ct.to_vec(),
+            params.degree(),
+            params.plaintext(),
+            params.moduli().to_vec(),
+        )
+    });
+
+    if evidence.share_proofs.is_empty() {
+        report.record(
+            "C6 share decryption proofs",
+            CheckStatus::Skipped("no C6 proofs in evidence".to_string()),
+        );
+    }
+    for (party_id, proof) in &evidence.share_proofs {
+        report.record(
+            format!("C6 proof (party {party_id})"),
+            zk_status(proof, CircuitName::ThresholdShareDecryption, || {
+                verifier.verify_share_proof(proof, *party_id)
+            }),
+        );
+
+        let status = match &ct_commitment {
+            None => CheckStatus::Skipped("ciphertext output missing".to_string()),
+            Some(Err(e)) => CheckStatus::Failed(format!("could not commit to ciphertext: {e}")),
+            Some(Ok(expected)) => match proof.extract_input("ct_commitment") {
+                None => CheckStatus::Failed("C6 public signals too short".to_string()),
+                Some(actual) if &actual[..] == expected.as_slice() => CheckStatus::Passed,
+                Some(_) => CheckStatus::Failed(
+                    "C6 ct_commitment does not match the published ciphertext".to_string(),
+                ),
+            },
+        };
+        report.record(format!("C6 ct_commitment (party {party_id})"), status);
+
+        let status = match &evidence.aggregation_proof {
+            None => CheckStatus::Skipped("C7 proof missing".to_string()),
+            Some(c7) => {
+                let link = C6ToC7DCommitmentLink;
+                let values = link.extract_source_values(&proof.public_signals);
+                if link.check_signals(&values, &c7.public_signals) {
+                    CheckStatus::Passed
+                } else {
+                    CheckStatus::Failed(
+                        "d_commitment not among C7 expected_d_commitments".to_string(),
+                    )
+                }
+            }
+        };
+        report.record(format!("C6->C7 d_commitment (party {party_id})"), status);
+    }
+
+    match &evidence.aggregation_proof {
+        None => {
+            report.record(
+                "C7 aggregation proof",
+                CheckStatus::Skipped("C7 proof missing".to_string()),
+            );
+            report.record(
+                "plaintext matches C7 message",
+                CheckStatus::Skipped("C7 proof missing".to_string()),
+            );
+        }
+        Some(c7) => {
+            report.record(
+                "C7 aggregation proof",
+                zk_status(c7, CircuitName::DecryptedSharesAggregation, || {
+                    verifier.verify_aggregation_proof(c7)
+                }),
+            );
+            report.record(
+                "plaintext matches C7 message",
+                check_plaintext(evidence.plaintext_output.as_ref(), c7),
+            );
+        }
+    }
+
+    if let Some(proof) = &evidence.decryption_aggregator_proof {
+        report.record(
+            "DecryptionAggregator proof",
+            zk_status(proof, CircuitName::DecryptionAggregator, || {
+                verifier.verify_decryption_aggregator_proof(proof)
+            }),
+        );
+    }
+
+    report
+}
+
+fn check_pk_commitment(evidence: &DecryptionEvidence, params: &BfvParameters) -> CheckStatus {
+    let (Some(pk), Some(expected)) = (&evidence.public_key, &evidence.pk_commitment) else {
+        return CheckStatus::Skipped("public key or published commitment missing".to_string());
+    };
+    match compute_pk_commitment(
+        pk.to_vec(),
+        params.degree(),
+        params.plaintext(),
+        params.moduli().to_vec(),
+    ) {
+        Ok(actual) if actual == *expected => CheckStatus::Passed,
+        Ok(_) => CheckStatus::Failed("recomputed commitment differs from published".to_string()),
+        Err(e) => CheckStatus::Failed(format!("could not commit to public key: {e}")),
+    }
+}
+
+fn check_ciphertext_hash(evidence: &DecryptionEvidence) -> CheckStatus {
+    let (Some(ct), Some(expected)) = (
+        &evidence.ciphertext_output,
+        &evidence.ciphertext_output_hash,
+    ) else {
+        return CheckStatus::Skipped("ciphertext or published hash missing".to_string());
+    };
+    if keccak256(&ct[..]).0 == *expected {
+        CheckStatus::Passed
+    } else {
+        CheckStatus::Failed("keccak256(ciphertext) differs from published hash".to_string())
+    }
+}
+
+fn zk_status(
+    proof: &Proof,
+    expected: CircuitName,
+    verify: impl FnOnce() -> Result<bool, ZkError>,
+) -> CheckStatus {
+    if proof.circuit != expected {
+        return CheckStatus::Failed(format!("expected {expected} proof, got {}", proof.circuit));
+    }
+    match verify() {
+        Ok(true) => CheckStatus::Passed,
+        Ok(false) => CheckStatus::Failed("proof did not verify".to_string()),
+        Err(e) => CheckStatus::Failed(e.to_string()),
+    }
+}
+
+/// C7 public signals end with `MAX_MSG_NON_ZERO_COEFFS` message fields, which must equal the
+/// published plaintext words.
+fn check_plaintext(plaintext: Option<&ArcBytes>, c7: &Proof) -> CheckStatus {
+    let Some(plaintext) = plaintext else {
+        return CheckStatus::Skipped("plaintext output missing".to_string());
+    };
+    let Ok(words) = decode_bytes_to_vec_u64(plaintext) else {
+        return CheckStatus::Failed("plaintext is not a sequence of u64 words".to_string());
+    };
+
+    let signals = &c7.public_signals;
+    let message_len = MAX_MSG_NON_ZERO_COEFFS * FIELD_BYTE_LEN;
+    if !signals.len().is_multiple_of(FIELD_BYTE_LEN) || signals.len() < message_len {
+        return CheckStatus::Failed("C7 public signals too short".to_string());
+    }
+    let message = &signals[signals.len() - message_len..];
+
+    for (i, field) in message.chunks_exact(FIELD_BYTE_LEN).enumerate() {
+        let word = words.get(i).copied().unwrap_or(0);
+        let (high, low) = field.split_at(FIELD_BYTE_LEN - 8);
+        if high.iter().any(|b| *b != 0) || u64::from_be_bytes(low.try_into().unwrap()) != word {
+            return CheckStatus::Failed(format!("message coefficient {i} differs"));
+        }
+    }
+    if words.iter().skip(MAX_MSG_NON_ZERO_COEFFS).any(|w| *w != 0) {
+        return CheckStatus::Failed(format!(
+            "plaintext has non-zero words beyond the {MAX_MSG_NON_ZERO_COEFFS} proven coefficients"
+        ));
+    }
+    CheckStatus::Passed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use e3_bfv_client::{bfv_encrypt, encode_vec_u64_to_bytes};
+    use e3_fhe_params::{BfvParamSet, BfvPreset};
+    use fhe::bfv::{PublicKey, SecretKey};
+    use fhe_traits::Serialize as _;
+    use std::sync::Arc;
+
+    const PRESET: BfvPreset = BfvPreset::InsecureThreshold512;
+
+    fn params() -> Arc<BfvParameters> {
+        BfvParamSet::from(PRESET).build_arc()
+    }
+
+    struct StubVerifier(bool);
+
+    impl DecryptionProofVerifier for StubVerifier {
+        fn verify_share_proof(&self, _: &Proof, _: u64) -> Result<bool, ZkError> {
+            Ok(self.0)
+        }
+        fn verify_aggregation_proof(&self, _: &Proof) -> Result<bool, ZkError> {
+            Ok(self.0)
+        }
+        fn verify_decryption_aggregator_proof(&self, _: &Proof) -> Result<bool, ZkError> {
+            Ok(self.0)
+        }
+    }
+
+    fn field(value: u64) -> [u8; FIELD_BYTE_LEN] {
+        let mut f = [0u8; FIELD_BYTE_LEN];
+        f[FIELD_BYTE_LEN - 8..].copy_from_slice(&value.to_be_bytes());
+        f
+    }
+
+    fn c6(ct_commitment: [u8; 32], d_commitment: [u8; 32]) -> Proof {
+        // Inputs: expected_sk_commitment, expected_e_sm_commitment, ct_commitment; output: d.
+        let signals = [field(1), field(2), ct_commitment, d_commitment].concat();
+        Proof::new(
+            CircuitName::ThresholdShareDecryption,
+            ArcBytes::from_bytes(&[0u8; 4]),
+            ArcBytes::from_bytes(&signals),
+        )
+    }
+
+    fn c7(d_commitments: &[[u8; 32]], message: &[u64]) -> Proof {
+        let mut signals = Vec::new();
+        for d in d_commitments {
+            signals.extend_from_slice(d);
+        }
+        for i in 0..d_commitments.len() {
+            signals.extend_from_slice(&field(i as u64 + 1));
+        }
+        for i in 0..MAX_MSG_NON_ZERO_COEFFS {
+            signals.extend_from_slice(&field(message.get(i).copied().unwrap_or(0)));
+        }
+        Proof::new(
+            CircuitName::DecryptedSharesAggregation,
+            ArcBytes::from_bytes(&[0u8; 4]),
+            ArcBytes::from_bytes(&signals),
+        )
+    }
+
+    fn evidence() -> DecryptionEvidence {
+        let params: BfvParamSet = PRESET.into();
+        let fhe_params = params.build_arc();
+        let mut rng = rand::rng();
+        let sk = SecretKey::random(&fhe_params, &mut rng);
+        let pk = PublicKey::new(&sk, &mut rng).to_bytes();
+        let ct = bfv_encrypt(
+            [7u64],
+            pk.clone(),
+            params.degree,
+            params.plaintext_modulus,
+            params.moduli,
+        )
+        .unwrap();
+
+        let pk_commitment = compute_pk_commitment(
+            pk.clone(),
+            params.degree,
+            params.plaintext_modulus,
+            params.moduli.to_vec(),
+        )
+        .unwrap();
+        let ct_commitment = compute_ct_commitment(
+            ct.clone(),
+            params.degree,
+            params.plaintext_modulus,
+            params.moduli.to_vec(),
+        )
+        .unwrap();
+
+        let message = vec![7u64];
+        let mut words = message.clone();
+        words.resize(MAX_MSG_NON_ZERO_COEFFS, 0);
+
+        let mut evidence = DecryptionEvidence::new(E3id::new("1", 1));
+        evidence.public_key = Some(ArcBytes::from_bytes(&pk));
+        evidence.pk_commitment = Some(pk_commitment);
+        evidence.ciphertext_output_hash = Some(keccak256(&ct).0);
+        evidence.ciphertext_output = Some(ArcBytes::from_bytes(&ct));
+        evidence.plaintext_output = Some(ArcBytes::from_bytes(&encode_vec_u64_to_bytes(&words)));
+        evidence
+            .share_proofs
+            .insert(0, c6(ct_commitment, field(100)));
+        evidence
+            .share_proofs
+            .insert(2, c6(ct_commitment, field(102)));
+        evidence.aggregation_proof = Some(c7(&[field(100), field(102)], &message));
+        evidence
+    }
+
+    fn status<'a>(report: &'a DecryptionAuditReport, name: &str) -> &'a CheckStatus {
+        &report
+            .checks
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap_or_else(|| panic!("missing check {name}"))
+            .status
+    }
+
+    #[test]
+    fn consistent_evidence_passes() {
+        let report = audit_decryption(&evidence(), &params(), &StubVerifier(true));
+        assert!(report.passed(), "{report}");
+    }
+
+    #[test]
+    fn tampered_plaintext_fails() {
+        let mut evidence = evidence();
+        let mut words = vec![8u64];
+        words.resize(MAX_MSG_NON_ZERO_COEFFS, 0);
+        evidence.plaintext_output = Some(ArcBytes::from_bytes(&encode_vec_u64_to_bytes(&words)));
+
+        let report = audit_decryption(&evidence, &params(), &StubVerifier(true));
+        assert!(!report.passed());
+        assert!(matches!(
+            status(&report, "plaintext matches C7 message"),
+            CheckStatus::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn share_proof_for_other_ciphertext_fails() {
+        let mut evidence = evidence();
+        evidence.share_proofs.insert(2, c6(field(999), field(102)));
+
+        let report = audit_decryption(&evidence, &params(), &StubVerifier(true));
+        assert!(matches!(
+            status(&report, "C6 ct_commitment (party 2)"),
+            CheckStatus::Failed(_)
+        ));
+        assert_eq!(
+            status(&report, "C6 ct_commitment (party 0)"),
+            &CheckStatus::Passed
+        );
+    }
+
+    #[test]
+    fn unlinked_share_and_bad_proofs_fail() {
+        let mut evidence = evidence();
+        evidence.aggregation_proof = Some(c7(&[field(100), field(555)], &[7]));
+
+        let report = audit_decryption(&evidence, &params(), &StubVerifier(false));
+        assert!(matches!(
+            status(&report, "C6->C7 d_commitment (party 2)"),
+            CheckStatus::Failed(_)
+        ));
+        assert!(matches!(
+            status(&report, "C7 aggregation proof"),
+            CheckStatus::Failed(_)
+        ));
+        assert_eq!(report.failures().count(), 4);
+    }
+
+    #[test]
+    fn missing_inputs_are_skipped_not_passed() {
+        let mut evidence = evidence();
+        evidence.public_key = None;
+        evidence.aggregation_proof = None;
+
+        let report = audit_decryption(&evidence, &params(), &StubVerifier(true));
+        assert!(!report.passed());
+        assert_eq!(report.failures().count(), 0);
+        assert!(matches!(
+            status(&report, "public key commitment"),
+            CheckStatus::Skipped(_)
+        ));
+    }
+
+    #[test]
+    fn ingest_ignores_other_e3s() {
+        let mut evidence = DecryptionEvidence::new(E3id::new("1", 1));
+        let other =
+            InterfoldEventData::PlaintextOutputPublished(e3_events::PlaintextOutputPublished {
+                e3_id: E3id::new("2", 1),
+                plaintext_output: ArcBytes::from_bytes(&[1]),
+                proof: ArcBytes::from_bytes(&[]),
+            });
+        assert!(!evidence.ingest(&other));
+        assert!(evidence.plaintext_output.is_none());
+    }
+}
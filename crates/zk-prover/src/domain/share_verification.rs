@@ -174,6 +174,7 @@ pub(crate) struct ZkTallyOutcome {
 pub(crate) fn label_for(kind: &VerificationKind) -> &'static str {
     match kind {
         VerificationKind::ShareProofs => "C2/C3",
+        VerificationKind::ShareComplaints => "C2/C3 complaint",
         VerificationKind::ThresholdDecryptionProofs => "C6",
         VerificationKind::PkGenerationProofs => "C1",
         VerificationKind::DecryptionProofs => "C4",
@@ -11,6 +11,9 @@ pub enum ZkError {
     #[error("Barretenberg binary not found. Run 'interfold noir setup' first.")]
     BbNotInstalled,
 
+    #[error("Barretenberg engine unavailable: {0}")]
+    EngineUnavailable(String),
+
     #[error("Circuit '{0}' not found. Run 'interfold noir setup' first.")]
     CircuitNotFound(String),
 
@@ -68,4 +71,13 @@ pub enum ZkError {
 
     #[error("Invalid proof input: {0}")]
     InvalidInput(String),
+
+    #[error("No prover worker available: {0}")]
+    RemoteWorkerUnavailable(String),
+
+    #[error("Invalid circuit bundle: {0}")]
+    BundleError(String),
+
+    #[error("Local {circuit} verification keys do not match the on-chain verifier ({local})")]
+    VkMismatch { circuit: String, local: String },
 }
@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! `bb`-backed [`InputProofVerifier`] for the `threshold/user_data_encryption` proofs clients
+//! attach to their ciphertexts.
+
+use crate::backend::ZkBackend;
+use crate::circuits::vk::load_vk_hash;
+use crate::error::ZkError;
+use crate::program::{ProgramCircuitArtifacts, ProgramProof};
+use crate::prover::ZkProver;
+use e3_compute_provider::{InputProof, InputProofVerifier};
+use e3_events::CircuitVariant;
+use e3_utils::utility_types::ArcBytes;
+
+const CIRCUIT: &str = "user_data_encryption";
+const INNER_CIRCUITS: [&str; 2] = ["user_data_encryption_ct0", "user_data_encryption_ct1"];
+
+pub struct UserDataEncryptionVerifier {
+    prover: ZkProver,
+    circuit: ProgramCircuitArtifacts,
+    inner_key_hashes: [[u8; 32]; 2],
+}
+
+impl UserDataEncryptionVerifier {
+    /// Verifies against the installed `user_data_encryption` VK in `artifacts_dir`, written for
+    /// the `variant` clients prove with. The inner ct0/ct1 proofs are recursive, so their key
+    /// hashes come from the recursive variant.
+    pub fn new(
+        backend: &ZkBackend,
+        artifacts_dir: &str,
+        variant: CircuitVariant,
+    ) -> Result<Self, ZkError> {
+        let prover = ZkProver::new(backend);
+        let dir = prover
+            .circuits_dir(variant, artifacts_dir)
+            .join("threshold")
+            .join(CIRCUIT);
+        let circuit = ProgramCircuitArtifacts::new(&dir, CIRCUIT, variant);
+
+        let inner_dir = prover
+            .circuits_dir(CircuitVariant::Recursive, artifacts_dir)
+            .join("threshold");
+        let inner_key_hashes = [
+            load_vk_hash(&inner_dir.join(INNER_CIRCUITS[0]), INNER_CIRCUITS[0])?,
+            load_vk_hash(&inner_dir.join(INNER_CIRCUITS[1]), INNER_CIRCUITS[1])?,
+        ];
+        Ok(Self {
+            prover,
+            circuit,
+            inner_key_hashes,
+        })
+    }
+}
+
+impl InputProofVerifier for UserDataEncryptionVerifier {
+    fn inner_key_hashes(&self) -> [[u8; 32]; 2] {
+        self.inner_key_hashes
+    }
+
+    fn verify(&self, proof: &InputProof) -> Result<bool, String> {
+        let proof = ProgramProof {
+            circuit: CIRCUIT.to_string(),
+            data: ArcBytes::from_bytes(&proof.proof),
+            public_signals: ArcBytes::from_bytes(&proof.public_inputs),
+        };
+        self.prover
+            .verify_program(&self.circuit, &proof, "input-validation")
+            .map_err(|e| e.to_string())
+    }
+}
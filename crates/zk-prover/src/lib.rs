@@ -6,13 +6,20 @@
 
 mod actors;
 mod backend;
+pub mod bb;
 mod circuits;
 mod config;
+mod decryption_audit;
 mod dkg_attestation_bundle;
 mod domain;
 mod error;
+mod input_verifier;
 mod node_fold_public;
+mod profile;
+mod program;
+mod proof_cache;
 mod prover;
+mod remote;
 pub mod test_utils;
 mod traits;
 mod witness;
@@ -24,7 +31,11 @@ pub use actors::{
     ZkVerificationResponse,
 };
 
-pub use backend::{SetupStatus, ZkBackend};
+pub use backend::{
+    BundleManifest, BundleSignature, InstalledCircuits, SetupStatus, ZkBackend,
+    ONCHAIN_VERIFIED_CIRCUITS,
+};
+pub use bb::BbEngine;
 pub use circuits::aggregation::c3_accumulator::generate_sequential_c3_fold;
 pub use circuits::aggregation::c6_accumulator::generate_sequential_c6_fold;
 pub use circuits::aggregation::node_dkg_fold::{
@@ -36,11 +47,26 @@ pub use circuits::aggregation::nodes_fold_accumulator::{
     generate_nodes_fold_step, generate_sequential_nodes_fold,
 };
 pub use config::{verify_checksum, BbTarget, CircuitInfo, VersionInfo, ZkConfig};
+pub use decryption_audit::{
+    audit_decryption, AuditCheck, CheckStatus, DecryptionAuditReport, DecryptionEvidence,
+    DecryptionProofVerifier, ProverDecryptionVerifier,
+};
 pub use dkg_attestation_bundle::encode_dkg_attestation_bundle;
 pub use e3_events::CircuitVariant;
 pub use e3_zk_helpers::circuits::dkg::pk::circuit::PkCircuit;
 pub use error::ZkError;
+pub use input_verifier::UserDataEncryptionVerifier;
 pub use node_fold_public::extract_node_fold_agg_commits;
+pub use profile::{
+    export_benchmarks, summarize, CircuitProfileSummary, ProfileKind, ProfileSource, ProfileStore,
+    ProofProfile,
+};
+pub use program::{ProgramCircuitArtifacts, ProgramProof};
+pub use proof_cache::{CacheKey, ProofCache};
 pub use prover::ZkProver;
+pub use remote::{
+    ProverWorkerServer, ProverWorkerServerBuilder, RemoteProveRequest, RemoteProverPool,
+    RemoteVerifyRequest, RemoteVerifyResponse,
+};
 pub use traits::Provable;
 pub use witness::{input_map, CompiledCircuit, WitnessGenerator};
@@ -0,0 +1,502 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Per-proof timing and size records, persisted per E3.
+//!
+//! [`crate::ZkProver`] appends one [`ProofProfile`] line per proof or verification to
+//! `<e3>.jsonl` under the profile dir. Job ids derived from an E3 id (`{e3}_{correlation}`,
+//! `{e3}-nodesfold-step-{n}`) land in the E3's file. [`summarize`] aggregates records per
+//! circuit and [`export_benchmarks`] writes them in the `circuits/benchmarks` raw result
+//! format, so production numbers can go through `generate_report.sh --input-dir`.
+
+use crate::error::ZkError;
+use crate::proof_cache::normalize_scope;
+use e3_events::{CircuitName, CircuitVariant};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::warn;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileKind {
+    Prove,
+    Verify,
+}
+
+/// Where a proof or verification outcome came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileSource {
+    Cache,
+    Remote,
+    Local,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProofProfile {
+    pub kind: ProfileKind,
+    /// `CircuitName::as_str`.
+    pub circuit: String,
+    /// `CircuitName::dir_path`, e.g. `dkg/pk`.
+    pub circuit_path: String,
+    pub variant: CircuitVariant,
+    pub artifacts_dir: String,
+    /// `e3_id` argument the prover was called with.
+    pub job_id: String,
+    pub source: ProfileSource,
+    /// Unix seconds when the operation started.
+    pub started_at: i64,
+    pub seconds: f64,
+    /// Gzipped witness size; 0 for verifications.
+    pub witness_bytes: usize,
+    pub proof_bytes: usize,
+    pub public_inputs_bytes: usize,
+    /// Peak resident set of the `bb` child, when a local `bb` process ran.
+    pub peak_rss_kb: Option<u64>,
+    pub success: bool,
+}
+
+impl ProofProfile {
+    /// A failed local run starting now; the prover fills in the outcome.
+    pub(crate) fn start(
+        kind: ProfileKind,
+        circuit: CircuitName,
+        circuit_path: &str,
+        variant: CircuitVariant,
+        artifacts_dir: &str,
+        job_id: &str,
+    ) -> Self {
+        Self {
+            kind,
+            circuit: circuit.as_str().to_string(),
+            circuit_path: circuit_path.to_string(),
+            variant,
+            artifacts_dir: artifacts_dir.to_string(),
+            job_id: job_id.to_string(),
+            source: ProfileSource::Local,
+            started_at: chrono::Utc::now().timestamp(),
+            seconds: 0.0,
+            witness_bytes: 0,
+            proof_bytes: 0,
+            public_inputs_bytes: 0,
+            peak_rss_kb: None,
+            success: false,
+        }
+    }
+
+    pub(crate) fn finish(&mut self, started: Instant, success: bool) {
+        self.seconds = started.elapsed().as_secs_f64();
+        self.success = success;
+    }
+}
+
+/// Append-only profile files, one per E3.
+#[derive(Clone, Debug)]
+pub struct ProfileStore {
+    root: PathBuf,
+}
+
+impl ProfileStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Profiling is best effort; a failed write only loses the record.
+    pub fn record(&self, profile: &ProofProfile) {
+        let path = self.path(e3_of(&profile.job_id));
+        let result = (|| -> Result<(), ZkError> {
+            fs::create_dir_all(&self.root)?;
+            let mut line = serde_json::to_vec(profile)?;
+            line.push(b'\n');
+            // One write per line keeps concurrent appends from interleaving.
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)?
+                .write_all(&line)?;
+            Ok(())
+        })();
+        if let Err(e) = result {
+            warn!("failed to write proof profile {}: {}", path.display(), e);
+        }
+    }
+
+    /// Records for `e3_id`, in the order they were written.
+    pub fn load(&self, e3_id: &str) -> Result<Vec<ProofProfile>, ZkError> {
+        let path = self.path(e3_of(e3_id));
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(fs::read_to_string(path)?
+            .lines()
+            // A torn final line from a crash is not worth failing the whole report for.
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// E3s with recorded profiles, as stored (`:` replaced by `_`).
+    pub fn e3_ids(&self) -> Result<Vec<String>, ZkError> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+        let mut ids = Vec::new();
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "jsonl") {
+                if let Some(stem) = path.file_stem() {
+                    ids.push(stem.to_string_lossy().into_owned());
+                }
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
+    fn path(&self, e3_id: &str) -> PathBuf {
+        self.root.join(format!("{}.jsonl", normalize_scope(e3_id)))
+    }
+}
+
+/// The E3 a job id belongs to: `{chain}:{id}` followed by anything is that E3; ids without a
+/// chain prefix stand for themselves.
+fn e3_of(job_id: &str) -> &str {
+    let Some(colon) = job_id.find(':') else {
+        return job_id;
+    };
+    let digits = job_id[colon + 1..]
+        .bytes()
+        .take_while(u8::is_ascii_digit)
+        .count();
+    &job_id[..colon + 1 + digits]
+}
+
+/// Aggregated records of one circuit within an E3.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct CircuitProfileSummary {
+    pub circuit: String,
+    pub circuit_path: String,
+    pub proofs: usize,
+    pub cache_hits: usize,
+    pub remote: usize,
+    pub failures: usize,
+    /// Over proofs that ran `bb` (locally or on a worker) and succeeded.
+    pub prove_seconds_total: f64,
+    pub prove_seconds_max: f64,
+    pub witness_bytes_avg: usize,
+    pub proof_bytes_avg: usize,
+    pub public_inputs_bytes_avg: usize,
+    pub peak_rss_kb_max: Option<u64>,
+    pub verifications: usize,
+    /// Over verifications that ran `bb`.
+    pub verify_seconds_total: f64,
+    verify_runs: usize,
+    prove_runs: usize,
+}
+
+impl CircuitProfileSummary {
+    pub fn prove_runs(&self) -> usize {
+        self.prove_runs
+    }
+
+    pub fn prove_seconds_avg(&self) -> f64 {
+        average(self.prove_seconds_total, self.prove_runs)
+    }
+
+    pub fn verify_seconds_avg(&self) -> f64 {
+        average(self.verify_seconds_total, self.verify_runs)
+    }
+}
+
+fn average(total: f64, runs: usize) -> f64 {
+    if runs == 0 {
+        0.0
+    } else {
+        total / runs as f64
+    }
+}
+
+/// Per-circuit summaries, ordered by circuit path.
+pub fn summarize(records: &[ProofProfile]) -> Vec<CircuitProfileSummary> {
+    let mut by_circuit: BTreeMap<&str, (CircuitProfileSummary, [usize; 3])> = BTreeMap::new();
+    for record in records {
+        let (summary, sizes) = by_circuit.entry(&record.circuit_path).or_insert_with(|| {
+            let summary = CircuitProfileSummary {
+                circuit: record.circuit.clone(),
+                circuit_path: record.circuit_path.clone(),
+                ..Default::default()
+            };
+            (summary, [0; 3])
+        });
+        let ran_bb = record.source != ProfileSource::Cache;
+        match record.kind {
+            ProfileKind::Prove => {
+                summary.proofs += 1;
+                match record.source {
+                    ProfileSource::Cache => summary.cache_hits += 1,
+                    ProfileSource::Remote => summary.remote += 1,
+                    ProfileSource::Local => {}
+                }
+                if !record.success {
+                    summary.failures += 1;
+                } else if ran_bb {
+                    summary.prove_runs += 1;
+                    summary.prove_seconds_total += record.seconds;
+                    summary.prove_seconds_max = summary.prove_seconds_max.max(record.seconds);
+                    sizes[0] += record.witness_bytes;
+                    sizes[1] += record.proof_bytes;
+                    sizes[2] += record.public_inputs_bytes;
+                }
+                if let Some(rss) = record.peak_rss_kb {
+                    summary.peak_rss_kb_max = Some(summary.peak_rss_kb_max.unwrap_or(0).max(rss));
+                }
+            }
+            ProfileKind::Verify => {
+                summary.verifications += 1;
+                if !record.success {
+                    summary.failures += 1;
+                } else if ran_bb {
+                    summary.verify_runs += 1;
+                    summary.verify_seconds_total += record.seconds;
+                }
+            }
+        }
+    }
+
+    by_circuit
+        .into_values()
+        .map(|(mut summary, [witness, proof, public_inputs])| {
+            let runs = summary.prove_runs.max(1);
+            summary.witness_bytes_avg = witness / runs;
+            summary.proof_bytes_avg = proof / runs;
+            summary.public_inputs_bytes_avg = public_inputs / runs;
+            summary
+        })
+        .collect()
+}
+
+/// One `circuits/benchmarks/scripts/benchmark_circuit.sh` result file. Only the fields
+/// `generate_report.sh` reads are filled; the rest of that format has no production source.
+#[derive(Serialize)]
+struct BenchmarkRecord<'a> {
+    circuit_name: &'a str,
+    circuit_path: String,
+    mode: &'a str,
+    oracle_type: &'a str,
+    timestamp: String,
+    system_info: SystemInfo,
+    execution: Execution,
+    proof_generation: ProofGeneration,
+    verification: Verification,
+    production: Production<'a>,
+}
+
+#[derive(Serialize)]
+struct SystemInfo {
+    os: &'static str,
+    arch: &'static str,
+    cpu_cores: String,
+}
+
+#[derive(Serialize)]
+struct Execution {
+    success: bool,
+    witness_size_bytes: usize,
+}
+
+#[derive(Serialize)]
+struct ProofGeneration {
+    time_seconds: f64,
+    success: bool,
+    proof_size_bytes: usize,
+}
+
+#[derive(Serialize)]
+struct Verification {
+    time_seconds: f64,
+    success: bool,
+    public_inputs_size_bytes: usize,
+}
+
+/// Fields the benchmark format has no slot for.
+#[derive(Serialize)]
+struct Production<'a> {
+    e3_id: &'a str,
+    proofs: usize,
+    cache_hits: usize,
+    prove_seconds_max: f64,
+    peak_rss_kb_max: Option<u64>,
+}
+
+/// Writes one `<circuit_path with / as _>_default.json` per circuit that ran `bb` into
+/// `out_dir`, with average timings and sizes. Returns the written paths.
+pub fn export_benchmarks(
+    e3_id: &str,
+    records: &[ProofProfile],
+    out_dir: &Path,
+) -> Result<Vec<PathBuf>, ZkError> {
+    fs::create_dir_all(out_dir)?;
+    let timestamp = records
+        .iter()
+        .map(|r| r.started_at)
+        .max()
+        .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_default();
+    let mode = match records.first() {
+        Some(r) if r.artifacts_dir.starts_with("insecure") => "insecure",
+        _ => "secure",
+    };
+    let cpu_cores = std::thread::available_parallelism()
+        .map(|n| n.to_string())
+        .unwrap_or_default();
+
+    let mut written = Vec::new();
+    for summary in summarize(records) {
+        if summary.prove_runs == 0 {
+            continue;
+        }
+        let record = BenchmarkRecord {
+            circuit_name: &summary.circuit,
+            circuit_path: format!("circuits/bin/{}", summary.circuit_path),
+            mode,
+            oracle_type: "default",
+            timestamp: timestamp.clone(),
+            system_info: SystemInfo {
+                os: std::env::consts::OS,
+                arch: std::env::consts::ARCH,
+                cpu_cores: cpu_cores.clone(),
+            },
+            execution: Execution {
+                success: true,
+                witness_size_bytes: summary.witness_bytes_avg,
+            },
+            proof_generation: ProofGeneration {
+                time_seconds: summary.prove_seconds_avg(),
+                success: summary.failures == 0,
+                proof_size_bytes: summary.proof_bytes_avg,
+            },
+            verification: Verification {
+                time_seconds: summary.verify_seconds_avg(),
+                success: summary.verify_runs > 0,
+                public_inputs_size_bytes: summary.public_inputs_bytes_avg,
+            },
+            production: Production {
+                e3_id,
+                proofs: summary.proofs,
+                cache_hits: summary.cache_hits,
+                prove_seconds_max: summary.prove_seconds_max,
+                peak_rss_kb_max: summary.peak_rss_kb_max,
+            },
+        };
+        let path = out_dir.join(format!(
+            "{}_default.json",
+            summary.circuit_path.replace('/', "_")
+        ));
+        fs::write(&path, serde_json::to_vec_pretty(&record)?)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::get_tempdir;
+
+    fn profile(
+        job_id: &str,
+        kind: ProfileKind,
+        source: ProfileSource,
+        seconds: f64,
+    ) -> ProofProfile {
+        ProofProfile {
+            kind,
+            circuit: "pk".to_string(),
+            circuit_path: "dkg/pk".to_string(),
+            variant: CircuitVariant::Recursive,
+            artifacts_dir: "insecure-512/minimum".to_string(),
+            job_id: job_id.to_string(),
+            source,
+            started_at: 1_700_000_000,
+            seconds,
+            witness_bytes: 100,
+            proof_bytes: 200,
+            public_inputs_bytes: 64,
+            peak_rss_kb: (source == ProfileSource::Local).then_some(4096),
+            success: true,
+        }
+    }
+
+    #[test]
+    fn test_records_group_by_e3() {
+        assert_eq!(e3_of("31337:5"), "31337:5");
+        assert_eq!(e3_of("31337:5_abc"), "31337:5");
+        assert_eq!(e3_of("31337:5-nodesfold-step-2"), "31337:5");
+        assert_eq!(e3_of("e3-1"), "e3-1");
+
+        let temp = get_tempdir().unwrap();
+        let store = ProfileStore::new(temp.path().join("profiles"));
+        store.record(&profile(
+            "31337:5",
+            ProfileKind::Prove,
+            ProfileSource::Local,
+            1.0,
+        ));
+        store.record(&profile(
+            "31337:5_7",
+            ProfileKind::Prove,
+            ProfileSource::Cache,
+            0.0,
+        ));
+        store.record(&profile(
+            "31337:50",
+            ProfileKind::Prove,
+            ProfileSource::Local,
+            1.0,
+        ));
+
+        assert_eq!(store.load("31337:5").unwrap().len(), 2);
+        assert_eq!(store.load("31337_50").unwrap().len(), 1);
+        assert_eq!(store.e3_ids().unwrap(), vec!["31337_5", "31337_50"]);
+    }
+
+    #[test]
+    fn test_summary_excludes_cache_hits_from_timings() {
+        let records = [
+            profile("31337:5", ProfileKind::Prove, ProfileSource::Local, 2.0),
+            profile("31337:5", ProfileKind::Prove, ProfileSource::Remote, 4.0),
+            profile("31337:5", ProfileKind::Prove, ProfileSource::Cache, 0.01),
+            profile("31337:5", ProfileKind::Verify, ProfileSource::Local, 0.5),
+        ];
+        let summary = &summarize(&records)[0];
+        assert_eq!(summary.proofs, 3);
+        assert_eq!(summary.cache_hits, 1);
+        assert_eq!(summary.remote, 1);
+        assert_eq!(summary.prove_runs(), 2);
+        assert_eq!(summary.prove_seconds_avg(), 3.0);
+        assert_eq!(summary.prove_seconds_max, 4.0);
+        assert_eq!(summary.verify_seconds_avg(), 0.5);
+        assert_eq!(summary.peak_rss_kb_max, Some(4096));
+
+        let temp = get_tempdir().unwrap();
+        let written = export_benchmarks("31337:5", &records, temp.path()).unwrap();
+        assert_eq!(written, vec![temp.path().join("dkg_pk_default.json")]);
+        let json: serde_json::Value =
+            serde_json::from_slice(&fs::read(&written[0]).unwrap()).unwrap();
+        assert_eq!(json["circuit_path"], "circuits/bin/dkg/pk");
+        assert_eq!(json["mode"], "insecure");
+        assert_eq!(json["proof_generation"]["time_seconds"], 3.0);
+        assert_eq!(json["proof_generation"]["proof_size_bytes"], 200);
+    }
+}
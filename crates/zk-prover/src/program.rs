@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Proving and verifying circuits supplied by E3 programs.
+//!
+//! Program circuits are compiled and keyed by the program, not installed by [`crate::ZkBackend`],
+//! so they are addressed by path instead of [`e3_events::CircuitName`]. Inputs are the JSON that
+//! `e3_zk_helpers::circuits::ProgramCircuit::inputs` produces.
+
+use crate::bb::{ProveJob, VerifyJob};
+use crate::circuits::utils::{bytes_to_field_strings, inputs_json_to_input_map};
+use crate::error::ZkError;
+use crate::prover::ZkProver;
+use crate::witness::{CompiledCircuit, WitnessGenerator};
+use e3_events::CircuitVariant;
+use e3_utils::utility_types::ArcBytes;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+/// A compiled program circuit and its verification key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramCircuitArtifacts {
+    pub name: String,
+    /// Compiled Noir circuit JSON.
+    pub circuit_path: PathBuf,
+    pub vk_path: PathBuf,
+    /// Variant the VK was written for.
+    pub variant: CircuitVariant,
+}
+
+impl ProgramCircuitArtifacts {
+    /// `<dir>/<name>.json` and `<dir>/<name>.vk`, the layout built-in circuits are installed in.
+    pub fn new(dir: &Path, name: &str, variant: CircuitVariant) -> Self {
+        Self {
+            name: name.to_string(),
+            circuit_path: dir.join(format!("{name}.json")),
+            vk_path: dir.join(format!("{name}.vk")),
+            variant,
+        }
+    }
+
+    fn check_exists(&self) -> Result<(), ZkError> {
+        if !self.circuit_path.exists() {
+            return Err(ZkError::CircuitNotFound(format!(
+                "Circuit not found: {} (expected at {})",
+                self.name,
+                self.circuit_path.display()
+            )));
+        }
+        if !self.vk_path.exists() {
+            return Err(ZkError::CircuitNotFound(format!(
+                "VK not found: {}",
+                self.vk_path.display()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A proof of a program circuit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramProof {
+    /// [`ProgramCircuitArtifacts::name`] of the circuit that generated this proof.
+    pub circuit: String,
+    pub data: ArcBytes,
+    pub public_signals: ArcBytes,
+}
+
+impl ProgramProof {
+    /// Public inputs and outputs as `0x`-prefixed field elements, in ABI order.
+    pub fn public_fields(&self) -> Result<Vec<String>, ZkError> {
+        bytes_to_field_strings(&self.public_signals)
+    }
+}
+
+impl ZkProver {
+    /// Generates the witness for `inputs` and proves it. `job_id` scopes the work dir like an
+    /// E3 id does for built-in circuits.
+    pub fn prove_program(
+        &self,
+        circuit: &ProgramCircuitArtifacts,
+        inputs: &serde_json::Value,
+        job_id: &str,
+    ) -> Result<ProgramProof, ZkError> {
+        circuit.check_exists()?;
+        let compiled = CompiledCircuit::from_file(&circuit.circuit_path)?;
+        let witness = WitnessGenerator::new()
+            .generate_witness(&compiled, inputs_json_to_input_map(inputs)?)?;
+
+        self.engine().ensure_available()?;
+        let job_dir = self.job_dir(job_id, &format!("prove_program_{}", circuit.name));
+        debug!(
+            "generating proof for program circuit {} ({} engine)",
+            circuit.name,
+            self.engine().name()
+        );
+
+        let output = self.engine().prove(&ProveJob {
+            circuit_path: &circuit.circuit_path,
+            vk_path: &circuit.vk_path,
+            witness: &witness,
+            variant: circuit.variant,
+            job_dir: &job_dir,
+        });
+        let _ = fs::remove_dir_all(&job_dir);
+        let output = output?;
+
+        info!(
+            "generated proof ({} bytes) for program circuit {} / {}",
+            output.proof.len(),
+            circuit.name,
+            job_id
+        );
+
+        Ok(ProgramProof {
+            circuit: circuit.name.clone(),
+            data: ArcBytes::from_bytes(&output.proof),
+            public_signals: ArcBytes::from_bytes(&output.public_inputs),
+        })
+    }
+
+    /// Verifies `proof` against `circuit`'s VK. Fails if the proof names another circuit.
+    pub fn verify_program(
+        &self,
+        circuit: &ProgramCircuitArtifacts,
+        proof: &ProgramProof,
+        job_id: &str,
+    ) -> Result<bool, ZkError> {
+        if proof.circuit != circuit.name {
+            return Err(ZkError::InvalidInput(format!(
+                "proof is for program circuit {}, not {}",
+                proof.circuit, circuit.name
+            )));
+        }
+        if !circuit.vk_path.exists() {
+            return Err(ZkError::CircuitNotFound(format!(
+                "VK not found: {}",
+                circuit.vk_path.display()
+            )));
+        }
+
+        self.engine().ensure_available()?;
+        let job_dir = self.job_dir(job_id, &format!("verify_program_{}", circuit.name));
+        let verified = self.engine().verify(&VerifyJob {
+            vk_path: &circuit.vk_path,
+            proof: &proof.data,
+            public_inputs: &proof.public_signals,
+            variant: circuit.variant,
+            job_dir: &job_dir,
+        });
+        let _ = fs::remove_dir_all(&job_dir);
+
+        verified
+    }
+}
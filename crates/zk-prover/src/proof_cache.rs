@@ -0,0 +1,332 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Content-addressed cache of generated proofs and successful verifications.
+//!
+//! Keys hash the circuit, variant, artifacts dir and VK bytes together with the witness (for
+//! proving) or the proof and its public inputs (for verification), so a changed circuit build
+//! never serves a stale entry. Every entry remembers the job id (`e3_id` argument of
+//! [`crate::ZkProver`]) that created it, which is how [`ProofCache::evict_scope`] and
+//! [`ProofCache::evict_e3`] find what to drop.
+//!
+//! Only positive verification outcomes are cached: a `false` from `bb` may be a crash rather
+//! than an invalid proof, and must not stick.
+
+use crate::error::ZkError;
+use e3_events::{CircuitName, CircuitVariant, Proof};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+const PROOFS_DIR: &str = "proofs";
+const VERIFIED_DIR: &str = "verified";
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    scope: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    proof: Option<Proof>,
+}
+
+/// Hex-encoded SHA-256 cache key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CacheKey(String);
+
+#[derive(Clone, Debug)]
+pub struct ProofCache {
+    root: PathBuf,
+}
+
+impl ProofCache {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Key for a proof of `witness` against the circuit whose VK lives at `vk_path`.
+    pub fn prove_key(
+        circuit: CircuitName,
+        variant: CircuitVariant,
+        artifacts_dir: &str,
+        vk_path: &Path,
+        witness: &[u8],
+    ) -> Result<CacheKey, ZkError> {
+        let vk = fs::read(vk_path)?;
+        Ok(Self::key(&[
+            b"prove",
+            circuit.as_str().as_bytes(),
+            variant.as_str().as_bytes(),
+            artifacts_dir.as_bytes(),
+            &Sha256::digest(&vk),
+            &Sha256::digest(witness),
+        ]))
+    }
+
+    /// Key for verifying `proof` against the VK at `vk_path`.
+    pub fn verify_key(
+        proof: &Proof,
+        variant: CircuitVariant,
+        artifacts_dir: &str,
+        vk_path: &Path,
+    ) -> Result<CacheKey, ZkError> {
+        let vk = fs::read(vk_path)?;
+        Ok(Self::key(&[
+            b"verify",
+            proof.circuit.as_str().as_bytes(),
+            variant.as_str().as_bytes(),
+            artifacts_dir.as_bytes(),
+            &Sha256::digest(&vk),
+            &Sha256::digest(&proof.public_signals[..]),
+            &Sha256::digest(&proof.data[..]),
+        ]))
+    }
+
+    fn key(parts: &[&[u8]]) -> CacheKey {
+        let mut hasher = Sha256::new();
+        for part in parts {
+            // Length-prefix each part so adjacent fields cannot run into each other.
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part);
+        }
+        CacheKey(hex::encode(hasher.finalize()))
+    }
+
+    pub fn get_proof(&self, key: &CacheKey) -> Option<Proof> {
+        self.read(PROOFS_DIR, key).and_then(|entry| entry.proof)
+    }
+
+    pub fn put_proof(&self, key: &CacheKey, scope: &str, proof: &Proof) {
+        self.write(
+            PROOFS_DIR,
+            key,
+            &CacheEntry {
+                scope: scope.to_string(),
+                proof: Some(proof.clone()),
+            },
+        );
+    }
+
+    pub fn is_verified(&self, key: &CacheKey) -> bool {
+        self.read(VERIFIED_DIR, key).is_some()
+    }
+
+    pub fn put_verified(&self, key: &CacheKey, scope: &str) {
+        self.write(
+            VERIFIED_DIR,
+            key,
+            &CacheEntry {
+                scope: scope.to_string(),
+                proof: None,
+            },
+        );
+    }
+
+    /// Drops every entry created under exactly `scope`.
+    pub fn evict_scope(&self, scope: &str) -> Result<usize, ZkError> {
+        self.evict(|s| s == scope)
+    }
+
+    /// Drops every entry created for `e3_id`, including per-job ids derived from it
+    /// (`{e3}_{correlation}`, `{e3}-nodesfold-step-{n}`).
+    pub fn evict_e3(&self, e3_id: &str) -> Result<usize, ZkError> {
+        let e3 = normalize_scope(e3_id);
+        self.evict(|s| {
+            let s = normalize_scope(s);
+            s == e3
+                || s.strip_prefix(e3.as_str())
+                    .is_some_and(|rest| rest.starts_with('_') || rest.starts_with('-'))
+        })
+    }
+
+    fn evict(&self, matches: impl Fn(&str) -> bool) -> Result<usize, ZkError> {
+        let mut removed = 0;
+        for kind in [PROOFS_DIR, VERIFIED_DIR] {
+            let dir = self.root.join(kind);
+            if !dir.exists() {
+                continue;
+            }
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                // Skip in-flight temp files from concurrent writes.
+                if path.extension().is_none_or(|ext| ext != "json") {
+                    continue;
+                }
+                let Some(cached) = read_entry(&path) else {
+                    // Unreadable entries are useless; drop them while we're here.
+                    let _ = fs::remove_file(&path);
+                    continue;
+                };
+                if matches(&cached.scope) {
+                    fs::remove_file(&path)?;
+                    removed += 1;
+                }
+            }
+        }
+        if removed > 0 {
+            debug!("evicted {removed} proof cache entries");
+        }
+        Ok(removed)
+    }
+
+    fn entry_path(&self, kind: &str, key: &CacheKey) -> PathBuf {
+        self.root.join(kind).join(format!("{}.json", key.0))
+    }
+
+    fn read(&self, kind: &str, key: &CacheKey) -> Option<CacheEntry> {
+        read_entry(&self.entry_path(kind, key))
+    }
+
+    /// Cache writes are best effort; a failure only costs a future `bb` run.
+    fn write(&self, kind: &str, key: &CacheKey, entry: &CacheEntry) {
+        let path = self.entry_path(kind, key);
+        let result = (|| -> Result<(), ZkError> {
+            let dir = self.root.join(kind);
+            fs::create_dir_all(&dir)?;
+            let tmp = tempfile::NamedTempFile::new_in(&dir)?;
+            serde_json::to_writer(&tmp, entry)?;
+            tmp.persist(&path).map_err(|e| ZkError::IoError(e.error))?;
+            Ok(())
+        })();
+        if let Err(e) = result {
+            warn!(
+                "failed to write proof cache entry {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+fn read_entry(path: &Path) -> Option<CacheEntry> {
+    let bytes = fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Job ids replace `:` / `/` / `\` from [`e3_events::E3id`] with `_` when used as path segments.
+pub(crate) fn normalize_scope(scope: &str) -> String {
+    scope
+        .chars()
+        .map(|c| match c {
+            ':' | '/' | '\\' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::get_tempdir;
+    use e3_utils::utility_types::ArcBytes;
+
+    fn proof(data: &[u8]) -> Proof {
+        Proof::new(
+            CircuitName::PkBfv,
+            ArcBytes::from_bytes(data),
+            ArcBytes::from_bytes(b"inputs"),
+        )
+    }
+
+    #[test]
+    fn test_proof_round_trip_and_key_sensitivity() {
+        let temp = get_tempdir().unwrap();
+        let vk = temp.path().join("pk.vk");
+        fs::write(&vk, b"vk-1").unwrap();
+        let cache = ProofCache::new(temp.path().join("cache"));
+
+        let key = ProofCache::prove_key(
+            CircuitName::PkBfv,
+            CircuitVariant::Recursive,
+            "insecure-512",
+            &vk,
+            b"witness",
+        )
+        .unwrap();
+        assert!(cache.get_proof(&key).is_none());
+        cache.put_proof(&key, "31337:1", &proof(b"p"));
+        assert_eq!(cache.get_proof(&key), Some(proof(b"p")));
+
+        let other_witness = ProofCache::prove_key(
+            CircuitName::PkBfv,
+            CircuitVariant::Recursive,
+            "insecure-512",
+            &vk,
+            b"witness2",
+        )
+        .unwrap();
+        assert_ne!(key, other_witness);
+
+        fs::write(&vk, b"vk-2").unwrap();
+        let rebuilt = ProofCache::prove_key(
+            CircuitName::PkBfv,
+            CircuitVariant::Recursive,
+            "insecure-512",
+            &vk,
+            b"witness",
+        )
+        .unwrap();
+        assert!(cache.get_proof(&rebuilt).is_none());
+    }
+
+    #[test]
+    fn test_verify_key_covers_proof_bytes() {
+        let temp = get_tempdir().unwrap();
+        let vk = temp.path().join("pk.vk");
+        fs::write(&vk, b"vk").unwrap();
+        let cache = ProofCache::new(temp.path().join("cache"));
+
+        let good =
+            ProofCache::verify_key(&proof(b"good"), CircuitVariant::Recursive, "a", &vk).unwrap();
+        let forged =
+            ProofCache::verify_key(&proof(b"forged"), CircuitVariant::Recursive, "a", &vk).unwrap();
+        cache.put_verified(&good, "31337:1");
+        assert!(cache.is_verified(&good));
+        assert!(!cache.is_verified(&forged));
+    }
+
+    #[test]
+    fn test_evict_e3_matches_derived_job_ids() {
+        let temp = get_tempdir().unwrap();
+        let vk = temp.path().join("pk.vk");
+        fs::write(&vk, b"vk").unwrap();
+        let cache = ProofCache::new(temp.path().join("cache"));
+
+        let scopes = [
+            "31337:5",
+            "31337_5_7",
+            "31337:5-nodesfold-step-2",
+            "31337:50",
+        ];
+        let keys: Vec<_> = scopes
+            .iter()
+            .map(|scope| {
+                let key = ProofCache::verify_key(
+                    &proof(scope.as_bytes()),
+                    CircuitVariant::Recursive,
+                    "a",
+                    &vk,
+                )
+                .unwrap();
+                cache.put_verified(&key, scope);
+                key
+            })
+            .collect();
+
+        assert_eq!(cache.evict_e3("31337:5").unwrap(), 3);
+        assert!(!cache.is_verified(&keys[0]));
+        assert!(!cache.is_verified(&keys[1]));
+        assert!(!cache.is_verified(&keys[2]));
+        assert!(cache.is_verified(&keys[3]));
+
+        assert_eq!(cache.evict_scope("31337:50").unwrap(), 1);
+        assert!(!cache.is_verified(&keys[3]));
+    }
+}
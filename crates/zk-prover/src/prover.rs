@@ -5,14 +5,19 @@
 // or FITNESS FOR A PARTICULAR PURPOSE.
 
 use crate::backend::ZkBackend;
+use crate::bb::{BbEngine, ProveJob, VerifyJob};
 use crate::error::ZkError;
+use crate::profile::{ProfileKind, ProfileSource, ProfileStore, ProofProfile};
+use crate::proof_cache::{CacheKey, ProofCache};
+use crate::remote::{RemoteProveRequest, RemoteProverPool, RemoteVerifyRequest};
 use e3_events::{CircuitName, CircuitVariant, Proof};
 use e3_fhe_params::BfvPreset;
 use e3_utils::utility_types::ArcBytes;
 use std::fs;
 use std::path::PathBuf;
-use std::process::Command as StdCommand;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
 use tracing::{debug, info, warn};
 
 /// Unique bb job directories — shared [`ZkBackend::work_dir`] must not reuse the same paths
@@ -26,16 +31,24 @@ fn next_bb_work_subdir(prefix: &str) -> String {
 
 pub struct ZkProver {
     bb_binary: PathBuf,
+    engine: BbEngine,
     circuits_dir: PathBuf,
     work_dir: PathBuf,
+    remote_workers: Option<Arc<RemoteProverPool>>,
+    proof_cache: Option<ProofCache>,
+    profiles: Option<ProfileStore>,
 }
 
 impl ZkProver {
     pub fn new(backend: &ZkBackend) -> Self {
         Self {
             bb_binary: backend.bb_binary.clone(),
+            engine: backend.engine(),
             circuits_dir: backend.circuits_dir.clone(),
             work_dir: backend.work_dir.clone(),
+            remote_workers: backend.remote_workers.clone(),
+            proof_cache: backend.proof_cache_dir.clone().map(ProofCache::new),
+            profiles: backend.profile_dir.clone().map(ProfileStore::new),
         }
     }
 
@@ -43,6 +56,18 @@ impl ZkProver {
         self.circuits_dir.join(artifacts_dir).join(variant.as_str())
     }
 
+    fn vk_path(
+        &self,
+        circuit: CircuitName,
+        dir_path: &str,
+        variant: CircuitVariant,
+        artifacts_dir: &str,
+    ) -> PathBuf {
+        self.circuits_dir(variant, artifacts_dir)
+            .join(dir_path)
+            .join(format!("{}.vk", circuit.as_str()))
+    }
+
     pub fn resolve_artifacts_dir(&self, preset: BfvPreset, committee: &str) -> String {
         preset.artifacts_dir_for_committee(committee)
     }
@@ -55,6 +80,25 @@ impl ZkProver {
         &self.bb_binary
     }
 
+    pub(crate) fn engine(&self) -> &BbEngine {
+        &self.engine
+    }
+
+    pub fn profiles(&self) -> Option<&ProfileStore> {
+        self.profiles.as_ref()
+    }
+
+    fn record_profile(&self, profile: &ProofProfile) {
+        if let Some(profiles) = &self.profiles {
+            profiles.record(profile);
+        }
+    }
+
+    /// Fresh scratch dir for one bb job under `scope` (an E3 id or other job id).
+    pub(crate) fn job_dir(&self, scope: &str, prefix: &str) -> PathBuf {
+        self.work_dir.join(scope).join(next_bb_work_subdir(prefix))
+    }
+
     pub fn generate_proof(
         &self,
         circuit: CircuitName,
@@ -132,6 +176,80 @@ impl ZkProver {
         variant: CircuitVariant,
         artifacts_dir: &str,
     ) -> Result<Proof, ZkError> {
+        let started = Instant::now();
+        let mut profile = ProofProfile::start(
+            ProfileKind::Prove,
+            circuit,
+            dir_path,
+            variant,
+            artifacts_dir,
+            e3_id,
+        );
+        profile.witness_bytes = witness_data.len();
+
+        let cached = self.proof_cache.as_ref().and_then(|cache| {
+            let vk_path = self.vk_path(circuit, dir_path, variant, artifacts_dir);
+            let key =
+                ProofCache::prove_key(circuit, variant, artifacts_dir, &vk_path, witness_data)
+                    .ok()?;
+            Some((cache, key))
+        });
+        if let Some((cache, key)) = &cached {
+            if let Some(proof) = cache.get_proof(key) {
+                debug!("proof cache hit for {} / {}", circuit.as_str(), e3_id);
+                profile.source = ProfileSource::Cache;
+                profile.proof_bytes = proof.data.len();
+                profile.public_inputs_bytes = proof.public_signals.len();
+                profile.finish(started, true);
+                self.record_profile(&profile);
+                return Ok(proof);
+            }
+        }
+
+        let result = self.prove_uncached(
+            circuit,
+            witness_data,
+            e3_id,
+            dir_path,
+            variant,
+            artifacts_dir,
+            &mut profile,
+        );
+        if let Ok(proof) = &result {
+            profile.proof_bytes = proof.data.len();
+            profile.public_inputs_bytes = proof.public_signals.len();
+        }
+        profile.finish(started, result.is_ok());
+        self.record_profile(&profile);
+
+        let proof = result?;
+        if let Some((cache, key)) = &cached {
+            cache.put_proof(key, e3_id, &proof);
+        }
+        Ok(proof)
+    }
+
+    /// Worker pool first (when configured), then the local `bb`. Notes which ran in `profile`.
+    #[allow(clippy::too_many_arguments)]
+    fn prove_uncached(
+        &self,
+        circuit: CircuitName,
+        witness_data: &[u8],
+        e3_id: &str,
+        dir_path: &str,
+        variant: CircuitVariant,
+        artifacts_dir: &str,
+        profile: &mut ProofProfile,
+    ) -> Result<Proof, ZkError> {
+        if let Some(pool) = &self.remote_workers {
+            let request =
+                RemoteProveRequest::new(circuit, witness_data, e3_id, variant, artifacts_dir);
+            if let Some(result) = pool.prove(&request) {
+                profile.source = ProfileSource::Remote;
+                return result;
+            }
+        }
+
         self.generate_proof_impl_with_dir(
             circuit,
             witness_data,
@@ -139,9 +257,11 @@ impl ZkProver {
             dir_path,
             variant,
             self.circuits_dir(variant, artifacts_dir),
+            profile,
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn generate_proof_impl_with_dir(
         &self,
         circuit: CircuitName,
@@ -150,12 +270,9 @@ impl ZkProver {
         dir_path: &str,
         variant: CircuitVariant,
         base_dir: std::path::PathBuf,
+        profile: &mut ProofProfile,
     ) -> Result<Proof, ZkError> {
-        if !self.bb_binary.exists() {
-            return Err(ZkError::BbNotInstalled);
-        }
-
-        let verifier_target = variant.verifier_target();
+        self.engine.ensure_available()?;
 
         let circuit_dir = base_dir.join(dir_path);
         let circuit_path = circuit_dir.join(format!("{}.json", circuit.as_str()));
@@ -179,66 +296,27 @@ impl ZkProver {
             .work_dir
             .join(e3_id)
             .join(next_bb_work_subdir(&format!("prove_{}", circuit.as_str())));
-        let witness_path = job_dir.join("witness.gz");
-        let output_dir = job_dir.join("out");
-        fs::create_dir_all(&job_dir)?;
-
-        fs::write(&witness_path, witness_data)?;
 
         debug!(
-            "generating proof for circuit {} using circuit: {}, vk: {}",
+            "generating proof for circuit {} using circuit: {}, vk: {} ({} engine)",
             circuit.as_str(),
             circuit_path.display(),
-            vk_path.display()
+            vk_path.display(),
+            self.engine.name()
         );
 
-        let circuit_path_s = circuit_path.to_string_lossy();
-        let witness_path_s = witness_path.to_string_lossy();
-        let vk_path_s = vk_path.to_string_lossy();
-        let output_dir_s = output_dir.to_string_lossy();
-
-        let args = vec![
-            "prove",
-            "-b",
-            circuit_path_s.as_ref(),
-            "-w",
-            witness_path_s.as_ref(),
-            "-k",
-            vk_path_s.as_ref(),
-            "-o",
-            output_dir_s.as_ref(),
-            "-v",
-            "-t",
-            verifier_target,
-        ];
-
-        let output = StdCommand::new(&self.bb_binary).args(&args).output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            return Err(ZkError::ProveFailed(format!(
-                "bb prove failed:\nstderr: {}\nstdout: {}",
-                stderr, stdout
-            )));
-        }
-
-        let proof_path = output_dir.join("proof");
-        let public_inputs_path = output_dir.join("public_inputs");
-        let proof_data = fs::read(&proof_path).map_err(|e| {
-            ZkError::OutputReadError(format!(
-                "bb output {}: {} (if bb exited 0, check bb version / prover flags)",
-                proof_path.display(),
-                e
-            ))
-        })?;
-        let public_signals = fs::read(&public_inputs_path).map_err(|e| {
-            ZkError::OutputReadError(format!("bb output {}: {}", public_inputs_path.display(), e))
+        let output = self.engine.prove(&ProveJob {
+            circuit_path: &circuit_path,
+            vk_path: &vk_path,
+            witness: witness_data,
+            variant,
+            job_dir: &job_dir,
         })?;
+        profile.peak_rss_kb = output.peak_rss_kb;
 
         info!(
             "generated proof ({} bytes) for {} / {}",
-            proof_data.len(),
+            output.proof.len(),
             circuit.as_str(),
             e3_id
         );
@@ -247,8 +325,8 @@ impl ZkProver {
 
         Ok(Proof::new(
             circuit,
-            ArcBytes::from_bytes(&proof_data),
-            ArcBytes::from_bytes(&public_signals),
+            ArcBytes::from_bytes(&output.proof),
+            ArcBytes::from_bytes(&output.public_inputs),
         ))
     }
 
@@ -277,6 +355,80 @@ impl ZkProver {
         variant: CircuitVariant,
         artifacts_dir: &str,
     ) -> Result<bool, ZkError> {
+        let started = Instant::now();
+        let mut profile = ProofProfile::start(
+            ProfileKind::Verify,
+            proof.circuit,
+            &proof.circuit.dir_path(),
+            variant,
+            artifacts_dir,
+            e3_id,
+        );
+        profile.proof_bytes = proof.data.len();
+        profile.public_inputs_bytes = proof.public_signals.len();
+
+        let cached = self.proof_cache.as_ref().and_then(|cache| {
+            let vk_path = self.vk_path(
+                proof.circuit,
+                &proof.circuit.dir_path(),
+                variant,
+                artifacts_dir,
+            );
+            let key = ProofCache::verify_key(proof, variant, artifacts_dir, &vk_path).ok()?;
+            Some((cache, key))
+        });
+        if let Some((cache, key)) = &cached {
+            if cache.is_verified(key) {
+                debug!(
+                    "verification cache hit for {} (party {})",
+                    proof.circuit.as_str(),
+                    party_id
+                );
+                profile.source = ProfileSource::Cache;
+                profile.finish(started, true);
+                self.record_profile(&profile);
+                return Ok(true);
+            }
+        }
+
+        let result =
+            self.verify_uncached(proof, e3_id, party_id, variant, artifacts_dir, &mut profile);
+        profile.finish(started, matches!(result, Ok(true)));
+        self.record_profile(&profile);
+
+        let verified = result?;
+        if verified {
+            if let Some((cache, key)) = &cached {
+                cache.put_verified(key, e3_id);
+            }
+        }
+        Ok(verified)
+    }
+
+    /// Worker pool first (when configured), then the local `bb`.
+    fn verify_uncached(
+        &self,
+        proof: &Proof,
+        e3_id: &str,
+        party_id: u64,
+        variant: CircuitVariant,
+        artifacts_dir: &str,
+        profile: &mut ProofProfile,
+    ) -> Result<bool, ZkError> {
+        if let Some(pool) = &self.remote_workers {
+            let request = RemoteVerifyRequest {
+                proof: proof.clone(),
+                e3_id: e3_id.to_string(),
+                party_id,
+                variant,
+                artifacts_dir: artifacts_dir.to_string(),
+            };
+            if let Some(result) = pool.verify(&request) {
+                profile.source = ProfileSource::Remote;
+                return result;
+            }
+        }
+
         self.verify_proof_impl(
             proof.circuit,
             &proof.data,
@@ -316,6 +468,156 @@ impl ZkProver {
         )
     }
 
+    /// Verifies `proofs` (each with its sender's party id) for one E3 in parallel and returns the
+    /// indices of those that fail.
+    ///
+    /// Every proof is still verified on its own; there is no aggregated verification. Proofs are
+    /// grouped by circuit, so each group shares one VK, and cache hits are skipped. Each group
+    /// goes to [`BbEngine::verify_all`], whose per-job results name the offenders directly; a
+    /// job that errors counts as failing. With remote workers configured every proof is verified
+    /// individually so it can be dispatched.
+    pub fn verify_parallel(
+        &self,
+        proofs: &[(u64, &Proof)],
+        e3_id: &str,
+        variant: CircuitVariant,
+        artifacts_dir: &str,
+    ) -> Vec<usize> {
+        let individually = |indices: Vec<usize>| -> Vec<usize> {
+            indices
+                .into_iter()
+                .filter(|&i| {
+                    let (party_id, proof) = proofs[i];
+                    !matches!(
+                        self.verify_proof_with_variant(
+                            proof,
+                            e3_id,
+                            party_id,
+                            variant,
+                            artifacts_dir
+                        ),
+                        Ok(true)
+                    )
+                })
+                .collect()
+        };
+        if self.remote_workers.is_some() || self.engine.ensure_available().is_err() {
+            return individually((0..proofs.len()).collect());
+        }
+
+        let mut groups: Vec<(CircuitName, Vec<usize>)> = Vec::new();
+        for (i, (_, proof)) in proofs.iter().enumerate() {
+            match groups
+                .iter_mut()
+                .find(|(circuit, _)| *circuit == proof.circuit)
+            {
+                Some((_, group)) => group.push(i),
+                None => groups.push((proof.circuit, vec![i])),
+            }
+        }
+
+        let mut failed = Vec::new();
+        for (circuit, indices) in groups {
+            let dir_path = circuit.dir_path();
+            let vk_path = self.vk_path(circuit, &dir_path, variant, artifacts_dir);
+            let pending: Vec<(usize, Option<CacheKey>)> = indices
+                .into_iter()
+                .map(|i| {
+                    let key = self.proof_cache.as_ref().and_then(|_| {
+                        ProofCache::verify_key(proofs[i].1, variant, artifacts_dir, &vk_path).ok()
+                    });
+                    (i, key)
+                })
+                .filter(|(_, key)| match (&self.proof_cache, key) {
+                    (Some(cache), Some(key)) => !cache.is_verified(key),
+                    _ => true,
+                })
+                .collect();
+            // A missing VK fails every proof; let the single-proof path report it.
+            if pending.len() < 2 || !vk_path.exists() {
+                failed.extend(individually(pending.iter().map(|(i, _)| *i).collect()));
+                continue;
+            }
+
+            let started = Instant::now();
+            let job_dirs: Vec<PathBuf> = pending
+                .iter()
+                .map(|(i, _)| {
+                    let prefix = format!(
+                        "verify_parallel_party_{}_{}",
+                        proofs[*i].0,
+                        circuit.as_str()
+                    );
+                    self.job_dir(e3_id, &prefix)
+                })
+                .collect();
+            let jobs: Vec<VerifyJob> = pending
+                .iter()
+                .zip(&job_dirs)
+                .map(|((i, _), job_dir)| VerifyJob {
+                    vk_path: &vk_path,
+                    proof: &proofs[*i].1.data,
+                    public_inputs: &proofs[*i].1.public_signals,
+                    variant,
+                    job_dir,
+                })
+                .collect();
+            let results = self.engine.verify_all(&jobs);
+            for job_dir in &job_dirs {
+                let _ = fs::remove_dir_all(job_dir);
+            }
+
+            let passed = results.iter().filter(|r| matches!(r, Ok(true))).count();
+            info!(
+                "verified {} {} proofs in parallel for {} in {:.2}s ({} failed)",
+                pending.len(),
+                circuit.as_str(),
+                e3_id,
+                started.elapsed().as_secs_f64(),
+                pending.len() - passed
+            );
+            // Profiles get the group time amortized over its proofs.
+            let seconds = started.elapsed().as_secs_f64() / pending.len() as f64;
+            for ((i, key), result) in pending.iter().zip(results) {
+                let proof = proofs[*i].1;
+                let verified = match result {
+                    Ok(verified) => verified,
+                    Err(e) => {
+                        warn!(
+                            "parallel verification of {} proof {} for {} errored: {e}",
+                            circuit.as_str(),
+                            i,
+                            e3_id
+                        );
+                        false
+                    }
+                };
+                if verified {
+                    if let (Some(cache), Some(key)) = (&self.proof_cache, key) {
+                        cache.put_verified(key, e3_id);
+                    }
+                } else {
+                    failed.push(*i);
+                }
+                let mut profile = ProofProfile::start(
+                    ProfileKind::Verify,
+                    circuit,
+                    &dir_path,
+                    variant,
+                    artifacts_dir,
+                    e3_id,
+                );
+                profile.proof_bytes = proof.data.len();
+                profile.public_inputs_bytes = proof.public_signals.len();
+                profile.seconds = seconds;
+                profile.success = verified;
+                self.record_profile(&profile);
+            }
+        }
+        failed.sort_unstable();
+        failed
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn verify_proof_impl(
         &self,
@@ -328,15 +630,9 @@ impl ZkProver {
         variant: CircuitVariant,
         artifacts_dir: &str,
     ) -> Result<bool, ZkError> {
-        if !self.bb_binary.exists() {
-            return Err(ZkError::BbNotInstalled);
-        }
+        self.engine.ensure_available()?;
 
-        let verifier_target = variant.verifier_target();
-        let vk_path = self
-            .circuits_dir(variant, artifacts_dir)
-            .join(&dir_path)
-            .join(format!("{}.vk", circuit.as_str()));
+        let vk_path = self.vk_path(circuit, &dir_path, variant, artifacts_dir);
         if !vk_path.exists() {
             return Err(ZkError::CircuitNotFound(format!(
                 "VK not found: {}",
@@ -355,57 +651,37 @@ impl ZkProver {
             "verify_party_{party_id}_{}",
             circuit.as_str()
         )));
-        let out_dir = job_dir.join("out");
-        fs::create_dir_all(&out_dir)?;
-
-        let proof_path = job_dir.join("proof");
-        let public_inputs_path = out_dir.join("public_inputs");
-
-        fs::write(&proof_path, proof_data)?;
-        fs::write(&public_inputs_path, public_signals)?;
-
-        let public_inputs_s = public_inputs_path.to_string_lossy();
-        let proof_s = proof_path.to_string_lossy();
-        let vk_s = vk_path.to_string_lossy();
-
-        let args = vec![
-            "verify",
-            "--scheme",
-            "ultra_honk",
-            "-i",
-            public_inputs_s.as_ref(),
-            "-p",
-            proof_s.as_ref(),
-            "-k",
-            vk_s.as_ref(),
-            "-t",
-            verifier_target,
-        ];
-
-        let output = StdCommand::new(&self.bb_binary).args(&args).output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            warn!(
-                "bb verification failed for {}:\nVK: {}\nstderr: {}\nstdout: {}",
-                circuit.as_str(),
-                vk_path.display(),
-                stderr,
-                stdout
-            );
-        }
+
+        let verified = self.engine.verify(&VerifyJob {
+            vk_path: &vk_path,
+            proof: proof_data,
+            public_inputs: public_signals,
+            variant,
+            job_dir: &job_dir,
+        });
 
         let _ = fs::remove_dir_all(&job_dir);
 
-        Ok(output.status.success())
+        verified
     }
 
+    /// Removes the job's work dir and any cache entries it created.
     pub fn cleanup(&self, e3_id: &str) -> Result<(), ZkError> {
         let job_dir = self.work_dir.join(e3_id);
         if job_dir.exists() {
             fs::remove_dir_all(&job_dir)?;
         }
+        if let Some(cache) = &self.proof_cache {
+            cache.evict_scope(e3_id)?;
+        }
+        Ok(())
+    }
+
+    /// Drops cache entries for a finished E3, including those of jobs derived from its id.
+    pub fn evict_e3(&self, e3_id: &str) -> Result<(), ZkError> {
+        if let Some(cache) = &self.proof_cache {
+            cache.evict_e3(e3_id)?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Out-of-process proving.
+//!
+//! A prover worker (`interfold prover-worker`) exposes the local [`crate::ZkProver`] over a small
+//! bearer-authenticated HTTP/JSON protocol. A node configured with a [`RemoteProverPool`]
+//! dispatches its `generate_proof*` / `verify_*` calls to those workers first and only falls back
+//! to its own `bb` binary when no worker could take the job.
+
+mod pool;
+mod server;
+
+#[cfg(test)]
+mod tests;
+
+pub use pool::RemoteProverPool;
+pub use server::{ProverWorkerServer, ProverWorkerServerBuilder};
+
+use base64::engine::{general_purpose, Engine};
+use e3_events::{CircuitName, CircuitVariant, Proof};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ZkError;
+
+pub const PROVE_PATH: &str = "/prove";
+pub const VERIFY_PATH: &str = "/verify";
+pub const HEALTH_PATH: &str = "/health";
+
+/// Witness + circuit to prove on a worker.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RemoteProveRequest {
+    pub circuit: CircuitName,
+    /// Gzipped witness, base64 encoded.
+    pub witness: String,
+    pub e3_id: String,
+    pub variant: CircuitVariant,
+    pub artifacts_dir: String,
+}
+
+impl RemoteProveRequest {
+    pub fn new(
+        circuit: CircuitName,
+        witness_data: &[u8],
+        e3_id: &str,
+        variant: CircuitVariant,
+        artifacts_dir: &str,
+    ) -> Self {
+        Self {
+            circuit,
+            witness: general_purpose::STANDARD.encode(witness_data),
+            e3_id: e3_id.to_string(),
+            variant,
+            artifacts_dir: artifacts_dir.to_string(),
+        }
+    }
+
+    pub fn witness_bytes(&self) -> Result<Vec<u8>, ZkError> {
+        general_purpose::STANDARD
+            .decode(&self.witness)
+            .map_err(|e| ZkError::SerializationError(format!("base64 decode: {}", e)))
+    }
+}
+
+/// Proof to verify on a worker.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RemoteVerifyRequest {
+    pub proof: Proof,
+    pub e3_id: String,
+    pub party_id: u64,
+    pub variant: CircuitVariant,
+    pub artifacts_dir: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RemoteVerifyResponse {
+    pub verified: bool,
+}
+
+/// Compares tokens without short-circuiting on the first differing byte.
+pub(crate) fn token_matches(expected: &str, provided: &str) -> bool {
+    let (a, b) = (expected.as_bytes(), provided.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,250 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use super::{
+    RemoteProveRequest, RemoteVerifyRequest, RemoteVerifyResponse, PROVE_PATH, VERIFY_PATH,
+};
+use crate::error::ZkError;
+use e3_config::ProverWorkersConfig;
+use e3_events::Proof;
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// How long a worker is skipped after a transport failure.
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(30);
+
+struct Worker {
+    url: String,
+    inflight: AtomicUsize,
+    unhealthy_until: Mutex<Option<Instant>>,
+}
+
+impl Worker {
+    fn new(url: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            inflight: AtomicUsize::new(0),
+            unhealthy_until: Mutex::new(None),
+        }
+    }
+
+    fn is_healthy(&self, now: Instant) -> bool {
+        match *self.unhealthy_until.lock().unwrap() {
+            Some(until) => now >= until,
+            None => true,
+        }
+    }
+
+    fn mark_unhealthy(&self) {
+        *self.unhealthy_until.lock().unwrap() = Some(Instant::now() + UNHEALTHY_COOLDOWN);
+    }
+
+    fn mark_healthy(&self) {
+        *self.unhealthy_until.lock().unwrap() = None;
+    }
+}
+
+/// Decrements a worker's in-flight counter when the request finishes.
+struct InflightGuard<'a>(&'a AtomicUsize);
+
+impl<'a> InflightGuard<'a> {
+    fn new(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(counter)
+    }
+}
+
+impl Drop for InflightGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Why a single worker did not return a result.
+enum DispatchError {
+    /// The worker ran the job and it failed; another worker (or local `bb`) would fail the same way.
+    Rejected(String),
+    /// The worker could not be reached, timed out, refused our token or is misconfigured.
+    Unavailable(String),
+}
+
+/// Client side of the prover worker protocol.
+///
+/// Jobs go to the healthy worker with the fewest in-flight requests (ties rotate). A worker
+/// that fails at the transport level is skipped for [`UNHEALTHY_COOLDOWN`] and the job moves
+/// on to the next one. When every worker is exhausted the caller proves locally, unless
+/// `fallback_local` is disabled.
+///
+/// Requests are blocking and are only attempted off the async runtime (i.e. from compute pool
+/// threads); calls made on a runtime thread are always served locally.
+pub struct RemoteProverPool {
+    workers: Vec<Worker>,
+    token: Option<String>,
+    timeout: Duration,
+    fallback_local: bool,
+    cursor: AtomicUsize,
+    client: OnceLock<Client>,
+}
+
+impl fmt::Debug for RemoteProverPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteProverPool")
+            .field(
+                "workers",
+                &self.workers.iter().map(|w| &w.url).collect::<Vec<_>>(),
+            )
+            .field("timeout", &self.timeout)
+            .field("fallback_local", &self.fallback_local)
+            .finish()
+    }
+}
+
+impl RemoteProverPool {
+    pub fn new(
+        urls: &[String],
+        token: Option<String>,
+        timeout: Duration,
+        fallback_local: bool,
+    ) -> Self {
+        Self {
+            workers: urls.iter().map(|url| Worker::new(url)).collect(),
+            token,
+            timeout,
+            fallback_local,
+            cursor: AtomicUsize::new(0),
+            client: OnceLock::new(),
+        }
+    }
+
+    pub fn from_config(config: &ProverWorkersConfig) -> Self {
+        Self::new(
+            &config.urls,
+            config.token.clone(),
+            Duration::from_secs(config.timeout_secs),
+            config.fallback_local,
+        )
+    }
+
+    pub fn urls(&self) -> Vec<&str> {
+        self.workers.iter().map(|w| w.url.as_str()).collect()
+    }
+
+    /// Proves on a worker. `None` means the job should be proven locally.
+    pub fn prove(&self, request: &RemoteProveRequest) -> Option<Result<Proof, ZkError>> {
+        self.dispatch(PROVE_PATH, request, ZkError::ProveFailed)
+    }
+
+    /// Verifies on a worker. `None` means the proof should be verified locally.
+    pub fn verify(&self, request: &RemoteVerifyRequest) -> Option<Result<bool, ZkError>> {
+        self.dispatch::<_, RemoteVerifyResponse>(VERIFY_PATH, request, ZkError::VerifyFailed)
+            .map(|res| res.map(|r| r.verified))
+    }
+
+    /// Worker indices in the order they should be tried.
+    pub(crate) fn candidates(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let len = self.workers.len();
+        if len == 0 {
+            return vec![];
+        }
+        let start = self.cursor.fetch_add(1, Ordering::Relaxed) % len;
+        let mut order: Vec<usize> = (0..len)
+            .map(|i| (start + i) % len)
+            .filter(|&i| self.workers[i].is_healthy(now))
+            .collect();
+        // Stable sort keeps the rotation among equally loaded workers.
+        order.sort_by_key(|&i| self.workers[i].inflight.load(Ordering::SeqCst));
+        order
+    }
+
+    fn dispatch<Req: Serialize, Res: DeserializeOwned>(
+        &self,
+        path: &str,
+        request: &Req,
+        rejected: fn(String) -> ZkError,
+    ) -> Option<Result<Res, ZkError>> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            debug!("remote prover dispatch skipped on async runtime thread; running locally");
+            return None;
+        }
+
+        let mut last_error = String::from("no healthy prover workers");
+        for index in self.candidates() {
+            let worker = &self.workers[index];
+            let _inflight = InflightGuard::new(&worker.inflight);
+            match self.send(worker, path, request) {
+                Ok(res) => {
+                    worker.mark_healthy();
+                    return Some(Ok(res));
+                }
+                Err(DispatchError::Rejected(msg)) => {
+                    return Some(Err(rejected(format!("worker {}: {}", worker.url, msg))));
+                }
+                Err(DispatchError::Unavailable(msg)) => {
+                    warn!("prover worker {} unavailable: {}", worker.url, msg);
+                    worker.mark_unhealthy();
+                    last_error = format!("{}: {}", worker.url, msg);
+                }
+            }
+        }
+
+        if self.fallback_local {
+            debug!("all prover workers failed ({last_error}); falling back to local bb");
+            None
+        } else {
+            Some(Err(ZkError::RemoteWorkerUnavailable(last_error)))
+        }
+    }
+
+    fn send<Req: Serialize, Res: DeserializeOwned>(
+        &self,
+        worker: &Worker,
+        path: &str,
+        request: &Req,
+    ) -> Result<Res, DispatchError> {
+        let client = match self.client.get() {
+            Some(client) => client,
+            None => {
+                let client = Client::builder()
+                    .build()
+                    .map_err(|e| DispatchError::Unavailable(e.to_string()))?;
+                self.client.get_or_init(|| client)
+            }
+        };
+
+        let mut builder = client
+            .post(format!("{}{}", worker.url, path))
+            .timeout(self.timeout)
+            .json(request);
+        if let Some(token) = &self.token {
+            builder = builder.bearer_auth(token);
+        }
+
+        let response = builder
+            .send()
+            .map_err(|e| DispatchError::Unavailable(e.to_string()))?;
+        let status = response.status();
+        if status.is_success() {
+            return response
+                .json::<Res>()
+                .map_err(|e| DispatchError::Unavailable(format!("invalid response: {e}")));
+        }
+
+        let body = response.text().unwrap_or_default();
+        if status == StatusCode::UNPROCESSABLE_ENTITY {
+            Err(DispatchError::Rejected(body))
+        } else {
+            Err(DispatchError::Unavailable(format!("{status}: {body}")))
+        }
+    }
+}
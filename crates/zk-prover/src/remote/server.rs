@@ -0,0 +1,263 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use super::{
+    token_matches, RemoteProveRequest, RemoteVerifyRequest, RemoteVerifyResponse, HEALTH_PATH,
+    PROVE_PATH, VERIFY_PATH,
+};
+use crate::backend::ZkBackend;
+use crate::error::ZkError;
+use crate::prover::ZkProver;
+use actix_web::{
+    dev::HttpServiceFactory, http::header, web, App, HttpRequest, HttpResponse, HttpServer,
+};
+use anyhow::Result;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+
+/// Witnesses for the larger circuits run to tens of MB once base64 encoded.
+const MAX_PAYLOAD_BYTES: usize = 256 * 1024 * 1024;
+
+#[derive(Clone)]
+pub struct ProverWorkerServerBuilder {
+    backend: ZkBackend,
+    token: Option<String>,
+    port: Option<u16>,
+    host: Option<String>,
+    max_jobs: Option<usize>,
+}
+
+impl ProverWorkerServerBuilder {
+    pub fn new(backend: ZkBackend) -> Self {
+        Self {
+            backend,
+            token: None,
+            port: None,
+            host: None,
+            max_jobs: None,
+        }
+    }
+
+    /// Require `Authorization: Bearer <token>` on every job request
+    pub fn with_token<S: Into<String>>(mut self, token: S) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Set the port number (default: 9200)
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Set the host address (default: "127.0.0.1")
+    pub fn with_host<S: Into<String>>(mut self, host: S) -> Self {
+        self.host = Some(host.into());
+        self
+    }
+
+    /// Maximum number of concurrent `bb` processes (default: available parallelism)
+    pub fn with_max_jobs(mut self, jobs: usize) -> Self {
+        self.max_jobs = Some(jobs.max(1));
+        self
+    }
+
+    pub fn build(self) -> ProverWorkerServer {
+        let max_jobs = self.max_jobs.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        });
+        ProverWorkerServer {
+            state: WorkerState {
+                // Workers always prove with their own bb; never forward to another pool.
+                prover: Arc::new(ZkProver::new(&self.backend.without_remote_workers())),
+                token: self.token.map(Arc::new),
+                jobs: Arc::new(Semaphore::new(max_jobs)),
+            },
+            port: self.port.unwrap_or(9200),
+            host: self.host.unwrap_or_else(|| "127.0.0.1".to_string()),
+        }
+    }
+}
+
+/// HTTP server exposing a local [`ZkProver`] to remote nodes.
+pub struct ProverWorkerServer {
+    state: WorkerState,
+    port: u16,
+    host: String,
+}
+
+impl ProverWorkerServer {
+    pub fn builder(backend: ZkBackend) -> ProverWorkerServerBuilder {
+        ProverWorkerServerBuilder::new(backend)
+    }
+
+    /// Get the bind address as a string
+    pub fn bind_address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    /// Run the HTTP server
+    pub async fn run(&self) -> Result<()> {
+        let bind_addr = self.bind_address();
+        if self.state.token.is_none() {
+            warn!("prover worker started without a token; any client can submit jobs");
+        }
+        let state = self.state.clone();
+        let server =
+            HttpServer::new(move || App::new().service(routes(state.clone()))).bind(&bind_addr)?;
+
+        info!("Prover worker listening on http://{}", bind_addr);
+        server.run().await.map_err(Into::into)
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct WorkerState {
+    prover: Arc<ZkProver>,
+    token: Option<Arc<String>>,
+    jobs: Arc<Semaphore>,
+}
+
+impl WorkerState {
+    #[cfg(test)]
+    pub(crate) fn new(backend: &ZkBackend, token: Option<&str>) -> Self {
+        Self {
+            prover: Arc::new(ZkProver::new(backend)),
+            token: token.map(|t| Arc::new(t.to_string())),
+            jobs: Arc::new(Semaphore::new(1)),
+        }
+    }
+}
+
+pub(crate) fn routes(state: WorkerState) -> impl HttpServiceFactory {
+    web::scope("")
+        .app_data(web::Data::new(state))
+        .app_data(web::JsonConfig::default().limit(MAX_PAYLOAD_BYTES))
+        .route(PROVE_PATH, web::post().to(handle_prove))
+        .route(VERIFY_PATH, web::post().to(handle_verify))
+        .route(HEALTH_PATH, web::get().to(handle_health_check))
+}
+
+fn authorized(state: &WorkerState, req: &HttpRequest) -> bool {
+    let Some(expected) = &state.token else {
+        return true;
+    };
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|provided| token_matches(expected, provided))
+}
+
+/// Job fields end up in paths under the worker's work and circuits dirs.
+fn validate_job(e3_id: &str, artifacts_dir: &str) -> Result<(), ZkError> {
+    ZkBackend::sanitize_e3_id(e3_id)
+        .map_err(|_| ZkError::InvalidInput(format!("invalid e3_id '{e3_id}'")))?;
+    if artifacts_dir.is_empty()
+        || artifacts_dir.contains("..")
+        || artifacts_dir.contains('\\')
+        || artifacts_dir.starts_with('/')
+    {
+        return Err(ZkError::InvalidInput(format!(
+            "invalid artifacts_dir '{artifacts_dir}'"
+        )));
+    }
+    Ok(())
+}
+
+/// Setup problems on the worker are reported as unavailable so the client tries elsewhere;
+/// anything else is a property of the job itself.
+fn error_response(err: ZkError) -> HttpResponse {
+    match err {
+        ZkError::BbNotInstalled
+        | ZkError::EngineUnavailable(_)
+        | ZkError::CircuitNotFound(_)
+        | ZkError::NotInitialized
+        | ZkError::IoError(_) => HttpResponse::ServiceUnavailable().body(err.to_string()),
+        _ => HttpResponse::UnprocessableEntity().body(err.to_string()),
+    }
+}
+
+async fn handle_prove(
+    state: web::Data<WorkerState>,
+    req: HttpRequest,
+    body: web::Json<RemoteProveRequest>,
+) -> HttpResponse {
+    if !authorized(&state, &req) {
+        return HttpResponse::Unauthorized().finish();
+    }
+    let request = body.into_inner();
+    if let Err(e) = validate_job(&request.e3_id, &request.artifacts_dir) {
+        return error_response(e);
+    }
+    let witness = match request.witness_bytes() {
+        Ok(witness) => witness,
+        Err(e) => return error_response(e),
+    };
+    let Ok(_permit) = state.jobs.acquire().await else {
+        return HttpResponse::ServiceUnavailable().finish();
+    };
+
+    let prover = state.prover.clone();
+    let result = web::block(move || {
+        prover.generate_proof_with_variant(
+            request.circuit,
+            &witness,
+            &request.e3_id,
+            request.variant,
+            &request.artifacts_dir,
+        )
+    })
+    .await;
+
+    match result {
+        Ok(Ok(proof)) => HttpResponse::Ok().json(proof),
+        Ok(Err(e)) => error_response(e),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
+async fn handle_verify(
+    state: web::Data<WorkerState>,
+    req: HttpRequest,
+    body: web::Json<RemoteVerifyRequest>,
+) -> HttpResponse {
+    if !authorized(&state, &req) {
+        return HttpResponse::Unauthorized().finish();
+    }
+    let request = body.into_inner();
+    if let Err(e) = validate_job(&request.e3_id, &request.artifacts_dir) {
+        return error_response(e);
+    }
+    let Ok(_permit) = state.jobs.acquire().await else {
+        return HttpResponse::ServiceUnavailable().finish();
+    };
+
+    let prover = state.prover.clone();
+    let result = web::block(move || {
+        prover.verify_proof_with_variant(
+            &request.proof,
+            &request.e3_id,
+            request.party_id,
+            request.variant,
+            &request.artifacts_dir,
+        )
+    })
+    .await;
+
+    match result {
+        Ok(Ok(verified)) => HttpResponse::Ok().json(RemoteVerifyResponse { verified }),
+        Ok(Err(e)) => error_response(e),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
+async fn handle_health_check() -> HttpResponse {
+    HttpResponse::Ok().body("ok")
+}
@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+use super::server::{routes, WorkerState};
+use super::*;
+use crate::backend::ZkBackend;
+use crate::test_utils::get_tempdir;
+use actix_web::{http::StatusCode, test, App};
+use e3_config::BBPath;
+use std::time::Duration;
+
+/// Nothing listens on port 1, so connections are refused immediately.
+const DEAD_WORKER: &str = "http://127.0.0.1:1";
+
+fn prove_request() -> RemoteProveRequest {
+    RemoteProveRequest::new(
+        CircuitName::PkBfv,
+        b"witness",
+        "e3-1",
+        CircuitVariant::Recursive,
+        "insecure-512",
+    )
+}
+
+fn test_backend(temp_path: &std::path::Path) -> ZkBackend {
+    let noir_dir = temp_path.join("noir");
+    ZkBackend::new(
+        BBPath::Default(noir_dir.join("bin").join("bb")),
+        noir_dir.join("circuits"),
+        noir_dir.join("work").join("test_node"),
+    )
+}
+
+#[test]
+fn test_token_matches() {
+    assert!(token_matches("secret", "secret"));
+    assert!(!token_matches("secret", "secreT"));
+    assert!(!token_matches("secret", "secret2"));
+    assert!(!token_matches("secret", ""));
+}
+
+#[test]
+fn test_witness_round_trip() {
+    assert_eq!(prove_request().witness_bytes().unwrap(), b"witness");
+}
+
+#[test]
+fn test_candidates_rotate_between_idle_workers() {
+    let urls = vec!["http://a".to_string(), "http://b".to_string()];
+    let pool = RemoteProverPool::new(&urls, None, Duration::from_secs(1), true);
+    let first = pool.candidates()[0];
+    let second = pool.candidates()[0];
+    assert_ne!(first, second);
+    assert_eq!(pool.candidates().len(), 2);
+}
+
+#[test]
+fn test_dead_worker_falls_back_to_local() {
+    let pool = RemoteProverPool::new(
+        &[DEAD_WORKER.to_string()],
+        None,
+        Duration::from_secs(5),
+        true,
+    );
+    assert!(pool.prove(&prove_request()).is_none());
+    // The worker is now cooling down and is no longer offered.
+    assert!(pool.candidates().is_empty());
+}
+
+#[test]
+fn test_dead_worker_without_fallback_errors() {
+    let pool = RemoteProverPool::new(
+        &[DEAD_WORKER.to_string()],
+        None,
+        Duration::from_secs(5),
+        false,
+    );
+    let result = pool.prove(&prove_request());
+    assert!(matches!(
+        result,
+        Some(Err(ZkError::RemoteWorkerUnavailable(_)))
+    ));
+}
+
+#[actix_web::test]
+async fn test_worker_rejects_bad_token() {
+    let temp = get_tempdir().unwrap();
+    let state = WorkerState::new(&test_backend(temp.path()), Some("secret"));
+    let app = test::init_service(App::new().service(routes(state))).await;
+
+    let req = test::TestRequest::post()
+        .uri(PROVE_PATH)
+        .insert_header(("Authorization", "Bearer wrong"))
+        .set_json(prove_request())
+        .to_request();
+    assert_eq!(
+        test::call_service(&app, req).await.status(),
+        StatusCode::UNAUTHORIZED
+    );
+
+    let req = test::TestRequest::get().uri(HEALTH_PATH).to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn test_worker_without_bb_reports_unavailable() {
+    let temp = get_tempdir().unwrap();
+    let state = WorkerState::new(&test_backend(temp.path()), Some("secret"));
+    let app = test::init_service(App::new().service(routes(state))).await;
+
+    let req = test::TestRequest::post()
+        .uri(PROVE_PATH)
+        .insert_header(("Authorization", "Bearer secret"))
+        .set_json(prove_request())
+        .to_request();
+    assert_eq!(
+        test::call_service(&app, req).await.status(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+}
+
+#[actix_web::test]
+async fn test_worker_rejects_path_traversal() {
+    let temp = get_tempdir().unwrap();
+    let state = WorkerState::new(&test_backend(temp.path()), None);
+    let app = test::init_service(App::new().service(routes(state))).await;
+
+    let mut request = prove_request();
+    request.artifacts_dir = "../../etc".to_string();
+    let req = test::TestRequest::post()
+        .uri(PROVE_PATH)
+        .set_json(request)
+        .to_request();
+    assert_eq!(
+        test::call_service(&app, req).await.status(),
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+}
@@ -14,7 +14,7 @@ use tempfile::TempDir;
 use tokio::fs;
 
 /// Root of the compiled circuit artifacts: `{workspace}/circuits/bin/`.
-fn circuits_build_root() -> PathBuf {
+pub fn circuits_build_root() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
         .join("..")
         .join("..")
@@ -233,6 +233,14 @@ pub fn active_bin_committee() -> Option<String> {
     v.get("committee")?.as_str().map(|s| s.to_lowercase())
 }
 
+/// Like [`active_bin_committee`] for the `preset` field (e.g. `"insecure-512"`).
+pub fn active_bin_preset() -> Option<String> {
+    let path = circuits_build_root().join(".active-preset.json");
+    let raw = std::fs::read_to_string(&path).ok()?;
+    let v: serde_json::Value = serde_json::from_str(&raw).ok()?;
+    v.get("preset")?.as_str().map(|s| s.to_lowercase())
+}
+
 /// Returns `true` when the compiled `pk_aggregation` (C5) circuit was built for the **minimum**
 /// committee (H=2): `expected_threshold_pk_commitments` array length == 2.
 ///
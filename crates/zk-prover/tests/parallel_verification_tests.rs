@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+mod common;
+
+use common::{find_bb, fixtures_dir, setup_test_prover};
+use e3_events::{CircuitName, Proof};
+use e3_utils::utility_types::ArcBytes;
+use e3_zk_prover::{CircuitVariant, ProgramCircuitArtifacts, ZkProver};
+use serde_json::json;
+use std::process::Command;
+
+const VARIANT: CircuitVariant = CircuitVariant::Recursive;
+const ARTIFACTS_DIR: &str = "insecure-512/minimum";
+const CIRCUIT: CircuitName = CircuitName::PkBfv;
+
+#[tokio::test]
+async fn test_verify_parallel_locates_offenders() {
+    let Some(bb) = find_bb().await else {
+        println!("skipping: bb not found");
+        return;
+    };
+    let (backend, _temp) = setup_test_prover(&bb).await;
+    let prover = ZkProver::new(&backend.without_proof_cache());
+
+    // Stage the `dummy` fixture where the prover looks for `CIRCUIT`.
+    let dir = prover
+        .circuits_dir(VARIANT, ARTIFACTS_DIR)
+        .join(CIRCUIT.dir_path());
+    std::fs::create_dir_all(&dir).unwrap();
+    let circuit = ProgramCircuitArtifacts::new(&dir, CIRCUIT.as_str(), VARIANT);
+    std::fs::copy(fixtures_dir().join("dummy.json"), &circuit.circuit_path).unwrap();
+    let status = Command::new(&bb)
+        .args(["write_vk", "-b"])
+        .arg(&circuit.circuit_path)
+        .arg("-o")
+        .arg(&dir)
+        .args(["-t", VARIANT.verifier_target()])
+        .status()
+        .unwrap();
+    assert!(status.success(), "bb write_vk failed");
+    std::fs::rename(dir.join("vk"), &circuit.vk_path).unwrap();
+
+    let mut proofs: Vec<Proof> = [(5, 3), (2, 2), (1, 9)]
+        .into_iter()
+        .map(|(x, y): (u64, u64)| {
+            let inputs =
+                json!({ "x": x.to_string(), "y": y.to_string(), "_sum": (x + y).to_string() });
+            let proof = prover.prove_program(&circuit, &inputs, "batch").unwrap();
+            Proof::new(CIRCUIT, proof.data, proof.public_signals)
+        })
+        .collect();
+
+    let parties = |proofs: &[Proof]| -> Vec<(u64, Proof)> {
+        proofs
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i as u64 + 1, p.clone()))
+            .collect()
+    };
+    let batch = parties(&proofs);
+    let refs: Vec<(u64, &Proof)> = batch.iter().map(|(id, p)| (*id, p)).collect();
+    assert!(prover
+        .verify_parallel(&refs, "31337:1", VARIANT, ARTIFACTS_DIR)
+        .is_empty());
+
+    let mut signals = proofs[1].public_signals.to_vec();
+    signals[31] ^= 1;
+    proofs[1].public_signals = ArcBytes::from_bytes(&signals);
+    let batch = parties(&proofs);
+    let refs: Vec<(u64, &Proof)> = batch.iter().map(|(id, p)| (*id, p)).collect();
+    assert_eq!(
+        prover.verify_parallel(&refs, "31337:1", VARIANT, ARTIFACTS_DIR),
+        vec![1]
+    );
+}
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+mod common;
+
+use common::{find_bb, fixtures_dir, setup_test_prover, test_backend};
+use e3_utils::utility_types::ArcBytes;
+use e3_zk_prover::{CircuitVariant, ProgramCircuitArtifacts, ZkConfig, ZkError, ZkProver};
+use serde_json::json;
+use std::path::Path;
+use std::process::Command;
+
+const VARIANT: CircuitVariant = CircuitVariant::Recursive;
+
+/// Stages the `dummy` fixture as a program circuit with a VK written for [`VARIANT`].
+fn stage_dummy(bb: &Path, dir: &Path) -> ProgramCircuitArtifacts {
+    let circuit = ProgramCircuitArtifacts::new(dir, "dummy", VARIANT);
+    std::fs::copy(fixtures_dir().join("dummy.json"), &circuit.circuit_path).unwrap();
+    let status = Command::new(bb)
+        .args(["write_vk", "-b"])
+        .arg(&circuit.circuit_path)
+        .arg("-o")
+        .arg(dir)
+        .args(["-t", VARIANT.verifier_target()])
+        .status()
+        .unwrap();
+    assert!(status.success(), "bb write_vk failed");
+    std::fs::rename(dir.join("vk"), &circuit.vk_path).unwrap();
+    circuit
+}
+
+#[tokio::test]
+async fn test_program_proof_round_trip() {
+    let Some(bb) = find_bb().await else {
+        println!("skipping: bb not found");
+        return;
+    };
+    let (backend, temp) = setup_test_prover(&bb).await;
+    let prover = ZkProver::new(&backend);
+    let circuit = stage_dummy(&bb, temp.path());
+
+    let proof = prover
+        .prove_program(
+            &circuit,
+            &json!({ "x": "5", "y": "3", "_sum": "8" }),
+            "program-1",
+        )
+        .unwrap();
+    assert_eq!(proof.circuit, "dummy");
+    assert_eq!(
+        proof.public_fields().unwrap(),
+        vec![format!("0x{:064x}", 8)]
+    );
+    assert!(prover
+        .verify_program(&circuit, &proof, "program-1")
+        .unwrap());
+
+    let mut tampered = proof.clone();
+    let mut signals = tampered.public_signals.to_vec();
+    signals[31] = 9;
+    tampered.public_signals = ArcBytes::from_bytes(&signals);
+    assert!(!prover
+        .verify_program(&circuit, &tampered, "program-1")
+        .unwrap());
+
+    let other = ProgramCircuitArtifacts {
+        name: "ballot".to_string(),
+        ..circuit.clone()
+    };
+    assert!(matches!(
+        prover.verify_program(&other, &proof, "program-1"),
+        Err(ZkError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_program_proof_rejects_bad_inputs_before_proving() {
+    let temp = tempfile::tempdir().unwrap();
+    let prover = ZkProver::new(&test_backend(temp.path(), ZkConfig::default()));
+    let circuit = ProgramCircuitArtifacts::new(&fixtures_dir(), "dummy", VARIANT);
+
+    let result = prover.prove_program(
+        &circuit,
+        &json!({ "x": "5", "y": "3", "_sum": "10" }),
+        "program-1",
+    );
+    assert!(matches!(result, Err(ZkError::WitnessGenerationFailed(_))));
+
+    let missing = ProgramCircuitArtifacts::new(temp.path(), "dummy", VARIANT);
+    let result = prover.prove_program(
+        &missing,
+        &json!({ "x": "5", "y": "3", "_sum": "8" }),
+        "program-1",
+    );
+    assert!(matches!(result, Err(ZkError::CircuitNotFound(_))));
+}
@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: LGPL-3.0-only
+//
+// This file is provided WITHOUT ANY WARRANTY;
+// without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.
+
+//! Executes the stored `e3-zk-helpers` test vectors against the compiled circuits: every
+//! positive vector must solve its circuit and every negative vector must fail to. Only vectors
+//! of the preset and committee `circuits/bin/` was last built for are executed.
+
+mod common;
+
+use common::{active_bin_committee, active_bin_preset, circuits_build_root};
+use e3_events::CircuitName;
+use e3_zk_helpers::computation::DkgInputType;
+use e3_zk_helpers::test_vectors::{load_vectors, CircuitVector};
+use e3_zk_prover::{CompiledCircuit, WitnessGenerator};
+use noirc_abi::input_parser::Format;
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// The compiled circuit a vector belongs to; `None` for circuits without a single package.
+fn circuit_name(vector: &CircuitVector) -> Option<CircuitName> {
+    let case = &vector.case;
+    Some(match (case.circuit.as_str(), case.dkg_input_type) {
+        ("pk", _) => CircuitName::PkBfv,
+        ("share-computation", Some(DkgInputType::SecretKey)) => CircuitName::SkShareComputation,
+        ("share-computation", Some(DkgInputType::SmudgingNoise)) => {
+            CircuitName::ESmShareComputation
+        }
+        ("share-encryption", _) => CircuitName::ShareEncryption,
+        ("share-decryption", _) => CircuitName::DkgShareDecryption,
+        ("pk-generation", _) => CircuitName::PkGeneration,
+        ("pk-aggregation", _) => CircuitName::PkAggregation,
+        ("threshold-share-decryption", _) => CircuitName::ThresholdShareDecryption,
+        ("decrypted-shares-aggregation", _) => CircuitName::DecryptedSharesAggregation,
+        _ => return None,
+    })
+}
+
+fn solves(circuit: &CompiledCircuit, inputs: &Value) -> bool {
+    let prover_toml = toml::to_string(inputs).unwrap();
+    let Ok(input_map) = Format::Toml.parse(&prover_toml, &circuit.abi) else {
+        return false;
+    };
+    WitnessGenerator::new()
+        .generate_witness(circuit, input_map)
+        .is_ok()
+}
+
+#[test]
+fn test_negative_vectors_fail_the_compiled_circuits() {
+    let (Some(preset), Some(committee)) = (active_bin_preset(), active_bin_committee()) else {
+        println!("skipping: no circuits compiled (run `pnpm build:circuits`)");
+        return;
+    };
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("zk-helpers")
+        .join("test-vectors");
+    let vectors: Vec<_> = load_vectors(&root)
+        .unwrap()
+        .into_iter()
+        .filter(|vector| {
+            vector.case.preset.artifacts_dir() == preset
+                && vector.case.committee.as_str() == committee
+        })
+        .collect();
+    assert!(
+        !vectors.is_empty(),
+        "no test vectors for {preset}/{committee} under {}",
+        root.display()
+    );
+
+    let mut executed = 0;
+    for vector in &vectors {
+        let Some(name) = circuit_name(vector) else {
+            continue;
+        };
+        let path = circuits_build_root()
+            .join(name.group())
+            .join("target")
+            .join(format!("{}.json", name.as_str()));
+        let circuit = CompiledCircuit::from_file(&path).unwrap();
+        let id = vector.case.id();
+
+        assert!(solves(&circuit, &vector.inputs), "{id} does not solve");
+        for negative in &vector.negatives {
+            assert!(
+                !solves(&circuit, &negative.apply(&vector.inputs)),
+                "{id} still solves with {} incremented",
+                negative.pointer
+            );
+        }
+        executed += 1;
+    }
+    assert!(executed > 0, "no vector matched a compiled circuit");
+}
@@ -28,6 +28,7 @@ DIRS=(
   "bin/recursive_aggregation/decryption_aggregator"
   "bin/dkg"
   "bin/threshold"
+  "bin/program/one_hot_ballot"
 )
 
 for dir in "${DIRS[@]}"; do